@@ -4,12 +4,15 @@ use crate::style::{BadgeStyle, Border, Chevron, Corners};
 
 /// Complete specification for a technology badge
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 pub struct TechBadge {
     /// Technology name (used for icon lookup)
     pub name: String,
     /// Custom label text (defaults to capitalized name)
     pub label: Option<String>,
     /// Visual style of the badge
+    #[cfg_attr(feature = "serde", serde(default))]
     pub style: BadgeStyle,
     /// Custom background color (overrides brand color)
     pub bg_color: Option<String>,
@@ -24,8 +27,10 @@ pub struct TechBadge {
     /// Border styling
     pub border: Option<Border>,
     /// Apply border to full badge (both segments) instead of just icon segment
+    #[cfg_attr(feature = "serde", serde(default))]
     pub border_full: bool,
     /// Show vertical divider line between icon and label segments
+    #[cfg_attr(feature = "serde", serde(default))]
     pub divider: bool,
     /// Custom corner radii
     pub corners: Option<Corners>,
@@ -36,10 +41,12 @@ pub struct TechBadge {
     /// Logo/icon size in pixels (default: 14)
     pub logo_size: Option<u32>,
     /// Outline mode
+    #[cfg_attr(feature = "serde", serde(default))]
     pub outline: bool,
     /// Custom font family
     pub font: Option<String>,
-    /// Custom SVG icon path (overrides built-in icons)
+    /// Custom SVG icon path data or sanitized 24×24 markup (overrides
+    /// built-in icons)
     pub custom_icon: Option<String>,
 }
 
@@ -155,6 +162,9 @@ impl BadgeBuilder {
     }
 
     /// Set custom SVG icon path (overrides built-in icons)
+    ///
+    /// Also accepts SVG markup for a 24×24 box, starting with `<`; see
+    /// [`render::icon_element`](crate::render::icon_element).
     pub fn custom_icon(mut self, path: impl Into<String>) -> Self {
         self.badge.custom_icon = Some(path.into());
         self
@@ -0,0 +1,125 @@
+//! Escaping for text placed in SVG and HTML
+//!
+//! Every renderer that interpolates user-supplied strings (labels, alt text,
+//! font names, URLs) into markup goes through these functions, so a label
+//! like `<script>` renders as text instead of changing the document.
+//!
+//! ```
+//! use badgefx::escape::{escape_attr, escape_text, sanitize_url};
+//!
+//! assert_eq!(escape_text("a < b & c"), "a &lt; b &amp; c");
+//! assert_eq!(escape_attr(r#"say "hi""#), "say &quot;hi&quot;");
+//! assert_eq!(sanitize_url("javascript:alert(1)"), "#");
+//! ```
+
+use std::borrow::Cow;
+
+/// Escape text for element content (`<text>...</text>`, `<p>...</p>`)
+pub fn escape_text(text: &str) -> Cow<'_, str> {
+    escape(text, false)
+}
+
+/// Escape text for a double- or single-quoted attribute value
+pub fn escape_attr(text: &str) -> Cow<'_, str> {
+    escape(text, true)
+}
+
+/// Neutralise URLs that run script when used as `href` or `src`
+///
+/// `javascript:`, `vbscript:`, and non-image `data:` URLs become `#`;
+/// everything else is returned unchanged. The result still needs
+/// [`escape_attr`] when placed in an attribute.
+pub fn sanitize_url(url: &str) -> Cow<'_, str> {
+    // Browsers ignore leading whitespace and control characters in schemes
+    let scheme: String = url
+        .trim_start()
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .take_while(|&c| c != ':')
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let has_scheme = url.contains(':');
+
+    let blocked = has_scheme
+        && match scheme.as_str() {
+            "javascript" | "vbscript" => true,
+            "data" => !url
+                .trim_start()
+                .get(5..)
+                .is_some_and(|rest| rest.to_ascii_lowercase().starts_with("image/")),
+            _ => false,
+        };
+
+    if blocked {
+        Cow::Borrowed("#")
+    } else {
+        Cow::Borrowed(url)
+    }
+}
+
+fn escape(text: &str, quotes: bool) -> Cow<'_, str> {
+    let needs_escape =
+        |c: char| matches!(c, '&' | '<' | '>') || (quotes && matches!(c, '"' | '\''));
+    if !text.contains(needs_escape) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len() + 8);
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' if quotes => out.push_str("&quot;"),
+            '\'' if quotes => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("plain", "plain")]
+    #[case("<script>", "&lt;script&gt;")]
+    #[case("A & B", "A &amp; B")]
+    #[case("&amp;", "&amp;amp;")]
+    #[case(r#""quoted" 'single'"#, r#""quoted" 'single'"#)]
+    fn test_escape_text(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(escape_text(input), expected);
+    }
+
+    #[rstest]
+    #[case("plain", "plain")]
+    #[case(r#"x" onload="alert(1)"#, "x&quot; onload=&quot;alert(1)")]
+    #[case("it's", "it&#39;s")]
+    #[case("<b>&</b>", "&lt;b&gt;&amp;&lt;/b&gt;")]
+    fn test_escape_attr(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(escape_attr(input), expected);
+    }
+
+    #[test]
+    fn test_unescaped_input_is_borrowed() {
+        assert!(matches!(escape_attr("Rust 1.70"), Cow::Borrowed(_)));
+    }
+
+    #[rstest]
+    #[case("https://example.com/a.svg", "https://example.com/a.svg")]
+    #[case("assets/badge.svg", "assets/badge.svg")]
+    #[case(
+        "data:image/svg+xml;base64,PHN2Zz4=",
+        "data:image/svg+xml;base64,PHN2Zz4="
+    )]
+    #[case("javascript:alert(1)", "#")]
+    #[case("  JavaScript:alert(1)", "#")]
+    #[case("java\tscript:alert(1)", "#")]
+    #[case("vbscript:msgbox", "#")]
+    #[case("data:text/html,<script>", "#")]
+    fn test_sanitize_url(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(sanitize_url(input), expected);
+    }
+}
@@ -38,8 +38,10 @@
 //! - **Chevron Shapes**: Directional arrows and custom badge shapes
 //! - **Typography**: Custom fonts and text styling
 //! - **Glyphs**: 500+ Unicode decorative characters (with "glyphs" feature)
+//! - **Badge Specs**: Define badges in JSON or YAML (with "serde" feature)
 
 pub mod badge;
+pub mod escape;
 pub mod group;
 pub mod license;
 pub mod render;
@@ -50,10 +52,15 @@ pub mod version;
 #[cfg(feature = "glyphs")]
 pub mod glyphs;
 
+#[cfg(feature = "serde")]
+pub mod spec;
+
 // Re-export main public API
 pub use badge::{BadgeBuilder, TechBadge};
 pub use license::{LicenseBadge, LicenseBuilder};
 pub use render::{render, render_to_file};
+#[cfg(feature = "serde")]
+pub use spec::{from_json, BadgeSpec};
 pub use style::{BadgeStyle, Border, Chevron, Corners, SvgMetrics};
 pub use version::{VersionBadge, VersionBuilder};
 
@@ -21,10 +21,13 @@
 //!     .render();
 //! ```
 
+use crate::escape::escape_text;
 use crate::style::{BadgeStyle, SvgMetrics};
 
 /// License categories for coloring
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum Category {
     /// Green - MIT, Apache, BSD, ISC
     Permissive,
@@ -156,7 +159,11 @@ pub fn format_name(license: &str) -> String {
 }
 
 /// License badge specification
+///
+/// When deserialized, a missing `category` is detected from the license.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "LicenseSpec"))]
 pub struct LicenseBadge {
     /// License identifier (e.g., "MIT", "GPL-3.0")
     pub license: String,
@@ -220,6 +227,41 @@ impl LicenseBadge {
     }
 }
 
+/// Serialized form of [`LicenseBadge`], with an optional category
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LicenseSpec {
+    license: String,
+    category: Option<Category>,
+    #[serde(default)]
+    style: BadgeStyle,
+    label: Option<String>,
+    bg_color: Option<String>,
+    text_color: Option<String>,
+    border_color: Option<String>,
+    border_width: Option<u32>,
+    rx: Option<u32>,
+}
+
+#[cfg(feature = "serde")]
+impl From<LicenseSpec> for LicenseBadge {
+    fn from(spec: LicenseSpec) -> Self {
+        let defaults = LicenseBadge::new(spec.license);
+        Self {
+            category: spec.category.unwrap_or(defaults.category),
+            style: spec.style,
+            label: spec.label,
+            bg_color: spec.bg_color,
+            text_color: spec.text_color,
+            border_color: spec.border_color,
+            border_width: spec.border_width,
+            rx: spec.rx,
+            license: defaults.license,
+        }
+    }
+}
+
 /// Builder for creating license badges
 #[derive(Debug)]
 pub struct LicenseBuilder {
@@ -330,7 +372,7 @@ pub fn render(badge: &LicenseBadge) -> String {
         text_y,
         text_color,
         font_size,
-        label
+        escape_text(&label)
     )
 }
 
@@ -5,6 +5,7 @@ use std::io;
 use std::path::Path;
 
 use crate::badge::TechBadge;
+use crate::escape::{escape_attr, escape_text};
 use crate::shapes::rounded_rect_path;
 use crate::style::{BadgeStyle, SvgMetrics};
 
@@ -128,7 +129,7 @@ fn render_two_segment(
         .map(|c| c.trim_start_matches('#'))
         .unwrap_or_else(|| get_logo_color_for_bg(&right_bg));
 
-    let font_family = badge.font.as_deref().unwrap_or("Verdana,Arial,sans-serif");
+    let font_family = escape_attr(badge.font.as_deref().unwrap_or("Verdana,Arial,sans-serif"));
     // Only apply border to left segment if not border_full mode
     let border_attr = if badge.border_full {
         String::new()
@@ -199,7 +200,7 @@ fn render_two_segment(
   <path d=\"{}\" fill=\"#{}\"{}/>  \n\
   <path d=\"{}\" fill=\"#{}\"/>  \n\
   <g transform=\"translate({}, {}) scale({})\">\n\
-    <path fill=\"#{}\" d=\"{}\"/>\n\
+    {}\n\
   </g>\n\
   <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#{}\" font-family=\"{}\" font-size=\"{}\" font-weight=\"600\">{}</text>\n\
 </svg>",
@@ -207,8 +208,8 @@ fn render_two_segment(
             left_path, left_bg, border_attr,
             right_path, right_bg,
             icon_x + content_offset, icon_y, scale,
-            logo_color, icon_path,
-            text_x as f32 + content_offset, text_y, text_color, font_family, font_size, label
+            icon_element(icon_path, logo_color),
+            text_x as f32 + content_offset, text_y, text_color, font_family, font_size, escape_text(label)
         );
     }
 
@@ -320,7 +321,7 @@ fn render_two_segment(
   {}\n\
   {}\n\
   <g transform=\"translate({}, {}) scale({})\">\n\
-    <path fill=\"#{}\" d=\"{}\"/>\n\
+    {}\n\
   </g>\n\
   <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#{}\" font-family=\"{}\" font-size=\"{}\" font-weight=\"600\">{}</text>{}{}\n\
 </svg>",
@@ -328,8 +329,8 @@ fn render_two_segment(
         left_segment,
         right_segment,
         icon_x, icon_y, scale,
-        logo_color, icon_path,
-        text_x, text_y, text_color, font_family, font_size, label,
+        icon_element(icon_path, logo_color),
+        text_x, text_y, text_color, font_family, font_size, escape_text(label),
         full_border_outline,
         divider_line
     )
@@ -388,7 +389,7 @@ fn render_raised_badge(
         .map(|c| c.trim_start_matches('#'))
         .unwrap_or_else(|| get_logo_color_for_bg(bg_color));
 
-    let font_family = badge.font.as_deref().unwrap_or("Verdana,Arial,sans-serif");
+    let font_family = escape_attr(badge.font.as_deref().unwrap_or("Verdana,Arial,sans-serif"));
 
     // Corner radius
     let rx = badge
@@ -408,7 +409,7 @@ fn render_raised_badge(
   <rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"#{}\" rx=\"{}\"{}/>\n\
   <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#{}\"/>\n\
   <g transform=\"translate({}, {}) scale({})\">\n\
-    <path fill=\"#{}\" d=\"{}\"/>\n\
+    {}\n\
   </g>\n\
   <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#{}\" font-family=\"{}\" font-size=\"{}\" font-weight=\"600\">{}</text>\n\
 </svg>",
@@ -419,9 +420,9 @@ fn render_raised_badge(
         icon_width, label_y_offset, label_width, label_height, bg_color,
         // Icon
         icon_x, icon_y, scale,
-        logo_color, icon_path,
+        icon_element(icon_path, logo_color),
         // Text
-        text_x, text_y as u32, text_color, font_family, font_size, label
+        text_x, text_y as u32, text_color, font_family, font_size, escape_text(label)
     )
 }
 
@@ -479,10 +480,10 @@ fn render_icon_only(
         "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n\
   {}\
   <g transform=\"translate({}, {}) scale({})\">\n\
-    <path fill=\"#{}\" d=\"{}\"/>\n\
+    {}\n\
   </g>\n\
 </svg>",
-        width, height, width, height, bg, icon_x, icon_y, scale, logo_color, icon_path
+        width, height, width, height, bg, icon_x, icon_y, scale, icon_element(icon_path, logo_color)
     )
 }
 
@@ -543,7 +544,7 @@ fn render_text_only(badge: &TechBadge, label: &str, bg_color: &str) -> String {
         width / 2,
         text_y,
         font_size,
-        label.to_uppercase()
+        escape_text(&label.to_uppercase())
     )
 }
 
@@ -587,7 +588,7 @@ fn render_outline_two_segment(
         .as_deref()
         .map(|c| c.trim_start_matches('#'))
         .unwrap_or(brand_color);
-    let font_family = badge.font.as_deref().unwrap_or("Verdana,Arial,sans-serif");
+    let font_family = escape_attr(badge.font.as_deref().unwrap_or("Verdana,Arial,sans-serif"));
 
     // Generate outline background
     let bg = if let Some(corners) = &badge.corners {
@@ -621,7 +622,7 @@ fn render_outline_two_segment(
   {}\n\
   {}\n\
   <g transform=\"translate({}, {}) scale({})\">\n\
-    <path fill=\"#{}\" d=\"{}\"/>\n\
+    {}\n\
   </g>\n\
   <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#{}\" font-family=\"{}\" font-size=\"{}\" font-weight=\"600\">{}</text>\n\
 </svg>",
@@ -629,8 +630,8 @@ fn render_outline_two_segment(
         bg,
         separator,
         icon_x, icon_y, scale,
-        icon_color, icon_path,
-        text_x, text_y, text_color, font_family, font_size, label
+        icon_element(icon_path, icon_color),
+        text_x, text_y, text_color, font_family, font_size, escape_text(label)
     )
 }
 
@@ -682,10 +683,10 @@ fn render_outline_icon_only(badge: &TechBadge, icon_path: &str, brand_color: &st
         "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n\
   {}\n\
   <g transform=\"translate({}, {}) scale({})\">\n\
-    <path fill=\"#{}\" d=\"{}\"/>\n\
+    {}\n\
   </g>\n\
 </svg>",
-        width, height, width, height, bg, icon_x, icon_y, scale, icon_color, icon_path
+        width, height, width, height, bg, icon_x, icon_y, scale, icon_element(icon_path, icon_color)
     )
 }
 
@@ -749,7 +750,7 @@ fn render_outline_text_only(badge: &TechBadge, label: &str, brand_color: &str) -
         text_y,
         text_color,
         font_size,
-        label.to_uppercase()
+        escape_text(&label.to_uppercase())
     )
 }
 
@@ -775,6 +776,19 @@ fn get_logo_color_for_bg(bg_hex: &str) -> &'static str {
     mdfx_colors::contrast_color(bg_hex).trim_start_matches('#')
 }
 
+/// The SVG element drawing a 24×24 icon in `color` (hex, without `#`)
+///
+/// `icon` is either path data, drawn as a single path, or SVG markup (it
+/// starts with `<`), wrapped in a group whose fill and `currentColor` are
+/// `color`. Markup is embedded as is, so it must already be sanitized.
+pub fn icon_element(icon: &str, color: &str) -> String {
+    if icon.starts_with('<') {
+        format!("<g fill=\"#{}\" color=\"#{}\">{}</g>", color, color, icon)
+    } else {
+        format!("<path fill=\"#{}\" d=\"{}\"/>", color, icon)
+    }
+}
+
 /// Render badge to file
 pub fn render_to_file(badge: &TechBadge, path: impl AsRef<Path>) -> io::Result<()> {
     let svg = render(badge);
@@ -810,6 +824,42 @@ mod tests {
         assert!(svg.contains("UNKNOWN-TECH"));
     }
 
+    #[rstest]
+    #[case("rust")]
+    #[case("unknown-tech")]
+    fn test_label_and_font_are_escaped(#[case] name: &str) {
+        let badge = BadgeBuilder::new(name)
+            .label("<script>&\"x\"")
+            .font("Arial\" onload=\"alert(1)")
+            .build();
+        let svg = render(&badge);
+
+        assert!(!svg.contains("<script>"));
+        assert!(svg.contains("&lt;"));
+        assert!(svg.contains("&amp;"));
+        assert!(!svg.contains("\" onload=\""));
+    }
+
+    #[test]
+    fn test_custom_icon_markup() {
+        let markup = "<g><circle cx=\"12\" cy=\"12\" r=\"10\" fill=\"currentColor\"/></g>";
+        let svg = render(
+            &BadgeBuilder::new("anything")
+                .custom_icon(markup)
+                .logo_color("ABCDEF")
+                .build(),
+        );
+        assert!(svg.contains(&format!(
+            "<g fill=\"#ABCDEF\" color=\"#ABCDEF\">{}</g>",
+            markup
+        )));
+
+        assert_eq!(
+            icon_element("M0 0h24v24H0z", "FFFFFF"),
+            "<path fill=\"#FFFFFF\" d=\"M0 0h24v24H0z\"/>"
+        );
+    }
+
     #[test]
     fn test_custom_styling() {
         let badge = BadgeBuilder::new("typescript")
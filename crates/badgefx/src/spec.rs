@@ -0,0 +1,124 @@
+//! Badges described as data
+//!
+//! With the `serde` feature, every badge type can be read from JSON, YAML,
+//! or any other serde format. A [`BadgeSpec`] names its kind with a `type`
+//! field and takes the badge's fields alongside it:
+//!
+//! ```
+//! let spec = badgefx::from_json(r#"{"type": "tech", "name": "rust", "style": "flat-square"}"#).unwrap();
+//! assert!(spec.render().contains("<svg"));
+//!
+//! let spec = badgefx::from_json(r#"{"type": "version", "version": "2.0.0-beta"}"#).unwrap();
+//! assert!(spec.render().contains("EAB308")); // status detected as beta
+//! ```
+
+use crate::badge::TechBadge;
+use crate::license::LicenseBadge;
+use crate::version::VersionBadge;
+use serde::{Deserialize, Serialize};
+
+/// Any badge badgefx can render
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum BadgeSpec {
+    /// `{"type": "tech", "name": "rust", ...}`
+    Tech(TechBadge),
+    /// `{"type": "version", "version": "1.0.0", ...}`
+    Version(VersionBadge),
+    /// `{"type": "license", "license": "MIT", ...}`
+    License(LicenseBadge),
+}
+
+impl BadgeSpec {
+    /// Render the badge to SVG
+    pub fn render(&self) -> String {
+        match self {
+            BadgeSpec::Tech(badge) => crate::render::render(badge),
+            BadgeSpec::Version(badge) => crate::version::render(badge),
+            BadgeSpec::License(badge) => crate::license::render(badge),
+        }
+    }
+}
+
+impl From<TechBadge> for BadgeSpec {
+    fn from(badge: TechBadge) -> Self {
+        BadgeSpec::Tech(badge)
+    }
+}
+
+impl From<VersionBadge> for BadgeSpec {
+    fn from(badge: VersionBadge) -> Self {
+        BadgeSpec::Version(badge)
+    }
+}
+
+impl From<LicenseBadge> for BadgeSpec {
+    fn from(badge: LicenseBadge) -> Self {
+        BadgeSpec::License(badge)
+    }
+}
+
+/// Parse a badge spec from JSON
+pub fn from_json(spec: &str) -> Result<BadgeSpec, serde_json::Error> {
+    serde_json::from_str(spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{BadgeStyle, ChevronDirection};
+    use crate::{badge, license, version};
+
+    #[test]
+    fn test_tech_spec_matches_builder() {
+        let spec = from_json(
+            r##"{"type": "tech", "name": "rust", "label": "Rust 1.80", "style": "for-the-badge",
+                "border": {"color": "#000000", "width": 2}, "chevron": {"direction": "right", "depth": 6.0}}"##,
+        )
+        .unwrap();
+        let BadgeSpec::Tech(ref tech) = spec else {
+            panic!("expected a tech badge");
+        };
+        assert_eq!(tech.style, BadgeStyle::ForTheBadge);
+        assert_eq!(
+            tech.chevron.as_ref().unwrap().direction,
+            ChevronDirection::Right
+        );
+
+        let built = badge("rust")
+            .label("Rust 1.80")
+            .style(BadgeStyle::ForTheBadge)
+            .border("#000000", 2)
+            .chevron(crate::Chevron::right(6.0))
+            .render();
+        assert_eq!(spec.render(), built);
+    }
+
+    #[test]
+    fn test_version_and_license_detect_defaults() {
+        let spec = from_json(r#"{"type": "version", "version": "2.0.0-beta"}"#).unwrap();
+        assert_eq!(spec.render(), version("2.0.0-beta").render());
+
+        let spec = from_json(r#"{"type": "license", "license": "GPL-3.0"}"#).unwrap();
+        assert_eq!(spec.render(), license("GPL-3.0").render());
+
+        let spec = from_json(r#"{"type": "license", "license": "MIT", "category": "proprietary"}"#)
+            .unwrap();
+        assert!(spec.render().contains("6B7280"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let spec = BadgeSpec::from(version("1.2.0").no_prefix().build());
+        let json = serde_json::to_string(&spec).unwrap();
+        assert!(json.contains(r#""type":"version""#));
+        assert_eq!(from_json(&json).unwrap().render(), spec.render());
+    }
+
+    #[test]
+    fn test_invalid_specs() {
+        assert!(from_json(r#"{"name": "rust"}"#).is_err());
+        assert!(from_json(r#"{"type": "tech"}"#).is_err());
+        assert!(from_json(r#"{"type": "tech", "name": "rust", "colour": "red"}"#).is_err());
+    }
+}
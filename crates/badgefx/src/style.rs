@@ -2,6 +2,8 @@
 
 /// Badge visual styles
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum BadgeStyle {
     /// Flat style with minimal visual elements
     #[default]
@@ -175,6 +177,7 @@ impl SvgMetrics {
 
 /// Border styling configuration
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Border {
     /// Border color (hex format)
     pub color: String,
@@ -194,6 +197,7 @@ impl Border {
 
 /// Custom corner radius configuration
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Corners {
     /// Top-left corner radius
     pub top_left: u32,
@@ -239,6 +243,7 @@ impl Corners {
 
 /// Chevron/arrow styling configuration
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Chevron {
     /// Direction of the chevron
     pub direction: ChevronDirection,
@@ -248,6 +253,8 @@ pub struct Chevron {
 
 /// Chevron direction options
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum ChevronDirection {
     /// Left-pointing arrow
     Left,
@@ -20,10 +20,13 @@
 //!     .render();
 //! ```
 
+use crate::escape::escape_text;
 use crate::style::{BadgeStyle, SvgMetrics};
 
 /// Version status categories for coloring
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Status {
     /// Green - production ready (1.x.x+)
     Stable,
@@ -38,6 +41,17 @@ pub enum Status {
 }
 
 impl Status {
+    /// Status name, as accepted by [`parse_status`]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Stable => "stable",
+            Status::Beta => "beta",
+            Status::Alpha => "alpha",
+            Status::Deprecated => "deprecated",
+            Status::Dev => "dev",
+        }
+    }
+
     /// Get the background color for this status (hex without #)
     pub fn bg_color(&self) -> &'static str {
         match self {
@@ -104,7 +118,11 @@ pub fn parse_status(status: &str) -> Option<Status> {
 }
 
 /// Version badge specification
+///
+/// When deserialized, a missing `status` is detected from the version.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "VersionSpec"))]
 pub struct VersionBadge {
     /// Version string (e.g., "1.0.0", "2.0.0-beta.1")
     pub version: String,
@@ -171,6 +189,41 @@ impl VersionBadge {
     }
 }
 
+/// Serialized form of [`VersionBadge`], with optional status and prefix
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VersionSpec {
+    version: String,
+    status: Option<Status>,
+    #[serde(default)]
+    style: BadgeStyle,
+    bg_color: Option<String>,
+    text_color: Option<String>,
+    prefix: Option<String>,
+    border_color: Option<String>,
+    border_width: Option<u32>,
+    rx: Option<u32>,
+}
+
+#[cfg(feature = "serde")]
+impl From<VersionSpec> for VersionBadge {
+    fn from(spec: VersionSpec) -> Self {
+        let defaults = VersionBadge::new(spec.version);
+        Self {
+            status: spec.status.unwrap_or(defaults.status),
+            style: spec.style,
+            bg_color: spec.bg_color,
+            text_color: spec.text_color,
+            prefix: spec.prefix.unwrap_or(defaults.prefix),
+            border_color: spec.border_color,
+            border_width: spec.border_width,
+            rx: spec.rx,
+            version: defaults.version,
+        }
+    }
+}
+
 /// Builder for creating version badges
 #[derive(Debug)]
 pub struct VersionBuilder {
@@ -287,7 +340,7 @@ pub fn render(badge: &VersionBadge) -> String {
         text_y,
         text_color,
         font_size,
-        label
+        escape_text(&label)
     )
 }
 
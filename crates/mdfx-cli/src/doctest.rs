@@ -0,0 +1,282 @@
+//! Compile and run the Rust code blocks of markdown files, for
+//! `mdfx test-snippets`
+//!
+//! Each block becomes a binary of a scratch package in `target/mdfx-snippets`
+//! that depends on the package in the current directory, so examples can
+//! `use` the crate like doctests do. Compiler errors are reported at their
+//! line in the markdown file.
+
+use colored::Colorize;
+use mdfx::ast::NodeKind;
+use mdfx::project;
+use mdfx::snippet::{code_blocks, doctest_program, rust_attributes};
+use mdfx::{Error, TemplateParser};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::info;
+
+/// Scratch package the blocks are built in, relative to the current directory
+const WORK_DIR: &str = "target/mdfx-snippets";
+
+/// A Rust code block to test
+struct Case {
+    file: String,
+    /// Line of the first line of code, or of the `{{snippet:...}}` template
+    line: usize,
+    /// Whether the block was inserted by a `{{snippet:...}}` template
+    managed: bool,
+    attributes: Vec<String>,
+    code: String,
+}
+
+impl Case {
+    fn has(&self, attribute: &str) -> bool {
+        self.attributes.iter().any(|a| a == attribute)
+    }
+
+    /// Location in the markdown file of line `line` of the block
+    fn location(&self, line: usize) -> String {
+        if self.managed {
+            format!("{}:{}: snippet line {}", self.file, self.line, line)
+        } else {
+            format!("{}:{}", self.file, self.line + line - 1)
+        }
+    }
+}
+
+/// How a block's test ended
+enum Outcome {
+    Passed,
+    Ignored,
+    Failed(String),
+}
+
+/// Test the Rust code blocks of each input
+///
+/// With `managed_only`, only blocks inserted by `{{snippet:...}}` templates
+/// are tested. Fails if any block fails.
+pub fn test_snippets(
+    inputs: &[PathBuf],
+    parser: &TemplateParser,
+    managed_only: bool,
+    offline: bool,
+) -> Result<(), Error> {
+    let mut cases = Vec::new();
+    for path in inputs {
+        let content = fs::read_to_string(path).map_err(|e| crate::read_failed(path, e))?;
+        cases.extend(collect_cases(
+            &path.display().to_string(),
+            &content,
+            parser,
+            managed_only,
+        )?);
+    }
+    if cases.is_empty() {
+        info!(status = "Tested:", "no Rust code blocks found");
+        return Ok(());
+    }
+
+    let work_dir = Path::new(WORK_DIR);
+    write_package(work_dir)?;
+
+    let (mut passed, mut failed, mut ignored) = (0, 0, 0);
+    for (index, case) in cases.iter().enumerate() {
+        let outcome = run_case(work_dir, &format!("snippet_{}", index + 1), case, offline)?;
+        let status = match &outcome {
+            Outcome::Passed => {
+                passed += 1;
+                "ok".green()
+            }
+            Outcome::Ignored => {
+                ignored += 1;
+                "ignored".yellow()
+            }
+            Outcome::Failed(_) => {
+                failed += 1;
+                "FAILED".red().bold()
+            }
+        };
+        let source = if case.managed { " (snippet)" } else { "" };
+        println!("test {}:{}{} ... {}", case.file, case.line, source, status);
+        if let Outcome::Failed(report) = outcome {
+            for line in report.lines() {
+                println!("    {}", line);
+            }
+        }
+    }
+
+    let summary = format!(
+        "{} snippet(s): {} passed, {} failed, {} ignored",
+        cases.len(),
+        passed,
+        failed,
+        ignored
+    );
+    if failed > 0 {
+        return Err(Error::CheckFailed(format!(
+            "Snippet tests failed: {}",
+            summary
+        )));
+    }
+    info!(status = "Tested:", "{}", summary);
+    Ok(())
+}
+
+/// The Rust blocks written in `content` and inserted by its snippet templates
+fn collect_cases(
+    file: &str,
+    content: &str,
+    parser: &TemplateParser,
+    managed_only: bool,
+) -> Result<Vec<Case>, Error> {
+    let mut cases = Vec::new();
+    if !managed_only {
+        for block in code_blocks(content) {
+            if let Some(attributes) = rust_attributes(&block.info) {
+                cases.push(Case {
+                    file: file.to_string(),
+                    line: block.line,
+                    managed: false,
+                    attributes: attributes.into_iter().map(String::from).collect(),
+                    code: block.code,
+                });
+            }
+        }
+    }
+
+    let nodes = parser.parse_ast(content)?;
+    for node in nodes
+        .iter()
+        .filter(|n| matches!(n.kind, NodeKind::Snippet { .. }))
+    {
+        let rendered = parser.render_ast(std::slice::from_ref(node))?.markdown;
+        for block in code_blocks(&rendered) {
+            if let Some(attributes) = rust_attributes(&block.info) {
+                cases.push(Case {
+                    file: file.to_string(),
+                    line: content[..node.range.start].matches('\n').count() + 1,
+                    managed: true,
+                    attributes: attributes.into_iter().map(String::from).collect(),
+                    code: block.code,
+                });
+            }
+        }
+    }
+    cases.sort_by_key(|c| c.line);
+    Ok(cases)
+}
+
+/// Write the scratch package, depending on the package in the current
+/// directory if there is one
+fn write_package(work_dir: &Path) -> Result<(), Error> {
+    let manifest = fs::read_to_string(project::CARGO_TOML).unwrap_or_default();
+    let edition = project::edition(&manifest).unwrap_or_else(|| "2021".to_string());
+    let mut cargo_toml = format!(
+        "[package]\nname = \"mdfx-snippets\"\nversion = \"0.0.0\"\nedition = \"{}\"\npublish = false\n\n[workspace]\n\n[dependencies]\n",
+        edition
+    );
+    if let Some(name) = project::package_name(&manifest) {
+        let root = std::env::current_dir()?;
+        cargo_toml.push_str(&format!(
+            "{} = {{ path = {:?} }}\n",
+            name,
+            root.display().to_string()
+        ));
+    }
+
+    let bin_dir = work_dir.join("src/bin");
+    if bin_dir.exists() {
+        fs::remove_dir_all(&bin_dir)?;
+    }
+    fs::create_dir_all(&bin_dir)?;
+    fs::write(work_dir.join("Cargo.toml"), cargo_toml)?;
+    Ok(())
+}
+
+/// Build and run one block as binary `name`
+fn run_case(work_dir: &Path, name: &str, case: &Case, offline: bool) -> Result<Outcome, Error> {
+    if case.has("ignore") {
+        return Ok(Outcome::Ignored);
+    }
+
+    let (program, offset) = doctest_program(&case.code);
+    let source = format!("src/bin/{}.rs", name);
+    fs::write(
+        work_dir.join(&source),
+        format!("#![allow(unused)]\n{}", program),
+    )?;
+
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut build = Command::new(cargo);
+    build
+        .args([
+            "build",
+            "--quiet",
+            "--message-format",
+            "short",
+            "--bin",
+            name,
+        ])
+        .current_dir(work_dir);
+    if offline {
+        build.arg("--offline");
+    }
+    let output = build.output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    match (output.status.success(), case.has("compile_fail")) {
+        (true, true) => return Ok(Outcome::Failed("compiled, but should fail".to_string())),
+        (false, true) => return Ok(Outcome::Passed),
+        (false, false) => {
+            return Ok(Outcome::Failed(map_diagnostics(
+                &stderr,
+                &source,
+                case,
+                offset + 1,
+            )))
+        }
+        (true, false) => {}
+    }
+    if case.has("no_run") {
+        return Ok(Outcome::Passed);
+    }
+
+    let binary =
+        work_dir
+            .join("target/debug")
+            .join(format!("{}{}", name, std::env::consts::EXE_SUFFIX));
+    let output = Command::new(binary).output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Ok(match (output.status.success(), case.has("should_panic")) {
+        (true, false) | (false, true) => Outcome::Passed,
+        (true, true) => Outcome::Failed("ran, but should panic".to_string()),
+        (false, false) => Outcome::Failed(format!("{}\n{}", output.status, stderr)),
+    })
+}
+
+/// Compiler messages about `source`, with its locations replaced by the
+/// block's location in the markdown file
+///
+/// `offset` is the number of lines of `source` before the block's code.
+/// Falls back to all of cargo's output when no message names the file, e.g.
+/// when a dependency fails to resolve.
+fn map_diagnostics(stderr: &str, source: &str, case: &Case, offset: usize) -> String {
+    let prefix = format!("{}:", source);
+    let mapped: Vec<String> = stderr
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix(&prefix)?;
+            let mut parts = rest.splitn(3, ':');
+            let line_no: usize = parts.next()?.parse().ok()?;
+            let _column = parts.next()?;
+            let message = parts.next()?.trim();
+            let code_line = (line_no.saturating_sub(offset)).max(1);
+            Some(format!("{}: {}", case.location(code_line), message))
+        })
+        .collect();
+    if mapped.is_empty() {
+        stderr.trim().to_string()
+    } else {
+        mapped.join("\n")
+    }
+}
@@ -0,0 +1,58 @@
+//! Exit codes and error reporting for the CLI
+//!
+//! Each kind of failure exits with its own code so wrapper scripts and CI
+//! can branch on it without scraping stderr. `--error-format json` also
+//! prints the error as one JSON object on stderr.
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | Success |
+//! | 2 | Invalid command-line usage (reported by clap) |
+//! | 3 | Template, config, or input could not be parsed |
+//! | 4 | Reading or writing a file failed |
+//! | 5 | Fetching live data or publishing assets failed |
+//! | 6 | A check found problems (`verify`, `check`, `fmt --check`, `lint`, `test-snippets`) |
+
+use clap::ValueEnum;
+use mdfx::{Error, ErrorKind};
+use serde_json::json;
+use tracing::error;
+
+/// Output format for the error that ends a run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// Logged like any other message
+    #[default]
+    Text,
+    /// One JSON object on stderr
+    Json,
+}
+
+/// Process exit code for an error
+pub fn code(error: &Error) -> i32 {
+    match error.kind() {
+        ErrorKind::Parse => 3,
+        ErrorKind::Io => 4,
+        ErrorKind::Fetch => 5,
+        ErrorKind::Check => 6,
+    }
+}
+
+/// Report the error that ends a run and return the exit code
+pub fn report(error: &Error, format: ErrorFormat) -> i32 {
+    let code = code(error);
+    match format {
+        ErrorFormat::Text => error!("{}", error),
+        ErrorFormat::Json => {
+            let report = json!({
+                "error": {
+                    "kind": error.kind().as_str(),
+                    "code": code,
+                    "message": error.to_string(),
+                }
+            });
+            eprintln!("{}", report);
+        }
+    }
+    code
+}
@@ -0,0 +1,316 @@
+//! Link and target compatibility checks for `mdfx lint`
+
+use clap::ValueEnum;
+use colored::Colorize;
+use mdfx::compat::lint_compat as check_compat;
+use mdfx::links::{extract_links, Link, LinkChecker};
+use mdfx::{Error, Severity, Target};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+#[cfg(feature = "fetch")]
+use mdfx::links::LinkKind;
+#[cfg(feature = "fetch")]
+use std::collections::{BTreeSet, HashMap};
+#[cfg(feature = "fetch")]
+use std::sync::Mutex;
+#[cfg(feature = "fetch")]
+use std::time::Duration;
+
+/// Output format for lint reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LintFormat {
+    /// One `file:line:column: severity: message` line per problem
+    Text,
+    /// Single JSON object
+    Json,
+}
+
+/// How to check http(s) links
+#[cfg(feature = "fetch")]
+#[derive(Debug, Clone, Copy)]
+pub struct ExternalCheck {
+    /// Requests in flight at once
+    pub concurrency: usize,
+    /// Timeout for each request
+    pub timeout: Duration,
+}
+
+struct BrokenLink {
+    file_index: usize,
+    file: String,
+    link: Link,
+    message: String,
+}
+
+/// Check the links in each input and print a report
+///
+/// Fails if any link is broken.
+pub fn lint_links(
+    inputs: &[PathBuf],
+    root: &Path,
+    format: LintFormat,
+    #[cfg(feature = "fetch")] external: Option<ExternalCheck>,
+) -> Result<(), Error> {
+    let documents = read_inputs(inputs)?;
+    lint_links_in(
+        &documents,
+        root,
+        format,
+        #[cfg(feature = "fetch")]
+        external,
+    )
+}
+
+/// [`lint_links`] for documents already in memory, as `(path, content)`
+///
+/// Relative links resolve against each path, which need not exist.
+pub fn lint_links_in(
+    documents: &[(PathBuf, String)],
+    root: &Path,
+    format: LintFormat,
+    #[cfg(feature = "fetch")] external: Option<ExternalCheck>,
+) -> Result<(), Error> {
+    let mut checker = LinkChecker::new(root);
+    let mut broken = Vec::new();
+    let mut total = 0;
+    #[cfg(feature = "fetch")]
+    let mut external_links = Vec::new();
+
+    for (file_index, (path, content)) in documents.iter().enumerate() {
+        let content = content.as_str();
+        let file = path.display().to_string();
+
+        let links = extract_links(content);
+        total += links.len();
+        for problem in checker.check(path, content) {
+            broken.push(BrokenLink {
+                file_index,
+                file: file.clone(),
+                link: problem.link,
+                message: problem.message,
+            });
+        }
+
+        #[cfg(feature = "fetch")]
+        if external.is_some() {
+            external_links.extend(
+                links
+                    .into_iter()
+                    .filter(|l| l.kind == LinkKind::External)
+                    .map(|l| (file_index, file.clone(), l)),
+            );
+        }
+    }
+
+    #[cfg(feature = "fetch")]
+    if let Some(check) = external {
+        let urls = external_links
+            .iter()
+            .map(|(_, _, link)| request_url(&link.target))
+            .collect();
+        let failures = check_urls(urls, check);
+        for (file_index, file, link) in external_links {
+            if let Some(message) = failures.get(&request_url(&link.target)) {
+                broken.push(BrokenLink {
+                    file_index,
+                    file,
+                    link,
+                    message: message.clone(),
+                });
+            }
+        }
+    }
+    broken.sort_by_key(|b| (b.file_index, b.link.line, b.link.column));
+
+    match format {
+        LintFormat::Text => {
+            for b in &broken {
+                println!(
+                    "{}:{}:{}: {}: {}",
+                    b.file,
+                    b.link.line,
+                    b.link.column,
+                    "error".red().bold(),
+                    b.message
+                );
+            }
+        }
+        LintFormat::Json => {
+            let report = json!({
+                "files": documents.len(),
+                "links": total,
+                "broken": broken.iter().map(|b| json!({
+                    "file": b.file,
+                    "line": b.link.line,
+                    "column": b.link.column,
+                    "target": b.link.target,
+                    "kind": b.link.kind,
+                    "message": b.message,
+                })).collect::<Vec<_>>(),
+            });
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+    }
+
+    let summary = format!(
+        "{} file(s), {} link(s): {} broken",
+        documents.len(),
+        total,
+        broken.len()
+    );
+    if !broken.is_empty() {
+        return Err(Error::CheckFailed(format!(
+            "Link check failed: {}",
+            summary
+        )));
+    }
+    info!(status = "Checked:", "{}", summary);
+    Ok(())
+}
+
+/// Check each input for constructs `target` can't render and print a report
+///
+/// Fails on errors, and on warnings with `deny_warnings`.
+pub fn lint_compat(
+    inputs: &[PathBuf],
+    target: &dyn Target,
+    format: LintFormat,
+    deny_warnings: bool,
+) -> Result<(), Error> {
+    lint_compat_in(&read_inputs(inputs)?, target, format, deny_warnings)
+}
+
+/// [`lint_compat`] for documents already in memory, as `(path, content)`
+pub fn lint_compat_in(
+    documents: &[(PathBuf, String)],
+    target: &dyn Target,
+    format: LintFormat,
+    deny_warnings: bool,
+) -> Result<(), Error> {
+    let reports: Vec<_> = documents
+        .iter()
+        .map(|(path, content)| (path.display().to_string(), check_compat(content, target)))
+        .collect();
+
+    let issues = || {
+        reports
+            .iter()
+            .flat_map(|(file, issues)| issues.iter().map(move |i| (file, i)))
+    };
+    let errors = issues()
+        .filter(|(_, i)| i.severity == Severity::Error)
+        .count();
+    let warnings = issues().count() - errors;
+
+    match format {
+        LintFormat::Text => {
+            for (file, issue) in issues() {
+                let severity = match issue.severity {
+                    Severity::Error => format!("error[{}]", issue.code).red().bold(),
+                    Severity::Warning => format!("warning[{}]", issue.code).yellow(),
+                };
+                println!(
+                    "{}:{}:{}: {}: {}",
+                    file, issue.line, issue.column, severity, issue.message
+                );
+            }
+        }
+        LintFormat::Json => {
+            let report = json!({
+                "target": target.name(),
+                "files": documents.len(),
+                "issues": issues().map(|(file, issue)| {
+                    let mut value = json!(issue);
+                    value["file"] = json!(file);
+                    value
+                }).collect::<Vec<_>>(),
+            });
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+    }
+
+    let summary = format!(
+        "{} file(s) for {}: {} error(s), {} warning(s)",
+        documents.len(),
+        target.name(),
+        errors,
+        warnings
+    );
+    if errors > 0 || (deny_warnings && warnings > 0) {
+        return Err(Error::CheckFailed(format!(
+            "Compatibility check failed: {}",
+            summary
+        )));
+    }
+    info!(status = "Checked:", "{}", summary);
+    Ok(())
+}
+
+/// Read each input as `(path, content)`
+fn read_inputs(inputs: &[PathBuf]) -> Result<Vec<(PathBuf, String)>, Error> {
+    inputs
+        .iter()
+        .map(|path| {
+            let content = fs::read_to_string(path).map_err(|e| crate::read_failed(path, e))?;
+            Ok((path.clone(), content))
+        })
+        .collect()
+}
+
+/// URL to request for an external link target
+#[cfg(feature = "fetch")]
+fn request_url(target: &str) -> String {
+    let url = target.split('#').next().unwrap_or(target);
+    match url.strip_prefix("//") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.to_string(),
+    }
+}
+
+/// Request each URL once, returning a message for each one that failed
+#[cfg(feature = "fetch")]
+fn check_urls(urls: BTreeSet<String>, check: ExternalCheck) -> HashMap<String, String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(check.timeout)
+        .user_agent(concat!("mdfx/", env!("CARGO_PKG_VERSION")))
+        .build();
+    let queue = Mutex::new(urls.into_iter());
+    let failures = Mutex::new(HashMap::new());
+
+    std::thread::scope(|scope| {
+        for _ in 0..check.concurrency.max(1) {
+            scope.spawn(|| loop {
+                let Some(url) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                tracing::debug!(url = %url, "Checking link");
+                if let Err(message) = check_url(&agent, &url) {
+                    failures.lock().unwrap().insert(url, message);
+                }
+            });
+        }
+    });
+
+    failures.into_inner().unwrap()
+}
+
+#[cfg(feature = "fetch")]
+fn check_url(agent: &ureq::Agent, url: &str) -> Result<(), String> {
+    let describe = |e: ureq::Error| match e {
+        // Rate limiting says nothing about whether the page exists
+        ureq::Error::Status(429, _) => Ok(()),
+        ureq::Error::Status(code, _) => Err(format!("{} returned HTTP {}", url, code)),
+        ureq::Error::Transport(t) => Err(t.to_string()),
+    };
+    match agent.head(url).call() {
+        Ok(_) => Ok(()),
+        // Some servers don't answer HEAD; ask again with GET
+        Err(ureq::Error::Status(403 | 405 | 501, _)) => {
+            agent.get(url).call().map(|_| ()).or_else(describe)
+        }
+        Err(e) => describe(e),
+    }
+}
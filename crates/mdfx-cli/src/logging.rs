@@ -0,0 +1,138 @@
+//! Logging setup for the CLI
+//!
+//! Diagnostics go through `tracing` and are written to stderr, keeping stdout
+//! free for processed output. The text format mirrors the CLI's labelled
+//! messages (`Info:`, `Warning:`, `Wrote:`); the JSON format emits one object
+//! per event for CI log parsing.
+//!
+//! Events may set a `status` field to replace the level label, e.g.
+//! `info!(status = "Wrote:", "{}", path.display())`.
+
+use clap::ValueEnum;
+use colored::Colorize;
+use std::fmt;
+use std::io;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable overriding the log filter (e.g. `MDFX_LOG=mdfx_fetch=trace`)
+pub const LOG_ENV: &str = "MDFX_LOG";
+
+/// Output format for log events
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable labelled messages
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Install the global subscriber
+///
+/// `--quiet` shows warnings and errors only, the default shows info,
+/// `--verbose` adds debug events (template timings, cache hits) and
+/// `--trace` shows everything. `MDFX_LOG` takes precedence when set.
+pub fn init(verbose: bool, trace: bool, quiet: bool, format: LogFormat) {
+    let level = if trace {
+        Level::TRACE
+    } else if verbose {
+        Level::DEBUG
+    } else if quiet {
+        Level::WARN
+    } else {
+        Level::INFO
+    };
+
+    let filter = EnvFilter::try_from_env(LOG_ENV).unwrap_or_else(|_| {
+        EnvFilter::new(format!(
+            "warn,mdfx={level},mdfx_cli={level},mdfx_fetch={level}",
+            level = level.as_str().to_lowercase()
+        ))
+    });
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr);
+
+    let result = match format {
+        LogFormat::Text => builder.event_format(TextFormat).try_init(),
+        LogFormat::Json => {
+            // Keep ANSI escapes out of machine-readable messages
+            colored::control::set_override(false);
+            builder
+                .json()
+                .flatten_event(true)
+                .with_current_span(false)
+                .try_init()
+        }
+    };
+
+    // Only fails if a subscriber is already installed
+    let _ = result;
+}
+
+/// Text event format: `Label: message key=value ...`
+struct TextFormat;
+
+impl<S, N> FormatEvent<S, N> for TextFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut fields = TextFields::default();
+        event.record(&mut fields);
+
+        let level = *event.metadata().level();
+        let label = match (level, fields.status.as_deref()) {
+            (Level::ERROR, status) => status.unwrap_or("Error:").red().bold(),
+            (Level::WARN, status) => status.unwrap_or("Warning:").yellow(),
+            (Level::INFO, Some(status)) => status.green(),
+            (Level::INFO, None) => "Info:".cyan(),
+            (Level::DEBUG, status) => status.unwrap_or("Debug:").blue(),
+            (_, status) => status.unwrap_or("Trace:").dimmed(),
+        };
+
+        write!(writer, "{} {}", label, fields.message)?;
+        for (name, value) in &fields.extra {
+            write!(writer, " {}", format!("{}={}", name, value).dimmed())?;
+        }
+        writeln!(writer)
+    }
+}
+
+/// Collects the message, status label, and remaining fields of an event
+#[derive(Default)]
+struct TextFields {
+    message: String,
+    status: Option<String>,
+    extra: Vec<(&'static str, String)>,
+}
+
+impl Visit for TextFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            "status" => self.status = Some(value.to_string()),
+            name => self.extra.push((name, value.to_string())),
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{:?}", value),
+            "status" => self.status = Some(format!("{:?}", value)),
+            name => self.extra.push((name, format!("{:?}", value))),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! CodeLens previews for mdfx templates
+//!
+//! Adds a "Preview" action above lines containing `{{ui:...}}` templates.
+//! Executing the action renders the whole line through the real parser and
+//! returns webview-friendly markdown (SVGs embedded as data URIs).
+
+use crate::lsp::parser::find_templates;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use mdfx::renderer::svg::SvgBackend;
+use mdfx::TemplateParser;
+use std::collections::HashMap;
+use tower_lsp::lsp_types::*;
+
+/// Command identifier for the preview CodeLens
+pub const PREVIEW_COMMAND: &str = "mdfx.preview";
+
+/// Generate a "Preview" CodeLens for each line containing a UI template
+///
+/// Lines inside fenced code blocks are skipped since the processor leaves them untouched.
+pub fn generate_code_lenses(text: &str, uri: &Url) -> Vec<CodeLens> {
+    let mut lenses = Vec::new();
+    let mut in_code_block = false;
+
+    for (line_num, line) in text.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        let has_ui_template =
+            find_templates(line)
+                .iter()
+                .any(|(_, is_closing, _, is_malformed, content, _)| {
+                    !is_closing && !is_malformed && content.starts_with("ui:")
+                });
+
+        if has_ui_template {
+            let line_num = line_num as u32;
+            lenses.push(CodeLens {
+                range: Range {
+                    start: Position {
+                        line: line_num,
+                        character: 0,
+                    },
+                    end: Position {
+                        line: line_num,
+                        character: line.len() as u32,
+                    },
+                },
+                command: Some(Command {
+                    title: "▶ Preview".to_string(),
+                    command: PREVIEW_COMMAND.to_string(),
+                    arguments: Some(vec![
+                        serde_json::json!(uri.as_str()),
+                        serde_json::json!(line_num),
+                    ]),
+                }),
+                data: None,
+            });
+        }
+    }
+
+    lenses
+}
+
+/// Render a single line of markdown into a preview
+///
+/// The line is processed with an inline SVG backend, then every raw `<svg>`
+/// element is rewritten to a base64 data URI image so the result displays in
+/// any markdown viewer. Styled Unicode text passes through unchanged.
+pub fn render_line_preview(line: &str, palette: &HashMap<String, String>) -> String {
+    let mut parser = match TemplateParser::with_backend(Box::new(SvgBackend::new_inline())) {
+        Ok(parser) => parser,
+        Err(e) => return format!("**Preview unavailable:** {}", e),
+    };
+    parser.extend_palette(palette.clone());
+
+    match parser.process(line) {
+        Ok(output) => format!("**Preview**\n\n{}", embed_svgs(&output)),
+        Err(e) => format!("**Preview error:** {}", e),
+    }
+}
+
+/// Replace inline `<svg>...</svg>` elements with markdown data URI images
+fn embed_svgs(markdown: &str) -> String {
+    let mut result = String::with_capacity(markdown.len());
+    let mut rest = markdown;
+
+    while let Some(start) = rest.find("<svg") {
+        let Some(end_offset) = rest[start..].find("</svg>") else {
+            break;
+        };
+        let end = start + end_offset + "</svg>".len();
+
+        result.push_str(&rest[..start]);
+        let b64 = STANDARD.encode(&rest[start..end]);
+        result.push_str(&format!("![preview](data:image/svg+xml;base64,{})", b64));
+        rest = &rest[end..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_uri() -> Url {
+        Url::parse("file:///tmp/README.md").unwrap()
+    }
+
+    #[test]
+    fn test_code_lens_for_ui_lines() {
+        let text = "# Title\n{{ui:tech:rust/}} and {{ui:tech:go/}}\nplain text\n{{bold}}x{{/bold}}";
+        let lenses = generate_code_lenses(text, &test_uri());
+
+        assert_eq!(lenses.len(), 1);
+        assert_eq!(lenses[0].range.start.line, 1);
+        let command = lenses[0].command.as_ref().unwrap();
+        assert_eq!(command.command, PREVIEW_COMMAND);
+    }
+
+    #[test]
+    fn test_code_lens_skips_code_blocks() {
+        let text = "```\n{{ui:tech:rust/}}\n```\n{{ui:progress:50/}}";
+        let lenses = generate_code_lenses(text, &test_uri());
+
+        assert_eq!(lenses.len(), 1);
+        assert_eq!(lenses[0].range.start.line, 3);
+    }
+
+    #[test]
+    fn test_render_line_preview_embeds_svg() {
+        let preview = render_line_preview("{{ui:tech:rust/}}", &HashMap::new());
+
+        assert!(preview.contains("data:image/svg+xml;base64,"));
+        assert!(!preview.contains("<svg"));
+    }
+
+    #[test]
+    fn test_render_line_preview_styles_text() {
+        let preview = render_line_preview(
+            "{{mathbold}}Hi{{/mathbold}} {{ui:swatch:accent/}}",
+            &HashMap::new(),
+        );
+
+        assert!(preview.contains("𝐇𝐢"));
+        assert!(preview.contains("data:image/svg+xml;base64,"));
+    }
+
+    #[test]
+    fn test_embed_svgs_passthrough() {
+        assert_eq!(embed_svgs("no images here"), "no images here");
+    }
+}
@@ -3,7 +3,7 @@
 //! Provides cached completion items and context detection for autocompletion.
 
 use mdfx::components::params::{params_for_visualization, LIVE_SOURCES, TECH_PARAMS};
-use mdfx::Registry;
+use mdfx::{MdfxConfig, Registry};
 use mdfx_icons::{brand_color, list_icons};
 use tower_lsp::lsp_types::*;
 
@@ -465,10 +465,14 @@ impl CachedCompletions {
                 First badge gets left corners, last gets right corners, middle badges are square.\n\
                 All parameters are inherited by child badges unless overridden.\n\n\
                 Parameters:\n\
-                - gap: Gap between badges in pixels (default: 0)\n\
+                - columns: Maximum badges per row\n\
+                - gap: Gap between badges and rows in pixels (default: 0)\n\
+                - wrap: Maximum row width in pixels (SVG only)\n\
+                - max_width: Scale down badges wider than this (SVG only)\n\
+                - align: Row alignment: left, center, right (default: left)\n\
                 - style, bg, text, etc.: Inherited by all children\n\n\
                 Example: {{ui:tech-group}}{{ui:tech:rust/}}{{ui:tech:go/}}{{/ui}}\n\
-                Example: {{ui:tech-group:style=flat:gap=2}}...{{/ui}}"
+                Example: {{ui:tech-group:columns=4:gap=4:align=center}}...{{/ui}}"
                     .to_string(),
             )),
             insert_text: Some("ui:tech-group".to_string()),
@@ -476,6 +480,28 @@ impl CachedCompletions {
             ..Default::default()
         });
 
+        // Add "ui:hero:" header component
+        top_level.push(CompletionItem {
+            label: "ui:hero:".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("Centered README header".to_string()),
+            documentation: Some(Documentation::String(
+                "Title, subtitle, tech badge row, and links in one centered block.\n\
+                Centered with HTML on targets that allow it, plain markdown elsewhere.\n\n\
+                Parameters:\n\
+                - title: Heading text (required)\n\
+                - title_style: Text style for the title, e.g. mathbold\n\
+                - subtitle: Line under the title\n\
+                - badges: Comma-separated tech names\n\
+                - links: Comma-separated Label|url links\n\n\
+                Example: {{ui:hero:title=mdfx:subtitle=Markdown effects:badges=rust,docker/}}"
+                    .to_string(),
+            )),
+            insert_text: Some("ui:hero:title=".to_string()),
+            insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+            ..Default::default()
+        });
+
         // Add "ui:sparkline:" data visualization component
         top_level.push(CompletionItem {
             label: "ui:sparkline:".to_string(),
@@ -544,6 +570,221 @@ impl CachedCompletions {
             ..Default::default()
         });
 
+        // Add "ui:statcard:" dashboard card component
+        top_level.push(CompletionItem {
+            label: "ui:statcard:".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("Stat card with big number and trend".to_string()),
+            documentation: Some(Documentation::String(
+                "Card with icon, big number, label, and trend arrow.\n\n\
+                Parameters:\n\
+                - value: Headline value (required)\n\
+                - label: Caption above the value\n\
+                - icon: download, star, heart, users, eye, check, clock, or a Simple Icons name\n\
+                - trend: +8% (up arrow) or -3% (down arrow)\n\
+                - width/height: Card size in pixels (default: 160x80)\n\
+                - bg, color, label_color, accent, up, down: Colors\n\n\
+                Example: {{ui:statcard:label=Downloads:value=1.2M:icon=download:trend=+8%/}}"
+                    .to_string(),
+            )),
+            insert_text: Some("ui:statcard:label=${1:label}:value=${2:value}/".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        });
+
+        // Add "ui:statcard-row" block component
+        top_level.push(CompletionItem {
+            label: "ui:statcard-row".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("Row of stat cards as one image".to_string()),
+            documentation: Some(Documentation::String(
+                "Lay out stat cards side by side as one image.\n\n\
+                Parameters:\n\
+                - columns: Maximum cards per row\n\
+                - gap: Gap between cards in pixels (default: 8)\n\
+                - wrap: Maximum row width in pixels\n\
+                - align: Row alignment: left, center, right (default: left)\n\n\
+                Example: {{ui:statcard-row}}{{ui:statcard:label=Stars:value=4k/}}{{/ui}}"
+                    .to_string(),
+            )),
+            insert_text: Some("ui:statcard-row".to_string()),
+            insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+            ..Default::default()
+        });
+
+        // Add "ui:matrix" comparison table component
+        top_level.push(CompletionItem {
+            label: "ui:matrix".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("Comparison table with check/cross badges".to_string()),
+            documentation: Some(Documentation::String(
+                "Rows like `Feature | A | B`; the first row is the header.\n\
+                yes/no/partial cells become colored check/cross/tilde badges.\n\n\
+                Parameters:\n\
+                - yes, no, partial: Mark colors (default: success, error, warning)\n\
+                - style: Badge style\n\n\
+                Example: {{ui:matrix}}\nFeature | ours | theirs\nSVG | yes | no\n{{/ui}}"
+                    .to_string(),
+            )),
+            insert_text: Some("ui:matrix".to_string()),
+            insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+            ..Default::default()
+        });
+
+        // Add "ui:tree" file tree component
+        top_level.push(CompletionItem {
+            label: "ui:tree".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("File tree with box-drawing connectors".to_string()),
+            documentation: Some(Documentation::String(
+                "Indented paths drawn as a tree in a code fence.\n\n\
+                Parameters:\n\
+                - icons: File-type glyphs (default: false)\n\
+                - depth: Maximum depth, 0 = unlimited (default: 0)\n\n\
+                Example: {{ui:tree}}\nsrc/\n  lib.rs\n{{/ui}}"
+                    .to_string(),
+            )),
+            insert_text: Some("ui:tree".to_string()),
+            insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+            ..Default::default()
+        });
+
+        // Add "ui:steps" onboarding component
+        top_level.push(CompletionItem {
+            label: "ui:steps".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("Numbered steps with connecting lines".to_string()),
+            documentation: Some(Documentation::String(
+                "One step per line; list markers are optional.\n\n\
+                Parameters:\n\
+                - current: 1-based current step (default: none)\n\
+                - size, spacing: Marker size and distance in px (default: 28, 120)\n\
+                - color, pending: Done/pending colors (default: accent, slate)\n\n\
+                Example: {{ui:steps:current=2}}\n1. Install\n2. Configure\n3. Ship\n{{/ui}}"
+                    .to_string(),
+            )),
+            insert_text: Some("ui:steps".to_string()),
+            insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+            ..Default::default()
+        });
+
+        // Add "ui:latest-release" changelog component
+        top_level.push(CompletionItem {
+            label: "ui:latest-release".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("Latest release from CHANGELOG.md".to_string()),
+            documentation: Some(Documentation::String(
+                "Version badge, release date and \"What's new\" summary of the newest\n\
+                Keep-a-Changelog release.\n\n\
+                Parameters:\n\
+                - items: Summary entries, 0 = all (default: 5)\n\
+                - heading: Summary heading (default: What's new)\n\
+                - notes, date: Show summary and date (default: true)\n\n\
+                Example: {{ui:latest-release:items=3/}}"
+                    .to_string(),
+            )),
+            insert_text: Some("ui:latest-release".to_string()),
+            insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+            ..Default::default()
+        });
+
+        // Add toolchain badges read from the project manifests
+        for (name, detail, source) in [
+            (
+                "msrv",
+                "Minimum supported Rust version",
+                "rust-version in Cargo.toml",
+            ),
+            (
+                "node-engines",
+                "Node.js version requirement",
+                "engines.node in package.json",
+            ),
+            (
+                "python-requires",
+                "Python version requirement",
+                "requires-python in pyproject.toml",
+            ),
+        ] {
+            top_level.push(CompletionItem {
+                label: format!("ui:{}", name),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some(detail.to_string()),
+                documentation: Some(Documentation::String(format!(
+                    "Version badge read from {}.\n\n\
+                    Parameters:\n\
+                    - label: Text before the version\n\
+                    - bg, style: Badge colors and style\n\n\
+                    Example: {{{{ui:{}/}}}}",
+                    source, name
+                ))),
+                insert_text: Some(format!("ui:{}", name)),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            });
+        }
+
+        // Add "ui:coverage" local coverage report badge
+        top_level.push(CompletionItem {
+            label: "ui:coverage".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("Coverage badge from a local report".to_string()),
+            documentation: Some(Documentation::String(
+                "Line coverage from lcov.info, Cobertura XML or tarpaulin JSON,\n\
+                colored by threshold (green ≥90%, red <50%).\n\n\
+                Parameters:\n\
+                - file: Report path (required)\n\
+                - label: Text before the percentage (default: coverage)\n\
+                - precision: Decimal places, 0-2 (default: 0)\n\n\
+                Example: {{ui:coverage:file=coverage/lcov.info/}}"
+                    .to_string(),
+            )),
+            insert_text: Some("ui:coverage:file=".to_string()),
+            insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+            ..Default::default()
+        });
+
+        // Add "ui:bench" criterion benchmark badge
+        top_level.push(CompletionItem {
+            label: "ui:bench".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("Criterion benchmark time".to_string()),
+            documentation: Some(Documentation::String(
+                "Latest time from target/criterion/<id>/new/estimates.json and the\n\
+                change against the baseline run (green faster, red slower).\n\n\
+                Parameters:\n\
+                - format: ns, us, ms, s or auto (default: auto)\n\
+                - stat: mean, median or slope (default: mean)\n\
+                - baseline: Baseline to compare with (default: base)\n\
+                - compare: Show the change (default: true)\n\n\
+                Example: {{ui:bench:parser/process_large:format=ms/}}"
+                    .to_string(),
+            )),
+            insert_text: Some("ui:bench:".to_string()),
+            insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+            ..Default::default()
+        });
+
+        // Add "ui:tests" local test report badge
+        top_level.push(CompletionItem {
+            label: "ui:tests".to_string(),
+            kind: Some(CompletionItemKind::MODULE),
+            detail: Some("Test results from a local report".to_string()),
+            documentation: Some(Documentation::String(
+                "Passed, failed and skipped counts from JUnit XML or libtest JSON\n\
+                (cargo-nextest, cargo test, pytest, jest-junit).\n\n\
+                Parameters:\n\
+                - file: Report path (required)\n\
+                - display: badge or bar (default: badge)\n\
+                - label: Text before the counts (default: tests)\n\n\
+                Example: {{ui:tests:file=target/nextest/ci/junit.xml/}}"
+                    .to_string(),
+            )),
+            insert_text: Some("ui:tests:file=".to_string()),
+            insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+            ..Default::default()
+        });
+
         // Add styles and components to top-level
         top_level.extend(styles.clone());
         top_level.extend(components.clone());
@@ -629,6 +870,19 @@ impl CachedCompletions {
                 insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
                 ..Default::default()
             },
+            CompletionItem {
+                label: "hero".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Centered README header".to_string()),
+                documentation: Some(Documentation::String(
+                    "Title, subtitle, tech badges, and links in one block.\n\n\
+                    Example: {{ui:hero:title=mdfx:badges=rust,docker/}}"
+                        .to_string(),
+                )),
+                insert_text: Some("hero:title=".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
             CompletionItem {
                 label: "progress:".to_string(),
                 kind: Some(CompletionItemKind::MODULE),
@@ -694,6 +948,162 @@ impl CachedCompletions {
                 insert_text_format: Some(InsertTextFormat::SNIPPET),
                 ..Default::default()
             },
+            CompletionItem {
+                label: "statcard:".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Stat card".to_string()),
+                documentation: Some(Documentation::String(
+                    "Card with icon, big number, label, and trend arrow.\n\n\
+                    Example: {{ui:statcard:label=Downloads:value=1.2M:trend=+8%/}}"
+                        .to_string(),
+                )),
+                insert_text: Some("statcard:label=${1:label}:value=${2:value}/".to_string()),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "statcard-row".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Row of stat cards".to_string()),
+                documentation: Some(Documentation::String(
+                    "Stat cards side by side as one image.\n\n\
+                    Example: {{ui:statcard-row}}...{{/ui}}"
+                        .to_string(),
+                )),
+                insert_text: Some("statcard-row".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "latest-release".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Latest changelog release".to_string()),
+                documentation: Some(Documentation::String(
+                    "Version badge and summary of the newest release in CHANGELOG.md.\n\n\
+                    Example: {{ui:latest-release/}}"
+                        .to_string(),
+                )),
+                insert_text: Some("latest-release".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "msrv".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Rust version badge".to_string()),
+                documentation: Some(Documentation::String(
+                    "Minimum supported Rust version from Cargo.toml.\n\n\
+                    Example: {{ui:msrv/}}"
+                        .to_string(),
+                )),
+                insert_text: Some("msrv".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "node-engines".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Node.js version badge".to_string()),
+                documentation: Some(Documentation::String(
+                    "engines.node from package.json.\n\n\
+                    Example: {{ui:node-engines/}}"
+                        .to_string(),
+                )),
+                insert_text: Some("node-engines".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "python-requires".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Python version badge".to_string()),
+                documentation: Some(Documentation::String(
+                    "requires-python from pyproject.toml.\n\n\
+                    Example: {{ui:python-requires/}}"
+                        .to_string(),
+                )),
+                insert_text: Some("python-requires".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "coverage".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Coverage badge".to_string()),
+                documentation: Some(Documentation::String(
+                    "Line coverage from a local lcov, Cobertura or tarpaulin report.\n\n\
+                    Example: {{ui:coverage:file=coverage/lcov.info/}}"
+                        .to_string(),
+                )),
+                insert_text: Some("coverage:file=".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "bench".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Benchmark badge".to_string()),
+                documentation: Some(Documentation::String(
+                    "Criterion benchmark time with the change against the baseline.\n\n\
+                    Example: {{ui:bench:parser/process_large/}}"
+                        .to_string(),
+                )),
+                insert_text: Some("bench:".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "tests".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Test results badge".to_string()),
+                documentation: Some(Documentation::String(
+                    "Pass/fail/skip counts from a JUnit XML or libtest JSON report.\n\n\
+                    Example: {{ui:tests:file=target/nextest/ci/junit.xml/}}"
+                        .to_string(),
+                )),
+                insert_text: Some("tests:file=".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "matrix".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Comparison table".to_string()),
+                documentation: Some(Documentation::String(
+                    "Feature comparison with check/cross/tilde badges.\n\n\
+                    Example: {{ui:matrix}}...{{/ui}}"
+                        .to_string(),
+                )),
+                insert_text: Some("matrix".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "steps".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("Numbered steps".to_string()),
+                documentation: Some(Documentation::String(
+                    "Onboarding steps joined by connecting lines.\n\n\
+                    Example: {{ui:steps}}...{{/ui}}"
+                        .to_string(),
+                )),
+                insert_text: Some("steps".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "tree".to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some("File tree".to_string()),
+                documentation: Some(Documentation::String(
+                    "Box-drawing file tree from an indented list.\n\n\
+                    Example: {{ui:tree}}...{{/ui}}"
+                        .to_string(),
+                )),
+                insert_text: Some("tree".to_string()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            },
             CompletionItem {
                 label: "waveform:".to_string(),
                 kind: Some(CompletionItemKind::MODULE),
@@ -724,6 +1134,71 @@ impl CachedCompletions {
         }
     }
 
+    /// Add user-defined names from a project `.mdfx.json`
+    ///
+    /// Project palette colors replace built-ins with the same name; custom
+    /// glyphs and partials are appended.
+    pub fn with_config(mut self, config: &MdfxConfig) -> Self {
+        let mut custom_colors: Vec<(&String, &String)> = config.palette.iter().collect();
+        custom_colors.sort();
+        for (name, hex) in custom_colors {
+            self.palette.retain(|item| &item.label != name);
+            self.palette.push(CompletionItem {
+                label: name.clone(),
+                kind: Some(CompletionItemKind::COLOR),
+                detail: Some(format!("#{} (project)", hex)),
+                documentation: Some(Documentation::String(format!(
+                    "Hex color: #{}\n\nDefined in .mdfx.json",
+                    hex
+                ))),
+                insert_text: Some(name.clone()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            });
+        }
+
+        let mut custom_glyphs: Vec<(&String, &String)> = config.glyphs.iter().collect();
+        custom_glyphs.sort();
+        for (name, char) in custom_glyphs {
+            self.glyphs.retain(|item| &item.label != name);
+            self.glyphs.push(CompletionItem {
+                label: name.clone(),
+                kind: Some(CompletionItemKind::TEXT),
+                detail: Some(format!("Glyph: {} (project)", char)),
+                documentation: Some(Documentation::String(format!(
+                    "Renders as: {}\n\nDefined in .mdfx.json",
+                    char
+                ))),
+                insert_text: Some(format!("{}/", name)),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..Default::default()
+            });
+        }
+
+        let mut partials: Vec<_> = config.partials.iter().collect();
+        partials.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, def) in partials {
+            self.top_level.push(CompletionItem {
+                label: format!("partial:{}", name),
+                kind: Some(CompletionItemKind::SNIPPET),
+                detail: Some(
+                    def.description
+                        .clone()
+                        .unwrap_or_else(|| "Project partial".to_string()),
+                ),
+                documentation: Some(Documentation::String(format!(
+                    "Template: {}\n\nDefined in .mdfx.json",
+                    def.template
+                ))),
+                insert_text: Some(format!("partial:{}}}${{1:content}}{{{{/partial}}}}", name)),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            });
+        }
+
+        self
+    }
+
     /// Build tech parameter completion items from shared definitions
     fn build_tech_param_completions() -> Vec<CompletionItem> {
         TECH_PARAMS
@@ -2,15 +2,24 @@
 //!
 //! Provides validation and error reporting for mdfx templates.
 
-use crate::lsp::parser::{extract_tag_name, find_templates, is_inherently_self_closing};
+use crate::lsp::parser::{
+    extract_tag_name, find_templates, is_inherently_self_closing, mask_literal_text,
+};
 use mdfx::components::params;
-use mdfx::Registry;
+use mdfx::{MdfxConfig, Registry, Severity, TemplateParser};
 use mdfx_icons::list_icons;
 use std::collections::HashSet;
 use tower_lsp::lsp_types::*;
 
 /// Generate diagnostics for template syntax errors
-pub fn generate_diagnostics(registry: &Registry, text: &str, uri: &Url) -> Vec<Diagnostic> {
+///
+/// Names defined in the project config (glyphs, partials) are treated as valid.
+pub fn generate_diagnostics(
+    registry: &Registry,
+    config: &MdfxConfig,
+    text: &str,
+    uri: &Url,
+) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
 
     // Collect valid tech names for diagnostics
@@ -19,8 +28,13 @@ pub fn generate_diagnostics(registry: &Registry, text: &str, uri: &Url) -> Vec<D
 
     // Track open tags for matching: (tag_name, line, start_col, end_col)
     let mut tag_stack: Vec<(String, u32, u32, u32)> = Vec::new();
+    // Closer of a raw block or comment spanning lines
+    let mut open_literal = None;
 
     for (line_num, line) in text.lines().enumerate() {
+        // Escaped templates, raw blocks and comments are literal text
+        let line = mask_literal_text(line, &mut open_literal);
+        let line = line.as_str();
         for (start, is_closing_tag, is_self_closing, is_malformed, content, end) in
             find_templates(line)
         {
@@ -334,7 +348,10 @@ pub fn generate_diagnostics(registry: &Registry, text: &str, uri: &Url) -> Vec<D
             }
             // Check glyphs: {{glyph:NAME/}}
             else if let Some(glyph_name) = content.strip_prefix("glyph:") {
-                if !glyph_name.is_empty() && registry.glyph(glyph_name).is_none() {
+                if !glyph_name.is_empty()
+                    && registry.glyph(glyph_name).is_none()
+                    && !config.glyphs.contains_key(glyph_name)
+                {
                     diagnostics.push(Diagnostic {
                         range: Range {
                             start: Position {
@@ -356,6 +373,30 @@ pub fn generate_diagnostics(registry: &Registry, text: &str, uri: &Url) -> Vec<D
                     });
                 }
             }
+            // Check partials: {{partial:NAME}}
+            else if let Some(partial_name) = content.strip_prefix("partial:") {
+                if !partial_name.is_empty() && !config.has_partial(partial_name) {
+                    diagnostics.push(Diagnostic {
+                        range: Range {
+                            start: Position {
+                                line: line_num,
+                                character: start_col,
+                            },
+                            end: Position {
+                                line: line_num,
+                                character: end_col,
+                            },
+                        },
+                        severity: Some(DiagnosticSeverity::WARNING),
+                        source: Some("mdfx".to_string()),
+                        message: format!(
+                            "Unknown partial '{}'. Define it under \"partials\" in .mdfx.json.",
+                            partial_name
+                        ),
+                        ..Default::default()
+                    });
+                }
+            }
             // Check live badges: {{ui:live:SOURCE:QUERY:METRIC/}}
             else if let Some(rest) = content.strip_prefix("ui:live:") {
                 let parts: Vec<&str> = rest.split(':').collect();
@@ -461,9 +502,89 @@ pub fn generate_diagnostics(registry: &Registry, text: &str, uri: &Url) -> Vec<D
         });
     }
 
+    render_diagnostics(registry, config, text, &mut diagnostics);
+
     diagnostics
 }
 
+/// Components that read the changelog or other project files, which the
+/// LSP doesn't load
+const PROJECT_COMPONENTS: &[&str] = &[
+    "latest-release",
+    "msrv",
+    "node-engines",
+    "python-requires",
+    "coverage",
+    "bench",
+    "tests",
+];
+
+/// Add errors that only show up when templates are rendered (bad component
+/// values, invalid frame specs) and parameter warnings, skipping lines that
+/// already have a diagnostic. Render errors from live badges, which need
+/// network access, and components that read project files are dropped.
+fn render_diagnostics(
+    registry: &Registry,
+    config: &MdfxConfig,
+    text: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Ok(mut parser) = TemplateParser::new() else {
+        return;
+    };
+    parser.load_config(config);
+
+    let flagged: HashSet<u32> = diagnostics.iter().map(|d| d.range.start.line).collect();
+
+    for found in parser.process_with_diagnostics(text).diagnostics {
+        let line = (found.line - 1) as u32;
+        if flagged.contains(&line) {
+            continue;
+        }
+
+        let severity = match found.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+        };
+        let source = text.get(found.range.clone()).unwrap_or_default();
+        let component = source
+            .strip_prefix("{{ui:")
+            .and_then(|rest| rest.split([':', '/', '}']).next());
+        let is_dynamic = component
+            .and_then(|name| registry.component(name))
+            .is_some_and(|c| c.component_type == "dynamic");
+        let is_unrenderable =
+            is_dynamic || component.is_some_and(|name| PROJECT_COMPONENTS.contains(&name));
+        if severity == DiagnosticSeverity::ERROR && is_unrenderable {
+            continue;
+        }
+
+        diagnostics.push(Diagnostic {
+            range: Range {
+                start: Position {
+                    line,
+                    character: (found.column - 1) as u32,
+                },
+                end: byte_position(text, found.range.end),
+            },
+            severity: Some(severity),
+            source: Some("mdfx".to_string()),
+            message: found.message,
+            ..Default::default()
+        });
+    }
+}
+
+/// Line and character of a byte offset
+fn byte_position(text: &str, offset: usize) -> Position {
+    let before = text.get(..offset).unwrap_or(text);
+    let line_start = before.rfind('\n').map_or(0, |p| p + 1);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: before[line_start..].chars().count() as u32,
+    }
+}
+
 /// Validate parameter values in a template
 fn validate_params(
     content: &str,
@@ -535,3 +656,64 @@ fn validate_params(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnose(text: &str) -> Vec<Diagnostic> {
+        let registry = Registry::new().unwrap();
+        let uri = Url::parse("file:///test.md").unwrap();
+        generate_diagnostics(&registry, &MdfxConfig::default(), text, &uri)
+    }
+
+    #[test]
+    fn test_render_errors_reported() {
+        let diagnostics = diagnose("ok\n{{ui:progress:abc/}}\n");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start, Position::new(1, 0));
+        assert_eq!(diagnostics[0].range.end, Position::new(1, 20));
+        assert!(diagnostics[0].message.contains("abc"));
+    }
+
+    #[test]
+    fn test_escaped_templates_ignored() {
+        assert!(diagnose("\\{{mathbold}} and \\{{ui:tech:nope/}}").is_empty());
+        assert!(diagnose("{{raw}}{{mathbold}}\n{{/italic}}{{/raw}} {{mb}}x{{/mb}}").is_empty());
+        assert!(diagnose("{{!-- {{mathbold}}\n{{ui:tech:nope/}} --}} {{mb}}x{{/mb}}").is_empty());
+    }
+
+    #[test]
+    fn test_live_badges_not_rendered() {
+        assert!(diagnose("{{ui:live:github:rust-lang/rust:stars/}}").is_empty());
+    }
+
+    #[test]
+    fn test_param_warnings_reported() {
+        let diagnostics = diagnose("{{ui:swatch:cobalt:opacity=half/}}\n");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert!(diagnostics[0].message.contains("'opacity'"));
+        assert!(diagnostics[0].message.contains("'swatch'"));
+    }
+
+    #[test]
+    fn test_live_badge_param_warnings_reported() {
+        let diagnostics = diagnose("{{ui:live:github:rust-lang/rust:stars:colour=red/}}");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0]
+            .message
+            .contains("Unknown parameter 'colour'"));
+    }
+
+    #[test]
+    fn test_project_components_not_rendered() {
+        // The LSP doesn't load the changelog or manifests, so these would always fail
+        for name in PROJECT_COMPONENTS {
+            assert!(diagnose(&format!("{{{{ui:{}/}}}}", name)).is_empty());
+        }
+    }
+}
@@ -3,6 +3,7 @@
 //! Implements the LanguageServer trait for handling LSP requests.
 
 use crate::lsp::code_actions::generate_code_actions;
+use crate::lsp::code_lens::{generate_code_lenses, render_line_preview, PREVIEW_COMMAND};
 use crate::lsp::color::{create_color_presentation, find_document_colors};
 use crate::lsp::completions::{
     build_visualization_param_completions, build_visualization_param_value_completions,
@@ -12,15 +13,32 @@ use crate::lsp::diagnostics::generate_diagnostics;
 use crate::lsp::inlay_hints::generate_inlay_hints;
 use crate::lsp::parser::find_templates;
 use crate::lsp::semantic_tokens::tokenize_document;
+use crate::lsp::workspace::{find_markdown_files, CONFIG_FILE};
 use crate::lsp::MdfxLanguageServer;
 use mdfx::components::params::{self, params_for_visualization, TECH_PARAMS};
+use mdfx::Formatter;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::LanguageServer;
 
 #[tower_lsp::async_trait]
 impl LanguageServer for MdfxLanguageServer {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        // Prefer the first workspace folder, fall back to the deprecated root URI
+        #[allow(deprecated)]
+        let root = params
+            .workspace_folders
+            .as_ref()
+            .and_then(|folders| folders.first())
+            .map(|folder| folder.uri.clone())
+            .or(params.root_uri)
+            .and_then(|uri| uri.to_file_path().ok());
+
+        if let Ok(mut slot) = self.workspace_root.write() {
+            *slot = root;
+        }
+        self.reload_config();
+
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
@@ -75,8 +93,16 @@ impl LanguageServer for MdfxLanguageServer {
                         ..Default::default()
                     },
                 )),
+                code_lens_provider: Some(CodeLensOptions {
+                    resolve_provider: Some(false),
+                }),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: vec![PREVIEW_COMMAND.to_string()],
+                    ..Default::default()
+                }),
                 inlay_hint_provider: Some(OneOf::Left(true)),
                 folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+                document_formatting_provider: Some(OneOf::Left(true)),
                 document_link_provider: Some(DocumentLinkOptions {
                     resolve_provider: Some(false),
                     work_done_progress_options: Default::default(),
@@ -94,6 +120,23 @@ impl LanguageServer for MdfxLanguageServer {
         self.client
             .log_message(MessageType::INFO, "mdfx LSP server initialized")
             .await;
+
+        self.publish_workspace_diagnostics().await;
+    }
+
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        let config_changed = params
+            .changes
+            .iter()
+            .any(|change| change.uri.path().ends_with(CONFIG_FILE));
+
+        if config_changed {
+            self.reload_config();
+            self.client
+                .log_message(MessageType::INFO, "Reloaded .mdfx.json")
+                .await;
+            self.publish_workspace_diagnostics().await;
+        }
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
@@ -105,7 +148,7 @@ impl LanguageServer for MdfxLanguageServer {
             docs.insert(params.text_document.uri.to_string(), text.clone());
         }
 
-        let diagnostics = generate_diagnostics(&self.registry, &text, &uri);
+        let diagnostics = generate_diagnostics(&self.registry, &self.config(), &text, &uri);
         self.client
             .publish_diagnostics(uri, diagnostics, None)
             .await;
@@ -119,7 +162,8 @@ impl LanguageServer for MdfxLanguageServer {
                 docs.insert(params.text_document.uri.to_string(), change.text.clone());
             }
 
-            let diagnostics = generate_diagnostics(&self.registry, &change.text, &uri);
+            let diagnostics =
+                generate_diagnostics(&self.registry, &self.config(), &change.text, &uri);
             self.client
                 .publish_diagnostics(uri, diagnostics, None)
                 .await;
@@ -148,33 +192,30 @@ impl LanguageServer for MdfxLanguageServer {
         };
 
         let context = get_completion_context(&self.registry, &text, position);
+        let cached = self.completions();
 
         // Use cached completions with filtering for fast responses
         let items = match context {
             CompletionContext::None => return Ok(None),
-            CompletionContext::TopLevel(prefix) => {
-                filter_completions(&self.cached.top_level, &prefix)
-            }
+            CompletionContext::TopLevel(prefix) => filter_completions(&cached.top_level, &prefix),
             CompletionContext::UiNamespace(prefix) => {
-                filter_completions(&self.cached.ui_namespace, &prefix)
+                filter_completions(&cached.ui_namespace, &prefix)
             }
-            CompletionContext::Glyph(prefix) => filter_completions(&self.cached.glyphs, &prefix),
-            CompletionContext::Frame(prefix) => filter_completions(&self.cached.frames, &prefix),
-            CompletionContext::Palette(prefix) => filter_completions(&self.cached.palette, &prefix),
+            CompletionContext::Glyph(prefix) => filter_completions(&cached.glyphs, &prefix),
+            CompletionContext::Frame(prefix) => filter_completions(&cached.frames, &prefix),
+            CompletionContext::Palette(prefix) => filter_completions(&cached.palette, &prefix),
             CompletionContext::ShieldStyle(prefix) => {
-                filter_completions(&self.cached.shield_styles, &prefix)
-            }
-            CompletionContext::TechName(prefix) => {
-                filter_completions(&self.cached.tech_names, &prefix)
+                filter_completions(&cached.shield_styles, &prefix)
             }
+            CompletionContext::TechName(prefix) => filter_completions(&cached.tech_names, &prefix),
             CompletionContext::TechParam(prefix) => {
-                filter_completions(&self.cached.tech_params, &prefix)
+                filter_completions(&cached.tech_params, &prefix)
             }
             CompletionContext::TechParamValue(param, prefix) => {
                 self.tech_param_value_completions(&param, &prefix)
             }
             CompletionContext::LiveSource(prefix) => {
-                filter_completions(&self.cached.live_sources, &prefix)
+                filter_completions(&cached.live_sources, &prefix)
             }
             CompletionContext::LiveMetric(source, prefix) => {
                 self.live_metric_completions(&source, &prefix)
@@ -187,7 +228,7 @@ impl LanguageServer for MdfxLanguageServer {
                     &component,
                     &param,
                     &prefix,
-                    &self.cached.palette,
+                    &cached.palette,
                 )
             }
         };
@@ -333,7 +374,13 @@ impl LanguageServer for MdfxLanguageServer {
                     name.to_string()
                 };
 
-                if let Some(char) = self.registry.glyph(&full_name) {
+                let config = self.config();
+                let glyph = config
+                    .glyphs
+                    .get(&full_name)
+                    .map(|s| s.as_str())
+                    .or_else(|| self.registry.glyph(&full_name));
+                if let Some(char) = glyph {
                     return Ok(Some(Hover {
                         contents: HoverContents::Markup(MarkupContent {
                             kind: MarkupKind::Markdown,
@@ -361,7 +408,7 @@ impl LanguageServer for MdfxLanguageServer {
                 let parts: Vec<&str> = component_and_args.split(':').collect();
 
                 if let Some(component_type) = parts.first() {
-                    let palette = self.registry.palette();
+                    let palette = &self.palette();
                     let preview = match *component_type {
                         "tech" => {
                             // ui:tech:NAME:params
@@ -695,7 +742,7 @@ impl LanguageServer for MdfxLanguageServer {
             None => return Ok(None),
         };
 
-        let palette = self.registry.palette().clone();
+        let palette = self.palette();
         let hints = generate_inlay_hints(&text, &palette, &params.range);
 
         if hints.is_empty() {
@@ -705,6 +752,56 @@ impl LanguageServer for MdfxLanguageServer {
         }
     }
 
+    async fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
+        let uri = &params.text_document.uri;
+        let text = match self.get_document_content(uri) {
+            Some(content) => content,
+            None => return Ok(None),
+        };
+
+        let lenses = generate_code_lenses(&text, uri);
+
+        if lenses.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(lenses))
+        }
+    }
+
+    async fn execute_command(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<serde_json::Value>> {
+        if params.command != PREVIEW_COMMAND {
+            return Ok(None);
+        }
+
+        // Arguments: [uri, line]
+        let uri = params
+            .arguments
+            .first()
+            .and_then(|v| v.as_str())
+            .and_then(|s| Url::parse(s).ok());
+        let line_num = params.arguments.get(1).and_then(|v| v.as_u64());
+
+        let (Some(uri), Some(line_num)) = (uri, line_num) else {
+            return Ok(None);
+        };
+
+        let text = match self.get_document_content(&uri) {
+            Some(content) => content,
+            None => return Ok(None),
+        };
+
+        let line = match text.lines().nth(line_num as usize) {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+
+        let preview = render_line_preview(line, &self.palette());
+        Ok(Some(serde_json::Value::String(preview)))
+    }
+
     async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
         let uri = params.text_document_position_params.text_document.uri;
         let position = params.text_document_position_params.position;
@@ -913,6 +1010,42 @@ impl LanguageServer for MdfxLanguageServer {
         }
     }
 
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let uri = &params.text_document.uri;
+        let text = match self.get_document_content(uri) {
+            Some(content) => content,
+            None => return Ok(None),
+        };
+
+        let formatter = match Formatter::with_options(self.config().format) {
+            Ok(formatter) => formatter,
+            Err(_) => return Ok(None),
+        };
+
+        let formatted = formatter.format(&text);
+        if formatted == text {
+            return Ok(None);
+        }
+
+        // Replace the whole document (end position in UTF-16 code units)
+        let last_line = text.rsplit('\n').next().unwrap_or("");
+        let end = Position {
+            line: text.matches('\n').count() as u32,
+            character: last_line.encode_utf16().count() as u32,
+        };
+
+        Ok(Some(vec![TextEdit {
+            range: Range {
+                start: Position {
+                    line: 0,
+                    character: 0,
+                },
+                end,
+            },
+            new_text: formatted,
+        }]))
+    }
+
     async fn document_link(&self, params: DocumentLinkParams) -> Result<Option<Vec<DocumentLink>>> {
         let uri = &params.text_document.uri;
         let text = match self.get_document_content(uri) {
@@ -962,6 +1095,31 @@ impl LanguageServer for MdfxLanguageServer {
 }
 
 impl MdfxLanguageServer {
+    /// Publish diagnostics for every markdown file in the workspace
+    ///
+    /// Open documents use their in-editor content; everything else is read from disk.
+    pub(crate) async fn publish_workspace_diagnostics(&self) {
+        let root = self.workspace_root.read().ok().and_then(|r| r.clone());
+        let Some(root) = root else {
+            return;
+        };
+
+        let config = self.config();
+        for path in find_markdown_files(&root) {
+            let Ok(uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+            let Some(text) = self.get_document_content(&uri) else {
+                continue;
+            };
+
+            let diagnostics = generate_diagnostics(&self.registry, &config, &text, &uri);
+            self.client
+                .publish_diagnostics(uri, diagnostics, None)
+                .await;
+        }
+    }
+
     /// Build completion items for live source metrics using shared definitions
     pub(crate) fn live_metric_completions(
         &self,
@@ -993,7 +1151,7 @@ impl MdfxLanguageServer {
         // Color parameters return palette completions from cache
         match param {
             "bg" | "bg_left" | "bg_right" | "logo" | "text" | "text_color" | "color" | "border" => {
-                return filter_completions(&self.cached.palette, prefix);
+                return filter_completions(&self.completions().palette, prefix);
             }
             _ => {}
         }
@@ -12,12 +12,15 @@
 //! - `semantic_tokens` - Syntax highlighting through semantic tokens
 //! - `diagnostics` - Validation and error reporting
 //! - `code_actions` - Quick fixes and suggestions
+//! - `code_lens` - Whole-line "Preview" actions for UI templates
 //! - `color` - Color picker support
 //! - `preview` - Hover preview generation with SVG data URIs
 //! - `inlay_hints` - Live inline color swatches that update as you type
+//! - `workspace` - Project config discovery and workspace file scanning
 //! - `handlers` - LSP protocol handlers
 
 mod code_actions;
+mod code_lens;
 mod color;
 mod completions;
 mod diagnostics;
@@ -26,10 +29,12 @@ mod inlay_hints;
 mod parser;
 mod preview;
 mod semantic_tokens;
+mod workspace;
 
 use completions::CachedCompletions;
-use mdfx::Registry;
+use mdfx::{MdfxConfig, Registry};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use tower_lsp::lsp_types::Url;
 use tower_lsp::{Client, LspService, Server};
@@ -40,23 +45,71 @@ pub struct MdfxLanguageServer {
     pub(crate) registry: Arc<Registry>,
     /// Cached document contents (URI -> text)
     pub(crate) documents: Arc<RwLock<HashMap<String, String>>>,
-    /// Pre-built completion items for fast responses
-    pub(crate) cached: Arc<CachedCompletions>,
+    /// Pre-built completion items for fast responses (rebuilt when config changes)
+    pub(crate) cached: RwLock<Arc<CachedCompletions>>,
+    /// Project configuration loaded from `.mdfx.json`
+    pub(crate) config: RwLock<MdfxConfig>,
+    /// Workspace root directory (from the client's initialize request)
+    pub(crate) workspace_root: RwLock<Option<PathBuf>>,
 }
 
 impl MdfxLanguageServer {
     /// Create a new language server instance
     pub fn new(client: Client) -> Self {
-        let registry = Registry::new().expect("Failed to load registry");
+        let registry = Registry::shared().expect("Failed to load registry");
 
         // Pre-build all completion items at startup for fast responses
         let cached = CachedCompletions::build(&registry);
 
         Self {
             client,
-            registry: Arc::new(registry),
+            registry,
             documents: Arc::new(RwLock::new(HashMap::new())),
-            cached: Arc::new(cached),
+            cached: RwLock::new(Arc::new(cached)),
+            config: RwLock::new(MdfxConfig::default()),
+            workspace_root: RwLock::new(None),
+        }
+    }
+
+    /// Get the current completion cache
+    pub(crate) fn completions(&self) -> Arc<CachedCompletions> {
+        self.cached
+            .read()
+            .map(|cached| Arc::clone(&cached))
+            .unwrap_or_else(|_| Arc::new(CachedCompletions::build(&self.registry)))
+    }
+
+    /// Get a snapshot of the project configuration
+    pub(crate) fn config(&self) -> MdfxConfig {
+        self.config
+            .read()
+            .map(|config| config.clone())
+            .unwrap_or_default()
+    }
+
+    /// Built-in palette merged with project palette overrides
+    pub(crate) fn palette(&self) -> HashMap<String, String> {
+        let mut palette = self.registry.palette().clone();
+        if let Ok(config) = self.config.read() {
+            palette.extend(config.palette.clone());
+        }
+        palette
+    }
+
+    /// Load `.mdfx.json` from the workspace root and rebuild completions
+    pub(crate) fn reload_config(&self) {
+        let root = self.workspace_root.read().ok().and_then(|r| r.clone());
+        let config = root
+            .as_deref()
+            .and_then(workspace::load_config)
+            .unwrap_or_default();
+
+        let cached = CachedCompletions::build(&self.registry).with_config(&config);
+        if let Ok(mut slot) = self.cached.write() {
+            *slot = Arc::new(cached);
+        }
+        if let Ok(mut slot) = self.config.write() {
+            *slot = config;
         }
     }
 
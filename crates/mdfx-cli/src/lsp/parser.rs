@@ -95,6 +95,55 @@ pub fn find_templates(line: &str) -> Vec<(usize, bool, bool, bool, &str, usize)>
     results
 }
 
+/// Blank out escaped templates, raw blocks and comments so they are not
+/// treated as templates
+///
+/// Masked text is replaced with spaces of the same byte length, so positions
+/// in the result match the original line. `open` holds the closer of a raw
+/// block or comment that continues onto the next line.
+pub fn mask_literal_text(line: &str, open: &mut Option<&'static str>) -> String {
+    fn blank(out: &mut String, text: &str) {
+        out.extend(std::iter::repeat_n(' ', text.len()));
+    }
+
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    loop {
+        if let Some(closer) = *open {
+            let Some(end) = rest.find(closer) else {
+                blank(&mut out, rest);
+                return out;
+            };
+            blank(&mut out, &rest[..end + closer.len()]);
+            rest = &rest[end + closer.len()..];
+            *open = None;
+        }
+
+        let Some(pos) = rest.find("{{") else {
+            out.push_str(rest);
+            return out;
+        };
+        out.push_str(&rest[..pos]);
+        let tag = &rest[pos..];
+
+        let opener = if rest[..pos].ends_with('\\') {
+            "{{"
+        } else if tag.starts_with("{{raw}}") {
+            *open = Some("{{/raw}}");
+            "{{raw}}"
+        } else if tag.starts_with("{{!--") {
+            *open = Some("--}}");
+            "{{!--"
+        } else {
+            out.push_str("{{");
+            rest = &tag[2..];
+            continue;
+        };
+        blank(&mut out, opener);
+        rest = &tag[opener.len()..];
+    }
+}
+
 /// Extract tag name from template content for matching
 /// e.g., "bold" from "bold", "frame:gradient" from "frame:gradient"
 pub fn extract_tag_name(content: &str) -> String {
@@ -155,6 +204,31 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[rstest]
+    #[case("a \\{{mb}}x", None, "a \\  mb}}x", None)]
+    #[case(
+        "{{raw}}{{mb}}{{/raw}}{{sc}}",
+        None,
+        "                     {{sc}}",
+        None
+    )]
+    #[case("x {{!-- {{mb}} --}} {{sc}}", None, "x                   {{sc}}", None)]
+    #[case("{{!-- → {{mb}}", None, "                ", Some("--}}"))]
+    #[case("end --}}{{sc}}", Some("--}}"), "        {{sc}}", None)]
+    #[case("{{mb}} {{raw}}", None, "{{mb}}        ", Some("{{/raw}}"))]
+    fn test_mask_literal_text(
+        #[case] line: &str,
+        #[case] open: Option<&'static str>,
+        #[case] expected: &str,
+        #[case] still_open: Option<&'static str>,
+    ) {
+        let mut open = open;
+        let masked = mask_literal_text(line, &mut open);
+        assert_eq!(masked, expected);
+        assert_eq!(masked.len(), line.len());
+        assert_eq!(open, still_open);
+    }
+
     #[test]
     fn test_find_templates_edge_cases() {
         // Incomplete template (no closing) - now marked as malformed
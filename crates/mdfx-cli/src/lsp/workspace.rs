@@ -0,0 +1,95 @@
+//! Workspace awareness for the mdfx LSP
+//!
+//! Discovers the project's `.mdfx.json` and the markdown files that should
+//! receive diagnostics on startup.
+
+use mdfx::MdfxConfig;
+use std::path::{Path, PathBuf};
+
+/// Name of the project configuration file
+pub const CONFIG_FILE: &str = ".mdfx.json";
+
+/// Directories never scanned for markdown files
+const SKIP_DIRS: &[&str] = &["target", "node_modules", "vendor", "dist", "build"];
+
+/// Load `.mdfx.json` from the workspace root, if present and valid
+pub fn load_config(root: &Path) -> Option<MdfxConfig> {
+    let path = root.join(CONFIG_FILE);
+    if !path.exists() {
+        return None;
+    }
+    MdfxConfig::load(&path).ok()
+}
+
+/// Recursively find markdown files under the workspace root
+///
+/// Hidden directories and common build/dependency directories are skipped.
+pub fn find_markdown_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            if path.is_dir() {
+                if !name.starts_with('.') && !SKIP_DIRS.contains(&name.as_ref()) {
+                    stack.push(path);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "md") {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_markdown_files_skips_ignored_dirs() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("docs")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("README.md"), "").unwrap();
+        std::fs::write(root.join("docs/guide.md"), "").unwrap();
+        std::fs::write(root.join("docs/notes.txt"), "").unwrap();
+        std::fs::write(root.join("target/out.md"), "").unwrap();
+        std::fs::write(root.join(".git/HEAD.md"), "").unwrap();
+
+        let files = find_markdown_files(root);
+        assert_eq!(
+            files,
+            vec![root.join("README.md"), root.join("docs/guide.md")]
+        );
+    }
+
+    #[test]
+    fn test_load_config() {
+        let dir = TempDir::new().unwrap();
+        assert!(load_config(dir.path()).is_none());
+
+        std::fs::write(
+            dir.path().join(CONFIG_FILE),
+            r#"{"palette": {"brand": "FF5500"}, "glyphs": {"logo": "⌬"}}"#,
+        )
+        .unwrap();
+
+        let config = load_config(dir.path()).unwrap();
+        assert_eq!(config.palette.get("brand"), Some(&"FF5500".to_string()));
+        assert_eq!(config.glyphs.get("logo"), Some(&"⌬".to_string()));
+    }
+}
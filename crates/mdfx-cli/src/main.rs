@@ -1,67 +1,146 @@
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
 use colored::Colorize;
+use mdfx::components::labels;
 use mdfx::manifest::AssetManifest;
+use mdfx::palette::{Scheme, TokenFormat};
 use mdfx::renderer::plaintext::PlainTextBackend;
-use mdfx::renderer::shields::ShieldsBackend;
+use mdfx::renderer::shields::{LiveBadgeCache, ShieldsBackend};
 use mdfx::renderer::svg::SvgBackend;
+use mdfx::vfs::AssetWriteSummary;
 use mdfx::{
-    available_targets, detect_target_from_path, get_target, BackendType, Converter, Error,
-    MdfxConfig, Registry, StyleCategory, Target, TemplateParser,
+    available_targets, detect_target_from_path, get_target, AssetChange, BackendType, CloserPolicy,
+    Converter, DiskFs, Error, Fallback, Formatter, GalleryFormat, GrammarFormat, ImageDimensions,
+    InlineMode, MdfxConfig, PipelineStage, ProcessedMarkdown, Profiler, Registry, SchemaFormat,
+    Severity, StatsCollector, StyleCategory, Target, TemplateParser,
 };
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::io::{self, Read};
 use std::path::PathBuf;
 use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tracing::{error, info};
 
+mod doctest;
+mod exit;
+mod lint;
+mod logging;
 #[cfg(feature = "lsp")]
 mod lsp;
+mod retention;
+#[cfg(feature = "serve")]
+mod serve;
+mod stats;
+
+use exit::ErrorFormat;
+use lint::LintFormat;
+use logging::LogFormat;
+use retention::Retention;
+use stats::{ProcessReport, StatsFormat};
+
+/// Where generated assets go when neither a flag nor .mdfx.json says otherwise
+const DEFAULT_ASSETS_DIR: &str = "assets/mdfx";
+
+/// Changelog read by `{{ui:latest-release/}}` and `mdfx gen changelog`
+const DEFAULT_CHANGELOG: &str = "CHANGELOG.md";
 
 /// Markdown effects: Unicode text styling and UI components
 #[derive(Parser)]
 #[command(name = "mdfx")]
 #[command(version, about)]
 #[command(
-    long_about = "Transform markdown with Unicode text effects and UI components through template syntax.\n\nSupports 24 styles including mathbold, fullwidth, script, fraktur, and more.\nUse templates: {{mathbold}}TEXT{{/mathbold}}\n\nFor more info: https://github.com/blackwell-systems/mdfx"
+    long_about = "Transform markdown with Unicode text effects and UI components through template syntax.\n\nSupports 29 styles including mathbold, fullwidth, script, fraktur, and more.\nUse templates: {{mathbold}}TEXT{{/mathbold}}\n\nFor more info: https://github.com/blackwell-systems/mdfx"
 )]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Show debug output: per-template expansion timings and cache hits
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Show all trace output (more detailed than --verbose)
+    #[arg(long, global = true)]
+    trace: bool,
+
+    /// Only show warnings and errors
+    #[arg(short, long, global = true, conflicts_with_all = ["verbose", "trace"])]
+    quiet: bool,
+
+    /// Log output format on stderr (text, json)
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    /// Format of the error that ends a failed run (text, json)
+    ///
+    /// json prints {"error": {"kind", "code", "message"}} on stderr, and
+    /// `mdfx check` prints its diagnostics as JSON lines. The exit code
+    /// tells the kind of failure: 3 parse, 4 IO, 5 fetch, 6 check failed.
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Text)]
+    error_format: ErrorFormat,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Convert text to a Unicode style
     ///
-    /// Transform plain text into styled Unicode characters using one of 24 available styles.
+    /// Transform plain text into styled Unicode characters using one of 29 available styles.
     /// Supports style aliases (e.g., 'mb' for 'mathbold') and character spacing.
+    /// Without a text argument, each line of stdin is converted.
     ///
     /// Examples:
     ///   mdfx convert --style mathbold "Hello World"
     ///   mdfx convert --style mb --spacing 1 "SPACED"
-    ///   mdfx convert --style script "Elegant Text"
+    ///   mdfx convert --style script,fraktur "Elegant Text"
+    ///   mdfx convert --style italic --fallback closest "Café 2024"
+    ///   mdfx convert --style zalgo --seed 7 "Glitch"
+    ///   echo "Piped" | mdfx convert --style mb
+    ///   mdfx convert --style mb --list-mapping
     ///
     /// Run 'mdfx list' to see all available styles.
     Convert {
-        /// The style to use (e.g., mathbold, fullwidth, mb)
-        #[arg(short, long)]
-        style: String,
+        /// The style(s) to use (e.g., mathbold, fullwidth, mb); repeat or
+        /// separate with commas to print one variant per style
+        #[arg(short, long, required = true, value_delimiter = ',')]
+        style: Vec<String>,
 
         /// Number of spaces between each character (0 = no spacing)
         #[arg(long, default_value = "0")]
         spacing: usize,
 
-        /// The text to convert
-        text: String,
+        /// What to do with letters and digits the style has no equivalent
+        /// for (passthrough, skip, error, closest)
+        #[arg(long, value_name = "POLICY")]
+        fallback: Option<String>,
+
+        /// Seed for styles that add random marks (zalgo); the same seed
+        /// always produces the same output
+        #[arg(long, value_name = "N")]
+        seed: Option<u64>,
+
+        /// Print the style's character mapping as tab-separated columns
+        /// (source, then one column per style) instead of converting text
+        #[arg(long, conflicts_with = "text")]
+        list_mapping: bool,
+
+        /// Also copy the result to the system clipboard
+        /// (requires the `clipboard` feature)
+        #[arg(long)]
+        copy: bool,
+
+        /// The text to convert (reads stdin if omitted)
+        text: Option<String>,
     },
 
     /// List available resources
     ///
     /// Display available styles, components, glyphs, frames, or palette colors.
-    /// Without a resource type, lists all 24 Unicode text styles.
+    /// Without a resource type, lists all 29 Unicode text styles.
     ///
     /// Examples:
     ///   mdfx list                  # List styles (default)
@@ -108,6 +187,11 @@ enum Commands {
     ///   npm    - npm package README (like GitHub)
     ///   gitlab - GitLab README (more HTML support)
     ///   pypi   - PyPI package (plain text, ASCII-safe)
+    ///   crates-io - crates.io README (relative links point at the repository)
+    ///   docs-site - Docusaurus/MDX page (MDX-safe escaping, admonitions)
+    ///   mkdocs - MkDocs Material page (admonitions, markdown in HTML)
+    ///   confluence - Confluence storage format (assets as attachments)
+    ///   jira   - Jira wiki markup (assets as attachments)
     ///   auto   - Auto-detect from output path
     ///
     /// Template syntax:
@@ -127,7 +211,7 @@ enum Commands {
         #[arg(short = 'i', long)]
         in_place: bool,
 
-        /// Target platform (github, local, npm, gitlab, pypi, auto)
+        /// Target platform (github, local, npm, gitlab, pypi, crates-io, docs-site, mkdocs, confluence, jira, auto)
         #[arg(short, long, default_value = "github")]
         target: String,
 
@@ -135,9 +219,10 @@ enum Commands {
         #[arg(short, long)]
         backend: Option<String>,
 
-        /// Output directory for SVG assets (only used with svg backend)
-        #[arg(long, default_value = "assets/mdfx")]
-        assets_dir: String,
+        /// Output directory for SVG assets (only used with svg backend).
+        /// Defaults to the target's "assets_dir" in .mdfx.json, then assets/mdfx
+        #[arg(long)]
+        assets_dir: Option<String>,
 
         /// Prefix for asset paths in markdown output (defaults to assets_dir)
         /// Use when assets_dir differs from the path in markdown references.
@@ -145,6 +230,13 @@ enum Commands {
         #[arg(long)]
         assets_prefix: Option<String>,
 
+        /// Start generated asset filenames with this namespace and record it
+        /// in manifest.json, so documents sharing an assets directory keep
+        /// their own assets. Pass the same namespace to clean and verify.
+        /// Example: --asset-namespace docs-api-
+        #[arg(long, value_name = "PREFIX")]
+        asset_namespace: Option<String>,
+
         /// Custom palette JSON file for color definitions
         /// Format: {"colorName": "HEXVALUE", ...}
         #[arg(long)]
@@ -169,6 +261,229 @@ enum Commands {
         #[cfg(feature = "fetch")]
         #[arg(long, default_value = ".mdfx-cache")]
         cache_dir: String,
+
+        /// User-Agent for live badge API requests
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "UA")]
+        user_agent: Option<String>,
+
+        /// HTTP proxy for live badge requests (default: HTTPS_PROXY, HTTP_PROXY or ALL_PROXY)
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "URL")]
+        proxy: Option<String>,
+
+        /// PEM file of extra CA certificates to trust for live badge requests
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "FILE")]
+        ca_bundle: Option<PathBuf>,
+
+        /// Seconds to cache a live badge whose source failed (rendered as n/a); 0 disables
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "SECONDS", default_value_t = 300)]
+        failure_ttl: u64,
+
+        /// Add a content-derived v= parameter to live shields.io badges, so
+        /// image proxies like GitHub's camo fetch them again when values change
+        #[cfg(feature = "fetch")]
+        #[arg(long)]
+        cache_bust: bool,
+
+        /// Add cacheSeconds=N to live shields.io badges
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "N")]
+        cache_seconds: Option<u32>,
+
+        /// Render live badges as gray placeholders ("stars: —") when their data
+        /// can't be fetched (offline without cache, missing token, API errors)
+        #[arg(long)]
+        placeholders: bool,
+
+        /// Process templates inside inline code spans (by default only
+        /// templates marked with `{{!`, e.g. `{{!mathbold}}`, are)
+        #[arg(long)]
+        inline_code: bool,
+
+        /// Process templates inside HTML comments and <pre>/<script> blocks,
+        /// which are left as written by default
+        #[arg(long)]
+        process_html: bool,
+
+        /// Parse the input as CommonMark and leave link destinations,
+        /// reference definitions and HTML tags as written
+        #[arg(long)]
+        commonmark: bool,
+
+        /// Print processing statistics to stderr (--stats or --stats=json)
+        /// Template counts by type, asset outcomes, fetch cache hit rate, and stage timings
+        #[arg(
+            long,
+            value_enum,
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "text"
+        )]
+        stats: Option<StatsFormat>,
+
+        /// Write the time spent in each template and backend render to FILE
+        /// as a Chrome trace (open in speedscope, Perfetto, or chrome://tracing)
+        #[arg(long, value_name = "FILE")]
+        profile: Option<PathBuf>,
+
+        /// How generated image references state their size (none, html, suffix).
+        /// Defaults to the target's setting: html for github, gitlab, npm, and local.
+        #[arg(long)]
+        image_dimensions: Option<String>,
+
+        /// Embed SVGs in the markdown instead of writing asset files
+        /// (--inline=svg for raw <svg>, --inline=data-uri for base64 images;
+        /// plain --inline picks the first form the target displays).
+        /// Implies --backend svg
+        #[arg(
+            long,
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "auto"
+        )]
+        inline: Option<String>,
+
+        /// Upload generated SVGs to the bucket in the "publish" section of
+        /// .mdfx.json and reference their public URLs instead of local files
+        /// (requires the `publish` feature; implies --backend svg)
+        #[arg(long)]
+        publish: bool,
+
+        /// Also copy the processed markdown to the system clipboard
+        /// (requires the `clipboard` feature)
+        #[arg(long)]
+        copy: bool,
+
+        /// Byte-identical output for identical inputs: zeroed manifest
+        /// timestamps, sorted manifest entries, and live badges only from
+        /// the --cache-dir snapshot (implies --offline)
+        #[arg(long)]
+        reproducible: bool,
+    },
+
+    /// Format template syntax in a markdown file
+    ///
+    /// Normalizes templates without changing rendered output: canonical style
+    /// and frame names instead of aliases, sorted key=value parameters, and a
+    /// consistent closer policy for frames and partials. Defaults come from the
+    /// "format" section of .mdfx.json.
+    ///
+    /// Examples:
+    ///   mdfx fmt README.template.md            # Print formatted output
+    ///   mdfx fmt -i README.template.md         # Format in place
+    ///   mdfx fmt --check README.template.md    # Exit with error if unformatted
+    ///   mdfx fmt --closers named -i docs/guide.md
+    Fmt {
+        /// Input file (use - or omit for stdin)
+        input: Option<PathBuf>,
+
+        /// Output file (use - or omit for stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Modify file in place
+        #[arg(short = 'i', long)]
+        in_place: bool,
+
+        /// Check formatting only; fail if the file would change
+        #[arg(long)]
+        check: bool,
+
+        /// Closer policy for frames and partials (preserve, named, universal)
+        #[arg(long)]
+        closers: Option<String>,
+
+        /// mdfx configuration file (default: auto-discover .mdfx.json)
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+
+    /// Check markdown files for template errors
+    ///
+    /// Processes each file in recovery mode and reports every problem found
+    /// (unknown styles, unclosed tags, bad component parameters, unknown
+    /// partials) as file:line:column lines. Exits with an error if any file
+    /// has errors. Nothing is written.
+    ///
+    /// Examples:
+    ///   mdfx check README.template.md
+    ///   mdfx check docs/*.md --deny-warnings
+    Check {
+        /// Files to check (use - for stdin)
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// mdfx configuration file (default: auto-discover .mdfx.json)
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Fail on warnings as well as errors
+        #[arg(long)]
+        deny_warnings: bool,
+
+        /// Run in offline mode (use cached data only, no network requests)
+        #[cfg(feature = "fetch")]
+        #[arg(long)]
+        offline: bool,
+
+        /// Cache directory for dynamic badge data
+        #[cfg(feature = "fetch")]
+        #[arg(long, default_value = ".mdfx-cache")]
+        cache_dir: String,
+
+        /// User-Agent for live badge API requests
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "UA")]
+        user_agent: Option<String>,
+
+        /// HTTP proxy for live badge requests (default: HTTPS_PROXY, HTTP_PROXY or ALL_PROXY)
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "URL")]
+        proxy: Option<String>,
+
+        /// PEM file of extra CA certificates to trust for live badge requests
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "FILE")]
+        ca_bundle: Option<PathBuf>,
+
+        /// Seconds to cache a live badge whose source failed (rendered as n/a); 0 disables
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "SECONDS", default_value_t = 300)]
+        failure_ttl: u64,
+    },
+
+    /// Compile and run the Rust code blocks of markdown files
+    ///
+    /// Tests ```rust blocks like doctests: lines starting with `# ` are
+    /// compiled but hidden, code without `fn main` is wrapped in one, and the
+    /// ignore, no_run, should_panic and compile_fail attributes are honored.
+    /// Blocks inserted by {{snippet:...}} templates are tested too. Each block
+    /// is built in target/mdfx-snippets against the package in the current
+    /// directory, and compiler errors are reported at their line in the
+    /// markdown file.
+    ///
+    /// Examples:
+    ///   mdfx test-snippets README.template.md
+    ///   mdfx test-snippets docs/*.md --managed --offline
+    TestSnippets {
+        /// Markdown files to test
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// mdfx configuration file (default: auto-discover .mdfx.json)
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Only test blocks inserted by {{snippet:...}} templates
+        #[arg(long)]
+        managed: bool,
+
+        /// Build without network access (passed on to cargo)
+        #[arg(long)]
+        offline: bool,
     },
 
     /// Generate shell completions
@@ -194,10 +509,24 @@ enum Commands {
     /// Examples:
     ///   mdfx verify --assets-dir assets/mdfx
     ///   mdfx verify  # Uses default assets/mdfx
+    ///   mdfx verify --public-key mdfx.pub --require-signature
+    ///   mdfx verify --asset-namespace docs-api-
     Verify {
         /// Assets directory containing manifest.json
         #[arg(long, default_value = "assets/mdfx")]
         assets_dir: String,
+
+        /// Only verify assets generated with this --asset-namespace
+        #[arg(long, value_name = "PREFIX")]
+        asset_namespace: Option<String>,
+
+        /// Check manifest.json.minisig against this minisign public key
+        #[arg(long, value_name = "FILE")]
+        public_key: Option<PathBuf>,
+
+        /// Fail if manifest.json isn't signed by --public-key
+        #[arg(long, requires = "public_key")]
+        require_signature: bool,
     },
 
     /// Clean unreferenced assets
@@ -211,11 +540,18 @@ enum Commands {
     ///   mdfx clean --dry-run  # Show what would be deleted
     ///   mdfx clean --scan "docs/**/*.md"  # Scan markdown files for references
     ///   mdfx clean --scan "*.md" --dry-run  # Preview what would be deleted
+    ///   mdfx clean --scan "docs/**/*.md" --keep-newer-than 30 --keep-git-history
+    ///   mdfx clean --asset-namespace docs-api-
     Clean {
         /// Assets directory containing manifest.json
         #[arg(long, default_value = "assets/mdfx")]
         assets_dir: String,
 
+        /// Only clean assets generated with this --asset-namespace; files
+        /// of other namespaces are left alone
+        #[arg(long, value_name = "PREFIX")]
+        asset_namespace: Option<String>,
+
         /// Show what would be deleted without actually deleting
         #[arg(long)]
         dry_run: bool,
@@ -224,8 +560,24 @@ enum Commands {
         /// When set, only assets referenced in matching files are kept
         #[arg(long)]
         scan: Option<String>,
+
+        /// Keep assets modified within the last N days
+        #[arg(long, value_name = "DAYS")]
+        keep_newer_than: Option<u64>,
+
+        /// Keep assets whose filename appears anywhere in git history (all branches)
+        #[arg(long)]
+        keep_git_history: bool,
+
+        /// Keep assets matching the filenames or globs listed in FILE, one per line
+        #[arg(long, value_name = "FILE")]
+        keep: Option<PathBuf>,
     },
 
+    /// Inspect generated asset directories
+    #[command(subcommand)]
+    Assets(AssetsCommands),
+
     /// Build markdown to multiple targets at once
     ///
     /// Compiles the same source file to multiple platform-specific outputs.
@@ -235,6 +587,8 @@ enum Commands {
     ///   mdfx build README.template.md --output-dir dist/
     ///   mdfx build README.template.md --targets github,pypi,npm
     ///   mdfx build README.template.md --all-targets
+    ///   mdfx build README.template.md --all-targets --jobs 4
+    ///   mdfx build README.template.md --i18n i18n --default-lang en
     Build {
         /// Input markdown file
         input: PathBuf,
@@ -243,7 +597,7 @@ enum Commands {
         #[arg(short, long, default_value = "dist")]
         output_dir: String,
 
-        /// Comma-separated list of targets (github,local,npm,gitlab,pypi)
+        /// Comma-separated list of targets (github,local,npm,gitlab,pypi,crates-io,docs-site,mkdocs,confluence,jira)
         #[arg(short, long)]
         targets: Option<String>,
 
@@ -251,9 +605,34 @@ enum Commands {
         #[arg(long)]
         all_targets: bool,
 
+        /// Number of targets to build at once
+        #[arg(short, long, default_value = "1", value_name = "N")]
+        jobs: usize,
+
         /// Custom palette JSON file for color definitions
         #[arg(long)]
         palette: Option<PathBuf>,
+
+        /// Directory of per-language string files (fr.json, de.json, ...) for
+        /// {{t:key/}} templates [default: i18n next to the input, if present]
+        #[arg(long, value_name = "DIR")]
+        i18n: Option<PathBuf>,
+
+        /// Language of the unsuffixed output; its string file is optional and
+        /// fills in strings missing from the other languages
+        #[arg(long, default_value = "en", value_name = "LANG")]
+        default_lang: String,
+
+        /// Embed SVGs in the markdown instead of writing asset files
+        /// (svg, data-uri, or plain --inline for the first form each target
+        /// displays). Targets that display neither keep asset files
+        #[arg(
+            long,
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "auto"
+        )]
+        inline: Option<String>,
     },
 
     /// Watch file for changes and rebuild automatically
@@ -281,9 +660,10 @@ enum Commands {
         #[arg(short, long)]
         backend: Option<String>,
 
-        /// Output directory for SVG assets (only used with svg backend)
-        #[arg(long, default_value = "assets/mdfx")]
-        assets_dir: String,
+        /// Output directory for SVG assets (only used with svg backend).
+        /// Defaults to the target's "assets_dir" in .mdfx.json, then assets/mdfx
+        #[arg(long)]
+        assets_dir: Option<String>,
 
         /// Prefix for asset paths in markdown output (defaults to assets_dir)
         /// Use when assets_dir differs from the path in markdown references.
@@ -299,11 +679,59 @@ enum Commands {
         #[arg(long, default_value = "100")]
         debounce: u64,
 
+        /// Embed SVGs in the markdown instead of writing asset files
+        /// (svg, data-uri, or plain --inline for the first form the target displays)
+        #[arg(
+            long,
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "auto"
+        )]
+        inline: Option<String>,
+
         /// mdfx configuration file (default: auto-discover .mdfx.json)
         #[arg(long)]
         config: Option<PathBuf>,
     },
 
+    /// Run a pipeline from .mdfx.json
+    ///
+    /// A pipeline reads its input, passes the text through each stage in
+    /// order (process for a target, post_process, wrap, link_check, compat,
+    /// write), and writes the result to its output. Without a name, lists
+    /// the configured pipelines.
+    ///
+    /// Examples:
+    ///   mdfx run
+    ///   mdfx run readme
+    Run {
+        /// Pipeline to run
+        pipeline: Option<String>,
+
+        /// mdfx configuration file (default: auto-discover .mdfx.json)
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+
+    /// Generate editor support files
+    ///
+    /// Generated files are derived from the template registry, so they stay
+    /// in sync with the styles, components, and frames the parser accepts.
+    #[command(subcommand)]
+    Gen(GenCommands),
+
+    /// Lint rendered markdown
+    #[command(subcommand)]
+    Lint(LintCommands),
+
+    /// Work with custom color palettes
+    #[command(subcommand)]
+    Palette(PaletteCommands),
+
+    /// Render badges defined in a spec file
+    #[command(subcommand)]
+    Badges(BadgesCommands),
+
     /// Language Server Protocol (LSP) commands
     ///
     /// Provides IDE integration with autocompletion for mdfx template syntax.
@@ -313,69 +741,490 @@ enum Commands {
     #[cfg(feature = "lsp")]
     #[command(subcommand)]
     Lsp(LspCommands),
+
+    /// Serve rendered badges over HTTP
+    ///
+    /// Runs a local badge service: /badge/<component>/<args>?key=value and
+    /// /render?template=... return SVGs, and / is a template playground.
+    ///
+    /// Examples:
+    ///   mdfx serve
+    ///   mdfx serve --port 3000 --offline
+    ///   curl "http://127.0.0.1:8080/badge/tech/rust?style=flat-square"
+    ///   MDFX_SERVE_KEY=... mdfx serve --signed --allow-source crates --rate-limit 60
+    ///   MDFX_SERVE_KEY=... mdfx serve --sign "/badge/tech/rust?style=flat"
+    ///
+    /// Requires: cargo install mdfx-cli --features serve
+    #[cfg(feature = "serve")]
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Port to listen on
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// mdfx configuration file (default: auto-discover .mdfx.json)
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Only answer URLs signed with the key in MDFX_SERVE_KEY
+        /// (also disables the playground)
+        #[arg(long)]
+        signed: bool,
+
+        /// Print a URL with its signature (using MDFX_SERVE_KEY) and exit
+        #[arg(long, value_name = "URL")]
+        sign: Option<String>,
+
+        /// Only render live badges from these sources (comma-separated)
+        #[arg(long, value_name = "SOURCES", value_delimiter = ',')]
+        allow_source: Vec<String>,
+
+        /// Only fetch remote icons (icon=url:https://...) from these hosts
+        /// (comma-separated; with --allow-source, other remote icons are refused)
+        #[arg(long, value_name = "HOSTS", value_delimiter = ',')]
+        allow_icon_host: Vec<String>,
+
+        /// Only accept these component parameters (comma-separated)
+        #[arg(long, value_name = "KEYS", value_delimiter = ',')]
+        allow_param: Vec<String>,
+
+        /// Requests per minute allowed from each client IP (0 = unlimited)
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        rate_limit: u32,
+
+        /// Run in offline mode (use cached data only, no network requests)
+        #[cfg(feature = "fetch")]
+        #[arg(long)]
+        offline: bool,
+
+        /// Cache directory for dynamic badge data
+        #[cfg(feature = "fetch")]
+        #[arg(long, default_value = ".mdfx-cache")]
+        cache_dir: String,
+
+        /// User-Agent for live badge API requests
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "UA")]
+        user_agent: Option<String>,
+
+        /// HTTP proxy for live badge requests (default: HTTPS_PROXY, HTTP_PROXY or ALL_PROXY)
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "URL")]
+        proxy: Option<String>,
+
+        /// PEM file of extra CA certificates to trust for live badge requests
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "FILE")]
+        ca_bundle: Option<PathBuf>,
+
+        /// Seconds to cache a live badge whose source failed (rendered as n/a); 0 disables
+        #[cfg(feature = "fetch")]
+        #[arg(long, value_name = "SECONDS", default_value_t = 300)]
+        failure_ttl: u64,
+    },
 }
 
-/// LSP subcommands
-#[cfg(feature = "lsp")]
+/// Generator subcommands
 #[derive(Subcommand)]
-enum LspCommands {
-    /// Start the LSP server (default)
+enum GenCommands {
+    /// Generate a syntax highlighting grammar for template syntax
     ///
-    /// The server communicates over stdio using the LSP protocol.
-    /// This is typically called by your editor, not manually.
-    Run {
-        /// Use stdio for communication (default, accepted for compatibility)
-        #[arg(long, default_value = "true")]
-        stdio: bool,
+    /// The TextMate grammar is a markdown injection grammar for VS Code,
+    /// Sublime Text, and other TextMate-compatible editors. The tree-sitter
+    /// grammar is a grammar.js for `tree-sitter generate`.
+    ///
+    /// Examples:
+    ///   mdfx gen grammar > mdfx.tmLanguage.json
+    ///   mdfx gen grammar --format tree-sitter -o grammar.js
+    Grammar {
+        /// Grammar format (textmate, tree-sitter)
+        #[arg(short, long, default_value = "textmate")]
+        format: String,
+
+        /// Output file (use - or omit for stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 
-    /// Install editor extension for LSP support
+    /// Export the component schema for external tooling
     ///
-    /// Automatically sets up the mdfx LSP extension for your editor.
-    /// Currently supports VS Code.
+    /// Lists every component with its arguments and parameters - types,
+    /// defaults, and allowed values - taken from the registry and the
+    /// component handlers. The JSON Schema describes a component call as
+    /// {"component", "args", "params"}; the catalog is a plain JSON listing.
     ///
     /// Examples:
-    ///   mdfx lsp install --editor vscode
-    ///   mdfx lsp install  # defaults to vscode
-    Install {
-        /// Editor to install extension for (vscode)
-        #[arg(short, long, default_value = "vscode")]
-        editor: String,
+    ///   mdfx gen schema > mdfx.schema.json
+    ///   mdfx gen schema --format catalog -o components.json
+    Schema {
+        /// Schema format (json-schema, catalog)
+        #[arg(short, long, default_value = "json-schema")]
+        format: String,
+
+        /// Output file (use - or omit for stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
-}
 
-fn main() {
-    let cli = Cli::parse();
+    /// Generate a release section from a Keep-a-Changelog file
+    ///
+    /// Renders the latest release as a version badge, its date, and a
+    /// "What's new" list - the same markdown `{{ui:latest-release/}}`
+    /// produces, for pasting into a README or release notes.
+    ///
+    /// Examples:
+    ///   mdfx gen changelog
+    ///   mdfx gen changelog --from docs/CHANGELOG.md --items 3 -o RELEASE.md
+    Changelog {
+        /// Changelog to read
+        #[arg(long, default_value = DEFAULT_CHANGELOG)]
+        from: PathBuf,
 
-    if let Err(e) = run(cli) {
-        eprintln!("{} {}", "Error:".red().bold(), e);
-        process::exit(1);
-    }
+        /// Number of entries to include (0 = all)
+        #[arg(long, default_value = "5")]
+        items: usize,
+
+        /// Output file (use - or omit for stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Generate a gallery of every style, frame, glyph, and component
+    ///
+    /// Renders each one with sample parameters using the SVG backend, so
+    /// what's available can be browsed instead of found by trial and error.
+    /// Components that read project files use sample files, and live badges
+    /// render as placeholders.
+    ///
+    /// Examples:
+    ///   mdfx gen gallery --out docs/gallery.md
+    ///   mdfx gen gallery --out gallery.html --assets-dir img
+    Gallery {
+        /// Gallery page to write
+        #[arg(long, default_value = "gallery.md")]
+        out: PathBuf,
+
+        /// Page format (markdown, html); defaults to html for .html files
+        #[arg(short, long)]
+        format: Option<String>,
+
+        /// Directory for the rendered SVGs, relative to the page
+        #[arg(long, default_value = "assets/gallery")]
+        assets_dir: String,
+    },
 }
 
-fn run(cli: Cli) -> Result<(), Error> {
-    let converter = Converter::new()?;
+/// Lint subcommands
+#[derive(Subcommand)]
+enum LintCommands {
+    /// Check that links resolve
+    ///
+    /// Checks intra-document anchors, relative file links, and asset
+    /// references such as generated SVGs in rendered markdown, reporting each
+    /// broken link as file:line:column. With --external, http(s) URLs are
+    /// checked too. Exits with an error if any link is broken.
+    ///
+    /// Examples:
+    ///   mdfx lint links README.md
+    ///   mdfx lint links docs/*.md --external --concurrency 16
+    ///   mdfx lint links README.md --format json
+    Links {
+        /// Markdown files to check
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Directory that links starting with / resolve against
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+
+        /// Report format (text, json)
+        #[arg(long, value_enum, default_value_t = LintFormat::Text)]
+        format: LintFormat,
+
+        /// Check http(s) links with HEAD requests
+        #[cfg(feature = "fetch")]
+        #[arg(long)]
+        external: bool,
 
-    match cli.command {
-        Commands::Convert {
-            style,
-            spacing,
-            text,
-        } => {
-            let result = converter.convert_with_spacing(&text, &style, spacing)?;
-            println!("{}", result);
-        }
+        /// Maximum number of external requests in flight
+        #[cfg(feature = "fetch")]
+        #[arg(long, default_value = "8")]
+        concurrency: usize,
 
-        Commands::List {
-            resource,
-            category,
-            samples,
-            filter,
-        } => {
-            let registry = Registry::new()?;
-            match resource.as_str() {
-                "styles" => list_styles(&converter, category, samples)?,
-                "components" => list_components(&registry, filter)?,
+        /// Timeout for each external request, in seconds
+        #[cfg(feature = "fetch")]
+        #[arg(long, default_value = "10", value_name = "SECONDS")]
+        timeout: u64,
+    },
+
+    /// Check for constructs a target can't render
+    ///
+    /// Flags raw HTML the target strips, data: URIs, SVG and external images
+    /// where unsupported, styled Unicode on ASCII-only targets, and long
+    /// heading anchors. Each problem has a rule code (C001...) that can be
+    /// suppressed with <!-- mdfx-lint-disable-next-line CODE --> or a
+    /// <!-- mdfx-lint-disable CODE --> ... <!-- mdfx-lint-enable CODE --> block.
+    ///
+    /// Examples:
+    ///   mdfx lint compat --target pypi README.md
+    ///   mdfx lint compat --target gitlab docs/*.md --deny-warnings
+    Compat {
+        /// Markdown files to check
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Target platform (github, local, npm, gitlab, pypi, crates-io, docs-site, mkdocs, confluence, jira)
+        #[arg(short, long, default_value = "github")]
+        target: String,
+
+        /// Report format (text, json)
+        #[arg(long, value_enum, default_value_t = LintFormat::Text)]
+        format: LintFormat,
+
+        /// Fail on warnings as well as errors
+        #[arg(long)]
+        deny_warnings: bool,
+    },
+}
+
+/// Palette subcommands
+#[derive(Subcommand)]
+enum PaletteCommands {
+    /// Convert design tokens into an mdfx palette
+    ///
+    /// Reads the colors of a Figma Tokens (Tokens Studio) or W3C token JSON
+    /// file, the `colors` of a tailwind.config.js, or CSS custom properties,
+    /// and writes the palette JSON used by --palette and the `palette`
+    /// section of .mdfx.json. Token paths become names like `brand-500`;
+    /// tokens that aren't colors are skipped with a warning.
+    ///
+    /// Examples:
+    ///   mdfx palette import --from figma-tokens.json -o palette.json
+    ///   mdfx palette import --from tailwind.config.js
+    ///   mdfx palette import --from theme.css --format css
+    Import {
+        /// Token file to read
+        #[arg(long)]
+        from: PathBuf,
+
+        /// Token format (figma, tailwind, css); detected from the extension by default
+        #[arg(short, long)]
+        format: Option<String>,
+
+        /// Output file (use - or omit for stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Generate a palette from a base color
+    ///
+    /// Derives harmonious colors by rotating the base's hue (analogous: 30°
+    /// either side, triadic: 120° apart) or a 50-900 scale of tints and
+    /// shades, and writes the palette JSON used by --palette.
+    ///
+    /// Examples:
+    ///   mdfx palette generate --base 2B6CB0 --scheme shades -o palette.json
+    ///   mdfx palette generate --base FF5500 --scheme triadic --name brand
+    Generate {
+        /// Base color as 6-digit hex (e.g., 2B6CB0) or a palette name (e.g., cobalt)
+        #[arg(long)]
+        base: String,
+
+        /// Scheme (analogous, triadic, shades)
+        #[arg(long, default_value = "analogous")]
+        scheme: String,
+
+        /// Palette name of the base color; derived colors extend it
+        #[arg(long, default_value = "primary")]
+        name: String,
+
+        /// Output file (use - or omit for stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Asset subcommands
+#[derive(Subcommand)]
+enum AssetsCommands {
+    /// Compare two asset directories
+    ///
+    /// Reports assets that were added, removed, or rendered differently,
+    /// e.g. between the output of two mdfx versions. Assets are matched by
+    /// filename, by content, and by the primitive recorded in manifest.json.
+    ///
+    /// Examples:
+    ///   mdfx assets diff old/assets/mdfx assets/mdfx
+    ///   mdfx assets diff old/ new/ --html diff.html
+    Diff {
+        /// Directory with the old assets
+        old: PathBuf,
+
+        /// Directory with the new assets
+        new: PathBuf,
+
+        /// Write a side-by-side HTML preview of the differences
+        #[arg(long, value_name = "FILE")]
+        html: Option<PathBuf>,
+    },
+
+    /// Generate a key pair for signing manifest.json
+    ///
+    /// Writes an unencrypted minisign secret key and its public key
+    /// (same name, .pub extension). Commit the public key; store the secret
+    /// key as a CI secret.
+    ///
+    /// Examples:
+    ///   mdfx assets keygen
+    ///   mdfx assets keygen -o ci/mdfx.key
+    #[cfg(feature = "sign")]
+    Keygen {
+        /// Secret key file to write
+        #[arg(short, long, default_value = "mdfx.key")]
+        output: PathBuf,
+
+        /// Overwrite existing key files
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Sign manifest.json with a minisign secret key
+    ///
+    /// Writes manifest.json.minisig next to the manifest. The key is read
+    /// from --key, or from the MDFX_SIGNING_KEY environment variable.
+    /// Check the signature with `mdfx verify --public-key` or
+    /// `minisign -Vm manifest.json -p mdfx.pub`.
+    ///
+    /// Examples:
+    ///   mdfx assets sign --key mdfx.key
+    ///   MDFX_SIGNING_KEY="$(cat mdfx.key)" mdfx assets sign --assets-dir docs/assets
+    #[cfg(feature = "sign")]
+    Sign {
+        /// Assets directory containing manifest.json
+        #[arg(long, default_value = "assets/mdfx")]
+        assets_dir: PathBuf,
+
+        /// Secret key file
+        #[arg(long, value_name = "FILE")]
+        key: Option<PathBuf>,
+    },
+}
+
+/// Badge subcommands
+#[derive(Subcommand)]
+enum BadgesCommands {
+    /// Render every badge in a JSON or YAML spec file to SVG
+    ///
+    /// The spec maps file names to badges. Each badge has a `type` (tech,
+    /// version, license) and the fields of its badgefx builder; a badge
+    /// named `rust` is written to `<out>/rust.svg`.
+    ///
+    /// Example badges.yaml:
+    ///   rust: { type: tech, name: rust, style: flat-square }
+    ///   release: { type: version, version: 1.2.0 }
+    ///   license: { type: license, license: MIT }
+    ///
+    /// Examples:
+    ///   mdfx badges render badges.yaml
+    ///   mdfx badges render badges.json --out assets/
+    Render {
+        /// Spec file (.json, .yaml, or .yml)
+        spec: PathBuf,
+
+        /// Directory for the rendered SVGs
+        #[arg(short, long, default_value = "assets/badges")]
+        out: PathBuf,
+    },
+}
+
+/// LSP subcommands
+#[cfg(feature = "lsp")]
+#[derive(Subcommand)]
+enum LspCommands {
+    /// Start the LSP server (default)
+    ///
+    /// The server communicates over stdio using the LSP protocol.
+    /// This is typically called by your editor, not manually.
+    Run {
+        /// Use stdio for communication (default, accepted for compatibility)
+        #[arg(long, default_value = "true")]
+        stdio: bool,
+    },
+
+    /// Install editor extension for LSP support
+    ///
+    /// Automatically sets up the mdfx LSP extension for your editor.
+    /// Supports VS Code, Neovim (nvim-lspconfig), and Helix.
+    ///
+    /// Examples:
+    ///   mdfx lsp install --editor vscode
+    ///   mdfx lsp install  # defaults to vscode
+    ///   mdfx lsp install --editor neovim
+    ///   mdfx lsp install --editor helix --print-config
+    Install {
+        /// Editor to install extension for (vscode, neovim, helix)
+        #[arg(short, long, default_value = "vscode")]
+        editor: String,
+
+        /// Print the editor configuration instead of installing it
+        #[arg(long)]
+        print_config: bool,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    logging::init(cli.verbose, cli.trace, cli.quiet, cli.log_format);
+
+    let error_format = cli.error_format;
+    if let Err(e) = run(cli) {
+        process::exit(exit::report(&e, error_format));
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Error> {
+    let error_format = cli.error_format;
+    let converter = Converter::new()?;
+
+    match cli.command {
+        Commands::Convert {
+            style,
+            spacing,
+            fallback,
+            seed,
+            list_mapping,
+            copy,
+            text,
+        } => {
+            let result = if list_mapping {
+                style_mapping(&converter, &style)?
+            } else {
+                let converter = match fallback {
+                    Some(policy) => converter.with_fallback(parse_fallback(&policy)?),
+                    None => converter,
+                };
+                let converter = converter.with_seed(seed.unwrap_or_default());
+                convert_text(&converter, &style, spacing, text)?
+            };
+            print!("{}", result);
+            if copy {
+                copy_to_clipboard(result.trim_end_matches('\n'))?;
+            }
+        }
+
+        Commands::List {
+            resource,
+            category,
+            samples,
+            filter,
+        } => {
+            let registry = Registry::shared()?;
+            match resource.as_str() {
+                "styles" => list_styles(&converter, category, samples)?,
+                "components" => list_components(&registry, filter)?,
                 "glyphs" => list_glyphs(&registry, filter)?,
                 "frames" => list_frames(&registry, filter)?,
                 "palette" => list_palette(&registry, filter)?,
@@ -396,6 +1245,7 @@ fn run(cli: Cli) -> Result<(), Error> {
             backend,
             assets_dir,
             assets_prefix,
+            asset_namespace,
             palette,
             config,
             #[cfg(feature = "fetch")]
@@ -404,61 +1254,244 @@ fn run(cli: Cli) -> Result<(), Error> {
             refresh,
             #[cfg(feature = "fetch")]
             cache_dir,
+            #[cfg(feature = "fetch")]
+            user_agent,
+            #[cfg(feature = "fetch")]
+            proxy,
+            #[cfg(feature = "fetch")]
+            ca_bundle,
+            #[cfg(feature = "fetch")]
+            failure_ttl,
+            #[cfg(feature = "fetch")]
+            cache_bust,
+            #[cfg(feature = "fetch")]
+            cache_seconds,
+            placeholders,
+            inline_code,
+            process_html,
+            commonmark,
+            stats,
+            profile,
+            image_dimensions,
+            inline,
+            publish,
+            copy,
+            reproducible,
         } => {
+            #[cfg(feature = "fetch")]
+            if reproducible && refresh {
+                return Err(Error::ParseError(
+                    "--reproducible can't be combined with --refresh: live data must come from the cache snapshot".to_string(),
+                ));
+            }
+
             #[cfg(feature = "fetch")]
             let fetch_config = Some(mdfx_fetch::FetchConfig {
                 cache_dir: std::path::PathBuf::from(&cache_dir),
                 default_ttl: 3600,
-                offline,
+                failure_ttl,
+                offline: offline || reproducible,
                 refresh,
+                user_agent,
+                proxy,
+                ca_bundle,
             });
             #[cfg(not(feature = "fetch"))]
             let fetch_config: Option<()> = None;
 
+            #[cfg(feature = "fetch")]
+            let live_cache = LiveBadgeCache {
+                bust: cache_bust,
+                cache_seconds,
+            };
+            #[cfg(not(feature = "fetch"))]
+            let live_cache = LiveBadgeCache::default();
+
             process_file(
                 input,
                 output,
                 in_place,
                 &target,
                 backend.as_deref(),
-                &assets_dir,
+                assets_dir.as_deref(),
                 assets_prefix.as_deref(),
+                asset_namespace.as_deref(),
                 palette.as_deref(),
                 config.as_deref(),
                 fetch_config,
+                stats,
+                profile.as_deref(),
+                image_dimensions.as_deref(),
+                inline.as_deref(),
+                live_cache,
+                placeholders,
+                inline_code,
+                process_html,
+                commonmark,
+                publish,
+                copy,
+                reproducible,
+            )?;
+        }
+
+        Commands::Fmt {
+            input,
+            output,
+            in_place,
+            check,
+            closers,
+            config,
+        } => {
+            format_file(
+                input,
+                output,
+                in_place,
+                check,
+                closers.as_deref(),
+                config.as_deref(),
+            )?;
+        }
+
+        Commands::Check {
+            inputs,
+            config,
+            deny_warnings,
+            #[cfg(feature = "fetch")]
+            offline,
+            #[cfg(feature = "fetch")]
+            cache_dir,
+            #[cfg(feature = "fetch")]
+            user_agent,
+            #[cfg(feature = "fetch")]
+            proxy,
+            #[cfg(feature = "fetch")]
+            ca_bundle,
+            #[cfg(feature = "fetch")]
+            failure_ttl,
+        } => {
+            #[cfg(feature = "fetch")]
+            let fetch_config = Some(mdfx_fetch::FetchConfig {
+                cache_dir: std::path::PathBuf::from(&cache_dir),
+                default_ttl: 3600,
+                failure_ttl,
+                offline,
+                refresh: false,
+                user_agent,
+                proxy,
+                ca_bundle,
+            });
+            #[cfg(not(feature = "fetch"))]
+            let fetch_config: Option<()> = None;
+
+            check_files(
+                &inputs,
+                config.as_deref(),
+                deny_warnings,
+                error_format,
+                fetch_config,
             )?;
         }
 
+        Commands::TestSnippets {
+            inputs,
+            config,
+            managed,
+            offline,
+        } => {
+            let mut parser = TemplateParser::new()?;
+            let config = match config {
+                Some(path) => Some(MdfxConfig::load(&path)?),
+                None => MdfxConfig::discover(),
+            };
+            if let Some(ref cfg) = config {
+                parser.load_config(cfg);
+            }
+            load_project_files(&mut parser, config.as_ref())?;
+            doctest::test_snippets(&inputs, &parser, managed, offline)?;
+        }
+
         Commands::Completions { shell } => {
             let mut cmd = Cli::command();
             generate(shell, &mut cmd, "mdfx", &mut io::stdout());
         }
 
-        Commands::Verify { assets_dir } => {
-            verify_assets(&assets_dir)?;
+        Commands::Verify {
+            assets_dir,
+            asset_namespace,
+            public_key,
+            require_signature,
+        } => {
+            verify_assets(
+                &assets_dir,
+                asset_namespace.as_deref(),
+                public_key.as_deref(),
+                require_signature,
+            )?;
         }
 
         Commands::Clean {
             assets_dir,
+            asset_namespace,
             dry_run,
             scan,
+            keep_newer_than,
+            keep_git_history,
+            keep,
         } => {
-            clean_assets(&assets_dir, dry_run, scan.as_deref())?;
+            let mut retention = Retention::default();
+            if let Some(days) = keep_newer_than {
+                retention = retention.keep_newer_than(days);
+            }
+            if keep_git_history {
+                retention = retention.keep_git_history()?;
+            }
+            if let Some(path) = keep {
+                retention = retention.keep_listed(&path)?;
+            }
+            clean_assets(
+                &assets_dir,
+                asset_namespace.as_deref(),
+                dry_run,
+                scan.as_deref(),
+                &retention,
+            )?;
         }
 
+        Commands::Assets(assets_cmd) => match assets_cmd {
+            AssetsCommands::Diff { old, new, html } => {
+                diff_assets(&old, &new, html)?;
+            }
+            #[cfg(feature = "sign")]
+            AssetsCommands::Keygen { output, force } => {
+                generate_signing_key(&output, force)?;
+            }
+            #[cfg(feature = "sign")]
+            AssetsCommands::Sign { assets_dir, key } => {
+                sign_manifest(&assets_dir, key.as_deref())?;
+            }
+        },
+
         Commands::Build {
             input,
             output_dir,
             targets,
             all_targets,
+            jobs,
             palette,
+            i18n,
+            default_lang,
+            inline,
         } => {
             build_multi_target(
                 &input,
                 &output_dir,
                 targets.as_deref(),
                 all_targets,
+                jobs,
                 palette.as_deref(),
+                i18n.as_deref(),
+                &default_lang,
+                inline.as_deref(),
             )?;
         }
 
@@ -471,6 +1504,7 @@ fn run(cli: Cli) -> Result<(), Error> {
             assets_prefix,
             palette,
             debounce,
+            inline,
             config,
         } => {
             watch_file(
@@ -478,51 +1512,303 @@ fn run(cli: Cli) -> Result<(), Error> {
                 output,
                 &target,
                 backend.as_deref(),
-                &assets_dir,
+                assets_dir.as_deref(),
                 assets_prefix.as_deref(),
                 palette.as_deref(),
                 debounce,
+                inline.as_deref(),
                 config.as_deref(),
             )?;
         }
 
-        #[cfg(feature = "lsp")]
-        Commands::Lsp(lsp_cmd) => match lsp_cmd {
-            LspCommands::Run { stdio: _ } => {
-                // Run the LSP server using tokio runtime
-                // stdio is always true (only mode supported)
-                tokio::runtime::Runtime::new()
-                    .expect("Failed to create tokio runtime")
-                    .block_on(lsp::run_lsp_server());
+        Commands::Run { pipeline, config } => {
+            run_pipeline(pipeline.as_deref(), config.as_deref())?;
+        }
+
+        Commands::Gen(gen_cmd) => match gen_cmd {
+            GenCommands::Grammar { format, output } => {
+                generate_grammar(&format, output)?;
+            }
+            GenCommands::Schema { format, output } => {
+                generate_schema(&format, output)?;
+            }
+            GenCommands::Changelog {
+                from,
+                items,
+                output,
+            } => {
+                generate_changelog(&from, items, output)?;
             }
-            LspCommands::Install { editor } => {
-                install_lsp_extension(&editor)?;
+            GenCommands::Gallery {
+                out,
+                format,
+                assets_dir,
+            } => {
+                generate_gallery(&out, format.as_deref(), &assets_dir)?;
             }
         },
-    }
-
-    Ok(())
-}
 
-fn list_styles(
-    converter: &Converter,
-    category: Option<String>,
-    show_samples: bool,
-) -> Result<(), Error> {
-    println!("{}", "Available styles:".bold());
-    println!();
+        Commands::Lint(lint_cmd) => match lint_cmd {
+            LintCommands::Links {
+                inputs,
+                root,
+                format,
+                #[cfg(feature = "fetch")]
+                external,
+                #[cfg(feature = "fetch")]
+                concurrency,
+                #[cfg(feature = "fetch")]
+                timeout,
+            } => {
+                lint::lint_links(
+                    &inputs,
+                    &root,
+                    format,
+                    #[cfg(feature = "fetch")]
+                    external.then_some(lint::ExternalCheck {
+                        concurrency,
+                        timeout: Duration::from_secs(timeout),
+                    }),
+                )?;
+            }
+            LintCommands::Compat {
+                inputs,
+                target,
+                format,
+                deny_warnings,
+            } => {
+                let target = get_target(&target).ok_or_else(|| {
+                    Error::ParseError(format!(
+                        "Unknown target '{}'. Available: {}",
+                        target,
+                        available_targets().join(", ")
+                    ))
+                })?;
+                lint::lint_compat(&inputs, target.as_ref(), format, deny_warnings)?;
+            }
+        },
 
-    let styles = converter.list_styles();
+        Commands::Palette(palette_cmd) => match palette_cmd {
+            PaletteCommands::Import {
+                from,
+                format,
+                output,
+            } => {
+                import_palette(&from, format.as_deref(), output)?;
+            }
+            PaletteCommands::Generate {
+                base,
+                scheme,
+                name,
+                output,
+            } => {
+                generate_palette(&base, &scheme, &name, output)?;
+            }
+        },
 
-    // Group by category
-    let categories = [
-        (StyleCategory::Bold, "Bold & Impactful"),
-        (StyleCategory::Boxed, "Boxed"),
-        (StyleCategory::Technical, "Technical & Code"),
-        (StyleCategory::Elegant, "Subtle & Elegant"),
-    ];
+        Commands::Badges(badges_cmd) => match badges_cmd {
+            BadgesCommands::Render { spec, out } => {
+                render_badges(&spec, &out)?;
+            }
+        },
 
-    for (cat, label) in categories {
+        #[cfg(feature = "serve")]
+        Commands::Serve {
+            host,
+            port,
+            config,
+            signed,
+            sign,
+            allow_source,
+            allow_icon_host,
+            allow_param,
+            rate_limit,
+            #[cfg(feature = "fetch")]
+            offline,
+            #[cfg(feature = "fetch")]
+            cache_dir,
+            #[cfg(feature = "fetch")]
+            user_agent,
+            #[cfg(feature = "fetch")]
+            proxy,
+            #[cfg(feature = "fetch")]
+            ca_bundle,
+            #[cfg(feature = "fetch")]
+            failure_ttl,
+        } => {
+            #[cfg(feature = "fetch")]
+            let fetch_config = Some(mdfx_fetch::FetchConfig {
+                cache_dir: std::path::PathBuf::from(&cache_dir),
+                default_ttl: 3600,
+                failure_ttl,
+                offline,
+                refresh: false,
+                user_agent,
+                proxy,
+                ca_bundle,
+            });
+            #[cfg(not(feature = "fetch"))]
+            let fetch_config: Option<()> = None;
+
+            if let Some(url) = sign {
+                let guard = serve::Guard::default().with_key(serve_key()?);
+                println!("{}", guard.sign(&url).unwrap_or(url));
+                return Ok(());
+            }
+
+            let mut guard = serve::Guard::default();
+            if signed {
+                guard = guard.with_key(serve_key()?);
+            }
+            if !allow_source.is_empty() {
+                guard = guard.allow_sources(allow_source);
+            }
+            if !allow_icon_host.is_empty() {
+                guard = guard.allow_icon_hosts(allow_icon_host);
+            }
+            if !allow_param.is_empty() {
+                guard = guard.allow_params(allow_param);
+            }
+            if rate_limit > 0 {
+                guard = guard.rate_limit(rate_limit);
+            }
+
+            serve_badges(&host, port, config.as_deref(), guard, fetch_config)?;
+        }
+
+        #[cfg(feature = "lsp")]
+        Commands::Lsp(lsp_cmd) => match lsp_cmd {
+            LspCommands::Run { stdio: _ } => {
+                // Run the LSP server using tokio runtime
+                // stdio is always true (only mode supported)
+                tokio::runtime::Runtime::new()
+                    .expect("Failed to create tokio runtime")
+                    .block_on(lsp::run_lsp_server());
+            }
+            LspCommands::Install {
+                editor,
+                print_config,
+            } => {
+                install_lsp_extension(&editor, print_config)?;
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Convert text (or each line of stdin) into every requested style, one
+/// variant after another
+fn convert_text(
+    converter: &Converter,
+    styles: &[String],
+    spacing: usize,
+    text: Option<String>,
+) -> Result<String, Error> {
+    let text = match text {
+        Some(text) => text,
+        None => {
+            let mut buffer = String::new();
+            io::stdin()
+                .read_to_string(&mut buffer)
+                .map_err(Error::IoError)?;
+            buffer
+        }
+    };
+
+    // Fail on an unknown style before printing any variant
+    for style in styles {
+        converter.get_style(style)?;
+    }
+
+    let mut result = String::new();
+    for style in styles {
+        for line in text.lines() {
+            result.push_str(&converter.convert_with_spacing(line, style, spacing)?);
+            result.push('\n');
+        }
+    }
+    Ok(result)
+}
+
+/// Parse a `--fallback` policy name
+fn parse_fallback(policy: &str) -> Result<Fallback, Error> {
+    Fallback::parse(policy).ok_or_else(|| {
+        Error::ParseError(format!(
+            "Unknown fallback '{}'. Available: passthrough, skip, error, closest",
+            policy
+        ))
+    })
+}
+
+/// Each mapped character and its styled form in every requested style,
+/// one tab-separated row per character
+fn style_mapping(converter: &Converter, styles: &[String]) -> Result<String, Error> {
+    let styles = styles
+        .iter()
+        .map(|name| converter.get_style(name))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let chars: BTreeSet<char> = styles
+        .iter()
+        .flat_map(|style| style.mappings.keys().copied())
+        .collect();
+
+    let mut result = String::new();
+    for c in chars {
+        result.push(c);
+        for style in &styles {
+            result.push('\t');
+            result.push_str(&style.convert_char_to_string(c));
+        }
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+/// Place text on the system clipboard
+#[cfg(feature = "clipboard")]
+fn copy_to_clipboard(text: &str) -> Result<(), Error> {
+    let clipboard_error = |e: arboard::Error| {
+        Error::IoError(io::Error::other(format!("Clipboard unavailable: {}", e)))
+    };
+    let mut clipboard = arboard::Clipboard::new().map_err(clipboard_error)?;
+    clipboard.set_text(text).map_err(clipboard_error)?;
+    info!(
+        status = "Copied:",
+        "{} characters to clipboard",
+        text.chars().count()
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn copy_to_clipboard(_text: &str) -> Result<(), Error> {
+    Err(Error::IoError(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this mdfx was built without the `clipboard` feature",
+    )))
+}
+
+fn list_styles(
+    converter: &Converter,
+    category: Option<String>,
+    show_samples: bool,
+) -> Result<(), Error> {
+    println!("{}", "Available styles:".bold());
+    println!();
+
+    let styles = converter.list_styles();
+
+    // Group by category
+    let categories = [
+        (StyleCategory::Bold, "Bold & Impactful"),
+        (StyleCategory::Boxed, "Boxed"),
+        (StyleCategory::Technical, "Technical & Code"),
+        (StyleCategory::Elegant, "Subtle & Elegant"),
+    ];
+
+    for (cat, label) in categories {
         // Filter by category if specified
         if let Some(ref filter) = category {
             let cat_str = format!("{:?}", cat).to_lowercase();
@@ -755,175 +2041,916 @@ fn list_palette(registry: &Registry, filter: Option<String>) -> Result<(), Error
         for (name, hex) in other {
             println!("  {} → #{}", name.green(), hex.cyan());
         }
-        println!();
+        println!();
+    }
+
+    println!("Total: {} colors", entries.len().to_string().yellow());
+    println!();
+    println!(
+        "{}",
+        "Usage: {{ui:swatch:name/}} or color params like bg=name".dimmed()
+    );
+
+    Ok(())
+}
+
+/// Parse an `--inline` value; `None` lets each target pick its form
+fn parse_inline_mode(value: &str) -> Result<Option<InlineMode>, Error> {
+    match value {
+        "auto" => Ok(None),
+        "svg" => Ok(Some(InlineMode::Svg)),
+        "data-uri" => Ok(Some(InlineMode::DataUri)),
+        other => Err(Error::ParseError(format!(
+            "Unknown inline mode '{}'. Available: svg, data-uri",
+            other
+        ))),
+    }
+}
+
+/// Inline form to use for `target`: the requested one if the target displays
+/// it, or with no request the first form it displays, preferring data URIs
+fn inline_mode_for(requested: Option<InlineMode>, target: &dyn Target) -> Option<InlineMode> {
+    match requested {
+        Some(mode) => Some(mode).filter(|&mode| target.supports_inline(mode)),
+        None => [InlineMode::DataUri, InlineMode::Svg]
+            .into_iter()
+            .find(|&mode| target.supports_inline(mode)),
+    }
+}
+
+/// Clock for relative dates in live badges: `SOURCE_DATE_EPOCH` when set,
+/// otherwise the system clock, or none at all for `--reproducible` builds
+fn build_clock(reproducible: bool) -> Result<mdfx::Clock, Error> {
+    match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(epoch) => epoch.trim().parse().map(mdfx::Clock::Fixed).map_err(|_| {
+            Error::ParseError(format!(
+                "SOURCE_DATE_EPOCH must be a Unix timestamp in seconds, got '{}'",
+                epoch
+            ))
+        }),
+        Err(_) if reproducible => Ok(mdfx::Clock::Unavailable),
+        Err(_) => Ok(mdfx::Clock::System),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn process_file(
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+    in_place: bool,
+    target_name: &str,
+    backend_override: Option<&str>,
+    assets_dir: Option<&str>,
+    assets_prefix: Option<&str>,
+    asset_namespace: Option<&str>,
+    palette_path: Option<&std::path::Path>,
+    config_path: Option<&std::path::Path>,
+    #[cfg(feature = "fetch")] fetch_config: Option<mdfx_fetch::FetchConfig>,
+    #[cfg(not(feature = "fetch"))] _fetch_config: Option<()>,
+    stats: Option<StatsFormat>,
+    profile: Option<&std::path::Path>,
+    image_dimensions: Option<&str>,
+    inline: Option<&str>,
+    live_cache: LiveBadgeCache,
+    placeholders: bool,
+    inline_code: bool,
+    process_html: bool,
+    commonmark: bool,
+    publish: bool,
+    copy: bool,
+    reproducible: bool,
+) -> Result<(), Error> {
+    let mut report = ProcessReport::start();
+    if let Some(namespace) = asset_namespace {
+        check_asset_namespace(namespace)?;
+    }
+
+    // Resolve target (with auto-detection support)
+    let mut target: Box<dyn Target> = if target_name == "auto" {
+        // Auto-detect from output path
+        let detected = output
+            .as_ref()
+            .and_then(|p| detect_target_from_path(p))
+            .or_else(|| input.as_ref().and_then(|p| detect_target_from_path(p)));
+
+        if let Some(name) = detected {
+            info!("Auto-detected target: {}", name.green());
+            // Safe: detect_target_from_path only returns known target names
+            get_target(name).expect("detected target should be valid")
+        } else {
+            info!("Could not auto-detect target, using github");
+            // Safe: "github" is a builtin target
+            get_target("github").expect("github target should exist")
+        }
+    } else {
+        get_target(target_name).ok_or_else(|| {
+            Error::ParseError(format!(
+                "Unknown target '{}'. Available: {}",
+                target_name,
+                available_targets().join(", ")
+            ))
+        })?
+    };
+
+    // Load config file (explicit path or auto-discover)
+    let config = if let Some(config_file) = config_path {
+        Some(MdfxConfig::load(config_file)?)
+    } else {
+        MdfxConfig::discover()
+    };
+    let overrides = config
+        .as_ref()
+        .map(|cfg| cfg.target_overrides(target.name()))
+        .unwrap_or_default();
+
+    // Determine backend: explicit override > .mdfx.json target setting > target's preferred backend
+    let backend_type = if let Some(backend) = backend_override {
+        match backend {
+            "shields" => BackendType::Shields,
+            "svg" => BackendType::Svg,
+            _ => {
+                return Err(Error::ParseError(format!(
+                    "Unknown backend '{}'. Available: svg, shields",
+                    backend
+                )));
+            }
+        }
+    } else if publish || inline.is_some() {
+        BackendType::Svg
+    } else {
+        overrides
+            .backend
+            .unwrap_or_else(|| target.preferred_backend())
+    };
+    let assets_dir = assets_dir
+        .or(overrides.assets_dir.as_deref())
+        .unwrap_or(DEFAULT_ASSETS_DIR);
+
+    let image_dimensions = match image_dimensions {
+        None => target.image_dimensions(),
+        Some("none") => ImageDimensions::None,
+        Some("html") => ImageDimensions::Html,
+        Some("suffix") => ImageDimensions::Suffix,
+        Some(other) => {
+            return Err(Error::ParseError(format!(
+                "Unknown image dimensions '{}'. Available: none, html, suffix",
+                other
+            )));
+        }
+    };
+
+    let inline_mode = match inline {
+        Some(_) if publish => {
+            return Err(Error::ParseError(
+                "--inline can't be combined with --publish: inline SVGs have no files to upload"
+                    .to_string(),
+            ));
+        }
+        Some(_) if backend_type != BackendType::Svg => {
+            return Err(Error::ParseError(
+                "--inline needs the svg backend".to_string(),
+            ));
+        }
+        Some(value) => {
+            let requested = parse_inline_mode(value)?;
+            let mode = inline_mode_for(requested, target.as_ref());
+            if mode.is_none() {
+                let form = match requested {
+                    Some(InlineMode::Svg) => "raw <svg> markup",
+                    Some(InlineMode::DataUri) => "data: URI images",
+                    None => "inline SVGs",
+                };
+                return Err(Error::ParseError(format!(
+                    "Target '{}' doesn't display {}; drop --inline to write asset files",
+                    target.name(),
+                    form
+                )));
+            }
+            mode
+        }
+        None => None,
+    };
+
+    // Create the appropriate backend
+    let mut parser = match backend_type {
+        BackendType::Svg => {
+            let backend = if let Some(mode) = inline_mode {
+                SvgBackend::inline(mode)
+            } else if let Some(prefix) = assets_prefix {
+                SvgBackend::with_prefix(assets_dir, prefix)
+            } else {
+                SvgBackend::new(assets_dir)
+            };
+            let filenames = config.as_ref().map(|cfg| cfg.filenames).unwrap_or_default();
+            let mut backend = backend
+                .with_image_dimensions(image_dimensions)
+                .with_filename_scheme(filenames);
+            if let Some(namespace) = asset_namespace {
+                backend = backend.with_namespace(namespace);
+            }
+            TemplateParser::with_backend(Box::new(backend))?
+        }
+        BackendType::Shields => {
+            let backend = ShieldsBackend::new()?.with_live_cache(live_cache);
+            TemplateParser::with_backend(Box::new(backend))?
+        }
+        BackendType::PlainText => TemplateParser::with_backend(Box::new(PlainTextBackend::new()))?,
+    };
+
+    if let Some(ref cfg) = config {
+        let partial_count = cfg.partials.len();
+        let palette_count = cfg.palette.len();
+
+        if partial_count > 0 || palette_count > 0 {
+            info!(
+                "Loaded config: {} partial(s), {} color(s)",
+                partial_count, palette_count
+            );
+        }
+
+        parser.load_config(cfg);
+        target.configure(cfg);
+    }
+    load_project_files(&mut parser, config.as_ref())?;
+    parser.set_target(target.as_ref());
+    if !overrides.palette.is_empty() {
+        parser.extend_palette(overrides.palette.clone());
+    }
+
+    // Load custom palette if provided (overrides config palette)
+    if let Some(palette_file) = palette_path {
+        let palette_content = fs::read_to_string(palette_file).map_err(Error::IoError)?;
+        let custom_palette: std::collections::HashMap<String, String> =
+            serde_json::from_str(&palette_content).map_err(|e| {
+                Error::ParseError(format!(
+                    "Failed to parse palette file '{}': {}",
+                    palette_file.display(),
+                    e
+                ))
+            })?;
+        info!(
+            "Loaded {} custom color(s) from {}",
+            custom_palette.len(),
+            palette_file.display()
+        );
+        parser.extend_palette(custom_palette);
+    }
+
+    parser.set_placeholders(placeholders);
+    let mut options = parser.options().clone();
+    options.process_inline_code = inline_code;
+    options.preserve_html_blocks = !process_html;
+    options.commonmark |= commonmark;
+    options.clock = build_clock(reproducible)?;
+    parser.set_options(options);
+
+    // Set up fetch context for dynamic badges (if fetch feature is enabled)
+    #[cfg(feature = "fetch")]
+    if let Some(config) = fetch_config {
+        match mdfx::FetchContext::new(config) {
+            Ok(ctx) => {
+                if !ctx.fetcher().config().offline {
+                    info!("Dynamic badges enabled (use --offline to disable)");
+                } else {
+                    info!("Dynamic badges in offline mode (cache only)");
+                }
+                parser.set_fetch_context(ctx);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to initialize fetch: {}", e);
+            }
+        }
+    }
+
+    let stats_collector = stats.map(|_| {
+        let collector = Arc::new(StatsCollector::new());
+        parser.add_observer(collector.clone());
+        collector
+    });
+    let profiler = profile.map(|_| {
+        let profiler = Arc::new(Profiler::new());
+        parser.add_observer(profiler.clone());
+        profiler
+    });
+    report.mark("setup");
+
+    // Read input
+    let content = if let Some(ref path) = input {
+        if path.to_str() == Some("-") {
+            // Read from stdin
+            let mut buffer = String::new();
+            io::stdin()
+                .read_to_string(&mut buffer)
+                .map_err(Error::IoError)?;
+            buffer
+        } else {
+            // Read from file
+            fs::read_to_string(path).map_err(Error::IoError)?
+        }
+    } else {
+        // No input specified, read from stdin
+        let mut buffer = String::new();
+        io::stdin()
+            .read_to_string(&mut buffer)
+            .map_err(Error::IoError)?;
+        buffer
+    };
+
+    report.mark("read");
+
+    // Process content with asset collection
+    let mut processed_result = parser.process_with_assets(&content)?;
+    report.mark("parse");
+
+    if publish && !processed_result.assets.is_empty() {
+        // Upload assets instead of writing them locally
+        let summary = publish_assets(&mut processed_result, config.as_ref())?;
+        report.assets.written = summary.written;
+        report.assets.skipped = summary.skipped;
+        report.assets.deduped += summary.deduped;
+    } else if !processed_result.assets.is_empty() {
+        // Write any file-based assets to disk
+        let mut disk = DiskFs;
+        let summary = processed_result.write_assets(&mut disk)?;
+        let (written, skipped) = (summary.written, summary.skipped);
+        report.assets.deduped += summary.deduped;
+
+        // Write manifest.json for SVG backend
+        if matches!(backend_type, BackendType::Svg) {
+            let mut manifest = AssetManifest::new("svg", assets_dir);
+            for asset in &processed_result.assets {
+                manifest.add_rendered(asset);
+            }
+            if let Some(namespace) = asset_namespace {
+                manifest.set_namespace(namespace);
+            }
+            let manifest_path = format!("{}/manifest.json", assets_dir);
+            // Other namespaces' assets stay listed for their own clean/verify
+            if let Ok(previous) = AssetManifest::load(std::path::Path::new(&manifest_path)) {
+                manifest.keep_other_namespaces(previous, asset_namespace);
+            }
+            if reproducible {
+                manifest.make_reproducible();
+            }
+            manifest.write_to(&mut disk, std::path::Path::new(&manifest_path))?;
+        }
+
+        // Report asset generation results
+        if written > 0 || skipped > 0 {
+            let mut parts = Vec::new();
+            if written > 0 {
+                parts.push(format!("{} written", written));
+            }
+            if skipped > 0 {
+                parts.push(format!("{} unchanged", skipped));
+            }
+            info!("Assets: {} ({})", parts.join(", "), assets_dir);
+        }
+
+        report.assets.written = written;
+        report.assets.skipped = skipped;
+    }
+    report.mark("assets");
+
+    // Apply target-specific post-processing, then reflow
+    let processed = if overrides.runs_post_process() {
+        target.post_process(&processed_result.markdown)?
+    } else {
+        processed_result.markdown
+    };
+    let processed = overrides.apply_wrap(processed);
+    report.mark("post-process");
+
+    if copy {
+        copy_to_clipboard(&processed)?;
+    }
+
+    // Write output
+    if in_place {
+        // In-place requires input file
+        if let Some(ref path) = input {
+            if path.to_str() == Some("-") {
+                return Err(Error::IoError(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Cannot use --in-place with stdin",
+                )));
+            }
+            fs::write(path, processed).map_err(Error::IoError)?;
+            info!(status = "Processed:", "{}", path.display());
+        } else {
+            return Err(Error::IoError(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Cannot use --in-place without input file",
+            )));
+        }
+    } else if let Some(ref path) = output {
+        if path.to_str() == Some("-") {
+            // Write to stdout
+            print!("{}", processed);
+        } else {
+            // Write to file
+            fs::write(path, processed).map_err(Error::IoError)?;
+            info!(status = "Wrote:", "{}", path.display());
+        }
+    } else {
+        // No output specified, write to stdout
+        print!("{}", processed);
+    }
+    report.mark("write");
+
+    if let Some(format) = stats {
+        if let Some(collector) = stats_collector {
+            report.templates = collector.snapshot();
+        }
+        #[cfg(feature = "fetch")]
+        {
+            report.fetch = parser.fetch_context().map(|ctx| ctx.fetcher().stats());
+        }
+        report.print(format);
+    }
+
+    if let (Some(path), Some(profiler)) = (profile, profiler) {
+        let file = match &input {
+            Some(input) if input.to_str() != Some("-") => input.display().to_string(),
+            _ => "<stdin>".to_string(),
+        };
+        let trace = profiler.to_chrome_trace(&file, &content);
+        fs::write(path, serde_json::to_string(&trace)?)?;
+        info!(status = "Wrote:", "{} (profile)", path.display());
+    }
+
+    Ok(())
+}
+
+/// Upload assets to the configured bucket and rewrite references to them
+#[cfg(feature = "publish")]
+fn publish_assets(
+    processed: &mut ProcessedMarkdown,
+    config: Option<&MdfxConfig>,
+) -> Result<AssetWriteSummary, Error> {
+    let publish_config = config.and_then(|c| c.publish.clone()).ok_or_else(|| {
+        Error::PublishError("--publish needs a \"publish\" section in .mdfx.json".to_string())
+    })?;
+    let mut publisher = mdfx::publish::Publisher::from_env(publish_config)?;
+    let summary = publisher.publish(processed)?;
+
+    let base_url = publisher.config().public_url("");
+    info!(
+        "Published: {} uploaded, {} already present ({})",
+        summary.written,
+        summary.skipped,
+        base_url.trim_end_matches('/')
+    );
+    Ok(summary)
+}
+
+#[cfg(not(feature = "publish"))]
+fn publish_assets(
+    _processed: &mut ProcessedMarkdown,
+    _config: Option<&MdfxConfig>,
+) -> Result<AssetWriteSummary, Error> {
+    Err(Error::PublishError(
+        "this mdfx was built without the `publish` feature".to_string(),
+    ))
+}
+
+fn generate_grammar(format: &str, output: Option<PathBuf>) -> Result<(), Error> {
+    let format = GrammarFormat::parse(format).ok_or_else(|| {
+        Error::ParseError(format!(
+            "Unknown grammar format '{}'. Available: textmate, tree-sitter",
+            format
+        ))
+    })?;
+
+    let registry = Registry::shared()?;
+    let grammar = mdfx::grammar::generate(&registry, format);
+
+    match output {
+        Some(ref path) if path.to_str() != Some("-") => {
+            fs::write(path, grammar).map_err(Error::IoError)?;
+            info!(status = "Wrote:", "{}", path.display());
+        }
+        _ => print!("{}", grammar),
+    }
+
+    Ok(())
+}
+
+fn generate_schema(format: &str, output: Option<PathBuf>) -> Result<(), Error> {
+    let format = SchemaFormat::parse(format).ok_or_else(|| {
+        Error::ParseError(format!(
+            "Unknown schema format '{}'. Available: json-schema, catalog",
+            format
+        ))
+    })?;
+
+    let registry = Registry::shared()?;
+    let schema = mdfx::schema::generate(&registry, format);
+
+    match output {
+        Some(ref path) if path.to_str() != Some("-") => {
+            fs::write(path, schema).map_err(Error::IoError)?;
+            info!(status = "Wrote:", "{}", path.display());
+        }
+        _ => print!("{}", schema),
+    }
+
+    Ok(())
+}
+
+/// Render the latest release of a changelog as markdown
+fn generate_changelog(
+    from: &std::path::Path,
+    items: usize,
+    output: Option<PathBuf>,
+) -> Result<(), Error> {
+    let markdown = fs::read_to_string(from).map_err(|e| read_failed(from, e))?;
+
+    let mut parser = TemplateParser::new()?;
+    parser.set_changelog(markdown);
+    let section = parser.process(&format!("{{{{ui:latest-release:items={}/}}}}", items))?;
+
+    match output {
+        Some(ref path) if path.to_str() != Some("-") => {
+            fs::write(path, format!("{}\n", section)).map_err(Error::IoError)?;
+            info!(status = "Wrote:", "{}", path.display());
+        }
+        _ => println!("{}", section),
+    }
+
+    Ok(())
+}
+
+/// Render the gallery page and its SVGs
+fn generate_gallery(
+    out: &std::path::Path,
+    format: Option<&str>,
+    assets_dir: &str,
+) -> Result<(), Error> {
+    let format = match format {
+        Some(name) => GalleryFormat::parse(name).ok_or_else(|| {
+            Error::ParseError(format!(
+                "Unknown gallery format '{}'. Available: markdown, html",
+                name
+            ))
+        })?,
+        None if out.extension().is_some_and(|ext| ext == "html") => GalleryFormat::Html,
+        None => GalleryFormat::Markdown,
+    };
+
+    // The page links to the SVGs relative to its own directory
+    let page_dir = out.parent().unwrap_or(std::path::Path::new(""));
+    let write_dir = page_dir.join(assets_dir);
+    let backend = SvgBackend::with_prefix(write_dir.to_string_lossy(), assets_dir);
+
+    let gallery = mdfx::gallery::generate(Registry::shared()?, Box::new(backend), format)?;
+    let summary = gallery.write_assets(&mut DiskFs)?;
+    fs::write(out, &gallery.markdown).map_err(Error::IoError)?;
+    info!(
+        status = "Wrote:",
+        "{} ({} assets in {})",
+        out.display(),
+        summary.written + summary.skipped,
+        write_dir.display()
+    );
+
+    Ok(())
+}
+
+/// Convert a design-token file into palette JSON
+fn import_palette(
+    from: &std::path::Path,
+    format: Option<&str>,
+    output: Option<PathBuf>,
+) -> Result<(), Error> {
+    let format = match format {
+        Some(name) => TokenFormat::parse(name).ok_or_else(|| {
+            Error::ParseError(format!(
+                "Unknown token format '{}'. Available: figma, tailwind, css",
+                name
+            ))
+        })?,
+        None => TokenFormat::detect(from).ok_or_else(|| {
+            Error::ParseError(format!(
+                "Can't tell the token format of '{}'; pass --format figma, tailwind, or css",
+                from.display()
+            ))
+        })?,
+    };
+
+    let source = fs::read_to_string(from).map_err(|e| read_failed(from, e))?;
+    let palette = mdfx::palette::import(&source, format)?;
+    if !palette.skipped.is_empty() {
+        tracing::warn!(
+            "Skipped {} token(s) that aren't colors: {}",
+            palette.skipped.len(),
+            palette.skipped.join(", ")
+        );
+    }
+
+    write_palette(&palette.colors, output)
+}
+
+/// Generate a palette from a base color and scheme
+fn generate_palette(
+    base: &str,
+    scheme: &str,
+    name: &str,
+    output: Option<PathBuf>,
+) -> Result<(), Error> {
+    let scheme = Scheme::parse(scheme).ok_or_else(|| {
+        Error::ParseError(format!(
+            "Unknown scheme '{}'. Available: analogous, triadic, shades",
+            scheme
+        ))
+    })?;
+    let registry = Registry::shared()?;
+    let base = registry.resolve_color(base).unwrap_or(base);
+    let colors = mdfx::palette::generate(base, scheme, name)?;
+    write_palette(&colors, output)
+}
+
+/// Write palette JSON to a file or stdout
+fn write_palette(
+    colors: &std::collections::BTreeMap<String, String>,
+    output: Option<PathBuf>,
+) -> Result<(), Error> {
+    let mut json = serde_json::to_string_pretty(colors).map_err(Error::InvalidJson)?;
+    json.push('\n');
+    match output {
+        Some(ref path) if path.to_str() != Some("-") => {
+            fs::write(path, json).map_err(Error::IoError)?;
+            info!(
+                status = "Wrote:",
+                "{} ({} colors)",
+                path.display(),
+                colors.len()
+            );
+        }
+        _ => print!("{}", json),
+    }
+
+    Ok(())
+}
+
+/// Render every badge in a badgefx spec file into `out`
+fn render_badges(spec: &std::path::Path, out: &std::path::Path) -> Result<(), Error> {
+    let source = fs::read_to_string(spec).map_err(|e| read_failed(spec, e))?;
+    let yaml = matches!(
+        spec.extension().and_then(|ext| ext.to_str()),
+        Some("yaml" | "yml")
+    );
+    let badges: std::collections::BTreeMap<String, badgefx::BadgeSpec> = if yaml {
+        serde_yaml::from_str(&source).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str(&source).map_err(|e| e.to_string())
+    }
+    .map_err(|e| Error::ParseError(format!("Invalid badge spec {}: {}", spec.display(), e)))?;
+
+    if let Some(name) = badges
+        .keys()
+        .find(|name| name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.'))
+    {
+        return Err(Error::ParseError(format!(
+            "Invalid badge name '{}': names become file names and can't contain path separators",
+            name
+        )));
+    }
+
+    fs::create_dir_all(out).map_err(Error::IoError)?;
+    for (name, badge) in &badges {
+        let path = out.join(format!("{}.svg", name));
+        fs::write(&path, badge.render()).map_err(Error::IoError)?;
+    }
+    info!(
+        status = "Wrote:",
+        "{} badge(s) to {}",
+        badges.len(),
+        out.display()
+    );
+
+    Ok(())
+}
+
+/// Give the project components the changelog and project files
+///
+/// Reads the `changelog` path from the config, or `CHANGELOG.md` in the
+/// current directory. Manifests and coverage reports are read from the
+/// current directory when a component asks for them. Without a configured
+/// path a missing file is fine; the component reports it only if a document
+/// uses it.
+fn load_project_files(
+    parser: &mut TemplateParser,
+    config: Option<&MdfxConfig>,
+) -> Result<(), Error> {
+    parser.set_project_root(".");
+
+    let configured = config.and_then(|cfg| cfg.changelog.as_deref());
+    let path = std::path::Path::new(configured.unwrap_or(DEFAULT_CHANGELOG));
+    match fs::read_to_string(path) {
+        Ok(markdown) => parser.set_changelog(markdown),
+        Err(e) if configured.is_some() => return Err(read_failed(path, e)),
+        Err(_) => {}
+    }
+
+    let mut plugins: Vec<_> = config.map_or(Vec::new(), |cfg| cfg.plugins.iter().collect());
+    plugins.sort_by_key(|(name, _)| name.as_str());
+    for (name, plugin) in plugins {
+        #[cfg(feature = "wasm")]
+        {
+            let mut wasm = mdfx::WasmPlugin::load(name, std::path::Path::new(&plugin.wasm))?;
+            if let Some(description) = &plugin.description {
+                wasm = wasm.with_description(description);
+            }
+            parser.register_component(Arc::new(wasm))?;
+        }
+        #[cfg(not(feature = "wasm"))]
+        return Err(Error::ParseError(format!(
+            "Plugin '{}' ({}) needs mdfx built with the `wasm` feature",
+            name, plugin.wasm
+        )));
+    }
+    Ok(())
+}
+
+/// Check files in recovery mode and print every diagnostic
+fn check_files(
+    inputs: &[PathBuf],
+    config_path: Option<&std::path::Path>,
+    deny_warnings: bool,
+    error_format: ErrorFormat,
+    #[cfg(feature = "fetch")] fetch_config: Option<mdfx_fetch::FetchConfig>,
+    #[cfg(not(feature = "fetch"))] _fetch_config: Option<()>,
+) -> Result<(), Error> {
+    let mut parser = TemplateParser::new()?;
+
+    let config = if let Some(config_file) = config_path {
+        Some(MdfxConfig::load(config_file)?)
+    } else {
+        MdfxConfig::discover()
+    };
+    if let Some(ref cfg) = config {
+        parser.load_config(cfg);
+    }
+    load_project_files(&mut parser, config.as_ref())?;
+
+    #[cfg(feature = "fetch")]
+    if let Some(config) = fetch_config {
+        match mdfx::FetchContext::new(config) {
+            Ok(ctx) => parser.set_fetch_context(ctx),
+            Err(e) => tracing::warn!("Failed to initialize fetch: {}", e),
+        }
+    }
+
+    let mut errors = 0;
+    let mut warnings = 0;
+
+    for path in inputs {
+        let (name, content) = if path.to_str() == Some("-") {
+            let mut buffer = String::new();
+            io::stdin()
+                .read_to_string(&mut buffer)
+                .map_err(Error::IoError)?;
+            ("<stdin>".to_string(), buffer)
+        } else {
+            let content = fs::read_to_string(path).map_err(|e| read_failed(path, e))?;
+            (path.display().to_string(), content)
+        };
+
+        let result = parser.process_with_diagnostics(&content);
+        for diagnostic in &result.diagnostics {
+            if error_format == ErrorFormat::Json {
+                match diagnostic.severity {
+                    Severity::Error => errors += 1,
+                    Severity::Warning => warnings += 1,
+                }
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "file": name,
+                        "line": diagnostic.line,
+                        "column": diagnostic.column,
+                        "severity": diagnostic.severity,
+                        "message": diagnostic.message,
+                    })
+                );
+                continue;
+            }
+            let severity = match diagnostic.severity {
+                Severity::Error => {
+                    errors += 1;
+                    "error".red().bold()
+                }
+                Severity::Warning => {
+                    warnings += 1;
+                    "warning".yellow()
+                }
+            };
+            println!(
+                "{}:{}:{}: {}: {}",
+                name, diagnostic.line, diagnostic.column, severity, diagnostic.message
+            );
+        }
     }
 
-    println!("Total: {} colors", entries.len().to_string().yellow());
-    println!();
-    println!(
-        "{}",
-        "Usage: {{ui:swatch:name/}} or color params like bg=name".dimmed()
+    let summary = format!(
+        "{} file(s): {} error(s), {} warning(s)",
+        inputs.len(),
+        errors,
+        warnings
     );
-
+    if errors > 0 || (deny_warnings && warnings > 0) {
+        return Err(Error::CheckFailed(format!("Check failed: {}", summary)));
+    }
+    info!(status = "Checked:", "{}", summary);
     Ok(())
 }
 
-#[allow(clippy::too_many_arguments)]
-fn process_file(
-    input: Option<PathBuf>,
-    output: Option<PathBuf>,
-    in_place: bool,
-    target_name: &str,
-    backend_override: Option<&str>,
-    assets_dir: &str,
-    assets_prefix: Option<&str>,
-    palette_path: Option<&std::path::Path>,
+/// URL signing key for `mdfx serve`
+#[cfg(feature = "serve")]
+fn serve_key() -> Result<Vec<u8>, Error> {
+    match std::env::var(serve::KEY_ENV) {
+        Ok(key) if !key.is_empty() => Ok(key.into_bytes()),
+        _ => Err(Error::ParseError(format!(
+            "URL signing needs a key in the {} environment variable",
+            serve::KEY_ENV
+        ))),
+    }
+}
+
+#[cfg(feature = "serve")]
+fn serve_badges(
+    host: &str,
+    port: u16,
     config_path: Option<&std::path::Path>,
+    guard: serve::Guard,
     #[cfg(feature = "fetch")] fetch_config: Option<mdfx_fetch::FetchConfig>,
     #[cfg(not(feature = "fetch"))] _fetch_config: Option<()>,
 ) -> Result<(), Error> {
-    // Resolve target (with auto-detection support)
-    let target: Box<dyn Target> = if target_name == "auto" {
-        // Auto-detect from output path
-        let detected = output
-            .as_ref()
-            .and_then(|p| detect_target_from_path(p))
-            .or_else(|| input.as_ref().and_then(|p| detect_target_from_path(p)));
-
-        if let Some(name) = detected {
-            eprintln!("{} Auto-detected target: {}", "Info:".cyan(), name.green());
-            // Safe: detect_target_from_path only returns known target names
-            get_target(name).expect("detected target should be valid")
-        } else {
-            eprintln!(
-                "{} Could not auto-detect target, using github",
-                "Info:".cyan()
-            );
-            // Safe: "github" is a builtin target
-            get_target("github").expect("github target should exist")
-        }
-    } else {
-        get_target(target_name).ok_or_else(|| {
-            Error::ParseError(format!(
-                "Unknown target '{}'. Available: {}",
-                target_name,
-                available_targets().join(", ")
-            ))
-        })?
-    };
-
-    // Determine backend: explicit override > target's preferred backend
-    let backend_type = if let Some(backend) = backend_override {
-        match backend {
-            "shields" => BackendType::Shields,
-            "svg" => BackendType::Svg,
-            _ => {
-                return Err(Error::ParseError(format!(
-                    "Unknown backend '{}'. Available: svg, shields",
-                    backend
-                )));
-            }
-        }
-    } else {
-        target.preferred_backend()
-    };
-
-    // Create the appropriate backend
-    let mut parser = match backend_type {
-        BackendType::Svg => {
-            let backend = if let Some(prefix) = assets_prefix {
-                SvgBackend::with_prefix(assets_dir, prefix)
-            } else {
-                SvgBackend::new(assets_dir)
-            };
-            TemplateParser::with_backend(Box::new(backend))?
-        }
-        BackendType::Shields => TemplateParser::with_backend(Box::new(ShieldsBackend::new()?))?,
-        BackendType::PlainText => TemplateParser::with_backend(Box::new(PlainTextBackend::new()))?,
-    };
+    let mut parser =
+        TemplateParser::with_registry(Registry::shared()?, Box::new(SvgBackend::new("badges")))?;
+    parser.set_options(serve::parser_options());
 
-    // Load config file (explicit path or auto-discover)
     let config = if let Some(config_file) = config_path {
         Some(MdfxConfig::load(config_file)?)
     } else {
         MdfxConfig::discover()
     };
-
     if let Some(ref cfg) = config {
-        let partial_count = cfg.partials.len();
-        let palette_count = cfg.palette.len();
-
-        if partial_count > 0 || palette_count > 0 {
-            eprintln!(
-                "{} Loaded config: {} partial(s), {} color(s)",
-                "Info:".cyan(),
-                partial_count,
-                palette_count
-            );
-        }
-
         parser.load_config(cfg);
     }
 
-    // Load custom palette if provided (overrides config palette)
-    if let Some(palette_file) = palette_path {
-        let palette_content = fs::read_to_string(palette_file).map_err(Error::IoError)?;
-        let custom_palette: std::collections::HashMap<String, String> =
-            serde_json::from_str(&palette_content).map_err(|e| {
-                Error::ParseError(format!(
-                    "Failed to parse palette file '{}': {}",
-                    palette_file.display(),
-                    e
-                ))
-            })?;
-        eprintln!(
-            "{} Loaded {} custom color(s) from {}",
-            "Info:".cyan(),
-            custom_palette.len(),
-            palette_file.display()
-        );
-        parser.extend_palette(custom_palette);
-    }
-
-    // Set up fetch context for dynamic badges (if fetch feature is enabled)
     #[cfg(feature = "fetch")]
     if let Some(config) = fetch_config {
         match mdfx::FetchContext::new(config) {
-            Ok(ctx) => {
-                if !ctx.fetcher().config().offline {
-                    eprintln!(
-                        "{} Dynamic badges enabled (use --offline to disable)",
-                        "Info:".cyan()
-                    );
-                } else {
-                    eprintln!(
-                        "{} Dynamic badges in offline mode (cache only)",
-                        "Info:".cyan()
-                    );
-                }
-                parser.set_fetch_context(ctx);
-            }
-            Err(e) => {
-                eprintln!("{} Failed to initialize fetch: {}", "Warning:".yellow(), e);
-            }
+            Ok(ctx) => parser.set_fetch_context(ctx),
+            Err(e) => tracing::warn!("Failed to initialize fetch: {}", e),
         }
     }
 
+    serve::run(
+        serve::BadgeServer::new(parser, guard),
+        &format!("{}:{}", host, port),
+    )
+}
+
+fn format_file(
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+    in_place: bool,
+    check: bool,
+    closers: Option<&str>,
+    config_path: Option<&std::path::Path>,
+) -> Result<(), Error> {
+    // Load format options from config (explicit path or auto-discover)
+    let config = if let Some(config_file) = config_path {
+        Some(MdfxConfig::load(config_file)?)
+    } else {
+        MdfxConfig::discover()
+    };
+    let mut options = config.map(|c| c.format).unwrap_or_default();
+
+    if let Some(policy) = closers {
+        options.closers = CloserPolicy::parse(policy).ok_or_else(|| {
+            Error::ParseError(format!(
+                "Unknown closer policy '{}'. Available: preserve, named, universal",
+                policy
+            ))
+        })?;
+    }
+
     // Read input
     let content = if let Some(ref path) = input {
         if path.to_str() == Some("-") {
-            // Read from stdin
             let mut buffer = String::new();
             io::stdin()
                 .read_to_string(&mut buffer)
                 .map_err(Error::IoError)?;
             buffer
         } else {
-            // Read from file
             fs::read_to_string(path).map_err(Error::IoError)?
         }
     } else {
-        // No input specified, read from stdin
         let mut buffer = String::new();
         io::stdin()
             .read_to_string(&mut buffer)
@@ -931,97 +2958,22 @@ fn process_file(
         buffer
     };
 
-    // Process content with asset collection
-    let processed_result = parser.process_with_assets(&content)?;
-
-    // Write any file-based assets to disk
-    if !processed_result.assets.is_empty() {
-        // Ensure assets directory exists
-        fs::create_dir_all(assets_dir).map_err(Error::IoError)?;
-
-        // Build manifest for SVG backend
-        let mut manifest = if matches!(backend_type, BackendType::Svg) {
-            Some(AssetManifest::new("svg", assets_dir))
-        } else {
-            None
-        };
-
-        let mut written = 0;
-        let mut skipped = 0;
-
-        for asset in &processed_result.assets {
-            if let Some(path) = asset.file_path() {
-                if let Some(bytes) = asset.file_bytes() {
-                    // Skip if file already exists (hash-based names mean same content)
-                    let path_ref = std::path::Path::new(path);
-                    if path_ref.exists() {
-                        skipped += 1;
-                    } else {
-                        // Write the asset file
-                        fs::write(path, bytes).map_err(Error::IoError)?;
-                        written += 1;
-                    }
-
-                    // Add to manifest if SVG backend
-                    if let Some(ref mut m) = manifest {
-                        if let mdfx::RenderedAsset::File {
-                            relative_path,
-                            bytes,
-                            primitive,
-                            ..
-                        } = asset
-                        {
-                            let asset_type = match primitive.as_ref() {
-                                mdfx::Primitive::Swatch { .. } => "swatch",
-                                mdfx::Primitive::Tech(_) => "tech",
-                                mdfx::Primitive::Version(_) => "version",
-                                mdfx::Primitive::License(_) => "license",
-                                mdfx::Primitive::Progress { .. } => "progress",
-                                mdfx::Primitive::Donut { .. } => "donut",
-                                mdfx::Primitive::Gauge { .. } => "gauge",
-                                mdfx::Primitive::Sparkline { .. } => "sparkline",
-                                mdfx::Primitive::Rating { .. } => "rating",
-                                mdfx::Primitive::Waveform { .. } => "waveform",
-                            };
-                            m.add_asset(
-                                relative_path.clone(),
-                                bytes,
-                                primitive.as_ref(),
-                                asset_type.to_string(),
-                            );
-                        }
-                    }
-                }
-            }
-        }
-
-        // Write manifest.json for SVG backend
-        if let Some(manifest) = manifest {
-            let manifest_path = format!("{}/manifest.json", assets_dir);
-            manifest.write(std::path::Path::new(&manifest_path))?;
-        }
+    let formatter = Formatter::with_options(options)?;
+    let formatted = formatter.format(&content);
 
-        // Report asset generation results
-        if written > 0 || skipped > 0 {
-            let mut parts = Vec::new();
-            if written > 0 {
-                parts.push(format!("{} written", written));
-            }
-            if skipped > 0 {
-                parts.push(format!("{} unchanged", skipped));
-            }
-            eprintln!(
-                "{} Assets: {} ({})",
-                "Info:".cyan(),
-                parts.join(", "),
-                assets_dir
-            );
+    if check {
+        let name = input
+            .as_ref()
+            .filter(|p| p.to_str() != Some("-"))
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "<stdin>".to_string());
+        if formatted != content {
+            return Err(Error::CheckFailed(format!("{} is not formatted", name)));
         }
+        info!(status = "Formatted:", "{}", name);
+        return Ok(());
     }
 
-    // Apply target-specific post-processing
-    let processed = target.post_process(&processed_result.markdown)?;
-
     // Write output
     if in_place {
         // In-place requires input file
@@ -1032,8 +2984,10 @@ fn process_file(
                     "Cannot use --in-place with stdin",
                 )));
             }
-            fs::write(path, processed).map_err(Error::IoError)?;
-            eprintln!("{} {}", "Processed:".green(), path.display());
+            if formatted != content {
+                fs::write(path, formatted).map_err(Error::IoError)?;
+                info!(status = "Formatted:", "{}", path.display());
+            }
         } else {
             return Err(Error::IoError(io::Error::new(
                 io::ErrorKind::InvalidInput,
@@ -1042,35 +2996,48 @@ fn process_file(
         }
     } else if let Some(ref path) = output {
         if path.to_str() == Some("-") {
-            // Write to stdout
-            print!("{}", processed);
+            print!("{}", formatted);
         } else {
-            // Write to file
-            fs::write(path, processed).map_err(Error::IoError)?;
-            eprintln!("{} {}", "Wrote:".green(), path.display());
+            fs::write(path, formatted).map_err(Error::IoError)?;
+            info!(status = "Wrote:", "{}", path.display());
         }
     } else {
-        // No output specified, write to stdout
-        print!("{}", processed);
+        print!("{}", formatted);
     }
 
     Ok(())
 }
 
-fn verify_assets(assets_dir: &str) -> Result<(), Error> {
+/// IO error naming the file that couldn't be read
+fn read_failed(path: &std::path::Path, e: io::Error) -> Error {
+    Error::IoError(io::Error::new(
+        e.kind(),
+        format!("Failed to read '{}': {}", path.display(), e),
+    ))
+}
+
+fn manifest_not_found() -> Error {
+    Error::IoError(io::Error::new(
+        io::ErrorKind::NotFound,
+        "manifest.json not found. Run with --backend svg to generate a manifest.",
+    ))
+}
+
+fn verify_assets(
+    assets_dir: &str,
+    asset_namespace: Option<&str>,
+    public_key: Option<&std::path::Path>,
+    require_signature: bool,
+) -> Result<(), Error> {
     let manifest_path = format!("{}/manifest.json", assets_dir);
 
     println!("{}", "Verifying assets...".bold());
     println!();
 
     // Load manifest
-    let manifest = match AssetManifest::load(std::path::Path::new(&manifest_path)) {
+    let mut manifest = match AssetManifest::load(std::path::Path::new(&manifest_path)) {
         Ok(m) => m,
-        Err(_) => {
-            eprintln!("{} manifest.json not found", "Error:".red().bold());
-            eprintln!("Run with --backend svg to generate a manifest.");
-            process::exit(1);
-        }
+        Err(_) => return Err(manifest_not_found()),
     };
 
     println!(
@@ -1079,7 +3046,15 @@ fn verify_assets(assets_dir: &str) -> Result<(), Error> {
         manifest.created_at.dimmed()
     );
     println!("Backend: {}", manifest.backend.cyan());
+    if let Some(namespace) = asset_namespace {
+        check_asset_namespace(namespace)?;
+        manifest.retain_namespace(namespace);
+        println!("Namespace: {}", namespace.cyan());
+    }
     println!("Total assets: {}", manifest.total_assets);
+    if let Some(public_key) = public_key {
+        verify_manifest_signature(assets_dir, public_key, require_signature)?;
+    }
     println!();
 
     // Verify each asset
@@ -1137,19 +3112,226 @@ fn verify_assets(assets_dir: &str) -> Result<(), Error> {
         println!("  Errors: {}", error_count.to_string().red());
     }
 
-    // Exit with error if any problems found
-    if missing_count > 0 || mismatch_count > 0 || error_count > 0 {
-        process::exit(1);
+    if missing_count > 0 || mismatch_count > 0 || error_count > 0 {
+        return Err(Error::CheckFailed(format!(
+            "Asset verification failed: {} missing, {} hash mismatch(es), {} unreadable",
+            missing_count, mismatch_count, error_count
+        )));
+    }
+
+    println!();
+    println!("{}", "✓ All assets verified successfully!".green().bold());
+
+    Ok(())
+}
+
+/// Environment variable holding the secret key for `mdfx assets sign`
+#[cfg(feature = "sign")]
+const SIGNING_KEY_ENV: &str = "MDFX_SIGNING_KEY";
+
+#[cfg(feature = "sign")]
+fn generate_signing_key(output: &std::path::Path, force: bool) -> Result<(), Error> {
+    let public_path = output.with_extension("pub");
+    for path in [output, public_path.as_path()] {
+        if path.exists() && !force {
+            return Err(Error::IoError(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "{} already exists (use --force to replace it)",
+                    path.display()
+                ),
+            )));
+        }
+    }
+
+    let key = mdfx::signing::SecretKey::generate()?;
+    write_secret_file(output, &key.to_minisign())?;
+    fs::write(&public_path, key.public_key().to_minisign())?;
+
+    info!(status = "Wrote:", "{} (secret key)", output.display());
+    info!(
+        status = "Wrote:",
+        "{} (public key {})",
+        public_path.display(),
+        key.public_key().key_id()
+    );
+    Ok(())
+}
+
+/// Write a file only the current user can read
+#[cfg(feature = "sign")]
+fn write_secret_file(path: &std::path::Path, contents: &str) -> Result<(), Error> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    io::Write::write_all(&mut file, contents.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(feature = "sign")]
+fn sign_manifest(assets_dir: &std::path::Path, key: Option<&std::path::Path>) -> Result<(), Error> {
+    let key = match key {
+        Some(path) => fs::read_to_string(path).map_err(|e| read_failed(path, e))?,
+        None => match std::env::var(SIGNING_KEY_ENV) {
+            Ok(key) if !key.is_empty() => key,
+            _ => {
+                return Err(Error::ParseError(format!(
+                    "Signing needs --key or a key in the {} environment variable",
+                    SIGNING_KEY_ENV
+                )))
+            }
+        },
+    };
+    let key = mdfx::signing::SecretKey::parse(&key)?;
+
+    let manifest_path = assets_dir.join("manifest.json");
+    let manifest = fs::read(&manifest_path).map_err(|_| manifest_not_found())?;
+    let trusted_comment = format!(
+        "timestamp:{}\tfile:manifest.json\tmdfx:{}",
+        chrono::Utc::now().timestamp(),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let signature_path = assets_dir.join(mdfx::signing::SIGNATURE_FILE);
+    fs::write(&signature_path, key.sign(&manifest, &trusted_comment))?;
+    info!(
+        status = "Wrote:",
+        "{} (key {})",
+        signature_path.display(),
+        key.public_key().key_id()
+    );
+    Ok(())
+}
+
+/// Check `manifest.json.minisig` before the assets it vouches for
+#[cfg(feature = "sign")]
+fn verify_manifest_signature(
+    assets_dir: &str,
+    public_key: &std::path::Path,
+    require_signature: bool,
+) -> Result<(), Error> {
+    let key = fs::read_to_string(public_key).map_err(|e| read_failed(public_key, e))?;
+    let key = mdfx::signing::PublicKey::parse(&key)?;
+
+    let dir = std::path::Path::new(assets_dir);
+    let signature = match fs::read_to_string(dir.join(mdfx::signing::SIGNATURE_FILE)) {
+        Ok(signature) => signature,
+        Err(_) if !require_signature => {
+            println!("Signature: {}", "none".yellow());
+            return Ok(());
+        }
+        Err(_) => {
+            println!("Signature: {}", "missing".red());
+            return Err(Error::CheckFailed(format!(
+                "manifest.json is not signed: {} not found",
+                dir.join(mdfx::signing::SIGNATURE_FILE).display()
+            )));
+        }
+    };
+
+    let manifest = fs::read(dir.join("manifest.json"))?;
+    match key.verify(&manifest, &signature) {
+        Ok(trusted_comment) => {
+            println!(
+                "Signature: {} key {} ({})",
+                "✓".green(),
+                key.key_id(),
+                trusted_comment.replace('\t', " ").dimmed()
+            );
+            Ok(())
+        }
+        Err(e) => {
+            println!("Signature: {}", "invalid".red());
+            Err(e)
+        }
+    }
+}
+
+#[cfg(not(feature = "sign"))]
+fn verify_manifest_signature(
+    _assets_dir: &str,
+    _public_key: &std::path::Path,
+    _require_signature: bool,
+) -> Result<(), Error> {
+    Err(Error::IoError(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this mdfx was built without the `sign` feature",
+    )))
+}
+
+/// Print the differences between two asset directories
+fn diff_assets(
+    old: &std::path::Path,
+    new: &std::path::Path,
+    html: Option<PathBuf>,
+) -> Result<(), Error> {
+    let diff = mdfx::asset_diff::diff(old, new)?;
+
+    for change in &diff.changes {
+        match change {
+            AssetChange::Added { file } => println!("  {} {}", "+".green(), file),
+            AssetChange::Removed { file } => println!("  {} {}", "-".red(), file),
+            AssetChange::Changed { old, new } if old == new => {
+                println!("  {} {}", "~".yellow(), new)
+            }
+            AssetChange::Changed { old, new } => {
+                println!("  {} {} {} {}", "~".yellow(), old, "->".dimmed(), new)
+            }
+        }
+    }
+
+    let (added, removed, changed) = diff.counts();
+    if !diff.is_empty() {
+        println!();
+    }
+    println!(
+        "{} {} changed, {} added, {} removed, {} unchanged",
+        "Summary:".bold(),
+        changed,
+        added,
+        removed,
+        diff.unchanged
+    );
+
+    if let Some(path) = html {
+        let page = mdfx::asset_diff::preview_html(&diff, old, new)?;
+        fs::write(&path, page).map_err(Error::IoError)?;
+        info!(status = "Wrote:", "{}", path.display());
     }
 
-    println!();
-    println!("{}", "✓ All assets verified successfully!".green().bold());
-
     Ok(())
 }
 
-fn clean_assets(assets_dir: &str, dry_run: bool, scan_pattern: Option<&str>) -> Result<(), Error> {
+/// Reject asset namespaces that aren't safe at the start of a filename
+fn check_asset_namespace(namespace: &str) -> Result<(), Error> {
+    let valid = !namespace.is_empty()
+        && namespace
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !namespace.starts_with('.');
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::ParseError(format!(
+            "Invalid asset namespace '{}': use letters, digits, '-', '_' and '.'",
+            namespace
+        )))
+    }
+}
+
+fn clean_assets(
+    assets_dir: &str,
+    asset_namespace: Option<&str>,
+    dry_run: bool,
+    scan_pattern: Option<&str>,
+    retention: &Retention,
+) -> Result<(), Error> {
     let manifest_path = format!("{}/manifest.json", assets_dir);
+    if let Some(namespace) = asset_namespace {
+        check_asset_namespace(namespace)?;
+    }
 
     println!(
         "{}",
@@ -1162,20 +3344,19 @@ fn clean_assets(assets_dir: &str, dry_run: bool, scan_pattern: Option<&str>) ->
     println!();
 
     // Determine which assets are referenced
-    let referenced: std::collections::HashSet<String> = if let Some(pattern) = scan_pattern {
+    let mut referenced: std::collections::HashSet<String> = if let Some(pattern) = scan_pattern {
         // Scan markdown files for asset references
         println!("{} Scanning markdown files: {}", "Info:".cyan(), pattern);
         scan_markdown_for_assets(pattern, assets_dir)?
     } else {
         // Load manifest
-        let manifest = match AssetManifest::load(std::path::Path::new(&manifest_path)) {
+        let mut manifest = match AssetManifest::load(std::path::Path::new(&manifest_path)) {
             Ok(m) => m,
-            Err(_) => {
-                eprintln!("{} manifest.json not found", "Error:".red().bold());
-                eprintln!("Run with --backend svg to generate a manifest.");
-                process::exit(1);
-            }
+            Err(_) => return Err(manifest_not_found()),
         };
+        if let Some(namespace) = asset_namespace {
+            manifest.retain_namespace(namespace);
+        }
 
         // Get referenced asset paths from manifest
         manifest
@@ -1229,10 +3410,28 @@ fn clean_assets(assets_dir: &str, dry_run: bool, scan_pattern: Option<&str>) ->
             .and_then(|n| n.to_str())
             .unwrap_or_default();
 
+        // Files of other namespaces belong to other runs
+        if asset_namespace.is_some_and(|ns| !filename.starts_with(ns)) {
+            continue;
+        }
+
         // Check if this asset is referenced (by full path or filename)
         let is_referenced =
             referenced.contains(&relative_path) || referenced.iter().any(|r| r.ends_with(filename));
 
+        // Unreferenced, but kept by --keep-newer-than, --keep-git-history, or --keep
+        let retained = if is_referenced {
+            None
+        } else {
+            retention.reason(&path)
+        };
+        if let Some(reason) = retained {
+            println!("  {} {} ({})", "Keeping:".cyan(), relative_path, reason);
+            referenced.insert(filename.to_string());
+            kept_count += 1;
+            continue;
+        }
+
         if !is_referenced {
             let metadata = fs::metadata(&path).map_err(Error::IoError)?;
             let size = metadata.len();
@@ -1402,13 +3601,69 @@ fn update_manifest_after_clean(
     Ok(())
 }
 
+/// A language and its strings for `{{t:key/}}` templates
+type Language = (String, HashMap<String, String>);
+
+/// Strings of each language in an i18n directory, default language first
+///
+/// Every `*.json` file in `dir` is a language named after the file. The
+/// default language's file is optional; its strings fill in keys the other
+/// languages lack. Without a directory there is only the default language.
+fn load_languages(
+    dir: Option<&std::path::Path>,
+    default_lang: &str,
+) -> Result<Vec<Language>, Error> {
+    let mut files = BTreeMap::new();
+    if let Some(dir) = dir {
+        for entry in fs::read_dir(dir).map_err(Error::IoError)? {
+            let path = entry.map_err(Error::IoError)?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(lang) = path.file_stem().and_then(|s| s.to_str()) {
+                    files.insert(lang.to_string(), path.clone());
+                }
+            }
+        }
+    }
+
+    let read = |path: &std::path::Path| -> Result<HashMap<String, String>, Error> {
+        let json = fs::read_to_string(path).map_err(Error::IoError)?;
+        mdfx::i18n::parse_strings(&json).map_err(|e| {
+            Error::ParseError(format!(
+                "Failed to parse string file '{}': {}",
+                path.display(),
+                e
+            ))
+        })
+    };
+
+    let defaults = match files.remove(default_lang) {
+        Some(path) => read(&path)?,
+        None => HashMap::new(),
+    };
+    let mut languages = vec![(default_lang.to_string(), defaults.clone())];
+    for (lang, path) in files {
+        let mut strings = defaults.clone();
+        strings.extend(read(&path)?);
+        info!("Loaded {} string(s) for '{}'", strings.len(), lang);
+        languages.push((lang, strings));
+    }
+    Ok(languages)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn build_multi_target(
     input: &std::path::Path,
     output_dir: &str,
     targets: Option<&str>,
     all_targets: bool,
+    jobs: usize,
     palette_path: Option<&std::path::Path>,
+    i18n_dir: Option<&std::path::Path>,
+    default_lang: &str,
+    inline: Option<&str>,
 ) -> Result<(), Error> {
+    let inline = inline.map(parse_inline_mode).transpose()?;
+
     // Determine which targets to build
     let target_names: Vec<&str> = if all_targets {
         available_targets()
@@ -1433,6 +3688,9 @@ fn build_multi_target(
     // Read input file
     let content = fs::read_to_string(input).map_err(Error::IoError)?;
 
+    // Project settings: partials, palette, and per-target overrides
+    let config = MdfxConfig::discover();
+
     // Load custom palette if provided
     let custom_palette: Option<std::collections::HashMap<String, String>> =
         if let Some(palette_file) = palette_path {
@@ -1445,9 +3703,8 @@ fn build_multi_target(
                         e
                     ))
                 })?;
-            eprintln!(
-                "{} Loaded {} custom color(s) from {}",
-                "Info:".cyan(),
+            info!(
+                "Loaded {} custom color(s) from {}",
                 palette.len(),
                 palette_file.display()
             );
@@ -1456,6 +3713,14 @@ fn build_multi_target(
             None
         };
 
+    // Per-language strings: --i18n, else an i18n directory beside the input
+    let default_i18n = input
+        .parent()
+        .unwrap_or(std::path::Path::new(""))
+        .join("i18n");
+    let i18n_dir = i18n_dir.or_else(|| default_i18n.is_dir().then_some(default_i18n.as_path()));
+    let languages = load_languages(i18n_dir, default_lang)?;
+
     // Create output directory
     fs::create_dir_all(output_dir).map_err(Error::IoError)?;
 
@@ -1468,59 +3733,130 @@ fn build_multi_target(
     println!("{}", "Building for multiple targets...".bold());
     println!();
 
-    let mut success_count = 0;
-
-    for target_name in &target_names {
+    // Each target renders on a worker with its own parser; assets and
+    // outputs are written here in target order, so targets sharing an
+    // assets directory never write the same file at once
+    let build_target = |target_name: &str| -> Result<Vec<TargetOutput>, Error> {
         // Safe: target_names are validated above
-        let target = get_target(target_name).expect("target was validated");
-
-        print!("  {} {} ", "Building:".cyan(), target_name);
+        let mut target = get_target(target_name).expect("target was validated");
+        if let Some(ref cfg) = config {
+            target.configure(cfg);
+        }
+        let overrides = config
+            .as_ref()
+            .map(|cfg| cfg.target_overrides(target.name()))
+            .unwrap_or_default();
 
-        // Create backend based on target's preference
-        let backend_type = target.preferred_backend();
-        let assets_dir = format!("{}/assets/{}", output_dir, target_name);
+        // Create backend: .mdfx.json target setting > target's preference
+        let backend_type = overrides
+            .backend
+            .unwrap_or_else(|| target.preferred_backend());
+        let assets_dir = overrides
+            .assets_dir
+            .clone()
+            .unwrap_or_else(|| format!("{}/assets/{}", output_dir, target_name));
+        let inline_mode = match inline {
+            Some(requested) if backend_type == BackendType::Svg => {
+                let mode = inline_mode_for(requested, target.as_ref());
+                if mode.is_none() {
+                    tracing::warn!(
+                        "Target '{}' doesn't display the requested inline SVGs, writing asset files",
+                        target_name
+                    );
+                }
+                mode
+            }
+            _ => None,
+        };
 
-        let mut parser = match backend_type {
-            BackendType::Svg => {
-                fs::create_dir_all(&assets_dir).map_err(Error::IoError)?;
-                TemplateParser::with_backend(Box::new(SvgBackend::new(&assets_dir)))?
+        // Languages share the target's assets; identical assets get the same file
+        let mut outputs = Vec::with_capacity(languages.len());
+        for (lang, strings) in &languages {
+            // Apply project config, with badge labels in this language when
+            // they are translated, then this target's palette, then the
+            // custom palette
+            let mut lang_config = config.clone();
+            if languages.len() > 1 && labels::parse_lang(lang).is_some() {
+                lang_config.get_or_insert_with(MdfxConfig::default).lang = Some(lang.clone());
             }
-            BackendType::Shields => TemplateParser::with_backend(Box::new(ShieldsBackend::new()?))?,
-            BackendType::PlainText => {
-                TemplateParser::with_backend(Box::new(PlainTextBackend::new()))?
+            let mut parser = target_parser(
+                target.as_ref(),
+                backend_type,
+                &assets_dir,
+                inline_mode,
+                lang_config.as_ref(),
+            )?;
+            if let Some(ref palette) = custom_palette {
+                parser.extend_palette(palette.clone());
             }
-        };
+            parser.set_strings(strings.clone());
 
-        // Apply custom palette
-        if let Some(ref palette) = custom_palette {
-            parser.extend_palette(palette.clone());
-        }
+            // Process content
+            let mut processed = parser.process_with_assets(&content)?;
 
-        // Process content
-        let processed_result = parser.process_with_assets(&content)?;
+            // Apply target-specific post-processing, then reflow
+            if overrides.runs_post_process() {
+                processed.markdown = target.post_process(&processed.markdown)?;
+            }
+            processed.markdown = overrides.apply_wrap(processed.markdown);
 
-        // Write any file-based assets (skip existing)
-        if !processed_result.assets.is_empty() {
-            for asset in &processed_result.assets {
-                if let Some(path) = asset.file_path() {
-                    if let Some(bytes) = asset.file_bytes() {
-                        let path_ref = std::path::Path::new(path);
-                        if !path_ref.exists() {
-                            fs::write(path, bytes).map_err(Error::IoError)?;
-                        }
-                    }
+            // Write output file: the default language unsuffixed, others as .{lang}.md
+            let path = if lang == default_lang {
+                format!("{}/{}_{}.md", output_dir, stem, target_name)
+            } else {
+                format!("{}/{}_{}.{}.md", output_dir, stem, target_name, lang)
+            };
+            outputs.push(TargetOutput {
+                lang: lang.clone(),
+                path,
+                processed,
+            });
+        }
+        Ok(outputs)
+    };
+
+    let queue = Mutex::new(target_names.iter().enumerate());
+    let results = Mutex::new(target_names.iter().map(|_| None).collect::<Vec<_>>());
+    let failed = AtomicBool::new(false);
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, target_names.len().max(1)) {
+            scope.spawn(|| loop {
+                // Stop taking targets once one has failed, as a sequential build would
+                if failed.load(Ordering::Relaxed) {
+                    break;
                 }
-            }
+                let Some((index, target_name)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let result = build_target(target_name);
+                if result.is_err() {
+                    failed.store(true, Ordering::Relaxed);
+                }
+                results.lock().unwrap()[index] = Some(result);
+            });
         }
+    });
+
+    let mut success_count = 0;
 
-        // Apply target-specific post-processing
-        let processed = target.post_process(&processed_result.markdown)?;
+    for (target_name, result) in target_names.iter().zip(results.into_inner().unwrap()) {
+        // Targets are taken in order, so any target that never started comes
+        // after the one that failed
+        let Some(result) = result else {
+            break;
+        };
+        for output in result? {
+            print!("  {} {} ", "Building:".cyan(), target_name);
+            if languages.len() > 1 {
+                print!("({}) ", output.lang);
+            }
 
-        // Write output file
-        let output_path = format!("{}/{}_{}.md", output_dir, stem, target_name);
-        fs::write(&output_path, &processed).map_err(Error::IoError)?;
+            // Write any file-based assets (skip existing)
+            output.processed.write_assets(&mut DiskFs)?;
+            fs::write(&output.path, &output.processed.markdown).map_err(Error::IoError)?;
 
-        println!("{} {}", "→".green(), output_path);
+            println!("{} {}", "→".green(), output.path);
+        }
         success_count += 1;
     }
 
@@ -1534,16 +3870,212 @@ fn build_multi_target(
     Ok(())
 }
 
+/// One language of a target rendered by `mdfx build`, waiting to be written
+struct TargetOutput {
+    lang: String,
+    path: String,
+    processed: ProcessedMarkdown,
+}
+
+/// Parser for `target` with the project config, project files and the
+/// target's palette applied
+fn target_parser(
+    target: &dyn Target,
+    backend_type: BackendType,
+    assets_dir: &str,
+    inline_mode: Option<InlineMode>,
+    config: Option<&MdfxConfig>,
+) -> Result<TemplateParser, Error> {
+    let mut parser = match backend_type {
+        BackendType::Svg => {
+            let backend = if let Some(mode) = inline_mode {
+                SvgBackend::inline(mode)
+            } else {
+                fs::create_dir_all(assets_dir).map_err(Error::IoError)?;
+                SvgBackend::new(assets_dir)
+            };
+            let filenames = config.map(|cfg| cfg.filenames).unwrap_or_default();
+            let backend = backend
+                .with_image_dimensions(target.image_dimensions())
+                .with_filename_scheme(filenames);
+            TemplateParser::with_backend(Box::new(backend))?
+        }
+        BackendType::Shields => TemplateParser::with_backend(Box::new(ShieldsBackend::new()?))?,
+        BackendType::PlainText => TemplateParser::with_backend(Box::new(PlainTextBackend::new()))?,
+    };
+    if let Some(cfg) = config {
+        parser.load_config(cfg);
+    }
+    load_project_files(&mut parser, config)?;
+    let mut options = parser.options().clone();
+    options.clock = build_clock(false)?;
+    parser.set_options(options);
+    parser.set_target(target);
+    if let Some(cfg) = config {
+        parser.extend_palette(cfg.target_overrides(target.name()).palette);
+    }
+    Ok(parser)
+}
+
+/// Run the pipeline `name` from `.mdfx.json`, or list the pipelines
+fn run_pipeline(name: Option<&str>, config_path: Option<&std::path::Path>) -> Result<(), Error> {
+    let config = match config_path {
+        Some(path) => Some(MdfxConfig::load(path)?),
+        None => MdfxConfig::discover(),
+    }
+    .filter(|cfg| !cfg.pipelines.is_empty())
+    .ok_or_else(|| {
+        Error::ParseError(
+            "No pipelines configured: add a \"pipelines\" section to .mdfx.json".to_string(),
+        )
+    })?;
+    let mut names: Vec<&String> = config.pipelines.keys().collect();
+    names.sort();
+
+    let Some(name) = name else {
+        for name in names {
+            let pipeline = &config.pipelines[name];
+            let stages: Vec<String> = pipeline.stages.iter().map(|s| s.to_string()).collect();
+            println!(
+                "{}  {} → {}",
+                name.bold(),
+                pipeline.input,
+                stages.join(" → ")
+            );
+        }
+        return Ok(());
+    };
+    let pipeline = config.pipelines.get(name).ok_or_else(|| {
+        Error::ParseError(format!(
+            "Unknown pipeline '{}'. Available: {}",
+            name,
+            names
+                .iter()
+                .map(|n| n.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    })?;
+
+    let resolve_target = |name: &str| {
+        get_target(name).ok_or_else(|| {
+            Error::ParseError(format!(
+                "Unknown target '{}'. Available: {}",
+                name,
+                available_targets().join(", ")
+            ))
+        })
+    };
+    // Validate every stage before running any, so a typo doesn't leave
+    // half the outputs written
+    for stage in &pipeline.stages {
+        if let PipelineStage::Process(target)
+        | PipelineStage::PostProcess(target)
+        | PipelineStage::Compat(target) = stage
+        {
+            resolve_target(target)?;
+        }
+    }
+
+    let input = PathBuf::from(&pipeline.input);
+    let mut text = fs::read_to_string(&input).map_err(|e| read_failed(&input, e))?;
+    // Where the text would live, for resolving relative links
+    let mut location = pipeline.output.as_ref().map_or(input, PathBuf::from);
+
+    for stage in &pipeline.stages {
+        info!(status = "Stage:", "{}", stage);
+        text = match stage {
+            PipelineStage::Process(name) => {
+                let mut target = resolve_target(name)?;
+                target.configure(&config);
+                let overrides = config.target_overrides(target.name());
+                let backend_type = overrides
+                    .backend
+                    .unwrap_or_else(|| target.preferred_backend());
+                let assets_dir = overrides
+                    .assets_dir
+                    .as_deref()
+                    .unwrap_or(DEFAULT_ASSETS_DIR);
+                let parser = target_parser(
+                    target.as_ref(),
+                    backend_type,
+                    assets_dir,
+                    None,
+                    Some(&config),
+                )?;
+                let processed = parser.process_with_assets(&text)?;
+                processed.write_assets(&mut DiskFs)?;
+                let markdown = if overrides.runs_post_process() {
+                    target.post_process(&processed.markdown)?
+                } else {
+                    processed.markdown
+                };
+                overrides.apply_wrap(markdown)
+            }
+            PipelineStage::PostProcess(name) => {
+                let mut target = resolve_target(name)?;
+                target.configure(&config);
+                target.post_process(&text)?
+            }
+            PipelineStage::Wrap(width) => mdfx::reflow::reflow(&text, *width),
+            PipelineStage::LinkCheck => {
+                let documents = [(location.clone(), text)];
+                lint::lint_links_in(
+                    &documents,
+                    std::path::Path::new("."),
+                    LintFormat::Text,
+                    #[cfg(feature = "fetch")]
+                    None,
+                )?;
+                let [(_, text)] = documents;
+                text
+            }
+            PipelineStage::Compat(name) => {
+                let target = resolve_target(name)?;
+                let documents = [(location.clone(), text)];
+                lint::lint_compat_in(&documents, target.as_ref(), LintFormat::Text, false)?;
+                let [(_, text)] = documents;
+                text
+            }
+            PipelineStage::Write(path) => {
+                write_pipeline_output(path, &text)?;
+                location = PathBuf::from(path);
+                text
+            }
+        };
+    }
+
+    match pipeline.output {
+        Some(ref path) => write_pipeline_output(path, &text),
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+/// Write pipeline text to `path`, creating its directory
+fn write_pipeline_output(path: &str, text: &str) -> Result<(), Error> {
+    let path = std::path::Path::new(path);
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(Error::IoError)?;
+    }
+    fs::write(path, text).map_err(Error::IoError)?;
+    info!(status = "Wrote:", "{}", path.display());
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn watch_file(
     input: PathBuf,
     output: PathBuf,
     target_name: &str,
     backend_override: Option<&str>,
-    assets_dir: &str,
+    assets_dir: Option<&str>,
     assets_prefix: Option<&str>,
     palette_path: Option<&std::path::Path>,
     debounce_ms: u64,
+    inline: Option<&str>,
     config_path: Option<&std::path::Path>,
 ) -> Result<(), Error> {
     // Validate input file exists
@@ -1566,7 +4098,7 @@ fn watch_file(
     println!();
 
     // Initial build
-    println!("{} Initial build...", "[watch]".cyan());
+    info!(status = "[watch]", "Initial build...");
     match process_file(
         Some(input.clone()),
         Some(output.clone()),
@@ -1575,12 +4107,25 @@ fn watch_file(
         backend_override,
         assets_dir,
         assets_prefix,
+        None,
         palette_path,
         config_path,
         None, // watch mode doesn't support fetch currently
+        None,
+        None,
+        None,
+        inline,
+        LiveBadgeCache::default(),
+        true, // so live badges render as placeholders
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
     ) {
-        Ok(()) => println!("{} Build complete", "[watch]".green()),
-        Err(e) => eprintln!("{} Build failed: {}", "[watch]".red(), e),
+        Ok(()) => info!(status = "[watch]", "Build complete"),
+        Err(e) => error!(status = "[watch]", "Build failed: {}", e),
     }
 
     // Set up file watcher
@@ -1608,7 +4153,7 @@ fn watch_file(
 
                 if is_our_file && event.kind.is_modify() {
                     println!();
-                    println!("{} File changed, rebuilding...", "[watch]".cyan());
+                    info!(status = "[watch]", "File changed, rebuilding...");
 
                     match process_file(
                         Some(input.clone()),
@@ -1618,20 +4163,33 @@ fn watch_file(
                         backend_override,
                         assets_dir,
                         assets_prefix,
+                        None,
                         palette_path,
                         config_path,
                         None, // watch mode doesn't support fetch currently
+                        None,
+                        None,
+                        None,
+                        inline,
+                        LiveBadgeCache::default(),
+                        true,
+                        false,
+                        false,
+                        false,
+                        false,
+                        false,
+                        false,
                     ) {
-                        Ok(()) => println!("{} Build complete", "[watch]".green()),
-                        Err(e) => eprintln!("{} Build failed: {}", "[watch]".red(), e),
+                        Ok(()) => info!(status = "[watch]", "Build complete"),
+                        Err(e) => error!(status = "[watch]", "Build failed: {}", e),
                     }
                 }
             }
             Ok(Err(e)) => {
-                eprintln!("{} Watch error: {}", "[watch]".red(), e);
+                error!(status = "[watch]", "Watch error: {}", e);
             }
             Err(e) => {
-                eprintln!("{} Channel error: {}", "[watch]".red(), e);
+                error!(status = "[watch]", "Channel error: {}", e);
                 break;
             }
         }
@@ -1641,16 +4199,234 @@ fn watch_file(
 }
 
 #[cfg(feature = "lsp")]
-fn install_lsp_extension(editor: &str) -> Result<(), Error> {
-    match editor.to_lowercase().as_str() {
-        "vscode" | "code" => install_vscode_extension(),
+fn install_lsp_extension(editor: &str, print_config: bool) -> Result<(), Error> {
+    // Find mdfx binary path
+    let mdfx_path = std::env::current_exe()
+        .map_err(Error::IoError)?
+        .to_string_lossy()
+        .to_string();
+
+    match (editor.to_lowercase().as_str(), print_config) {
+        ("vscode" | "code", false) => install_vscode_extension(),
+        ("vscode" | "code", true) => {
+            println!("// package.json");
+            println!("{}", generate_package_json(&mdfx_path));
+            println!();
+            println!("// extension.js");
+            print!("{}", generate_extension_js(&mdfx_path));
+            Ok(())
+        }
+        ("neovim" | "nvim", false) => install_neovim_plugin(&mdfx_path),
+        ("neovim" | "nvim", true) => {
+            print!("{}", generate_neovim_config(&mdfx_path));
+            Ok(())
+        }
+        ("helix" | "hx", false) => install_helix_config(&mdfx_path),
+        ("helix" | "hx", true) => {
+            print!("{}", generate_helix_config(&mdfx_path));
+            Ok(())
+        }
         _ => Err(Error::ParseError(format!(
-            "Unsupported editor '{}'. Currently supported: vscode",
+            "Unsupported editor '{}'. Currently supported: vscode, neovim, helix",
             editor
         ))),
     }
 }
 
+#[cfg(feature = "lsp")]
+fn install_neovim_plugin(mdfx_path: &str) -> Result<(), Error> {
+    println!("{}", "Installing mdfx LSP plugin for Neovim...".bold());
+    println!();
+
+    let plugin_dir = get_config_home()?.join("nvim").join("lua");
+    let plugin_path = plugin_dir.join("mdfx.lua");
+
+    println!("  {} {}", "Plugin path:".cyan(), plugin_path.display());
+    println!("  {} {}", "mdfx binary:".cyan(), mdfx_path);
+    println!();
+
+    fs::create_dir_all(&plugin_dir).map_err(Error::IoError)?;
+    fs::write(&plugin_path, generate_neovim_plugin(mdfx_path)).map_err(Error::IoError)?;
+    println!("  {} lua/mdfx.lua", "Created:".green());
+
+    println!();
+    println!("{}", "✓ Neovim plugin installed!".green().bold());
+    println!();
+    println!("{}", "Next steps:".bold());
+    println!("  1. Make sure nvim-lspconfig is installed");
+    println!("  2. Add to your init.lua:  require('mdfx').setup()");
+    println!("  3. Open a .md file and type {{{{ui:tech: to see completions");
+
+    Ok(())
+}
+
+#[cfg(feature = "lsp")]
+fn install_helix_config(mdfx_path: &str) -> Result<(), Error> {
+    println!("{}", "Installing mdfx language server for Helix...".bold());
+    println!();
+
+    let helix_dir = get_config_home()?.join("helix");
+    let languages_path = helix_dir.join("languages.toml");
+
+    println!("  {} {}", "Config path:".cyan(), languages_path.display());
+    println!("  {} {}", "mdfx binary:".cyan(), mdfx_path);
+    println!();
+
+    let existing = fs::read_to_string(&languages_path).unwrap_or_default();
+    if existing.contains("[language-server.mdfx]") {
+        println!(
+            "  {} languages.toml already configures mdfx",
+            "Skipped:".yellow()
+        );
+        return Ok(());
+    }
+
+    // Helix rejects duplicate language entries, so only add the server
+    // definition when the user already customizes markdown
+    let has_markdown = existing.contains("name = \"markdown\"");
+    let snippet = if has_markdown {
+        generate_helix_server_config(mdfx_path)
+    } else {
+        generate_helix_config(mdfx_path)
+    };
+
+    let mut content = existing;
+    if !content.is_empty() && !content.ends_with("\n\n") {
+        content.push_str(if content.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        });
+    }
+    content.push_str(&snippet);
+
+    fs::create_dir_all(&helix_dir).map_err(Error::IoError)?;
+    fs::write(&languages_path, content).map_err(Error::IoError)?;
+    println!("  {} languages.toml", "Updated:".green());
+
+    println!();
+    println!("{}", "✓ Helix configuration installed!".green().bold());
+    if has_markdown {
+        println!();
+        println!("{}", "Next steps:".bold());
+        println!("  Add \"mdfx\" to language-servers in your existing markdown entry:");
+        println!("    language-servers = [\"marksman\", \"mdfx\"]");
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "lsp")]
+fn get_config_home() -> Result<PathBuf, Error> {
+    if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+        if !xdg.is_empty() {
+            return Ok(PathBuf::from(xdg));
+        }
+    }
+
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| Error::ParseError("Could not determine home directory".to_string()))?;
+
+    Ok(PathBuf::from(home).join(".config"))
+}
+
+#[cfg(feature = "lsp")]
+fn lua_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+#[cfg(feature = "lsp")]
+fn generate_neovim_config(mdfx_path: &str) -> String {
+    format!(
+        r#"-- mdfx language server (nvim-lspconfig)
+local lspconfig = require('lspconfig')
+local configs = require('lspconfig.configs')
+
+if not configs.mdfx then
+  configs.mdfx = {{
+    default_config = {{
+      cmd = {{ '{}', 'lsp', 'run' }},
+      filetypes = {{ 'markdown' }},
+      root_dir = lspconfig.util.root_pattern('.mdfx.json', '.git'),
+      single_file_support = true,
+      settings = {{}},
+    }},
+  }}
+end
+
+lspconfig.mdfx.setup({{}})
+"#,
+        lua_string(mdfx_path)
+    )
+}
+
+#[cfg(feature = "lsp")]
+fn generate_neovim_plugin(mdfx_path: &str) -> String {
+    format!(
+        r#"-- mdfx language server plugin for Neovim
+-- Generated by `mdfx lsp install --editor neovim`
+--
+-- Usage (init.lua):
+--   require('mdfx').setup()
+--   require('mdfx').setup({{ cmd = '/path/to/mdfx', lsp = {{ on_attach = my_on_attach }} }})
+
+local M = {{}}
+
+function M.setup(opts)
+  opts = opts or {{}}
+
+  local ok, lspconfig = pcall(require, 'lspconfig')
+  if not ok then
+    vim.notify('mdfx: nvim-lspconfig is required', vim.log.levels.ERROR)
+    return
+  end
+  local configs = require('lspconfig.configs')
+
+  if not configs.mdfx then
+    configs.mdfx = {{
+      default_config = {{
+        cmd = {{ opts.cmd or '{}', 'lsp', 'run' }},
+        filetypes = {{ 'markdown' }},
+        root_dir = lspconfig.util.root_pattern('.mdfx.json', '.git'),
+        single_file_support = true,
+        settings = {{}},
+      }},
+    }}
+  end
+
+  lspconfig.mdfx.setup(opts.lsp or {{}})
+end
+
+return M
+"#,
+        lua_string(mdfx_path)
+    )
+}
+
+#[cfg(feature = "lsp")]
+fn generate_helix_server_config(mdfx_path: &str) -> String {
+    format!(
+        r#"[language-server.mdfx]
+command = "{}"
+args = ["lsp", "run"]
+"#,
+        mdfx_path.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+#[cfg(feature = "lsp")]
+fn generate_helix_config(mdfx_path: &str) -> String {
+    format!(
+        r#"[[language]]
+name = "markdown"
+language-servers = ["marksman", "mdfx"]
+
+{}"#,
+        generate_helix_server_config(mdfx_path)
+    )
+}
+
 #[cfg(feature = "lsp")]
 fn install_vscode_extension() -> Result<(), Error> {
     use std::process::Command;
@@ -1694,7 +4470,7 @@ fn install_vscode_extension() -> Result<(), Error> {
     println!("  {} extension.js", "Created:".green());
 
     // Write TextMate grammar for syntax highlighting
-    let grammar = generate_textmate_grammar();
+    let grammar = mdfx::grammar::textmate(&*Registry::shared()?);
     let grammar_path = syntaxes_dir.join("mdfx.tmLanguage.json");
     fs::write(&grammar_path, grammar).map_err(Error::IoError)?;
     println!("  {} syntaxes/mdfx.tmLanguage.json", "Created:".green());
@@ -1715,17 +4491,19 @@ fn install_vscode_extension() -> Result<(), Error> {
                 println!("  {} npm install", "Success:".green());
             } else {
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                eprintln!("  {} npm install failed: {}", "Warning:".yellow(), stderr);
-                eprintln!();
-                eprintln!("  You may need to run manually:");
-                eprintln!("    cd {} && npm install", extension_path.display());
+                tracing::warn!(
+                    "npm install failed: {}. You may need to run manually: cd {} && npm install",
+                    stderr.trim(),
+                    extension_path.display()
+                );
             }
         }
         Err(e) => {
-            eprintln!("  {} Could not run npm: {}", "Warning:".yellow(), e);
-            eprintln!();
-            eprintln!("  Please install dependencies manually:");
-            eprintln!("    cd {} && npm install", extension_path.display());
+            tracing::warn!(
+                "Could not run npm: {}. Please install dependencies manually: cd {} && npm install",
+                e,
+                extension_path.display()
+            );
         }
     }
 
@@ -1825,80 +4603,6 @@ fn generate_package_json(mdfx_path: &str) -> String {
     )
 }
 
-#[cfg(feature = "lsp")]
-fn generate_textmate_grammar() -> String {
-    // Note: We use format! with doubled braces {{ }} for literal braces in the JSON
-    // and \\{{ for regex escapes of literal braces
-    r##"{
-  "scopeName": "text.mdfx.injection",
-  "injectionSelector": "L:text.html.markdown",
-  "patterns": [
-    { "include": "#mdfx-template" }
-  ],
-  "repository": {
-    "mdfx-template": {
-      "patterns": [
-        { "include": "#mdfx-self-closing" },
-        { "include": "#mdfx-block-open" },
-        { "include": "#mdfx-block-close" }
-      ]
-    },
-    "mdfx-self-closing": {
-      "name": "meta.template.mdfx",
-      "match": "(\\{\\{)(ui:(?:tech|progress|gauge|donut|swatch|row|live)|glyph|frame)(:)([^/\\}]*?)(/\\}\\})",
-      "captures": {
-        "1": { "name": "punctuation.definition.template.begin.mdfx" },
-        "2": { "name": "entity.name.tag.mdfx" },
-        "3": { "name": "punctuation.separator.mdfx" },
-        "4": {
-          "patterns": [
-            { "include": "#mdfx-params" }
-          ]
-        },
-        "5": { "name": "punctuation.definition.template.end.mdfx" }
-      }
-    },
-    "mdfx-block-open": {
-      "name": "meta.template.mdfx.block.open",
-      "match": "(\\{\\{)([a-zA-Z][a-zA-Z0-9_\\.]*)(\\}\\})",
-      "captures": {
-        "1": { "name": "punctuation.definition.template.begin.mdfx" },
-        "2": { "name": "entity.name.function.mdfx" },
-        "3": { "name": "punctuation.definition.template.end.mdfx" }
-      }
-    },
-    "mdfx-block-close": {
-      "name": "meta.template.mdfx.block.close",
-      "match": "(\\{\\{)(/)([a-zA-Z][a-zA-Z0-9_\\.]*)(\\}\\})",
-      "captures": {
-        "1": { "name": "punctuation.definition.template.begin.mdfx" },
-        "2": { "name": "punctuation.separator.mdfx" },
-        "3": { "name": "entity.name.function.mdfx" },
-        "4": { "name": "punctuation.definition.template.end.mdfx" }
-      }
-    },
-    "mdfx-params": {
-      "patterns": [
-        {
-          "name": "variable.parameter.mdfx",
-          "match": "([a-zA-Z_][a-zA-Z0-9_]*)(=)([^:\\}]*)",
-          "captures": {
-            "1": { "name": "variable.parameter.mdfx" },
-            "2": { "name": "keyword.operator.assignment.mdfx" },
-            "3": { "name": "string.unquoted.mdfx" }
-          }
-        },
-        {
-          "name": "variable.other.mdfx",
-          "match": "[^:=\\}]+"
-        }
-      ]
-    }
-  }
-}
-"##.to_string()
-}
-
 #[cfg(feature = "lsp")]
 fn generate_extension_js(mdfx_path: &str) -> String {
     format!(
@@ -1923,7 +4627,17 @@ function activate(context) {{
             {{ scheme: 'untitled', language: 'markdown' }}
         ],
         synchronize: {{
-            fileEvents: vscode.workspace.createFileSystemWatcher('**/*.md')
+            fileEvents: vscode.workspace.createFileSystemWatcher('**/{{*.md,.mdfx.json}}')
+        }},
+        middleware: {{
+            // Show "Preview" CodeLens results in a side panel
+            executeCommand: async (command, args, next) => {{
+                const result = await next(command, args);
+                if (command === 'mdfx.preview' && typeof result === 'string') {{
+                    showPreview(result);
+                }}
+                return result;
+            }}
         }}
     }};
 
@@ -1938,6 +4652,20 @@ function activate(context) {{
     console.log('mdfx LSP client started');
 }}
 
+function showPreview(markdown) {{
+    const panel = vscode.window.createWebviewPanel(
+        'mdfxPreview',
+        'mdfx Preview',
+        vscode.ViewColumn.Beside,
+        {{}}
+    );
+    vscode.commands
+        .executeCommand('markdown.api.render', markdown)
+        .then((html) => {{
+            panel.webview.html = html;
+        }});
+}}
+
 function deactivate() {{
     if (client) {{
         return client.stop();
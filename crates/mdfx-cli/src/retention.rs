@@ -0,0 +1,132 @@
+//! Retention rules for `mdfx clean`
+//!
+//! `mdfx clean` deletes assets that no scanned markdown references, but a
+//! reference can live where the scan doesn't look: a branch that isn't
+//! merged yet, docs outside the glob, a README on another site. A
+//! [`Retention`] keeps such assets anyway when they are recent, mentioned
+//! anywhere in git history, or listed in a keep file.
+
+use glob::Pattern;
+use mdfx::Error;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+/// Assets `mdfx clean` must keep even when nothing references them
+#[derive(Default)]
+pub struct Retention {
+    /// Keep files modified after this time
+    newer_than: Option<SystemTime>,
+    /// SVG filenames mentioned in git history
+    git_history: Option<HashSet<String>>,
+    /// Patterns from the keep file
+    keep: Vec<Pattern>,
+}
+
+impl Retention {
+    /// Keep assets modified within the last `days` days
+    pub fn keep_newer_than(mut self, days: u64) -> Self {
+        let age = Duration::from_secs(days * 24 * 60 * 60);
+        self.newer_than = SystemTime::now().checked_sub(age);
+        self
+    }
+
+    /// Keep assets whose filename appears in the history of any branch
+    pub fn keep_git_history(mut self) -> Result<Self, Error> {
+        self.git_history = Some(git_history_assets()?);
+        Ok(self)
+    }
+
+    /// Keep assets matching the filenames or glob patterns in `path`
+    ///
+    /// One entry per line; blank lines and lines starting with `#` are
+    /// ignored. An entry matches an asset's filename or its path.
+    pub fn keep_listed(mut self, path: &Path) -> Result<Self, Error> {
+        let list = fs::read_to_string(path).map_err(|e| crate::read_failed(path, e))?;
+        for line in list.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let pattern = Pattern::new(line).map_err(|e| {
+                Error::ParseError(format!(
+                    "Invalid pattern '{}' in {}: {}",
+                    line,
+                    path.display(),
+                    e
+                ))
+            })?;
+            self.keep.push(pattern);
+        }
+        Ok(self)
+    }
+
+    /// Why the asset at `path` must be kept, if a rule applies
+    pub fn reason(&self, path: &Path) -> Option<&'static str> {
+        let filename = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+
+        if self
+            .keep
+            .iter()
+            .any(|pattern| pattern.matches(filename) || pattern.matches_path(path))
+        {
+            return Some("keep list");
+        }
+        if let Some(cutoff) = self.newer_than {
+            let modified = fs::metadata(path).and_then(|m| m.modified());
+            if modified.is_ok_and(|modified| modified > cutoff) {
+                return Some("recent");
+            }
+        }
+        if self
+            .git_history
+            .as_ref()
+            .is_some_and(|names| names.contains(filename))
+        {
+            return Some("git history");
+        }
+        None
+    }
+}
+
+/// SVG filenames mentioned in the history of every branch, excluding the
+/// SVG files themselves
+fn git_history_assets() -> Result<HashSet<String>, Error> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--all",
+            "-p",
+            "--format=",
+            "--no-color",
+            "--no-ext-diff",
+            "--",
+            ":/",
+            ":(top,exclude)*.svg",
+        ])
+        .output()
+        .map_err(|e| {
+            Error::IoError(std::io::Error::other(format!(
+                "Failed to run git for --keep-git-history: {}",
+                e
+            )))
+        })?;
+    if !output.status.success() {
+        return Err(Error::IoError(std::io::Error::other(format!(
+            "git log failed for --keep-git-history: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))));
+    }
+
+    let svg_name = Regex::new(r"[\w.-]+\.svg").expect("valid regex");
+    let history = String::from_utf8_lossy(&output.stdout);
+    Ok(svg_name
+        .find_iter(&history)
+        .map(|m| m.as_str().to_string())
+        .collect())
+}
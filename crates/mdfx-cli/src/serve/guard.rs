@@ -0,0 +1,444 @@
+//! Limits for exposing `mdfx serve` publicly
+//!
+//! A [`Guard`] decides which requests get rendered: URLs can be required to
+//! carry an HMAC signature, live badges, remote icon hosts and parameters
+//! can be limited to an allowlist, and each client IP gets a fixed number of
+//! requests per minute. The default guard allows everything, which suits a
+//! server on localhost.
+
+use hmac::{Hmac, Mac};
+use mdfx::ast::{self, Node, NodeKind};
+use mdfx::components::param_schema::normalize_key;
+use mdfx::Registry;
+use sha2::Sha256;
+use std::collections::{BTreeSet, HashMap};
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Environment variable holding the signing key
+pub const KEY_ENV: &str = "MDFX_SERVE_KEY";
+
+/// Query parameter carrying the signature
+pub(super) const SIG_PARAM: &str = "sig";
+
+/// Length of a rate limit window
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Number of tracked clients before expired windows are dropped
+const MAX_TRACKED_CLIENTS: usize = 4096;
+
+/// Why a request was refused
+#[derive(Debug, PartialEq)]
+pub enum Rejection {
+    /// Signature missing or wrong, or a disallowed source or parameter
+    Forbidden(String),
+    /// Too many requests; retry after this many seconds
+    RateLimited(u64),
+}
+
+/// Request policy for the badge server
+#[derive(Default)]
+pub struct Guard {
+    key: Option<Vec<u8>>,
+    allowed_sources: Option<BTreeSet<String>>,
+    allowed_icon_hosts: Option<BTreeSet<String>>,
+    allowed_params: Option<BTreeSet<String>>,
+    limiter: Option<RateLimiter>,
+}
+
+impl Guard {
+    /// Require every request to be signed with `key`
+    pub fn with_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Only render `{{ui:live:...}}` badges from these sources
+    pub fn allow_sources(mut self, sources: impl IntoIterator<Item = String>) -> Self {
+        self.allowed_sources = Some(sources.into_iter().collect());
+        self
+    }
+
+    /// Only fetch remote icons (`icon=url:https://...`) from these hosts
+    ///
+    /// Once sources are limited with [`allow_sources`](Self::allow_sources),
+    /// remote icons are refused unless their host is listed here.
+    pub fn allow_icon_hosts(mut self, hosts: impl IntoIterator<Item = String>) -> Self {
+        self.allowed_icon_hosts = Some(hosts.into_iter().map(|h| h.to_lowercase()).collect());
+        self
+    }
+
+    /// Only accept these `key=value` component parameters
+    ///
+    /// Keys are compared in their canonical spelling, so `bgColor`,
+    /// `bg-color` and `bg_color` are the same parameter.
+    pub fn allow_params(mut self, params: impl IntoIterator<Item = String>) -> Self {
+        self.allowed_params = Some(params.into_iter().map(|p| normalize_key(&p)).collect());
+        self
+    }
+
+    /// Allow each client IP `per_minute` requests per minute
+    pub fn rate_limit(mut self, per_minute: u32) -> Self {
+        self.limiter = Some(RateLimiter::new(per_minute));
+        self
+    }
+
+    /// Whether URLs must be signed
+    pub fn requires_signature(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Count a request from `client` against its rate limit
+    pub fn admit(&self, client: Option<IpAddr>, now: Instant) -> Result<(), Rejection> {
+        match (&self.limiter, client) {
+            (Some(limiter), Some(ip)) => limiter.admit(ip, now),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the `sig` parameter of a request target (path and query)
+    pub fn verify(&self, url: &str) -> Result<(), Rejection> {
+        let Some(key) = &self.key else {
+            return Ok(());
+        };
+        let (message, sig) = split_signature(url);
+        let sig = sig
+            .and_then(decode_hex)
+            .ok_or_else(|| Rejection::Forbidden("Missing or malformed signature".to_string()))?;
+        mac(key, &message)
+            .verify_slice(&sig)
+            .map_err(|_| Rejection::Forbidden("Invalid signature".to_string()))
+    }
+
+    /// Append a signature to a request target
+    ///
+    /// Returns `None` if no key is configured.
+    pub fn sign(&self, url: &str) -> Option<String> {
+        let key = self.key.as_ref()?;
+        let (message, _) = split_signature(url);
+        let sig: String = mac(key, &message)
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        let separator = if message.contains('?') { '&' } else { '?' };
+        Some(format!("{}{}{}={}", message, separator, SIG_PARAM, sig))
+    }
+
+    /// Check every component in parsed templates against the allowlists
+    pub fn check(&self, nodes: &[Node]) -> Result<(), Rejection> {
+        let mut result = Ok(());
+        ast::walk(nodes, &mut |node| {
+            if result.is_ok() {
+                result = self.check_node(node);
+            }
+        });
+        result
+    }
+
+    fn check_node(&self, node: &Node) -> Result<(), Rejection> {
+        let NodeKind::Component { name, args, .. } = &node.kind else {
+            return Ok(());
+        };
+
+        let params = params(name, args);
+        if let Some(allowed) = &self.allowed_params {
+            if let Some((key, _)) = params.iter().find(|(key, _)| !allowed.contains(key)) {
+                return Err(Rejection::Forbidden(format!(
+                    "Parameter '{}' is not allowed",
+                    key
+                )));
+            }
+        }
+
+        // The server downloads remote icons, so they are a source too
+        for url in params
+            .iter()
+            .filter(|(key, _)| key == "icon")
+            .filter_map(|(_, value)| mdfx::icon::remote_url(value))
+        {
+            self.check_icon_url(url)?;
+        }
+
+        if let Some(allowed) = &self.allowed_sources {
+            let source = match name.as_str() {
+                "live" => args
+                    .iter()
+                    .find(|arg| !arg.contains('='))
+                    .map(String::as_str),
+                // ci-matrix reads its workflows from the actions source
+                "ci-matrix" => Some("actions"),
+                _ => return Ok(()),
+            };
+            if !source.is_some_and(|source| allowed.contains(source)) {
+                return Err(Rejection::Forbidden(format!(
+                    "Live source '{}' is not allowed",
+                    source.unwrap_or_default()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_icon_url(&self, url: &str) -> Result<(), Rejection> {
+        let host = icon_host(url);
+        let allowed = match (&self.allowed_icon_hosts, &self.allowed_sources) {
+            (Some(hosts), _) => host.as_ref().is_some_and(|host| hosts.contains(host)),
+            (None, Some(_)) => false,
+            (None, None) => true,
+        };
+        if allowed {
+            return Ok(());
+        }
+        Err(Rejection::Forbidden(format!(
+            "Remote icon host '{}' is not allowed",
+            host.unwrap_or_default()
+        )))
+    }
+}
+
+/// `key=value` parameters of a component use, keyed by the name the
+/// component reads them under
+fn params<'a>(component: &str, args: &'a [String]) -> Vec<(String, &'a str)> {
+    let registry = Registry::shared().ok();
+    let definition = registry.as_ref().and_then(|r| r.component(component));
+    args.iter()
+        .filter_map(|arg| arg.split_once('='))
+        .map(|(key, value)| {
+            let key = match definition {
+                Some(definition) => definition.param_name(key),
+                None => normalize_key(key),
+            };
+            (key, value)
+        })
+        .collect()
+}
+
+/// Lowercase host of an `https://` URL, without user info or port
+fn icon_host(url: &str) -> Option<String> {
+    let rest = url.strip_prefix("https://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = match host.strip_prefix('[') {
+        // IPv6 literal: [::1]:443
+        Some(literal) => literal.split(']').next()?,
+        None => host.split(':').next()?,
+    };
+    (!host.is_empty()).then(|| host.to_lowercase())
+}
+
+/// Fixed-window request counter per client IP
+struct RateLimiter {
+    per_minute: u32,
+    windows: Mutex<HashMap<IpAddr, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn admit(&self, ip: IpAddr, now: Instant) -> Result<(), Rejection> {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        if windows.len() >= MAX_TRACKED_CLIENTS {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < WINDOW);
+        }
+
+        let (start, count) = windows.entry(ip).or_insert((now, 0));
+        let elapsed = now.duration_since(*start);
+        if elapsed >= WINDOW {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.per_minute {
+            let retry_after = WINDOW.saturating_sub(now.duration_since(*start));
+            return Err(Rejection::RateLimited(retry_after.as_secs().max(1)));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+fn mac(key: &[u8], message: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message.as_bytes());
+    mac
+}
+
+/// Split a request target into the signed part (without `sig`) and the signature
+fn split_signature(url: &str) -> (String, Option<&str>) {
+    let Some((path, query)) = url.split_once('?') else {
+        return (url.to_string(), None);
+    };
+
+    let mut sig = None;
+    let mut kept = Vec::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        match pair.split_once('=') {
+            Some((SIG_PARAM, value)) => sig = Some(value),
+            _ => kept.push(pair),
+        }
+    }
+    if kept.is_empty() {
+        (path.to_string(), sig)
+    } else {
+        (format!("{}?{}", path, kept.join("&")), sig)
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let guard = Guard::default().with_key("secret");
+        let signed = guard.sign("/badge/tech/rust?style=flat").unwrap();
+        assert!(signed.starts_with("/badge/tech/rust?style=flat&sig="));
+        assert_eq!(guard.verify(&signed), Ok(()));
+
+        let tampered = signed.replace("flat", "plastic");
+        assert!(matches!(
+            guard.verify(&tampered),
+            Err(Rejection::Forbidden(_))
+        ));
+        assert!(guard.verify("/badge/tech/rust").is_err());
+        assert!(guard.verify("/badge/tech/rust?sig=zz").is_err());
+
+        let other = Guard::default().with_key("other");
+        assert!(other.verify(&signed).is_err());
+    }
+
+    #[test]
+    fn test_unsigned_guard_allows_everything() {
+        let guard = Guard::default();
+        assert_eq!(guard.verify("/badge/tech/rust"), Ok(()));
+        assert_eq!(guard.sign("/badge/tech/rust"), None);
+        let nodes = ast::parse("{{ui:live:github:rust-lang/rust:stars:color=red/}}").unwrap();
+        assert_eq!(guard.check(&nodes), Ok(()));
+    }
+
+    #[test]
+    fn test_source_allowlist() {
+        let guard = Guard::default().allow_sources(["crates".to_string()]);
+        let allowed = ast::parse("{{ui:live:crates:serde:version/}}").unwrap();
+        let denied = ast::parse("{{ui:live:github:rust-lang/rust:stars/}}").unwrap();
+        assert_eq!(guard.check(&allowed), Ok(()));
+        assert!(guard.check(&denied).is_err());
+
+        let matrix = ast::parse("{{ui:ci-matrix:owner/repo:workflows=test/}}").unwrap();
+        assert!(guard.check(&matrix).is_err());
+        let guard = Guard::default().allow_sources(["actions".to_string()]);
+        assert_eq!(guard.check(&matrix), Ok(()));
+    }
+
+    #[test]
+    fn test_remote_icon_hosts() {
+        let icon =
+            |url: &str| ast::parse(&format!("{{{{ui:tech:acme:icon=url:{}/}}}}", url)).unwrap();
+        let cdn = icon("https://cdn.simpleicons.org/rust");
+        let internal = icon("https://169.254.169.254/latest/meta-data");
+        let disguised = icon("https://cdn.simpleicons.org@169.254.169.254:443/x.svg");
+
+        // Unrestricted guards fetch any icon
+        assert_eq!(Guard::default().check(&internal), Ok(()));
+
+        // Restricting sources refuses remote icons until their hosts are listed
+        let guard = Guard::default().allow_sources(["crates".to_string()]);
+        assert!(guard.check(&cdn).is_err());
+
+        let guard = guard.allow_icon_hosts(["CDN.simpleicons.org".to_string()]);
+        assert_eq!(guard.check(&cdn), Ok(()));
+        assert_eq!(
+            guard.check(&internal),
+            Err(Rejection::Forbidden(
+                "Remote icon host '169.254.169.254' is not allowed".to_string()
+            ))
+        );
+        assert!(guard.check(&disguised).is_err());
+
+        for spelling in ["ICON", "Icon"] {
+            let shouted = ast::parse(&format!(
+                "{{{{ui:tech:x:{}=url:https://169.254.169.254/latest/meta-data/}}}}",
+                spelling
+            ))
+            .unwrap();
+            assert!(guard.check(&shouted).is_err(), "{}", spelling);
+        }
+
+        let nested = ast::parse(
+            "{{ui:row}}{{ui:swatch:red:icon=url:https://169.254.169.254/x.svg/}}{{/ui}}",
+        )
+        .unwrap();
+        assert!(guard.check(&nested).is_err());
+    }
+
+    #[rstest]
+    #[case("https://cdn.example.com/icon.svg", Some("cdn.example.com"))]
+    #[case("https://Cdn.Example.com:8443/icon.svg", Some("cdn.example.com"))]
+    #[case("https://user@internal.host/icon.svg", Some("internal.host"))]
+    #[case("https://[::1]:443/icon.svg", Some("::1"))]
+    #[case("https://cdn.example.com?x=1", Some("cdn.example.com"))]
+    #[case("http://cdn.example.com/icon.svg", None)]
+    fn test_icon_host(#[case] url: &str, #[case] host: Option<&str>) {
+        assert_eq!(icon_host(url).as_deref(), host);
+    }
+
+    #[test]
+    fn test_param_allowlist_checks_nested_components() {
+        let guard = Guard::default().allow_params(["style".to_string()]);
+        let allowed = ast::parse("{{ui:tech:rust:style=flat/}}").unwrap();
+        let denied = ast::parse("{{ui:row}}{{ui:tech:rust:bg=red/}}{{/ui}}").unwrap();
+        assert_eq!(guard.check(&allowed), Ok(()));
+        assert_eq!(
+            guard.check(&denied),
+            Err(Rejection::Forbidden(
+                "Parameter 'bg' is not allowed".to_string()
+            ))
+        );
+
+        // Other spellings of a key are the same parameter
+        let shouted = ast::parse("{{ui:tech:rust:STYLE=flat/}}").unwrap();
+        assert_eq!(guard.check(&shouted), Ok(()));
+        let sneaky = ast::parse("{{ui:tech:rust:ICON=url:https://x.test/a.svg/}}").unwrap();
+        assert_eq!(
+            guard.check(&sneaky),
+            Err(Rejection::Forbidden(
+                "Parameter 'icon' is not allowed".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_per_ip() {
+        let guard = Guard::default().rate_limit(2);
+        let start = Instant::now();
+        let a = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        let b = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)));
+
+        assert_eq!(guard.admit(a, start), Ok(()));
+        assert_eq!(guard.admit(a, start), Ok(()));
+        assert_eq!(guard.admit(a, start), Err(Rejection::RateLimited(60)));
+        assert_eq!(guard.admit(b, start), Ok(()));
+        assert_eq!(guard.admit(a, start + WINDOW), Ok(()));
+    }
+}
@@ -0,0 +1,418 @@
+//! Local badge service for mdfx templates
+//!
+//! `mdfx serve` answers plain HTTP GET requests with rendered SVGs, so badges
+//! can be linked from anywhere that shows an image:
+//!
+//! - `/` - HTML playground for trying templates
+//! - `/badge/<component>/<args...>?key=value` - renders `{{ui:component:args:key=value/}}`
+//! - `/render?template=...` - renders any template that produces one image
+//!
+//! A [`Guard`] limits what a public server renders (see `guard`).
+//!
+//! Enable with: `cargo install mdfx-cli --features serve`
+
+mod guard;
+
+pub use guard::{Guard, Rejection, KEY_ENV};
+
+use mdfx::{Error, ParserOptions, RenderedAsset, TemplateParser};
+use std::net::IpAddr;
+use std::time::Instant;
+use tiny_http::{Header, Server};
+use tracing::{debug, info};
+
+/// Playground page served at `/`
+const PLAYGROUND: &str = include_str!("playground.html");
+
+/// How long clients may cache a rendered badge
+const CACHE_CONTROL: &str = "public, max-age=300";
+
+/// An HTTP response before it is written to the socket
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    /// Headers besides `Content-Type`
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    fn svg(bytes: Vec<u8>) -> Self {
+        Self {
+            status: 200,
+            content_type: "image/svg+xml",
+            headers: vec![("Cache-Control", CACHE_CONTROL.to_string())],
+            body: bytes,
+        }
+    }
+
+    fn html(page: &str) -> Self {
+        Self {
+            status: 200,
+            content_type: "text/html; charset=utf-8",
+            headers: Vec::new(),
+            body: page.as_bytes().to_vec(),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            headers: Vec::new(),
+            body: message.into().into_bytes(),
+        }
+    }
+
+    fn rejected(rejection: Rejection) -> Self {
+        match rejection {
+            Rejection::Forbidden(message) => Self::error(403, message),
+            Rejection::RateLimited(retry_after) => {
+                let mut response = Self::error(429, "Too many requests");
+                response
+                    .headers
+                    .push(("Retry-After", retry_after.to_string()));
+                response
+            }
+        }
+    }
+}
+
+/// Options for the server's parser
+///
+/// Requests come from anyone who can reach the server, so the parser is
+/// untrusted: no project files, commands or plugins. Live badges and remote
+/// icons stay on, limited by the [`Guard`].
+pub fn parser_options() -> ParserOptions {
+    ParserOptions {
+        allow_network: true,
+        ..ParserOptions::untrusted()
+    }
+}
+
+/// Renders badge requests with one parser shared by every request
+pub struct BadgeServer {
+    parser: TemplateParser,
+    guard: Guard,
+}
+
+impl BadgeServer {
+    /// Serve templates with `parser`, which should use an SVG backend,
+    /// refusing requests that `guard` doesn't allow
+    pub fn new(parser: TemplateParser, guard: Guard) -> Self {
+        Self { parser, guard }
+    }
+
+    /// Answer a request for `url` (path and query) from `client`
+    pub fn handle(&self, method: &str, url: &str, client: Option<IpAddr>) -> Response {
+        if method != "GET" && method != "HEAD" {
+            return Response::error(405, "Only GET requests are supported");
+        }
+        if let Err(rejection) = self.guard.admit(client, Instant::now()) {
+            return Response::rejected(rejection);
+        }
+
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let query: Vec<_> = query_pairs(query)
+            .into_iter()
+            .filter(|(key, _)| key != guard::SIG_PARAM)
+            .collect();
+
+        // The playground renders whatever is typed, so it can't be signed
+        if path == "/" && !self.guard.requires_signature() {
+            return Response::html(PLAYGROUND);
+        }
+        if let Err(rejection) = self.guard.verify(url) {
+            return Response::rejected(rejection);
+        }
+        if path == "/render" {
+            return match query.iter().find(|(key, _)| key == "template") {
+                Some((_, template)) => self.render(template),
+                None => Response::error(400, "Missing ?template= parameter"),
+            };
+        }
+        if let Some(rest) = path.strip_prefix("/badge/") {
+            return match badge_template(rest, &query) {
+                Ok(template) => self.render(&template),
+                Err(message) => Response::error(400, message),
+            };
+        }
+        Response::error(404, format!("No route for {}", path))
+    }
+
+    /// Render a template that must produce exactly one image
+    fn render(&self, template: &str) -> Response {
+        let nodes = match self.parser.parse_ast(template) {
+            Ok(nodes) => nodes,
+            Err(e) => return Response::error(400, e.to_string()),
+        };
+        if let Err(rejection) = self.guard.check(&nodes) {
+            return Response::rejected(rejection);
+        }
+        let processed = match self.parser.render_ast(&nodes) {
+            Ok(processed) => processed,
+            Err(e) => return Response::error(400, e.to_string()),
+        };
+
+        let mut images = processed
+            .assets
+            .into_iter()
+            .filter_map(|asset| match asset {
+                RenderedAsset::File { bytes, .. } => Some(bytes),
+                RenderedAsset::InlineMarkdown(_) => None,
+            });
+        match (images.next(), images.next()) {
+            (Some(bytes), None) => Response::svg(bytes),
+            (None, _) => Response::error(422, "Template does not render an image"),
+            (Some(_), Some(_)) => Response::error(422, "Template renders more than one image"),
+        }
+    }
+}
+
+/// Build `{{ui:component:args:key=value/}}` from a `/badge/` path and query
+///
+/// Values may not contain template syntax, so a request can only ever
+/// render the one component it names.
+fn badge_template(path: &str, query: &[(String, String)]) -> Result<String, String> {
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(percent_decode)
+        .collect();
+    if segments.is_empty() {
+        return Err("Missing component name, e.g. /badge/tech/rust".to_string());
+    }
+
+    let mut parts = Vec::with_capacity(segments.len() + query.len());
+    for segment in &segments {
+        check_value(segment)?;
+        parts.push(segment.clone());
+    }
+    for (key, value) in query {
+        check_value(key)?;
+        check_value(value)?;
+        parts.push(format!("{}={}", key, value));
+    }
+    Ok(format!("{{{{ui:{}/}}}}", parts.join(":")))
+}
+
+fn check_value(value: &str) -> Result<(), String> {
+    if value.contains(['{', '}', ':']) {
+        return Err(format!("Invalid character in '{}'", value));
+    }
+    Ok(())
+}
+
+/// Split a query string into decoded key/value pairs
+fn query_pairs(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Decode `%XX` escapes and `+` as space, leaving invalid escapes as written
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        if let Some(byte) = escaped {
+            out.push(byte);
+            i += 3;
+            continue;
+        }
+        out.push(if bytes[i] == b'+' { b' ' } else { bytes[i] });
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Listen on `addr` and answer requests until the process is stopped
+pub fn run(server: BadgeServer, addr: &str) -> Result<(), Error> {
+    let http = Server::http(addr).map_err(|e| {
+        Error::IoError(std::io::Error::other(format!(
+            "Failed to listen on {}: {}",
+            addr, e
+        )))
+    })?;
+    info!(status = "Serving:", "http://{}", addr);
+
+    for request in http.incoming_requests() {
+        let client = request.remote_addr().map(|addr| addr.ip());
+        let response = server.handle(request.method().as_str(), request.url(), client);
+        debug!(
+            method = %request.method(),
+            url = request.url(),
+            status = response.status,
+            "handled request"
+        );
+
+        let mut reply = tiny_http::Response::from_data(response.body)
+            .with_status_code(response.status)
+            .with_header(header("Content-Type", response.content_type));
+        for (name, value) in &response.headers {
+            reply.add_header(header(name, value));
+        }
+        if let Err(e) = request.respond(reply) {
+            debug!("Failed to send response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("header is ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mdfx::renderer::svg::SvgBackend;
+
+    fn server() -> BadgeServer {
+        guarded(Guard::default())
+    }
+
+    fn guarded(guard: Guard) -> BadgeServer {
+        let mut parser = TemplateParser::with_backend(Box::new(SvgBackend::new("badges"))).unwrap();
+        parser.set_options(parser_options());
+        BadgeServer::new(parser, guard)
+    }
+
+    fn get(server: &BadgeServer, url: &str) -> Response {
+        server.handle("GET", url, None)
+    }
+
+    #[test]
+    fn test_badge_route_renders_svg() {
+        let response = get(&server(), "/badge/tech/rust?style=flat-square");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "image/svg+xml");
+        assert!(String::from_utf8(response.body)
+            .unwrap()
+            .starts_with("<svg"));
+    }
+
+    #[test]
+    fn test_render_route_decodes_template() {
+        let response = get(
+            &server(),
+            "/render?template=%7B%7Bui%3Aswatch%3Acobalt%2F%7D%7D",
+        );
+        assert_eq!(response.status, 200);
+        assert!(String::from_utf8(response.body).unwrap().contains("<svg"));
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let server = server();
+        assert_eq!(get(&server, "/render").status, 400);
+        assert_eq!(get(&server, "/render?template=plain+text").status, 422);
+        assert_eq!(
+            get(
+                &server,
+                "/render?template=%7B%7Bnosuch%7D%7Dx%7B%7B%2Fnosuch%7D%7D"
+            )
+            .status,
+            400
+        );
+        assert_eq!(get(&server, "/nowhere").status, 404);
+        assert_eq!(server.handle("POST", "/render", None).status, 405);
+    }
+
+    #[test]
+    fn test_render_refuses_project_files() {
+        let render = |template: &str| {
+            let encoded: String = template
+                .bytes()
+                .map(|byte| format!("%{:02X}", byte))
+                .collect();
+            let response = get(&server(), &format!("/render?template={}", encoded));
+            (response.status, String::from_utf8(response.body).unwrap())
+        };
+
+        let (status, body) = render("{{snippet:/etc/passwd/}}");
+        assert_eq!(status, 400);
+        assert!(
+            body.contains("Not allowed: snippet '/etc/passwd' reads a project file"),
+            "{}",
+            body
+        );
+
+        let (status, body) = render("{{ui:msrv/}}");
+        assert_eq!(status, 400);
+        assert!(body.contains("Not allowed:"), "{}", body);
+
+        // There is no include template, so the text is never read as a path
+        let (status, body) = render("{{include:/etc/passwd/}}");
+        assert_eq!(status, 422);
+        assert!(!body.contains("root:"), "{}", body);
+    }
+
+    #[test]
+    fn test_badge_template_rejects_template_syntax() {
+        let query = vec![("label".to_string(), "x/}}{{ui:live".to_string())];
+        assert!(badge_template("tech/rust", &query).is_err());
+        assert!(badge_template("tech/rust:bg=red", &[]).is_err());
+        assert_eq!(
+            badge_template("tech/rust", &[("style".into(), "flat".into())]).unwrap(),
+            "{{ui:tech:rust:style=flat/}}"
+        );
+    }
+
+    #[test]
+    fn test_playground_page() {
+        let response = get(&server(), "/");
+        assert_eq!(response.status, 200);
+        assert!(response.content_type.starts_with("text/html"));
+    }
+
+    #[test]
+    fn test_signed_server() {
+        let guard = Guard::default().with_key("secret");
+        let signed = guard.sign("/badge/tech/rust?style=flat").unwrap();
+        let server = guarded(guard);
+
+        assert_eq!(get(&server, &signed).status, 200);
+        assert_eq!(get(&server, "/badge/tech/rust?style=flat").status, 403);
+        assert_eq!(get(&server, "/").status, 403);
+    }
+
+    #[test]
+    fn test_guard_rejections() {
+        let server = guarded(
+            Guard::default()
+                .allow_params(["style".to_string()])
+                .rate_limit(2),
+        );
+        let client = Some(IpAddr::from([127, 0, 0, 1]));
+
+        let denied = server.handle("GET", "/badge/tech/rust?bg=red", client);
+        assert_eq!(denied.status, 403);
+        assert_eq!(server.handle("GET", "/badge/tech/rust", client).status, 200);
+
+        let limited = server.handle("GET", "/badge/tech/rust", client);
+        assert_eq!(limited.status, 429);
+        assert!(limited
+            .headers
+            .iter()
+            .any(|(name, _)| *name == "Retry-After"));
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%20b+c"), "a b c");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+}
@@ -0,0 +1,221 @@
+//! Processing statistics for `mdfx process --stats`
+
+use clap::ValueEnum;
+use colored::Colorize;
+use mdfx::TemplateStats;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+/// Output format for the statistics report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatsFormat {
+    /// Human-readable summary
+    Text,
+    /// Single JSON object
+    Json,
+}
+
+/// Asset file outcomes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AssetCounts {
+    /// New files written to disk
+    pub written: usize,
+    /// Files that already existed from a previous run
+    pub skipped: usize,
+    /// Repeated references to an asset already handled in this run
+    pub deduped: usize,
+}
+
+/// Statistics gathered while processing one file
+#[derive(Debug)]
+pub struct ProcessReport {
+    started: Instant,
+    last_mark: Instant,
+    stages: Vec<(&'static str, Duration)>,
+    pub templates: TemplateStats,
+    pub assets: AssetCounts,
+    #[cfg(feature = "fetch")]
+    pub fetch: Option<mdfx_fetch::FetchStats>,
+}
+
+impl ProcessReport {
+    /// Start timing; the first stage runs from now until the first [`mark`](Self::mark)
+    pub fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            last_mark: now,
+            stages: Vec::new(),
+            templates: TemplateStats::default(),
+            assets: AssetCounts::default(),
+            #[cfg(feature = "fetch")]
+            fetch: None,
+        }
+    }
+
+    /// Record the time since the previous mark as the named stage
+    pub fn mark(&mut self, stage: &'static str) {
+        let now = Instant::now();
+        self.stages.push((stage, now - self.last_mark));
+        self.last_mark = now;
+    }
+
+    /// Print the report to stderr
+    pub fn print(&self, format: StatsFormat) {
+        match format {
+            StatsFormat::Text => eprint!("{}", self.to_text()),
+            StatsFormat::Json => eprintln!("{}", self.to_json()),
+        }
+    }
+
+    fn total(&self) -> Duration {
+        self.last_mark - self.started
+    }
+
+    fn to_text(&self) -> String {
+        let t = &self.templates;
+        let mut out = format!("{}\n", "Stats:".bold());
+
+        out.push_str(&format!(
+            "  Templates:  {} (styles {}, frames {}, components {}, live badges {}, other {})\n",
+            t.total, t.styles, t.frames, t.components, t.live_badges, t.other
+        ));
+        if !t.by_component.is_empty() {
+            let parts: Vec<String> = t
+                .by_component
+                .iter()
+                .map(|(name, count)| format!("{} ×{}", name, count))
+                .collect();
+            out.push_str(&format!("  Components: {}\n", parts.join(", ")));
+        }
+
+        let a = &self.assets;
+        out.push_str(&format!(
+            "  Assets:     {} written, {} skipped, {} deduped\n",
+            a.written, a.skipped, a.deduped
+        ));
+
+        #[cfg(feature = "fetch")]
+        if let Some(f) = &self.fetch {
+            match f.hit_rate() {
+                Some(rate) => out.push_str(&format!(
+                    "  Fetch:      {} request(s), {:.0}% cache hit rate ({} hit, {} miss, {} stale, {} network, {} failed), {} downloaded\n",
+                    f.requests,
+                    rate * 100.0,
+                    f.cache_hits,
+                    f.cache_misses,
+                    f.stale_hits,
+                    f.network_fetches,
+                    f.failures,
+                    format_bytes(f.bytes_downloaded)
+                )),
+                None => out.push_str("  Fetch:      no requests\n"),
+            }
+            for (id, source) in &f.sources {
+                let latency = source
+                    .average_latency()
+                    .map(|d| format!(", avg {}", format_duration(d)))
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "    {:<10} {} request(s), {} hit, {} network{}, {}\n",
+                    format!("{}:", id),
+                    source.requests,
+                    source.cache_hits,
+                    source.network_fetches,
+                    latency,
+                    format_bytes(source.bytes_downloaded)
+                ));
+            }
+        }
+
+        let stages: Vec<String> = self
+            .stages
+            .iter()
+            .map(|(name, d)| format!("{} {}", name, format_duration(*d)))
+            .collect();
+        out.push_str(&format!(
+            "  Time:       {} (total {})\n",
+            stages.join(", "),
+            format_duration(self.total())
+        ));
+
+        out
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let stages: serde_json::Map<String, serde_json::Value> = self
+            .stages
+            .iter()
+            .map(|(name, d)| (name.to_string(), json!(millis(*d))))
+            .collect();
+
+        #[allow(unused_mut)]
+        let mut report = json!({
+            "templates": self.templates,
+            "assets": {
+                "written": self.assets.written,
+                "skipped": self.assets.skipped,
+                "deduped": self.assets.deduped,
+            },
+            "timings_ms": stages,
+            "total_ms": millis(self.total()),
+        });
+
+        #[cfg(feature = "fetch")]
+        if let Some(f) = &self.fetch {
+            report["fetch"] = json!({
+                "requests": f.requests,
+                "cache_hits": f.cache_hits,
+                "cache_misses": f.cache_misses,
+                "stale_hits": f.stale_hits,
+                "network_fetches": f.network_fetches,
+                "failures": f.failures,
+                "hit_rate": f.hit_rate(),
+                "bytes_downloaded": f.bytes_downloaded,
+                "sources": f
+                    .sources
+                    .iter()
+                    .map(|(id, s)| {
+                        (
+                            id.clone(),
+                            json!({
+                                "requests": s.requests,
+                                "cache_hits": s.cache_hits,
+                                "cache_misses": s.cache_misses,
+                                "stale_hits": s.stale_hits,
+                                "network_fetches": s.network_fetches,
+                                "failures": s.failures,
+                                "bytes_downloaded": s.bytes_downloaded,
+                                "network_ms": millis(s.network_time),
+                                "average_latency_ms": s.average_latency().map(millis),
+                            }),
+                        )
+                    })
+                    .collect::<serde_json::Map<_, _>>(),
+            });
+        }
+
+        report
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    (d.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+#[cfg(feature = "fetch")]
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1_048_575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    if d.as_secs() > 0 {
+        format!("{:.2}s", d.as_secs_f64())
+    } else {
+        format!("{:.1}ms", d.as_secs_f64() * 1000.0)
+    }
+}
@@ -61,6 +61,98 @@ fn test_convert_script() {
         .success();
 }
 
+#[test]
+fn test_convert_reads_stdin() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["convert", "--style", "mathbold"])
+        .write_stdin("AB\nCD\n")
+        .assert()
+        .success()
+        .stdout("𝐀𝐁\n𝐂𝐃\n");
+}
+
+#[test]
+fn test_convert_multiple_styles() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args([
+            "convert",
+            "-s",
+            "mathbold,fullwidth",
+            "-s",
+            "double-struck",
+            "R",
+        ])
+        .assert()
+        .success()
+        .stdout("𝐑\nＲ\nℝ\n");
+}
+
+#[test]
+fn test_convert_fallback() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["convert", "-s", "italic", "--fallback", "skip", "Top 10"])
+        .assert()
+        .success()
+        .stdout("𝑇𝑜𝑝 \n");
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["convert", "-s", "italic", "--fallback", "error", "Top 10"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("no equivalent for '1'"));
+}
+
+#[test]
+fn test_convert_zalgo_seed() {
+    let zalgo = |seed: &str| {
+        let output = Command::cargo_bin("mdfx")
+            .unwrap()
+            .args(["convert", "-s", "zalgo", "--seed", seed, "Glitch"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert_eq!(zalgo("7"), zalgo("7"));
+    assert_ne!(zalgo("7"), zalgo("8"));
+    assert!(zalgo("7").chars().count() > "Glitch\n".len());
+}
+
+#[cfg(not(feature = "clipboard"))]
+#[test]
+fn test_convert_copy_needs_clipboard_feature() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["convert", "--style", "mathbold", "--copy", "Hi"])
+        .assert()
+        .code(4)
+        .stdout("𝐇𝐢\n")
+        .stderr(predicate::str::contains("`clipboard` feature"));
+}
+
+#[test]
+fn test_convert_list_mapping() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args([
+            "convert",
+            "--style",
+            "mb",
+            "--style",
+            "fullwidth",
+            "--list-mapping",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("A\t𝐀\tＡ\n"))
+        .stdout(predicate::str::contains("z\t𝐳\tｚ\n"));
+}
+
 // =============================================================================
 // LIST COMMAND TESTS (Parameterized)
 // =============================================================================
@@ -178,8 +270,321 @@ fn test_process_with_tech_badge() {
         .success();
 
     let content = fs::read_to_string(&output).unwrap();
-    // Should contain a markdown image reference
-    assert!(content.contains("![](") || content.contains("!["));
+    // The github target states image sizes to avoid layout shift
+    assert!(content.contains("<img src=\""));
+    assert!(content.contains("height=\"20\""));
+}
+
+#[rstest]
+#[case("none", "![](assets/tech_")]
+#[case("suffix", "x20)")]
+fn test_process_image_dimensions_override(#[case] mode: &str, #[case] expected: &str) {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("input.md"), "{{ui:tech:rust/}}").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "process",
+            "-b",
+            "svg",
+            "--assets-dir",
+            "assets",
+            "--image-dimensions",
+            mode,
+            "input.md",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(expected))
+        .stdout(predicate::str::contains("<img").not());
+}
+
+#[rstest]
+#[case("--inline=data-uri", "(data:image/svg+xml;base64,")]
+#[case("--inline=svg", "<svg")]
+#[case("--inline", "(data:image/svg+xml;base64,")]
+fn test_process_inline(#[case] flag: &str, #[case] expected: &str) {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("input.md"), "{{ui:swatch:F41C80/}}").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "process",
+            "--target",
+            "local",
+            "--image-dimensions",
+            "none",
+            flag,
+            "input.md",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(expected));
+
+    assert!(!temp.path().join("assets").exists());
+}
+
+#[rstest]
+#[case("github", "--inline", "doesn't display inline SVGs")]
+#[case("gitlab", "--inline=svg", "doesn't display raw <svg> markup")]
+#[case("local", "--inline=png", "Unknown inline mode 'png'")]
+fn test_process_inline_rejected(#[case] target: &str, #[case] flag: &str, #[case] error: &str) {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("input.md"), "{{ui:swatch:F41C80/}}").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--target", target, flag, "input.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(error));
+}
+
+#[test]
+fn test_process_live_badge_cache_busting() {
+    let temp = TempDir::new().unwrap();
+    let cache = temp.path().join(".mdfx-cache");
+    fs::create_dir(&cache).unwrap();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    fs::write(
+        cache.join("crates_serde_version.json"),
+        format!(r#"{{"value":"1.0.200","created_at":{},"ttl":86400}}"#, now),
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("input.md"),
+        "{{ui:live:crates:serde:version/}}\n{{ui:swatch:accent/}}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "process",
+            "-b",
+            "shields",
+            "--offline",
+            "--cache-bust",
+            "--cache-seconds",
+            "600",
+            "input.md",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("&cacheSeconds=600&v="))
+        // Static badges are left alone
+        .stdout(predicate::str::contains("F41C80?style=flat-square)\n"));
+}
+
+#[test]
+fn test_process_live_badge_unavailable() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("input.md"),
+        "{{ui:live:crates:serde:version/}}\n",
+    )
+    .unwrap();
+    let badge = "Version:%20n/a-6B7280";
+
+    // A refused connection renders a gray n/a badge instead of failing
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .env_remove("NO_PROXY")
+        .env_remove("no_proxy")
+        .args([
+            "process",
+            "-b",
+            "shields",
+            "--proxy",
+            "http://127.0.0.1:1",
+            "input.md",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(badge))
+        .stderr(predicate::str::contains("unavailable"));
+
+    // The failure is cached, so an offline run finds it
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "-b", "shields", "--offline", "input.md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(badge));
+}
+
+#[test]
+fn test_process_live_badge_placeholders() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("input.md"),
+        "{{ui:live:github:rust-lang/rust:stars/}}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "-b", "shields", "--offline", "input.md"])
+        .assert()
+        .failure();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "process",
+            "-b",
+            "shields",
+            "--offline",
+            "--placeholders",
+            "input.md",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("stars:%20—-6B7280"));
+}
+
+#[test]
+fn test_process_reproducible() {
+    let input = "{{ui:live:crates:serde:version/}}\n{{ui:swatch:FF0000/}} {{ui:tech:rust/}}\n";
+    let run = |with_snapshot: bool| {
+        let temp = TempDir::new().unwrap();
+        if with_snapshot {
+            // An expired entry still serves as the snapshot
+            let cache = temp.path().join(".mdfx-cache");
+            fs::create_dir(&cache).unwrap();
+            fs::write(
+                cache.join("crates_serde_version.json"),
+                r#"{"value":"1.0.200","created_at":1,"ttl":60}"#,
+            )
+            .unwrap();
+        }
+        fs::write(temp.path().join("input.md"), input).unwrap();
+        let assert = Command::cargo_bin("mdfx")
+            .unwrap()
+            .current_dir(temp.path())
+            .args([
+                "process",
+                "-b",
+                "svg",
+                "--assets-dir",
+                "assets",
+                "--reproducible",
+                "-o",
+                "out.md",
+                "input.md",
+            ])
+            .assert();
+        (temp, assert)
+    };
+
+    let (first, assert) = run(true);
+    assert.success();
+    let (second, assert) = run(true);
+    assert.success();
+
+    let manifest = |dir: &TempDir| fs::read(dir.path().join("assets/manifest.json")).unwrap();
+    let output = |dir: &TempDir| fs::read(dir.path().join("out.md")).unwrap();
+    assert_eq!(manifest(&first), manifest(&second));
+    assert_eq!(output(&first), output(&second));
+    assert!(String::from_utf8(manifest(&first))
+        .unwrap()
+        .contains("\"created_at\": \"1970-01-01T00:00:00+00:00\""));
+
+    // Live data never comes from the network
+    let (_empty, assert) = run(false);
+    assert.code(5);
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(first.path())
+        .args(["process", "--reproducible", "--refresh", "input.md"])
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn test_process_reproducible_relative_dates() {
+    let temp = TempDir::new().unwrap();
+    let cache = temp.path().join(".mdfx-cache");
+    fs::create_dir(&cache).unwrap();
+    fs::write(
+        cache.join("github_rust-lang_rust_last_commit.json"),
+        r#"{"value":"2024-06-03","created_at":1,"ttl":60}"#,
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("input.md"),
+        "{{ui:live:github:rust-lang/rust:last_commit:format=relative/}}\n",
+    )
+    .unwrap();
+    let process = || {
+        let mut command = Command::cargo_bin("mdfx").unwrap();
+        command
+            .current_dir(temp.path())
+            .env_remove("SOURCE_DATE_EPOCH")
+            .args(["process", "-b", "shields", "--reproducible", "input.md"]);
+        command
+    };
+
+    // Without a pinned date the output would change from day to day
+    process()
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("SOURCE_DATE_EPOCH"));
+
+    // 2024-06-13T12:00:00Z
+    process()
+        .env("SOURCE_DATE_EPOCH", "1718280000")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("10%20days%20ago"));
+}
+
+#[test]
+fn test_process_profile() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("input.md"),
+        "# Title\n\n{{mathbold}}Bold{{/mathbold}} {{ui:tech:rust/}}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "process",
+            "-b",
+            "svg",
+            "--profile",
+            "trace.json",
+            "input.md",
+        ])
+        .assert()
+        .success();
+
+    let trace: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("trace.json")).unwrap()).unwrap();
+    let events = trace["traceEvents"].as_array().unwrap();
+    let tech = events.iter().find(|e| e["name"] == "ui:tech").unwrap();
+    assert_eq!(tech["ph"], "X");
+    assert_eq!(tech["args"]["location"], "input.md:3:31");
+    assert_eq!(tech["args"]["template"], "{{ui:tech:rust/}}");
+    assert!(events.iter().any(|e| e["name"] == "render:tech"));
+    assert!(events.iter().any(|e| e["name"] == "style:mathbold"));
 }
 
 #[test]
@@ -237,6 +642,24 @@ fn test_process_plaintext_backend() {
     assert!(content.contains("𝐇𝐞𝐥𝐥𝐨"));
 }
 
+#[test]
+fn test_process_publish_fails_without_bucket() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("input.md"), "{{ui:swatch:accent/}}").unwrap();
+
+    // Without the publish feature or a "publish" config section, nothing is
+    // uploaded and no local assets are written either
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--publish", "input.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Publish failed"));
+
+    assert!(!temp.path().join("assets").exists());
+}
+
 #[test]
 fn test_process_missing_input() {
     Command::cargo_bin("mdfx")
@@ -570,6 +993,171 @@ fn test_verify_missing_manifest() {
         .failure();
 }
 
+#[cfg(feature = "sign")]
+#[test]
+fn test_signed_manifest() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("input.md"), "{{ui:swatch:FF0000/}}").unwrap();
+    let mdfx = || {
+        let mut cmd = Command::cargo_bin("mdfx").unwrap();
+        cmd.current_dir(temp.path());
+        cmd
+    };
+    let verify = || {
+        let mut cmd = mdfx();
+        cmd.args([
+            "verify",
+            "--assets-dir",
+            "assets",
+            "--public-key",
+            "mdfx.pub",
+            "--require-signature",
+        ]);
+        cmd
+    };
+
+    mdfx()
+        .args(["process", "-b", "svg", "--assets-dir", "assets", "input.md"])
+        .assert()
+        .success();
+    mdfx().args(["assets", "keygen"]).assert().success();
+    assert!(fs::read_to_string(temp.path().join("mdfx.pub"))
+        .unwrap()
+        .starts_with("untrusted comment: minisign public key"));
+    mdfx().args(["assets", "keygen"]).assert().code(4);
+
+    // Unsigned
+    verify()
+        .assert()
+        .code(6)
+        .stdout(predicate::str::contains("Signature: missing"));
+
+    mdfx()
+        .args([
+            "assets",
+            "sign",
+            "--assets-dir",
+            "assets",
+            "--key",
+            "mdfx.key",
+        ])
+        .assert()
+        .success();
+    assert!(temp.path().join("assets/manifest.json.minisig").exists());
+    verify()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("file:manifest.json"));
+
+    // Tampered manifest
+    let manifest = temp.path().join("assets/manifest.json");
+    let json = fs::read_to_string(&manifest).unwrap();
+    fs::write(&manifest, json.replace("\"svg\"", "\"SVG\"")).unwrap();
+    verify()
+        .assert()
+        .code(6)
+        .stderr(predicate::str::contains("Invalid signature"));
+
+    // Key from the environment
+    mdfx()
+        .args(["assets", "sign", "--assets-dir", "assets"])
+        .env(
+            "MDFX_SIGNING_KEY",
+            fs::read_to_string(temp.path().join("mdfx.key")).unwrap(),
+        )
+        .assert()
+        .success();
+    verify().assert().success();
+}
+
+#[test]
+fn test_filename_scheme_and_collisions() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{"filenames": {"hash_length": 10, "slug": true}}"#,
+    )
+    .unwrap();
+    fs::write(temp.path().join("input.md"), "{{ui:tech:rust/}}").unwrap();
+    let process = || {
+        Command::cargo_bin("mdfx")
+            .unwrap()
+            .current_dir(temp.path())
+            .args([
+                "process",
+                "-b",
+                "svg",
+                "--assets-dir",
+                "assets",
+                "-o",
+                "out.md",
+                "input.md",
+            ])
+            .assert()
+    };
+
+    process().success();
+    let svg = fs::read_dir(temp.path().join("assets"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "svg"))
+        .unwrap();
+    let name = svg.file_name().unwrap().to_str().unwrap().to_string();
+    assert!(name.starts_with("tech_rust_"), "{}", name);
+    assert_eq!(name.len(), "tech_rust_".len() + 10 + ".svg".len());
+
+    // A different file under the same name is reported, not skipped
+    fs::write(&svg, "<svg>other</svg>").unwrap();
+    process()
+        .code(4)
+        .stderr(predicate::str::contains("collision"));
+}
+
+#[test]
+fn test_assets_diff_reports_changes_and_html() {
+    let temp = TempDir::new().unwrap();
+    let render = |dir: &str, markdown: &str| {
+        fs::write(temp.path().join("input.md"), markdown).unwrap();
+        Command::cargo_bin("mdfx")
+            .unwrap()
+            .current_dir(temp.path())
+            .args([
+                "process",
+                "-b",
+                "svg",
+                "--assets-dir",
+                dir,
+                "-o",
+                "out.md",
+                "input.md",
+            ])
+            .assert()
+            .success();
+    };
+    render(
+        "old",
+        "{{ui:tech:rust:label=Rust/}} {{ui:swatch:FF0000/}} {{ui:swatch:00FF00/}}",
+    );
+    render(
+        "new",
+        "{{ui:tech:rust:label=Rust 2/}} {{ui:swatch:FF0000/}} {{ui:swatch:0000FF/}}",
+    );
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["assets", "diff", "old", "new", "--html", "diff.html"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("~ tech_"))
+        .stdout(predicate::str::contains(
+            "1 changed, 1 added, 1 removed, 1 unchanged",
+        ));
+
+    let html = fs::read_to_string(temp.path().join("diff.html")).unwrap();
+    assert_eq!(html.matches("<tr><td>").count(), 3);
+}
+
 // =============================================================================
 // CLEAN COMMAND TESTS
 // =============================================================================
@@ -657,23 +1245,161 @@ fn test_clean_removes_orphans() {
     assert!(!orphan.exists());
 }
 
-// =============================================================================
-// BUILD COMMAND TESTS
-// =============================================================================
-
 #[test]
-fn test_build_single_target() {
+fn test_clean_and_verify_asset_namespaces() {
     let temp = TempDir::new().unwrap();
-    let input = temp.path().join("input.md");
-    let output_dir = temp.path().join("dist");
+    let process = |doc: &str, template: &str, namespace: &str| {
+        fs::write(temp.path().join(doc), template).unwrap();
+        Command::cargo_bin("mdfx")
+            .unwrap()
+            .current_dir(temp.path())
+            .args(["process", "-b", "svg", "--assets-dir", "assets"])
+            .args(["--asset-namespace", namespace, "-i", doc])
+            .assert()
+            .success();
+    };
+    let mdfx = |args: &[&str]| {
+        Command::cargo_bin("mdfx")
+            .unwrap()
+            .current_dir(temp.path())
+            .args(args)
+            .args(["--assets-dir", "assets"])
+            .assert()
+    };
+
+    process("api.md", "{{ui:swatch:FF0000/}}", "api-");
+    process("guide.md", "{{ui:swatch:00FF00/}}", "guide-");
+    assert!(fs::read_to_string(temp.path().join("guide.md"))
+        .unwrap()
+        .contains("assets/guide-swatch_"));
+    let manifest = fs::read_to_string(temp.path().join("assets/manifest.json")).unwrap();
+    assert!(manifest.contains(r#""namespace": "api-""#));
+    assert!(manifest.contains(r#""namespace": "guide-""#));
+
+    // Re-running one namespace keeps the other's entries
+    process("api.md", "{{ui:swatch:0000FF/}}", "api-");
+    let manifest = fs::read_to_string(temp.path().join("assets/manifest.json")).unwrap();
+    assert_eq!(manifest.matches(r#""namespace""#).count(), 2);
+
+    for orphan in ["guide-orphan.svg", "other.svg"] {
+        fs::write(temp.path().join("assets").join(orphan), "<svg></svg>").unwrap();
+    }
+    mdfx(&["clean", "--asset-namespace", "guide-"]).success();
+    let api_assets = || -> Vec<std::path::PathBuf> {
+        fs::read_dir(temp.path().join("assets"))
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.to_string_lossy().contains("api-swatch_"))
+            .collect()
+    };
+    assert!(!temp.path().join("assets/guide-orphan.svg").exists());
+    assert!(temp.path().join("assets/other.svg").exists());
+    // The replaced api- swatch is unlisted, but not guide-'s to delete
+    assert_eq!(api_assets().len(), 2);
+
+    for path in api_assets() {
+        fs::remove_file(path).unwrap();
+    }
+    mdfx(&["verify", "--asset-namespace", "guide-"]).success();
+    mdfx(&["verify", "--asset-namespace", "api-"]).failure();
 
-    fs::write(&input, "{{mathbold}}Hello{{/mathbold}}").unwrap();
+    mdfx(&["clean", "--asset-namespace", "../x"])
+        .failure()
+        .stderr(predicate::str::contains("Invalid asset namespace"));
+}
 
-    Command::cargo_bin("mdfx")
-        .unwrap()
-        .args([
-            "build",
-            input.to_str().unwrap(),
+/// Assets directory with unreferenced SVGs, next to a doc that references none
+fn orphaned_assets(names: &[&str]) -> TempDir {
+    let temp = TempDir::new().unwrap();
+    fs::create_dir(temp.path().join("assets")).unwrap();
+    for name in names {
+        fs::write(temp.path().join("assets").join(name), "<svg></svg>").unwrap();
+    }
+    fs::write(temp.path().join("README.md"), "# No images\n").unwrap();
+    temp
+}
+
+#[test]
+fn test_clean_keep_list_and_recent_assets() {
+    let temp = orphaned_assets(&["tech_a.svg", "tech_b.svg", "swatch_c.svg"]);
+    fs::write(
+        temp.path().join("keep.txt"),
+        "# pinned\ntech_a.svg\n\nassets/swatch_*\n",
+    )
+    .unwrap();
+    let clean = |extra: &[&str]| {
+        Command::cargo_bin("mdfx")
+            .unwrap()
+            .current_dir(temp.path())
+            .args(["clean", "--scan", "*.md", "--assets-dir", "assets"])
+            .args(extra)
+            .assert()
+            .success()
+    };
+
+    clean(&["--keep-newer-than", "1", "--dry-run"])
+        .stdout(predicate::str::contains("(recent)"))
+        .stdout(predicate::str::contains("Would delete").not());
+
+    clean(&["--keep", "keep.txt"]).stdout(predicate::str::contains("(keep list)"));
+    assert!(temp.path().join("assets/tech_a.svg").exists());
+    assert!(temp.path().join("assets/swatch_c.svg").exists());
+    assert!(!temp.path().join("assets/tech_b.svg").exists());
+}
+
+#[test]
+fn test_clean_keep_git_history() {
+    let temp = orphaned_assets(&["old.svg", "never.svg"]);
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=mdfx", "-c", "user.email=mdfx@example.com"])
+            .args(args)
+            .current_dir(temp.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    git(&["init", "-q"]);
+    fs::write(temp.path().join("README.md"), "![](assets/old.svg)\n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "docs"]);
+    fs::write(temp.path().join("README.md"), "# No images\n").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "clean",
+            "--scan",
+            "*.md",
+            "--assets-dir",
+            "assets",
+            "--keep-git-history",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(git history)"));
+    assert!(temp.path().join("assets/old.svg").exists());
+    assert!(!temp.path().join("assets/never.svg").exists());
+}
+
+// =============================================================================
+// BUILD COMMAND TESTS
+// =============================================================================
+
+#[test]
+fn test_build_single_target() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("input.md");
+    let output_dir = temp.path().join("dist");
+
+    fs::write(&input, "{{mathbold}}Hello{{/mathbold}}").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args([
+            "build",
+            input.to_str().unwrap(),
             "--output-dir",
             output_dir.to_str().unwrap(),
             "--targets",
@@ -712,53 +1438,1691 @@ fn test_build_multiple_targets() {
     assert!(output_dir.join("input_pypi.md").exists());
 }
 
-// =============================================================================
-// COMPLETIONS COMMAND TESTS
-// =============================================================================
+#[test]
+fn test_build_inline_falls_back_per_target() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("input.md");
+    let output_dir = temp.path().join("dist");
+
+    fs::write(&input, "{{ui:swatch:F41C80/}}").unwrap();
 
-#[rstest]
-#[case("bash")]
-#[case("zsh")]
-#[case("fish")]
-fn test_completions_generation(#[case] shell: &str) {
     Command::cargo_bin("mdfx")
         .unwrap()
-        .args(["completions", shell])
+        .args([
+            "build",
+            input.to_str().unwrap(),
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+            "--targets",
+            "github,gitlab",
+            "--inline",
+        ])
+        .assert()
+        .success();
+
+    let github = fs::read_to_string(output_dir.join("input_github.md")).unwrap();
+    assert!(github.contains("assets/github/swatch_"), "{}", github);
+    let gitlab = fs::read_to_string(output_dir.join("input_gitlab.md")).unwrap();
+    assert!(gitlab.contains("data:image/svg+xml;base64,"), "{}", gitlab);
+    assert!(!output_dir.join("assets/gitlab").exists());
+}
+
+#[test]
+fn test_build_languages() {
+    let temp = TempDir::new().unwrap();
+    fs::create_dir(temp.path().join("i18n")).unwrap();
+    fs::write(
+        temp.path().join("i18n/en.json"),
+        r#"{"title": "Hello", "intro": {"lead": "A tool"}}"#,
+    )
+    .unwrap();
+    fs::write(temp.path().join("i18n/fr.json"), r#"{"title": "Bonjour"}"#).unwrap();
+    fs::write(temp.path().join("i18n/de.json"), r#"{"title": "Hallo"}"#).unwrap();
+    fs::write(
+        temp.path().join("README.md"),
+        "# {{t:title/}}\n{{t:intro.lead/}} {{ui:swatch:FF0000/}}",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["build", "README.md", "--targets", "local"])
         .assert()
         .success()
-        .stdout(predicate::str::is_empty().not());
+        .stdout(predicate::str::contains("(fr)"));
+
+    let dist = temp.path().join("dist");
+    let read = |name: &str| fs::read_to_string(dist.join(name)).unwrap();
+    assert!(read("README_local.md").starts_with("# Hello\nA tool "));
+    assert!(read("README_local.fr.md").starts_with("# Bonjour\nA tool "));
+    assert!(read("README_local.de.md").starts_with("# Hallo\nA tool "));
+
+    // The swatch is written once and shared by every language
+    let assets: Vec<_> = fs::read_dir(dist.join("assets/local")).unwrap().collect();
+    assert_eq!(assets.len(), 1);
 }
 
 #[test]
-fn test_completions_invalid_shell() {
+fn test_build_unknown_string() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("README.md"), "# {{t:title/}}").unwrap();
+
     Command::cargo_bin("mdfx")
         .unwrap()
-        .args(["completions", "invalid-shell"])
+        .current_dir(temp.path())
+        .args(["build", "README.md", "--targets", "github"])
         .assert()
-        .failure();
+        .failure()
+        .stderr(predicate::str::contains("Unknown string 'title'"));
 }
 
-// =============================================================================
-// LIST PALETTE TESTS
-// =============================================================================
+#[test]
+fn test_process_hero_per_target() {
+    let input = "{{ui:hero:title=mdfx:subtitle=Markdown effects:links=Docs|https://docs.rs/mdfx/}}";
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["process", "--target", "github", "--backend", "shields"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "<div align=\"center\">\n\n# mdfx\n",
+        ))
+        .stdout(predicate::str::contains(
+            "[Docs](https://docs.rs/mdfx)\n\n</div>",
+        ));
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["process", "--target", "pypi"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# mdfx\n\nMarkdown effects"))
+        .stdout(predicate::str::contains("<div").not());
+}
 
 #[test]
-fn test_list_palette() {
+fn test_process_applies_style_defaults() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{"styles": {"mathbold": {"spacing": 1}}}"#,
+    )
+    .unwrap();
+
     Command::cargo_bin("mdfx")
         .unwrap()
-        .args(["list", "palette"])
+        .current_dir(temp.path())
+        .arg("process")
+        .write_stdin("# {{mathbold}}HI{{/mathbold}}")
         .assert()
         .success()
-        .stdout(predicate::str::contains("accent"))
-        .stdout(predicate::str::contains("F41C80"));
+        .stdout(predicate::str::contains("# 𝐇 𝐈"));
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("process")
+        .write_stdin(
+            "---\nmdfx-styles:\n  mathbold: separator=dot\n---\n# {{mathbold}}HI{{/mathbold}}",
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# 𝐇·𝐈"));
 }
 
 #[test]
-fn test_list_palette_with_filter() {
+fn test_process_inline_code() {
+    let input = "`{{mb}}A{{/mb}}` `{{!mb}}B{{/mb}}`";
+
     Command::cargo_bin("mdfx")
         .unwrap()
-        .args(["list", "palette", "-f", "dark"])
+        .arg("process")
+        .write_stdin(input)
         .assert()
         .success()
-        .stdout(predicate::str::contains("dark1"));
+        .stdout(predicate::str::contains("`{{mb}}A{{/mb}}` `𝐁`"));
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["process", "--inline-code"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("`𝐀` `𝐁`"));
+}
+
+#[test]
+fn test_process_preserves_html_blocks() {
+    let input = "<!-- {{mb}}A{{/mb}} -->\n<pre>{{mb}}B{{/mb}}</pre>";
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .arg("process")
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(input));
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["process", "--process-html"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<!-- 𝐀 -->\n<pre>𝐁</pre>"));
+}
+
+#[test]
+fn test_process_applies_target_overrides() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{
+            "palette": {"brand": "FF0000"},
+            "targets": {"github": {"palette": {"brand": "00FF00"}, "assets_dir": "gh-assets"}}
+        }"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--target", "github"])
+        .write_stdin("{{ui:swatch:brand/}}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("gh-assets/swatch_"));
+
+    let svg = fs::read_dir(temp.path().join("gh-assets"))
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|p| p.extension().is_some_and(|e| e == "svg"))
+        .unwrap();
+    assert!(fs::read_to_string(svg).unwrap().contains("00FF00"));
+
+    // An explicit flag still wins
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "process",
+            "--target",
+            "github",
+            "--assets-dir",
+            "cli-assets",
+        ])
+        .write_stdin("{{ui:swatch:brand/}}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("cli-assets/swatch_"));
+}
+
+#[test]
+fn test_build_jobs_matches_sequential_build() {
+    let temp = TempDir::new().unwrap();
+    // Every target writes the same swatch into one shared directory
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{"targets": {
+            "github": {"backend": "svg", "assets_dir": "assets"},
+            "gitlab": {"backend": "svg", "assets_dir": "assets"},
+            "npm": {"backend": "svg", "assets_dir": "assets"},
+            "local": {"backend": "svg", "assets_dir": "assets"}
+        }}"#,
+    )
+    .unwrap();
+    fs::write(temp.path().join("input.md"), "{{ui:swatch:F41C80/}} Ready").unwrap();
+
+    let build = |jobs: &str, output_dir: &str| {
+        let output = Command::cargo_bin("mdfx")
+            .unwrap()
+            .current_dir(temp.path())
+            .args(["build", "input.md", "--targets", "github,gitlab,npm,local"])
+            .args(["--jobs", jobs, "--output-dir", output_dir])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let sequential = build("1", "sequential");
+    let parallel = build("4", "parallel");
+
+    // Outputs are reported in target order however the targets finish
+    assert_eq!(
+        sequential.replace("sequential/", ""),
+        parallel.replace("parallel/", "")
+    );
+    for target in ["github", "gitlab", "npm", "local"] {
+        let file = format!("input_{}.md", target);
+        assert_eq!(
+            fs::read_to_string(temp.path().join("sequential").join(&file)).unwrap(),
+            fs::read_to_string(temp.path().join("parallel").join(&file)).unwrap()
+        );
+    }
+    let assets: Vec<_> = fs::read_dir(temp.path().join("assets")).unwrap().collect();
+    assert_eq!(assets.len(), 1);
+}
+
+#[test]
+fn test_build_applies_target_overrides() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{"targets": {"pypi": {"post_process": false}, "npm": {"backend": "plaintext"}}}"#,
+    )
+    .unwrap();
+    fs::write(temp.path().join("input.md"), "Next → {{ui:swatch:FF0000/}}").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["build", "input.md", "--targets", "pypi,npm"])
+        .assert()
+        .success();
+
+    let dist = temp.path().join("dist");
+    assert!(fs::read_to_string(dist.join("input_pypi.md"))
+        .unwrap()
+        .contains("Next →"));
+    assert_eq!(
+        fs::read_to_string(dist.join("input_npm.md")).unwrap(),
+        "Next → [#FF0000]"
+    );
+}
+
+#[test]
+fn test_build_wraps_per_target() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{"targets": {"pypi": {"wrap": 20}}}"#,
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("input.md"),
+        "# A heading longer than twenty\n\nSome text that is longer than twenty columns.\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["build", "input.md", "--targets", "pypi,npm"])
+        .assert()
+        .success();
+
+    let dist = temp.path().join("dist");
+    assert_eq!(
+        fs::read_to_string(dist.join("input_pypi.md")).unwrap(),
+        "# A heading longer than twenty\n\nSome text that is\nlonger than twenty\ncolumns.\n"
+    );
+    assert!(fs::read_to_string(dist.join("input_npm.md"))
+        .unwrap()
+        .contains("Some text that is longer than twenty columns."));
+}
+
+#[test]
+fn test_process_docs_site_is_mdx_safe() {
+    let temp = TempDir::new().unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--target", "docs-site", "--assets-dir", "assets"])
+        .write_stdin("{{ui:swatch:FF0000/}} Use {config} <!-- todo -->")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("alt=\"\" />"))
+        .stdout(predicate::str::contains("Use \\{config\\} {/* todo */}"));
+}
+
+#[test]
+fn test_process_jira_wiki_markup() {
+    let temp = TempDir::new().unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--target", "jira", "--assets-dir", "assets"])
+        .write_stdin("# {{mathbold}}Title{{/mathbold}}\n\n{{ui:swatch:FF0000/}} **Ready**\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("h1. Title\n\n!swatch_"))
+        .stdout(predicate::str::contains(".svg! *Ready*"));
+}
+
+#[test]
+fn test_process_crates_io_rewrites_relative_links() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{"repository": {"url": "https://github.com/o/r.git"}}"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--target", "crates-io", "--assets-dir", "assets"])
+        .write_stdin("{{ui:swatch:FF0000/}} See [the guide](docs/guide.md).")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<img src=\"https://raw.githubusercontent.com/o/r/main/assets/swatch_",
+        ))
+        .stdout(predicate::str::contains(
+            "[the guide](https://github.com/o/r/blob/main/docs/guide.md)",
+        ));
+}
+
+#[test]
+fn test_process_loads_wasm_plugins() {
+    let temp = TempDir::new().unwrap();
+    let reply = r#"{"template":"{{mathbold}}NOTE{{/mathbold}}"}"#;
+    let wasm = wat::parse_str(format!(
+        r#"(module
+            (memory (export "memory") 1)
+            (data (i32.const 1024) "{}")
+            (func (export "alloc") (param i32) (result i32) i32.const 0)
+            (func (export "expand") (param i32 i32) (result i64) i64.const {}))"#,
+        reply.replace('"', "\\\""),
+        (1024u64 << 32) | reply.len() as u64
+    ))
+    .unwrap();
+    fs::create_dir(temp.path().join("plugins")).unwrap();
+    fs::write(temp.path().join("plugins/note.wasm"), wasm).unwrap();
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{"plugins": {"note": {"wasm": "plugins/note.wasm"}}}"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("process")
+        .write_stdin("{{ui:note/}} Read this")
+        .assert()
+        .success()
+        .stdout("𝐍𝐎𝐓𝐄 Read this");
+
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{"plugins": {"note": {"wasm": "plugins/missing.wasm"}}}"#,
+    )
+    .unwrap();
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("process")
+        .write_stdin("Read this")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Plugin 'note': can't read plugins/missing.wasm",
+        ));
+}
+
+// =============================================================================
+// RUN COMMAND TESTS
+// =============================================================================
+
+const PIPELINE_CONFIG: &str = r#"{
+    "targets": { "github": { "assets_dir": "assets" } },
+    "pipelines": {
+        "readme": {
+            "input": "README.template.md",
+            "output": "README.md",
+            "stages": [
+                { "process": "github" },
+                { "write": "github.md" },
+                { "post_process": "pypi" },
+                "link_check"
+            ]
+        },
+        "broken": {
+            "input": "README.template.md",
+            "stages": [{ "process": "github" }, "link_check"]
+        }
+    }
+}"#;
+
+fn pipeline_project() -> TempDir {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join(".mdfx.json"), PIPELINE_CONFIG).unwrap();
+    fs::write(
+        temp.path().join("README.template.md"),
+        "# Title\n\n{{ui:swatch:F41C80/}}\n\nSee [the title](#title) → details.\n",
+    )
+    .unwrap();
+    temp
+}
+
+#[test]
+fn test_run_pipeline_chains_stages() {
+    let temp = pipeline_project();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["run", "readme"])
+        .assert()
+        .success();
+
+    // The github stage rendered the badge and kept the arrow
+    let github = fs::read_to_string(temp.path().join("github.md")).unwrap();
+    assert!(github.contains("assets/swatch_"), "{}", github);
+    assert!(github.contains("→ details"), "{}", github);
+    // The pypi stage rewrote the arrow in the github output
+    let readme = fs::read_to_string(temp.path().join("README.md")).unwrap();
+    assert!(readme.contains("assets/swatch_"), "{}", readme);
+    assert!(readme.contains("-> details"), "{}", readme);
+}
+
+#[test]
+fn test_run_pipeline_fails_on_broken_link() {
+    let temp = pipeline_project();
+    fs::write(
+        temp.path().join("README.template.md"),
+        "See [the guide](docs/guide.md).\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["run", "broken"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("docs/guide.md"))
+        .stderr(predicate::str::contains("Link check failed"));
+}
+
+#[test]
+fn test_run_lists_and_rejects_unknown_pipelines() {
+    let temp = pipeline_project();
+    let run = |args: &[&str]| {
+        Command::cargo_bin("mdfx")
+            .unwrap()
+            .current_dir(temp.path())
+            .arg("run")
+            .args(args)
+            .assert()
+    };
+
+    run(&[])
+        .success()
+        .stdout(predicate::str::contains("broken"))
+        .stdout(predicate::str::contains(
+            "README.template.md → process github → write github.md",
+        ));
+    run(&["site"]).failure().stderr(predicate::str::contains(
+        "Unknown pipeline 'site'. Available: broken, readme",
+    ));
+}
+
+// =============================================================================
+// COMPLETIONS COMMAND TESTS
+// =============================================================================
+
+#[rstest]
+#[case("bash")]
+#[case("zsh")]
+#[case("fish")]
+fn test_completions_generation(#[case] shell: &str) {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["completions", shell])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty().not());
+}
+
+#[test]
+fn test_completions_invalid_shell() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["completions", "invalid-shell"])
+        .assert()
+        .failure();
+}
+
+// =============================================================================
+// PALETTE TESTS
+// =============================================================================
+
+#[test]
+fn test_list_palette() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["list", "palette"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("accent"))
+        .stdout(predicate::str::contains("F41C80"));
+}
+
+#[test]
+fn test_list_palette_with_filter() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["list", "palette", "-f", "dark"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dark1"));
+}
+
+#[test]
+fn test_palette_import_tailwind_used_by_process() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("tailwind.config.js"),
+        "module.exports = { theme: { extend: { colors: { brand: { DEFAULT: '#ff5500', dark: '#cc4400' } } } } }",
+    )
+    .unwrap();
+    fs::write(temp.path().join("input.md"), "{{ui:swatch:brand-dark/}}").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "palette",
+            "import",
+            "--from",
+            "tailwind.config.js",
+            "-o",
+            "palette.json",
+        ])
+        .assert()
+        .success();
+
+    let palette: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("palette.json")).unwrap())
+            .unwrap();
+    assert_eq!(
+        palette,
+        serde_json::json!({"brand": "FF5500", "brand-dark": "CC4400"})
+    );
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "process",
+            "input.md",
+            "--backend",
+            "shields",
+            "--palette",
+            "palette.json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("CC4400"));
+}
+
+#[test]
+fn test_palette_import_css_to_stdout() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("theme.css"),
+        ":root { --color-primary: rgb(43, 108, 176); --radius: 4px; }",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["palette", "import", "--from", "theme.css"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""primary": "2B6CB0""#))
+        .stderr(predicate::str::contains("radius"));
+}
+
+#[test]
+fn test_palette_generate_shades_to_file() {
+    let temp = TempDir::new().unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "palette",
+            "generate",
+            "--base",
+            "2B6CB0",
+            "--scheme",
+            "shades",
+            "-o",
+            "palette.json",
+        ])
+        .assert()
+        .success();
+
+    let palette: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("palette.json")).unwrap())
+            .unwrap();
+    assert_eq!(palette["primary"], "2B6CB0");
+    assert_eq!(palette["primary-500"], "2B6CB0");
+    assert_eq!(palette["primary-50"], "EAF0F7");
+    assert_eq!(palette.as_object().unwrap().len(), 11);
+}
+
+#[test]
+fn test_badges_render_yaml_spec() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("badges.yaml"),
+        "rust: { type: tech, name: rust, style: flat-square }\n\
+         release:\n  type: version\n  version: 2.0.0-beta\n\
+         license: { type: license, license: MIT }\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["badges", "render", "badges.yaml", "--out", "assets"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("3 badge(s)"));
+
+    for name in ["rust", "release", "license"] {
+        let svg = fs::read_to_string(temp.path().join(format!("assets/{}.svg", name))).unwrap();
+        assert!(svg.starts_with("<svg"), "{} is not an SVG", name);
+    }
+    let release = fs::read_to_string(temp.path().join("assets/release.svg")).unwrap();
+    assert!(release.contains("v2.0.0-beta"));
+}
+
+#[rstest]
+#[case::unknown_field(r#"{"rust": {"type": "tech", "name": "rust", "colour": "red"}}"#)]
+#[case::unknown_type(r#"{"rust": {"type": "shield", "name": "rust"}}"#)]
+#[case::path_name(r#"{"../rust": {"type": "tech", "name": "rust"}}"#)]
+fn test_badges_render_rejects_bad_specs(#[case] spec: &str) {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("badges.json"), spec).unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["badges", "render", "badges.json"])
+        .assert()
+        .code(3);
+    assert!(!temp.path().join("assets").exists());
+}
+
+#[test]
+fn test_palette_generate_from_palette_name() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args([
+            "palette", "generate", "--base", "cobalt", "--scheme", "triadic", "--name", "brand",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""brand": "2B6CB0""#))
+        .stdout(predicate::str::contains(r#""brand-triadic-1": "B02B6C""#));
+}
+
+#[test]
+fn test_palette_generate_invalid() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args([
+            "palette", "generate", "--base", "2B6CB0", "--scheme", "pastel",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown scheme 'pastel'"));
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["palette", "generate", "--base", "not-a-color"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid color 'not-a-color'"));
+}
+
+#[test]
+fn test_palette_import_unknown_extension() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("tokens.yaml"), "brand: '#ff5500'").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["palette", "import", "--from", "tokens.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--format figma, tailwind, or css"));
+}
+
+// =============================================================================
+// FMT COMMAND TESTS
+// =============================================================================
+
+#[test]
+fn test_fmt_stdout() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("test.md");
+    fs::write(
+        &input,
+        "{{mb}}Title{{/mb}} {{ui:tech:rust:style=flat:bg=000/}}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["fmt", input.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout("{{mathbold}}Title{{/mathbold}} {{ui:tech:rust:bg=000:style=flat/}}\n");
+}
+
+#[test]
+fn test_fmt_inplace_with_closer_policy() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("test.md");
+    fs::write(&input, "{{frame:grad}}Hi{{/}}").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["fmt", "-i", "--closers", "named", input.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&input).unwrap();
+    assert_eq!(content, "{{frame:gradient}}Hi{{/frame}}");
+}
+
+#[test]
+fn test_fmt_check() {
+    let temp = TempDir::new().unwrap();
+    let formatted = temp.path().join("formatted.md");
+    let unformatted = temp.path().join("unformatted.md");
+    fs::write(&formatted, "{{mathbold}}Hi{{/mathbold}}").unwrap();
+    fs::write(&unformatted, "{{mb}}Hi{{/mb}}").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["fmt", "--check", formatted.to_str().unwrap()])
+        .assert()
+        .success();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["fmt", "--check", unformatted.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not formatted"));
+
+    // --check never modifies the file
+    assert_eq!(fs::read_to_string(&unformatted).unwrap(), "{{mb}}Hi{{/mb}}");
+}
+
+#[test]
+fn test_fmt_uses_config_policy() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{"format": {"closers": "universal"}}"#,
+    )
+    .unwrap();
+    let input = temp.path().join("test.md");
+    fs::write(&input, "{{frame:star}}Hi{{/frame}}").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["fmt", input.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout("{{frame:star}}Hi{{/}}");
+}
+
+#[test]
+fn test_check_reports_all_errors() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("doc.md");
+    fs::write(
+        &input,
+        "{{nosuch}}A{{/nosuch}}\n{{mathbold}}ok{{/mathbold}}\n{{glyph:nope/}}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["check", "--offline", "doc.md"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "doc.md:1:1: error: Unknown style 'nosuch'",
+        ))
+        .stdout(predicate::str::contains("doc.md:3:1: error:"))
+        .stderr(predicate::str::contains("2 error(s)"));
+}
+
+#[test]
+fn test_check_warnings() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("doc.md"), "{{partial:missing/}}\n").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["check", "--offline", "doc.md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "warning: Unknown partial 'missing'",
+        ));
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["check", "--offline", "--deny-warnings", "doc.md"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_check_unmapped_chars() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("doc.md"),
+        "# {{italic}}Release 2{{/italic}}\n{{italic:fallback=skip}}v2{{/italic}}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["check", "--offline", "doc.md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "doc.md:1:3: warning: Style 'italic' has no equivalent for '2'",
+        ))
+        .stderr(predicate::str::contains("1 warning(s)"));
+}
+
+#[test]
+fn test_check_param_warnings() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("doc.md"),
+        "{{ui:row:align=middle}}{{ui:swatch:cobalt/}}{{/ui}}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["check", "--offline", "--deny-warnings", "doc.md"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "doc.md:1:1: warning: Invalid value 'middle' for parameter 'align' of component 'row', default used. Expected one of: left, center, right",
+        ));
+}
+
+#[test]
+fn test_check_accepts_key_spellings_and_aliases() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("doc.md"),
+        "{{ui:progress:50:labelColor=red:thumb-border=000000:color=gray/}}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["check", "--offline", "--deny-warnings", "doc.md"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_check_json_diagnostics() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("doc.md"), "{{nosuch}}A{{/nosuch}}\n").unwrap();
+
+    let output = Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["check", "--offline", "--error-format", "json", "doc.md"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(6));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let diagnostic: serde_json::Value =
+        serde_json::from_str(stdout.lines().next().unwrap()).unwrap();
+    assert_eq!(diagnostic["file"], "doc.md");
+    assert_eq!(diagnostic["line"], 1);
+    assert_eq!(diagnostic["column"], 1);
+    assert_eq!(diagnostic["severity"], "error");
+
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["error"]["kind"], "check");
+    assert_eq!(error["error"]["code"], 6);
+}
+
+// ============================================================================
+// Exit code tests
+// ============================================================================
+
+#[rstest]
+#[case(&["convert", "--style", "nosuch", "hi"], 3)]
+#[case(&["process", "--offline", "missing.md"], 4)]
+#[case(&["check", "--offline", "bad.md"], 6)]
+#[case(&["--no-such-flag"], 2)]
+fn test_exit_codes(#[case] args: &[&str], #[case] expected: i32) {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("bad.md"), "{{glyph:nope/}}\n").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(args)
+        .assert()
+        .code(expected);
+}
+
+#[test]
+fn test_error_format_json() {
+    let output = Command::cargo_bin("mdfx")
+        .unwrap()
+        .args([
+            "--error-format",
+            "json",
+            "convert",
+            "--style",
+            "nosuch",
+            "hi",
+        ])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["error"]["kind"], "parse");
+    assert_eq!(error["error"]["code"], 3);
+    assert!(error["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Unknown style 'nosuch'"));
+}
+
+// ============================================================================
+// Link lint tests
+// ============================================================================
+
+#[test]
+fn test_lint_links_reports_broken_links() {
+    let temp = TempDir::new().unwrap();
+    fs::create_dir_all(temp.path().join("docs")).unwrap();
+    fs::write(temp.path().join("docs/guide.md"), "# Setup\n").unwrap();
+    fs::write(
+        temp.path().join("README.md"),
+        "# Intro\n[ok](#intro) [guide](docs/guide.md#setup) [bad](#outro)\n\
+         ![](assets/mdfx/swatch_0000.svg)\n```\n[skipped](#code)\n```\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["lint", "links", "README.md"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "README.md:2:49: error: no heading for anchor '#outro'",
+        ))
+        .stdout(predicate::str::contains(
+            "README.md:3:5: error: asset not found: assets/mdfx/swatch_0000.svg",
+        ))
+        .stdout(predicate::str::contains("#code").not())
+        .stderr(predicate::str::contains("4 link(s): 2 broken"));
+}
+
+#[test]
+fn test_lint_links_json_and_success() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("ok.md"), "## Usage\n[usage](#usage)\n").unwrap();
+    fs::write(temp.path().join("bad.md"), "[gone](missing.md)\n").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["lint", "links", "ok.md"])
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["lint", "links", "--format", "json", "ok.md", "bad.md"])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report["files"], 2);
+    assert_eq!(report["broken"][0]["file"], "bad.md");
+    assert_eq!(report["broken"][0]["kind"], "file");
+}
+
+#[test]
+fn test_lint_compat_for_target() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("README.md"),
+        "# Title\n<b>bold</b> 𝐒𝐭𝐲𝐥𝐞𝐝\n<!-- mdfx-lint-disable-next-line C001 -->\n<u>kept</u>\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["lint", "compat", "--target", "pypi", "README.md"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "README.md:2:1: error[C001]: <b> is stripped by pypi",
+        ))
+        .stdout(predicate::str::contains("warning[C005]"))
+        .stdout(predicate::str::contains("<u>").not());
+
+    // GitHub renders <b> and styled Unicode
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["lint", "compat", "--target", "github", "README.md"])
+        .assert()
+        .success();
+}
+
+// ============================================================================
+// Snippet tests
+// ============================================================================
+
+#[test]
+fn test_test_snippets() {
+    let temp = TempDir::new().unwrap();
+    fs::create_dir(temp.path().join("examples")).unwrap();
+    fs::write(
+        temp.path().join("examples/demo.rs"),
+        "fn main() {\n    // mdfx:begin add\n    assert_eq!(1 + 2, 3);\n    // mdfx:end add\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("README.md"),
+        "# Demo\n\n```rust\n# let x = 2;\nassert_eq!(x * 2, 4);\n```\n\n\
+         ```rust,should_panic\npanic!(\"boom\");\n```\n\n\
+         ```rust,ignore\nnot rust\n```\n\n\
+         {{snippet:examples/demo.rs:region=add/}}\n\n\
+         ```rust\nlet a = 1;\nlet y: u8 = \"x\";\n```\n\n\
+         ```sh\ncargo run\n```\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["test-snippets", "README.md", "--offline"])
+        .assert()
+        .code(6)
+        .stdout(predicate::str::contains("test README.md:4 ... ok"))
+        .stdout(predicate::str::contains("test README.md:9 ... ok"))
+        .stdout(predicate::str::contains("test README.md:13 ... ignored"))
+        .stdout(predicate::str::contains(
+            "test README.md:16 (snippet) ... ok",
+        ))
+        .stdout(predicate::str::contains("test README.md:19 ... FAILED"))
+        .stdout(predicate::str::contains(
+            "README.md:20: error[E0308]: mismatched types",
+        ))
+        .stderr(predicate::str::contains(
+            "5 snippet(s): 3 passed, 1 failed, 1 ignored",
+        ));
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["test-snippets", "README.md", "--managed", "--offline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "test README.md:16 (snippet) ... ok",
+        ))
+        .stdout(predicate::str::contains("README.md:4").not());
+}
+
+// ============================================================================
+// Grammar generation tests
+// ============================================================================
+
+#[test]
+fn test_gen_grammar_textmate() {
+    let output = Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["gen", "grammar"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let grammar: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(grammar["scopeName"], "text.mdfx.injection");
+    let styles = grammar["repository"]["mdfx-style-open"]["match"]
+        .as_str()
+        .unwrap();
+    assert!(styles.contains("mathbold"));
+}
+
+#[test]
+fn test_gen_grammar_tree_sitter_to_file() {
+    let temp = TempDir::new().unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "gen",
+            "grammar",
+            "--format",
+            "tree-sitter",
+            "-o",
+            "grammar.js",
+        ])
+        .assert()
+        .success();
+
+    let grammar = fs::read_to_string(temp.path().join("grammar.js")).unwrap();
+    assert!(grammar.contains("module.exports = grammar({"));
+    assert!(grammar.contains("'tech',"));
+}
+
+#[test]
+fn test_gen_grammar_unknown_format() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["gen", "grammar", "--format", "vim"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown grammar format"));
+}
+
+#[test]
+fn test_gen_schema_json_schema() {
+    let output = Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["gen", "schema"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    let progress = &schema["$defs"]["ui:progress"]["properties"];
+    assert_eq!(progress["args"]["prefixItems"][0]["title"], "percent");
+    assert_eq!(progress["params"]["properties"]["width"]["default"], 100);
+}
+
+#[test]
+fn test_gen_schema_catalog_to_file() {
+    let temp = TempDir::new().unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "gen",
+            "schema",
+            "--format",
+            "catalog",
+            "-o",
+            "components.json",
+        ])
+        .assert()
+        .success();
+
+    let catalog: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("components.json")).unwrap())
+            .unwrap();
+    assert_eq!(
+        catalog["components"]["row"]["params"]["align"]["values"],
+        serde_json::json!(["left", "center", "right"])
+    );
+    assert!(catalog["components"]["live"]["sources"]["github"].is_object());
+}
+
+#[test]
+fn test_gen_schema_unknown_format() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["gen", "schema", "--format", "yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown schema format"));
+}
+
+#[test]
+fn test_gen_gallery_writes_page_and_assets() {
+    let temp = TempDir::new().unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["gen", "gallery", "--out", "docs/gallery.md"])
+        .assert()
+        .success();
+
+    let page = fs::read_to_string(temp.path().join("docs/gallery.md")).unwrap();
+    assert!(page.starts_with("# mdfx Gallery\n"));
+    assert!(page.contains("`{{mathbold}}Sample Text{{/mathbold}}`"));
+    assert!(page.contains("\n### donut\n"));
+
+    let svg = page
+        .split("](assets/gallery/")
+        .nth(1)
+        .and_then(|rest| rest.split(')').next())
+        .unwrap();
+    assert!(temp.path().join("docs/assets/gallery").join(svg).exists());
+}
+
+#[test]
+fn test_gen_gallery_html_from_extension() {
+    let temp = TempDir::new().unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "gen",
+            "gallery",
+            "--out",
+            "gallery.html",
+            "--assets-dir",
+            "img",
+        ])
+        .assert()
+        .success();
+
+    let page = fs::read_to_string(temp.path().join("gallery.html")).unwrap();
+    assert!(page.starts_with("<!DOCTYPE html>"));
+    assert!(page.contains("<img src=\"img/donut_"));
+    assert!(temp.path().join("img").is_dir());
+}
+
+#[test]
+fn test_gen_gallery_unknown_format() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["gen", "gallery", "--format", "pdf"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown gallery format"));
+}
+
+// ============================================================================
+// Changelog tests
+// ============================================================================
+
+const CHANGELOG: &str =
+    "# Changelog\n\n## [Unreleased]\n\n## [1.4.0] - 2024-06-01\n\n### Added\n\n- Steps\n- Trees\n";
+
+#[test]
+fn test_gen_changelog() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("CHANGELOG.md"), CHANGELOG).unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["gen", "changelog", "--items", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("badge/v1.4.0"))
+        .stdout(predicate::str::contains(
+            "Released 2024-06-01\n\n### What's new\n\n**Added**\n\n- Steps\n",
+        ))
+        .stdout(predicate::str::contains("Trees").not());
+}
+
+#[test]
+fn test_process_latest_release_uses_configured_changelog() {
+    let temp = TempDir::new().unwrap();
+    fs::write(temp.path().join("HISTORY.md"), CHANGELOG).unwrap();
+    fs::write(
+        temp.path().join(".mdfx.json"),
+        r#"{"changelog": "HISTORY.md"}"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--config", ".mdfx.json"])
+        .write_stdin("{{ui:latest-release:notes=false/}}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Released 2024-06-01"))
+        .stdout(predicate::str::contains("What's new").not());
+}
+
+#[test]
+fn test_process_toolchain_badges_read_manifests() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nrust-version = \"1.74\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("pyproject.toml"),
+        "[project]\nrequires-python = \">=3.9\"\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--backend", "shields"])
+        .write_stdin("{{ui:msrv/}} {{ui:python-requires/}}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("-MSRV%201.74-"))
+        .stdout(predicate::str::contains("-python%20>=3.9-"));
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--backend", "shields"])
+        .write_stdin("{{ui:node-engines/}}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires package.json"));
+}
+
+#[test]
+fn test_process_coverage_reads_report() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("cobertura.xml"),
+        r#"<?xml version="1.0" ?><coverage line-rate="0.734" lines-valid="1000"></coverage>"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--backend", "shields"])
+        .write_stdin("{{ui:coverage:file=cobertura.xml:precision=1/}}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("-coverage:%2073.4%25-EAB308?"));
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--backend", "shields"])
+        .write_stdin("{{ui:coverage:file=coverage/lcov.info/}}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'coverage/lcov.info' not found"));
+}
+
+#[test]
+fn test_process_bench_reads_criterion_results() {
+    let temp = TempDir::new().unwrap();
+    let results = temp
+        .path()
+        .join("target/criterion/parser/process_large/new");
+    fs::create_dir_all(&results).unwrap();
+    fs::write(
+        results.join("estimates.json"),
+        r#"{"mean": {"point_estimate": 2500000.0}, "slope": null}"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--backend", "shields"])
+        .write_stdin("{{ui:bench:parser/process_large:format=ms:precision=1/}}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "-parser/process__large:%202.5%20ms-3B82F6?",
+        ));
+}
+
+#[test]
+fn test_process_tests_reads_report() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("results.json"),
+        concat!(
+            r#"{ "type": "suite", "event": "started", "test_count": 10 }"#,
+            "\n",
+            r#"{ "type": "suite", "event": "failed", "passed": 8, "failed": 1, "ignored": 1 }"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--backend", "shields"])
+        .write_stdin("{{ui:tests:file=results.json/}}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "-tests:%208%20passed,%201%20failed,%201%20skipped-EF4444?",
+        ));
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--backend", "shields"])
+        .write_stdin("{{ui:tests:file=results.json:display=bar/}}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("/badge/failed-1-EF4444?"));
+}
+
+// ============================================================================
+// Logging tests
+// ============================================================================
+
+#[test]
+fn test_quiet_silences_info() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("input.md");
+    fs::write(&input, "{{mathbold}}Hi{{/mathbold}}").unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--quiet", "input.md", "-o", "output.md"])
+        .assert()
+        .success()
+        .stderr("");
+}
+
+#[test]
+fn test_verbose_shows_template_timings() {
+    let temp = TempDir::new().unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--verbose"])
+        .write_stdin("{{mathbold}}Hi{{/mathbold}}")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("expanded template"))
+        .stderr(predicate::str::contains("kind=style"));
+}
+
+#[test]
+fn test_log_format_json() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("input.md");
+    fs::write(&input, "{{mathbold}}Hi{{/mathbold}}").unwrap();
+
+    let output = Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "process",
+            "--log-format",
+            "json",
+            "input.md",
+            "-o",
+            "output.md",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let events: Vec<serde_json::Value> = stderr
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(events
+        .iter()
+        .any(|e| e["status"] == "Wrote:" && e["level"] == "INFO"));
+}
+
+#[test]
+fn test_error_logged_as_json() {
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .args(["convert", "--style", "nope", "--log-format", "json", "x"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(r#""level":"ERROR""#));
+}
+
+// ============================================================================
+// Processing statistics tests
+// ============================================================================
+
+#[test]
+fn test_process_stats_text() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("input.md"),
+        "{{mathbold}}Hi{{/mathbold}} {{ui:tech:rust/}} {{ui:tech:rust/}}",
+    )
+    .unwrap();
+
+    Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args(["process", "--stats", "input.md", "-o", "output.md"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Templates:  3"))
+        .stderr(predicate::str::contains("tech ×2"))
+        .stderr(predicate::str::contains("parse"));
+}
+
+#[test]
+fn test_process_stats_json() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("input.md"),
+        "{{frame:star}}{{mathbold}}Hi{{/mathbold}}{{/frame}} {{ui:swatch:accent/}} {{ui:swatch:accent/}}",
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "process",
+            "--quiet",
+            "--stats=json",
+            "--backend",
+            "svg",
+            "input.md",
+            "-o",
+            "output.md",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stats: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(stats["templates"]["frames"], 1);
+    assert_eq!(stats["templates"]["styles"], 1);
+    assert_eq!(stats["templates"]["by_component"]["swatch"], 2);
+    assert_eq!(stats["assets"]["written"], 1);
+    assert_eq!(stats["assets"]["deduped"], 1);
+    assert!(stats["timings_ms"]["parse"].is_number());
+}
+
+#[test]
+fn test_process_stats_fetch() {
+    let temp = TempDir::new().unwrap();
+    let cache = temp.path().join(".mdfx-cache");
+    fs::create_dir(&cache).unwrap();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    fs::write(
+        cache.join("crates_serde_version.json"),
+        format!(r#"{{"value":"1.0.200","created_at":{},"ttl":86400}}"#, now),
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("input.md"),
+        "{{ui:live:crates:serde:version/}} {{ui:live:crates:serde:version/}}\n",
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("mdfx")
+        .unwrap()
+        .current_dir(temp.path())
+        .args([
+            "process",
+            "--quiet",
+            "--offline",
+            "--stats=json",
+            "input.md",
+            "-o",
+            "output.md",
+        ])
+        .output()
+        .unwrap();
+
+    let stats: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    let fetch = &stats["fetch"];
+    assert_eq!(fetch["requests"], 2);
+    assert_eq!(fetch["cache_hits"], 2);
+    assert_eq!(fetch["cache_misses"], 0);
+    assert_eq!(fetch["bytes_downloaded"], 0);
+    assert_eq!(fetch["sources"]["crates"]["requests"], 2);
+    assert!(fetch["sources"]["crates"]["average_latency_ms"].is_null());
 }
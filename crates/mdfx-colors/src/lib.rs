@@ -2,7 +2,8 @@
 //!
 //! This crate provides lightweight color manipulation functions focused on
 //! badge generation use cases: luminance calculation for contrast detection,
-//! color darkening, and hex color parsing.
+//! color darkening and lightening, hue rotation for palette schemes, and hex
+//! color parsing.
 
 /// Calculate the relative luminance of a hex color using ITU-R BT.709 coefficients
 ///
@@ -79,6 +80,117 @@ pub fn darken(hex: &str, amount: f32) -> String {
     format!("#{:02X}{:02X}{:02X}", new_r, new_g, new_b)
 }
 
+/// Lighten a hex color by mixing it with white
+///
+/// The amount should be between 0.0 (no change) and 1.0 (white); the
+/// counterpart of [`darken`] for generating tints.
+///
+/// # Examples
+///
+/// ```
+/// use mdfx_colors::lighten;
+///
+/// assert_eq!(lighten("#000000", 0.5), "#808080");
+/// assert_eq!(lighten("#FF0000", 0.2), "#FF3333");
+/// ```
+pub fn lighten(hex: &str, amount: f32) -> String {
+    let (r, g, b) = parse_hex(hex).unwrap_or((0, 0, 0));
+
+    let amount = amount.clamp(0.0, 1.0);
+    let mix = |c: u8| (c as f32 + (255.0 - c as f32) * amount).round() as u8;
+
+    format!("#{:02X}{:02X}{:02X}", mix(r), mix(g), mix(b))
+}
+
+/// Rotate the hue of a hex color by the given number of degrees
+///
+/// Saturation and lightness are kept, so rotating by 120 and 240 degrees
+/// gives a triadic scheme and by ±30 degrees an analogous one.
+///
+/// # Examples
+///
+/// ```
+/// use mdfx_colors::rotate_hue;
+///
+/// assert_eq!(rotate_hue("#FF0000", 120.0), "#00FF00");
+/// assert_eq!(rotate_hue("#FF0000", -120.0), "#0000FF");
+/// ```
+pub fn rotate_hue(hex: &str, degrees: f32) -> String {
+    let (h, s, l) = to_hsl(hex).unwrap_or((0.0, 0.0, 0.0));
+    from_hsl(h + degrees, s, l)
+}
+
+/// Convert a hex color to HSL
+///
+/// Returns hue in degrees (0-360) and saturation and lightness between
+/// 0.0 and 1.0, or None for invalid input.
+///
+/// # Examples
+///
+/// ```
+/// use mdfx_colors::to_hsl;
+///
+/// assert_eq!(to_hsl("#FF0000"), Some((0.0, 1.0, 0.5)));
+/// assert_eq!(to_hsl("#FFFFFF"), Some((0.0, 0.0, 1.0)));
+/// ```
+pub fn to_hsl(hex: &str) -> Option<(f32, f32, f32)> {
+    let (r, g, b) = parse_hex(hex)?;
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return Some((0.0, 0.0, lightness));
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    Some((hue, saturation, lightness))
+}
+
+/// Convert HSL to a hex color
+///
+/// Hue is in degrees and wraps around; saturation and lightness are
+/// clamped to 0.0-1.0.
+///
+/// # Examples
+///
+/// ```
+/// use mdfx_colors::from_hsl;
+///
+/// assert_eq!(from_hsl(0.0, 1.0, 0.5), "#FF0000");
+/// assert_eq!(from_hsl(20.0, 1.0, 0.5), "#FF5500");
+/// assert_eq!(from_hsl(480.0, 1.0, 0.5), "#00FF00"); // wraps to 120
+/// ```
+pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> String {
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+
+    format!("#{:02X}{:02X}{:02X}", channel(r), channel(g), channel(b))
+}
+
 /// Parse a hex color string into RGB components
 ///
 /// Accepts hex colors with or without the # prefix, in 3-digit or 6-digit format.
@@ -167,6 +279,47 @@ mod tests {
         assert_eq!(darken(hex, amount), expected);
     }
 
+    // ========================================================================
+    // Lighten (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("#000000", 0.0, "#000000")] // No change
+    #[case("#000000", 1.0, "#FFFFFF")] // Complete lightening
+    #[case("#2B6CB0", 0.5, "#95B6D8")] // Tint of cobalt
+    fn test_lighten(#[case] hex: &str, #[case] amount: f32, #[case] expected: &str) {
+        assert_eq!(lighten(hex, amount), expected);
+    }
+
+    // ========================================================================
+    // HSL (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("#FF0000", 0.0, "#FF0000")] // No rotation
+    #[case("#FF0000", 360.0, "#FF0000")] // Full turn
+    #[case("#FF0000", 180.0, "#00FFFF")] // Complement
+    #[case("#2B6CB0", 30.0, "#2C2BB0")] // Analogous to cobalt
+    #[case("#808080", 90.0, "#808080")] // Grays have no hue
+    fn test_rotate_hue(#[case] hex: &str, #[case] degrees: f32, #[case] expected: &str) {
+        assert_eq!(rotate_hue(hex, degrees), expected);
+    }
+
+    #[rstest]
+    #[case("#2B6CB0")]
+    #[case("#F41C80")]
+    #[case("#22C55E")]
+    #[case("#000000")]
+    fn test_hsl_round_trip(#[case] hex: &str) {
+        let (h, s, l) = to_hsl(hex).unwrap();
+        assert_eq!(from_hsl(h, s, l), hex);
+    }
+
+    #[test]
+    fn test_to_hsl_invalid() {
+        assert_eq!(to_hsl("invalid"), None);
+    }
+
     // ========================================================================
     // Hex Parsing (Parameterized)
     // ========================================================================
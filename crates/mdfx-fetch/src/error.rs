@@ -35,10 +35,18 @@ pub enum FetchError {
         available: Vec<String>,
     },
 
+    /// Source needs an API key that isn't set
+    #[error("Missing credentials: set {0}")]
+    MissingCredentials(String),
+
     /// Resource not found (404)
     #[error("Not found: {0}")]
     NotFound(String),
 
+    /// Invalid proxy or TLS settings
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+
     /// Cache error
     #[error("Cache error: {0}")]
     CacheError(String),
@@ -70,6 +78,21 @@ impl FetchError {
         )
     }
 
+    /// Check if the source failed to provide a value, as opposed to a
+    /// mistake in the query or setup
+    ///
+    /// These are rendered as "n/a" badges and cached for a short time
+    /// instead of failing the document.
+    pub fn is_unavailable(&self) -> bool {
+        matches!(
+            self,
+            FetchError::HttpError(_)
+                | FetchError::RateLimited { .. }
+                | FetchError::ApiError { .. }
+                | FetchError::NotFound(_)
+        )
+    }
+
     /// Check if this is a rate limit error
     pub fn is_rate_limited(&self) -> bool {
         matches!(self, FetchError::RateLimited { .. })
@@ -93,7 +116,21 @@ mod tests {
     #[case(FetchError::NotFound("repo".to_string()), false)]
     #[case(FetchError::UnknownSource("unknown".to_string()), false)]
     #[case(FetchError::ParseError("invalid json".to_string()), false)]
+    #[case(FetchError::MissingCredentials("LIBRARIES_IO_API_KEY".to_string()), false)]
     fn test_is_recoverable(#[case] error: FetchError, #[case] expected: bool) {
         assert_eq!(error.is_recoverable(), expected);
     }
+
+    #[rstest]
+    #[case(FetchError::HttpError("timeout".to_string()), true)]
+    #[case(FetchError::RateLimited { retry_after: 60 }, true)]
+    #[case(FetchError::ApiError { status: 403, message: "forbidden".to_string() }, true)]
+    #[case(FetchError::NotFound("repo".to_string()), true)]
+    #[case(FetchError::ParseError("invalid query".to_string()), false)]
+    #[case(FetchError::UnknownMetric { metric: "x".to_string(), available: vec![] }, false)]
+    #[case(FetchError::MissingCredentials("LIBRARIES_IO_API_KEY".to_string()), false)]
+    #[case(FetchError::OfflineNoCache("github:a/b:stars".to_string()), false)]
+    fn test_is_unavailable(#[case] error: FetchError, #[case] expected: bool) {
+        assert_eq!(error.is_unavailable(), expected);
+    }
 }
@@ -2,9 +2,14 @@
 
 use crate::cache::{Cache, CacheConfig};
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::SourceRegistry;
 use crate::value::DataValue;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
 
 /// Configuration for the fetcher
 #[derive(Debug, Clone)]
@@ -13,10 +18,23 @@ pub struct FetchConfig {
     pub cache_dir: PathBuf,
     /// Default TTL for cache entries (in seconds)
     pub default_ttl: u64,
+    /// TTL for cached failures (in seconds); 0 disables negative caching
+    ///
+    /// A badge whose source returns not found, an API error or a network
+    /// error is cached as [`DataValue::Unavailable`] for this long.
+    pub failure_ttl: u64,
     /// Run in offline mode (cache only, no network)
     pub offline: bool,
     /// Force refresh (ignore cache, always fetch)
     pub refresh: bool,
+    /// User-Agent for API requests (default: [`DEFAULT_USER_AGENT`](crate::http::DEFAULT_USER_AGENT))
+    pub user_agent: Option<String>,
+    /// HTTP proxy URL, e.g. `http://proxy.corp:3128`; when unset, the
+    /// `HTTPS_PROXY`, `HTTP_PROXY` and `ALL_PROXY` variables are used.
+    /// Hosts listed in `NO_PROXY` are always fetched directly.
+    pub proxy: Option<String>,
+    /// PEM file of CA certificates to trust in addition to the built-in roots
+    pub ca_bundle: Option<PathBuf>,
 }
 
 impl Default for FetchConfig {
@@ -24,9 +42,75 @@ impl Default for FetchConfig {
         FetchConfig {
             cache_dir: PathBuf::from(".mdfx-cache"),
             default_ttl: 3600,
+            failure_ttl: 300,
             offline: false,
             refresh: false,
+            user_agent: None,
+            proxy: None,
+            ca_bundle: None,
+        }
+    }
+}
+
+/// Cache and network counters for a fetcher
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchStats {
+    /// Total fetch requests for known sources
+    pub requests: usize,
+    /// Requests served from a fresh cache entry
+    pub cache_hits: usize,
+    /// Requests without a fresh cache entry (including forced refreshes)
+    pub cache_misses: usize,
+    /// Requests served from an expired cache entry (offline or network fallback)
+    pub stale_hits: usize,
+    /// Requests that went to the network
+    pub network_fetches: usize,
+    /// Requests that returned an error
+    pub failures: usize,
+    /// Response bytes received, as reported by `Content-Length`
+    pub bytes_downloaded: u64,
+    /// Counters for each source that was requested, by source ID
+    pub sources: BTreeMap<String, SourceStats>,
+}
+
+impl FetchStats {
+    /// Fraction of requests served from cache (fresh or stale), if any were made
+    pub fn hit_rate(&self) -> Option<f64> {
+        if self.requests == 0 {
+            return None;
         }
+        Some((self.cache_hits + self.stale_hits) as f64 / self.requests as f64)
+    }
+}
+
+/// Counters for a single data source
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourceStats {
+    /// Fetch requests for this source
+    pub requests: usize,
+    /// Requests served from a fresh cache entry
+    pub cache_hits: usize,
+    /// Requests without a fresh cache entry (including forced refreshes)
+    pub cache_misses: usize,
+    /// Requests served from an expired cache entry
+    pub stale_hits: usize,
+    /// Requests that went to the network
+    pub network_fetches: usize,
+    /// Requests that returned an error
+    pub failures: usize,
+    /// Response bytes received, as reported by `Content-Length`
+    pub bytes_downloaded: u64,
+    /// Time spent in network fetches, including failed ones
+    pub network_time: Duration,
+}
+
+impl SourceStats {
+    /// Mean duration of a network fetch, if any were made
+    ///
+    /// One fetch may send several HTTP requests (e.g. Matrix registers a
+    /// guest before reading a room), so this is per badge value, not per request.
+    pub fn average_latency(&self) -> Option<Duration> {
+        (self.network_fetches > 0).then(|| self.network_time / self.network_fetches as u32)
     }
 }
 
@@ -34,11 +118,16 @@ impl Default for FetchConfig {
 pub struct Fetcher {
     cache: Cache,
     sources: SourceRegistry,
+    http: HttpClient,
     config: FetchConfig,
+    stats: Mutex<BTreeMap<&'static str, SourceStats>>,
 }
 
 impl Fetcher {
     /// Create a new fetcher with the given configuration
+    ///
+    /// Fails if the cache directory can't be created, the proxy URL is
+    /// invalid, or the CA bundle can't be read.
     pub fn new(config: FetchConfig) -> Result<Self> {
         let cache_config = CacheConfig {
             dir: config.cache_dir.clone(),
@@ -48,7 +137,9 @@ impl Fetcher {
         Ok(Fetcher {
             cache: Cache::new(cache_config)?,
             sources: SourceRegistry::new(),
+            http: HttpClient::new(&config)?,
             config,
+            stats: Mutex::default(),
         })
     }
 
@@ -67,20 +158,32 @@ impl Fetcher {
             .sources
             .get(source_id)
             .ok_or_else(|| FetchError::UnknownSource(source_id.to_string()))?;
+        let source_id = source.id();
+        self.record(source_id, |s| s.requests += 1);
 
         // Check if we should use cache
         if !self.config.refresh {
             // Try to get fresh cache entry
             if let Some(entry) = self.cache.get_fresh(source_id, query, metric) {
+                debug!(source = source_id, query, metric, "cache hit");
+                self.record(source_id, |s| s.cache_hits += 1);
                 return Ok(entry.value);
             }
         }
+        self.record(source_id, |s| s.cache_misses += 1);
 
         // If offline, only use cache (even stale)
         if self.config.offline {
             if let Some(entry) = self.cache.get_stale(source_id, query, metric) {
+                debug!(
+                    source = source_id,
+                    query, metric, "stale cache hit (offline)"
+                );
+                self.record(source_id, |s| s.stale_hits += 1);
                 return Ok(entry.value);
             }
+            debug!(source = source_id, query, metric, "cache miss (offline)");
+            self.record(source_id, |s| s.failures += 1);
             return Err(FetchError::OfflineNoCache(format!(
                 "{}:{}:{}",
                 source_id, query, metric
@@ -88,7 +191,27 @@ impl Fetcher {
         }
 
         // Fetch from network
-        match source.fetch(query, metric) {
+        let bytes_before = self.http.bytes_downloaded();
+        let started = Instant::now();
+        let fetched = source.fetch(&self.http, query, metric);
+        let elapsed = started.elapsed();
+        let bytes = self.http.bytes_downloaded() - bytes_before;
+        self.record(source_id, |s| {
+            s.network_fetches += 1;
+            s.network_time += elapsed;
+            s.bytes_downloaded += bytes;
+        });
+        debug!(
+            source = source_id,
+            query,
+            metric,
+            elapsed_ms = elapsed.as_millis() as u64,
+            bytes,
+            ok = fetched.is_ok(),
+            "fetched from network"
+        );
+
+        match fetched {
             Ok(value) => {
                 // Cache the result
                 let _ = self.cache.set(
@@ -100,14 +223,35 @@ impl Fetcher {
                 );
                 Ok(value)
             }
-            Err(e) if e.is_recoverable() => {
+            Err(e) => {
                 // On recoverable errors, try stale cache as fallback
-                if let Some(entry) = self.cache.get_stale(source_id, query, metric) {
-                    return Ok(entry.value);
+                if e.is_recoverable() {
+                    if let Some(entry) = self.cache.get_stale(source_id, query, metric) {
+                        warn!(
+                            "Using stale cache for {}:{}:{} ({})",
+                            source_id, query, metric, e
+                        );
+                        self.record(source_id, |s| s.stale_hits += 1);
+                        return Ok(entry.value);
+                    }
                 }
-                Err(e)
+                self.record(source_id, |s| s.failures += 1);
+                if !e.is_unavailable() {
+                    return Err(e);
+                }
+
+                warn!("{}:{}:{} unavailable ({})", source_id, query, metric, e);
+                if self.config.failure_ttl > 0 {
+                    let _ = self.cache.set(
+                        source_id,
+                        query,
+                        metric,
+                        DataValue::Unavailable,
+                        Some(self.config.failure_ttl),
+                    );
+                }
+                Ok(DataValue::Unavailable)
             }
-            Err(e) => Err(e),
         }
     }
 
@@ -152,6 +296,28 @@ impl Fetcher {
         self.sources.get(source_id).is_some()
     }
 
+    /// Cache and network counters since this fetcher was created
+    pub fn stats(&self) -> FetchStats {
+        let sources = self.stats.lock().unwrap();
+        let mut stats = FetchStats::default();
+        for (id, s) in sources.iter() {
+            stats.requests += s.requests;
+            stats.cache_hits += s.cache_hits;
+            stats.cache_misses += s.cache_misses;
+            stats.stale_hits += s.stale_hits;
+            stats.network_fetches += s.network_fetches;
+            stats.failures += s.failures;
+            stats.bytes_downloaded += s.bytes_downloaded;
+            stats.sources.insert(id.to_string(), *s);
+        }
+        stats
+    }
+
+    /// Update the counters of a source
+    fn record(&self, source_id: &'static str, update: impl FnOnce(&mut SourceStats)) {
+        update(self.stats.lock().unwrap().entry(source_id).or_default());
+    }
+
     /// Get the fetcher configuration
     pub fn config(&self) -> &FetchConfig {
         &self.config
@@ -180,6 +346,7 @@ mod tests {
             default_ttl: 3600,
             offline,
             refresh,
+            ..FetchConfig::default()
         };
         let fetcher = Fetcher::new(config).unwrap();
         (fetcher, dir)
@@ -227,6 +394,150 @@ mod tests {
         let (fetcher, _dir) = temp_fetcher(true, false);
         let result = fetcher.fetch("github", "rust-lang/rust", "stars");
         assert!(matches!(result, Err(FetchError::OfflineNoCache(_))));
+
+        let stats = fetcher.stats();
+        assert_eq!(stats.requests, 1);
+        assert_eq!(stats.cache_misses, 1);
+        assert_eq!(stats.failures, 1);
+        assert_eq!(stats.network_fetches, 0);
+        assert_eq!(stats.hit_rate(), Some(0.0));
+        assert_eq!(stats.sources["github"].average_latency(), None);
+    }
+
+    #[test]
+    fn test_fetcher_stats_cache_hits() {
+        let (fetcher, _dir) = temp_fetcher(true, false);
+        fetcher
+            .cache
+            .set(
+                "github",
+                "rust-lang/rust",
+                "stars",
+                DataValue::Number(1),
+                None,
+            )
+            .unwrap();
+
+        fetcher.fetch("github", "rust-lang/rust", "stars").unwrap();
+        fetcher.fetch("github", "rust-lang/rust", "stars").unwrap();
+        let _ = fetcher.fetch("github", "rust-lang/rust", "forks");
+
+        let stats = fetcher.stats();
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.cache_hits, 2);
+        assert_eq!(stats.cache_misses, 1);
+        assert_eq!(stats.failures, 1);
+        assert_eq!(stats.hit_rate(), Some(2.0 / 3.0));
+        assert_eq!(stats.bytes_downloaded, 0);
+        assert_eq!(stats.sources.len(), 1);
+        assert_eq!(stats.sources["github"].requests, 3);
+    }
+
+    #[test]
+    fn test_fetcher_stats_by_source() {
+        let (fetcher, _dir) = temp_fetcher(true, false);
+        let _ = fetcher.fetch("github", "rust-lang/rust", "stars");
+        let _ = fetcher.fetch("npm", "react", "version");
+        let _ = fetcher.fetch("npm", "vue", "version");
+
+        let stats = fetcher.stats();
+        assert_eq!(stats.requests, 3);
+        let sources: Vec<_> = stats.sources.keys().map(String::as_str).collect();
+        assert_eq!(sources, ["github", "npm"]);
+        assert_eq!(stats.sources["npm"].requests, 2);
+        assert_eq!(stats.sources["npm"].failures, 2);
+    }
+
+    #[rstest]
+    #[case(0, 0, None)]
+    #[case(1, 250, Some(250))]
+    #[case(4, 1000, Some(250))]
+    fn test_average_latency(
+        #[case] network_fetches: usize,
+        #[case] total_ms: u64,
+        #[case] expected_ms: Option<u64>,
+    ) {
+        let stats = SourceStats {
+            network_fetches,
+            network_time: Duration::from_millis(total_ms),
+            ..SourceStats::default()
+        };
+        assert_eq!(
+            stats.average_latency(),
+            expected_ms.map(Duration::from_millis)
+        );
+    }
+
+    /// Fetcher whose requests all fail with a connection error
+    fn unreachable_fetcher(failure_ttl: u64) -> (Fetcher, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let config = FetchConfig {
+            cache_dir: dir.path().to_path_buf(),
+            failure_ttl,
+            proxy: Some("http://127.0.0.1:1".to_string()),
+            ..FetchConfig::default()
+        };
+        (Fetcher::new(config).unwrap(), dir)
+    }
+
+    #[test]
+    fn test_fetcher_caches_failures() {
+        let (fetcher, _dir) = unreachable_fetcher(300);
+        let value = fetcher.fetch("crates", "serde", "version").unwrap();
+        assert_eq!(value, DataValue::Unavailable);
+
+        let entry = fetcher
+            .cache
+            .get_fresh("crates", "serde", "version")
+            .unwrap();
+        assert_eq!(entry.value, DataValue::Unavailable);
+        assert_eq!(entry.ttl, 300);
+
+        // The second fetch is served from the negative cache entry
+        let value = fetcher.fetch("crates", "serde", "version").unwrap();
+        assert_eq!(value, DataValue::Unavailable);
+        let stats = fetcher.stats();
+        assert_eq!(stats.network_fetches, 1);
+        assert_eq!(stats.cache_hits, 1);
+        assert_eq!(stats.failures, 1);
+    }
+
+    #[test]
+    fn test_fetcher_failure_ttl_zero() {
+        let (fetcher, _dir) = unreachable_fetcher(0);
+        let value = fetcher.fetch("crates", "serde", "version").unwrap();
+        assert_eq!(value, DataValue::Unavailable);
+        assert!(fetcher.cache.get("crates", "serde", "version").is_none());
+    }
+
+    #[test]
+    fn test_fetcher_prefers_stale_over_unavailable() {
+        let (fetcher, _dir) = unreachable_fetcher(300);
+        fetcher
+            .cache
+            .set(
+                "crates",
+                "serde",
+                "version",
+                DataValue::String("1.0.200".to_string()),
+                Some(0),
+            )
+            .unwrap();
+
+        let value = fetcher.fetch("crates", "serde", "version").unwrap();
+        assert_eq!(value, DataValue::String("1.0.200".to_string()));
+        assert_eq!(fetcher.stats().stale_hits, 1);
+    }
+
+    #[test]
+    fn test_fetcher_query_errors_not_cached() {
+        let (fetcher, _dir) = unreachable_fetcher(300);
+        let result = fetcher.fetch("docsrs", "rust-lang/rust", "status");
+        assert!(matches!(result, Err(FetchError::ParseError(_))));
+        assert!(fetcher
+            .cache
+            .get("docsrs", "rust-lang/rust", "status")
+            .is_none());
     }
 
     #[test]
@@ -234,5 +545,6 @@ mod tests {
         let (fetcher, _dir) = temp_fetcher(false, false);
         let result = fetcher.fetch("nonexistent", "query", "metric");
         assert!(matches!(result, Err(FetchError::UnknownSource(_))));
+        assert_eq!(fetcher.stats().hit_rate(), None);
     }
 }
@@ -0,0 +1,382 @@
+//! HTTP client shared by all data sources
+//!
+//! Wraps a `ureq` agent built from [`FetchConfig`]: the User-Agent sent to
+//! every API, an HTTP proxy (explicit, or from `HTTPS_PROXY`/`HTTP_PROXY`/
+//! `ALL_PROXY` with `NO_PROXY` exclusions), and extra CA certificates for
+//! networks that intercept TLS. It also counts the bytes received, for
+//! [`FetchStats`](crate::FetchStats).
+
+use crate::error::{FetchError, Result};
+use crate::fetcher::FetchConfig;
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::CertificateDer;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::warn;
+
+/// User-Agent sent when [`FetchConfig::user_agent`] is not set
+pub const DEFAULT_USER_AGENT: &str = "mdfx-fetch/1.0 (https://github.com/blackwell-systems/mdfx)";
+
+/// Proxy variables, in the order they are checked
+const PROXY_VARS: &[&str] = &[
+    "HTTPS_PROXY",
+    "https_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+];
+
+/// HTTP client used by data sources
+#[derive(Clone)]
+pub struct HttpClient {
+    /// Agent for most requests (through the proxy, if any)
+    agent: ureq::Agent,
+    /// Agent without proxy, for hosts matched by `NO_PROXY`
+    direct: ureq::Agent,
+    no_proxy: Vec<String>,
+    user_agent: String,
+    /// Response bytes received by both agents
+    bytes: Arc<AtomicU64>,
+}
+
+impl Default for HttpClient {
+    /// Client with the default User-Agent and no proxy or extra CAs
+    fn default() -> Self {
+        let bytes = Arc::new(AtomicU64::new(0));
+        let agent = ureq::AgentBuilder::new()
+            .middleware(ByteCounter(bytes.clone()))
+            .build();
+        HttpClient {
+            direct: agent.clone(),
+            agent,
+            no_proxy: Vec::new(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            bytes,
+        }
+    }
+}
+
+impl HttpClient {
+    /// Build a client from the fetcher configuration and proxy variables
+    pub fn new(config: &FetchConfig) -> Result<Self> {
+        Self::with_env(config, |name| std::env::var(name).ok())
+    }
+
+    /// Build a client, reading proxy variables through `env`
+    fn with_env(config: &FetchConfig, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let tls = match &config.ca_bundle {
+            Some(path) => Some(Arc::new(tls_config(path)?)),
+            None => None,
+        };
+        let bytes = Arc::new(AtomicU64::new(0));
+        let builder = || {
+            let builder = ureq::AgentBuilder::new().middleware(ByteCounter(bytes.clone()));
+            match &tls {
+                Some(tls) => builder.tls_config(tls.clone()),
+                None => builder,
+            }
+        };
+        let direct = builder().build();
+
+        let proxy =
+            match &config.proxy {
+                Some(url) => Some(ureq::Proxy::new(url).map_err(|e| {
+                    FetchError::ConfigError(format!("invalid proxy '{}': {}", url, e))
+                })?),
+                None => PROXY_VARS.iter().find_map(|var| {
+                    let url = env(var).filter(|v| !v.trim().is_empty())?;
+                    ureq::Proxy::new(&url)
+                        .inspect_err(|e| warn!("Ignoring {}={}: {}", var, url, e))
+                        .ok()
+                }),
+            };
+        let agent = match proxy {
+            Some(proxy) => builder().proxy(proxy).build(),
+            None => direct.clone(),
+        };
+
+        let no_proxy = env("NO_PROXY")
+            .or_else(|| env("no_proxy"))
+            .map(|list| parse_no_proxy(&list))
+            .unwrap_or_default();
+
+        Ok(HttpClient {
+            agent,
+            direct,
+            no_proxy,
+            user_agent: config
+                .user_agent
+                .clone()
+                .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string()),
+            bytes,
+        })
+    }
+
+    /// User-Agent sent with every request
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Start a GET request
+    pub fn get(&self, url: &str) -> ureq::Request {
+        self.agent_for(url)
+            .get(url)
+            .set("User-Agent", &self.user_agent)
+    }
+
+    /// Start a POST request
+    pub fn post(&self, url: &str) -> ureq::Request {
+        self.agent_for(url)
+            .post(url)
+            .set("User-Agent", &self.user_agent)
+    }
+
+    /// Response bytes received so far, as reported by `Content-Length`
+    ///
+    /// Chunked responses without a length are not counted.
+    pub fn bytes_downloaded(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    fn agent_for(&self, url: &str) -> &ureq::Agent {
+        if bypasses_proxy(&self.no_proxy, host(url)) {
+            &self.direct
+        } else {
+            &self.agent
+        }
+    }
+}
+
+/// Middleware adding each response's `Content-Length` to a counter
+struct ByteCounter(Arc<AtomicU64>);
+
+impl ureq::Middleware for ByteCounter {
+    fn handle(
+        &self,
+        request: ureq::Request,
+        next: ureq::MiddlewareNext,
+    ) -> std::result::Result<ureq::Response, ureq::Error> {
+        let response = next.handle(request)?;
+        if let Some(length) = response
+            .header("Content-Length")
+            .and_then(|v| v.parse::<u64>().ok())
+        {
+            self.0.fetch_add(length, Ordering::Relaxed);
+        }
+        Ok(response)
+    }
+}
+
+/// Rustls config trusting the built-in roots plus the certificates in a PEM file
+fn tls_config(path: &Path) -> Result<rustls::ClientConfig> {
+    let invalid = |reason: String| {
+        FetchError::ConfigError(format!("CA bundle '{}': {}", path.display(), reason))
+    };
+    let certs = CertificateDer::pem_file_iter(path)
+        .map_err(|e| invalid(e.to_string()))?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| invalid(e.to_string()))?;
+    if certs.is_empty() {
+        return Err(invalid("no certificates found".to_string()));
+    }
+
+    let mut roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    for cert in certs {
+        roots.add(cert).map_err(|e| invalid(e.to_string()))?;
+    }
+
+    // Same provider and protocol versions as ureq's own default config
+    Ok(
+        rustls::ClientConfig::builder_with_provider(
+            rustls::crypto::ring::default_provider().into(),
+        )
+        .with_protocol_versions(&[&rustls::version::TLS12, &rustls::version::TLS13])
+        .map_err(|e| invalid(e.to_string()))?
+        .with_root_certificates(roots)
+        .with_no_client_auth(),
+    )
+}
+
+/// Lowercase `NO_PROXY` entries without ports or leading wildcards
+fn parse_no_proxy(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|entry| entry.trim().to_ascii_lowercase())
+        .filter_map(|entry| {
+            if entry == "*" {
+                return Some(entry);
+            }
+            let host = entry.trim_start_matches('*').trim_start_matches('.');
+            let host = host.split(':').next().unwrap_or_default();
+            (!host.is_empty()).then(|| host.to_string())
+        })
+        .collect()
+}
+
+/// Whether a host is excluded from proxying: equal to, or a subdomain of, an entry
+fn bypasses_proxy(no_proxy: &[String], host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    no_proxy.iter().any(|entry| {
+        entry == "*"
+            || host == *entry
+            || host
+                .strip_suffix(entry.as_str())
+                .is_some_and(|rest| rest.ends_with('.'))
+    })
+}
+
+/// Host part of a URL, without scheme, credentials or port
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    const TEST_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBhjCCASugAwIBAgIUfup2HiMwLkR9noJcSukHu1gwZrUwCgYIKoZIzj0EAwIw
+FzEVMBMGA1UEAwwMbWRmeCB0ZXN0IENBMCAXDTI2MTAxNjE2NTExNloYDzIxMjYw
+OTIyMTY1MTE2WjAXMRUwEwYDVQQDDAxtZGZ4IHRlc3QgQ0EwWTATBgcqhkjOPQIB
+BggqhkjOPQMBBwNCAAQraKj7484VmsZOy5lra76bzLOgEqMSUKvAOoo6JzK4jgOm
+hcq9CM04uCh7DnaE4Lc96kIrWFFwsyUEsdQHENbao1MwUTAdBgNVHQ4EFgQUXQ8L
+7CKJCbBVW5dE/IGNoPbyyhwwHwYDVR0jBBgwFoAUXQ8L7CKJCbBVW5dE/IGNoPby
+yhwwDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNJADBGAiEArRhg6Z4smrC4
+5edzRZ4ngO8L2CuM9JdWHk0cnZUJtBgCIQDkwvnnv7HENix+o7nn7JzuXZ5lmjD6
+XvuYkAKNXM0Bkg==
+-----END CERTIFICATE-----
+";
+
+    fn client(config: &FetchConfig, env: &[(&str, &str)]) -> Result<HttpClient> {
+        let env: HashMap<String, String> = env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        HttpClient::with_env(config, |name| env.get(name).cloned())
+    }
+
+    #[test]
+    fn test_user_agent() {
+        let default = client(&FetchConfig::default(), &[]).unwrap();
+        assert_eq!(default.user_agent(), DEFAULT_USER_AGENT);
+
+        let config = FetchConfig {
+            user_agent: Some("acme-docs/2.0".to_string()),
+            ..FetchConfig::default()
+        };
+        let custom = client(&config, &[]).unwrap();
+        assert_eq!(custom.user_agent(), "acme-docs/2.0");
+        let request = custom.get("https://registry.npmjs.org/react");
+        assert_eq!(request.header("User-Agent"), Some("acme-docs/2.0"));
+    }
+
+    #[test]
+    fn test_bytes_downloaded() {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            for status in ["200 OK", "404 Not Found"] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0; 1024];
+                let _ = stream.read(&mut request).unwrap();
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello",
+                    status
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        let client = client(&FetchConfig::default(), &[]).unwrap();
+        assert_eq!(client.bytes_downloaded(), 0);
+        assert_eq!(
+            client.get(&url).call().unwrap().into_string().unwrap(),
+            "hello"
+        );
+        assert!(client.get(&url).call().is_err());
+        server.join().unwrap();
+        assert_eq!(client.bytes_downloaded(), 10);
+    }
+
+    #[rstest]
+    #[case("https://api.github.com/repos/a/b", "api.github.com")]
+    #[case("http://user:pw@localhost:8080/x", "localhost")]
+    #[case("https://[::1]:443/", "::1")]
+    #[case("https://crates.io?q=1", "crates.io")]
+    fn test_host(#[case] url: &str, #[case] expected: &str) {
+        assert_eq!(host(url), expected);
+    }
+
+    #[rstest]
+    #[case("internal.corp,.example.com", "internal.corp", true)]
+    #[case("internal.corp,.example.com", "api.example.com", true)]
+    #[case("internal.corp,.example.com", "example.com", true)]
+    #[case("internal.corp,.example.com", "notexample.com", false)]
+    #[case("*.example.com, localhost:8080", "localhost", true)]
+    #[case("*", "api.github.com", true)]
+    #[case("", "api.github.com", false)]
+    fn test_no_proxy(#[case] list: &str, #[case] host: &str, #[case] expected: bool) {
+        assert_eq!(bypasses_proxy(&parse_no_proxy(list), host), expected);
+    }
+
+    #[test]
+    fn test_invalid_proxy() {
+        let config = FetchConfig {
+            proxy: Some("ftp://proxy.corp:21".to_string()),
+            ..FetchConfig::default()
+        };
+        let err = client(&config, &[]).err().unwrap();
+        assert!(matches!(err, FetchError::ConfigError(_)));
+        assert!(err.to_string().contains("ftp://proxy.corp:21"), "{}", err);
+    }
+
+    #[test]
+    fn test_invalid_env_proxy_ignored() {
+        let config = FetchConfig::default();
+        assert!(client(&config, &[("HTTPS_PROXY", "ftp://proxy.corp:21")]).is_ok());
+        assert!(client(&config, &[("HTTPS_PROXY", "http://proxy.corp:3128")]).is_ok());
+    }
+
+    #[test]
+    fn test_ca_bundle() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("corp-ca.pem");
+        std::fs::write(&path, TEST_CA).unwrap();
+        let config = FetchConfig {
+            ca_bundle: Some(path),
+            ..FetchConfig::default()
+        };
+        assert!(client(&config, &[]).is_ok());
+    }
+
+    #[rstest]
+    #[case(None, "No such file")]
+    #[case(Some("not a certificate\n"), "no certificates found")]
+    fn test_invalid_ca_bundle(#[case] contents: Option<&str>, #[case] message: &str) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("corp-ca.pem");
+        if let Some(contents) = contents {
+            std::fs::write(&path, contents).unwrap();
+        }
+        let config = FetchConfig {
+            ca_bundle: Some(path),
+            ..FetchConfig::default()
+        };
+        let err = client(&config, &[]).err().unwrap();
+        assert!(matches!(err, FetchError::ConfigError(_)));
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+}
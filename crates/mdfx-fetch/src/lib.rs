@@ -1,7 +1,8 @@
 //! mdfx-fetch: Data fetching for dynamic badges
 //!
 //! This crate provides the infrastructure for fetching live data from external APIs
-//! (GitHub, npm, crates.io, etc.) to power dynamic badges in mdfx.
+//! (GitHub, npm, crates.io, OpenSSF Scorecard, OSV, Discord, GitHub Sponsors, etc.) to power
+//! dynamic badges in mdfx.
 //!
 //! # Architecture
 //!
@@ -28,11 +29,13 @@
 pub mod cache;
 pub mod error;
 pub mod fetcher;
+pub mod http;
 pub mod sources;
 pub mod value;
 
 pub use cache::{Cache, CacheConfig};
 pub use error::{FetchError, Result};
-pub use fetcher::{FetchConfig, Fetcher};
+pub use fetcher::{FetchConfig, FetchStats, Fetcher, SourceStats};
+pub use http::HttpClient;
 pub use sources::DataSource;
-pub use value::DataValue;
+pub use value::{DataValue, Locale};
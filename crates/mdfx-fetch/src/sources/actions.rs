@@ -1,6 +1,7 @@
 //! GitHub Actions API data source
 
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::DataSource;
 use crate::value::DataValue;
 use serde::Deserialize;
@@ -62,6 +63,7 @@ impl ActionsSource {
     /// Fetch latest workflow run from GitHub Actions API
     fn fetch_latest_run(
         &self,
+        http: &HttpClient,
         owner: &str,
         repo: &str,
         workflow: Option<&str>,
@@ -69,8 +71,13 @@ impl ActionsSource {
     ) -> Result<WorkflowRun> {
         let mut url = format!("{}/repos/{}/{}/actions/runs", self.api_base, owner, repo);
 
-        // Build query params
-        let mut params = vec!["per_page=1"];
+        // Build query params; runs are filtered by workflow name below, so
+        // ask for a page that covers more than the newest run
+        let mut params = vec![if workflow.is_some() {
+            "per_page=100"
+        } else {
+            "per_page=1"
+        }];
         let branch_param;
         if let Some(b) = branch {
             branch_param = format!("branch={}", b);
@@ -81,9 +88,9 @@ impl ActionsSource {
             url = format!("{}?{}", url, params.join("&"));
         }
 
-        let mut request = ureq::get(&url)
+        let mut request = http
+            .get(&url)
             .set("Accept", "application/vnd.github+json")
-            .set("User-Agent", "mdfx-fetch/1.0")
             .set("X-GitHub-Api-Version", "2022-11-28");
 
         // Add authorization header if token is available
@@ -179,9 +186,9 @@ impl DataSource for ActionsSource {
         "GitHub Actions"
     }
 
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue> {
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
         let (owner, repo, workflow, branch) = Self::parse_query(query)?;
-        let run = self.fetch_latest_run(owner, repo, workflow, branch)?;
+        let run = self.fetch_latest_run(http, owner, repo, workflow, branch)?;
 
         match metric {
             "status" => Ok(DataValue::String(run.status)),
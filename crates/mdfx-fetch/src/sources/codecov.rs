@@ -1,6 +1,7 @@
 //! Codecov API data source
 
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::DataSource;
 use crate::value::DataValue;
 use serde::Deserialize;
@@ -59,13 +60,16 @@ impl CodecovSource {
     }
 
     /// Fetch repository data from Codecov API
-    fn fetch_repo(&self, service: &str, owner: &str, repo: &str) -> Result<RepoResponse> {
+    fn fetch_repo(
+        &self,
+        http: &HttpClient,
+        service: &str,
+        owner: &str,
+        repo: &str,
+    ) -> Result<RepoResponse> {
         let url = format!("{}/{}/{}/repos/{}", self.api_base, service, owner, repo);
 
-        let response = ureq::get(&url)
-            .set("Accept", "application/json")
-            .set("User-Agent", "mdfx-fetch/1.0")
-            .call();
+        let response = http.get(&url).set("Accept", "application/json").call();
 
         match response {
             Ok(resp) => {
@@ -121,9 +125,9 @@ impl DataSource for CodecovSource {
         "Codecov"
     }
 
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue> {
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
         let (service, owner, repo) = Self::parse_query(query)?;
-        let data = self.fetch_repo(service, owner, repo)?;
+        let data = self.fetch_repo(http, service, owner, repo)?;
 
         match metric {
             "coverage" => {
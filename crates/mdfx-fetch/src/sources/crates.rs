@@ -1,6 +1,7 @@
 //! crates.io data source
 
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::DataSource;
 use crate::value::DataValue;
 use serde::Deserialize;
@@ -46,16 +47,10 @@ impl CratesSource {
     }
 
     /// Fetch crate data from crates.io
-    fn fetch_crate(&self, name: &str) -> Result<CrateResponse> {
+    fn fetch_crate(&self, http: &HttpClient, name: &str) -> Result<CrateResponse> {
         let url = format!("{}/crates/{}", self.api_base, name);
 
-        let response = ureq::get(&url)
-            .set("Accept", "application/json")
-            .set(
-                "User-Agent",
-                "mdfx-fetch/1.0 (https://github.com/blackwell-systems/mdfx)",
-            )
-            .call();
+        let response = http.get(&url).set("Accept", "application/json").call();
 
         match response {
             Ok(resp) => {
@@ -86,8 +81,8 @@ impl DataSource for CratesSource {
         "crates.io"
     }
 
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue> {
-        let data = self.fetch_crate(query)?;
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let data = self.fetch_crate(http, query)?;
 
         match metric {
             "version" => Ok(DataValue::String(data.max_version)),
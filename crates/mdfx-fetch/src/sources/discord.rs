@@ -0,0 +1,329 @@
+//! Discord API data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// Invite API response with counts (partial)
+#[derive(Debug, Deserialize)]
+struct InviteResponse {
+    #[serde(default)]
+    guild: Option<Guild>,
+    #[serde(default)]
+    approximate_member_count: Option<u64>,
+    #[serde(default)]
+    approximate_presence_count: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Guild {
+    name: String,
+}
+
+/// Server widget response (partial)
+#[derive(Debug, Deserialize)]
+struct WidgetResponse {
+    name: String,
+    presence_count: u64,
+    /// Set when the widget has an invite channel
+    #[serde(default)]
+    instant_invite: Option<String>,
+}
+
+/// Discord data source
+///
+/// Queries are either an invite code (`rust-lang`) or a numeric server id
+/// whose widget is enabled in the server settings.
+pub struct DiscordSource {
+    api_base: String,
+}
+
+impl Default for DiscordSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiscordSource {
+    /// Create a new Discord source
+    pub fn new() -> Self {
+        DiscordSource {
+            api_base: "https://discord.com/api/v10".to_string(),
+        }
+    }
+
+    /// GET a Discord API path and parse the JSON body
+    fn get<T: DeserializeOwned>(&self, http: &HttpClient, path: &str, what: &str) -> Result<T> {
+        let url = format!("{}/{}", self.api_base, path);
+
+        let response = http.get(&url).set("Accept", "application/json").call();
+
+        match response {
+            Ok(resp) => {
+                let body: T = resp.into_json().map_err(|e| {
+                    FetchError::ParseError(format!("Failed to parse Discord response: {}", e))
+                })?;
+                Ok(body)
+            }
+            Err(ureq::Error::Status(404, _)) => Err(FetchError::NotFound(what.to_string())),
+            // Servers return 403 when the widget is turned off
+            Err(ureq::Error::Status(403, _)) => Err(FetchError::ApiError {
+                status: 403,
+                message: format!("{} (enable the server widget)", what),
+            }),
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<f64>().ok())
+                    .map(|s| s.ceil() as u64)
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// Fetch an invite with approximate member and online counts
+    fn fetch_invite(&self, http: &HttpClient, code: &str) -> Result<InviteResponse> {
+        self.get(
+            http,
+            &format!("invites/{}?with_counts=true", code),
+            &format!("invite {}", code),
+        )
+    }
+
+    /// Fetch the public widget of a server
+    fn fetch_widget(&self, http: &HttpClient, server_id: &str) -> Result<WidgetResponse> {
+        self.get(
+            http,
+            &format!("guilds/{}/widget.json", server_id),
+            &format!("server {}", server_id),
+        )
+    }
+
+    /// Whether a query is a server id rather than an invite code
+    fn is_server_id(query: &str) -> bool {
+        query.len() >= 17 && query.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    /// Parse query into an invite code or server id
+    ///
+    /// Accepts full invite links (`discord.gg/rust-lang`) as well as bare codes.
+    fn parse_query(query: &str) -> Result<&str> {
+        let code = query
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default();
+        if code.is_empty() || !code.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+            return Err(FetchError::ParseError(format!(
+                "Invalid Discord query '{}'. Expected an invite code or server id",
+                query
+            )));
+        }
+        Ok(code)
+    }
+
+    /// Invite code at the end of a widget's instant invite URL
+    fn invite_code(widget: &WidgetResponse) -> Option<&str> {
+        let url = widget.instant_invite.as_deref()?;
+        url.rsplit('/').next().filter(|code| !code.is_empty())
+    }
+
+    /// Value of a metric for an invite
+    fn invite_value(&self, data: &InviteResponse, metric: &str) -> Result<DataValue> {
+        match metric {
+            "members" => Ok(DataValue::Number(
+                data.approximate_member_count.unwrap_or(0),
+            )),
+            "online" => Ok(DataValue::Number(
+                data.approximate_presence_count.unwrap_or(0),
+            )),
+            "name" => Ok(DataValue::String(
+                data.guild
+                    .as_ref()
+                    .map(|g| g.name.clone())
+                    .unwrap_or_else(|| "unknown".to_string()),
+            )),
+            _ => Err(self.unknown_metric(metric)),
+        }
+    }
+
+    /// Value of a metric the widget reports directly
+    fn widget_value(&self, data: &WidgetResponse, metric: &str) -> Option<DataValue> {
+        match metric {
+            "online" => Some(DataValue::Number(data.presence_count)),
+            "name" => Some(DataValue::String(data.name.clone())),
+            _ => None,
+        }
+    }
+
+    fn unknown_metric(&self, metric: &str) -> FetchError {
+        FetchError::UnknownMetric {
+            metric: metric.to_string(),
+            available: self
+                .available_metrics()
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl DataSource for DiscordSource {
+    fn id(&self) -> &'static str {
+        "discord"
+    }
+
+    fn name(&self) -> &'static str {
+        "Discord"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        if !self.available_metrics().contains(&metric) {
+            return Err(self.unknown_metric(metric));
+        }
+        let code = Self::parse_query(query)?;
+        if !Self::is_server_id(code) {
+            let data = self.fetch_invite(http, code)?;
+            return self.invite_value(&data, metric);
+        }
+
+        // The widget only lists online members; the total comes from its invite
+        let widget = self.fetch_widget(http, code)?;
+        if let Some(value) = self.widget_value(&widget, metric) {
+            return Ok(value);
+        }
+        let invite = Self::invite_code(&widget).ok_or_else(|| FetchError::ApiError {
+            status: 403,
+            message: format!(
+                "server {} widget has no invite channel; use an invite code for {}",
+                code, metric
+            ),
+        })?;
+        let data = self.fetch_invite(http, invite)?;
+        self.invite_value(&data, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &["members", "online", "name"]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        3600 // 1 hour - counts are approximate anyway
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "members" => "Discord",
+            "online" => "Online",
+            "name" => "Server",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, _value: &DataValue) -> Option<&str> {
+        match metric {
+            "members" | "online" => Some("5865F2"), // Discord blurple
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const INVITE: &str = r#"{
+        "type": 0,
+        "code": "rust-lang",
+        "guild": {"id": "442252698964721669", "name": "Rust Programming Language"},
+        "approximate_member_count": 48213,
+        "approximate_presence_count": 9120
+    }"#;
+
+    const WIDGET: &str = r#"{
+        "id": "442252698964721669",
+        "name": "Rust Programming Language",
+        "instant_invite": "https://discord.com/invite/rust-lang",
+        "channels": [],
+        "members": [{"id": "0", "username": "ferris", "status": "online"}],
+        "presence_count": 9087
+    }"#;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("rust-lang", Some("rust-lang"))]
+    #[case("https://discord.gg/rust-lang", Some("rust-lang"))]
+    #[case("discord.com/invite/rust-lang/", Some("rust-lang"))]
+    #[case("442252698964721669", Some("442252698964721669"))]
+    #[case("", None)]
+    #[case("rust lang", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(DiscordSource::parse_query(input).ok(), expected);
+    }
+
+    #[rstest]
+    #[case("442252698964721669", true)]
+    #[case("rust-lang", false)]
+    #[case("12345", false)]
+    fn test_is_server_id(#[case] query: &str, #[case] expected: bool) {
+        assert_eq!(DiscordSource::is_server_id(query), expected);
+    }
+
+    // ========================================================================
+    // Metric Values (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("members", DataValue::Number(48213))]
+    #[case("online", DataValue::Number(9120))]
+    #[case("name", DataValue::String("Rust Programming Language".to_string()))]
+    fn test_invite_value(#[case] metric: &str, #[case] expected: DataValue) {
+        let data: InviteResponse = serde_json::from_str(INVITE).unwrap();
+        let source = DiscordSource::new();
+        assert_eq!(source.invite_value(&data, metric).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("online", Some(DataValue::Number(9087)))]
+    #[case("name", Some(DataValue::String("Rust Programming Language".to_string())))]
+    #[case("members", None)]
+    fn test_widget_value(#[case] metric: &str, #[case] expected: Option<DataValue>) {
+        let data: WidgetResponse = serde_json::from_str(WIDGET).unwrap();
+        let source = DiscordSource::new();
+        assert_eq!(source.widget_value(&data, metric), expected);
+    }
+
+    #[test]
+    fn test_widget_invite_code() {
+        let mut data: WidgetResponse = serde_json::from_str(WIDGET).unwrap();
+        assert_eq!(DiscordSource::invite_code(&data), Some("rust-lang"));
+        data.instant_invite = None;
+        assert_eq!(DiscordSource::invite_code(&data), None);
+    }
+
+    #[test]
+    fn test_unknown_metric() {
+        let source = DiscordSource::new();
+        assert!(matches!(
+            source.fetch(&HttpClient::default(), "rust-lang", "stars"),
+            Err(FetchError::UnknownMetric { .. })
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 //! Docker Hub data source
 
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::DataSource;
 use crate::value::DataValue;
 use serde::Deserialize;
@@ -64,13 +65,10 @@ impl DockerSource {
     }
 
     /// Fetch repository data from Docker Hub
-    fn fetch_repo(&self, namespace: &str, repo: &str) -> Result<RepoResponse> {
+    fn fetch_repo(&self, http: &HttpClient, namespace: &str, repo: &str) -> Result<RepoResponse> {
         let url = format!("{}/repositories/{}/{}", self.api_base, namespace, repo);
 
-        let response = ureq::get(&url)
-            .set("Accept", "application/json")
-            .set("User-Agent", "mdfx-fetch/1.0")
-            .call();
+        let response = http.get(&url).set("Accept", "application/json").call();
 
         match response {
             Ok(resp) => {
@@ -94,16 +92,13 @@ impl DockerSource {
     }
 
     /// Fetch latest tag for a repository
-    fn fetch_latest_tag(&self, namespace: &str, repo: &str) -> Result<String> {
+    fn fetch_latest_tag(&self, http: &HttpClient, namespace: &str, repo: &str) -> Result<String> {
         let url = format!(
             "{}/repositories/{}/{}/tags?page_size=1&ordering=last_updated",
             self.api_base, namespace, repo
         );
 
-        let response = ureq::get(&url)
-            .set("Accept", "application/json")
-            .set("User-Agent", "mdfx-fetch/1.0")
-            .call();
+        let response = http.get(&url).set("Accept", "application/json").call();
 
         match response {
             Ok(resp) => {
@@ -144,35 +139,43 @@ impl DataSource for DockerSource {
         "Docker Hub"
     }
 
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue> {
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
         let (namespace, repo) = Self::parse_query(query);
 
         match metric {
             "pulls" => {
-                let data = self.fetch_repo(namespace, repo)?;
+                let data = self.fetch_repo(http, namespace, repo)?;
                 Ok(DataValue::String(Self::format_pulls(data.pull_count)))
             }
             "pulls_raw" => {
-                let data = self.fetch_repo(namespace, repo)?;
+                let data = self.fetch_repo(http, namespace, repo)?;
                 Ok(DataValue::Number(data.pull_count))
             }
             "stars" => {
-                let data = self.fetch_repo(namespace, repo)?;
+                let data = self.fetch_repo(http, namespace, repo)?;
                 Ok(DataValue::Number(data.star_count))
             }
             "tag" => {
-                let tag = self.fetch_latest_tag(namespace, repo)?;
+                let tag = self.fetch_latest_tag(http, namespace, repo)?;
                 Ok(DataValue::String(tag))
             }
             "description" => {
-                let data = self.fetch_repo(namespace, repo)?;
+                let data = self.fetch_repo(http, namespace, repo)?;
                 Ok(DataValue::String(
                     data.description
                         .unwrap_or_else(|| "No description".to_string()),
                 ))
             }
+            "updated" => {
+                let data = self.fetch_repo(http, namespace, repo)?;
+                Ok(DataValue::String(
+                    data.last_updated
+                        .map(|d| d.chars().take(10).collect())
+                        .unwrap_or_else(|| "unknown".to_string()),
+                ))
+            }
             "official" => {
-                let data = self.fetch_repo(namespace, repo)?;
+                let data = self.fetch_repo(http, namespace, repo)?;
                 Ok(DataValue::String(
                     if data.is_official.unwrap_or(false) {
                         "Official"
@@ -201,6 +204,7 @@ impl DataSource for DockerSource {
             "tag",
             "description",
             "official",
+            "updated",
         ]
     }
 
@@ -215,6 +219,7 @@ impl DataSource for DockerSource {
             "tag" => "Tag",
             "description" => "Description",
             "official" => "Type",
+            "updated" => "Updated",
             _ => "Unknown",
         }
     }
@@ -256,6 +261,7 @@ mod tests {
     #[case("pulls", "Pulls")]
     #[case("stars", "Stars")]
     #[case("tag", "Tag")]
+    #[case("updated", "Updated")]
     #[case("unknown", "Unknown")]
     fn test_metric_label(#[case] metric: &str, #[case] expected: &str) {
         let source = DockerSource::new();
@@ -0,0 +1,207 @@
+//! docs.rs build status data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use serde::Deserialize;
+
+/// docs.rs release status response
+#[derive(Debug, Deserialize)]
+struct StatusResponse {
+    doc_status: bool,
+    version: String,
+}
+
+/// docs.rs data source
+pub struct DocsRsSource {
+    api_base: String,
+}
+
+impl Default for DocsRsSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocsRsSource {
+    /// Create a new docs.rs source
+    pub fn new() -> Self {
+        DocsRsSource {
+            api_base: "https://docs.rs".to_string(),
+        }
+    }
+
+    /// Fetch the documentation build status of a release
+    fn fetch_status(&self, http: &HttpClient, name: &str, version: &str) -> Result<StatusResponse> {
+        let url = format!("{}/crate/{}/{}/status.json", self.api_base, name, version);
+
+        let response = http.get(&url).set("Accept", "application/json").call();
+
+        match response {
+            Ok(resp) => {
+                let body: StatusResponse = resp.into_json().map_err(|e| {
+                    FetchError::ParseError(format!("Failed to parse docs.rs response: {}", e))
+                })?;
+                Ok(body)
+            }
+            Err(ureq::Error::Status(404, _)) => {
+                Err(FetchError::NotFound(format!("{}@{}", name, version)))
+            }
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// Parse query into (crate, version), defaulting to the latest release
+    fn parse_query(query: &str) -> Result<(&str, &str)> {
+        let (name, version) = query.split_once('@').unwrap_or((query, "latest"));
+        if name.is_empty() || version.is_empty() || name.contains('/') {
+            return Err(FetchError::ParseError(format!(
+                "Invalid docs.rs query '{}'. Expected format: crate or crate@version",
+                query
+            )));
+        }
+        Ok((name, version))
+    }
+
+    /// Value of a metric for a release status
+    fn metric_value(&self, data: &StatusResponse, metric: &str) -> Result<DataValue> {
+        match metric {
+            "status" => Ok(DataValue::String(
+                if data.doc_status {
+                    "passing"
+                } else {
+                    "failing"
+                }
+                .to_string(),
+            )),
+            "version" => Ok(DataValue::String(data.version.clone())),
+            _ => Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: self
+                    .available_metrics()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl DataSource for DocsRsSource {
+    fn id(&self) -> &'static str {
+        "docsrs"
+    }
+
+    fn name(&self) -> &'static str {
+        "docs.rs"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let (name, version) = Self::parse_query(query)?;
+        let data = self.fetch_status(http, name, version)?;
+        self.metric_value(&data, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &["status", "version"]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        3600 // 1 hour - builds finish within minutes of a release
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "status" => "docs.rs",
+            "version" => "Docs version",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, value: &DataValue) -> Option<&str> {
+        match metric {
+            "status" => Some(build_status_color(value)),
+            _ => None,
+        }
+    }
+}
+
+/// Color for a documentation build status
+pub(crate) fn build_status_color(value: &DataValue) -> &'static str {
+    match value {
+        DataValue::String(s) if s == "passing" => "22C55E", // Green
+        DataValue::String(s) if s == "failing" => "EF4444", // Red
+        _ => "6B7280",                                      // Gray - unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("serde", Some(("serde", "latest")))]
+    #[case("serde@1.0.203", Some(("serde", "1.0.203")))]
+    #[case("", None)]
+    #[case("serde@", None)]
+    #[case("rust-lang/rust", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<(&str, &str)>) {
+        assert_eq!(DocsRsSource::parse_query(input).ok(), expected);
+    }
+
+    // ========================================================================
+    // Metric Values (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case(r#"{"doc_status": true, "version": "1.0.203"}"#, "status", DataValue::String("passing".to_string()))]
+    #[case(r#"{"doc_status": false, "version": "0.3.1"}"#, "status", DataValue::String("failing".to_string()))]
+    #[case(r#"{"doc_status": true, "version": "1.0.203"}"#, "version", DataValue::String("1.0.203".to_string()))]
+    fn test_metric_value(
+        #[case] response: &str,
+        #[case] metric: &str,
+        #[case] expected: DataValue,
+    ) {
+        let data: StatusResponse = serde_json::from_str(response).unwrap();
+        let source = DocsRsSource::new();
+        assert_eq!(source.metric_value(&data, metric).unwrap(), expected);
+    }
+
+    // ========================================================================
+    // Status Colors (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("status", DataValue::String("passing".to_string()), Some("22C55E"))]
+    #[case("status", DataValue::String("failing".to_string()), Some("EF4444"))]
+    #[case("version", DataValue::String("1.0.203".to_string()), None)]
+    fn test_metric_colors(
+        #[case] metric: &str,
+        #[case] value: DataValue,
+        #[case] expected: Option<&str>,
+    ) {
+        let source = DocsRsSource::new();
+        assert_eq!(source.metric_color(metric, &value), expected);
+    }
+}
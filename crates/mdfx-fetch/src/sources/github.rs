@@ -1,8 +1,10 @@
 //! GitHub API data source
 
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::DataSource;
 use crate::value::DataValue;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 
 /// GitHub repository API response (partial)
@@ -21,6 +23,7 @@ struct RepoResponse {
     default_branch: String,
     topics: Vec<String>,
     description: Option<String>,
+    pushed_at: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,6 +33,32 @@ struct LicenseInfo {
     name: String,
 }
 
+/// GitHub release API response (partial)
+#[derive(Debug, Deserialize)]
+struct Release {
+    published_at: Option<String>,
+    #[serde(default)]
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseAsset {
+    download_count: u64,
+}
+
+impl Release {
+    /// Downloads of all assets of the release
+    fn downloads(&self) -> u64 {
+        self.assets.iter().map(|a| a.download_count).sum()
+    }
+}
+
+/// Releases per page when summing downloads over all releases
+const RELEASES_PER_PAGE: usize = 100;
+
+/// Most release pages read for `downloads`, so huge histories stay bounded
+const MAX_RELEASE_PAGES: usize = 10;
+
 /// GitHub data source
 pub struct GitHubSource {
     api_base: String,
@@ -65,12 +94,49 @@ impl GitHubSource {
     }
 
     /// Fetch repository data from GitHub API
-    fn fetch_repo(&self, owner: &str, repo: &str) -> Result<RepoResponse> {
+    fn fetch_repo(&self, http: &HttpClient, owner: &str, repo: &str) -> Result<RepoResponse> {
         let url = format!("{}/repos/{}/{}", self.api_base, owner, repo);
+        self.get_json(http, &url, || format!("{}/{}", owner, repo))
+    }
+
+    /// Fetch the latest release (not a draft or prerelease)
+    fn fetch_latest_release(&self, http: &HttpClient, owner: &str, repo: &str) -> Result<Release> {
+        let url = format!("{}/repos/{}/{}/releases/latest", self.api_base, owner, repo);
+        self.get_json(http, &url, || format!("{}/{} has no releases", owner, repo))
+    }
+
+    /// Sum the asset downloads of every release
+    ///
+    /// Reads at most [`MAX_RELEASE_PAGES`] pages of releases, newest first.
+    fn fetch_total_downloads(&self, http: &HttpClient, owner: &str, repo: &str) -> Result<u64> {
+        let mut total = 0;
+        for page in 1..=MAX_RELEASE_PAGES {
+            let url = format!(
+                "{}/repos/{}/{}/releases?per_page={}&page={}",
+                self.api_base, owner, repo, RELEASES_PER_PAGE, page
+            );
+            let releases: Vec<Release> =
+                self.get_json(http, &url, || format!("{}/{}", owner, repo))?;
+            total += releases.iter().map(Release::downloads).sum::<u64>();
+            if releases.len() < RELEASES_PER_PAGE {
+                break;
+            }
+        }
+        Ok(total)
+    }
 
-        let mut request = ureq::get(&url)
+    /// GET a GitHub API URL and parse the JSON response
+    ///
+    /// A 404 becomes [`FetchError::NotFound`] with the message from `not_found`.
+    fn get_json<T: DeserializeOwned>(
+        &self,
+        http: &HttpClient,
+        url: &str,
+        not_found: impl FnOnce() -> String,
+    ) -> Result<T> {
+        let mut request = http
+            .get(url)
             .set("Accept", "application/vnd.github+json")
-            .set("User-Agent", "mdfx-fetch/1.0")
             .set("X-GitHub-Api-Version", "2022-11-28");
 
         // Add authorization header if token is available
@@ -81,15 +147,10 @@ impl GitHubSource {
         let response = request.call();
 
         match response {
-            Ok(resp) => {
-                let body: RepoResponse = resp.into_json().map_err(|e| {
-                    FetchError::ParseError(format!("Failed to parse GitHub response: {}", e))
-                })?;
-                Ok(body)
-            }
-            Err(ureq::Error::Status(404, _)) => {
-                Err(FetchError::NotFound(format!("{}/{}", owner, repo)))
-            }
+            Ok(resp) => resp.into_json().map_err(|e| {
+                FetchError::ParseError(format!("Failed to parse GitHub response: {}", e))
+            }),
+            Err(ureq::Error::Status(404, _)) => Err(FetchError::NotFound(not_found())),
             Err(ureq::Error::Status(403, resp)) => {
                 // Check for rate limiting
                 let retry_after = resp
@@ -140,9 +201,28 @@ impl DataSource for GitHubSource {
         "GitHub"
     }
 
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue> {
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
         let (owner, repo) = Self::parse_query(query)?;
-        let data = self.fetch_repo(owner, repo)?;
+
+        // Release metrics come from the releases API, not the repository
+        match metric {
+            "downloads" => {
+                return Ok(DataValue::Number(
+                    self.fetch_total_downloads(http, owner, repo)?,
+                ))
+            }
+            "release_downloads" | "assets" | "release_age" => {
+                let release = self.fetch_latest_release(http, owner, repo)?;
+                return Ok(match metric {
+                    "release_downloads" => DataValue::Number(release.downloads()),
+                    "assets" => DataValue::Number(release.assets.len() as u64),
+                    _ => DataValue::Number(days_since(release.published_at.as_deref())?),
+                });
+            }
+            _ => {}
+        }
+
+        let data = self.fetch_repo(http, owner, repo)?;
 
         match metric {
             "stars" => Ok(DataValue::Number(data.stargazers_count)),
@@ -171,6 +251,11 @@ impl DataSource for GitHubSource {
                 data.description
                     .unwrap_or_else(|| "No description".to_string()),
             )),
+            "last_commit" => Ok(DataValue::String(
+                data.pushed_at
+                    .map(|d| d.chars().take(10).collect())
+                    .unwrap_or_else(|| "unknown".to_string()),
+            )),
             _ => Err(FetchError::UnknownMetric {
                 metric: metric.to_string(),
                 available: self
@@ -195,6 +280,11 @@ impl DataSource for GitHubSource {
             "branch",
             "topics",
             "description",
+            "last_commit",
+            "downloads",
+            "release_downloads",
+            "assets",
+            "release_age",
         ]
     }
 
@@ -215,6 +305,11 @@ impl DataSource for GitHubSource {
             "branch" => "Branch",
             "topics" => "Topics",
             "description" => "Description",
+            "last_commit" => "Last commit",
+            "downloads" => "Downloads",
+            "release_downloads" => "Release downloads",
+            "assets" => "Assets",
+            "release_age" => "Days since release",
             _ => "Unknown",
         }
     }
@@ -231,6 +326,13 @@ impl DataSource for GitHubSource {
                 }
             }
             "forks" | "issues" | "watchers" => Some("3B82F6"), // Blue
+            "downloads" | "release_downloads" | "assets" => Some("3B82F6"), // Blue
+            "release_age" => match value.as_number() {
+                Some(days) if days <= 30 => Some("22C55E"), // Green - fresh
+                Some(days) if days <= 180 => Some("EAB308"), // Yellow - aging
+                Some(_) => Some("EF4444"),                  // Red - stale
+                None => None,
+            },
             "license" => {
                 // Color based on license type
                 if let DataValue::String(license) = value {
@@ -260,6 +362,17 @@ impl DataSource for GitHubSource {
     }
 }
 
+/// Whole days from an ISO 8601 date-time until now
+fn days_since(published_at: Option<&str>) -> Result<u64> {
+    let published = published_at
+        .and_then(|date| DataValue::from(date).as_days())
+        .ok_or_else(|| FetchError::ParseError("Release has no publish date".to_string()))?;
+    let today = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64 / 86_400);
+    Ok((today - published).max(0) as u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,6 +417,10 @@ mod tests {
     #[case("license", DataValue::String("NONE".to_string()), Some("6B7280"))]
     #[case("archived", DataValue::Bool(true), Some("EF4444"))]
     #[case("archived", DataValue::Bool(false), Some("22C55E"))]
+    #[case("release_downloads", DataValue::Number(1540), Some("3B82F6"))]
+    #[case("release_age", DataValue::Number(12), Some("22C55E"))]
+    #[case("release_age", DataValue::Number(90), Some("EAB308"))]
+    #[case("release_age", DataValue::Number(400), Some("EF4444"))]
     fn test_metric_colors(
         #[case] metric: &str,
         #[case] value: DataValue,
@@ -320,5 +437,34 @@ mod tests {
         assert!(metrics.contains(&"stars"));
         assert!(metrics.contains(&"license"));
         assert!(metrics.contains(&"forks"));
+        assert!(metrics.contains(&"last_commit"));
+        assert!(metrics.contains(&"release_downloads"));
+    }
+
+    #[test]
+    fn test_release_downloads() {
+        let release: Release = serde_json::from_str(
+            r#"{
+                "tag_name": "v1.2.0",
+                "published_at": "2024-06-03T12:00:00Z",
+                "assets": [
+                    {"name": "app-linux.tar.gz", "download_count": 1200},
+                    {"name": "app-macos.tar.gz", "download_count": 340}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(release.downloads(), 1540);
+        assert_eq!(release.assets.len(), 2);
+
+        let empty: Release = serde_json::from_str(r#"{"published_at": null}"#).unwrap();
+        assert_eq!(empty.downloads(), 0);
+    }
+
+    #[test]
+    fn test_days_since() {
+        assert!(days_since(Some("1970-01-01T00:00:00Z")).unwrap() > 19_000);
+        assert_eq!(days_since(Some("2999-01-01")).unwrap(), 0);
+        assert!(days_since(None).is_err());
     }
 }
@@ -0,0 +1,167 @@
+//! Remote SVG icons for badges (`icon=url:https://...`)
+//!
+//! The query is the icon's URL and the only metric is `svg`, the document
+//! as downloaded. Sanitizing and embedding it is up to the caller; caching
+//! it here means each icon is downloaded once a week, not once per build.
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use std::io::Read;
+
+/// Largest icon accepted, in bytes
+pub const MAX_ICON_BYTES: u64 = 256 * 1024;
+
+/// Remote SVG icon source
+#[derive(Default)]
+pub struct IconSource;
+
+impl IconSource {
+    /// Create a new icon source
+    pub fn new() -> Self {
+        IconSource
+    }
+
+    /// Check that `url` is an https URL
+    fn check_url(url: &str) -> Result<()> {
+        match url.strip_prefix("https://") {
+            Some(rest) if !rest.is_empty() && !rest.starts_with('/') => Ok(()),
+            _ => Err(FetchError::ParseError(format!(
+                "Invalid icon URL '{}'. Expected an https:// URL",
+                url
+            ))),
+        }
+    }
+
+    /// Check that a downloaded body is an SVG document
+    fn check_svg(url: &str, body: &[u8]) -> Result<String> {
+        if body.len() as u64 > MAX_ICON_BYTES {
+            return Err(FetchError::ParseError(format!(
+                "Icon {} is larger than {} KiB",
+                url,
+                MAX_ICON_BYTES / 1024
+            )));
+        }
+        let text = std::str::from_utf8(body)
+            .map_err(|_| FetchError::ParseError(format!("Icon {} is not UTF-8 text", url)))?;
+        if !text.contains("<svg") {
+            return Err(FetchError::ParseError(format!(
+                "Icon {} is not an SVG document",
+                url
+            )));
+        }
+        Ok(text.to_string())
+    }
+
+    /// Download an icon
+    fn fetch_icon(&self, http: &HttpClient, url: &str) -> Result<String> {
+        let response = http.get(url).set("Accept", "image/svg+xml").call();
+
+        match response {
+            Ok(resp) => {
+                let mut body = Vec::new();
+                resp.into_reader()
+                    .take(MAX_ICON_BYTES + 1)
+                    .read_to_end(&mut body)?;
+                Self::check_svg(url, &body)
+            }
+            Err(ureq::Error::Status(404, _)) => Err(FetchError::NotFound(url.to_string())),
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+}
+
+impl DataSource for IconSource {
+    fn id(&self) -> &'static str {
+        "icon"
+    }
+
+    fn name(&self) -> &'static str {
+        "Remote icon"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        if metric != "svg" {
+            return Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: vec!["svg".to_string()],
+            });
+        }
+        Self::check_url(query)?;
+        Ok(DataValue::String(self.fetch_icon(http, query)?))
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &["svg"]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        7 * 24 * 3600 // 1 week - logos rarely change
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "svg" => "icon",
+            _ => "Unknown",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("https://example.com/logo.svg", true)]
+    #[case("https://cdn.example.com/a/b.svg?v=2", true)]
+    #[case("http://example.com/logo.svg", false)]
+    #[case("file:///etc/passwd", false)]
+    #[case("https://", false)]
+    #[case("https:///logo.svg", false)]
+    fn test_check_url(#[case] url: &str, #[case] valid: bool) {
+        assert_eq!(IconSource::check_url(url).is_ok(), valid);
+    }
+
+    #[rstest]
+    #[case(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>", true)]
+    #[case(b"<?xml version=\"1.0\"?>\n<svg viewBox=\"0 0 24 24\"></svg>", true)]
+    #[case(b"<html>Not found</html>", false)]
+    #[case(b"\xff\xfe<svg/>", false)]
+    fn test_check_svg(#[case] body: &[u8], #[case] valid: bool) {
+        assert_eq!(IconSource::check_svg("x", body).is_ok(), valid);
+    }
+
+    #[test]
+    fn test_check_svg_size_limit() {
+        let mut body = b"<svg>".to_vec();
+        body.resize(MAX_ICON_BYTES as usize + 1, b' ');
+        assert!(IconSource::check_svg("x", &body).is_err());
+    }
+
+    #[test]
+    fn test_unknown_metric() {
+        let config = crate::FetchConfig::default();
+        let http = HttpClient::new(&config).unwrap();
+        assert!(matches!(
+            IconSource::new().fetch(&http, "https://example.com/a.svg", "png"),
+            Err(FetchError::UnknownMetric { .. })
+        ));
+    }
+}
@@ -0,0 +1,294 @@
+//! Libraries.io API data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use serde::Deserialize;
+
+/// Environment variable holding the Libraries.io API key
+const API_KEY_VAR: &str = "LIBRARIES_IO_API_KEY";
+
+/// Libraries.io project API response (partial)
+#[derive(Debug, Deserialize)]
+struct ProjectResponse {
+    #[serde(default)]
+    rank: u64,
+    #[serde(default)]
+    dependents_count: u64,
+    #[serde(default)]
+    dependent_repos_count: u64,
+    #[serde(default)]
+    stars: u64,
+    #[serde(default)]
+    forks: u64,
+    #[serde(default)]
+    latest_release_number: Option<String>,
+}
+
+/// Libraries.io data source
+pub struct LibrariesIoSource {
+    api_base: String,
+    api_key: Option<String>,
+}
+
+impl Default for LibrariesIoSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LibrariesIoSource {
+    /// Create a new Libraries.io source
+    ///
+    /// Reads LIBRARIES_IO_API_KEY from environment; every request needs a
+    /// key (free with a Libraries.io account).
+    pub fn new() -> Self {
+        LibrariesIoSource {
+            api_base: "https://libraries.io/api".to_string(),
+            api_key: std::env::var(API_KEY_VAR).ok().filter(|k| !k.is_empty()),
+        }
+    }
+
+    /// Create a Libraries.io source with a specific API key
+    pub fn with_api_key(api_key: String) -> Self {
+        LibrariesIoSource {
+            api_base: "https://libraries.io/api".to_string(),
+            api_key: Some(api_key),
+        }
+    }
+
+    /// Fetch project data from Libraries.io API
+    fn fetch_project(
+        &self,
+        http: &HttpClient,
+        platform: &str,
+        name: &str,
+    ) -> Result<ProjectResponse> {
+        let api_key = self
+            .api_key
+            .as_deref()
+            .ok_or_else(|| FetchError::MissingCredentials(API_KEY_VAR.to_string()))?;
+        // Scoped npm names (@scope/name) are one path segment
+        let url = format!(
+            "{}/{}/{}",
+            self.api_base,
+            platform,
+            name.replace('@', "%40").replace('/', "%2F")
+        );
+
+        let response = http
+            .get(&url)
+            .query("api_key", api_key)
+            .set("Accept", "application/json")
+            .call();
+
+        match response {
+            Ok(resp) => {
+                let body: ProjectResponse = resp.into_json().map_err(|e| {
+                    FetchError::ParseError(format!("Failed to parse Libraries.io response: {}", e))
+                })?;
+                Ok(body)
+            }
+            Err(ureq::Error::Status(404, _)) => {
+                Err(FetchError::NotFound(format!("{}/{}", platform, name)))
+            }
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// Parse query into (platform, name)
+    ///
+    /// Formats: "cargo/serde", "npm/@babel/core", "pypi/requests"
+    fn parse_query(query: &str) -> Result<(&str, &str)> {
+        match query.split_once('/') {
+            Some((platform, name)) if !platform.is_empty() && !name.is_empty() => {
+                Ok((platform, name))
+            }
+            _ => Err(FetchError::ParseError(format!(
+                "Invalid Libraries.io query '{}'. Expected format: platform/package",
+                query
+            ))),
+        }
+    }
+
+    /// Value of a metric for a project
+    fn metric_value(&self, data: &ProjectResponse, metric: &str) -> Result<DataValue> {
+        match metric {
+            "sourcerank" => Ok(DataValue::Number(data.rank)),
+            "dependents" => Ok(DataValue::Number(data.dependents_count)),
+            "dependent_repos" => Ok(DataValue::Number(data.dependent_repos_count)),
+            "stars" => Ok(DataValue::Number(data.stars)),
+            "forks" => Ok(DataValue::Number(data.forks)),
+            "version" => Ok(DataValue::String(
+                data.latest_release_number
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+            )),
+            _ => Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: self
+                    .available_metrics()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl DataSource for LibrariesIoSource {
+    fn id(&self) -> &'static str {
+        "librariesio"
+    }
+
+    fn name(&self) -> &'static str {
+        "Libraries.io"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let (platform, name) = Self::parse_query(query)?;
+        let data = self.fetch_project(http, platform, name)?;
+        self.metric_value(&data, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &[
+            "sourcerank",
+            "dependents",
+            "dependent_repos",
+            "stars",
+            "forks",
+            "version",
+        ]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        86400 // 24 hours - SourceRank changes slowly
+    }
+
+    fn requires_auth(&self) -> bool {
+        true
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "sourcerank" => "SourceRank",
+            "dependents" => "Dependents",
+            "dependent_repos" => "Dependent repos",
+            "stars" => "Stars",
+            "forks" => "Forks",
+            "version" => "Version",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, value: &DataValue) -> Option<&str> {
+        match metric {
+            "sourcerank" => {
+                // Color based on SourceRank (well-maintained popular packages score 20+)
+                match value.as_number() {
+                    Some(r) if r >= 20 => Some("22C55E"), // Green - excellent
+                    Some(r) if r >= 15 => Some("84CC16"), // Lime - good
+                    Some(r) if r >= 10 => Some("EAB308"), // Yellow - fair
+                    Some(r) if r >= 5 => Some("F97316"),  // Orange - weak
+                    _ => Some("EF4444"),                  // Red - poor
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const RESPONSE: &str = r#"{
+        "name": "serde",
+        "platform": "Cargo",
+        "rank": 28,
+        "dependents_count": 41250,
+        "dependent_repos_count": 350000,
+        "stars": 8900,
+        "forks": 770,
+        "latest_release_number": "1.0.203"
+    }"#;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("cargo/serde", Some(("cargo", "serde")))]
+    #[case("npm/@babel/core", Some(("npm", "@babel/core")))]
+    #[case("serde", None)]
+    #[case("cargo/", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<(&str, &str)>) {
+        assert_eq!(LibrariesIoSource::parse_query(input).ok(), expected);
+    }
+
+    // ========================================================================
+    // Metric Values (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("sourcerank", DataValue::Number(28))]
+    #[case("dependents", DataValue::Number(41250))]
+    #[case("dependent_repos", DataValue::Number(350000))]
+    #[case("version", DataValue::String("1.0.203".to_string()))]
+    fn test_metric_value(#[case] metric: &str, #[case] expected: DataValue) {
+        let data: ProjectResponse = serde_json::from_str(RESPONSE).unwrap();
+        let source = LibrariesIoSource::with_api_key("key".to_string());
+        assert_eq!(source.metric_value(&data, metric).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_missing_api_key() {
+        let source = LibrariesIoSource {
+            api_base: "http://127.0.0.1:9".to_string(),
+            api_key: None,
+        };
+        let err = source
+            .fetch(&HttpClient::default(), "cargo/serde", "sourcerank")
+            .unwrap_err();
+        assert!(matches!(err, FetchError::MissingCredentials(_)));
+        assert!(err.to_string().contains("LIBRARIES_IO_API_KEY"), "{}", err);
+    }
+
+    // ========================================================================
+    // SourceRank Colors (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("sourcerank", DataValue::Number(28), Some("22C55E"))]
+    #[case("sourcerank", DataValue::Number(15), Some("84CC16"))]
+    #[case("sourcerank", DataValue::Number(12), Some("EAB308"))]
+    #[case("sourcerank", DataValue::Number(5), Some("F97316"))]
+    #[case("sourcerank", DataValue::Number(2), Some("EF4444"))]
+    #[case("stars", DataValue::Number(100), None)]
+    fn test_metric_colors(
+        #[case] metric: &str,
+        #[case] value: DataValue,
+        #[case] expected: Option<&str>,
+    ) {
+        let source = LibrariesIoSource::with_api_key("key".to_string());
+        assert_eq!(source.metric_color(metric, &value), expected);
+    }
+}
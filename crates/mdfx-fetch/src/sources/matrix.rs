@@ -0,0 +1,316 @@
+//! Matrix client-server API data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// Guest registration response (partial)
+#[derive(Debug, Deserialize)]
+struct RegisterResponse {
+    access_token: String,
+}
+
+/// Room alias lookup response (partial)
+#[derive(Debug, Deserialize)]
+struct DirectoryResponse {
+    room_id: String,
+}
+
+/// Client discovery document (partial)
+#[derive(Debug, Deserialize)]
+struct WellKnown {
+    #[serde(rename = "m.homeserver")]
+    homeserver: BaseUrl,
+}
+
+#[derive(Debug, Deserialize)]
+struct BaseUrl {
+    base_url: String,
+}
+
+/// One state event of a room (partial)
+#[derive(Debug, Deserialize)]
+struct StateEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    content: serde_json::Value,
+}
+
+/// Matrix data source
+///
+/// Reads the member list of a public room as a guest, the same way
+/// shields.io does, so no account or token is needed.
+pub struct MatrixSource;
+
+impl Default for MatrixSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MatrixSource {
+    /// Create a new Matrix source
+    pub fn new() -> Self {
+        MatrixSource
+    }
+
+    /// Send a request and parse the JSON body
+    fn send<T: DeserializeOwned>(
+        request: ureq::Request,
+        body: Option<&str>,
+        what: &str,
+    ) -> Result<T> {
+        let request = request.set("Accept", "application/json");
+        let response = match body {
+            Some(body) => request
+                .set("Content-Type", "application/json")
+                .send_string(body),
+            None => request.call(),
+        };
+
+        match response {
+            Ok(resp) => {
+                let body: T = resp.into_json().map_err(|e| {
+                    FetchError::ParseError(format!("Failed to parse Matrix response: {}", e))
+                })?;
+                Ok(body)
+            }
+            Err(ureq::Error::Status(404, _)) => Err(FetchError::NotFound(what.to_string())),
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// Client API base URL for a server name, following `.well-known` delegation
+    fn homeserver(http: &HttpClient, server: &str) -> String {
+        let url = format!("https://{}/.well-known/matrix/client", server);
+        Self::send::<WellKnown>(http.get(&url), None, server)
+            .map(|w| w.homeserver.base_url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|_| format!("https://{}", server))
+    }
+
+    /// State events of the room behind an alias
+    fn fetch_state(&self, http: &HttpClient, server: &str, room: &str) -> Result<Vec<StateEvent>> {
+        let base = Self::homeserver(http, server);
+        let alias = format!("#{}:{}", room, server);
+
+        let guest: RegisterResponse = Self::send(
+            http.post(&format!("{}/_matrix/client/v3/register", base))
+                .query("kind", "guest"),
+            Some("{}"),
+            server,
+        )?;
+        let auth = format!("Bearer {}", guest.access_token);
+
+        let directory: DirectoryResponse = Self::send(
+            http.get(&format!(
+                "{}/_matrix/client/v3/directory/room/{}",
+                base,
+                encode(&alias)
+            ))
+            .set("Authorization", &auth),
+            None,
+            &alias,
+        )?;
+
+        Self::send(
+            http.get(&format!(
+                "{}/_matrix/client/v3/rooms/{}/state",
+                base,
+                encode(&directory.room_id)
+            ))
+            .set("Authorization", &auth),
+            None,
+            &alias,
+        )
+    }
+
+    /// Parse query into (server, room)
+    ///
+    /// Format: "matrix.org/rust" for `#rust:matrix.org` (a colon would end
+    /// the query in template syntax).
+    fn parse_query(query: &str) -> Result<(&str, &str)> {
+        let query = query.trim_start_matches('#');
+        match query.split_once('/') {
+            Some((server, room))
+                if !server.is_empty() && !room.is_empty() && !room.contains('/') =>
+            {
+                Ok((server, room))
+            }
+            _ => Err(FetchError::ParseError(format!(
+                "Invalid Matrix query '{}'. Expected format: server/room (for #room:server)",
+                query
+            ))),
+        }
+    }
+
+    /// Value of a metric for a room's state
+    fn metric_value(&self, state: &[StateEvent], metric: &str) -> Result<DataValue> {
+        let content = |kind: &str, key: &str| {
+            state
+                .iter()
+                .find(|e| e.kind == kind)
+                .and_then(|e| e.content.get(key))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+
+        match metric {
+            "members" => Ok(DataValue::Number(
+                state
+                    .iter()
+                    .filter(|e| e.kind == "m.room.member")
+                    .filter(|e| {
+                        e.content.get("membership").and_then(|m| m.as_str()) == Some("join")
+                    })
+                    .count() as u64,
+            )),
+            "name" => Ok(DataValue::String(
+                content("m.room.name", "name")
+                    .or_else(|| content("m.room.canonical_alias", "alias"))
+                    .unwrap_or_else(|| "unknown".to_string()),
+            )),
+            _ => Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: self
+                    .available_metrics()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/// Percent-encode a room alias or id for use as a path segment
+fn encode(segment: &str) -> String {
+    segment
+        .replace('%', "%25")
+        .replace('#', "%23")
+        .replace('!', "%21")
+        .replace(':', "%3A")
+        .replace('/', "%2F")
+}
+
+impl DataSource for MatrixSource {
+    fn id(&self) -> &'static str {
+        "matrix"
+    }
+
+    fn name(&self) -> &'static str {
+        "Matrix"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let (server, room) = Self::parse_query(query)?;
+        let state = self.fetch_state(http, server, room)?;
+        self.metric_value(&state, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &["members", "name"]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        3600 // 1 hour - each fetch registers a guest
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "members" => "Matrix",
+            "name" => "Room",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, _value: &DataValue) -> Option<&str> {
+        match metric {
+            "members" => Some("000000"), // Matrix black
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const STATE: &str = r##"[
+        {"type": "m.room.create", "state_key": "", "content": {"room_version": "10"}},
+        {"type": "m.room.name", "state_key": "", "content": {"name": "Rust"}},
+        {"type": "m.room.canonical_alias", "state_key": "", "content": {"alias": "#rust:matrix.org"}},
+        {"type": "m.room.member", "state_key": "@a:matrix.org", "content": {"membership": "join"}},
+        {"type": "m.room.member", "state_key": "@b:matrix.org", "content": {"membership": "join"}},
+        {"type": "m.room.member", "state_key": "@c:matrix.org", "content": {"membership": "leave"}},
+        {"type": "m.room.member", "state_key": "@d:example.com", "content": {"membership": "join"}},
+        {"type": "m.room.member", "state_key": "@e:example.com", "content": {"membership": "ban"}}
+    ]"##;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("matrix.org/rust", Some(("matrix.org", "rust")))]
+    #[case("#mozilla.org/rust-lang", Some(("mozilla.org", "rust-lang")))]
+    #[case("rust", None)]
+    #[case("matrix.org/", None)]
+    #[case("matrix.org/a/b", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<(&str, &str)>) {
+        assert_eq!(MatrixSource::parse_query(input).ok(), expected);
+    }
+
+    // ========================================================================
+    // Metric Values (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case(STATE, "members", DataValue::Number(3))]
+    #[case(STATE, "name", DataValue::String("Rust".to_string()))]
+    #[case(
+        r##"[{"type": "m.room.canonical_alias", "content": {"alias": "#rust:matrix.org"}}]"##,
+        "name",
+        DataValue::String("#rust:matrix.org".to_string())
+    )]
+    #[case("[]", "members", DataValue::Number(0))]
+    fn test_metric_value(#[case] state: &str, #[case] metric: &str, #[case] expected: DataValue) {
+        let state: Vec<StateEvent> = serde_json::from_str(state).unwrap();
+        let source = MatrixSource::new();
+        assert_eq!(source.metric_value(&state, metric).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_unknown_metric() {
+        let source = MatrixSource::new();
+        assert!(matches!(
+            source.metric_value(&[], "online"),
+            Err(FetchError::UnknownMetric { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case("#rust:matrix.org", "%23rust%3Amatrix.org")]
+    #[case("!abc:matrix.org", "%21abc%3Amatrix.org")]
+    fn test_encode(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(encode(input), expected);
+    }
+}
@@ -3,26 +3,51 @@
 mod actions;
 mod codecov;
 mod crates;
+mod discord;
 mod docker;
+mod docsrs;
 mod github;
+mod icon;
+mod librariesio;
+mod matrix;
 mod npm;
 mod nuget;
+mod opencollective;
+mod osv;
 mod packagist;
+mod patreon;
 mod pypi;
+mod readthedocs;
+mod reddit;
 mod rubygems;
+mod scorecard;
+mod sponsors;
 
 pub use actions::ActionsSource;
 pub use codecov::CodecovSource;
 pub use crates::CratesSource;
+pub use discord::DiscordSource;
 pub use docker::DockerSource;
+pub use docsrs::DocsRsSource;
 pub use github::GitHubSource;
+pub use icon::IconSource;
+pub use librariesio::LibrariesIoSource;
+pub use matrix::MatrixSource;
 pub use npm::NpmSource;
 pub use nuget::NuGetSource;
+pub use opencollective::OpenCollectiveSource;
+pub use osv::OsvSource;
 pub use packagist::PackagistSource;
+pub use patreon::PatreonSource;
 pub use pypi::PyPISource;
+pub use readthedocs::ReadTheDocsSource;
+pub use reddit::RedditSource;
 pub use rubygems::RubyGemsSource;
+pub use scorecard::ScorecardSource;
+pub use sponsors::SponsorsSource;
 
 use crate::error::Result;
+use crate::http::HttpClient;
 use crate::value::DataValue;
 
 /// Trait for data sources that can fetch metrics
@@ -36,12 +61,13 @@ pub trait DataSource: Send + Sync {
     /// Fetch a metric value for the given query
     ///
     /// # Arguments
+    /// * `http` - Client carrying the fetcher's User-Agent, proxy and TLS settings
     /// * `query` - Source-specific query (e.g., "owner/repo" for GitHub)
     /// * `metric` - Metric to fetch (e.g., "stars", "version")
     ///
     /// # Returns
     /// The fetched value or an error
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue>;
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue>;
 
     /// List of available metrics for this source
     fn available_metrics(&self) -> &'static [&'static str];
@@ -91,6 +117,18 @@ impl SourceRegistry {
                 Box::new(PackagistSource::new()),
                 Box::new(RubyGemsSource::new()),
                 Box::new(NuGetSource::new()),
+                Box::new(ScorecardSource::new()),
+                Box::new(OsvSource::new()),
+                Box::new(LibrariesIoSource::new()),
+                Box::new(DiscordSource::new()),
+                Box::new(MatrixSource::new()),
+                Box::new(RedditSource::new()),
+                Box::new(DocsRsSource::new()),
+                Box::new(ReadTheDocsSource::new()),
+                Box::new(SponsorsSource::new()),
+                Box::new(OpenCollectiveSource::new()),
+                Box::new(PatreonSource::new()),
+                Box::new(IconSource::new()),
             ],
         }
     }
@@ -137,6 +175,18 @@ mod tests {
     #[case("packagist")]
     #[case("rubygems")]
     #[case("nuget")]
+    #[case("scorecard")]
+    #[case("osv")]
+    #[case("librariesio")]
+    #[case("discord")]
+    #[case("matrix")]
+    #[case("reddit")]
+    #[case("docsrs")]
+    #[case("readthedocs")]
+    #[case("sponsors")]
+    #[case("opencollective")]
+    #[case("patreon")]
+    #[case("icon")]
     fn test_registry_has_source(#[case] source_id: &str) {
         let registry = SourceRegistry::new();
         assert!(
@@ -160,5 +210,16 @@ mod tests {
         assert!(sources.contains(&"packagist"));
         assert!(sources.contains(&"rubygems"));
         assert!(sources.contains(&"nuget"));
+        assert!(sources.contains(&"scorecard"));
+        assert!(sources.contains(&"osv"));
+        assert!(sources.contains(&"librariesio"));
+        assert!(sources.contains(&"discord"));
+        assert!(sources.contains(&"matrix"));
+        assert!(sources.contains(&"reddit"));
+        assert!(sources.contains(&"docsrs"));
+        assert!(sources.contains(&"readthedocs"));
+        assert!(sources.contains(&"sponsors"));
+        assert!(sources.contains(&"opencollective"));
+        assert!(sources.contains(&"patreon"));
     }
 }
@@ -1,6 +1,7 @@
 //! npm registry data source
 
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::DataSource;
 use crate::value::DataValue;
 use serde::Deserialize;
@@ -45,15 +46,12 @@ impl NpmSource {
     }
 
     /// Fetch package data from npm registry
-    fn fetch_package(&self, name: &str) -> Result<PackageResponse> {
+    fn fetch_package(&self, http: &HttpClient, name: &str) -> Result<PackageResponse> {
         // Handle scoped packages (@scope/name)
         let encoded_name = name.replace('/', "%2F");
         let url = format!("{}/{}", self.api_base, encoded_name);
 
-        let response = ureq::get(&url)
-            .set("Accept", "application/json")
-            .set("User-Agent", "mdfx-fetch/1.0")
-            .call();
+        let response = http.get(&url).set("Accept", "application/json").call();
 
         match response {
             Ok(resp) => {
@@ -84,8 +82,8 @@ impl DataSource for NpmSource {
         "npm"
     }
 
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue> {
-        let data = self.fetch_package(query)?;
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let data = self.fetch_package(http, query)?;
 
         match metric {
             "version" => Ok(DataValue::String(data.dist_tags.latest)),
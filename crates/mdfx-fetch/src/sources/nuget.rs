@@ -1,6 +1,7 @@
 //! NuGet (.NET) data source
 
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::DataSource;
 use crate::value::DataValue;
 use serde::Deserialize;
@@ -82,17 +83,14 @@ impl NuGetSource {
     }
 
     /// Fetch package data from NuGet registration API
-    fn fetch_registration(&self, name: &str) -> Result<RegistrationIndex> {
+    fn fetch_registration(&self, http: &HttpClient, name: &str) -> Result<RegistrationIndex> {
         let url = format!(
             "{}/{}/index.json",
             self.registration_base,
             name.to_lowercase()
         );
 
-        let response = ureq::get(&url)
-            .set("Accept", "application/json")
-            .set("User-Agent", "mdfx-fetch/1.0")
-            .call();
+        let response = http.get(&url).set("Accept", "application/json").call();
 
         match response {
             Ok(resp) => {
@@ -114,13 +112,10 @@ impl NuGetSource {
     }
 
     /// Fetch package data from NuGet search API (for download counts)
-    fn fetch_search(&self, name: &str) -> Result<SearchPackage> {
+    fn fetch_search(&self, http: &HttpClient, name: &str) -> Result<SearchPackage> {
         let url = format!("{}?q=packageid:{}&take=1", self.search_base, name);
 
-        let response = ureq::get(&url)
-            .set("Accept", "application/json")
-            .set("User-Agent", "mdfx-fetch/1.0")
-            .call();
+        let response = http.get(&url).set("Accept", "application/json").call();
 
         match response {
             Ok(resp) => {
@@ -187,37 +182,37 @@ impl DataSource for NuGetSource {
         "NuGet"
     }
 
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue> {
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
         match metric {
             "version" => {
-                let reg = self.fetch_registration(query)?;
+                let reg = self.fetch_registration(http, query)?;
                 Ok(DataValue::String(Self::get_latest_version(&reg)))
             }
             "downloads" => {
-                let search = self.fetch_search(query)?;
+                let search = self.fetch_search(http, query)?;
                 let count = search.total_downloads.unwrap_or(0);
                 Ok(DataValue::String(Self::format_downloads(count)))
             }
             "downloads_raw" => {
-                let search = self.fetch_search(query)?;
+                let search = self.fetch_search(http, query)?;
                 Ok(search.total_downloads.unwrap_or(0).into())
             }
             "description" => {
-                let reg = self.fetch_registration(query)?;
+                let reg = self.fetch_registration(http, query)?;
                 let desc = Self::get_latest_entry(&reg)
                     .and_then(|e| e.description.clone())
                     .unwrap_or_else(|| "No description".to_string());
                 Ok(DataValue::String(desc))
             }
             "authors" => {
-                let reg = self.fetch_registration(query)?;
+                let reg = self.fetch_registration(http, query)?;
                 let authors = Self::get_latest_entry(&reg)
                     .and_then(|e| e.authors.clone())
                     .unwrap_or_else(|| "Unknown".to_string());
                 Ok(DataValue::String(authors))
             }
             "license" => {
-                let reg = self.fetch_registration(query)?;
+                let reg = self.fetch_registration(http, query)?;
                 let license = Self::get_latest_entry(&reg)
                     .and_then(|e| e.license_expression.clone())
                     .unwrap_or_else(|| "Unknown".to_string());
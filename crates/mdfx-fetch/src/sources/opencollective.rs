@@ -0,0 +1,218 @@
+//! Open Collective data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use serde::Deserialize;
+
+/// Open Collective collective API response (partial)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectiveResponse {
+    #[serde(default)]
+    backers_count: u64,
+    /// Balance in cents of `currency`
+    #[serde(default)]
+    balance: i64,
+    #[serde(default)]
+    currency: Option<String>,
+}
+
+/// Open Collective data source
+pub struct OpenCollectiveSource {
+    api_base: String,
+}
+
+impl Default for OpenCollectiveSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenCollectiveSource {
+    /// Create a new Open Collective source
+    pub fn new() -> Self {
+        OpenCollectiveSource {
+            api_base: "https://opencollective.com".to_string(),
+        }
+    }
+
+    /// Fetch collective data from Open Collective
+    fn fetch_collective(&self, http: &HttpClient, slug: &str) -> Result<CollectiveResponse> {
+        let url = format!("{}/{}.json", self.api_base, slug);
+
+        let response = http.get(&url).set("Accept", "application/json").call();
+
+        match response {
+            Ok(resp) => {
+                let body: CollectiveResponse = resp.into_json().map_err(|e| {
+                    FetchError::ParseError(format!(
+                        "Failed to parse Open Collective response: {}",
+                        e
+                    ))
+                })?;
+                Ok(body)
+            }
+            Err(ureq::Error::Status(404, _)) => Err(FetchError::NotFound(slug.to_string())),
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// Parse query into a collective slug
+    fn parse_query(query: &str) -> Result<&str> {
+        if query.is_empty()
+            || !query
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        {
+            return Err(FetchError::ParseError(format!(
+                "Invalid Open Collective query '{}'. Expected format: collective slug",
+                query
+            )));
+        }
+        Ok(query)
+    }
+
+    /// Balance in whole units with its currency, e.g. `$12.3k`
+    fn format_balance(cents: i64, currency: Option<&str>) -> String {
+        let amount = DataValue::Number(cents.max(0) as u64 / 100).format();
+        match currency.unwrap_or("USD") {
+            "USD" => format!("${}", amount),
+            "EUR" => format!("€{}", amount),
+            "GBP" => format!("£{}", amount),
+            other => format!("{} {}", amount, other),
+        }
+    }
+
+    /// Value of a metric for a collective
+    fn metric_value(&self, data: &CollectiveResponse, metric: &str) -> Result<DataValue> {
+        match metric {
+            "backers" => Ok(DataValue::Number(data.backers_count)),
+            "balance" => Ok(DataValue::String(Self::format_balance(
+                data.balance,
+                data.currency.as_deref(),
+            ))),
+            _ => Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: self
+                    .available_metrics()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl DataSource for OpenCollectiveSource {
+    fn id(&self) -> &'static str {
+        "opencollective"
+    }
+
+    fn name(&self) -> &'static str {
+        "Open Collective"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let slug = Self::parse_query(query)?;
+        let data = self.fetch_collective(http, slug)?;
+        self.metric_value(&data, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &["backers", "balance"]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        21600 // 6 hours - contributions arrive through the day
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "backers" => "Backers",
+            "balance" => "Balance",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, _value: &DataValue) -> Option<&str> {
+        match metric {
+            "backers" | "balance" => Some("7FADF2"), // Open Collective blue
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const RESPONSE: &str = r#"{
+        "slug": "webpack",
+        "currency": "USD",
+        "image": "https://opencollective-production.s3.us-west-1.amazonaws.com/webpack.png",
+        "balance": 1234567,
+        "yearlyIncome": 9876543,
+        "backersCount": 2150,
+        "contributorsCount": 2300
+    }"#;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("webpack", Some("webpack"))]
+    #[case("babel-collective", Some("babel-collective"))]
+    #[case("", None)]
+    #[case("webpack/events", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(OpenCollectiveSource::parse_query(input).ok(), expected);
+    }
+
+    // ========================================================================
+    // Metric Values (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("backers", DataValue::Number(2150))]
+    #[case("balance", DataValue::String("$12.3k".to_string()))]
+    fn test_metric_value(#[case] metric: &str, #[case] expected: DataValue) {
+        let data: CollectiveResponse = serde_json::from_str(RESPONSE).unwrap();
+        let source = OpenCollectiveSource::new();
+        assert_eq!(source.metric_value(&data, metric).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(99900, Some("EUR"), "€999")]
+    #[case(250000000, Some("GBP"), "£2.5M")]
+    #[case(500000, Some("CAD"), "5.0k CAD")]
+    #[case(-1200, None, "$0")]
+    fn test_format_balance(
+        #[case] cents: i64,
+        #[case] currency: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            OpenCollectiveSource::format_balance(cents, currency),
+            expected
+        );
+    }
+}
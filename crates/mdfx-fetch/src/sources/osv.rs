@@ -0,0 +1,265 @@
+//! OSV (Open Source Vulnerabilities) API data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use serde::Deserialize;
+
+/// OSV query API response (partial)
+#[derive(Debug, Deserialize)]
+struct QueryResponse {
+    /// Absent when the package has no known vulnerabilities
+    #[serde(default)]
+    vulns: Vec<Vulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Vulnerability {
+    id: String,
+}
+
+/// OSV data source
+pub struct OsvSource {
+    api_base: String,
+}
+
+impl Default for OsvSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OsvSource {
+    /// Create a new OSV source
+    pub fn new() -> Self {
+        OsvSource {
+            api_base: "https://api.osv.dev/v1".to_string(),
+        }
+    }
+
+    /// Query the advisories affecting a package (and version, if given)
+    fn query(
+        &self,
+        http: &HttpClient,
+        ecosystem: &str,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<QueryResponse> {
+        let url = format!("{}/query", self.api_base);
+        let mut body = serde_json::json!({
+            "package": { "name": name, "ecosystem": ecosystem }
+        });
+        if let Some(version) = version {
+            body["version"] = version.into();
+        }
+
+        let response = http
+            .post(&url)
+            .set("Accept", "application/json")
+            .send_json(body);
+
+        match response {
+            Ok(resp) => {
+                let body: QueryResponse = resp.into_json().map_err(|e| {
+                    FetchError::ParseError(format!("Failed to parse OSV response: {}", e))
+                })?;
+                Ok(body)
+            }
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// Parse query into (ecosystem, name, version)
+    ///
+    /// Formats: "crates/time@0.1.45", "npm/@babel/traverse@7.23.0", "pypi/django".
+    /// Ecosystems use the live source names where one exists; anything else
+    /// is passed to OSV as-is (e.g. "Go", "Maven", "Hex").
+    fn parse_query(query: &str) -> Result<(&str, &str, Option<&str>)> {
+        let invalid = || {
+            FetchError::ParseError(format!(
+                "Invalid OSV query '{}'. Expected format: ecosystem/package or ecosystem/package@version",
+                query
+            ))
+        };
+        let (ecosystem, package) = query.split_once('/').ok_or_else(invalid)?;
+        // A leading '@' belongs to an npm scope, not a version
+        let (name, version) = match package.rfind('@').filter(|i| *i > 0) {
+            Some(i) => (&package[..i], Some(&package[i + 1..])),
+            None => (package, None),
+        };
+        if ecosystem.is_empty() || name.is_empty() || version == Some("") {
+            return Err(invalid());
+        }
+        Ok((Self::ecosystem(ecosystem), name, version))
+    }
+
+    /// OSV ecosystem name for a live source name
+    fn ecosystem(name: &str) -> &str {
+        match name {
+            "crates" => "crates.io",
+            "pypi" => "PyPI",
+            "rubygems" => "RubyGems",
+            "nuget" => "NuGet",
+            "packagist" => "Packagist",
+            "go" => "Go",
+            "maven" => "Maven",
+            other => other,
+        }
+    }
+
+    /// Value of a metric for a query response
+    fn metric_value(&self, data: &QueryResponse, metric: &str) -> Result<DataValue> {
+        match metric {
+            "vulnerabilities" => Ok(DataValue::Number(data.vulns.len() as u64)),
+            "vulnerable" => Ok(DataValue::Bool(!data.vulns.is_empty())),
+            "ids" => Ok(DataValue::String(if data.vulns.is_empty() {
+                "none".to_string()
+            } else {
+                data.vulns
+                    .iter()
+                    .map(|v| v.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })),
+            _ => Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: self
+                    .available_metrics()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl DataSource for OsvSource {
+    fn id(&self) -> &'static str {
+        "osv"
+    }
+
+    fn name(&self) -> &'static str {
+        "OSV"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let (ecosystem, name, version) = Self::parse_query(query)?;
+        let data = self.query(http, ecosystem, name, version)?;
+        self.metric_value(&data, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &["vulnerabilities", "vulnerable", "ids"]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        21600 // 6 hours - new advisories should show up the same day
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "vulnerabilities" => "Vulnerabilities",
+            "vulnerable" => "Vulnerable",
+            "ids" => "Advisories",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, value: &DataValue) -> Option<&str> {
+        let clean = match metric {
+            "vulnerabilities" => value.as_number() == Some(0),
+            "vulnerable" => value.as_bool() == Some(false),
+            "ids" => value.as_string() == "none",
+            _ => return None,
+        };
+        Some(if clean { "22C55E" } else { "EF4444" }) // Green - none, red - any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("crates/time@0.1.45", Some(("crates.io", "time", Some("0.1.45"))))]
+    #[case("pypi/django", Some(("PyPI", "django", None)))]
+    #[case("npm/@babel/traverse@7.23.0", Some(("npm", "@babel/traverse", Some("7.23.0"))))]
+    #[case("npm/@babel/traverse", Some(("npm", "@babel/traverse", None)))]
+    #[case("Go/golang.org/x/net@0.17.0", Some(("Go", "golang.org/x/net", Some("0.17.0"))))]
+    #[case("serde", None)]
+    #[case("crates/", None)]
+    #[case("crates/serde@", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<(&str, &str, Option<&str>)>) {
+        assert_eq!(OsvSource::parse_query(input).ok(), expected);
+    }
+
+    // ========================================================================
+    // Metric Values (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case(
+        r#"{"vulns": [{"id": "RUSTSEC-2020-0071"}, {"id": "GHSA-wcg3-cvx6-7396"}]}"#,
+        "vulnerabilities",
+        DataValue::Number(2)
+    )]
+    #[case(r#"{"vulns": [{"id": "RUSTSEC-2020-0071"}, {"id": "GHSA-wcg3-cvx6-7396"}]}"#, "ids", DataValue::String("RUSTSEC-2020-0071, GHSA-wcg3-cvx6-7396".to_string()))]
+    #[case(
+        r#"{"vulns": [{"id": "PYSEC-2023-100"}]}"#,
+        "vulnerable",
+        DataValue::Bool(true)
+    )]
+    #[case("{}", "vulnerabilities", DataValue::Number(0))]
+    #[case("{}", "vulnerable", DataValue::Bool(false))]
+    #[case("{}", "ids", DataValue::String("none".to_string()))]
+    fn test_metric_value(
+        #[case] response: &str,
+        #[case] metric: &str,
+        #[case] expected: DataValue,
+    ) {
+        let data: QueryResponse = serde_json::from_str(response).unwrap();
+        let source = OsvSource::new();
+        assert_eq!(source.metric_value(&data, metric).unwrap(), expected);
+    }
+
+    // ========================================================================
+    // Colors (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("vulnerabilities", DataValue::Number(0), Some("22C55E"))]
+    #[case("vulnerabilities", DataValue::Number(3), Some("EF4444"))]
+    #[case("vulnerable", DataValue::Bool(false), Some("22C55E"))]
+    #[case("vulnerable", DataValue::Bool(true), Some("EF4444"))]
+    #[case("ids", DataValue::String("none".to_string()), Some("22C55E"))]
+    #[case("ids", DataValue::String("RUSTSEC-2020-0071".to_string()), Some("EF4444"))]
+    fn test_metric_colors(
+        #[case] metric: &str,
+        #[case] value: DataValue,
+        #[case] expected: Option<&str>,
+    ) {
+        let source = OsvSource::new();
+        assert_eq!(source.metric_color(metric, &value), expected);
+    }
+}
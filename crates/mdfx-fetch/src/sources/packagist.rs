@@ -1,6 +1,7 @@
 //! Packagist (PHP/Composer) data source
 
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::DataSource;
 use crate::value::DataValue;
 use serde::Deserialize;
@@ -56,13 +57,10 @@ impl PackagistSource {
     }
 
     /// Fetch package data from Packagist
-    fn fetch_package(&self, name: &str) -> Result<PackageResponse> {
+    fn fetch_package(&self, http: &HttpClient, name: &str) -> Result<PackageResponse> {
         let url = format!("{}/{}.json", self.api_base, name);
 
-        let response = ureq::get(&url)
-            .set("Accept", "application/json")
-            .set("User-Agent", "mdfx-fetch/1.0")
-            .call();
+        let response = http.get(&url).set("Accept", "application/json").call();
 
         match response {
             Ok(resp) => {
@@ -148,8 +146,8 @@ impl DataSource for PackagistSource {
         "Packagist"
     }
 
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue> {
-        let data = self.fetch_package(query)?;
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let data = self.fetch_package(http, query)?;
 
         match metric {
             "version" => Ok(DataValue::String(Self::get_latest_version(&data))),
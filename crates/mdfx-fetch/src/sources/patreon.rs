@@ -0,0 +1,211 @@
+//! Patreon data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use serde::Deserialize;
+
+/// Patreon campaign API response (partial)
+#[derive(Debug, Deserialize)]
+struct CampaignResponse {
+    data: Campaign,
+}
+
+#[derive(Debug, Deserialize)]
+struct Campaign {
+    attributes: CampaignAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+struct CampaignAttributes {
+    /// Absent when the creator hides their patron count
+    #[serde(default)]
+    patron_count: Option<u64>,
+}
+
+/// Patreon data source
+///
+/// Reads the public campaign endpoint, which is keyed by the numeric
+/// campaign id rather than the creator's page name.
+pub struct PatreonSource {
+    api_base: String,
+}
+
+impl Default for PatreonSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PatreonSource {
+    /// Create a new Patreon source
+    pub fn new() -> Self {
+        PatreonSource {
+            api_base: "https://www.patreon.com/api".to_string(),
+        }
+    }
+
+    /// Fetch campaign data from Patreon
+    fn fetch_campaign(&self, http: &HttpClient, id: &str) -> Result<CampaignResponse> {
+        let url = format!("{}/campaigns/{}", self.api_base, id);
+
+        let response = http.get(&url).set("Accept", "application/json").call();
+
+        match response {
+            Ok(resp) => {
+                let body: CampaignResponse = resp.into_json().map_err(|e| {
+                    FetchError::ParseError(format!("Failed to parse Patreon response: {}", e))
+                })?;
+                Ok(body)
+            }
+            Err(ureq::Error::Status(404, _)) => {
+                Err(FetchError::NotFound(format!("campaign {}", id)))
+            }
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// Parse query into a campaign id
+    fn parse_query(query: &str) -> Result<&str> {
+        if query.is_empty() || !query.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(FetchError::ParseError(format!(
+                "Invalid Patreon query '{}'. Expected format: numeric campaign id",
+                query
+            )));
+        }
+        Ok(query)
+    }
+
+    /// Value of a metric for a campaign
+    fn metric_value(&self, data: &CampaignResponse, metric: &str) -> Result<DataValue> {
+        match metric {
+            "patrons" => data
+                .data
+                .attributes
+                .patron_count
+                .map(DataValue::Number)
+                .ok_or_else(|| FetchError::ApiError {
+                    status: 200,
+                    message: "campaign hides its patron count".to_string(),
+                }),
+            _ => Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: self
+                    .available_metrics()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl DataSource for PatreonSource {
+    fn id(&self) -> &'static str {
+        "patreon"
+    }
+
+    fn name(&self) -> &'static str {
+        "Patreon"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let id = Self::parse_query(query)?;
+        let data = self.fetch_campaign(http, id)?;
+        self.metric_value(&data, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &["patrons"]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        21600 // 6 hours - pledges change through the month
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "patrons" => "Patrons",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, _value: &DataValue) -> Option<&str> {
+        match metric {
+            "patrons" => Some("F96854"), // Patreon coral
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const RESPONSE: &str = r#"{
+        "data": {
+            "attributes": {
+                "created_at": "2017-03-01T18:22:05.000+00:00",
+                "creation_name": "open source software",
+                "patron_count": 412,
+                "pay_per_name": "month"
+            },
+            "id": "1234567",
+            "type": "campaign"
+        }
+    }"#;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("1234567", Some("1234567"))]
+    #[case("", None)]
+    #[case("someone", None)]
+    #[case("123/posts", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(PatreonSource::parse_query(input).ok(), expected);
+    }
+
+    #[test]
+    fn test_patron_count() {
+        let data: CampaignResponse = serde_json::from_str(RESPONSE).unwrap();
+        let source = PatreonSource::new();
+        assert_eq!(
+            source.metric_value(&data, "patrons").unwrap(),
+            DataValue::Number(412)
+        );
+    }
+
+    #[test]
+    fn test_hidden_patron_count() {
+        let data: CampaignResponse =
+            serde_json::from_str(r#"{ "data": { "attributes": { "pay_per_name": "month" } } }"#)
+                .unwrap();
+        let source = PatreonSource::new();
+        let err = source.metric_value(&data, "patrons").unwrap_err();
+        assert!(
+            err.to_string().contains("hides its patron count"),
+            "{}",
+            err
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! PyPI data source
 
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::DataSource;
 use crate::value::DataValue;
 use serde::Deserialize;
@@ -44,13 +45,10 @@ impl PyPISource {
     }
 
     /// Fetch package data from PyPI
-    fn fetch_package(&self, name: &str) -> Result<PackageInfo> {
+    fn fetch_package(&self, http: &HttpClient, name: &str) -> Result<PackageInfo> {
         let url = format!("{}/{}/json", self.api_base, name);
 
-        let response = ureq::get(&url)
-            .set("Accept", "application/json")
-            .set("User-Agent", "mdfx-fetch/1.0")
-            .call();
+        let response = http.get(&url).set("Accept", "application/json").call();
 
         match response {
             Ok(resp) => {
@@ -81,8 +79,8 @@ impl DataSource for PyPISource {
         "PyPI"
     }
 
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue> {
-        let data = self.fetch_package(query)?;
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let data = self.fetch_package(http, query)?;
 
         match metric {
             "version" => Ok(DataValue::String(data.version)),
@@ -0,0 +1,192 @@
+//! Read the Docs build status data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::docsrs::build_status_color;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+
+/// Read the Docs data source
+///
+/// Read the Docs has no public build API without a token, so the status is
+/// read from the project's own badge, as shields.io does.
+pub struct ReadTheDocsSource {
+    api_base: String,
+}
+
+impl Default for ReadTheDocsSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReadTheDocsSource {
+    /// Create a new Read the Docs source
+    pub fn new() -> Self {
+        ReadTheDocsSource {
+            api_base: "https://readthedocs.org".to_string(),
+        }
+    }
+
+    /// Fetch the status badge SVG of a project version
+    fn fetch_badge(&self, http: &HttpClient, project: &str, version: &str) -> Result<String> {
+        let url = format!("{}/projects/{}/badge/", self.api_base, project);
+
+        let response = http.get(&url).query("version", version).call();
+
+        match response {
+            Ok(resp) => resp.into_string().map_err(|e| {
+                FetchError::ParseError(format!("Failed to read Read the Docs badge: {}", e))
+            }),
+            Err(ureq::Error::Status(404, _)) => {
+                Err(FetchError::NotFound(format!("{}@{}", project, version)))
+            }
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// Parse query into (project, version), defaulting to "latest"
+    fn parse_query(query: &str) -> Result<(&str, &str)> {
+        let (project, version) = query.split_once('@').unwrap_or((query, "latest"));
+        if project.is_empty() || version.is_empty() || project.contains('/') {
+            return Err(FetchError::ParseError(format!(
+                "Invalid Read the Docs query '{}'. Expected format: project or project@version",
+                query
+            )));
+        }
+        Ok((project, version))
+    }
+
+    /// Build status shown on a badge: passing, failing or unknown
+    fn badge_status(svg: &str) -> &'static str {
+        ["passing", "failing"]
+            .into_iter()
+            .find(|status| svg.contains(&format!(">{}<", status)))
+            .unwrap_or("unknown")
+    }
+
+    /// Value of a metric for a badge
+    fn metric_value(&self, svg: &str, metric: &str) -> Result<DataValue> {
+        match metric {
+            "status" => Ok(DataValue::String(Self::badge_status(svg).to_string())),
+            _ => Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: self
+                    .available_metrics()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl DataSource for ReadTheDocsSource {
+    fn id(&self) -> &'static str {
+        "readthedocs"
+    }
+
+    fn name(&self) -> &'static str {
+        "Read the Docs"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let (project, version) = Self::parse_query(query)?;
+        let svg = self.fetch_badge(http, project, version)?;
+        self.metric_value(&svg, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &["status"]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        3600 // 1 hour - builds run on every push
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "status" => "docs",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, value: &DataValue) -> Option<&str> {
+        match metric {
+            "status" => Some(build_status_color(value)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const PASSING: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="86" height="20"><g fill="#fff" text-anchor="middle" font-size="11"><text x="19.5" y="15" fill="#010101" fill-opacity=".3">docs</text><text x="19.5" y="14">docs</text><text x="61.5" y="15" fill="#010101" fill-opacity=".3">passing</text><text x="61.5" y="14">passing</text></g></svg>"##;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("requests", Some(("requests", "latest")))]
+    #[case("requests@stable", Some(("requests", "stable")))]
+    #[case("", None)]
+    #[case("requests@", None)]
+    #[case("psf/requests", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<(&str, &str)>) {
+        assert_eq!(ReadTheDocsSource::parse_query(input).ok(), expected);
+    }
+
+    // ========================================================================
+    // Badge Status (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case(PASSING, "passing")]
+    #[case("<svg><text>docs</text><text>failing</text></svg>", "failing")]
+    #[case("<svg><text>docs</text><text>unknown</text></svg>", "unknown")]
+    #[case("not a badge", "unknown")]
+    fn test_badge_status(#[case] svg: &str, #[case] expected: &str) {
+        assert_eq!(ReadTheDocsSource::badge_status(svg), expected);
+    }
+
+    #[test]
+    fn test_unknown_metric() {
+        let source = ReadTheDocsSource::new();
+        assert!(matches!(
+            source.metric_value(PASSING, "version"),
+            Err(FetchError::UnknownMetric { .. })
+        ));
+    }
+
+    // ========================================================================
+    // Status Colors (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case(DataValue::String("passing".to_string()), Some("22C55E"))]
+    #[case(DataValue::String("failing".to_string()), Some("EF4444"))]
+    #[case(DataValue::String("unknown".to_string()), Some("6B7280"))]
+    fn test_metric_colors(#[case] value: DataValue, #[case] expected: Option<&str>) {
+        let source = ReadTheDocsSource::new();
+        assert_eq!(source.metric_color("status", &value), expected);
+    }
+}
@@ -0,0 +1,213 @@
+//! Reddit API data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use serde::Deserialize;
+
+/// Subreddit about response
+#[derive(Debug, Deserialize)]
+struct AboutResponse {
+    data: Subreddit,
+}
+
+/// Subreddit details (partial)
+#[derive(Debug, Deserialize)]
+struct Subreddit {
+    #[serde(default)]
+    display_name_prefixed: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    subscribers: u64,
+    #[serde(default)]
+    active_user_count: Option<u64>,
+}
+
+/// Reddit data source
+pub struct RedditSource {
+    api_base: String,
+}
+
+impl Default for RedditSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RedditSource {
+    /// Create a new Reddit source
+    pub fn new() -> Self {
+        RedditSource {
+            api_base: "https://www.reddit.com".to_string(),
+        }
+    }
+
+    /// Fetch subreddit details from Reddit API
+    fn fetch_subreddit(&self, http: &HttpClient, name: &str) -> Result<Subreddit> {
+        let url = format!("{}/r/{}/about.json", self.api_base, name);
+
+        let response = http.get(&url).set("Accept", "application/json").call();
+
+        match response {
+            Ok(resp) => {
+                let body: AboutResponse = resp.into_json().map_err(|e| {
+                    FetchError::ParseError(format!("Failed to parse Reddit response: {}", e))
+                })?;
+                Ok(body.data)
+            }
+            // Private and banned subreddits answer 403
+            Err(ureq::Error::Status(404 | 403, _)) => {
+                Err(FetchError::NotFound(format!("r/{}", name)))
+            }
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// Parse query into a subreddit name ("rust" or "r/rust")
+    fn parse_query(query: &str) -> Result<&str> {
+        let name = query.strip_prefix("r/").unwrap_or(query);
+        if name.is_empty() || !name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') {
+            return Err(FetchError::ParseError(format!(
+                "Invalid Reddit query '{}'. Expected format: subreddit or r/subreddit",
+                query
+            )));
+        }
+        Ok(name)
+    }
+
+    /// Value of a metric for a subreddit
+    fn metric_value(&self, data: &Subreddit, metric: &str) -> Result<DataValue> {
+        match metric {
+            "subscribers" => Ok(DataValue::Number(data.subscribers)),
+            "active" => Ok(DataValue::Number(data.active_user_count.unwrap_or(0))),
+            "title" => Ok(DataValue::String(
+                data.title
+                    .clone()
+                    .filter(|t| !t.is_empty())
+                    .or_else(|| data.display_name_prefixed.clone())
+                    .unwrap_or_else(|| "unknown".to_string()),
+            )),
+            _ => Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: self
+                    .available_metrics()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl DataSource for RedditSource {
+    fn id(&self) -> &'static str {
+        "reddit"
+    }
+
+    fn name(&self) -> &'static str {
+        "Reddit"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let name = Self::parse_query(query)?;
+        let data = self.fetch_subreddit(http, name)?;
+        self.metric_value(&data, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &["subscribers", "active", "title"]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        3600 // 1 hour - unauthenticated requests are tightly rate limited
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "subscribers" => "Reddit",
+            "active" => "Online",
+            "title" => "Subreddit",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, _value: &DataValue) -> Option<&str> {
+        match metric {
+            "subscribers" | "active" => Some("FF4500"), // Reddit orange
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const RESPONSE: &str = r#"{
+        "kind": "t5",
+        "data": {
+            "display_name": "rust",
+            "display_name_prefixed": "r/rust",
+            "title": "The Rust Programming Language",
+            "subscribers": 337000,
+            "active_user_count": 812,
+            "over18": false
+        }
+    }"#;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("rust", Some("rust"))]
+    #[case("r/rust_gamedev", Some("rust_gamedev"))]
+    #[case("", None)]
+    #[case("r/", None)]
+    #[case("rust/new", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(RedditSource::parse_query(input).ok(), expected);
+    }
+
+    // ========================================================================
+    // Metric Values (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("subscribers", DataValue::Number(337000))]
+    #[case("active", DataValue::Number(812))]
+    #[case("title", DataValue::String("The Rust Programming Language".to_string()))]
+    fn test_metric_value(#[case] metric: &str, #[case] expected: DataValue) {
+        let data: AboutResponse = serde_json::from_str(RESPONSE).unwrap();
+        let source = RedditSource::new();
+        assert_eq!(source.metric_value(&data.data, metric).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_unknown_metric() {
+        let data: AboutResponse = serde_json::from_str(RESPONSE).unwrap();
+        let source = RedditSource::new();
+        assert!(matches!(
+            source.metric_value(&data.data, "karma"),
+            Err(FetchError::UnknownMetric { .. })
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 //! RubyGems data source
 
 use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
 use crate::sources::DataSource;
 use crate::value::DataValue;
 use serde::Deserialize;
@@ -42,13 +43,10 @@ impl RubyGemsSource {
     }
 
     /// Fetch gem data from RubyGems
-    fn fetch_gem(&self, name: &str) -> Result<GemResponse> {
+    fn fetch_gem(&self, http: &HttpClient, name: &str) -> Result<GemResponse> {
         let url = format!("{}/{}.json", self.api_base, name);
 
-        let response = ureq::get(&url)
-            .set("Accept", "application/json")
-            .set("User-Agent", "mdfx-fetch/1.0")
-            .call();
+        let response = http.get(&url).set("Accept", "application/json").call();
 
         match response {
             Ok(resp) => {
@@ -92,8 +90,8 @@ impl DataSource for RubyGemsSource {
         "RubyGems"
     }
 
-    fn fetch(&self, query: &str, metric: &str) -> Result<DataValue> {
-        let data = self.fetch_gem(query)?;
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let data = self.fetch_gem(http, query)?;
 
         match metric {
             "version" => Ok(DataValue::String(data.version)),
@@ -0,0 +1,337 @@
+//! OpenSSF Scorecard API data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use serde::Deserialize;
+
+/// Scorecard project API response (partial)
+#[derive(Debug, Deserialize)]
+struct ScorecardResponse {
+    #[serde(default)]
+    date: Option<String>,
+    score: f64,
+    #[serde(default)]
+    checks: Vec<Check>,
+}
+
+/// Individual check result
+#[derive(Debug, Deserialize)]
+struct Check {
+    name: String,
+    /// 0-10, or -1 when the check was inconclusive
+    score: i64,
+}
+
+/// Metrics that report a single check, named after it in snake case
+const CHECK_METRICS: &[&str] = &[
+    "binary_artifacts",
+    "branch_protection",
+    "ci_tests",
+    "cii_best_practices",
+    "code_review",
+    "contributors",
+    "dangerous_workflow",
+    "dependency_update_tool",
+    "fuzzing",
+    "license",
+    "maintained",
+    "packaging",
+    "pinned_dependencies",
+    "sast",
+    "security_policy",
+    "signed_releases",
+    "token_permissions",
+    "vulnerabilities",
+];
+
+/// OpenSSF Scorecard data source
+pub struct ScorecardSource {
+    api_base: String,
+}
+
+impl Default for ScorecardSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScorecardSource {
+    /// Create a new Scorecard source
+    pub fn new() -> Self {
+        ScorecardSource {
+            api_base: "https://api.securityscorecards.dev".to_string(),
+        }
+    }
+
+    /// Fetch the latest published scorecard for a project
+    fn fetch_project(
+        &self,
+        http: &HttpClient,
+        host: &str,
+        owner: &str,
+        repo: &str,
+    ) -> Result<ScorecardResponse> {
+        let url = format!("{}/projects/{}/{}/{}", self.api_base, host, owner, repo);
+
+        let response = http.get(&url).set("Accept", "application/json").call();
+
+        match response {
+            Ok(resp) => {
+                let body: ScorecardResponse = resp.into_json().map_err(|e| {
+                    FetchError::ParseError(format!("Failed to parse Scorecard response: {}", e))
+                })?;
+                Ok(body)
+            }
+            // Projects not scanned by the weekly Scorecard job have no result
+            Err(ureq::Error::Status(404, _)) => Err(FetchError::NotFound(format!(
+                "{}/{}/{} (not in the Scorecard dataset)",
+                host, owner, repo
+            ))),
+            Err(ureq::Error::Status(429, resp)) => {
+                let retry_after = resp
+                    .header("Retry-After")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// Parse query into host/owner/repo (assumes github.com by default)
+    fn parse_query(query: &str) -> Result<(&str, &str, &str)> {
+        let parts: Vec<&str> = query.split('/').collect();
+        match parts.as_slice() {
+            [owner, repo] if !owner.is_empty() && !repo.is_empty() => {
+                Ok(("github.com", owner, repo))
+            }
+            [host, owner, repo] if !owner.is_empty() && !repo.is_empty() => Ok((host, owner, repo)),
+            _ => Err(FetchError::ParseError(format!(
+                "Invalid Scorecard query '{}'. Expected format: owner/repo or host/owner/repo",
+                query
+            ))),
+        }
+    }
+
+    /// Value of a metric in a scorecard
+    fn metric_value(&self, data: &ScorecardResponse, metric: &str) -> Result<DataValue> {
+        match metric {
+            "score" => Ok(DataValue::Float(data.score)),
+            "date" => Ok(DataValue::String(
+                data.date
+                    .as_deref()
+                    .map(|d| d.chars().take(10).collect())
+                    .unwrap_or_else(|| "unknown".to_string()),
+            )),
+            check if CHECK_METRICS.contains(&check) => {
+                let name = check.replace('_', "-");
+                let score = data
+                    .checks
+                    .iter()
+                    .find(|c| c.name.eq_ignore_ascii_case(&name))
+                    .map(|c| c.score);
+                Ok(match score {
+                    Some(score) if score >= 0 => DataValue::Number(score as u64),
+                    Some(_) => DataValue::String("inconclusive".to_string()),
+                    None => DataValue::String("n/a".to_string()),
+                })
+            }
+            _ => Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: self
+                    .available_metrics()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl DataSource for ScorecardSource {
+    fn id(&self) -> &'static str {
+        "scorecard"
+    }
+
+    fn name(&self) -> &'static str {
+        "OpenSSF Scorecard"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let (host, owner, repo) = Self::parse_query(query)?;
+        let data = self.fetch_project(http, host, owner, repo)?;
+        self.metric_value(&data, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &[
+            "score",
+            "date",
+            "binary_artifacts",
+            "branch_protection",
+            "ci_tests",
+            "cii_best_practices",
+            "code_review",
+            "contributors",
+            "dangerous_workflow",
+            "dependency_update_tool",
+            "fuzzing",
+            "license",
+            "maintained",
+            "packaging",
+            "pinned_dependencies",
+            "sast",
+            "security_policy",
+            "signed_releases",
+            "token_permissions",
+            "vulnerabilities",
+        ]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        86400 // 24 hours - scorecards are recomputed weekly
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "score" => "OpenSSF Scorecard",
+            "date" => "Scorecard date",
+            "binary_artifacts" => "Binary Artifacts",
+            "branch_protection" => "Branch Protection",
+            "ci_tests" => "CI Tests",
+            "cii_best_practices" => "Best Practices",
+            "code_review" => "Code Review",
+            "contributors" => "Contributors",
+            "dangerous_workflow" => "Dangerous Workflow",
+            "dependency_update_tool" => "Dependency Updates",
+            "fuzzing" => "Fuzzing",
+            "license" => "License",
+            "maintained" => "Maintained",
+            "packaging" => "Packaging",
+            "pinned_dependencies" => "Pinned Dependencies",
+            "sast" => "SAST",
+            "security_policy" => "Security Policy",
+            "signed_releases" => "Signed Releases",
+            "token_permissions" => "Token Permissions",
+            "vulnerabilities" => "Vulnerabilities",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, value: &DataValue) -> Option<&str> {
+        if metric == "date" {
+            return None;
+        }
+        // Scores and check results are 0-10; inconclusive checks are gray
+        match value.as_float() {
+            Some(s) if s >= 8.0 => Some("22C55E"), // Green - strong
+            Some(s) if s >= 6.0 => Some("84CC16"), // Lime - good
+            Some(s) if s >= 4.0 => Some("EAB308"), // Yellow - fair
+            Some(s) if s >= 2.0 => Some("F97316"), // Orange - weak
+            Some(_) => Some("EF4444"),             // Red - poor
+            None => Some("6B7280"),                // Gray - no result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const RESPONSE: &str = r#"{
+        "date": "2024-06-03T12:00:00Z",
+        "repo": {"name": "github.com/ossf/scorecard", "commit": "abc123"},
+        "scorecard": {"version": "v5.0.0", "commit": "def456"},
+        "score": 7.6,
+        "checks": [
+            {"name": "Code-Review", "score": 9, "reason": "all changesets reviewed"},
+            {"name": "CII-Best-Practices", "score": 5, "reason": "passing badge"},
+            {"name": "Fuzzing", "score": 10, "reason": "project is fuzzed"},
+            {"name": "Packaging", "score": -1, "reason": "packaging workflow not detected"}
+        ]
+    }"#;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("ossf/scorecard", Some(("github.com", "ossf", "scorecard")))]
+    #[case("gitlab.com/owner/repo", Some(("gitlab.com", "owner", "repo")))]
+    #[case("ossf", None)]
+    #[case("ossf/", None)]
+    #[case("a/b/c/d", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<(&str, &str, &str)>) {
+        assert_eq!(ScorecardSource::parse_query(input).ok(), expected);
+    }
+
+    // ========================================================================
+    // Metric Values (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("score", DataValue::Float(7.6))]
+    #[case("date", DataValue::String("2024-06-03".to_string()))]
+    #[case("code_review", DataValue::Number(9))]
+    #[case("cii_best_practices", DataValue::Number(5))]
+    #[case("packaging", DataValue::String("inconclusive".to_string()))]
+    #[case("sast", DataValue::String("n/a".to_string()))]
+    fn test_metric_value(#[case] metric: &str, #[case] expected: DataValue) {
+        let data: ScorecardResponse = serde_json::from_str(RESPONSE).unwrap();
+        let source = ScorecardSource::new();
+        assert_eq!(source.metric_value(&data, metric).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_unknown_metric() {
+        let data: ScorecardResponse = serde_json::from_str(RESPONSE).unwrap();
+        let source = ScorecardSource::new();
+        assert!(matches!(
+            source.metric_value(&data, "stars"),
+            Err(FetchError::UnknownMetric { .. })
+        ));
+    }
+
+    // ========================================================================
+    // Score Colors (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("score", DataValue::Float(9.1), Some("22C55E"))]
+    #[case("score", DataValue::Float(6.0), Some("84CC16"))]
+    #[case("score", DataValue::Float(4.5), Some("EAB308"))]
+    #[case("score", DataValue::Float(2.0), Some("F97316"))]
+    #[case("score", DataValue::Float(1.2), Some("EF4444"))]
+    #[case("maintained", DataValue::Number(10), Some("22C55E"))]
+    #[case("fuzzing", DataValue::Number(0), Some("EF4444"))]
+    #[case("packaging", DataValue::String("inconclusive".to_string()), Some("6B7280"))]
+    #[case("date", DataValue::String("2024-06-03".to_string()), None)]
+    fn test_metric_colors(
+        #[case] metric: &str,
+        #[case] value: DataValue,
+        #[case] expected: Option<&str>,
+    ) {
+        let source = ScorecardSource::new();
+        assert_eq!(source.metric_color(metric, &value), expected);
+    }
+
+    #[test]
+    fn test_check_metrics_available() {
+        let source = ScorecardSource::new();
+        for check in CHECK_METRICS {
+            assert!(source.available_metrics().contains(check), "{}", check);
+            assert_ne!(source.metric_label(check), "Unknown", "{}", check);
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! GitHub Sponsors data source
+
+use crate::error::{FetchError, Result};
+use crate::http::HttpClient;
+use crate::sources::DataSource;
+use crate::value::DataValue;
+use serde::Deserialize;
+
+/// Environment variable holding the GitHub token
+const TOKEN_VAR: &str = "GITHUB_TOKEN";
+
+/// Sponsor counts of a user or organization
+const QUERY: &str = "query($login: String!) { repositoryOwner(login: $login) { \
+    ... on Sponsorable { sponsors { totalCount } sponsoring { totalCount } } } }";
+
+/// GraphQL response envelope
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse {
+    #[serde(default)]
+    data: Option<Data>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Data {
+    repository_owner: Option<Owner>,
+}
+
+/// Sponsorable account (partial)
+#[derive(Debug, Deserialize)]
+struct Owner {
+    sponsors: Count,
+    sponsoring: Count,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Count {
+    total_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+/// GitHub Sponsors data source
+///
+/// Sponsor counts are only available from the GraphQL API, which needs a
+/// token even for public data.
+pub struct SponsorsSource {
+    api_base: String,
+    token: Option<String>,
+}
+
+impl Default for SponsorsSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SponsorsSource {
+    /// Create a new GitHub Sponsors source
+    ///
+    /// Reads GITHUB_TOKEN from environment; every request needs it.
+    pub fn new() -> Self {
+        SponsorsSource {
+            api_base: "https://api.github.com".to_string(),
+            token: std::env::var(TOKEN_VAR).ok().filter(|t| !t.is_empty()),
+        }
+    }
+
+    /// Create a GitHub Sponsors source with a specific token
+    pub fn with_token(token: String) -> Self {
+        SponsorsSource {
+            api_base: "https://api.github.com".to_string(),
+            token: Some(token),
+        }
+    }
+
+    /// Fetch the sponsor counts of a user or organization
+    fn fetch_owner(&self, http: &HttpClient, login: &str) -> Result<Owner> {
+        let token = self
+            .token
+            .as_deref()
+            .ok_or_else(|| FetchError::MissingCredentials(TOKEN_VAR.to_string()))?;
+        let url = format!("{}/graphql", self.api_base);
+
+        let response = http
+            .post(&url)
+            .set("Accept", "application/json")
+            .set("Authorization", &format!("Bearer {}", token))
+            .send_json(serde_json::json!({
+                "query": QUERY,
+                "variables": { "login": login },
+            }));
+
+        match response {
+            Ok(resp) => {
+                let body: GraphQlResponse = resp.into_json().map_err(|e| {
+                    FetchError::ParseError(format!("Failed to parse GitHub response: {}", e))
+                })?;
+                Self::owner(body, login)
+            }
+            Err(ureq::Error::Status(403, resp)) => {
+                let retry_after = resp
+                    .header("X-RateLimit-Reset")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .map(|reset| {
+                        reset.saturating_sub(
+                            std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .unwrap()
+                                .as_secs(),
+                        )
+                    })
+                    .unwrap_or(60);
+
+                Err(FetchError::RateLimited { retry_after })
+            }
+            Err(ureq::Error::Status(code, resp)) => {
+                let message = resp.into_string().unwrap_or_default();
+                Err(FetchError::ApiError {
+                    status: code,
+                    message,
+                })
+            }
+            Err(e) => Err(FetchError::HttpError(e.to_string())),
+        }
+    }
+
+    /// The account from a GraphQL response
+    ///
+    /// GraphQL answers 200 for unknown logins, with a null owner and an error.
+    fn owner(body: GraphQlResponse, login: &str) -> Result<Owner> {
+        match body.data.and_then(|data| data.repository_owner) {
+            Some(owner) => Ok(owner),
+            None if body.errors.is_empty() => Err(FetchError::NotFound(login.to_string())),
+            None if body
+                .errors
+                .iter()
+                .any(|e| e.message.contains("Could not resolve")) =>
+            {
+                Err(FetchError::NotFound(login.to_string()))
+            }
+            None => Err(FetchError::ApiError {
+                status: 200,
+                message: body
+                    .errors
+                    .into_iter()
+                    .map(|e| e.message)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            }),
+        }
+    }
+
+    /// Parse query into a GitHub login
+    fn parse_query(query: &str) -> Result<&str> {
+        let login = query.strip_prefix('@').unwrap_or(query);
+        if login.is_empty()
+            || !login
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        {
+            return Err(FetchError::ParseError(format!(
+                "Invalid GitHub Sponsors query '{}'. Expected format: user or organization login",
+                query
+            )));
+        }
+        Ok(login)
+    }
+
+    /// Value of a metric for an account
+    fn metric_value(&self, owner: &Owner, metric: &str) -> Result<DataValue> {
+        match metric {
+            "sponsors" => Ok(DataValue::Number(owner.sponsors.total_count)),
+            "sponsoring" => Ok(DataValue::Number(owner.sponsoring.total_count)),
+            _ => Err(FetchError::UnknownMetric {
+                metric: metric.to_string(),
+                available: self
+                    .available_metrics()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl DataSource for SponsorsSource {
+    fn id(&self) -> &'static str {
+        "sponsors"
+    }
+
+    fn name(&self) -> &'static str {
+        "GitHub Sponsors"
+    }
+
+    fn fetch(&self, http: &HttpClient, query: &str, metric: &str) -> Result<DataValue> {
+        let login = Self::parse_query(query)?;
+        let owner = self.fetch_owner(http, login)?;
+        self.metric_value(&owner, metric)
+    }
+
+    fn available_metrics(&self) -> &'static [&'static str] {
+        &["sponsors", "sponsoring"]
+    }
+
+    fn default_ttl(&self) -> u64 {
+        86400 // 24 hours - sponsor counts change slowly
+    }
+
+    fn requires_auth(&self) -> bool {
+        true
+    }
+
+    fn metric_label(&self, metric: &str) -> &'static str {
+        match metric {
+            "sponsors" => "Sponsors",
+            "sponsoring" => "Sponsoring",
+            _ => "Unknown",
+        }
+    }
+
+    fn metric_color(&self, metric: &str, _value: &DataValue) -> Option<&str> {
+        match metric {
+            "sponsors" | "sponsoring" => Some("EA4AAA"), // Sponsors pink
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const RESPONSE: &str = r#"{
+        "data": {
+            "repositoryOwner": {
+                "sponsors": { "totalCount": 128 },
+                "sponsoring": { "totalCount": 7 }
+            }
+        }
+    }"#;
+
+    // ========================================================================
+    // Query Parsing (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("dtolnay", Some("dtolnay"))]
+    #[case("@rust-lang", Some("rust-lang"))]
+    #[case("", None)]
+    #[case("owner/repo", None)]
+    fn test_parse_query(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(SponsorsSource::parse_query(input).ok(), expected);
+    }
+
+    // ========================================================================
+    // Metric Values (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("sponsors", DataValue::Number(128))]
+    #[case("sponsoring", DataValue::Number(7))]
+    fn test_metric_value(#[case] metric: &str, #[case] expected: DataValue) {
+        let body: GraphQlResponse = serde_json::from_str(RESPONSE).unwrap();
+        let owner = SponsorsSource::owner(body, "dtolnay").unwrap();
+        let source = SponsorsSource::with_token("token".to_string());
+        assert_eq!(source.metric_value(&owner, metric).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_unknown_login() {
+        let body: GraphQlResponse = serde_json::from_str(
+            r#"{
+                "data": { "repositoryOwner": null },
+                "errors": [{ "message": "Could not resolve to a RepositoryOwner with the login of 'nobody-here'." }]
+            }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            SponsorsSource::owner(body, "nobody-here"),
+            Err(FetchError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_token() {
+        let source = SponsorsSource {
+            api_base: "http://127.0.0.1:9".to_string(),
+            token: None,
+        };
+        let err = source
+            .fetch(&HttpClient::default(), "dtolnay", "sponsors")
+            .unwrap_err();
+        assert!(matches!(err, FetchError::MissingCredentials(_)));
+        assert!(err.to_string().contains("GITHUB_TOKEN"), "{}", err);
+    }
+}
@@ -15,17 +15,108 @@ pub enum DataValue {
     String(String),
     /// Boolean value (has_issues, archived)
     Bool(bool),
+    /// The source could not provide a value (not found, API or network error)
+    ///
+    /// Cached for [`FetchConfig::failure_ttl`](crate::FetchConfig::failure_ttl)
+    /// so a broken badge isn't fetched again on every run. Stored as `null`.
+    Unavailable,
 }
 
 impl DataValue {
     /// Format the value for display in a badge
+    ///
+    /// Same as [`format_with`](Self::format_with) and [`Locale::ISO`]: `.`
+    /// decimal mark, no thousands separators, and dates as `2024-06-03`.
     pub fn format(&self) -> String {
+        self.format_with(&Locale::ISO)
+    }
+
+    /// Format the value for display in a badge, using `locale` for decimal
+    /// marks, thousands separators, and dates
+    ///
+    /// Counts stay abbreviated (`1.5k`, or `1,5k` in German). Strings that
+    /// are an ISO 8601 date or date-time, such as release dates, are
+    /// rewritten in the locale's date order; other strings are unchanged.
+    ///
+    /// ```
+    /// use mdfx_fetch::{DataValue, Locale};
+    ///
+    /// let de = Locale::parse("de").unwrap();
+    /// assert_eq!(DataValue::Number(1500).format_with(&de), "1,5k");
+    /// assert_eq!(DataValue::Float(12345.67).format_with(&de), "12.345,7");
+    /// assert_eq!(DataValue::from("2024-06-03").format_with(&de), "03.06.2024");
+    /// ```
+    pub fn format_with(&self, locale: &Locale) -> String {
         match self {
-            DataValue::Number(n) => format_number(*n),
-            DataValue::Float(f) => format!("{:.1}", f),
-            DataValue::String(s) => s.clone(),
+            DataValue::Number(n) => format_number(*n, locale),
+            DataValue::Float(f) => locale.decimal(&format!("{:.1}", f)),
+            DataValue::String(s) => locale.date(s).unwrap_or_else(|| s.clone()),
             DataValue::Bool(b) => if *b { "yes" } else { "no" }.to_string(),
+            DataValue::Unavailable => "n/a".to_string(),
+        }
+    }
+
+    /// Days since 1970-01-01 of the date this value holds
+    ///
+    /// `None` unless the value is a string that is an ISO 8601 date or
+    /// date-time, such as a release date; the time of day is ignored.
+    pub fn as_days(&self) -> Option<i64> {
+        let DataValue::String(s) = self else {
+            return None;
+        };
+        let [y, m, d] = iso_date(s)?;
+        let (y, m, d): (i64, i64, i64) = (y.parse().ok()?, m.parse().ok()?, d.parse().ok()?);
+        if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+            return None;
         }
+        // Days from civil, counting from March so leap days come last
+        let y = if m <= 2 { y - 1 } else { y };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        Some(era * 146_097 + doe - 719_468)
+    }
+
+    /// How long before `today` the date this value holds was: `today`,
+    /// `yesterday`, `3 days ago`, `2 weeks ago`, `5 months ago`, `2 years ago`
+    ///
+    /// `today` counts days since 1970-01-01, like [`as_days`](Self::as_days);
+    /// dates after it read `in 3 days`. `None` if the value is not a date.
+    ///
+    /// ```
+    /// use mdfx_fetch::DataValue;
+    ///
+    /// let released = DataValue::from("2024-06-03");
+    /// let today = released.as_days().unwrap() + 45;
+    /// assert_eq!(released.format_relative(today).as_deref(), Some("6 weeks ago"));
+    /// ```
+    pub fn format_relative(&self, today: i64) -> Option<String> {
+        let days = today - self.as_days()?;
+        let (n, unit) = match days.unsigned_abs() {
+            0 => return Some("today".to_string()),
+            1 if days > 0 => return Some("yesterday".to_string()),
+            1 => return Some("tomorrow".to_string()),
+            n @ 2..=13 => (n, "day"),
+            n @ 14..=59 => (n / 7, "week"),
+            n @ 60..=364 => (n / 30, "month"),
+            n => (n / 365, "year"),
+        };
+        let unit = if n == 1 {
+            unit.to_string()
+        } else {
+            format!("{}s", unit)
+        };
+        Some(if days > 0 {
+            format!("{} {} ago", n, unit)
+        } else {
+            format!("in {} {}", n, unit)
+        })
+    }
+
+    /// Check if this is [`DataValue::Unavailable`]
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self, DataValue::Unavailable)
     }
 
     /// Get as number, returning None if not a number
@@ -103,18 +194,164 @@ impl From<bool> for DataValue {
 }
 
 /// Format a large number with K/M/B suffixes
-fn format_number(n: u64) -> String {
+fn format_number(n: u64, locale: &Locale) -> String {
     if n >= 1_000_000_000 {
-        format!("{:.1}B", n as f64 / 1_000_000_000.0)
+        format!("{}B", locale.decimal(&format!("{:.1}", n as f64 / 1e9)))
     } else if n >= 1_000_000 {
-        format!("{:.1}M", n as f64 / 1_000_000.0)
+        format!("{}M", locale.decimal(&format!("{:.1}", n as f64 / 1e6)))
     } else if n >= 1_000 {
-        format!("{:.1}k", n as f64 / 1_000.0)
+        format!("{}k", locale.decimal(&format!("{:.1}", n as f64 / 1e3)))
     } else {
         n.to_string()
     }
 }
 
+/// Order of day, month, and year in a locale's dates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateOrder {
+    Ymd,
+    Dmy,
+    Mdy,
+}
+
+/// Number and date conventions for displaying fetched values
+///
+/// Chosen with `locale=` on live badges or `"locale"` in `.mdfx.json`.
+/// Only the conventions of a fixed set of locales are known, see
+/// [`Locale::available`]; there is no dependency on the system locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    tag: &'static str,
+    group: &'static str,
+    decimal: char,
+    order: DateOrder,
+    date_sep: char,
+}
+
+impl Locale {
+    /// `1234.5`, `2024-06-03`: the output of [`DataValue::format`]
+    pub const ISO: Locale = Locale::new("iso", "", '.', DateOrder::Ymd, '-');
+
+    const fn new(
+        tag: &'static str,
+        group: &'static str,
+        decimal: char,
+        order: DateOrder,
+        date_sep: char,
+    ) -> Self {
+        Locale {
+            tag,
+            group,
+            decimal,
+            order,
+            date_sep,
+        }
+    }
+
+    /// Look up a locale by BCP 47 tag (`de-DE`, `en_GB`, `fr`)
+    ///
+    /// Case and `_` vs `-` don't matter. A language without a known region
+    /// uses the first region listed for it (`en` is `en-US`, `de-AT` is
+    /// `de-DE`).
+    pub fn parse(tag: &str) -> Option<Self> {
+        let tag = tag.replace('_', "-");
+        LOCALES
+            .iter()
+            .find(|l| l.tag.eq_ignore_ascii_case(&tag))
+            .or_else(|| {
+                let lang = tag.split('-').next()?;
+                LOCALES
+                    .iter()
+                    .find(|l| l.tag.split('-').next() == Some(lang.to_ascii_lowercase().as_str()))
+            })
+            .copied()
+    }
+
+    /// Tags of the known locales
+    pub fn available() -> impl Iterator<Item = &'static str> {
+        LOCALES.iter().map(|l| l.tag)
+    }
+
+    /// The locale's tag, as listed by [`available`](Self::available)
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    /// Rewrite a `1234.5`-style number with this locale's separators
+    fn decimal(&self, number: &str) -> String {
+        let (int, frac) = number.split_once('.').unwrap_or((number, ""));
+        let (sign, digits) = match int.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", int),
+        };
+        let mut out = sign.to_string();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                out.push_str(self.group);
+            }
+            out.push(digit);
+        }
+        if !frac.is_empty() {
+            out.push(self.decimal);
+            out.push_str(frac);
+        }
+        out
+    }
+
+    /// Rewrite an ISO 8601 date or date-time as a date in this locale
+    fn date(&self, value: &str) -> Option<String> {
+        let [y, m, d] = iso_date(value)?;
+        let [a, b, c] = match self.order {
+            DateOrder::Ymd => [y, m, d],
+            DateOrder::Dmy => [d, m, y],
+            DateOrder::Mdy => [m, d, y],
+        };
+        let sep = self.date_sep;
+        Some(format!("{a}{sep}{b}{sep}{c}"))
+    }
+}
+
+/// Year, month, and day of an ISO 8601 date or date-time
+fn iso_date(value: &str) -> Option<[&str; 3]> {
+    let date = value.get(..10)?;
+    let rest = &value[10..];
+    if !(rest.is_empty() || rest.starts_with('T') || rest.starts_with(' ')) {
+        return None;
+    }
+    let mut parts = date.split('-');
+    let (y, m, d) = (parts.next()?, parts.next()?, parts.next()?);
+    let digits = |s: &str, len| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+    if !(digits(y, 4) && digits(m, 2) && digits(d, 2)) {
+        return None;
+    }
+    Some([y, m, d])
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::ISO
+    }
+}
+
+/// Known locales; the first region listed for a language is its default
+const LOCALES: [Locale; 15] = [
+    Locale::ISO,
+    Locale::new("en-US", ",", '.', DateOrder::Mdy, '/'),
+    Locale::new("en-GB", ",", '.', DateOrder::Dmy, '/'),
+    Locale::new("de-DE", ".", ',', DateOrder::Dmy, '.'),
+    Locale::new("fr-FR", "\u{202F}", ',', DateOrder::Dmy, '/'),
+    Locale::new("es-ES", ".", ',', DateOrder::Dmy, '/'),
+    Locale::new("it-IT", ".", ',', DateOrder::Dmy, '/'),
+    Locale::new("nl-NL", ".", ',', DateOrder::Dmy, '-'),
+    Locale::new("pt-BR", ".", ',', DateOrder::Dmy, '/'),
+    Locale::new("pl-PL", "\u{A0}", ',', DateOrder::Dmy, '.'),
+    Locale::new("ru-RU", "\u{A0}", ',', DateOrder::Dmy, '.'),
+    Locale::new("sv-SE", "\u{A0}", ',', DateOrder::Ymd, '-'),
+    Locale::new("ja-JP", ",", '.', DateOrder::Ymd, '/'),
+    Locale::new("zh-CN", ",", '.', DateOrder::Ymd, '/'),
+    Locale::new("ko-KR", ",", '.', DateOrder::Ymd, '.'),
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,7 +368,82 @@ mod tests {
     #[case(1_500_000, "1.5M")]
     #[case(1_500_000_000, "1.5B")]
     fn test_format_number(#[case] input: u64, #[case] expected: &str) {
-        assert_eq!(format_number(input), expected);
+        assert_eq!(format_number(input, &Locale::ISO), expected);
+    }
+
+    // ========================================================================
+    // Locales (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("en-US", DataValue::Number(1500), "1.5k")]
+    #[case("de-DE", DataValue::Number(2_500_000), "2,5M")]
+    #[case("en", DataValue::Float(1234567.89), "1,234,567.9")]
+    #[case("fr", DataValue::Float(-1234.5), "-1\u{202F}234,5")]
+    #[case("iso", DataValue::Float(1234.5), "1234.5")]
+    #[case("de", DataValue::Float(999.0), "999,0")]
+    #[case("en-US", DataValue::String("2024-06-03".into()), "06/03/2024")]
+    #[case("en-GB", DataValue::String("2024-06-03T12:00:00Z".into()), "03/06/2024")]
+    #[case("ja", DataValue::String("2024-06-03".into()), "2024/06/03")]
+    #[case("de", DataValue::String("1.2.3".into()), "1.2.3")]
+    #[case("de", DataValue::String("2024-06-03-beta".into()), "2024-06-03-beta")]
+    #[case("de", DataValue::String("20240603".into()), "20240603")]
+    #[case("de", DataValue::Bool(true), "yes")]
+    fn test_format_with_locale(
+        #[case] tag: &str,
+        #[case] value: DataValue,
+        #[case] expected: &str,
+    ) {
+        let locale = Locale::parse(tag).unwrap();
+        assert_eq!(value.format_with(&locale), expected);
+    }
+
+    #[rstest]
+    #[case("1970-01-01", Some(0))]
+    #[case("2000-03-01", Some(11_017))]
+    #[case("2024-02-29T23:59:59Z", Some(19_782))]
+    #[case("2024-13-01", None)]
+    #[case("v1.2.3", None)]
+    fn test_as_days(#[case] value: &str, #[case] expected: Option<i64>) {
+        assert_eq!(DataValue::from(value).as_days(), expected);
+    }
+
+    #[rstest]
+    #[case(0, "today")]
+    #[case(1, "yesterday")]
+    #[case(-1, "tomorrow")]
+    #[case(3, "3 days ago")]
+    #[case(13, "13 days ago")]
+    #[case(14, "2 weeks ago")]
+    #[case(59, "8 weeks ago")]
+    #[case(60, "2 months ago")]
+    #[case(364, "12 months ago")]
+    #[case(365, "1 year ago")]
+    #[case(800, "2 years ago")]
+    #[case(-10, "in 10 days")]
+    fn test_format_relative(#[case] age: i64, #[case] expected: &str) {
+        let value = DataValue::from("2024-06-03");
+        let today = value.as_days().unwrap() + age;
+        assert_eq!(value.format_relative(today).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn test_format_relative_not_a_date() {
+        assert_eq!(DataValue::Number(3).format_relative(0), None);
+        assert_eq!(DataValue::from("1.0.0").format_relative(0), None);
+    }
+
+    #[rstest]
+    #[case("de-DE", Some("de-DE"))]
+    #[case("en_gb", Some("en-GB"))]
+    #[case("EN", Some("en-US"))]
+    #[case("de-AT", Some("de-DE"))]
+    #[case("pt", Some("pt-BR"))]
+    #[case("ISO", Some("iso"))]
+    #[case("xx", None)]
+    #[case("", None)]
+    fn test_locale_parse(#[case] tag: &str, #[case] expected: Option<&str>) {
+        assert_eq!(Locale::parse(tag).map(|l| l.tag()), expected);
     }
 
     // ========================================================================
@@ -145,6 +457,7 @@ mod tests {
     #[case(DataValue::String("MIT".to_string()), "MIT")]
     #[case(DataValue::Bool(true), "yes")]
     #[case(DataValue::Bool(false), "no")]
+    #[case(DataValue::Unavailable, "n/a")]
     fn test_data_value_display(#[case] value: DataValue, #[case] expected: &str) {
         assert_eq!(value.format(), expected);
     }
@@ -160,4 +473,13 @@ mod tests {
         let b: DataValue = true.into();
         assert_eq!(b, DataValue::Bool(true));
     }
+
+    #[rstest]
+    #[case(DataValue::Number(42), "42")]
+    #[case(DataValue::String("MIT".to_string()), r#""MIT""#)]
+    #[case(DataValue::Unavailable, "null")]
+    fn test_serde_roundtrip(#[case] value: DataValue, #[case] json: &str) {
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+        assert_eq!(serde_json::from_str::<DataValue>(json).unwrap(), value);
+    }
 }
@@ -103,6 +103,9 @@ pub fn icon_path(name: &str) -> Option<&'static str> {
         "android" => Some("M17.523 0c-.514.015-.996.27-1.363.738-.326.416-.53.94-.579 1.487a.28.28 0 0 0 .158.291l.218.108a13.18 13.18 0 0 1 .756.41c.146.087.37.206.542.314.138.087.27.18.396.277l-.063.36a5.112 5.112 0 0 0-.029.88c.05.69.28 1.433.709 2.088.43.654 1.05 1.217 1.837 1.577.1.056.226.126.387.212.153.087.303.176.414.252.1.067.261.18.339.25-.064.195-.163.39-.29.624a7.046 7.046 0 0 1-.532.76c-.264.333-.596.705-.993 1.109-.418.425-.824.724-1.218.926a6.398 6.398 0 0 1-.69.305 4.347 4.347 0 0 0-.462.21c-.13.074-.319.197-.396.26a1.75 1.75 0 0 0-.339.38c-.23.346-.323.718-.32 1.062.01.687.364 1.286.887 1.664.52.377 1.189.532 1.846.48.656-.053 1.301-.312 1.783-.759.485-.448.827-1.074.903-1.788a2.234 2.234 0 0 0-.055-.692c.207-.205.398-.44.59-.681.25-.32.498-.65.689-.98.216-.375.38-.786.512-1.206l.036-.115a.28.28 0 0 0-.193-.35l-.036-.008c-.2-.03-.432-.098-.615-.157a6.94 6.94 0 0 1-.628-.228 3.622 3.622 0 0 1-1.274-.876 3.168 3.168 0 0 1-.59-.922 3.1 3.1 0 0 1-.21-1.136c-.002-.356.057-.685.163-.988.084-.236.221-.59.3-.786l.077-.175a.28.28 0 0 0-.098-.34A6.996 6.996 0 0 0 18.2 4.2a4.812 4.812 0 0 0-.555-.328l-.218-.11a.28.28 0 0 0-.319.063 1.86 1.86 0 0 1-.31.255 1.785 1.785 0 0 1-1.105.298 1.782 1.782 0 0 1-1.057-.408 1.862 1.862 0 0 1-.278-.284.28.28 0 0 0-.318-.096l-.253.108c-.184.08-.444.207-.62.306a7.29 7.29 0 0 0-.753.494.28.28 0 0 0-.089.346l.1.213c.094.207.254.617.352.898.124.36.2.759.212 1.179.018.596-.1 1.233-.363 1.835a3.857 3.857 0 0 1-.497.834 4.264 4.264 0 0 1-.707.693 5.096 5.096 0 0 1-.502.35l-.206.12a.28.28 0 0 0-.106.378l.024.042c.142.235.324.447.524.638.258.247.554.458.877.617.47.232 1.003.382 1.538.413.265.015.529.003.785-.04.261-.043.508-.117.737-.214l-.006.02c-.035.138-.108.443-.141.618a5.16 5.16 0 0 0-.074.87c0 .398.053.79.17 1.168a3.14 3.14 0 0 0 .535.976c.263.327.59.608.967.826.24.14.504.25.782.326a.282.282 0 0 0 .345-.259l.015-.256c.02-.331.068-.799.102-1.115.045-.416.056-.844.033-1.257a5.56 5.56 0 0 0-.116-.89c-.069-.32-.16-.631-.27-.93l-.037-.096a.28.28 0 0 1 .194-.365l.048-.011c.22-.04.448-.109.68-.2.327-.128.665-.31.982-.539a3.66 3.66 0 0 0 .815-.778c.254-.325.456-.697.586-1.11.082-.26.13-.538.14-.82l.003-.078a.28.28 0 0 0-.216-.27l-.194-.046c-.204-.05-.499-.139-.698-.205a5.462 5.462 0 0 1-.803-.333 3.32 3.32 0 0 1-.714-.497 2.64 2.64 0 0 1-.499-.645 2.54 2.54 0 0 1-.25-.812 2.857 2.857 0 0 1-.016-.858c.028-.238.12-.606.181-.848l.08-.282a.28.28 0 0 0-.17-.335 5.463 5.463 0 0 0-.725-.206 6.066 6.066 0 0 0-.786-.117 6.415 6.415 0 0 0-.801-.015c-.139.008-.278.02-.416.04z"),
         "figma" => Some("M15.852 8.981h-4.588V0h4.588c2.476 0 4.49 2.014 4.49 4.49s-2.014 4.491-4.49 4.491zM12.735 7.51h3.117c1.665 0 3.019-1.355 3.019-3.019s-1.355-3.019-3.019-3.019h-3.117V7.51zm0 1.471H8.148c-2.476 0-4.49-2.014-4.49-4.49S5.672 0 8.148 0h4.588v8.981zm-4.587-7.51c-1.665 0-3.019 1.355-3.019 3.019s1.354 3.02 3.019 3.02h3.117V1.471H8.148zm4.587 15.019H8.148c-2.476 0-4.49-2.014-4.49-4.49s2.014-4.49 4.49-4.49h4.588v8.98zM8.148 8.981c-1.665 0-3.019 1.355-3.019 3.019s1.355 3.019 3.019 3.019h3.117V8.981H8.148zM8.172 24c-2.489 0-4.515-2.014-4.515-4.49s2.014-4.49 4.49-4.49h4.588v4.441c0 2.503-2.047 4.539-4.563 4.539zm-.024-7.51a3.023 3.023 0 0 0-3.019 3.019c0 1.665 1.365 3.019 3.044 3.019 1.705 0 3.093-1.376 3.093-3.068v-2.97H8.148zm7.704 0h-.098c-2.476 0-4.49-2.014-4.49-4.49s2.014-4.49 4.49-4.49h.098c2.476 0 4.49 2.014 4.49 4.49s-2.014 4.49-4.49 4.49zm-.098-7.509c-1.665 0-3.019 1.355-3.019 3.019s1.355 3.019 3.019 3.019h.098c1.665 0 3.019-1.355 3.019-3.019s-1.355-3.019-3.019-3.019h-.098z"),
         "discord" => Some("M20.317 4.37a19.791 19.791 0 0 0-4.885-1.515.074.074 0 0 0-.079.037c-.21.375-.444.864-.608 1.25a18.27 18.27 0 0 0-5.487 0 12.64 12.64 0 0 0-.617-1.25.077.077 0 0 0-.079-.037A19.736 19.736 0 0 0 3.677 4.37a.07.07 0 0 0-.032.027C.533 9.046-.32 13.58.099 18.057a.082.082 0 0 0 .031.057 19.9 19.9 0 0 0 5.993 3.03.078.078 0 0 0 .084-.028 14.09 14.09 0 0 0 1.226-1.994.076.076 0 0 0-.041-.106 13.107 13.107 0 0 1-1.872-.892.077.077 0 0 1-.008-.128 10.2 10.2 0 0 0 .372-.292.074.074 0 0 1 .077-.01c3.928 1.793 8.18 1.793 12.062 0a.074.074 0 0 1 .078.01c.12.098.246.198.373.292a.077.077 0 0 1-.006.127 12.299 12.299 0 0 1-1.873.892.077.077 0 0 0-.041.107c.36.698.772 1.362 1.225 1.993a.076.076 0 0 0 .084.028 19.839 19.839 0 0 0 6.002-3.03.077.077 0 0 0 .032-.054c.5-5.177-.838-9.674-3.549-13.66a.061.061 0 0 0-.031-.03zM8.02 15.33c-1.183 0-2.157-1.085-2.157-2.419 0-1.333.956-2.419 2.157-2.419 1.21 0 2.176 1.096 2.157 2.42 0 1.333-.956 2.418-2.157 2.418zm7.975 0c-1.183 0-2.157-1.085-2.157-2.419 0-1.333.955-2.419 2.157-2.419 1.21 0 2.176 1.096 2.157 2.42 0 1.333-.946 2.418-2.157 2.418z"),
+        "matrix" => Some("M.632.55v22.9H2.28V24H0V0h2.28v.55zm7.043 7.26v1.157h.033c.309-.443.683-.784 1.117-1.024.433-.245.936-.365 1.5-.365.54 0 1.033.107 1.481.314.448.208.785.582 1.02 1.108.254-.374.6-.706 1.034-.992.434-.287.95-.43 1.546-.43.453 0 .872.056 1.26.167.388.11.716.286.993.53.276.245.489.559.646.951.152.392.23.863.23 1.417v5.728h-2.349V11.52c0-.286-.01-.559-.032-.812a1.755 1.755 0 0 0-.18-.66 1.106 1.106 0 0 0-.438-.448c-.194-.11-.457-.166-.785-.166-.332 0-.6.064-.803.189a1.38 1.38 0 0 0-.48.499 1.946 1.946 0 0 0-.231.696 5.56 5.56 0 0 0-.06.785v4.768h-2.35v-4.8c0-.254-.004-.503-.018-.752a2.074 2.074 0 0 0-.143-.688 1.052 1.052 0 0 0-.415-.503c-.194-.125-.476-.19-.854-.19-.111 0-.259.024-.439.074-.18.051-.36.143-.53.282-.171.138-.319.337-.439.595-.12.259-.18.6-.18 1.02v4.966H5.46V7.81zm15.693 15.64V.55H21.72V0H24v24h-2.28v-.55z"),
+        "reddit" => Some("M12 0C5.373 0 0 5.373 0 12c0 3.314 1.343 6.314 3.515 8.485l-2.286 2.286C.775 23.225 1.097 24 1.738 24H12c6.627 0 12-5.373 12-12S18.627 0 12 0Zm4.388 3.199c1.104 0 1.999.895 1.999 1.999 0 1.105-.895 2-1.999 2-.946 0-1.739-.657-1.947-1.539v.002c-1.147.162-2.032 1.15-2.032 2.341v.007c1.776.067 3.4.567 4.686 1.363.473-.363 1.064-.58 1.707-.58 1.547 0 2.802 1.254 2.802 2.802 0 1.117-.655 2.081-1.601 2.531-.088 3.256-3.637 5.876-7.997 5.876-4.361 0-7.905-2.617-7.998-5.87-.954-.447-1.614-1.415-1.614-2.538 0-1.548 1.255-2.802 2.803-2.802.645 0 1.239.218 1.712.585 1.275-.79 2.881-1.291 4.64-1.365v-.01c0-1.663 1.263-3.034 2.88-3.207.188-.911.993-1.595 1.959-1.595Zm-8.085 8.376c-.784 0-1.459.78-1.506 1.797-.047 1.016.64 1.429 1.426 1.429.786 0 1.371-.369 1.418-1.385.047-1.017-.553-1.841-1.338-1.841Zm7.406 0c-.786 0-1.385.824-1.338 1.841.047 1.017.634 1.385 1.418 1.385.785 0 1.473-.413 1.426-1.429-.046-1.017-.721-1.797-1.506-1.797Zm-3.703 4.013c-.974 0-1.907.048-2.77.135-.147.015-.241.168-.183.305.483 1.154 1.622 1.964 2.953 1.964 1.33 0 2.47-.81 2.953-1.964.057-.137-.037-.29-.184-.305-.863-.087-1.795-.135-2.769-.135Z"),
+        "githubsponsors" => Some("M17.625 1.499c-2.32 0-4.354 1.203-5.625 3.03-1.271-1.827-3.305-3.03-5.625-3.03C3.129 1.499 0 4.253 0 8.249c0 4.275 3.068 7.847 5.828 10.227a33.14 33.14 0 0 0 5.616 3.876l.028.017.008.003-.001.003c.163.085.342.126.521.125.179.001.358-.041.521-.125l-.001-.003.008-.003.028-.017a33.14 33.14 0 0 0 5.616-3.876C20.932 16.096 24 12.524 24 8.249c0-3.996-3.129-6.75-6.375-6.75zm-.919 15.275a30.766 30.766 0 0 1-4.703 3.316l-.004-.002-.004.002a30.955 30.955 0 0 1-4.703-3.316c-2.677-2.307-5.047-5.298-5.047-8.523 0-2.754 2.121-4.5 4.125-4.5 2.06 0 3.914 1.479 4.544 3.684.143.495.596.797 1.086.796.49.001.943-.302 1.085-.796.63-2.205 2.484-3.684 4.544-3.684 2.004 0 4.125 1.746 4.125 4.5 0 3.225-2.37 6.216-5.048 8.523z"),
         // DevOps Extended
         "pulumi" => Some("M11.997 0C10.226 0 8.79.83 8.79 1.856c0 1.025 1.436 1.856 3.207 1.856 1.772 0 3.208-.831 3.208-1.856C15.205.83 13.77 0 11.997 0zM5.95 3.488c-1.772 0-3.208.83-3.208 1.856C2.742 6.369 4.178 7.2 5.95 7.2c1.771 0 3.207-.831 3.207-1.856 0-1.025-1.436-1.856-3.207-1.856zm12.103 0c-1.772 0-3.208.83-3.208 1.856 0 1.025 1.436 1.856 3.208 1.856 1.771 0 3.207-.831 3.207-1.856 0-1.025-1.436-1.856-3.207-1.856zm-6.056 3.495c-1.771 0-3.207.831-3.207 1.856 0 1.025 1.436 1.856 3.207 1.856 1.772 0 3.208-.83 3.208-1.856 0-1.025-1.436-1.856-3.208-1.856zm-10.127.67a1.157 1.157 0 0 0-.55.151c-.888.513-.89 2.172-.004 3.706.886 1.534 2.324 2.362 3.211 1.85.888-.513.89-2.171.003-3.706-.72-1.246-1.803-2.027-2.66-2zm20.257.004c-.857-.026-1.941.754-2.661 2-.886 1.535-.884 3.194.003 3.707.888.512 2.325-.316 3.211-1.85.886-1.534.885-3.193-.003-3.706a1.157 1.157 0 0 0-.55-.15zm-6.048 3.492c-.857-.026-1.94.754-2.66 2-.886 1.535-.885 3.194.003 3.706.887.513 2.325-.316 3.21-1.85.887-1.534.885-3.193-.003-3.706a1.157 1.157 0 0 0-.55-.15zm-8.16.001a1.157 1.157 0 0 0-.55.151c-.888.513-.89 2.172-.004 3.706.886 1.535 2.324 2.363 3.211 1.85.888-.512.89-2.171.003-3.705-.72-1.247-1.803-2.028-2.66-2.002zm-6.047 3.494a1.157 1.157 0 0 0-.55.151c-.888.513-.89 2.172-.004 3.706.886 1.534 2.324 2.362 3.212 1.85.887-.513.888-2.172.003-3.706-.72-1.246-1.804-2.027-2.661-2.001zm20.258.002c-.857-.026-1.941.755-2.66 2.001-.887 1.535-.885 3.193.003 3.706.887.512 2.325-.316 3.21-1.85.886-1.534.885-3.193-.003-3.706a1.157 1.157 0 0 0-.55-.15zm-6.047 3.492c-.858-.026-1.942.754-2.661 2-.886 1.535-.885 3.194.003 3.706.888.513 2.325-.315 3.21-1.85.887-1.533.885-3.193-.002-3.705a1.157 1.157 0 0 0-.55-.151zm-8.163.003a1.157 1.157 0 0 0-.55.151c-.887.513-.889 2.172-.003 3.706.886 1.534 2.323 2.363 3.211 1.85.888-.512.89-2.171.004-3.706-.72-1.246-1.804-2.027-2.662-2z"),
         "vagrant" => Some("M3.556 0L.392 1.846V4.11l7.124 17.3L11.998 24l4.523-2.611 7.083-17.345V1.848l.004-.002L20.44 0l-5.274 3.087v2.111l-3.168 7.384-3.164-7.384V3.109l-.017-.008.017-.01z"),
@@ -232,6 +235,9 @@ pub fn brand_color(name: &str) -> Option<&'static str> {
         "android" => Some("3DDC84"),
         "figma" => Some("F24E1E"),
         "discord" => Some("5865F2"),
+        "matrix" => Some("000000"),
+        "reddit" => Some("FF4500"),
+        "githubsponsors" => Some("EA4AAA"),
         // DevOps Extended
         "pulumi" => Some("8A3391"),
         "vagrant" => Some("1868F2"),
@@ -403,6 +409,9 @@ pub fn list_icons() -> &'static [&'static str] {
         "android",
         "figma",
         "discord",
+        "matrix",
+        "reddit",
+        "githubsponsors",
     ]
 }
 
@@ -0,0 +1,153 @@
+//! Parser benchmarks
+//!
+//! Covers `TemplateParser::process` on small and large documents, heavy
+//! nesting, and SVG-heavy input, plus batch `Converter` styling. See docs/PERFORMANCE.md for the budget
+//! these numbers are checked against.
+//!
+//! ```bash
+//! cargo bench -p mdfx --bench parser
+//! cargo bench -p mdfx --bench parser -- --save-baseline main
+//! cargo bench -p mdfx --bench parser -- --baseline main
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mdfx::renderer::svg::SvgBackend;
+use mdfx::{Converter, TemplateParser};
+
+/// A typical README section: headings, styled text, badges, a frame, and code
+const README_SECTION: &str = r#"# {{mathbold}}Project Title{{/mathbold}}
+
+{{ui:tech:rust/}} {{ui:tech:typescript/}} {{ui:tech:docker:style=flat-square/}}
+
+{{frame:gradient}}{{small-caps}}Fast, safe, and friendly{{/small-caps}}{{/frame}}
+
+## {{script}}Features{{/script}}
+
+- {{glyph:star/}} Unicode text styling with {{negative-squared}}24{{/negative-squared}} styles
+- {{ui:progress:75:width=120/}} Progress bars and {{ui:swatch:accent/}} swatches
+- Frames like {{fr:star}}this{{/}} and {{frame:solid-left}}this{{/frame}}
+
+```rust
+// Templates in code are preserved: {{mathbold}}not processed{{/mathbold}}
+fn main() {}
+```
+
+Inline code `{{ui:tech:rust/}}` is left alone too.
+
+"#;
+
+/// Repeat the README section until the document reaches `target_bytes`
+fn document(target_bytes: usize) -> String {
+    let copies = target_bytes.div_ceil(README_SECTION.len());
+    README_SECTION.repeat(copies)
+}
+
+/// Frames and styles nested `depth` levels deep
+fn nested(depth: usize) -> String {
+    let frames = ["gradient", "star", "solid-left", "diamond", "line-double"];
+    let mut text = "{{mathbold}}core{{/mathbold}}".to_string();
+    for i in 0..depth {
+        text = format!(
+            "{{{{frame:{}}}}}{}{{{{/frame}}}}",
+            frames[i % frames.len()],
+            text
+        );
+    }
+    text
+}
+
+/// A dashboard of SVG components
+fn svg_heavy(components: usize) -> String {
+    let templates = [
+        "{{ui:progress:65:width=200:fill=accent/}}",
+        "{{ui:donut:72:size=48/}}",
+        "{{ui:gauge:40:size=80/}}",
+        "{{ui:sparkline:1,4,2,8,5,7,3,9/}}",
+        "{{ui:rating:4.5/}}",
+        "{{ui:tech:rust:style=flat-square/}}",
+        "{{ui:waveform:1,3,2,5,4,6,3/}}",
+    ];
+    (0..components)
+        .map(|i| templates[i % templates.len()])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn bench_documents(c: &mut Criterion) {
+    let parser = TemplateParser::new().unwrap();
+    let mut group = c.benchmark_group("document");
+
+    for (name, size) in [("1KB", 1_000), ("50KB", 50_000), ("500KB", 500_000)] {
+        let input = document(size);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        if size >= 500_000 {
+            group.sample_size(10);
+        }
+        group.bench_with_input(BenchmarkId::from_parameter(name), &input, |b, input| {
+            b.iter(|| parser.process(black_box(input)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_nesting(c: &mut Criterion) {
+    let parser = TemplateParser::new().unwrap();
+    let mut group = c.benchmark_group("nesting");
+
+    for depth in [1, 8, 32] {
+        let input = nested(depth);
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &input, |b, input| {
+            b.iter(|| parser.process(black_box(input)).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_svg(c: &mut Criterion) {
+    let file_parser =
+        TemplateParser::with_backend(Box::new(SvgBackend::new("assets/mdfx"))).unwrap();
+    let inline_parser = TemplateParser::with_backend(Box::new(SvgBackend::new_inline())).unwrap();
+    let input = svg_heavy(100);
+    let mut group = c.benchmark_group("svg_100_components");
+
+    group.bench_function("file", |b| {
+        b.iter(|| file_parser.process_with_assets(black_box(&input)).unwrap())
+    });
+    group.bench_function("inline", |b| {
+        b.iter(|| inline_parser.process(black_box(&input)).unwrap())
+    });
+
+    group.finish();
+}
+
+fn bench_convert(c: &mut Criterion) {
+    let converter = Converter::new().unwrap();
+    let labels: Vec<String> = (0..1_000).map(|i| format!("Item {} Ready", i)).collect();
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let mut group = c.benchmark_group("convert_1000_strings");
+    group.throughput(Throughput::Elements(labels.len() as u64));
+
+    for style in ["mathbold", "strikethrough"] {
+        group.bench_with_input(BenchmarkId::from_parameter(style), &labels, |b, labels| {
+            b.iter(|| converter.convert_many(black_box(labels), style).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_construction(c: &mut Criterion) {
+    c.bench_function("parser_new", |b| b.iter(|| TemplateParser::new().unwrap()));
+}
+
+criterion_group!(
+    benches,
+    bench_documents,
+    bench_nesting,
+    bench_svg,
+    bench_convert,
+    bench_construction
+);
+criterion_main!(benches);
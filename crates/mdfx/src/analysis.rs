@@ -0,0 +1,188 @@
+//! Template usages found without rendering
+//!
+//! [`TemplateParser::analyze`](crate::TemplateParser::analyze) lists every
+//! template in a document - kind, name, arguments, and location - with the
+//! problems rendering it would report. Nothing is rendered: no assets are
+//! produced, no live data is fetched, and component output isn't built, so
+//! editors and linters can call it on every change.
+//!
+//! ```
+//! use mdfx::TemplateParser;
+//!
+//! let parser = TemplateParser::new().unwrap();
+//! let usages = parser.analyze("{{mathbold}}Hi{{/mathbold}}\n{{ui:tech:rust:bg=red/}} {{glyph:nope/}}");
+//!
+//! assert_eq!(usages.len(), 3);
+//! assert_eq!((usages[1].kind, usages[1].name.as_str()), ("ui", "tech"));
+//! assert_eq!(usages[1].args, ["rust"]);
+//! assert_eq!(usages[1].params["bg"], "red");
+//! assert_eq!((usages[1].line, usages[1].column), (2, 1));
+//! assert!(!usages[2].is_valid());
+//! ```
+
+use crate::ast::{Node, NodeKind};
+use crate::diagnostics::{line_column, Diagnostic, Severity};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// A template in a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateUsage {
+    /// Template type: style, frame, ui, shields, glyph, kbd, num, t, snippet,
+    /// partial, raw, or comment
+    pub kind: &'static str,
+    /// Style, frame spec, component, shield type, glyph, keys, number
+    /// format, string key, snippet path, or partial name; empty for raw
+    /// and comment templates
+    pub name: String,
+    /// Positional arguments, e.g. the value of `{{num:roman:2024/}}`
+    pub args: Vec<String>,
+    /// `key=value` parameters
+    pub params: BTreeMap<String, String>,
+    /// Byte range of the whole template; for templates that failed to
+    /// parse, of the opening tag
+    pub range: Range<usize>,
+    /// 1-based line of `range.start`
+    pub line: usize,
+    /// 1-based column (in characters) of `range.start`
+    pub column: usize,
+    /// Problems rendering would report, in source order
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl TemplateUsage {
+    /// Whether the template would render, possibly with warnings
+    pub fn is_valid(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .all(|d| d.severity != Severity::Error)
+    }
+
+    fn new(kind: &'static str, name: impl Into<String>, range: Range<usize>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            args: Vec::new(),
+            params: BTreeMap::new(),
+            range,
+            line: 0,
+            column: 0,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// The usage for a template node; `None` for text, code, and inline code
+    pub(crate) fn from_node(node: &Node) -> Option<Self> {
+        let range = node.range.clone();
+        let usage = match &node.kind {
+            NodeKind::Text(_) | NodeKind::Code(_) | NodeKind::InlineCode(_) => return None,
+            NodeKind::Raw(_) => Self::new("raw", "", range),
+            NodeKind::Comment(_) => Self::new("comment", "", range),
+            NodeKind::Style {
+                style,
+                spacing,
+                separator,
+                fallback,
+                seed,
+                ..
+            } => {
+                let mut usage = Self::new("style", style, range);
+                if *spacing > 0 {
+                    usage.params.insert("spacing".into(), spacing.to_string());
+                }
+                if let Some(separator) = separator {
+                    usage.params.insert("separator".into(), separator.clone());
+                }
+                if let Some(fallback) = fallback {
+                    usage
+                        .params
+                        .insert("fallback".into(), fallback.as_str().into());
+                }
+                if let Some(seed) = seed {
+                    usage.params.insert("seed".into(), seed.to_string());
+                }
+                usage
+            }
+            NodeKind::Frame { spec, .. } => Self::new("frame", spec, range),
+            NodeKind::Component { name, args, .. } => {
+                let mut usage = Self::new("ui", name, range);
+                for arg in args {
+                    match arg.split_once('=') {
+                        Some((key, value)) => {
+                            usage.params.insert(key.to_string(), value.to_string());
+                        }
+                        None => usage.args.push(arg.clone()),
+                    }
+                }
+                usage
+            }
+            NodeKind::Shield {
+                shield_type,
+                params,
+            } => Self {
+                params: params.clone(),
+                ..Self::new("shields", shield_type, range)
+            },
+            NodeKind::Glyph { name } => Self::new("glyph", name, range),
+            NodeKind::Kbd { keys } => Self::new("kbd", keys, range),
+            NodeKind::Number { format, value } => Self {
+                args: vec![value.clone()],
+                ..Self::new("num", format, range)
+            },
+            NodeKind::Translation { key } => Self::new("t", key, range),
+            NodeKind::Snippet { path, region, lang } => {
+                let mut usage = Self::new("snippet", path, range);
+                if let Some(region) = region {
+                    usage.params.insert("region".into(), region.clone());
+                }
+                if let Some(lang) = lang {
+                    usage.params.insert("lang".into(), lang.clone());
+                }
+                usage
+            }
+            NodeKind::Partial { name, .. } => Self::new("partial", name, range),
+        };
+        Some(usage)
+    }
+
+    /// The usage for a template that didn't parse, read from the opening
+    /// tag at `diagnostic.range`
+    pub(crate) fn from_tag(source: &str, diagnostic: Diagnostic) -> Self {
+        let tag = source.get(diagnostic.range.clone()).unwrap_or("");
+        let inner = tag.trim_start_matches("{{").trim_start_matches(['/', '!']);
+        let mut parts = inner.split([':', '/', '}']);
+        let head = parts.next().unwrap_or("");
+        let kind = match head {
+            "" if inner.starts_with("--") => "comment",
+            "raw" => "raw",
+            "ui" => "ui",
+            "frame" | "fr" => "frame",
+            "shields" => "shields",
+            "glyph" => "glyph",
+            "kbd" => "kbd",
+            "num" => "num",
+            "t" => "t",
+            "snippet" => "snippet",
+            "partial" => "partial",
+            _ => "style",
+        };
+        let name = match kind {
+            "comment" | "raw" => "",
+            "style" => head,
+            _ => parts.next().unwrap_or(""),
+        };
+        let mut usage = Self::new(kind, name, diagnostic.range.clone());
+        usage.diagnostics.push(diagnostic);
+        usage
+    }
+
+    /// Fill in line and column, and those of the diagnostics, from the
+    /// source text
+    pub(crate) fn locate(&mut self, source: &str) {
+        (self.line, self.column) = line_column(source, self.range.start);
+        for diagnostic in &mut self.diagnostics {
+            diagnostic.locate(source);
+        }
+    }
+}
@@ -0,0 +1,350 @@
+//! Comparing two generated asset directories
+//!
+//! When upgrading mdfx, the same templates can render different SVGs.
+//! [`diff`] compares an old and a new assets directory and reports which
+//! assets were added, removed, or changed, and [`preview_html`] shows the
+//! changed ones side by side for review.
+//!
+//! Content-addressed filenames change with the content, so assets are
+//! matched in three passes:
+//!
+//! 1. the same filename in both directories (changed if the bytes differ)
+//! 2. the same content under another filename (unchanged, only renamed)
+//! 3. the same primitive in both `manifest.json` files (changed)
+//!
+//! Whatever is left over was added or removed. SVG files that aren't in a
+//! manifest are still compared by filename and content.
+
+use crate::error::{Error, Result};
+use crate::manifest::AssetManifest;
+use badgefx::escape::{escape_attr, escape_text};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// An SVG file in one of the compared directories
+#[derive(Debug, Clone)]
+struct Asset {
+    file: String,
+    /// Asset type and primitive from the manifest, if listed there
+    identity: Option<String>,
+    sha256: String,
+}
+
+/// Difference between two assets directories
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssetChange {
+    /// Only in the new directory
+    Added { file: String },
+    /// Only in the old directory
+    Removed { file: String },
+    /// The same asset rendered differently
+    Changed { old: String, new: String },
+}
+
+/// Result of [`diff`]
+#[derive(Debug, Clone, Default)]
+pub struct AssetDiff {
+    /// Added, removed, and changed assets, sorted by filename
+    pub changes: Vec<AssetChange>,
+    /// Number of assets with identical content in both directories
+    pub unchanged: usize,
+}
+
+impl AssetDiff {
+    /// Whether both directories render the same assets
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Count of added, removed, and changed assets
+    pub fn counts(&self) -> (usize, usize, usize) {
+        self.changes.iter().fold(
+            (0, 0, 0),
+            |(added, removed, changed), change| match change {
+                AssetChange::Added { .. } => (added + 1, removed, changed),
+                AssetChange::Removed { .. } => (added, removed + 1, changed),
+                AssetChange::Changed { .. } => (added, removed, changed + 1),
+            },
+        )
+    }
+}
+
+/// Compare the SVG assets of two directories
+pub fn diff(old_dir: &Path, new_dir: &Path) -> Result<AssetDiff> {
+    let mut old = load(old_dir)?;
+    let mut new = load(new_dir)?;
+    let mut result = AssetDiff::default();
+
+    // Same filename
+    old.retain(|a| match new.iter().position(|b| b.file == a.file) {
+        Some(i) => {
+            let b = new.remove(i);
+            if a.sha256 == b.sha256 {
+                result.unchanged += 1;
+            } else {
+                result.changes.push(AssetChange::Changed {
+                    old: a.file.clone(),
+                    new: b.file,
+                });
+            }
+            false
+        }
+        None => true,
+    });
+
+    // Same content, renamed
+    old.retain(|a| match new.iter().position(|b| b.sha256 == a.sha256) {
+        Some(i) => {
+            new.remove(i);
+            result.unchanged += 1;
+            false
+        }
+        None => true,
+    });
+
+    // Same primitive, new content
+    old.retain(|a| {
+        let Some(identity) = &a.identity else {
+            return true;
+        };
+        match new
+            .iter()
+            .position(|b| b.identity.as_ref() == Some(identity))
+        {
+            Some(i) => {
+                result.changes.push(AssetChange::Changed {
+                    old: a.file.clone(),
+                    new: new.remove(i).file,
+                });
+                false
+            }
+            None => true,
+        }
+    });
+
+    result.changes.extend(
+        old.into_iter()
+            .map(|a| AssetChange::Removed { file: a.file }),
+    );
+    result
+        .changes
+        .extend(new.into_iter().map(|b| AssetChange::Added { file: b.file }));
+    result
+        .changes
+        .sort_by(|a, b| change_file(a).cmp(change_file(b)));
+
+    Ok(result)
+}
+
+fn change_file(change: &AssetChange) -> &str {
+    match change {
+        AssetChange::Added { file } | AssetChange::Removed { file } => file,
+        AssetChange::Changed { new, .. } => new,
+    }
+}
+
+/// Read every SVG in `dir`, with identities from its manifest if it has one
+fn load(dir: &Path) -> Result<Vec<Asset>> {
+    if !dir.is_dir() {
+        return Err(Error::FileNotFound(dir.to_path_buf()));
+    }
+
+    let manifest_path = dir.join("manifest.json");
+    let mut identities = BTreeMap::new();
+    if manifest_path.exists() {
+        let manifest = AssetManifest::load(&manifest_path)?;
+        for entry in &manifest.assets {
+            let Some(file) = Path::new(&entry.path).file_name().and_then(|f| f.to_str()) else {
+                continue;
+            };
+            let primitive = serde_json::to_string(&entry.primitive)?;
+            identities.insert(
+                file.to_string(),
+                format!("{}:{}", entry.asset_type, primitive),
+            );
+        }
+    }
+
+    let mut assets = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("svg") {
+            continue;
+        }
+        let Some(file) = path.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        let bytes = fs::read(&path)?;
+        assets.push(Asset {
+            file: file.to_string(),
+            identity: identities.get(file).cloned(),
+            sha256: format!("{:x}", Sha256::digest(&bytes)),
+        });
+    }
+    assets.sort_by(|a, b| a.file.cmp(&b.file));
+
+    Ok(assets)
+}
+
+/// Standalone HTML page showing changed assets side by side
+///
+/// SVGs are embedded, so the page can be opened from anywhere. Added and
+/// removed assets are shown in a single column.
+pub fn preview_html(diff: &AssetDiff, old_dir: &Path, new_dir: &Path) -> Result<String> {
+    let mut out = String::from(concat!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n",
+        "<title>mdfx asset diff</title>\n<style>\n",
+        "body { font-family: system-ui, sans-serif; max-width: 960px; margin: 2em auto; padding: 0 1em; }\n",
+        "table { border-collapse: collapse; width: 100%; }\n",
+        "th, td { border: 1px solid #d0d7de; padding: 8px; text-align: left; vertical-align: top; }\n",
+        "td img { max-width: 100%; }\n",
+        "code { font-size: 0.85em; }\n",
+        "</style>\n</head>\n<body>\n",
+    ));
+
+    let (added, removed, changed) = diff.counts();
+    out.push_str(&format!(
+        "<h1>Asset diff</h1>\n<p>{} changed, {} added, {} removed, {} unchanged</p>\n",
+        changed, added, removed, diff.unchanged
+    ));
+    out.push_str(&format!(
+        "<table>\n<tr><th>{}</th><th>{}</th></tr>\n",
+        escape_text(&old_dir.display().to_string()),
+        escape_text(&new_dir.display().to_string())
+    ));
+
+    for change in &diff.changes {
+        let (old, new) = match change {
+            AssetChange::Changed { old, new } => (Some(old), Some(new)),
+            AssetChange::Removed { file } => (Some(file), None),
+            AssetChange::Added { file } => (None, Some(file)),
+        };
+        out.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td></tr>\n",
+            preview_cell(old_dir, old)?,
+            preview_cell(new_dir, new)?
+        ));
+    }
+
+    out.push_str("</table>\n</body>\n</html>\n");
+    Ok(out)
+}
+
+fn preview_cell(dir: &Path, file: Option<&String>) -> Result<String> {
+    let Some(file) = file else {
+        return Ok(String::new());
+    };
+    let svg = fs::read_to_string(dir.join(file))?;
+    let uri = format!(
+        "data:image/svg+xml;charset=utf-8,{}",
+        svg.replace('%', "%25").replace('#', "%23")
+    );
+    Ok(format!(
+        "<img src=\"{}\" alt=\"{}\"><br><code>{}</code>",
+        escape_attr(&uri),
+        escape_attr(file),
+        escape_text(file)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::Primitive;
+    use tempfile::TempDir;
+
+    /// Write SVGs into a directory with a manifest listing them
+    fn assets_dir(files: &[(&str, &str, Primitive)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let mut manifest = AssetManifest::new("svg", "assets");
+        for (file, svg, primitive) in files {
+            fs::write(dir.path().join(file), svg).unwrap();
+            manifest.add_asset(
+                format!("assets/{}", file),
+                svg.as_bytes(),
+                primitive,
+                "swatch".to_string(),
+            );
+        }
+        manifest.write(&dir.path().join("manifest.json")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_diff_matches_by_name_content_and_primitive() {
+        let red = || Primitive::simple_swatch("FF0000", "flat");
+        let blue = || Primitive::simple_swatch("0000FF", "flat");
+        let green = || Primitive::simple_swatch("00FF00", "flat");
+        let old = assets_dir(&[
+            ("same.svg", "<svg>same</svg>", red()),
+            ("swatch_aaaa.svg", "<svg>blue</svg>", blue()),
+            ("renamed_old.svg", "<svg>moved</svg>", red()),
+            ("gone.svg", "<svg>gone</svg>", green()),
+        ]);
+        let new = assets_dir(&[
+            ("same.svg", "<svg>same</svg>", red()),
+            ("swatch_bbbb.svg", "<svg>blue v2</svg>", blue()),
+            ("renamed_new.svg", "<svg>moved</svg>", red()),
+            (
+                "fresh.svg",
+                "<svg>fresh</svg>",
+                Primitive::simple_swatch("FFFFFF", "flat"),
+            ),
+        ]);
+
+        let diff = diff(old.path(), new.path()).unwrap();
+        assert_eq!(diff.unchanged, 2);
+        assert_eq!(
+            diff.changes,
+            vec![
+                AssetChange::Added {
+                    file: "fresh.svg".to_string()
+                },
+                AssetChange::Removed {
+                    file: "gone.svg".to_string()
+                },
+                AssetChange::Changed {
+                    old: "swatch_aaaa.svg".to_string(),
+                    new: "swatch_bbbb.svg".to_string()
+                },
+            ]
+        );
+        assert_eq!(diff.counts(), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_diff_without_manifest_compares_files() {
+        let old = TempDir::new().unwrap();
+        let new = TempDir::new().unwrap();
+        fs::write(old.path().join("a.svg"), "<svg>1</svg>").unwrap();
+        fs::write(new.path().join("a.svg"), "<svg>2</svg>").unwrap();
+        fs::write(new.path().join("notes.txt"), "ignored").unwrap();
+
+        let diff = diff(old.path(), new.path()).unwrap();
+        assert_eq!(
+            diff.changes,
+            vec![AssetChange::Changed {
+                old: "a.svg".to_string(),
+                new: "a.svg".to_string()
+            }]
+        );
+        assert!(super::diff(old.path(), &old.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_preview_embeds_both_sides() {
+        let old = TempDir::new().unwrap();
+        let new = TempDir::new().unwrap();
+        fs::write(old.path().join("a.svg"), "<svg fill=\"#F00\">1</svg>").unwrap();
+        fs::write(new.path().join("a.svg"), "<svg>2</svg>").unwrap();
+
+        let diff = diff(old.path(), new.path()).unwrap();
+        let html = preview_html(&diff, old.path(), new.path()).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("1 changed, 0 added, 0 removed, 0 unchanged"));
+        assert_eq!(html.matches("<img src=\"data:image/svg+xml").count(), 2);
+        assert!(html.contains("fill=&quot;%23F00&quot;"));
+    }
+}
@@ -0,0 +1,477 @@
+//! Template syntax tree
+//!
+//! [`parse`] turns markdown into a list of [`Node`]s: plain text, code that
+//! the parser leaves untouched, and one node per template with its source
+//! range. Nodes are syntactic — names are not checked against the registry,
+//! so an unknown style still parses and only fails when rendered.
+//!
+//! [`to_source`] writes nodes back as template markup, and
+//! [`TemplateParser::render_ast`](crate::TemplateParser::render_ast) renders
+//! them, so tools can parse, rewrite, and render without re-implementing the
+//! template grammar.
+//!
+//! ```
+//! use mdfx::ast::{self, NodeKind};
+//!
+//! let nodes = ast::parse("# {{mathbold}}Title{{/mathbold}} {{ui:tech:rust/}}").unwrap();
+//!
+//! assert!(matches!(&nodes[1].kind, NodeKind::Style { style, .. } if style == "mathbold"));
+//! assert_eq!(nodes[1].range, 2..32);
+//! assert!(matches!(&nodes[3].kind, NodeKind::Component { name, .. } if name == "tech"));
+//! ```
+
+use crate::converter::Fallback;
+use crate::error::Result;
+use crate::parser::TemplateParser;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// A node in the template syntax tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    /// What the node is
+    pub kind: NodeKind,
+    /// Byte range in the parsed text
+    ///
+    /// Ranges point into the text after `{{//}}` close-all tags have been
+    /// expanded; for input without `{{//}}` that is the original text.
+    pub range: Range<usize>,
+}
+
+/// Node variants
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeKind {
+    /// Literal markdown
+    Text(String),
+    /// Fenced code block, inline code span, or HTML block (comment, `<pre>`,
+    /// `<script>`), kept verbatim including fences
+    Code(String),
+    /// `\{{` or `{{raw}}...{{/raw}}`: text emitted as written, without the
+    /// escape
+    Raw(String),
+    /// `{{!-- text --}}`, removed from the output
+    Comment(String),
+    /// Inline code span whose templates are processed, because they are
+    /// opted in with `{{!name...}}` or
+    /// [`ParserOptions::process_inline_code`](crate::ParserOptions::process_inline_code)
+    /// is set
+    ///
+    /// Children hold the span's text, backticks included, and its templates.
+    InlineCode(Vec<Node>),
+    /// `{{style:spacing=N:separator=S:fallback=P:seed=N}}content{{/style}}`
+    ///
+    /// Style content is converted character by character, not parsed.
+    Style {
+        style: String,
+        spacing: usize,
+        separator: Option<String>,
+        /// Policy for characters the style has no equivalent for, if set
+        fallback: Option<Fallback>,
+        /// Seed for styles with random marks, if set
+        seed: Option<u64>,
+        content: String,
+    },
+    /// `{{frame:spec}}...{{/frame}}`, `{{fr:spec}}...{{/}}`, or `{{fr:spec:text/}}`
+    Frame { spec: String, children: Vec<Node> },
+    /// `{{ui:name:args/}}` or `{{ui:name:args}}...{{/ui}}`
+    Component {
+        name: String,
+        args: Vec<String>,
+        /// `None` for self-closing components
+        children: Option<Vec<Node>>,
+    },
+    /// `{{shields:type:key=value/}}`
+    Shield {
+        shield_type: String,
+        params: BTreeMap<String, String>,
+    },
+    /// `{{glyph:name/}}`
+    Glyph { name: String },
+    /// `{{kbd:keys/}}`
+    Kbd { keys: String },
+    /// `{{num:format:value/}}`, with format and value as written
+    Number { format: String, value: String },
+    /// `{{t:key/}}`, a translated string
+    Translation { key: String },
+    /// `{{snippet:path:region=name:lang=lang/}}`, a region of a source file
+    Snippet {
+        path: String,
+        region: Option<String>,
+        lang: Option<String>,
+    },
+    /// `{{partial:name}}...{{/partial}}`, only for partials known to the parser
+    Partial { name: String, children: Vec<Node> },
+}
+
+impl Node {
+    /// Child nodes of frames, block components, partials, and inline code
+    pub fn children(&self) -> &[Node] {
+        match &self.kind {
+            NodeKind::Frame { children, .. }
+            | NodeKind::Partial { children, .. }
+            | NodeKind::InlineCode(children)
+            | NodeKind::Component {
+                children: Some(children),
+                ..
+            } => children,
+            _ => &[],
+        }
+    }
+
+    /// Mutable child nodes, `None` for nodes that can't have children
+    pub fn children_mut(&mut self) -> Option<&mut Vec<Node>> {
+        match &mut self.kind {
+            NodeKind::Frame { children, .. }
+            | NodeKind::Partial { children, .. }
+            | NodeKind::InlineCode(children)
+            | NodeKind::Component {
+                children: Some(children),
+                ..
+            } => Some(children),
+            _ => None,
+        }
+    }
+
+    /// Whether this node is a template (not text or code)
+    pub fn is_template(&self) -> bool {
+        !matches!(self.kind, NodeKind::Text(_) | NodeKind::Code(_))
+    }
+}
+
+/// Parse markdown into nodes using the built-in registry
+///
+/// Use [`TemplateParser::parse_ast`] to recognise partials from a config.
+pub fn parse(text: &str) -> Result<Vec<Node>> {
+    TemplateParser::new()?.parse_ast(text)
+}
+
+/// Visit every node depth-first, parents before children
+pub fn walk<'a>(nodes: &'a [Node], visit: &mut impl FnMut(&'a Node)) {
+    for node in nodes {
+        visit(node);
+        walk(node.children(), visit);
+    }
+}
+
+/// Write nodes back as template markup
+///
+/// Templates are written in their long form (`{{frame:...}}...{{/frame}}`,
+/// `{{/ui}}`), so shorthand closers in the input are normalised.
+pub fn to_source(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        write_node(&mut out, node);
+    }
+    out
+}
+
+fn write_node(out: &mut String, node: &Node) {
+    match &node.kind {
+        NodeKind::Text(text) | NodeKind::Code(text) => out.push_str(text),
+        NodeKind::Raw(text) => out.push_str(&format!("{{{{raw}}}}{}{{{{/raw}}}}", text)),
+        NodeKind::Comment(text) => out.push_str(&format!("{{{{!--{}--}}}}", text)),
+        NodeKind::InlineCode(children) => {
+            // Templates keep the `{{!` marker so they are processed again
+            for child in children {
+                if child.is_template() {
+                    let source = to_source(std::slice::from_ref(child));
+                    out.push_str("{{!");
+                    out.push_str(&source[2..]);
+                } else {
+                    write_node(out, child);
+                }
+            }
+        }
+        NodeKind::Style {
+            style,
+            spacing,
+            separator,
+            fallback,
+            seed,
+            content,
+        } => {
+            out.push_str("{{");
+            out.push_str(style);
+            if *spacing > 0 {
+                out.push_str(&format!(":spacing={}", spacing));
+            }
+            if let Some(sep) = separator {
+                out.push_str(&format!(":separator={}", sep));
+            }
+            if let Some(fallback) = fallback {
+                out.push_str(&format!(":fallback={}", fallback.as_str()));
+            }
+            if let Some(seed) = seed {
+                out.push_str(&format!(":seed={}", seed));
+            }
+            out.push_str(&format!("}}}}{}{{{{/{}}}}}", content, style));
+        }
+        NodeKind::Frame { spec, children } => {
+            out.push_str(&format!("{{{{frame:{}}}}}", spec));
+            out.push_str(&to_source(children));
+            out.push_str("{{/frame}}");
+        }
+        NodeKind::Component {
+            name,
+            args,
+            children,
+        } => {
+            out.push_str("{{ui:");
+            out.push_str(name);
+            for arg in args {
+                out.push(':');
+                out.push_str(arg);
+            }
+            match children {
+                Some(children) => {
+                    out.push_str("}}");
+                    out.push_str(&to_source(children));
+                    out.push_str("{{/ui}}");
+                }
+                None => out.push_str("/}}"),
+            }
+        }
+        NodeKind::Shield {
+            shield_type,
+            params,
+        } => {
+            out.push_str("{{shields:");
+            out.push_str(shield_type);
+            for (key, value) in params {
+                out.push_str(&format!(":{}={}", key, value));
+            }
+            out.push_str("/}}");
+        }
+        NodeKind::Glyph { name } => out.push_str(&format!("{{{{glyph:{}/}}}}", name)),
+        NodeKind::Kbd { keys } => out.push_str(&format!("{{{{kbd:{}/}}}}", keys)),
+        NodeKind::Number { format, value } => {
+            out.push_str(&format!("{{{{num:{}:{}/}}}}", format, value))
+        }
+        NodeKind::Translation { key } => out.push_str(&format!("{{{{t:{}/}}}}", key)),
+        NodeKind::Snippet { path, region, lang } => {
+            out.push_str("{{snippet:");
+            out.push_str(path);
+            if let Some(region) = region {
+                out.push_str(&format!(":region={}", region));
+            }
+            if let Some(lang) = lang {
+                out.push_str(&format!(":lang={}", lang));
+            }
+            out.push_str("/}}");
+        }
+        NodeKind::Partial { name, children } => {
+            if children.is_empty() {
+                out.push_str(&format!("{{{{partial:{}/}}}}", name));
+            } else {
+                out.push_str(&format!("{{{{partial:{}}}}}", name));
+                out.push_str(&to_source(children));
+                out.push_str("{{/partial}}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn kinds(nodes: &[Node]) -> Vec<&'static str> {
+        nodes
+            .iter()
+            .map(|n| match n.kind {
+                NodeKind::Text(_) => "text",
+                NodeKind::Code(_) => "code",
+                NodeKind::Raw(_) => "raw",
+                NodeKind::Comment(_) => "comment",
+                NodeKind::InlineCode(_) => "inline_code",
+                NodeKind::Style { .. } => "style",
+                NodeKind::Frame { .. } => "frame",
+                NodeKind::Component { .. } => "ui",
+                NodeKind::Shield { .. } => "shields",
+                NodeKind::Glyph { .. } => "glyph",
+                NodeKind::Kbd { .. } => "kbd",
+                NodeKind::Number { .. } => "num",
+                NodeKind::Translation { .. } => "t",
+                NodeKind::Snippet { .. } => "snippet",
+                NodeKind::Partial { .. } => "partial",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ranges_cover_source() {
+        let input = "Hi {{mathbold}}A{{/mathbold}} {{glyph:star/}}\n`{{x}}` end";
+        let nodes = parse(input).unwrap();
+
+        assert_eq!(
+            kinds(&nodes),
+            vec!["text", "style", "text", "glyph", "text", "code", "text"]
+        );
+        for node in &nodes {
+            if let NodeKind::Text(text) | NodeKind::Code(text) = &node.kind {
+                assert_eq!(&input[node.range.clone()], text);
+            }
+        }
+        assert_eq!(&input[nodes[1].range.clone()], "{{mathbold}}A{{/mathbold}}");
+        assert_eq!(&input[nodes[3].range.clone()], "{{glyph:star/}}");
+    }
+
+    #[test]
+    fn test_multibyte_ranges_are_byte_offsets() {
+        let input = "→ {{kbd:Ctrl+C/}}";
+        let nodes = parse(input).unwrap();
+        assert_eq!(nodes[1].range, 4..input.len());
+    }
+
+    #[test]
+    fn test_frame_children_ranges() {
+        let input = "{{frame:star}}x {{mathbold}}B{{/mathbold}}{{/frame}}";
+        let nodes = parse(input).unwrap();
+
+        assert_eq!(nodes.len(), 1);
+        let children = nodes[0].children();
+        assert_eq!(kinds(children), vec!["text", "style"]);
+        assert_eq!(
+            &input[children[1].range.clone()],
+            "{{mathbold}}B{{/mathbold}}"
+        );
+    }
+
+    #[test]
+    fn test_fenced_code_is_opaque() {
+        let input = "```\n{{ui:tech:rust/}}\n```\n{{ui:tech:go/}}";
+        let nodes = parse(input).unwrap();
+
+        assert_eq!(kinds(&nodes), vec!["code", "ui"]);
+        assert_eq!(
+            nodes[0].kind,
+            NodeKind::Code("```\n{{ui:tech:rust/}}\n```\n".into())
+        );
+    }
+
+    #[test]
+    fn test_component_block_and_args() {
+        let nodes = parse("{{ui:row:align=center}}{{ui:tech:rust/}}{{/ui}}").unwrap();
+        let NodeKind::Component {
+            name,
+            args,
+            children,
+        } = &nodes[0].kind
+        else {
+            panic!("expected component");
+        };
+        assert_eq!(name, "row");
+        assert_eq!(args, &vec!["align=center".to_string()]);
+        assert_eq!(kinds(children.as_deref().unwrap()), vec!["ui"]);
+    }
+
+    #[test]
+    fn test_unknown_style_still_parses() {
+        let nodes = parse("{{nosuchstyle}}A{{/nosuchstyle}}").unwrap();
+        assert_eq!(kinds(&nodes), vec!["style"]);
+    }
+
+    #[test]
+    fn test_unclosed_tag_is_error() {
+        assert!(parse("{{mathbold}}A").is_err());
+    }
+
+    #[test]
+    fn test_walk_visits_nested() {
+        let nodes = parse("{{fr:star}}{{fr:dot}}{{glyph:star/}}{{/}}{{/}}").unwrap();
+        let mut seen = Vec::new();
+        walk(&nodes, &mut |n| seen.extend(kinds(std::slice::from_ref(n))));
+        assert_eq!(seen, vec!["frame", "frame", "glyph"]);
+    }
+
+    #[rstest]
+    #[case("{{mathbold:spacing=1}}AB{{/mathbold}}")]
+    #[case("{{mathbold:separator=·:fallback=closest}}AB{{/mathbold}}")]
+    #[case("{{zalgo:seed=3}}AB{{/zalgo}}")]
+    #[case("{{frame:gradient}}Title{{/frame}}")]
+    #[case("{{ui:tech:rust/}} and {{shields:block:color=accent:style=flat/}}")]
+    #[case("{{kbd:Ctrl+C/}} {{glyph:star/}}")]
+    #[case("{{num:roman:2024/}} {{num:sup:007/}}")]
+    #[case("{{t:intro.title/}} {{t:see-also/}}")]
+    #[case("{{snippet:src/lib.rs/}} {{snippet:src/lib.rs:region=example:lang=rust/}}")]
+    fn test_to_source_round_trip(#[case] input: &str) {
+        let nodes = parse(input).unwrap();
+        assert_eq!(to_source(&nodes), input);
+    }
+
+    #[test]
+    fn test_to_source_normalises_closers() {
+        let nodes = parse("{{fr:star}}A{{/}}").unwrap();
+        assert_eq!(to_source(&nodes), "{{frame:star}}A{{/frame}}");
+    }
+
+    #[rstest]
+    #[case("# {{mathbold}}TITLE{{/mathbold}}\n\n```\n{{x}}\n```\n`{{y}}` {{fr:star}}Hi{{/}}\n")]
+    #[case("{{ui:tech:rust/}} {{glyph:star/}} {{kbd:Ctrl+C/}}")]
+    #[case("{{frame:star}}{{mathbold}}A{{/mathbold}}{{/frame}}")]
+    #[case("Step {{num:circled:3/}} of {{num:circled:99/}}")]
+    fn test_render_ast_matches_process(#[case] input: &str) {
+        let parser = TemplateParser::new().unwrap();
+        let nodes = parser.parse_ast(input).unwrap();
+        assert_eq!(
+            parser.render_ast(&nodes).unwrap().markdown,
+            parser.process(input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_escapes_parse_as_raw() {
+        let input = "\\{{mb}} {{raw}}{{ui:tech:rust/}}{{/raw}}";
+        let nodes = parse(input).unwrap();
+
+        assert_eq!(kinds(&nodes), vec!["raw", "text", "raw"]);
+        assert_eq!(nodes[0].kind, NodeKind::Raw("{{".into()));
+        assert_eq!(
+            &input[nodes[2].range.clone()],
+            "{{raw}}{{ui:tech:rust/}}{{/raw}}"
+        );
+        assert_eq!(
+            to_source(&nodes),
+            "{{raw}}{{{{/raw}}mb}} {{raw}}{{ui:tech:rust/}}{{/raw}}"
+        );
+    }
+
+    #[test]
+    fn test_forced_inline_code() {
+        let input = "`{{mb}}a{{/mb}} {{!mb}}b{{/mb}}` `{{mb}}c{{/mb}}`";
+        let nodes = parse(input).unwrap();
+
+        assert_eq!(kinds(&nodes), vec!["inline_code", "text", "code"]);
+        assert_eq!(kinds(nodes[0].children()), vec!["code", "style", "code"]);
+        assert_eq!(
+            &input[nodes[0].children()[1].range.clone()],
+            "{{!mb}}b{{/mb}}"
+        );
+        assert_eq!(to_source(&nodes), input);
+    }
+
+    #[test]
+    fn test_comment_node() {
+        let input = "a {{!-- {{mb}} note --}}b";
+        let nodes = parse(input).unwrap();
+
+        assert_eq!(kinds(&nodes), vec!["text", "comment", "text"]);
+        assert_eq!(nodes[1].kind, NodeKind::Comment(" {{mb}} note ".into()));
+        assert_eq!(&input[nodes[1].range.clone()], "{{!-- {{mb}} note --}}");
+        assert_eq!(to_source(&nodes), input);
+    }
+
+    #[test]
+    fn test_render_edited_ast() {
+        let parser = TemplateParser::new().unwrap();
+        let mut nodes = parser.parse_ast("{{mathbold}}A{{/mathbold}}").unwrap();
+        if let NodeKind::Style { style, .. } = &mut nodes[0].kind {
+            *style = "script".to_string();
+        }
+        assert_eq!(
+            parser.render_ast(&nodes).unwrap().markdown,
+            parser.process("{{script}}A{{/script}}").unwrap()
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! Keep-a-Changelog parsing
+//!
+//! Finds the most recent release in a `CHANGELOG.md` written in the
+//! [Keep a Changelog](https://keepachangelog.com) format, for
+//! `{{ui:latest-release/}}` and `mdfx gen changelog`.
+//!
+//! ```
+//! use mdfx::changelog::latest_release;
+//!
+//! let changelog = "# Changelog\n\n## [Unreleased]\n\n## [1.2.0] - 2024-05-01\n\n### Added\n\n- Dark mode\n";
+//! let release = latest_release(changelog).unwrap();
+//!
+//! assert_eq!(release.version, "1.2.0");
+//! assert_eq!(release.date.as_deref(), Some("2024-05-01"));
+//! assert_eq!(release.notes, "### Added\n\n- Dark mode");
+//! ```
+
+/// A release section of a changelog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    /// Version from the heading, without brackets or a `v` prefix
+    pub version: String,
+    /// Release date (`YYYY-MM-DD`), if the heading has one
+    pub date: Option<String>,
+    /// Markdown between this heading and the next release
+    pub notes: String,
+}
+
+impl Release {
+    /// The notes cut down to the first `items` entries (0 keeps all)
+    ///
+    /// Entries are top-level list items; `### Added`-style subheadings
+    /// become bold lines and are dropped when none of their entries fit.
+    pub fn summary(&self, items: usize) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        let mut heading = None;
+        let mut kept = 0;
+        let mut keeping = false;
+
+        for line in self.notes.lines() {
+            if let Some(title) = line.strip_prefix("### ") {
+                heading = Some(format!("**{}**", title.trim()));
+                keeping = false;
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            // Indented lines continue the entry above them
+            if !line.starts_with(char::is_whitespace) {
+                keeping = items == 0 || kept < items;
+                if keeping {
+                    kept += 1;
+                    if let Some(heading) = heading.take() {
+                        if !lines.is_empty() {
+                            lines.push(String::new());
+                        }
+                        lines.push(heading);
+                        lines.push(String::new());
+                    }
+                }
+            }
+            if keeping {
+                lines.push(line.to_string());
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// The first released version in a Keep-a-Changelog file
+///
+/// `## [Unreleased]` and level-2 headings that don't start with a version
+/// are skipped. Accepts `## [1.2.0] - 2024-05-01`, `## 1.2.0 (2024-05-01)`,
+/// `## v1.2.0` and linked versions like `## [1.2.0](https://...) - ...`.
+pub fn latest_release(changelog: &str) -> Option<Release> {
+    let mut lines = changelog.lines();
+
+    let (version, date) = lines.by_ref().find_map(|line| {
+        let heading = line.strip_prefix("## ")?.trim();
+        let version = parse_version(heading)?;
+        Some((version, find_date(heading)))
+    })?;
+
+    let notes: Vec<&str> = lines
+        .take_while(|line| !line.starts_with("## ") && !line.starts_with("# "))
+        .filter(|line| !is_link_definition(line))
+        .collect();
+
+    Some(Release {
+        version,
+        date,
+        notes: notes.join("\n").trim().to_string(),
+    })
+}
+
+/// Version at the start of a release heading
+fn parse_version(heading: &str) -> Option<String> {
+    let heading = heading.strip_prefix('[').unwrap_or(heading);
+    let token = heading
+        .split(|c: char| c == ']' || c == '(' || c.is_whitespace())
+        .next()?;
+    let version = token
+        .strip_prefix(['v', 'V'])
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(token);
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| version.to_string())
+}
+
+/// Whether a line is a link reference like `[1.2.0]: https://...`
+fn is_link_definition(line: &str) -> bool {
+    line.strip_prefix('[')
+        .and_then(|rest| rest.split_once("]:"))
+        .is_some_and(|(label, _)| !label.is_empty())
+}
+
+/// First `YYYY-MM-DD` date in a heading
+fn find_date(heading: &str) -> Option<String> {
+    let bytes = heading.as_bytes();
+    (0..bytes.len().saturating_sub(9)).find_map(|start| {
+        let candidate = &bytes[start..start + 10];
+        let is_date = candidate.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        });
+        is_date.then(|| heading[start..start + 10].to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const CHANGELOG: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+## [Unreleased]
+
+- Work in progress
+
+## [1.2.0] - 2024-05-01
+
+### Added
+
+- Dark mode
+- Steps component
+  with connecting lines
+
+### Fixed
+
+- Crash on empty input
+
+## [1.1.0] - 2024-03-10
+
+- Older change
+
+[1.2.0]: https://example.com/compare/v1.1.0...v1.2.0
+";
+
+    #[test]
+    fn test_latest_release_skips_unreleased() {
+        let release = latest_release(CHANGELOG).unwrap();
+        assert_eq!(release.version, "1.2.0");
+        assert_eq!(release.date.as_deref(), Some("2024-05-01"));
+        assert!(release.notes.starts_with("### Added"));
+        assert!(release.notes.ends_with("- Crash on empty input"));
+    }
+
+    #[rstest]
+    #[case("## [1.2.0] - 2024-05-01", "1.2.0", Some("2024-05-01"))]
+    #[case("## 1.2.0 (2024-05-01)", "1.2.0", Some("2024-05-01"))]
+    #[case("## v2.0.0-beta.1", "2.0.0-beta.1", None)]
+    #[case(
+        "## [0.3.1](https://example.com/v0.3.1) - 2023-12-24",
+        "0.3.1",
+        Some("2023-12-24")
+    )]
+    fn test_latest_release_headings(
+        #[case] heading: &str,
+        #[case] version: &str,
+        #[case] date: Option<&str>,
+    ) {
+        let release = latest_release(&format!("## Notes\n\n{}\n\n- Change", heading)).unwrap();
+        assert_eq!(release.version, version);
+        assert_eq!(release.date.as_deref(), date);
+        assert_eq!(release.notes, "- Change");
+    }
+
+    #[test]
+    fn test_latest_release_drops_link_definitions() {
+        let release =
+            latest_release("## [1.0.0]\n\n- First\n\n[1.0.0]: https://example.com/v1.0.0\n")
+                .unwrap();
+        assert_eq!(release.notes, "- First");
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("# Changelog\n\n## [Unreleased]\n\n- Soon\n")]
+    fn test_latest_release_none(#[case] changelog: &str) {
+        assert_eq!(latest_release(changelog), None);
+    }
+
+    #[rstest]
+    #[case(0, "**Added**\n\n- Dark mode\n- Steps component\n  with connecting lines\n\n**Fixed**\n\n- Crash on empty input")]
+    #[case(
+        2,
+        "**Added**\n\n- Dark mode\n- Steps component\n  with connecting lines"
+    )]
+    #[case(1, "**Added**\n\n- Dark mode")]
+    fn test_summary_limits_entries(#[case] items: usize, #[case] expected: &str) {
+        let release = latest_release(CHANGELOG).unwrap();
+        assert_eq!(release.summary(items), expected);
+    }
+}
@@ -0,0 +1,269 @@
+//! CommonMark-aware protection of markdown syntax
+//!
+//! The template parser treats markdown as text, so a style or frame around
+//! a link also restyles its URL, and a template-looking string in an HTML
+//! attribute is expanded. With
+//! [`ParserOptions::commonmark`](crate::ParserOptions::commonmark), the
+//! document is first parsed with pulldown-cmark and [`protected_ranges`]
+//! are left as written:
+//!
+//! - link and image destinations (`(url "title")`, `[label]`, `<url>`),
+//!   and the whole of links written as a bare reference (`[label]`)
+//! - reference definitions (`[label]: url`)
+//! - HTML tags and comments, inline or in HTML blocks; text between the
+//!   tags of an HTML block is still processed
+//!
+//! ```
+//! use mdfx::commonmark::protected_ranges;
+//!
+//! let markdown = "[docs](https://docs.rs) <b>bold</b>";
+//! let ranges: Vec<&str> = protected_ranges(markdown)
+//!     .into_iter()
+//!     .map(|r| &markdown[r])
+//!     .collect();
+//! assert_eq!(ranges, ["(https://docs.rs)", "<b>", "</b>"]);
+//! ```
+
+use lazy_static::lazy_static;
+use pulldown_cmark::{Event, LinkType, Options, Parser, Tag};
+use regex::Regex;
+use std::ops::Range;
+
+lazy_static! {
+    /// An HTML comment or tag, with quoted attribute values that may hold `>`
+    static ref HTML_TAG: Regex = Regex::new(
+        r#"<!--[\s\S]*?-->|</?[A-Za-z][A-Za-z0-9-]*(?:\s+[^\s"'>/=]+(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s"'=<>`]+))?)*\s*/?>"#
+    )
+    .unwrap();
+}
+
+/// First private-use character standing in for a protected range
+const PLACEHOLDER_BASE: u32 = 0xF0000;
+
+/// Number of placeholder characters (Supplementary Private Use Area-A)
+const PLACEHOLDER_COUNT: usize = 0xFFFE;
+
+/// Byte ranges of `markdown` that templates must not touch, sorted and
+/// without overlaps
+pub fn protected_ranges(markdown: &str) -> Vec<Range<usize>> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+    let parser = Parser::new_ext(markdown, options).into_offset_iter();
+
+    let mut ranges: Vec<Range<usize>> = parser
+        .reference_definitions()
+        .iter()
+        .map(|(_, def)| def.span.clone())
+        .collect();
+
+    for (event, range) in parser {
+        match event {
+            Event::Start(Tag::Link { link_type, .. } | Tag::Image { link_type, .. }) => {
+                if let Some(target) = link_target(markdown, range, link_type) {
+                    ranges.push(target);
+                }
+            }
+            Event::InlineHtml(_) => ranges.push(range),
+            Event::Html(_) => ranges.extend(
+                HTML_TAG
+                    .find_iter(&markdown[range.clone()])
+                    .map(|tag| range.start + tag.start()..range.start + tag.end()),
+            ),
+            _ => {}
+        }
+    }
+
+    ranges.retain(|r| !r.is_empty());
+    ranges.sort_by_key(|r| (r.start, std::cmp::Reverse(r.end)));
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start < last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// The part of a link or image at `range` that must stay as written
+///
+/// That is everything after the link text for inline and full reference
+/// links, and the whole link when its text is also its label or URL.
+fn link_target(markdown: &str, range: Range<usize>, link_type: LinkType) -> Option<Range<usize>> {
+    match link_type {
+        LinkType::Inline | LinkType::Reference | LinkType::ReferenceUnknown => {
+            let source = &markdown[range.clone()];
+            let open = source.find('[')?;
+            let close = matching_bracket(source, open)?;
+            Some(range.start + close + 1..range.end)
+        }
+        _ => Some(range),
+    }
+}
+
+/// Index of the `]` closing the `[` at `open`, skipping escapes and code
+/// spans
+fn matching_bracket(source: &str, open: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'`' => {
+                let run = source[i..].len() - source[i..].trim_start_matches('`').len();
+                let fence = &source[i..i + run];
+                match source[i + run..].find(fence) {
+                    Some(p) => i += run + p + run - 1,
+                    None => i += run - 1,
+                }
+            }
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Markdown with its protected ranges replaced by private-use placeholder
+/// characters, which templates pass through unchanged
+pub(crate) struct Masked<'a> {
+    /// The markdown to process
+    pub text: String,
+    /// Original text of each placeholder, by index
+    originals: Vec<&'a str>,
+    /// Range of the markdown each placeholder replaced, by index
+    ranges: Vec<Range<usize>>,
+}
+
+impl<'a> Masked<'a> {
+    /// Mask the protected ranges of `markdown`
+    ///
+    /// Returns `None` when there is nothing to protect, or when the markdown
+    /// already uses the placeholder characters.
+    pub fn new(markdown: &'a str) -> Option<Self> {
+        if markdown.chars().any(is_placeholder) {
+            return None;
+        }
+        let ranges = protected_ranges(markdown);
+        if ranges.is_empty() {
+            return None;
+        }
+
+        let mut text = String::with_capacity(markdown.len());
+        let mut originals = Vec::new();
+        let mut last = 0;
+        let ranges: Vec<_> = ranges.into_iter().take(PLACEHOLDER_COUNT).collect();
+        for range in &ranges {
+            text.push_str(&markdown[last..range.start]);
+            text.extend(char::from_u32(PLACEHOLDER_BASE + originals.len() as u32));
+            originals.push(&markdown[range.clone()]);
+            last = range.end;
+        }
+        text.push_str(&markdown[last..]);
+        Some(Self {
+            text,
+            originals,
+            ranges,
+        })
+    }
+
+    /// Byte offset in the original markdown of each byte of `text`, plus
+    /// its end; a placeholder's bytes map to the start of what it replaced
+    pub fn source_offsets(&self) -> Vec<usize> {
+        let mut offsets = Vec::with_capacity(self.text.len() + 1);
+        let mut last = 0;
+        for (index, range) in self.ranges.iter().enumerate() {
+            offsets.extend(last..range.start);
+            let placeholder =
+                char::from_u32(PLACEHOLDER_BASE + index as u32).map_or(0, char::len_utf8);
+            offsets.extend(std::iter::repeat_n(range.start, placeholder));
+            last = range.end;
+        }
+        let rest = self.text.len() - offsets.len();
+        offsets.extend(last..=last + rest);
+        offsets
+    }
+
+    /// Put the original text back in place of the placeholders in `output`
+    pub fn restore(&self, output: &str) -> String {
+        let mut restored = String::with_capacity(output.len());
+        for c in output.chars() {
+            let original = is_placeholder(c)
+                .then(|| self.originals.get((c as u32 - PLACEHOLDER_BASE) as usize))
+                .flatten();
+            match original {
+                Some(original) => restored.push_str(original),
+                None => restored.push(c),
+            }
+        }
+        restored
+    }
+}
+
+/// Whether `c` is a placeholder character
+fn is_placeholder(c: char) -> bool {
+    (PLACEHOLDER_BASE..PLACEHOLDER_BASE + PLACEHOLDER_COUNT as u32).contains(&(c as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn protected(markdown: &str) -> Vec<&str> {
+        protected_ranges(markdown)
+            .into_iter()
+            .map(|r| &markdown[r])
+            .collect()
+    }
+
+    #[rstest]
+    #[case("[docs](https://docs.rs \"Docs\")", &["(https://docs.rs \"Docs\")"])]
+    #[case("![logo](logo.svg)", &["(logo.svg)"])]
+    #[case("[![ci](ci.svg)](https://ci.example)", &["(ci.svg)", "(https://ci.example)"])]
+    #[case("[a `]` b](x.md)", &["(x.md)"])]
+    #[case("[docs][ref]\n\n[ref]: https://x.dev", &["[ref]", "[ref]: https://x.dev"])]
+    #[case("[ref]\n\n[ref]: https://x.dev", &["[ref]", "[ref]: https://x.dev"])]
+    #[case("<https://x.dev>", &["<https://x.dev>"])]
+    #[case("a <a href=\"{{x}}\">b</a>", &["<a href=\"{{x}}\">", "</a>"])]
+    #[case("<!-- {{mathbold}}x{{/mathbold}} -->", &["<!-- {{mathbold}}x{{/mathbold}} -->"])]
+    #[case("`[code](x)`", &[])]
+    #[case("[not a link]", &[])]
+    fn test_protected_ranges(#[case] markdown: &str, #[case] expected: &[&str]) {
+        assert_eq!(protected(markdown), expected);
+    }
+
+    #[test]
+    fn test_html_block_text_not_protected() {
+        let markdown = "<p align=\"center\">\n  {{ui:tech:rust/}}\n</p>\n";
+        assert_eq!(protected(markdown), ["<p align=\"center\">", "</p>"]);
+    }
+
+    #[test]
+    fn test_mask_round_trip() {
+        let markdown = "See [docs](https://docs.rs) and <b>this</b>.";
+        let masked = Masked::new(markdown).unwrap();
+        assert_eq!(
+            masked.text.chars().filter(|c| is_placeholder(*c)).count(),
+            3
+        );
+        assert!(!masked.text.contains("https"));
+        assert_eq!(masked.restore(&masked.text), markdown);
+    }
+
+    #[test]
+    fn test_mask_nothing_to_protect() {
+        assert!(Masked::new("plain {{mathbold}}text{{/mathbold}}").is_none());
+        assert!(Masked::new("[a](b) \u{F0000}").is_none());
+    }
+}
@@ -0,0 +1,481 @@
+//! Target compatibility lint
+//!
+//! Rendered markdown can contain constructs a target silently drops: raw
+//! HTML on PyPI, `data:` URIs behind GitHub's image proxy, very long heading
+//! anchors on GitLab. [`lint_compat`] checks a document against a
+//! [`Target`]'s capabilities and reports each problem with a rule code from
+//! [`RULES`].
+//!
+//! Problems can be suppressed with HTML comments, by code or rule name; with
+//! no rules listed, every rule is suppressed:
+//!
+//! ```markdown
+//! <!-- mdfx-lint-disable-next-line raw-html -->
+//! <img src="logo.png">
+//!
+//! <!-- mdfx-lint-disable C005 -->
+//! 𝐒𝐭𝐲𝐥𝐞𝐝 𝐭𝐞𝐱𝐭 kept on purpose
+//! <!-- mdfx-lint-enable C005 -->
+//! ```
+//!
+//! ```
+//! use mdfx::compat::lint_compat;
+//! use mdfx::get_target;
+//!
+//! let pypi = get_target("pypi").unwrap();
+//! let issues = lint_compat("<b>Fast</b> parser\n", pypi.as_ref());
+//!
+//! assert_eq!(issues[0].code, "C001");
+//! assert_eq!(issues[0].rule, "raw-html");
+//! ```
+
+use crate::converter::Converter;
+use crate::diagnostics::Severity;
+use crate::links::{atx_heading, prose_lines, slugify, url_host, ProseLine};
+use crate::targets::Target;
+use crate::width::display_width;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+
+lazy_static! {
+    static ref SUPPRESSION: Regex =
+        Regex::new(r"<!--\s*mdfx-lint-(disable-next-line|disable|enable)((?:\s+[\w-]+)*)\s*-->")
+            .unwrap();
+    static ref HTML_COMMENT: Regex = Regex::new(r"<!--.*?-->").unwrap();
+    /// Opening or self-closing tag; `<https://...>` and `<a@b.c>` autolinks don't match
+    static ref HTML_TAG: Regex = Regex::new(r"<([A-Za-z][A-Za-z0-9-]*)(?:\s[^>]*)?/?>").unwrap();
+    static ref DATA_URI: Regex =
+        Regex::new(r#"(?i)(?:\]\(\s*<?|\b(?:src|href)\s*=\s*["']?)(data:)"#).unwrap();
+    static ref MARKDOWN_IMAGE: Regex = Regex::new(r"!\[[^\]]*\]\(\s*<?([^)\s>]+)").unwrap();
+    static ref HTML_IMAGE: Regex =
+        Regex::new(r#"(?i)<img\b[^>]*?\bsrc\s*=\s*["']([^"']+)"#).unwrap();
+    /// Single-character suffixes of combining-mark styles
+    static ref STYLE_SUFFIXES: HashSet<char> = Converter::new()
+        .map(|converter| {
+            converter
+                .list_styles()
+                .iter()
+                .filter_map(|style| style.suffix.as_deref()?.chars().next())
+                .collect()
+        })
+        .unwrap_or_default();
+}
+
+/// A compatibility rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CompatRule {
+    /// Stable code, e.g. `C001`
+    pub code: &'static str,
+    /// Rule name, e.g. `raw-html`
+    pub name: &'static str,
+    /// Severity of problems found by the rule
+    pub severity: Severity,
+    /// What the rule checks
+    pub summary: &'static str,
+}
+
+/// All compatibility rules, in code order
+pub const RULES: &[CompatRule] = &[
+    CompatRule {
+        code: "C001",
+        name: "raw-html",
+        severity: Severity::Error,
+        summary: "HTML tags the target strips",
+    },
+    CompatRule {
+        code: "C002",
+        name: "data-uri",
+        severity: Severity::Error,
+        summary: "data: URIs on targets that don't render them",
+    },
+    CompatRule {
+        code: "C003",
+        name: "external-image",
+        severity: Severity::Warning,
+        summary: "External images on targets that prefer local assets",
+    },
+    CompatRule {
+        code: "C004",
+        name: "svg-image",
+        severity: Severity::Error,
+        summary: "SVG images on targets that don't display SVG",
+    },
+    CompatRule {
+        code: "C005",
+        name: "unicode-styling",
+        severity: Severity::Warning,
+        summary: "Styled Unicode text on targets with limited Unicode support",
+    },
+    CompatRule {
+        code: "C006",
+        name: "long-anchor",
+        severity: Severity::Warning,
+        summary: "Heading anchors longer than the target links reliably",
+    },
+    CompatRule {
+        code: "C007",
+        name: "line-length",
+        severity: Severity::Warning,
+        summary: "Lines wider than the target's maximum line length",
+    },
+    CompatRule {
+        code: "C008",
+        name: "untrusted-image-host",
+        severity: Severity::Warning,
+        summary: "Images from hosts the target may not display",
+    },
+    CompatRule {
+        code: "C009",
+        name: "combining-marks",
+        severity: Severity::Warning,
+        summary: "Combining-mark styles on targets that don't render them",
+    },
+];
+
+/// Look up a rule by code or name
+pub fn find_rule(code_or_name: &str) -> Option<&'static CompatRule> {
+    RULES
+        .iter()
+        .find(|r| r.code.eq_ignore_ascii_case(code_or_name) || r.name == code_or_name)
+}
+
+/// A construct the target can't render
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompatIssue {
+    /// Rule code, e.g. `C001`
+    pub code: &'static str,
+    /// Rule name, e.g. `raw-html`
+    pub rule: &'static str,
+    /// Severity of the rule
+    pub severity: Severity,
+    /// 1-based line
+    pub line: usize,
+    /// 1-based column (in characters)
+    pub column: usize,
+    /// What is wrong
+    pub message: String,
+}
+
+/// Which rules are suppressed
+#[derive(Default)]
+struct Suppressed {
+    all: bool,
+    codes: HashSet<&'static str>,
+}
+
+impl Suppressed {
+    fn contains(&self, code: &str) -> bool {
+        self.all || self.codes.contains(code)
+    }
+
+    /// Apply the rule list of a comment; an empty list means every rule
+    fn update(&mut self, rules: &str, enable: bool) {
+        let codes: Vec<_> = rules
+            .split_whitespace()
+            .filter_map(find_rule)
+            .map(|r| r.code)
+            .collect();
+        match (codes.is_empty(), enable) {
+            (true, false) => self.all = true,
+            (true, true) => *self = Self::default(),
+            (false, false) => self.codes.extend(codes),
+            (false, true) => {
+                for code in codes {
+                    self.codes.remove(code);
+                }
+            }
+        }
+    }
+}
+
+/// Check `markdown` for constructs `target` can't render
+///
+/// Fenced code blocks and inline code are skipped.
+pub fn lint_compat(markdown: &str, target: &dyn Target) -> Vec<CompatIssue> {
+    let mut issues = Vec::new();
+    let mut suppressed = Suppressed::default();
+    let mut next_line: Option<(usize, Suppressed)> = None;
+
+    for line in prose_lines(markdown) {
+        let line_no = line.number;
+        let line_suppressed = match next_line.take() {
+            Some((target_line, rules)) if target_line == line_no => Some(rules),
+            _ => None,
+        };
+        for caps in SUPPRESSION.captures_iter(&line.prose) {
+            let rules = caps.get(2).map_or("", |m| m.as_str());
+            match &caps[1] {
+                "disable-next-line" => {
+                    let mut rules_for_line = Suppressed::default();
+                    rules_for_line.update(rules, false);
+                    next_line = Some((line_no + 1, rules_for_line));
+                }
+                "disable" => suppressed.update(rules, false),
+                _ => suppressed.update(rules, true),
+            }
+        }
+
+        let mut report = |code: &'static str, start: usize, message: String| {
+            let rule = find_rule(code).expect("rule codes are defined in RULES");
+            if suppressed.contains(code)
+                || line_suppressed.as_ref().is_some_and(|s| s.contains(code))
+            {
+                return;
+            }
+            issues.push(CompatIssue {
+                code,
+                rule: rule.name,
+                severity: rule.severity,
+                line: line_no,
+                column: line.column(start),
+                message,
+            });
+        };
+
+        check_line(&line, target, &mut report);
+    }
+
+    issues
+}
+
+fn check_line(
+    line: &ProseLine,
+    target: &dyn Target,
+    report: &mut impl FnMut(&'static str, usize, String),
+) {
+    let name = target.name();
+    // Comments are invisible on every target
+    let without_comments = HTML_COMMENT.replace_all(&line.prose, |caps: &regex::Captures| {
+        " ".repeat(caps[0].len())
+    });
+    let text = without_comments.as_ref();
+
+    if !target.supports_html() {
+        let allowed = target.allowed_html_tags();
+        for caps in HTML_TAG.captures_iter(text) {
+            let tag = caps[1].to_ascii_lowercase();
+            if !allowed.contains(&tag.as_str()) {
+                let start = caps.get(0).unwrap().start();
+                report("C001", start, format!("<{}> is stripped by {}", tag, name));
+            }
+        }
+    }
+
+    if !target.supports_data_uris() {
+        for caps in DATA_URI.captures_iter(text) {
+            let start = caps.get(1).unwrap().start();
+            report(
+                "C002",
+                start,
+                format!("data: URIs are not rendered by {}", name),
+            );
+        }
+    }
+
+    let images = MARKDOWN_IMAGE
+        .captures_iter(text)
+        .chain(HTML_IMAGE.captures_iter(text))
+        .map(|caps| caps.get(1).unwrap());
+    for image in images {
+        let src = image.as_str();
+        let lower = src.to_ascii_lowercase();
+        let external = lower.starts_with("http://") || lower.starts_with("https://");
+        if external && !target.supports_external_images() {
+            report(
+                "C003",
+                image.start(),
+                format!("external image {}; {} prefers local assets", src, name),
+            );
+        }
+        if let Some(host) = url_host(src).filter(|h| !target.is_trusted_image_host(h)) {
+            report(
+                "C008",
+                image.start(),
+                format!("image from {} may not display on {}", host, name),
+            );
+        }
+        let path = lower.split(['?', '#']).next().unwrap_or_default();
+        if !target.supports_svg_embed() && path.ends_with(".svg") {
+            report(
+                "C004",
+                image.start(),
+                format!("SVG image {} is not displayed by {}", src, name),
+            );
+        }
+    }
+
+    if !target.supports_unicode_styling() {
+        if let Some((start, _)) = text.char_indices().find(|(_, c)| is_styled_char(*c)) {
+            report(
+                "C005",
+                start,
+                format!("styled Unicode text may not display on {}", name),
+            );
+        }
+    }
+
+    if !target.supports_combining_marks() {
+        if let Some(start) = combining_style_start(text) {
+            report(
+                "C009",
+                start,
+                format!(
+                    "strikethrough, underline, or zalgo text may not display on {}",
+                    name
+                ),
+            );
+        }
+    }
+
+    if let (Some(max), Some(heading)) = (target.max_anchor_length(), atx_heading(text)) {
+        let anchor = slugify(heading);
+        let length = anchor.chars().count();
+        if length > max {
+            let start = text.find(heading).unwrap_or(0);
+            report(
+                "C006",
+                start,
+                format!(
+                    "heading anchor is {} characters; {} links up to {}",
+                    length, name, max
+                ),
+            );
+        }
+    }
+
+    if let Some(max) = target.max_line_length() {
+        let width = display_width(line.text);
+        if width > max {
+            let start = line
+                .text
+                .char_indices()
+                .scan(0, |w, (i, c)| {
+                    *w += display_width(c.encode_utf8(&mut [0; 4]));
+                    Some((i, *w))
+                })
+                .find(|(_, w)| *w > max)
+                .map_or(0, |(i, _)| i);
+            report(
+                "C007",
+                start,
+                format!("line is {} columns; {} allows {}", width, name, max),
+            );
+        }
+    }
+}
+
+/// Characters produced by mdfx styles that ASCII-only clients can't show
+fn is_styled_char(c: char) -> bool {
+    matches!(c as u32,
+        0x1D400..=0x1D7FF // Mathematical alphanumerics (bold, script, fraktur, ...)
+        | 0xFF01..=0xFF5E // Fullwidth
+        | 0x2460..=0x24FF // Enclosed alphanumerics (circled)
+        | 0x1F130..=0x1F189 // Squared and negative letters
+        | 0x1D00..=0x1D2B // Small capitals
+    )
+}
+
+/// Start of the first character decorated by a combining-mark style
+///
+/// A style suffix (strikethrough, underline, overline) counts wherever it
+/// appears; other combining marks only in runs of two or more, which
+/// accented letters in decomposed form don't produce.
+fn combining_style_start(text: &str) -> Option<usize> {
+    let is_mark = |c: char| matches!(c as u32, 0x0300..=0x036F);
+    let mut base = None;
+    let mut run = 0;
+    for (i, c) in text.char_indices() {
+        if !is_mark(c) {
+            base = Some(i);
+            run = 0;
+            continue;
+        }
+        run += 1;
+        if run >= 2 || STYLE_SUFFIXES.contains(&c) {
+            return Some(base.unwrap_or(i));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::targets::get_target;
+    use rstest::rstest;
+
+    fn codes(markdown: &str, target: &str) -> Vec<&'static str> {
+        lint_compat(markdown, get_target(target).unwrap().as_ref())
+            .into_iter()
+            .map(|i| i.code)
+            .collect()
+    }
+
+    #[rstest]
+    #[case("<b>bold</b>", "pypi", vec!["C001"])]
+    #[case("<b>bold</b> <img src=\"https://x.io/a.png\">", "github", vec![])]
+    #[case("<marquee>hi</marquee>", "github", vec!["C001"])]
+    #[case("<marquee>hi</marquee>", "gitlab", vec![])]
+    #[case("<!-- comment --> <https://example.com>", "pypi", vec![])]
+    #[case("![](data:image/svg+xml;base64,AAAA)", "github", vec!["C002"])]
+    #[case("![](data:image/svg+xml;base64,AAAA)", "local", vec![])]
+    #[case("![](https://img.shields.io/a.svg)", "local", vec!["C003"])]
+    #[case("![](https://img.shields.io/a.svg)", "pypi", vec!["C004"])]
+    #[case("![](assets/logo.png)", "pypi", vec![])]
+    #[case("𝐁𝐨𝐥𝐝 title", "pypi", vec!["C005"])]
+    #[case("𝐁𝐨𝐥𝐝 title", "github", vec![])]
+    #[case("`<b>` and `𝐁` in code", "pypi", vec![])]
+    #[case("<picture><source srcset=\"a.svg\"></picture>", "crates-io", vec!["C001", "C001"])]
+    #[case("![](https://tracker.example.net/p.gif)", "crates-io", vec!["C008"])]
+    #[case("![](https://img.shields.io/badge/a-b-c)", "crates-io", vec![])]
+    #[case("Old: s\u{336}o\u{336}o\u{336}n\u{336}", "pypi", vec!["C009"])]
+    #[case("Old: s\u{336}o\u{336}o\u{336}n\u{336}", "github", vec![])]
+    #[case("z\u{30B}\u{317}a", "jira", vec!["C009"])]
+    #[case("Cafe\u{301}", "pypi", vec![])]
+    fn test_rules(#[case] markdown: &str, #[case] target: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(codes(markdown, target), expected);
+    }
+
+    #[test]
+    fn test_long_anchor_and_line_length() {
+        let heading = format!("## {}", "word ".repeat(25));
+        assert_eq!(codes(&heading, "gitlab"), vec!["C006"]);
+        assert_eq!(codes(&heading, "github"), Vec::<&str>::new());
+
+        let issues = lint_compat(&"x".repeat(90), get_target("pypi").unwrap().as_ref());
+        assert_eq!(issues[0].code, "C007");
+        assert_eq!(issues[0].column, 81);
+    }
+
+    #[test]
+    fn test_suppression_comments() {
+        let markdown = "\
+<!-- mdfx-lint-disable-next-line raw-html -->
+<b>one</b>
+<b>two</b>
+<!-- mdfx-lint-disable C001 -->
+<b>three</b> 𝐁
+<!-- mdfx-lint-enable -->
+<b>four</b>
+<!-- mdfx-lint-disable -->
+<b>five</b> 𝐁
+";
+        let issues = lint_compat(markdown, get_target("pypi").unwrap().as_ref());
+        let found: Vec<_> = issues.iter().map(|i| (i.line, i.code)).collect();
+
+        assert_eq!(found, vec![(3, "C001"), (5, "C005"), (7, "C001")]);
+    }
+
+    #[test]
+    fn test_issue_position_and_rule_lookup() {
+        let issues = lint_compat("Intro <u>x</u>", get_target("pypi").unwrap().as_ref());
+        assert_eq!((issues[0].line, issues[0].column), (1, 7));
+        assert_eq!(issues[0].severity, Severity::Error);
+
+        assert_eq!(find_rule("c002").unwrap().name, "data-uri");
+        assert_eq!(find_rule("long-anchor").unwrap().code, "C006");
+        assert!(find_rule("nope").is_none());
+    }
+}
@@ -0,0 +1,178 @@
+//! Benchmark badge component handler
+//!
+//! Shows a criterion benchmark's latest time, compared with its baseline
+//! when one exists, so performance numbers in a README follow `cargo bench`.
+
+use super::{get_string, metric_badge, parse_bool, parse_param, parse_param_clamped};
+use crate::components::ComponentOutput;
+use crate::criterion::{estimates_path, point_estimate, TimeUnit, DEFAULT_DIR};
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// Handle bench component expansion
+///
+/// Syntax: {{ui:bench:GROUP/FUNCTION/}}
+///
+/// - `format`: `ns`, `us`, `ms`, `s` or `auto`
+/// - `stat`: `mean`, `median` or `slope`
+/// - `baseline`: saved baseline to compare with (`base` is the previous run)
+/// - `compare`: `false` leaves out the change
+/// - `noise`: changes within this many percent count as unchanged
+pub fn handle(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    read_file: impl Fn(&str) -> Option<String>,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let id = args.first().ok_or_else(|| {
+        Error::ParseError(
+            "bench component requires a benchmark id, e.g. {{ui:bench:parser/process_large/}}"
+                .to_string(),
+        )
+    })?;
+    let dir = get_string(params, "dir", DEFAULT_DIR);
+    let stat = get_string(params, "stat", "mean");
+
+    let current_path = estimates_path(&dir, id, "new");
+    let current = read_file(&current_path).ok_or_else(|| {
+        Error::ParseError(format!(
+            "no criterion results for '{}' (run cargo bench; looked for {})",
+            id, current_path
+        ))
+    })?;
+    let current = point_estimate(&current, &stat)
+        .ok_or_else(|| Error::ParseError(format!("no {} estimate in {}", stat, current_path)))?;
+
+    let baseline = get_string(params, "baseline", "base");
+    let change = parse_bool(params, "compare", true)
+        .then(|| read_file(&estimates_path(&dir, id, &baseline)))
+        .flatten()
+        .and_then(|estimates| point_estimate(&estimates, &stat))
+        .filter(|base| *base > 0.0)
+        .map(|base| (current - base) / base * 100.0);
+
+    let unit = params
+        .get("format")
+        .and_then(|unit| TimeUnit::parse(unit))
+        .unwrap_or_else(|| TimeUnit::auto(current));
+    let precision: usize = parse_param_clamped(params, "precision", 2, 0, 4);
+    let mut value = unit.format(current, precision);
+
+    let noise: f64 = parse_param(params, "noise", 1.0);
+    let color = match change {
+        Some(change) => {
+            value.push_str(&format!(" ({:+.1}%)", change));
+            if change.abs() <= noise {
+                "gray"
+            } else if change < 0.0 {
+                "success"
+            } else {
+                "error"
+            }
+        }
+        None => "info",
+    };
+    let bg_color = resolve_color(params.get("bg").map_or(color, String::as_str));
+
+    let label = get_string(params, "label", id);
+    Ok(metric_badge(
+        format!("{}: {}", label, value),
+        bg_color,
+        params,
+        style,
+        resolve_color,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::Primitive;
+    use rstest::rstest;
+
+    fn identity_color(c: &str) -> String {
+        c.to_string()
+    }
+
+    fn estimates(mean: f64) -> String {
+        format!(
+            r#"{{"mean": {{"point_estimate": {}}}, "median": {{"point_estimate": {}}}, "slope": null}}"#,
+            mean,
+            mean * 0.9
+        )
+    }
+
+    fn bench(params_input: &[(&str, &str)], base: Option<f64>) -> (String, String) {
+        let params = params_input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let read_file = |path: &str| match path {
+            "target/criterion/parser/process_large/new/estimates.json" => {
+                Some(estimates(1_500_000.0))
+            }
+            "target/criterion/parser/process_large/base/estimates.json" => base.map(estimates),
+            "target/criterion/parser/process_large/v1/estimates.json" => {
+                Some(estimates(3_000_000.0))
+            }
+            _ => None,
+        };
+        let output = handle(
+            &["parser/process_large".to_string()],
+            &params,
+            "flat",
+            read_file,
+            identity_color,
+        )
+        .unwrap();
+        let ComponentOutput::Primitive(Primitive::Swatch { label, color, .. }) = output else {
+            panic!("Expected Swatch primitive");
+        };
+        (label.unwrap(), color)
+    }
+
+    #[rstest]
+    #[case(&[], None, "parser/process_large: 1.50 ms", "info")]
+    #[case(&[], Some(2_000_000.0), "parser/process_large: 1.50 ms (-25.0%)", "success")]
+    #[case(&[], Some(1_200_000.0), "parser/process_large: 1.50 ms (+25.0%)", "error")]
+    #[case(&[], Some(1_495_000.0), "parser/process_large: 1.50 ms (+0.3%)", "gray")]
+    #[case(&[("compare", "false")], Some(2_000_000.0), "parser/process_large: 1.50 ms", "info")]
+    #[case(&[("baseline", "v1")], None, "parser/process_large: 1.50 ms (-50.0%)", "success")]
+    #[case(&[("format", "us"), ("precision", "0"), ("label", "large doc")], None, "large doc: 1500 µs", "info")]
+    #[case(&[("stat", "median")], None, "parser/process_large: 1.35 ms", "info")]
+    #[case(&[("bg", "accent")], Some(1_200_000.0), "parser/process_large: 1.50 ms (+25.0%)", "accent")]
+    fn test_bench_badge(
+        #[case] params_input: &[(&str, &str)],
+        #[case] base: Option<f64>,
+        #[case] expected_label: &str,
+        #[case] expected_color: &str,
+    ) {
+        assert_eq!(
+            bench(params_input, base),
+            (expected_label.to_string(), expected_color.to_string())
+        );
+    }
+
+    #[rstest]
+    #[case(&[], &[], "requires a benchmark id")]
+    #[case(&["parser/missing"], &[], "no criterion results for 'parser/missing'")]
+    #[case(&["parser/process_large"], &[("stat", "slope")], "no slope estimate")]
+    fn test_bench_errors(
+        #[case] args: &[&str],
+        #[case] params_input: &[(&str, &str)],
+        #[case] message: &str,
+    ) {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let params = params_input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let read_file = |path: &str| {
+            (path == "target/criterion/parser/process_large/new/estimates.json")
+                .then(|| estimates(1000.0))
+        };
+        let err = handle(&args, &params, "flat", read_file, identity_color).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+}
@@ -0,0 +1,277 @@
+//! CI matrix component handler
+//!
+//! Shows the latest GitHub Actions result of several workflows at once, as a
+//! row of badges or as one combined badge, instead of one live badge per
+//! workflow. The workflows are fetched concurrently.
+
+use super::github::{PLACEHOLDER_VALUE, UNAVAILABLE_COLOR};
+use super::metric_badge;
+use super::tech_group::parse_layout;
+use crate::components::ComponentOutput;
+use crate::error::{Error, Result};
+use crate::primitive::Primitive;
+use std::collections::HashMap;
+
+/// Most workflows one matrix fetches
+const MAX_WORKFLOWS: usize = 20;
+
+/// Space between the badges of a row, unless `gap` is set
+const DEFAULT_GAP: u32 = 4;
+
+/// Latest conclusion and its color for one workflow, `None` if unavailable
+type Outcome = Option<(String, String)>;
+
+/// Handle ci-matrix component expansion
+///
+/// Syntax: {{ui:ci-matrix:owner/repo:workflows=test,lint,release/}}
+///
+/// - `workflows`: comma-separated workflow names (required)
+/// - `branch`: only runs on this branch
+/// - `display`: `row` (default) for a badge per workflow, or `badge` for
+///   one badge with a mark per workflow
+/// - `label`: text before the marks (badge)
+/// - `bg`: fixed color instead of the conclusion colors
+/// - `columns`, `gap`, `align`, ...: row layout, as for tech-group
+///
+/// `fetch` returns the conclusion and color of the latest run for an
+/// `actions` query (`owner/repo/workflow@branch`); it is called from one
+/// thread per workflow.
+pub fn handle(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    fetch: impl Fn(&str) -> Result<Outcome> + Sync,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let repo = args
+        .first()
+        .filter(|repo| {
+            repo.split_once('/').is_some_and(|(owner, name)| {
+                !owner.is_empty() && !name.is_empty() && !name.contains(['/', '@'])
+            })
+        })
+        .ok_or_else(|| Error::ParseError("ci-matrix component requires owner/repo".to_string()))?;
+    let workflows: Vec<&str> = params
+        .get("workflows")
+        .map(|list| {
+            list.split(',')
+                .map(str::trim)
+                .filter(|w| !w.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    if workflows.is_empty() {
+        return Err(Error::ParseError(
+            "ci-matrix component requires workflows=NAME,NAME".to_string(),
+        ));
+    }
+    if workflows.len() > MAX_WORKFLOWS {
+        return Err(Error::ParseError(format!(
+            "ci-matrix supports at most {} workflows",
+            MAX_WORKFLOWS
+        )));
+    }
+    if let Some(workflow) = workflows.iter().find(|w| w.contains(['/', '@'])) {
+        return Err(Error::ParseError(format!(
+            "Invalid workflow name '{}'",
+            workflow
+        )));
+    }
+    let branch = params
+        .get("branch")
+        .map(|branch| format!("@{}", branch))
+        .unwrap_or_default();
+
+    let display = params.get("display").map_or("row", String::as_str);
+    if !matches!(display, "row" | "badge") {
+        return Err(Error::ParseError(format!(
+            "Unknown ci-matrix display '{}' - use row or badge",
+            display
+        )));
+    }
+
+    let outcomes = std::thread::scope(|scope| {
+        let fetches: Vec<_> = workflows
+            .iter()
+            .map(|workflow| {
+                let query = format!("{}/{}{}", repo, workflow, branch);
+                let fetch = &fetch;
+                scope.spawn(move || fetch(&query))
+            })
+            .collect();
+        fetches
+            .into_iter()
+            .map(|fetch| fetch.join().expect("workflow fetch panicked"))
+            .collect::<Result<Vec<Outcome>>>()
+    })?;
+
+    let bg = params.get("bg").map(|c| resolve_color(c));
+    let color = |outcome: &Outcome| {
+        bg.clone().unwrap_or_else(|| {
+            outcome
+                .as_ref()
+                .map_or(UNAVAILABLE_COLOR.to_string(), |(_, color)| color.clone())
+        })
+    };
+
+    if display == "badge" {
+        let marks: Vec<String> = workflows
+            .iter()
+            .zip(&outcomes)
+            .map(|(workflow, outcome)| format!("{} {}", workflow, mark(outcome).0))
+            .collect();
+        let worst = outcomes
+            .iter()
+            .rev()
+            .max_by_key(|outcome| mark(outcome).1)
+            .expect("at least one workflow");
+        let label = params.get("label").map_or("ci", String::as_str);
+        return Ok(metric_badge(
+            format!("{}: {}", label, marks.join(" ")),
+            color(worst),
+            params,
+            style,
+            resolve_color,
+        ));
+    }
+
+    let items = workflows
+        .iter()
+        .zip(&outcomes)
+        .map(|(workflow, outcome)| {
+            let value = outcome
+                .as_ref()
+                .map_or(PLACEHOLDER_VALUE, |(conclusion, _)| conclusion.as_str());
+            match metric_badge(
+                format!("{}: {}", workflow, value),
+                color(outcome),
+                params,
+                style,
+                &resolve_color,
+            ) {
+                ComponentOutput::Primitive(badge) => badge,
+                _ => unreachable!("metric_badge returns a primitive"),
+            }
+        })
+        .collect();
+    let mut layout = parse_layout(params).unwrap_or_default();
+    if !params.contains_key("gap") {
+        layout.gap = DEFAULT_GAP;
+    }
+    Ok(ComponentOutput::Primitive(Primitive::Group {
+        items,
+        layout,
+    }))
+}
+
+/// Mark for the combined badge and how bad the outcome is (higher is worse)
+fn mark(outcome: &Outcome) -> (&'static str, u8) {
+    match outcome.as_ref().map(|(conclusion, _)| conclusion.as_str()) {
+        Some("success") => ("✓", 0),
+        Some("cancelled" | "skipped" | "neutral") => ("–", 1),
+        None => (PLACEHOLDER_VALUE, 1),
+        Some("pending") => ("●", 2),
+        Some("action_required") => ("!", 3),
+        Some(_) => ("✗", 4),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// Fake `actions` results keyed by query
+    fn fetch(query: &str) -> Result<Outcome> {
+        let outcome =
+            |conclusion: &str, color: &str| Ok(Some((conclusion.to_string(), color.to_string())));
+        match query {
+            "owner/repo/test" | "owner/repo/test@main" => outcome("success", "22C55E"),
+            "owner/repo/lint" => outcome("success", "22C55E"),
+            "owner/repo/release" => outcome("failure", "EF4444"),
+            "owner/repo/docs" => outcome("pending", "3B82F6"),
+            "owner/repo/broken" => Err(Error::FetchFailed("actions data: timeout".to_string())),
+            _ => Ok(None),
+        }
+    }
+
+    fn expand(args: &[&str], params: &[(&str, &str)]) -> Result<ComponentOutput> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        handle(&args, &params, "flat", fetch, |c| c.to_string())
+    }
+
+    fn swatch(badge: &Primitive) -> (String, String) {
+        match badge {
+            Primitive::Swatch {
+                label: Some(label),
+                color,
+                ..
+            } => (label.clone(), color.clone()),
+            _ => unreachable!("Expected a badge"),
+        }
+    }
+
+    #[test]
+    fn test_ci_matrix_row() {
+        let output = expand(
+            &["owner/repo"],
+            &[("workflows", "test, lint,release,nightly")],
+        )
+        .unwrap();
+        let ComponentOutput::Primitive(Primitive::Group { items, layout }) = output else {
+            panic!("Expected a badge group");
+        };
+        let badges: Vec<_> = items.iter().map(swatch).collect();
+        assert_eq!(
+            badges,
+            [
+                ("test: success".to_string(), "22C55E".to_string()),
+                ("lint: success".to_string(), "22C55E".to_string()),
+                ("release: failure".to_string(), "EF4444".to_string()),
+                ("nightly: —".to_string(), UNAVAILABLE_COLOR.to_string()),
+            ]
+        );
+        assert_eq!(layout.gap, 4);
+    }
+
+    #[rstest]
+    #[case(&[("workflows", "test,lint")], "ci: test ✓ lint ✓", "22C55E")]
+    #[case(&[("workflows", "test,docs,release")], "ci: test ✓ docs ● release ✗", "EF4444")]
+    #[case(&[("workflows", "test,nightly")], "ci: test ✓ nightly —", "6B7280")]
+    #[case(&[("workflows", "test"), ("branch", "main"), ("label", "build")], "build: test ✓", "22C55E")]
+    #[case(&[("workflows", "release"), ("bg", "accent")], "ci: release ✗", "accent")]
+    fn test_ci_matrix_badge(
+        #[case] params: &[(&str, &str)],
+        #[case] label: &str,
+        #[case] color: &str,
+    ) {
+        let mut params = params.to_vec();
+        params.push(("display", "badge"));
+        let ComponentOutput::Primitive(badge) = expand(&["owner/repo"], &params).unwrap() else {
+            panic!("Expected a badge");
+        };
+        assert_eq!(swatch(&badge), (label.to_string(), color.to_string()));
+    }
+
+    #[rstest]
+    #[case(&[], &[("workflows", "test")], "requires owner/repo")]
+    #[case(&["owner"], &[("workflows", "test")], "requires owner/repo")]
+    #[case(&["owner/repo/ci"], &[("workflows", "test")], "requires owner/repo")]
+    #[case(&["owner/repo"], &[], "requires workflows=")]
+    #[case(&["owner/repo"], &[("workflows", " , ")], "requires workflows=")]
+    #[case(&["owner/repo"], &[("workflows", "ci/test")], "Invalid workflow name")]
+    #[case(&["owner/repo"], &[("workflows", "test"), ("display", "grid")], "Unknown ci-matrix display")]
+    #[case(&["owner/repo"], &[("workflows", "test,broken")], "timeout")]
+    fn test_ci_matrix_errors(
+        #[case] args: &[&str],
+        #[case] params: &[(&str, &str)],
+        #[case] message: &str,
+    ) {
+        let err = expand(args, params).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+}
@@ -0,0 +1,108 @@
+//! Coverage badge component handler
+//!
+//! Reads a local coverage report (lcov, Cobertura XML or tarpaulin JSON)
+//! and renders the line coverage as a threshold-colored badge, for projects
+//! that don't upload to Codecov.
+
+use super::{metric_badge, parse_param_clamped};
+use crate::components::{labels, ComponentOutput};
+use crate::coverage::{coverage_color, line_coverage};
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// Handle coverage component expansion
+///
+/// - `file`: report path, relative to the project root (required)
+/// - `label`: text before the percentage
+/// - `lang`: language of the default label
+/// - `precision`: decimal places (0-2)
+/// - `bg`: fixed color instead of the threshold scale
+pub fn handle(
+    params: &HashMap<String, String>,
+    style: &str,
+    read_file: impl Fn(&str) -> Option<String>,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let file = params.get("file").ok_or_else(|| {
+        Error::ParseError(
+            "coverage component requires file=PATH (lcov.info, cobertura.xml or tarpaulin JSON)"
+                .to_string(),
+        )
+    })?;
+    let report = read_file(file)
+        .ok_or_else(|| Error::ParseError(format!("coverage report '{}' not found", file)))?;
+    let percent = line_coverage(&report).ok_or_else(|| {
+        Error::ParseError(format!(
+            "no line coverage in '{}' (expected lcov, Cobertura XML or tarpaulin JSON)",
+            file
+        ))
+    })?;
+
+    let precision: usize = parse_param_clamped(params, "precision", 0, 0, 2);
+    let label = labels::badge_label(params, "coverage")?;
+    let bg_color = params
+        .get("bg")
+        .map(|c| resolve_color(c))
+        .unwrap_or_else(|| coverage_color(percent).to_string());
+
+    Ok(metric_badge(
+        format!("{}: {:.*}%", label, precision, percent),
+        bg_color,
+        params,
+        style,
+        resolve_color,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::Primitive;
+    use rstest::rstest;
+
+    fn identity_color(c: &str) -> String {
+        c.to_string()
+    }
+
+    fn read_lcov(path: &str) -> Option<String> {
+        (path == "coverage/lcov.info").then(|| "LF:300\nLH:256\n".to_string())
+    }
+
+    #[rstest]
+    #[case(&[], "coverage: 85%", "84CC16")]
+    #[case(&[("precision", "2")], "coverage: 85.33%", "84CC16")]
+    #[case(&[("label", "lines"), ("bg", "accent")], "lines: 85%", "accent")]
+    #[case(&[("lang", "fr")], "couverture: 85%", "84CC16")]
+    fn test_coverage_badge(
+        #[case] params_input: &[(&str, &str)],
+        #[case] expected_label: &str,
+        #[case] expected_color: &str,
+    ) {
+        let mut params: HashMap<String, String> = params_input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        params.insert("file".to_string(), "coverage/lcov.info".to_string());
+
+        let output = handle(&params, "flat", read_lcov, identity_color).unwrap();
+        let ComponentOutput::Primitive(Primitive::Swatch { label, color, .. }) = output else {
+            panic!("Expected Swatch primitive");
+        };
+        assert_eq!(label.as_deref(), Some(expected_label));
+        assert_eq!(color, expected_color);
+    }
+
+    #[rstest]
+    #[case(&[], "requires file=PATH")]
+    #[case(&[("file", "missing.info")], "'missing.info' not found")]
+    #[case(&[("file", "README.md")], "no line coverage in 'README.md'")]
+    fn test_coverage_errors(#[case] params_input: &[(&str, &str)], #[case] message: &str) {
+        let params = params_input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let read = |path: &str| (path == "README.md").then(|| "# Demo\n".to_string());
+        let err = handle(&params, "flat", read, identity_color).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+}
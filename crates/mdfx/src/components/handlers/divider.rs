@@ -0,0 +1,179 @@
+//! Divider component handler
+//!
+//! Repeats a glyph pattern to an exact display width, so a divider lines
+//! up with the text around it without counting characters by hand. A label
+//! is centered in the line.
+
+use crate::components::ComponentOutput;
+use crate::error::{Error, Result};
+use crate::width::{display_width, truncate, Truncate};
+use std::collections::HashMap;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Named line patterns for `style=`
+pub const PATTERNS: [(&str, &str); 10] = [
+    ("line", "─"),
+    ("heavy", "━"),
+    ("double", "═"),
+    ("dashed", "╌"),
+    ("dotted", "┈"),
+    ("wave", "∿"),
+    ("zigzag", "╱╲"),
+    ("dots", "• "),
+    ("stars", "✦ "),
+    ("gradient", "░▒▓█▓▒"),
+];
+
+/// Handle divider component expansion
+///
+/// - `style`: named pattern (default `line`)
+/// - `pattern`: literal pattern, overriding `style`
+/// - `width`: display columns to fill (default 80, at most 1000)
+/// - `label`: text centered in the line, shortened with `…` to fit
+pub fn handle(params: &HashMap<String, String>) -> Result<ComponentOutput> {
+    let pattern = match params.get("pattern") {
+        Some(pattern) if pattern.trim().is_empty() || display_width(pattern) == 0 => {
+            return Err(Error::ParseError(
+                "divider pattern must not be empty".to_string(),
+            ))
+        }
+        Some(pattern) => pattern.as_str(),
+        None => {
+            let style = params.get("style").map_or("line", String::as_str);
+            PATTERNS
+                .iter()
+                .find(|(name, _)| *name == style)
+                .map(|(_, pattern)| *pattern)
+                .ok_or_else(|| {
+                    let names: Vec<&str> = PATTERNS.iter().map(|(name, _)| *name).collect();
+                    Error::ParseError(format!(
+                        "Unknown divider style '{}'. Available: {}",
+                        style,
+                        names.join(", ")
+                    ))
+                })?
+        }
+    };
+    let width = match params.get("width") {
+        Some(width) => match width.parse::<usize>() {
+            Ok(width) if width > 0 => width.min(1000),
+            _ => {
+                return Err(Error::ParseError(format!(
+                    "divider width must be a positive number of columns, got '{}'",
+                    width
+                )))
+            }
+        },
+        None => 80,
+    };
+
+    let line = match params.get("label").filter(|label| !label.is_empty()) {
+        Some(label) => {
+            // One column of space on each side of the label
+            if width < 3 {
+                return Err(Error::ParseError(format!(
+                    "divider width {} leaves no room for a label",
+                    width
+                )));
+            }
+            let label = format!(" {} ", truncate(label, width - 2, Truncate::Ellipsis));
+            let sides = width.saturating_sub(display_width(&label));
+            let left = sides / 2;
+            format!(
+                "{}{}{}",
+                fill(pattern, left),
+                label,
+                fill(pattern, sides - left)
+            )
+        }
+        None => fill(pattern, width),
+    };
+
+    Ok(ComponentOutput::Template(line))
+}
+
+/// Repeat `pattern` to exactly `width` columns
+///
+/// Whole graphemes only; when the next one is too wide for what is left,
+/// the line is finished with spaces. `pattern` must have a nonzero width.
+fn fill(pattern: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut used = 0;
+    for grapheme in pattern.graphemes(true).cycle() {
+        let w = display_width(grapheme);
+        if used == width {
+            break;
+        }
+        if used + w > width {
+            out.push_str(&" ".repeat(width - used));
+            break;
+        }
+        out.push_str(grapheme);
+        used += w;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn divider(params: &[(&str, &str)]) -> Result<String> {
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        match handle(&params)? {
+            ComponentOutput::Template(line) => Ok(line),
+            _ => unreachable!("divider renders text"),
+        }
+    }
+
+    #[rstest]
+    #[case(&[("width", "5")], "─────")]
+    #[case(&[("style", "wave"), ("width", "4")], "∿∿∿∿")]
+    #[case(&[("style", "zigzag"), ("width", "5")], "╱╲╱╲╱")]
+    #[case(&[("pattern", "🚀"), ("width", "5")], "🚀🚀 ")] // wide glyph padded
+    #[case(&[("width", "11"), ("label", "Hi")], "─── Hi ────")]
+    #[case(&[("width", "4"), ("label", "Label")], " L… ")] // label shortened to fit
+    #[case(&[("width", "3"), ("label", "中文")], " … ")] // only the ellipsis fits
+    #[case(&[("width", "5000")], &"─".repeat(1000))]
+    fn test_divider(#[case] params: &[(&str, &str)], #[case] expected: &str) {
+        assert_eq!(divider(params).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_divider_exact_width() {
+        for (name, _) in PATTERNS {
+            for width in [1, 7, 80] {
+                let line = divider(&[("style", name), ("width", &width.to_string())]).unwrap();
+                assert_eq!(display_width(&line), width, "{} at {}", name, width);
+            }
+        }
+        let line = divider(&[("style", "stars"), ("label", "中文 Title")]).unwrap();
+        assert_eq!(display_width(&line), 80);
+
+        let label = "Hello World Long Label";
+        for width in [3, 7, 12, 24] {
+            let line = divider(&[
+                ("style", "wave"),
+                ("width", &width.to_string()),
+                ("label", label),
+            ])
+            .unwrap();
+            assert_eq!(display_width(&line), width, "{}", line);
+        }
+    }
+
+    #[rstest]
+    #[case(&[("style", "sparkles")])]
+    #[case(&[("pattern", " ")])]
+    #[case(&[("pattern", "\u{FE0E}")])]
+    #[case(&[("style", "wave"), ("width", "0")])]
+    #[case(&[("width", "wide")])]
+    #[case(&[("width", "2"), ("label", "Hi")])]
+    fn test_divider_errors(#[case] params: &[(&str, &str)]) {
+        assert!(divider(params).is_err());
+    }
+}
@@ -1,20 +1,43 @@
 //! Dynamic badge component handlers
 //!
-//! Renders badges with live data from external APIs (GitHub, npm, crates.io, PyPI).
-//! Requires the `fetch` feature to be enabled.
+//! Renders badges with live data from external APIs (GitHub, npm, crates.io, PyPI,
+//! OpenSSF Scorecard, OSV, Discord, ...).
+//! Requires the `fetch` feature to be enabled, except for placeholders.
 
+use super::metric_badge;
 use crate::components::ComponentOutput;
+use crate::components::{labels, params};
 use crate::error::{Error, Result};
-use crate::primitive::Primitive;
 use std::collections::HashMap;
 
 #[cfg(feature = "fetch")]
-use mdfx_fetch::{FetchConfig, Fetcher};
+use crate::options::Clock;
+#[cfg(feature = "fetch")]
+use mdfx_fetch::{FetchConfig, FetchError, Fetcher, Locale};
+
+/// Badge color for values a source could not provide
+pub(super) const UNAVAILABLE_COLOR: &str = "6B7280";
+
+/// Value shown on placeholder badges
+pub(super) const PLACEHOLDER_VALUE: &str = "—";
+
+/// Default `age_color` thresholds in days: fresh up to the first, aging up
+/// to the second, stale after
+#[cfg(feature = "fetch")]
+const AGE_THRESHOLDS: (i64, i64) = (30, 180);
+
+/// Badge colors for fresh, aging, and stale dates
+#[cfg(feature = "fetch")]
+const AGE_COLORS: [&str; 3] = ["22C55E", "EAB308", "EF4444"];
 
 /// Fetch context for dynamic badges
 #[cfg(feature = "fetch")]
 pub struct FetchContext {
     fetcher: Fetcher,
+    /// Render placeholders instead of failing when data can't be fetched
+    placeholders: bool,
+    /// Source of today's date for relative dates and age colors
+    clock: Clock,
 }
 
 #[cfg(feature = "fetch")]
@@ -22,7 +45,19 @@ impl FetchContext {
     /// Create a new fetch context
     pub fn new(config: FetchConfig) -> Result<Self> {
         let fetcher = Fetcher::new(config).map_err(|e| Error::ParseError(e.to_string()))?;
-        Ok(FetchContext { fetcher })
+        Ok(FetchContext {
+            fetcher,
+            placeholders: false,
+            clock: Clock::System,
+        })
+    }
+
+    pub(crate) fn set_placeholders(&mut self, enabled: bool) {
+        self.placeholders = enabled;
+    }
+
+    pub(crate) fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
     }
 
     /// Get the underlying fetcher
@@ -55,19 +90,55 @@ pub fn handle_source(
     }
 
     let query = &args[0];
+    let locale = match params.get("locale") {
+        Some(tag) => Locale::parse(tag).ok_or_else(|| {
+            Error::ParseError(format!(
+                "Unknown locale '{}'. Available: {}",
+                tag,
+                Locale::available().collect::<Vec<_>>().join(", ")
+            ))
+        })?,
+        None => Locale::default(),
+    };
+    let relative = match params.get("format").map(String::as_str) {
+        Some("relative") => true,
+        Some("absolute") | None => false,
+        Some(other) => {
+            return Err(Error::ParseError(format!(
+                "Unknown format '{}'. Available: absolute, relative",
+                other
+            )))
+        }
+    };
+    let age_thresholds = parse_age_thresholds(params)?;
 
     // Metric can be second arg or param
     let metric = args
         .get(1)
         .or_else(|| params.get("metric"))
-        .map(|s| s.as_str())
-        .unwrap_or(default_metric);
+        .map(|s| params::normalize_metric(s))
+        .unwrap_or_else(|| default_metric.to_string());
+    let metric = metric.as_str();
 
     // Fetch the data
-    let value = fetch_ctx
-        .fetcher
-        .fetch(source_id, query, metric)
-        .map_err(|e| Error::ParseError(format!("Failed to fetch {} data: {}", source_id, e)))?;
+    let value = match fetch_ctx.fetcher.fetch(source_id, query, metric) {
+        Ok(value) if value.is_unavailable() && fetch_ctx.placeholders => {
+            return placeholder_badge(metric, params, style, resolve_color);
+        }
+        Ok(value) => value,
+        // Mistakes in the template still fail; missing data doesn't
+        Err(e) if fetch_ctx.placeholders && !is_template_error(&e) => {
+            tracing::warn!("Placeholder for {}:{}:{} ({})", source_id, query, metric, e);
+            return placeholder_badge(metric, params, style, resolve_color);
+        }
+        Err(e) if is_template_error(&e) => {
+            return Err(Error::ParseError(format!(
+                "Failed to fetch {} data: {}",
+                source_id, e
+            )))
+        }
+        Err(e) => return Err(Error::FetchFailed(format!("{} data: {}", source_id, e))),
+    };
 
     // Get metric info
     let label = fetch_ctx
@@ -75,74 +146,175 @@ pub fn handle_source(
         .metric_info(source_id, metric)
         .map(|info| info.label)
         .unwrap_or_else(|| metric.to_string());
+    let label = labels::badge_label(params, &label)?;
+
+    // Dates are measured against the day the document is built
+    let today = fetch_ctx.clock.today();
+    if today.is_none() && value.as_days().is_some() && (relative || age_thresholds.is_some()) {
+        return Err(Error::ParseError(
+            "format=relative and age_color depend on the build date, which isn't available; set SOURCE_DATE_EPOCH to pin it".to_string(),
+        ));
+    }
+    let age = value.as_days().zip(today).map(|(days, today)| today - days);
 
-    // Determine color
+    // Determine color; unavailable values are always gray
     let bg_color = params
         .get("bg")
         .map(|c| resolve_color(c))
         .unwrap_or_else(|| {
+            if value.is_unavailable() {
+                return UNAVAILABLE_COLOR.to_string();
+            }
+            if let (Some(age), Some((fresh, aging))) = (age, age_thresholds) {
+                let color = match age {
+                    age if age <= fresh => AGE_COLORS[0],
+                    age if age <= aging => AGE_COLORS[1],
+                    _ => AGE_COLORS[2],
+                };
+                return color.to_string();
+            }
             fetch_ctx
                 .fetcher
                 .metric_color(source_id, metric, &value)
                 .unwrap_or_else(|| default_color.to_string())
         });
 
-    // Text color (default white for most colors, black for yellow/bright)
-    let text_color = params
-        .get("text")
-        .or_else(|| params.get("text_color"))
-        .map(|c| resolve_color(c))
-        .unwrap_or_else(|| {
-            // Use black text for yellow-ish backgrounds
-            if bg_color == "EAB308" || bg_color == "FFD700" || bg_color == "FFD43B" {
-                "000000".to_string()
-            } else {
-                "FFFFFF".to_string()
-            }
-        });
+    let shown = match today.and_then(|today| value.format_relative(today)) {
+        Some(relative_date) if relative => relative_date,
+        _ => value.format_with(&locale),
+    };
+
+    Ok(metric_badge(
+        format!("{}: {}", label, shown),
+        bg_color,
+        params,
+        style,
+        resolve_color,
+    ))
+}
+
+/// Fetch one value for a component that shows several, such as `ci-matrix`
+///
+/// Returns the formatted value with its color, or `None` when the source
+/// can't provide it or, in placeholder mode, when fetching fails.
+#[cfg(feature = "fetch")]
+pub(crate) fn fetch_value(
+    fetch_ctx: &FetchContext,
+    source_id: &str,
+    query: &str,
+    metric: &str,
+    default_color: &str,
+) -> Result<Option<(String, String)>> {
+    match fetch_ctx.fetcher.fetch(source_id, query, metric) {
+        Ok(value) if value.is_unavailable() => Ok(None),
+        Ok(value) => {
+            let color = fetch_ctx
+                .fetcher
+                .metric_color(source_id, metric, &value)
+                .unwrap_or_else(|| default_color.to_string());
+            Ok(Some((value.format(), color)))
+        }
+        Err(e) if is_template_error(&e) => Err(Error::ParseError(format!(
+            "Failed to fetch {} data: {}",
+            source_id, e
+        ))),
+        Err(e) if fetch_ctx.placeholders => {
+            tracing::warn!("Placeholder for {}:{}:{} ({})", source_id, query, metric, e);
+            Ok(None)
+        }
+        Err(e) => Err(Error::FetchFailed(format!("{} data: {}", source_id, e))),
+    }
+}
+
+/// Staleness thresholds from `age_color`: `true` for the defaults, or the
+/// fresh and aging limits in days (`age_color=7,90`)
+#[cfg(feature = "fetch")]
+fn parse_age_thresholds(params: &HashMap<String, String>) -> Result<Option<(i64, i64)>> {
+    let Some(value) = params.get("age_color").or_else(|| params.get("age-color")) else {
+        return Ok(None);
+    };
+    match value.as_str() {
+        "true" | "1" => return Ok(Some(AGE_THRESHOLDS)),
+        "false" | "0" => return Ok(None),
+        _ => {}
+    }
+    value
+        .split_once(',')
+        .and_then(|(fresh, aging)| Some((fresh.trim().parse().ok()?, aging.trim().parse().ok()?)))
+        .filter(|(fresh, aging): &(i64, i64)| 0 <= *fresh && fresh <= aging)
+        .map(Some)
+        .ok_or_else(|| {
+            Error::ParseError(format!(
+                "Invalid age_color '{}'. Expected true or two day counts, e.g. 30,180",
+                value
+            ))
+        })
+}
+
+/// Whether a fetch error comes from the template rather than the data
+#[cfg(feature = "fetch")]
+fn is_template_error(error: &FetchError) -> bool {
+    matches!(
+        error,
+        FetchError::UnknownSource(_) | FetchError::UnknownMetric { .. } | FetchError::ParseError(_)
+    )
+}
 
-    // Format label with value
-    let display_label = format!("{}: {}", label, value.format());
-
-    // Calculate width (approx 7px per char + 16px padding)
-    let estimated_width = params
-        .get("width")
-        .and_then(|w| w.parse().ok())
-        .unwrap_or_else(|| (display_label.len() as u32 * 7 + 16).max(50));
-
-    // Border parameters
-    let border_color = params.get("border").map(|c| resolve_color(c));
-    let border_width = params.get("border_width").and_then(|v| v.parse().ok());
-
-    // Corner radius - default to 3
-    let rx = params.get("rx").and_then(|v| v.parse().ok()).or(Some(3));
-
-    // Optional icon
-    let icon = params.get("icon").cloned();
-
-    Ok(ComponentOutput::Primitive(Primitive::Swatch {
-        color: bg_color,
-        style: style.to_string(),
-        opacity: None,
-        width: Some(estimated_width),
-        height: None,
-        border_color,
-        border_width,
-        label: Some(display_label),
-        label_color: Some(text_color),
-        icon,
-        icon_color: None,
-        rx,
-        ry: None,
-        shadow: None,
-        gradient: None,
-        stroke_dash: None,
-        logo_size: None,
-        border_top: None,
-        border_right: None,
-        border_bottom: None,
-        border_left: None,
-    }))
+/// Handle a live badge without fetching: `metric: —` in gray
+///
+/// Used in placeholder mode when no fetch context is set or the `fetch`
+/// feature is disabled, so the output doesn't depend on the network.
+///
+/// Syntax: {{ui:live:source:query:metric/}}, as for the fetching handlers
+pub fn handle_placeholder(
+    source_id: &str,
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let default_metric = params::default_metric(source_id).ok_or_else(|| {
+        Error::ParseError(format!(
+            "Unknown live source '{}'. Available: {}",
+            source_id,
+            params::valid_live_sources().collect::<Vec<_>>().join(", ")
+        ))
+    })?;
+    if args.is_empty() {
+        return Err(Error::ParseError(format!(
+            "{} component requires a query argument",
+            source_id
+        )));
+    }
+
+    let metric = args
+        .get(1)
+        .or_else(|| params.get("metric"))
+        .map(|s| params::normalize_metric(s))
+        .unwrap_or_else(|| default_metric.to_string());
+
+    placeholder_badge(&metric, params, style, resolve_color)
+}
+
+/// Gray `metric: —` badge, unless the template sets `bg`
+fn placeholder_badge(
+    metric: &str,
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let label = labels::badge_label(params, metric)?;
+    let bg_color = params
+        .get("bg")
+        .map(|c| resolve_color(c))
+        .unwrap_or_else(|| UNAVAILABLE_COLOR.to_string());
+    Ok(metric_badge(
+        format!("{}: {}", label, PLACEHOLDER_VALUE),
+        bg_color,
+        params,
+        style,
+        resolve_color,
+    ))
 }
 
 /// Handle github source for live component
@@ -508,6 +680,370 @@ pub fn handle_nuget(
     )
 }
 
+/// Handle scorecard source for live component
+///
+/// Syntax: {{ui:live:scorecard:owner/repo:metric/}}
+///
+/// Metrics:
+/// - score - Aggregate OpenSSF Scorecard score (0-10)
+/// - date - Date of the latest scan
+/// - code_review, maintained, vulnerabilities, ... - Individual check scores
+///
+/// Examples:
+/// - {{ui:live:scorecard:ossf/scorecard/}}
+/// - {{ui:live:scorecard:tokio-rs/tokio:maintained/}}
+/// - {{ui:live:scorecard:gitlab.com/owner/repo/}}
+#[cfg(feature = "fetch")]
+pub fn handle_scorecard(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "scorecard",
+        args,
+        params,
+        style,
+        resolve_color,
+        fetch_ctx,
+        "score",
+        "6B7280",
+    )
+}
+
+/// Handle osv source for live component
+///
+/// Syntax: {{ui:live:osv:ecosystem/package@version:metric/}}
+///
+/// Metrics:
+/// - vulnerabilities - Number of known advisories
+/// - vulnerable - Whether any advisory applies
+/// - ids - Advisory IDs
+///
+/// Examples:
+/// - {{ui:live:osv:crates/time@0.1.45/}}
+/// - {{ui:live:osv:npm/@babel/traverse@7.23.0/}}
+/// - {{ui:live:osv:pypi/django@4.2.0:ids/}}
+#[cfg(feature = "fetch")]
+pub fn handle_osv(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "osv",
+        args,
+        params,
+        style,
+        resolve_color,
+        fetch_ctx,
+        "vulnerabilities",
+        "6B7280",
+    )
+}
+
+/// Handle librariesio source for live component
+///
+/// Syntax: {{ui:live:librariesio:platform/package:metric/}}
+///
+/// Requires LIBRARIES_IO_API_KEY.
+///
+/// Metrics:
+/// - sourcerank - Libraries.io SourceRank
+/// - dependents - Packages depending on it
+/// - dependent_repos - Repositories depending on it
+///
+/// Examples:
+/// - {{ui:live:librariesio:cargo/serde/}}
+/// - {{ui:live:librariesio:npm/react:dependents/}}
+#[cfg(feature = "fetch")]
+pub fn handle_librariesio(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "librariesio",
+        args,
+        params,
+        style,
+        resolve_color,
+        fetch_ctx,
+        "sourcerank",
+        "337AB7", // Libraries.io blue
+    )
+}
+
+/// Params with a brand icon unless the template picks its own
+#[cfg(feature = "fetch")]
+fn with_icon(params: &HashMap<String, String>, icon: &str) -> HashMap<String, String> {
+    let mut params = params.clone();
+    params
+        .entry("icon".to_string())
+        .or_insert_with(|| icon.to_string());
+    params
+}
+
+/// Handle discord source for live component
+///
+/// Syntax: {{ui:live:discord:INVITE:metric/}}
+///
+/// The query is an invite code, or a server id with the widget enabled.
+///
+/// Metrics:
+/// - members - Approximate member count
+/// - online - Members online now
+/// - name - Server name
+///
+/// Examples:
+/// - {{ui:live:discord:rust-lang/}}
+/// - {{ui:live:discord:442252698964721669:online/}}
+#[cfg(feature = "fetch")]
+pub fn handle_discord(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "discord",
+        args,
+        &with_icon(params, "discord"),
+        style,
+        resolve_color,
+        fetch_ctx,
+        "members",
+        "5865F2", // Discord blurple
+    )
+}
+
+/// Handle matrix source for live component
+///
+/// Syntax: {{ui:live:matrix:server/room:metric/}}
+///
+/// The query `matrix.org/rust` is the room `#rust:matrix.org`.
+///
+/// Metrics:
+/// - members - Joined members
+/// - name - Room name
+///
+/// Examples:
+/// - {{ui:live:matrix:matrix.org/rust/}}
+#[cfg(feature = "fetch")]
+pub fn handle_matrix(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "matrix",
+        args,
+        &with_icon(params, "matrix"),
+        style,
+        resolve_color,
+        fetch_ctx,
+        "members",
+        "000000", // Matrix black
+    )
+}
+
+/// Handle reddit source for live component
+///
+/// Syntax: {{ui:live:reddit:subreddit:metric/}}
+///
+/// Metrics:
+/// - subscribers - Subscriber count
+/// - active - Users online now
+/// - title - Subreddit title
+///
+/// Examples:
+/// - {{ui:live:reddit:rust/}}
+/// - {{ui:live:reddit:rust:active/}}
+#[cfg(feature = "fetch")]
+pub fn handle_reddit(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "reddit",
+        args,
+        &with_icon(params, "reddit"),
+        style,
+        resolve_color,
+        fetch_ctx,
+        "subscribers",
+        "FF4500", // Reddit orange
+    )
+}
+
+/// Handle docsrs source for live component
+///
+/// Syntax: {{ui:live:docsrs:crate@version:metric/}}
+///
+/// Metrics:
+/// - status - Documentation build: passing or failing
+/// - version - Release the status is for
+///
+/// Examples:
+/// - {{ui:live:docsrs:serde/}}
+/// - {{ui:live:docsrs:tokio@1.38.0/}}
+#[cfg(feature = "fetch")]
+pub fn handle_docsrs(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "docsrs",
+        args,
+        params,
+        style,
+        resolve_color,
+        fetch_ctx,
+        "status",
+        "6B7280",
+    )
+}
+
+/// Handle readthedocs source for live component
+///
+/// Syntax: {{ui:live:readthedocs:project@version/}}
+///
+/// Metrics:
+/// - status - Build status: passing, failing or unknown
+///
+/// Examples:
+/// - {{ui:live:readthedocs:requests/}}
+/// - {{ui:live:readthedocs:pip@stable/}}
+#[cfg(feature = "fetch")]
+pub fn handle_readthedocs(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "readthedocs",
+        args,
+        params,
+        style,
+        resolve_color,
+        fetch_ctx,
+        "status",
+        "6B7280",
+    )
+}
+
+/// Handle sponsors source for live component
+///
+/// Syntax: {{ui:live:sponsors:LOGIN:metric/}}
+///
+/// Needs GITHUB_TOKEN: sponsor counts are only in the GraphQL API.
+///
+/// Metrics:
+/// - sponsors - Number of sponsors of the user or organization
+/// - sponsoring - Number of accounts it sponsors
+///
+/// Examples:
+/// - {{ui:live:sponsors:dtolnay/}}
+/// - {{ui:live:sponsors:rust-lang:sponsoring/}}
+#[cfg(feature = "fetch")]
+pub fn handle_sponsors(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "sponsors",
+        args,
+        &with_icon(params, "githubsponsors"),
+        style,
+        resolve_color,
+        fetch_ctx,
+        "sponsors",
+        "EA4AAA", // Sponsors pink
+    )
+}
+
+/// Handle opencollective source for live component
+///
+/// Syntax: {{ui:live:opencollective:SLUG:metric/}}
+///
+/// Metrics:
+/// - backers - Number of backers
+/// - balance - Current balance, e.g. `$12.3k`
+///
+/// Examples:
+/// - {{ui:live:opencollective:webpack/}}
+/// - {{ui:live:opencollective:babel:balance/}}
+#[cfg(feature = "fetch")]
+pub fn handle_opencollective(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "opencollective",
+        args,
+        &with_icon(params, "githubsponsors"),
+        style,
+        resolve_color,
+        fetch_ctx,
+        "backers",
+        "7FADF2", // Open Collective blue
+    )
+}
+
+/// Handle patreon source for live component
+///
+/// Syntax: {{ui:live:patreon:CAMPAIGN_ID/}}
+///
+/// The query is the numeric campaign id, not the creator's page name.
+///
+/// Metrics:
+/// - patrons - Number of patrons
+///
+/// Examples:
+/// - {{ui:live:patreon:1234567/}}
+#[cfg(feature = "fetch")]
+pub fn handle_patreon(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+    fetch_ctx: &FetchContext,
+) -> Result<ComponentOutput> {
+    handle_source(
+        "patreon",
+        args,
+        &with_icon(params, "githubsponsors"),
+        style,
+        resolve_color,
+        fetch_ctx,
+        "patrons",
+        "F96854", // Patreon coral
+    )
+}
+
 #[cfg(all(test, feature = "fetch"))]
 mod tests {
     use super::*;
@@ -521,6 +1057,7 @@ mod tests {
             default_ttl: 3600,
             offline,
             refresh: false,
+            ..FetchConfig::default()
         };
         let ctx = FetchContext::new(config).unwrap();
         (ctx, dir)
@@ -541,6 +1078,17 @@ mod tests {
     #[case("packagist", "laravel/laravel")]
     #[case("rubygems", "rails")]
     #[case("nuget", "Newtonsoft.Json")]
+    #[case("scorecard", "ossf/scorecard")]
+    #[case("osv", "crates/time@0.1.45")]
+    #[case("librariesio", "cargo/serde")]
+    #[case("discord", "rust-lang")]
+    #[case("matrix", "matrix.org/rust")]
+    #[case("reddit", "rust")]
+    #[case("docsrs", "serde")]
+    #[case("readthedocs", "requests")]
+    #[case("sponsors", "dtolnay")]
+    #[case("opencollective", "webpack")]
+    #[case("patreon", "1234567")]
     fn test_source_offline_no_cache(#[case] source: &str, #[case] query: &str) {
         let (ctx, _dir) = temp_fetch_ctx(true);
         let params = HashMap::new();
@@ -557,6 +1105,19 @@ mod tests {
             "packagist" => handle_packagist(&args, &params, "flat", |c| c.to_string(), &ctx),
             "rubygems" => handle_rubygems(&args, &params, "flat", |c| c.to_string(), &ctx),
             "nuget" => handle_nuget(&args, &params, "flat", |c| c.to_string(), &ctx),
+            "scorecard" => handle_scorecard(&args, &params, "flat", |c| c.to_string(), &ctx),
+            "osv" => handle_osv(&args, &params, "flat", |c| c.to_string(), &ctx),
+            "librariesio" => handle_librariesio(&args, &params, "flat", |c| c.to_string(), &ctx),
+            "discord" => handle_discord(&args, &params, "flat", |c| c.to_string(), &ctx),
+            "matrix" => handle_matrix(&args, &params, "flat", |c| c.to_string(), &ctx),
+            "reddit" => handle_reddit(&args, &params, "flat", |c| c.to_string(), &ctx),
+            "docsrs" => handle_docsrs(&args, &params, "flat", |c| c.to_string(), &ctx),
+            "readthedocs" => handle_readthedocs(&args, &params, "flat", |c| c.to_string(), &ctx),
+            "sponsors" => handle_sponsors(&args, &params, "flat", |c| c.to_string(), &ctx),
+            "opencollective" => {
+                handle_opencollective(&args, &params, "flat", |c| c.to_string(), &ctx)
+            }
+            "patreon" => handle_patreon(&args, &params, "flat", |c| c.to_string(), &ctx),
             _ => unreachable!("Unknown source"),
         };
 
@@ -567,6 +1128,216 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case(HashMap::new(), "6B7280")]
+    #[case(HashMap::from([("bg".to_string(), "112233".to_string())]), "112233")]
+    fn test_unavailable_badge(#[case] params: HashMap<String, String>, #[case] color: &str) {
+        let (ctx, dir) = temp_fetch_ctx(true);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        std::fs::write(
+            dir.path().join("github_rust-lang_rust_stars.json"),
+            format!(r#"{{"value":null,"created_at":{},"ttl":300}}"#, now),
+        )
+        .unwrap();
+
+        let args = vec!["rust-lang/rust".to_string()];
+        let output = handle_github(&args, &params, "flat", |c| c.to_string(), &ctx).unwrap();
+        let rendered = format!("{:?}", output);
+        assert!(rendered.contains("Stars: n/a"), "{}", rendered);
+        assert!(rendered.contains(color), "{}", rendered);
+    }
+
+    #[rstest]
+    #[case("stars", "1234567", None, "Stars: 1.2M")]
+    #[case("stars", "1234567", Some("de"), "Stars: 1,2M")]
+    #[case("date", r#""2024-06-03""#, Some("en-US"), "Scorecard date: 06/03/2024")]
+    #[case("date", r#""2024-06-03""#, Some("de-DE"), "Scorecard date: 03.06.2024")]
+    fn test_locale(
+        #[case] metric: &str,
+        #[case] cached: &str,
+        #[case] locale: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let (ctx, dir) = temp_fetch_ctx(true);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let source = if metric == "date" {
+            "scorecard"
+        } else {
+            "github"
+        };
+        std::fs::write(
+            dir.path()
+                .join(format!("{}_ossf_scorecard_{}.json", source, metric)),
+            format!(r#"{{"value":{},"created_at":{},"ttl":300}}"#, cached, now),
+        )
+        .unwrap();
+
+        let mut params = HashMap::new();
+        if let Some(locale) = locale {
+            params.insert("locale".to_string(), locale.to_string());
+        }
+        let args = vec!["ossf/scorecard".to_string(), metric.to_string()];
+        let output = match source {
+            "scorecard" => handle_scorecard(&args, &params, "flat", |c| c.to_string(), &ctx),
+            _ => handle_github(&args, &params, "flat", |c| c.to_string(), &ctx),
+        }
+        .unwrap();
+        let rendered = format!("{:?}", output);
+        assert!(rendered.contains(expected), "{}", rendered);
+    }
+
+    #[rstest]
+    #[case(3, &[], "Last commit: 2", None)]
+    #[case(3, &[("format", "relative")], "Last commit: 3 days ago", None)]
+    #[case(3, &[("age_color", "true")], "Last commit: 2", Some("22C55E"))]
+    #[case(100, &[("age-color", "true")], "Last commit: 2", Some("EAB308"))]
+    #[case(100, &[("age_color", "7,90")], "Last commit: 2", Some("EF4444"))]
+    #[case(100, &[("age_color", "7,90"), ("bg", "112233")], "Last commit: 2", Some("112233"))]
+    fn test_relative_date(
+        #[case] age: u64,
+        #[case] params: &[(&str, &str)],
+        #[case] expected: &str,
+        #[case] color: Option<&str>,
+    ) {
+        let (ctx, dir) = temp_fetch_ctx(true);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let date = chrono::Utc::now().date_naive() - chrono::Days::new(age);
+        std::fs::write(
+            dir.path().join("github_rust-lang_rust_last_commit.json"),
+            format!(
+                r#"{{"value":"{}","created_at":{},"ttl":300}}"#,
+                date.format("%Y-%m-%d"),
+                now
+            ),
+        )
+        .unwrap();
+
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let args = vec!["rust-lang/rust".to_string(), "last_commit".to_string()];
+        let output = handle_github(&args, &params, "flat", |c| c.to_string(), &ctx).unwrap();
+        let rendered = format!("{:?}", output);
+        assert!(rendered.contains(expected), "{}", rendered);
+        if let Some(color) = color {
+            assert!(rendered.contains(color), "{}", rendered);
+        }
+    }
+
+    /// A cached `last_commit` of 2024-06-03 for rust-lang/rust
+    fn cached_last_commit(dir: &std::path::Path) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        std::fs::write(
+            dir.join("github_rust-lang_rust_last_commit.json"),
+            format!(r#"{{"value":"2024-06-03","created_at":{},"ttl":300}}"#, now),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_relative_date_uses_fixed_clock() {
+        let (mut ctx, dir) = temp_fetch_ctx(true);
+        cached_last_commit(dir.path());
+        // 2024-06-13T12:00:00Z
+        ctx.set_clock(Clock::Fixed(1_718_280_000));
+
+        let params = HashMap::from([
+            ("format".to_string(), "relative".to_string()),
+            ("age_color".to_string(), "7,90".to_string()),
+        ]);
+        let args = vec!["rust-lang/rust".to_string(), "last_commit".to_string()];
+        let output = handle_github(&args, &params, "flat", |c| c.to_string(), &ctx).unwrap();
+        let rendered = format!("{:?}", output);
+        assert!(
+            rendered.contains("Last commit: 10 days ago"),
+            "{}",
+            rendered
+        );
+        assert!(rendered.contains("EAB308"), "{}", rendered);
+    }
+
+    #[rstest]
+    #[case(&[("format", "relative")])]
+    #[case(&[("age_color", "true")])]
+    fn test_relative_date_without_clock(#[case] params: &[(&str, &str)]) {
+        let (mut ctx, dir) = temp_fetch_ctx(true);
+        cached_last_commit(dir.path());
+        ctx.set_clock(Clock::Unavailable);
+
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let args = vec!["rust-lang/rust".to_string(), "last_commit".to_string()];
+        let err = handle_github(&args, &params, "flat", |c| c.to_string(), &ctx).unwrap_err();
+        assert!(err.to_string().contains("SOURCE_DATE_EPOCH"), "{}", err);
+
+        // Absolute dates don't need the clock
+        let output =
+            handle_github(&args, &HashMap::new(), "flat", |c| c.to_string(), &ctx).unwrap();
+        assert!(format!("{:?}", output).contains("Last commit: 2"));
+    }
+
+    #[rstest]
+    #[case("format", "ago", "Unknown format 'ago'")]
+    #[case("age_color", "90,7", "Invalid age_color '90,7'")]
+    #[case("age_color", "soon", "Invalid age_color 'soon'")]
+    fn test_relative_date_errors(#[case] key: &str, #[case] value: &str, #[case] message: &str) {
+        let (ctx, _dir) = temp_fetch_ctx(true);
+        let params = HashMap::from([(key.to_string(), value.to_string())]);
+        let args = vec!["rust-lang/rust".to_string()];
+        let err = handle_github(&args, &params, "flat", |c| c.to_string(), &ctx).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+
+    #[test]
+    fn test_unknown_locale() {
+        let (ctx, _dir) = temp_fetch_ctx(true);
+        let params = HashMap::from([("locale".to_string(), "xx".to_string())]);
+        let args = vec!["rust-lang/rust".to_string()];
+        let err = handle_github(&args, &params, "flat", |c| c.to_string(), &ctx).unwrap_err();
+        assert!(err.to_string().contains("Unknown locale 'xx'"), "{}", err);
+    }
+
+    #[rstest]
+    #[case(None)]
+    #[case(Some(r#"{"value":null,"created_at":NOW,"ttl":300}"#))]
+    fn test_placeholder_on_failure(#[case] cached: Option<&str>) {
+        let (mut ctx, dir) = temp_fetch_ctx(true);
+        ctx.set_placeholders(true);
+        if let Some(cached) = cached {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            std::fs::write(
+                dir.path().join("github_rust-lang_rust_stars.json"),
+                cached.replace("NOW", &now.to_string()),
+            )
+            .unwrap();
+        }
+
+        let args = vec!["rust-lang/rust".to_string()];
+        let output =
+            handle_github(&args, &HashMap::new(), "flat", |c| c.to_string(), &ctx).unwrap();
+        let rendered = format!("{:?}", output);
+        assert!(rendered.contains("stars: —"), "{}", rendered);
+        assert!(rendered.contains(UNAVAILABLE_COLOR), "{}", rendered);
+    }
+
     // ========================================================================
     // Missing Query Argument (Parameterized)
     // ========================================================================
@@ -582,6 +1353,14 @@ mod tests {
     #[case("packagist")]
     #[case("rubygems")]
     #[case("nuget")]
+    #[case("scorecard")]
+    #[case("osv")]
+    #[case("librariesio")]
+    #[case("discord")]
+    #[case("matrix")]
+    #[case("reddit")]
+    #[case("docsrs")]
+    #[case("readthedocs")]
     fn test_missing_query(#[case] source: &str) {
         let (ctx, _dir) = temp_fetch_ctx(true);
         let params = HashMap::new();
@@ -597,6 +1376,14 @@ mod tests {
             "packagist" => handle_packagist(&[], &params, "flat", |c| c.to_string(), &ctx),
             "rubygems" => handle_rubygems(&[], &params, "flat", |c| c.to_string(), &ctx),
             "nuget" => handle_nuget(&[], &params, "flat", |c| c.to_string(), &ctx),
+            "scorecard" => handle_scorecard(&[], &params, "flat", |c| c.to_string(), &ctx),
+            "osv" => handle_osv(&[], &params, "flat", |c| c.to_string(), &ctx),
+            "librariesio" => handle_librariesio(&[], &params, "flat", |c| c.to_string(), &ctx),
+            "discord" => handle_discord(&[], &params, "flat", |c| c.to_string(), &ctx),
+            "matrix" => handle_matrix(&[], &params, "flat", |c| c.to_string(), &ctx),
+            "reddit" => handle_reddit(&[], &params, "flat", |c| c.to_string(), &ctx),
+            "docsrs" => handle_docsrs(&[], &params, "flat", |c| c.to_string(), &ctx),
+            "readthedocs" => handle_readthedocs(&[], &params, "flat", |c| c.to_string(), &ctx),
             _ => unreachable!("Unknown source"),
         };
 
@@ -0,0 +1,128 @@
+//! Hero header component handler
+//!
+//! Composes the usual README header - title, subtitle, a row of tech badges
+//! and call-to-action links - into one block. The parser centers it in a
+//! `<div align="center">` on targets that render HTML, and leaves it as
+//! plain markdown elsewhere.
+
+use crate::components::{ComponentOutput, PostProcess};
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// Handle hero component expansion
+///
+/// - `title`: heading text (required)
+/// - `title_style`: text style applied to the title, e.g. `mathbold`
+/// - `subtitle`: line under the title
+/// - `badges`: comma-separated tech names, rendered as `{{ui:tech:NAME/}}`
+/// - `links`: comma-separated `Label|url` pairs
+pub fn handle(params: &HashMap<String, String>, style: &str) -> Result<ComponentOutput> {
+    let title = params
+        .get("title")
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| Error::ParseError("hero component requires title=...".to_string()))?;
+
+    let mut sections = vec![match params.get("title_style") {
+        Some(title_style) => format!("# {{{{{0}}}}}{1}{{{{/{0}}}}}", title_style, title),
+        None => format!("# {}", title),
+    }];
+
+    if let Some(subtitle) = params.get("subtitle").filter(|s| !s.trim().is_empty()) {
+        sections.push(subtitle.trim().to_string());
+    }
+
+    let badges: Vec<String> = list(params.get("badges"))
+        .map(|name| format!("{{{{ui:tech:{}:style={}/}}}}", name, style))
+        .collect();
+    if !badges.is_empty() {
+        sections.push(badges.join(" "));
+    }
+
+    let links: Vec<String> = list(params.get("links"))
+        .map(|link| match link.split_once('|') {
+            Some((label, url)) => format!("[{}]({})", label.trim(), url.trim()),
+            None => format!("[{0}]({0})", link),
+        })
+        .collect();
+    if !links.is_empty() {
+        sections.push(links.join(" · "));
+    }
+
+    Ok(ComponentOutput::TemplateDelayed {
+        template: sections.join("\n\n"),
+        post_process: PostProcess::Center,
+    })
+}
+
+/// Non-empty, trimmed entries of a comma-separated param
+fn list(value: Option<&String>) -> impl Iterator<Item = &str> {
+    value
+        .into_iter()
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn template(params_input: &[(&str, &str)]) -> String {
+        let params = params_input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let ComponentOutput::TemplateDelayed {
+            template,
+            post_process,
+        } = handle(&params, "flat-square").unwrap()
+        else {
+            unreachable!("Expected delayed template");
+        };
+        assert_eq!(post_process, PostProcess::Center);
+        template
+    }
+
+    #[test]
+    fn test_hero_full() {
+        let result = template(&[
+            ("title", "mdfx"),
+            ("subtitle", "Markdown effects"),
+            ("badges", "rust, docker"),
+            (
+                "links",
+                "Docs|https://docs.rs/mdfx,Demo|https://example.com",
+            ),
+        ]);
+        assert_eq!(
+            result,
+            "# mdfx\n\n\
+             Markdown effects\n\n\
+             {{ui:tech:rust:style=flat-square/}} {{ui:tech:docker:style=flat-square/}}\n\n\
+             [Docs](https://docs.rs/mdfx) · [Demo](https://example.com)"
+        );
+    }
+
+    #[rstest]
+    #[case(&[("title", "mdfx")], "# mdfx")]
+    #[case(&[("title", "mdfx"), ("title_style", "mathbold")], "# {{mathbold}}mdfx{{/mathbold}}")]
+    #[case(&[("title", "mdfx"), ("badges", ",,"), ("subtitle", " ")], "# mdfx")]
+    #[case(&[("title", "mdfx"), ("links", "https://x.dev")], "# mdfx\n\n[https://x.dev](https://x.dev)")]
+    fn test_hero_sections(#[case] params_input: &[(&str, &str)], #[case] expected: &str) {
+        assert_eq!(template(params_input), expected);
+    }
+
+    #[rstest]
+    #[case(&[])]
+    #[case(&[("title", "  ")])]
+    fn test_hero_requires_title(#[case] params_input: &[(&str, &str)]) {
+        let params = params_input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let err = handle(&params, "flat-square").unwrap_err();
+        assert!(err.to_string().contains("requires title"));
+    }
+}
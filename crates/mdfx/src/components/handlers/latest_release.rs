@@ -0,0 +1,120 @@
+//! Latest release component handler
+//!
+//! Shows the newest release from the project changelog: a version badge,
+//! the release date, and a "What's new" summary of its notes. The notes are
+//! appended as written, so templates quoted in the changelog stay literal.
+
+use super::{get_string, parse_bool, parse_param};
+use crate::changelog::latest_release;
+use crate::components::{ComponentOutput, PostProcess};
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// Handle latest-release component expansion
+///
+/// - `items`: number of entries in the summary (0 = all)
+/// - `heading`: summary heading; empty to leave it out
+/// - `notes`: `false` shows only the badge and date
+/// - `date`: `false` leaves out the release date
+pub fn handle(
+    params: &HashMap<String, String>,
+    style: &str,
+    changelog: Option<&str>,
+) -> Result<ComponentOutput> {
+    let changelog = changelog.ok_or_else(|| {
+        Error::ParseError(
+            "latest-release component requires a changelog (CHANGELOG.md or \"changelog\" in .mdfx.json)"
+                .to_string(),
+        )
+    })?;
+    let release = latest_release(changelog).ok_or_else(|| {
+        Error::ParseError(
+            "no release heading like '## [1.0.0] - 2024-01-31' in changelog".to_string(),
+        )
+    })?;
+
+    let mut badge_line = format!("{{{{ui:version:{}:style={}/}}}}", release.version, style);
+    if let Some(date) = release
+        .date
+        .as_ref()
+        .filter(|_| parse_bool(params, "date", true))
+    {
+        badge_line.push_str(&format!(" Released {}", date));
+    }
+    let mut sections = vec![badge_line];
+
+    let summary = release.summary(parse_param(params, "items", 5));
+    if !parse_bool(params, "notes", true) || summary.is_empty() {
+        return Ok(ComponentOutput::Template(sections.join("\n\n")));
+    }
+
+    let heading = get_string(params, "heading", "What's new");
+    if !heading.trim().is_empty() {
+        sections.push(format!("### {}", heading.trim()));
+    }
+    Ok(ComponentOutput::TemplateDelayed {
+        template: sections.join("\n\n"),
+        post_process: PostProcess::AppendRaw(summary),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const CHANGELOG: &str =
+        "## [Unreleased]\n\n## [1.2.0] - 2024-05-01\n\n### Added\n\n- Dark mode\n- Steps\n";
+
+    fn template(params_input: &[(&str, &str)], changelog: &str) -> String {
+        let params = params_input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        // Join the parts the way the parser does, notes last
+        match handle(&params, "flat-square", Some(changelog)).unwrap() {
+            ComponentOutput::Template(template) => template,
+            ComponentOutput::TemplateDelayed {
+                template,
+                post_process: PostProcess::AppendRaw(notes),
+            } => format!("{}\n\n{}", template, notes),
+            _ => unreachable!("Expected template"),
+        }
+    }
+
+    #[test]
+    fn test_latest_release_full() {
+        assert_eq!(
+            template(&[], CHANGELOG),
+            "{{ui:version:1.2.0:style=flat-square/}} Released 2024-05-01\n\n\
+             ### What's new\n\n\
+             **Added**\n\n\
+             - Dark mode\n\
+             - Steps"
+        );
+    }
+
+    #[rstest]
+    #[case(&[("notes", "false")], "{{ui:version:1.2.0:style=flat-square/}} Released 2024-05-01")]
+    #[case(&[("notes", "false"), ("date", "false")], "{{ui:version:1.2.0:style=flat-square/}}")]
+    #[case(&[("heading", ""), ("items", "1")], "{{ui:version:1.2.0:style=flat-square/}} Released 2024-05-01\n\n**Added**\n\n- Dark mode")]
+    fn test_latest_release_options(#[case] params_input: &[(&str, &str)], #[case] expected: &str) {
+        assert_eq!(template(params_input, CHANGELOG), expected);
+    }
+
+    #[test]
+    fn test_latest_release_without_notes_or_date() {
+        assert_eq!(
+            template(&[], "## 0.1.0\n"),
+            "{{ui:version:0.1.0:style=flat-square/}}"
+        );
+    }
+
+    #[rstest]
+    #[case(None, "requires a changelog")]
+    #[case(Some("# Changelog\n\n## [Unreleased]\n"), "no release heading")]
+    fn test_latest_release_errors(#[case] changelog: Option<&str>, #[case] message: &str) {
+        let err = handle(&HashMap::new(), "flat-square", changelog).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+}
@@ -3,6 +3,7 @@
 //! Renders license badges with category-aware coloring.
 //! Category detection and rendering is delegated to badgefx.
 
+use super::truncate_label;
 use crate::components::ComponentOutput;
 use crate::error::{Error, Result};
 use crate::primitive::{LicenseConfig, Primitive};
@@ -32,11 +33,21 @@ pub fn handle(
 
     let license = &args[0];
 
+    // A truncated label replaces the formatted name; category detection
+    // still sees the full license
+    let label = match params.get("label") {
+        Some(label) => Some(truncate_label(params, label)?),
+        None => {
+            let name = badgefx::license::format_name(license);
+            Some(truncate_label(params, &name)?).filter(|label| *label != name)
+        }
+    };
+
     // Build LicenseConfig - badgefx handles category detection
     let config = LicenseConfig {
         license: license.clone(),
         style: style.to_string(),
-        label: params.get("label").cloned(),
+        label,
         bg_color: params.get("bg").map(|c| resolve_color(c)),
         text_color: params
             .get("text")
@@ -90,6 +101,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_handle_max_width() {
+        let mut params = HashMap::new();
+        params.insert("max_width".to_string(), "8".to_string());
+
+        // BSD-3-Clause formats as "BSD 3-Clause"
+        let result = handle(
+            &["BSD-3-Clause".to_string()],
+            &params,
+            "flat",
+            identity_color,
+        );
+        if let Ok(ComponentOutput::Primitive(Primitive::License(config))) = result {
+            assert_eq!(config.label, Some("BSD 3-C…".to_string()));
+            assert_eq!(config.license, "BSD-3-Clause");
+        } else {
+            panic!("Expected License primitive");
+        }
+
+        // Short names keep the default label
+        let result = handle(&["MIT".to_string()], &params, "flat", identity_color);
+        if let Ok(ComponentOutput::Primitive(Primitive::License(config))) = result {
+            assert_eq!(config.label, None);
+        } else {
+            panic!("Expected License primitive");
+        }
+    }
+
     #[test]
     fn test_handle_with_colors() {
         let mut params = HashMap::new();
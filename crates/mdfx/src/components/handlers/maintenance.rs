@@ -0,0 +1,331 @@
+//! Maintenance status component handler
+//!
+//! Renders a project status badge with the wording of
+//! [repostatus.org](https://www.repostatus.org). `auto` picks Active or
+//! Inactive from the age of the last commit, so the badge follows the
+//! project without anyone editing it.
+
+use super::{metric_badge, parse_bool, parse_param};
+use crate::components::{ComponentOutput, PostProcess};
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// A repostatus.org status
+pub struct Status {
+    /// Name used in templates
+    pub name: &'static str,
+    /// Other names accepted in templates
+    pub aliases: &'static [&'static str],
+    /// Badge text
+    pub title: &'static str,
+    /// The repostatus.org description
+    pub description: &'static str,
+    /// Badge color
+    pub color: &'static str,
+}
+
+/// The repostatus.org statuses
+pub const STATUSES: [Status; 8] = [
+    Status {
+        name: "concept",
+        aliases: &[],
+        title: "Concept",
+        description: "Minimal or no implementation has been done yet, or the repository is only intended to be a limited example, demo, or proof-of-concept.",
+        color: "DFB317",
+    },
+    Status {
+        name: "wip",
+        aliases: &[],
+        title: "WIP",
+        description: "Initial development is in progress, but there has not yet been a stable, usable release suitable for the public.",
+        color: "DFB317",
+    },
+    Status {
+        name: "suspended",
+        aliases: &[],
+        title: "Suspended",
+        description: "Initial development has started, but there has not yet been a stable, usable release; work has been stopped for the time being but the author(s) intend on resuming work.",
+        color: "FE7D37",
+    },
+    Status {
+        name: "abandoned",
+        aliases: &[],
+        title: "Abandoned",
+        description: "Initial development has started, but there has not yet been a stable, usable release; the project has been abandoned and the author(s) do not intend on continuing development.",
+        color: "E05D44",
+    },
+    Status {
+        name: "active",
+        aliases: &[],
+        title: "Active",
+        description: "The project has reached a stable, usable state and is being actively developed.",
+        color: "44CC11",
+    },
+    Status {
+        name: "inactive",
+        aliases: &["passive"],
+        title: "Inactive",
+        description: "The project has reached a stable, usable state but is no longer being actively developed; support/maintenance will be provided as time allows.",
+        color: "FE7D37",
+    },
+    Status {
+        name: "unsupported",
+        aliases: &["deprecated"],
+        title: "Unsupported",
+        description: "The project has reached a stable, usable state but the author(s) have ceased all work on it. A new maintainer may be desired.",
+        color: "E05D44",
+    },
+    Status {
+        name: "moved",
+        aliases: &[],
+        title: "Moved",
+        description: "The project has been moved to a new location, and the version at that location should be considered authoritative.",
+        color: "E05D44",
+    },
+];
+
+/// Look up a status by name or alias
+pub fn status(name: &str) -> Option<&'static Status> {
+    STATUSES
+        .iter()
+        .find(|s| s.name == name || s.aliases.contains(&name))
+}
+
+/// Handle maintenance component expansion
+///
+/// Syntax: {{ui:maintenance:STATUS/}} or {{ui:maintenance:auto/}}
+///
+/// - `repo`: GitHub `owner/repo` whose last push `auto` uses (default:
+///   the local git history)
+/// - `active_days`: commits at most this old count as Active (default 180)
+/// - `label`: text before the status
+/// - `describe`: add the repostatus.org description below the badge
+/// - `bg`: fixed color instead of the status color
+///
+/// `today` is the build date in days since the Unix epoch, `None` when the
+/// parser's clock is unavailable. `last_commit` returns the ISO date of the
+/// last commit of `repo`, or of the local repository when `repo` is `None`.
+pub fn handle(
+    args: &[String],
+    params: &HashMap<String, String>,
+    style: &str,
+    today: Option<i64>,
+    last_commit: impl Fn(Option<&str>) -> Result<Option<String>>,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let name = args.first().map_or("auto", String::as_str);
+    let status = if name == "auto" {
+        let today = today.ok_or_else(|| {
+            Error::ParseError(
+                "maintenance:auto depends on the build date, which isn't available; set SOURCE_DATE_EPOCH to pin it".to_string(),
+            )
+        })?;
+        let repo = params.get("repo").map(String::as_str);
+        let date = last_commit(repo)?.ok_or_else(|| {
+            Error::ParseError(
+                "maintenance:auto needs repo=owner/repo or a git repository".to_string(),
+            )
+        })?;
+        let committed = date
+            .get(..10)
+            .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .ok_or_else(|| Error::ParseError(format!("Invalid last commit date '{}'", date)))?;
+        let age = today - (committed - chrono::DateTime::UNIX_EPOCH.date_naive()).num_days();
+        let active_days: i64 = parse_param(params, "active_days", 180);
+        let name = if age <= active_days {
+            "active"
+        } else {
+            "inactive"
+        };
+        status(name).expect("built-in status")
+    } else {
+        status(name).ok_or_else(|| {
+            let names: Vec<&str> = STATUSES.iter().map(|s| s.name).collect();
+            Error::ParseError(format!(
+                "Unknown maintenance status '{}'. Available: auto, {}",
+                name,
+                names.join(", ")
+            ))
+        })?
+    };
+
+    // The description follows the badge as its own paragraph; the badge is
+    // rendered by expanding the resolved status without `describe`
+    if parse_bool(params, "describe", false) {
+        let mut forwarded: Vec<String> = params
+            .iter()
+            .filter(|(key, _)| !matches!(key.as_str(), "describe" | "repo" | "active_days"))
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        forwarded.sort();
+        forwarded.push(format!("style={}", style));
+        return Ok(ComponentOutput::TemplateDelayed {
+            template: format!(
+                "{{{{ui:maintenance:{}:{}/}}}}",
+                status.name,
+                forwarded.join(":")
+            ),
+            post_process: PostProcess::AppendRaw(format!(
+                "Project Status: {} – {}",
+                status.title, status.description
+            )),
+        });
+    }
+
+    let label = params.get("label").map_or("project status", String::as_str);
+    let bg_color = params
+        .get("bg")
+        .map(|c| resolve_color(c))
+        .unwrap_or_else(|| status.color.to_string());
+    Ok(metric_badge(
+        format!("{}: {}", label, status.title),
+        bg_color,
+        params,
+        style,
+        resolve_color,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::Primitive;
+    use rstest::rstest;
+
+    /// 2024-06-13, in days since the Unix epoch
+    const TODAY: i64 = 19_887;
+
+    fn days_ago(days: u64) -> String {
+        (chrono::NaiveDate::from_ymd_opt(2024, 6, 13).unwrap() - chrono::Days::new(days))
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    fn badge(
+        args: &[&str],
+        params: &[(&str, &str)],
+        last_commit: Option<String>,
+    ) -> Result<(String, String)> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let output = handle(
+            &args,
+            &params,
+            "flat",
+            Some(TODAY),
+            |_| Ok(last_commit.clone()),
+            |c| c.to_string(),
+        )?;
+        match output {
+            ComponentOutput::Primitive(Primitive::Swatch {
+                label: Some(label),
+                color,
+                ..
+            }) => Ok((label, color)),
+            _ => unreachable!("Expected a badge"),
+        }
+    }
+
+    #[rstest]
+    #[case(&["active"], &[], "project status: Active", "44CC11")]
+    #[case(&["passive"], &[], "project status: Inactive", "FE7D37")]
+    #[case(&["deprecated"], &[], "project status: Unsupported", "E05D44")]
+    #[case(&["wip"], &[("label", "repo"), ("bg", "accent")], "repo: WIP", "accent")]
+    fn test_maintenance_status(
+        #[case] args: &[&str],
+        #[case] params: &[(&str, &str)],
+        #[case] label: &str,
+        #[case] color: &str,
+    ) {
+        let (badge_label, badge_color) = badge(args, params, None).unwrap();
+        assert_eq!(badge_label, label);
+        assert_eq!(badge_color, color);
+    }
+
+    #[rstest]
+    #[case(10, &[], "Active")]
+    #[case(400, &[], "Inactive")]
+    #[case(100, &[("active_days", "90")], "Inactive")]
+    #[case(180, &[], "Active")]
+    #[case(181, &[], "Inactive")]
+    fn test_maintenance_auto(
+        #[case] age: u64,
+        #[case] params: &[(&str, &str)],
+        #[case] expected: &str,
+    ) {
+        let (label, _) = badge(&["auto"], params, Some(days_ago(age))).unwrap();
+        assert_eq!(label, format!("project status: {}", expected));
+        let (label, _) = badge(&[], params, Some(format!("{}T12:00:00Z", days_ago(age)))).unwrap();
+        assert_eq!(label, format!("project status: {}", expected));
+    }
+
+    #[test]
+    fn test_maintenance_describe() {
+        let params = HashMap::from([
+            ("describe".to_string(), "true".to_string()),
+            ("label".to_string(), "status".to_string()),
+        ]);
+        let output = handle(
+            &["passive".to_string()],
+            &params,
+            "flat",
+            Some(TODAY),
+            |_| Ok(None),
+            |c| c.to_string(),
+        )
+        .unwrap();
+        let ComponentOutput::TemplateDelayed {
+            template,
+            post_process: PostProcess::AppendRaw(description),
+        } = output
+        else {
+            panic!("Expected a template with the description");
+        };
+        assert_eq!(
+            template,
+            "{{ui:maintenance:inactive:label=status:style=flat/}}"
+        );
+        assert!(description.starts_with("Project Status: Inactive – The project has reached"));
+    }
+
+    #[rstest]
+    #[case(&["retired"], None, "Unknown maintenance status 'retired'")]
+    #[case(&["auto"], None, "needs repo=owner/repo or a git repository")]
+    #[case(&["auto"], Some("yesterday"), "Invalid last commit date")]
+    fn test_maintenance_errors(
+        #[case] args: &[&str],
+        #[case] last_commit: Option<&str>,
+        #[case] message: &str,
+    ) {
+        let err = badge(args, &[], last_commit.map(String::from)).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+
+    #[test]
+    fn test_maintenance_auto_without_clock() {
+        let err = handle(
+            &[],
+            &HashMap::new(),
+            "flat",
+            None,
+            |_| Ok(Some(days_ago(1))),
+            |c| c.to_string(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("SOURCE_DATE_EPOCH"), "{}", err);
+
+        // Explicit statuses don't need the date
+        let output = handle(
+            &["active".to_string()],
+            &HashMap::new(),
+            "flat",
+            None,
+            |_| unreachable!("no commit lookup"),
+            |c| c.to_string(),
+        );
+        assert!(output.is_ok());
+    }
+}
@@ -0,0 +1,229 @@
+//! Comparison matrix component handler
+//!
+//! Turns `Feature | ours | theirs` rows into a markdown table. Cells that say
+//! `yes`, `no` or `partial` become colored check/cross/tilde swatches; any
+//! other cell is kept as text.
+
+use super::resolve_color_with_default;
+use crate::components::{ComponentOutput, PostProcess};
+use crate::error::{Error, Result};
+use crate::primitive::Primitive;
+use std::collections::HashMap;
+
+/// A parsed comparison matrix, rendered by the parser
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixTable {
+    /// Column headings, first one labels the feature column
+    pub header: Vec<String>,
+    /// Body rows, each as wide as the header
+    pub rows: Vec<Vec<MatrixCell>>,
+}
+
+/// One cell of a comparison matrix
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatrixCell {
+    /// Free text, parsed as a template
+    Text(String),
+    /// A yes/no/partial mark
+    Mark {
+        /// Swatch shown by image backends
+        badge: Box<Primitive>,
+        /// Glyph shown by text-only backends
+        text: &'static str,
+    },
+}
+
+/// Mark glyphs and their color params: (glyph, param, default color)
+const YES: (&str, &str, &str) = ("✓", "yes", "success");
+const NO: (&str, &str, &str) = ("✗", "no", "error");
+const PARTIAL: (&str, &str, &str) = ("~", "partial", "warning");
+
+/// Handle matrix component expansion
+pub fn handle(
+    params: &HashMap<String, String>,
+    content: Option<&str>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let mut lines = content
+        .unwrap_or("")
+        .lines()
+        .map(split_row)
+        .filter(|cells| !cells.is_empty() && !is_separator(cells));
+
+    let header = lines.next().ok_or_else(|| {
+        Error::ParseError(
+            "matrix component requires a header row like 'Feature | A | B'".to_string(),
+        )
+    })?;
+
+    let mark = |(glyph, key, default): (&'static str, &str, &str)| {
+        let color = resolve_color_with_default(params, key, default, &resolve_color);
+        let mut badge = Primitive::simple_swatch(color, style);
+        if let Primitive::Swatch { label, .. } = &mut badge {
+            *label = Some(glyph.to_string());
+        }
+        MatrixCell::Mark {
+            badge: Box::new(badge),
+            text: glyph,
+        }
+    };
+
+    let rows = lines
+        .map(|cells| {
+            (0..header.len())
+                .map(|i| {
+                    let cell = cells.get(i).map(String::as_str).unwrap_or("");
+                    match cell.to_lowercase().as_str() {
+                        _ if i == 0 => MatrixCell::Text(cell.to_string()),
+                        "yes" | "y" | "true" | "✓" => mark(YES),
+                        "no" | "n" | "false" | "✗" => mark(NO),
+                        "partial" | "some" | "~" => mark(PARTIAL),
+                        _ => MatrixCell::Text(cell.to_string()),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(ComponentOutput::TemplateDelayed {
+        template: String::new(),
+        post_process: PostProcess::Matrix(MatrixTable { header, rows }),
+    })
+}
+
+/// Build the markdown table from rendered cells
+///
+/// The feature column is left-aligned, every other column centered.
+pub fn to_markdown(header: &[String], rows: &[Vec<String>]) -> String {
+    let line = |cells: &[String]| format!("| {} |", cells.join(" | "));
+    let separator: Vec<String> = (0..header.len())
+        .map(|i| if i == 0 { "---" } else { ":---:" }.to_string())
+        .collect();
+
+    let mut table = vec![line(header), line(&separator)];
+    table.extend(rows.iter().map(|row| line(row)));
+    table.join("\n")
+}
+
+/// Split a `a | b | c` line into trimmed cells, ignoring outer pipes
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Vec::new();
+    }
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|')
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+/// Whether a row is a markdown table separator like `---|:---:`
+fn is_separator(cells: &[String]) -> bool {
+    cells.iter().all(|cell| {
+        let dashes = cell.trim_matches(':');
+        !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn identity_color(c: &str) -> String {
+        c.to_string()
+    }
+
+    fn table(content: &str, params: &[(&str, &str)]) -> MatrixTable {
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let ComponentOutput::TemplateDelayed {
+            post_process: PostProcess::Matrix(table),
+            ..
+        } = handle(&params, Some(content), "flat-square", identity_color).unwrap()
+        else {
+            panic!("Expected matrix post-process");
+        };
+        table
+    }
+
+    fn marks(row: &[MatrixCell]) -> Vec<&str> {
+        row.iter()
+            .map(|cell| match cell {
+                MatrixCell::Text(text) => text.as_str(),
+                MatrixCell::Mark { text, .. } => text,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matrix_rows() {
+        let table = table(
+            "Feature | ours | theirs\n\
+             Unicode | yes | no\n\
+             SVG | YES | partial\n\
+             Price | free | $10",
+            &[],
+        );
+        assert_eq!(table.header, vec!["Feature", "ours", "theirs"]);
+        assert_eq!(marks(&table.rows[0]), vec!["Unicode", "✓", "✗"]);
+        assert_eq!(marks(&table.rows[1]), vec!["SVG", "✓", "~"]);
+        assert_eq!(marks(&table.rows[2]), vec!["Price", "free", "$10"]);
+    }
+
+    #[test]
+    fn test_matrix_markdown_syntax_and_ragged_rows() {
+        let table = table(
+            "| Feature | a | b |\n|---|:---:|:---:|\n\n| yes | no |\n| x | y | n | extra |",
+            &[],
+        );
+        assert_eq!(table.rows.len(), 2);
+        // The feature column is never a mark; missing cells are empty
+        assert_eq!(marks(&table.rows[0]), vec!["yes", "✗", ""]);
+        assert_eq!(marks(&table.rows[1]), vec!["x", "✓", "✗"]);
+    }
+
+    #[rstest]
+    #[case(&[], "success")]
+    #[case(&[("yes", "info")], "info")]
+    fn test_matrix_mark_badge(#[case] params: &[(&str, &str)], #[case] color: &str) {
+        let table = table("F | a\nx | yes", params);
+        let MatrixCell::Mark { badge, .. } = &table.rows[0][1] else {
+            panic!("Expected mark");
+        };
+        let Primitive::Swatch {
+            color: c,
+            label,
+            style,
+            ..
+        } = badge.as_ref()
+        else {
+            panic!("Expected swatch");
+        };
+        assert_eq!(c, color);
+        assert_eq!(label.as_deref(), Some("✓"));
+        assert_eq!(style, "flat-square");
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("\n---|---\n")]
+    fn test_matrix_requires_header(#[case] content: &str) {
+        let err = handle(&HashMap::new(), Some(content), "flat", identity_color).unwrap_err();
+        assert!(err.to_string().contains("header row"));
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let header = vec!["Feature".to_string(), "ours".to_string()];
+        let rows = vec![vec!["SVG".to_string(), "✓".to_string()]];
+        assert_eq!(
+            to_markdown(&header, &rows),
+            "| Feature | ours |\n| --- | :---: |\n| SVG | ✓ |"
+        );
+    }
+}
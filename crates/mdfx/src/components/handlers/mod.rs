@@ -164,7 +164,35 @@ pub fn get_string(params: &HashMap<String, String>, key: &str, default: &str) ->
         .unwrap_or_else(|| default.to_string())
 }
 
-use crate::primitive::ThumbConfig;
+/// Apply the `max_width` and `truncate` parameters to a badge label.
+///
+/// Without `max_width` the label is returned unchanged. An unknown
+/// `truncate` policy is an error.
+///
+/// # Example
+/// ```ignore
+/// let label = truncate_label(&params, &name)?;
+/// ```
+pub fn truncate_label(params: &HashMap<String, String>, label: &str) -> Result<String> {
+    let Some(max_width) = parse_param_opt_clamped(params, "max_width", 1, 1000) else {
+        return Ok(label.to_string());
+    };
+    let policy = match params.get("truncate") {
+        Some(name) => Truncate::parse(name).ok_or_else(|| {
+            Error::ParseError(format!(
+                "Unknown truncate policy '{}'. Available: ellipsis, middle, none",
+                name
+            ))
+        })?,
+        None => Truncate::default(),
+    };
+    Ok(truncate(label, max_width, policy))
+}
+
+use crate::components::ComponentOutput;
+use crate::error::{Error, Result};
+use crate::primitive::{Primitive, ThumbConfig};
+use crate::width::{truncate, Truncate};
 
 /// Parse thumb configuration from parameters.
 ///
@@ -194,25 +222,107 @@ pub fn parse_thumb_config(
     })
 }
 
+/// A `label: value` badge, as used by live metrics and `{{ui:coverage/}}`
+///
+/// Handles the shared `text`, `width`, `border`, `border_width`, `rx` and
+/// `icon` parameters.
+pub fn metric_badge(
+    display_label: String,
+    bg_color: String,
+    params: &HashMap<String, String>,
+    style: &str,
+    resolve_color: impl Fn(&str) -> String,
+) -> ComponentOutput {
+    // Text color (default white for most colors, black for yellow/bright)
+    let text_color = params
+        .get("text")
+        .or_else(|| params.get("text_color"))
+        .map(|c| resolve_color(c))
+        .unwrap_or_else(|| {
+            // Use black text for yellow-ish backgrounds
+            if bg_color == "EAB308" || bg_color == "FFD700" || bg_color == "FFD43B" {
+                "000000".to_string()
+            } else {
+                "FFFFFF".to_string()
+            }
+        });
+
+    // Calculate width (approx 7px per char + 16px padding)
+    let estimated_width = params
+        .get("width")
+        .and_then(|w| w.parse().ok())
+        .unwrap_or_else(|| (display_label.len() as u32 * 7 + 16).max(50));
+
+    // Border parameters
+    let border_color = params.get("border").map(|c| resolve_color(c));
+    let border_width = params.get("border_width").and_then(|v| v.parse().ok());
+
+    // Corner radius - default to 3
+    let rx = params.get("rx").and_then(|v| v.parse().ok()).or(Some(3));
+
+    // Optional icon
+    let icon = params.get("icon").cloned();
+
+    ComponentOutput::Primitive(Primitive::Swatch {
+        color: bg_color,
+        style: style.to_string(),
+        opacity: None,
+        width: Some(estimated_width),
+        height: None,
+        border_color,
+        border_width,
+        label: Some(display_label),
+        label_color: Some(text_color),
+        icon,
+        icon_color: None,
+        rx,
+        ry: None,
+        shadow: None,
+        gradient: None,
+        stroke_dash: None,
+        logo_size: None,
+        border_top: None,
+        border_right: None,
+        border_bottom: None,
+        border_left: None,
+    })
+}
+
+pub mod bench;
+pub mod ci_matrix;
+pub mod coverage;
+pub mod divider;
 pub mod donut;
 pub mod gauge;
-#[cfg(feature = "fetch")]
 pub mod github;
+pub mod hero;
+pub mod latest_release;
 pub mod license;
+pub mod maintenance;
+pub mod matrix;
 pub mod progress;
+pub mod project;
 pub mod rating;
 pub mod row;
 pub mod sparkline;
+pub mod statcard;
+pub mod steps;
 pub mod swatch;
 pub mod tech;
 pub mod tech_group;
+pub mod test_results;
+pub mod tree;
 pub mod version;
 pub mod waveform;
 
+pub use github::handle_placeholder;
 #[cfg(feature = "fetch")]
 pub use github::{
-    handle_actions, handle_codecov, handle_crates, handle_docker, handle_github, handle_npm,
-    handle_nuget, handle_packagist, handle_pypi, handle_rubygems, FetchContext,
+    handle_actions, handle_codecov, handle_crates, handle_discord, handle_docker, handle_docsrs,
+    handle_github, handle_librariesio, handle_matrix, handle_npm, handle_nuget,
+    handle_opencollective, handle_osv, handle_packagist, handle_patreon, handle_pypi,
+    handle_readthedocs, handle_reddit, handle_rubygems, handle_scorecard, handle_sponsors,
+    FetchContext,
 };
 
 #[cfg(test)]
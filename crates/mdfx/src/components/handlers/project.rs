@@ -0,0 +1,137 @@
+//! Project toolchain badge handlers
+//!
+//! `msrv`, `node-engines` and `python-requires` read a version requirement
+//! from the project manifests and render it as a tech badge, so the badge
+//! follows `Cargo.toml`, `package.json` or `pyproject.toml` without manual
+//! updates.
+
+use super::tech;
+use crate::components::ComponentOutput;
+use crate::error::{Error, Result};
+use crate::project::{
+    node_engines, python_requires, rust_version, CARGO_TOML, PACKAGE_JSON, PYPROJECT_TOML,
+};
+use std::collections::HashMap;
+
+/// Handle msrv / node-engines / python-requires expansion
+///
+/// `label` replaces the text before the version; all other parameters are
+/// passed to the tech badge.
+pub fn handle(
+    component: &str,
+    params: &HashMap<String, String>,
+    style: &str,
+    read_file: impl Fn(&str) -> Option<String>,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let (file, key, read, logo, label): (_, _, fn(&str) -> Option<String>, _, _) = match component {
+        "msrv" => (CARGO_TOML, "rust-version", rust_version, "rust", "MSRV"),
+        "node-engines" => (PACKAGE_JSON, "engines.node", node_engines, "nodejs", "node"),
+        "python-requires" => (
+            PYPROJECT_TOML,
+            "requires-python",
+            python_requires,
+            "python",
+            "python",
+        ),
+        _ => {
+            return Err(Error::ParseError(format!(
+                "'{}' is not a project badge",
+                component
+            )))
+        }
+    };
+
+    let manifest = read_file(file).ok_or_else(|| {
+        Error::ParseError(format!(
+            "{} component requires {} in the project root",
+            component, file
+        ))
+    })?;
+    let version =
+        read(&manifest).ok_or_else(|| Error::ParseError(format!("no {} in {}", key, file)))?;
+
+    let mut params = params.clone();
+    let label = params.get("label").map_or(label, String::as_str);
+    let label = format!("{} {}", label, version).trim().to_string();
+    params.insert("label".to_string(), label);
+
+    tech::handle(&[logo.to_string()], &params, style, resolve_color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::Primitive;
+    use rstest::rstest;
+
+    fn identity_color(c: &str) -> String {
+        c.to_string()
+    }
+
+    fn manifests() -> HashMap<String, String> {
+        HashMap::from([
+            (
+                CARGO_TOML.to_string(),
+                "[package]\nrust-version = \"1.70\"\n".to_string(),
+            ),
+            (
+                PACKAGE_JSON.to_string(),
+                r#"{"engines": {"node": ">=18"}}"#.to_string(),
+            ),
+            (
+                PYPROJECT_TOML.to_string(),
+                "[project]\nrequires-python = \">=3.9\"\n".to_string(),
+            ),
+        ])
+    }
+
+    #[rstest]
+    #[case("msrv", &[], "rust", "MSRV 1.70")]
+    #[case("node-engines", &[], "nodejs", "node >=18")]
+    #[case("python-requires", &[], "python", "python >=3.9")]
+    #[case("msrv", &[("label", "rustc")], "rust", "rustc 1.70")]
+    #[case("msrv", &[("label", "")], "rust", "1.70")]
+    fn test_project_badge(
+        #[case] component: &str,
+        #[case] params_input: &[(&str, &str)],
+        #[case] logo: &str,
+        #[case] label: &str,
+    ) {
+        let params = params_input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let manifests = manifests();
+        let read_file = |name: &str| manifests.get(name).cloned();
+        let output = handle(component, &params, "flat", read_file, identity_color).unwrap();
+        let ComponentOutput::Primitive(Primitive::Tech(config)) = output else {
+            panic!("Expected Tech primitive");
+        };
+        assert_eq!(config.name, logo);
+        assert_eq!(config.label.as_deref(), Some(label));
+    }
+
+    #[rstest]
+    #[case("msrv", HashMap::new(), "requires Cargo.toml")]
+    #[case(
+        "python-requires",
+        HashMap::from([(PYPROJECT_TOML.to_string(), "[project]\nname = \"demo\"\n".to_string())]),
+        "no requires-python in pyproject.toml"
+    )]
+    fn test_project_badge_errors(
+        #[case] component: &str,
+        #[case] manifests: HashMap<String, String>,
+        #[case] message: &str,
+    ) {
+        let err = handle(
+            component,
+            &HashMap::new(),
+            "flat",
+            |name: &str| manifests.get(name).cloned(),
+            identity_color,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+}
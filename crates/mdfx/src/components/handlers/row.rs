@@ -2,6 +2,7 @@
 
 use crate::components::{ComponentOutput, PostProcess};
 use crate::error::Result;
+use crate::escape::{escape_attr, sanitize_url};
 use std::collections::HashMap;
 
 /// Handle row component expansion
@@ -31,7 +32,8 @@ pub fn handle(params: &HashMap<String, String>, content: Option<&str>) -> Result
 ///
 /// This is called AFTER recursive parsing to transform rendered content:
 /// 1. Collapses whitespace/newlines to single spaces
-/// 2. Converts markdown images `![alt](url)` to HTML `<img alt="alt" src="url">`
+/// 2. Converts markdown images `![alt](url)` to HTML `<img alt="alt" src="url">`,
+///    escaping both attributes
 /// 3. Wraps with `<p align="...">...</p>`
 ///
 /// This is necessary because GitHub Flavored Markdown doesn't parse
@@ -62,8 +64,24 @@ pub fn apply_row(content: &str, align: &str) -> String {
                 if let Some(url_end) = after_paren.find(')') {
                     let url = &after_paren[..url_end];
 
+                    // Keep an `=WxH` size suffix as width/height attributes
+                    let (url, size) = match url.rsplit_once(" =") {
+                        Some((url, size)) => match size.split_once('x') {
+                            Some((w, h)) if is_number(w) && is_number(h) => {
+                                (url, format!(r#" width="{}" height="{}""#, w, h))
+                            }
+                            _ => (url, String::new()),
+                        },
+                        None => (url, String::new()),
+                    };
+
                     // Convert to HTML img tag
-                    result.push_str(&format!(r#"<img alt="{}" src="{}">"#, alt, url));
+                    result.push_str(&format!(
+                        r#"<img alt="{}" src="{}"{}>"#,
+                        escape_attr(alt),
+                        escape_attr(&sanitize_url(url)),
+                        size
+                    ));
                     remaining = &after_paren[url_end + 1..];
                     continue;
                 }
@@ -83,7 +101,11 @@ pub fn apply_row(content: &str, align: &str) -> String {
         r#"<p align="{}">
 {}
 </p>"#,
-        align,
+        escape_attr(align),
         result.trim()
     )
 }
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
@@ -0,0 +1,174 @@
+//! Stat card component handlers (`statcard` and the `statcard-row` wrapper)
+
+use super::tech_group::parse_layout;
+use super::{get_string, parse_param_clamped, resolve_color_with_default};
+use crate::components::{ComponentOutput, PostProcess};
+use crate::error::{Error, Result};
+use crate::primitive::Primitive;
+use std::collections::HashMap;
+
+/// Gap between cards in a `statcard-row` unless `gap=` is given
+const ROW_GAP: u32 = 8;
+
+/// Handle statcard component expansion
+pub fn handle(
+    params: &HashMap<String, String>,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let value = params
+        .get("value")
+        .filter(|v| !v.trim().is_empty())
+        .ok_or_else(|| Error::ParseError("statcard component requires value=...".to_string()))?;
+
+    // Width: 80-400px, Height: 48-200px, Corner radius: 0-40px
+    let width: u32 = parse_param_clamped(params, "width", 160, 80, 400);
+    let height: u32 = parse_param_clamped(params, "height", 80, 48, 200);
+    let rx: u32 = parse_param_clamped(params, "rx", 8, 0, 40);
+
+    let label_color = resolve_color_with_default(params, "label_color", "gray", &resolve_color);
+    let trend = params.get("trend").filter(|t| !t.is_empty()).cloned();
+    let trend_color = match trend.as_deref().and_then(|t| t.chars().next()) {
+        Some('+') => resolve_color_with_default(params, "up", "success", &resolve_color),
+        Some('-') => resolve_color_with_default(params, "down", "error", &resolve_color),
+        _ => label_color.clone(),
+    };
+
+    Ok(ComponentOutput::Primitive(Primitive::StatCard {
+        label: get_string(params, "label", ""),
+        value: value.clone(),
+        icon: params.get("icon").cloned(),
+        trend,
+        width,
+        height,
+        rx,
+        bg_color: resolve_color_with_default(params, "bg", "dark3", &resolve_color),
+        value_color: resolve_color_with_default(params, "color", "white", &resolve_color),
+        label_color,
+        icon_color: resolve_color_with_default(params, "accent", "accent", &resolve_color),
+        trend_color,
+    }))
+}
+
+/// Handle statcard-row expansion: lay the cards out side by side as one image
+pub fn handle_row(
+    params: &HashMap<String, String>,
+    content: Option<&str>,
+) -> Result<ComponentOutput> {
+    let mut layout = parse_layout(params).unwrap_or_default();
+    if !params.contains_key("gap") {
+        layout.gap = ROW_GAP;
+    }
+
+    Ok(ComponentOutput::TemplateDelayed {
+        template: content.unwrap_or("").to_string(),
+        post_process: PostProcess::Group(layout),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::GroupLayout;
+    use rstest::rstest;
+
+    fn identity_color(c: &str) -> String {
+        c.to_string()
+    }
+
+    fn params(input: &[(&str, &str)]) -> HashMap<String, String> {
+        input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_handle_defaults() {
+        let result = handle(&params(&[("value", "1.2M")]), identity_color).unwrap();
+        let ComponentOutput::Primitive(Primitive::StatCard {
+            label,
+            value,
+            icon,
+            trend,
+            width,
+            height,
+            rx,
+            bg_color,
+            icon_color,
+            ..
+        }) = result
+        else {
+            panic!("Expected StatCard primitive");
+        };
+        assert_eq!(label, "");
+        assert_eq!(value, "1.2M");
+        assert_eq!((icon, trend), (None, None));
+        assert_eq!((width, height, rx), (160, 80, 8));
+        assert_eq!(bg_color, "dark3");
+        assert_eq!(icon_color, "accent");
+    }
+
+    #[rstest]
+    #[case(&[], "gray")]
+    #[case(&[("trend", "+8%")], "success")]
+    #[case(&[("trend", "-3%")], "error")]
+    #[case(&[("trend", "0%")], "gray")]
+    #[case(&[("trend", "+8%"), ("up", "info")], "info")]
+    #[case(&[("trend", "-3%"), ("down", "warning")], "warning")]
+    fn test_handle_trend_color(#[case] input: &[(&str, &str)], #[case] expected: &str) {
+        let mut params = params(input);
+        params.insert("value".to_string(), "42".to_string());
+        let Ok(ComponentOutput::Primitive(Primitive::StatCard { trend_color, .. })) =
+            handle(&params, identity_color)
+        else {
+            panic!("Expected StatCard primitive");
+        };
+        assert_eq!(trend_color, expected);
+    }
+
+    #[rstest]
+    #[case("width", "10", 80)]
+    #[case("width", "1000", 400)]
+    #[case("height", "10", 48)]
+    #[case("rx", "99", 40)]
+    fn test_handle_clamping(#[case] key: &str, #[case] value: &str, #[case] expected: u32) {
+        let params = params(&[("value", "42"), (key, value)]);
+        let Ok(ComponentOutput::Primitive(Primitive::StatCard {
+            width, height, rx, ..
+        })) = handle(&params, identity_color)
+        else {
+            panic!("Expected StatCard primitive");
+        };
+        let actual = match key {
+            "width" => width,
+            "height" => height,
+            _ => rx,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(&[])]
+    #[case(&[("label", "Downloads"), ("value", " ")])]
+    fn test_handle_requires_value(#[case] input: &[(&str, &str)]) {
+        let err = handle(&params(input), identity_color).unwrap_err();
+        assert!(err.to_string().contains("requires value"));
+    }
+
+    #[rstest]
+    #[case(&[], GroupLayout { gap: 8, ..Default::default() })]
+    #[case(&[("gap", "0"), ("columns", "2")], GroupLayout { columns: Some(2), ..Default::default() })]
+    #[case(&[("align", "center")], GroupLayout { gap: 8, align: "center".to_string(), ..Default::default() })]
+    fn test_handle_row_layout(#[case] input: &[(&str, &str)], #[case] expected: GroupLayout) {
+        let result = handle_row(&params(input), Some("{{ui:statcard:value=1/}}")).unwrap();
+        let ComponentOutput::TemplateDelayed {
+            template,
+            post_process: PostProcess::Group(layout),
+        } = result
+        else {
+            panic!("Expected group layout");
+        };
+        assert_eq!(template, "{{ui:statcard:value=1/}}");
+        assert_eq!(layout, expected);
+    }
+}
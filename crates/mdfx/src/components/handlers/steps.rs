@@ -0,0 +1,142 @@
+//! Steps component handler
+//!
+//! Turns a list of steps (`1. Install`, `- Install` or bare lines) into
+//! numbered markers joined by connecting lines.
+
+use super::{parse_param_clamped, parse_param_opt, resolve_color_with_default};
+use crate::components::ComponentOutput;
+use crate::error::{Error, Result};
+use crate::primitive::Primitive;
+use std::collections::HashMap;
+
+/// Handle steps component expansion
+pub fn handle(
+    params: &HashMap<String, String>,
+    content: Option<&str>,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let items: Vec<String> = content
+        .unwrap_or("")
+        .lines()
+        .map(strip_marker)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect();
+    if items.is_empty() {
+        return Err(Error::ParseError(
+            "steps component requires at least one step".to_string(),
+        ));
+    }
+
+    // Current step: 1..=steps, anything else means no current step
+    let current = parse_param_opt(params, "current").filter(|c| (1..=items.len()).contains(c));
+
+    // Marker size: 16-64px, Spacing between markers: 60-300px
+    let size: u32 = parse_param_clamped(params, "size", 28, 16, 64);
+    let spacing: u32 = parse_param_clamped(params, "spacing", 120, 60, 300);
+
+    Ok(ComponentOutput::Primitive(Primitive::Steps {
+        items,
+        current,
+        size,
+        spacing,
+        color: resolve_color_with_default(params, "color", "accent", &resolve_color),
+        pending_color: resolve_color_with_default(params, "pending", "slate", &resolve_color),
+        text_color: resolve_color_with_default(params, "text_color", "white", &resolve_color),
+        label_color: resolve_color_with_default(params, "label_color", "gray", &resolve_color),
+    }))
+}
+
+/// Strip a list marker (`1.`, `1)`, `-`, `*`) from a step line
+fn strip_marker(line: &str) -> &str {
+    let line = line.trim();
+    let rest = line.trim_start_matches(|c: char| c.is_ascii_digit());
+    let rest = if rest.len() < line.len() {
+        rest.strip_prefix(['.', ')'])
+    } else {
+        rest.strip_prefix(['-', '*'])
+    };
+    match rest {
+        Some(rest) if rest.is_empty() || rest.starts_with(char::is_whitespace) => rest.trim(),
+        _ => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn identity_color(c: &str) -> String {
+        c.to_string()
+    }
+
+    fn steps(content: &str, params: &[(&str, &str)]) -> Primitive {
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let ComponentOutput::Primitive(primitive) =
+            handle(&params, Some(content), identity_color).unwrap()
+        else {
+            panic!("Expected primitive");
+        };
+        primitive
+    }
+
+    #[rstest]
+    #[case("1. Install", "Install")]
+    #[case("12) Configure", "Configure")]
+    #[case("- Ship", "Ship")]
+    #[case("* Ship", "Ship")]
+    #[case("Ship it", "Ship it")]
+    #[case("2024 release", "2024 release")]
+    #[case("-v flag", "-v flag")]
+    fn test_strip_marker(#[case] line: &str, #[case] expected: &str) {
+        assert_eq!(strip_marker(line), expected);
+    }
+
+    #[test]
+    fn test_steps_defaults() {
+        let Primitive::Steps {
+            items,
+            current,
+            size,
+            spacing,
+            color,
+            pending_color,
+            ..
+        } = steps("1. Install\n2. Configure\n\n3. Ship", &[])
+        else {
+            panic!("Expected Steps primitive");
+        };
+        assert_eq!(items, vec!["Install", "Configure", "Ship"]);
+        assert_eq!(current, None);
+        assert_eq!((size, spacing), (28, 120));
+        assert_eq!(
+            (color.as_str(), pending_color.as_str()),
+            ("accent", "slate")
+        );
+    }
+
+    #[rstest]
+    #[case("2", Some(2))]
+    #[case("3", Some(3))]
+    #[case("0", None)]
+    #[case("4", None)]
+    #[case("two", None)]
+    fn test_steps_current(#[case] value: &str, #[case] expected: Option<usize>) {
+        let Primitive::Steps { current, .. } = steps("a\nb\nc", &[("current", value)]) else {
+            panic!("Expected Steps primitive");
+        };
+        assert_eq!(current, expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("1.\n-\n")]
+    fn test_steps_requires_items(#[case] content: &str) {
+        let err = handle(&HashMap::new(), Some(content), identity_color).unwrap_err();
+        assert!(err.to_string().contains("at least one step"));
+    }
+}
@@ -1,5 +1,6 @@
 //! Swatch component handler - colored rectangles
 
+use super::truncate_label;
 use crate::components::ComponentOutput;
 use crate::error::{Error, Result};
 use crate::primitive::Primitive;
@@ -31,7 +32,10 @@ pub fn handle(
     let border_width = params
         .get("border_width")
         .and_then(|v| v.parse::<u32>().ok());
-    let label = params.get("label").cloned();
+    let label = params
+        .get("label")
+        .map(|label| truncate_label(params, label))
+        .transpose()?;
     let label_color = params.get("label_color").map(|v| resolve_color(v));
     let icon = params.get("icon").cloned();
     let icon_color = params.get("icon_color").map(|v| resolve_color(v));
@@ -3,6 +3,7 @@
 //! Parameter definitions are centralized in [`crate::components::params::TECH_PARAMS`]
 //! which is the single source of truth for both the renderer and LSP.
 
+use super::truncate_label;
 use crate::components::ComponentOutput;
 use crate::error::{Error, Result};
 use crate::primitive::{Primitive, TechConfig};
@@ -94,7 +95,10 @@ pub fn handle(
         .unwrap_or_else(|| get_logo_color_for_bg(&bg_color).to_string());
 
     // Default label to tech name for shields.io style badges
-    let label = params.get("label").cloned().or_else(|| Some(name.clone()));
+    let label = Some(truncate_label(
+        params,
+        params.get("label").unwrap_or(&name),
+    )?);
     let border_color = params.get("border").map(|c| resolve_color(c));
     let border_width = params.get("border_width").and_then(|v| v.parse().ok());
     let border_full = params
@@ -237,4 +241,26 @@ mod tests {
             panic!("Expected Tech primitive");
         }
     }
+
+    #[test]
+    fn test_max_width_truncates_label() {
+        let mut params = HashMap::new();
+        params.insert("max_width".to_string(), "7".to_string());
+
+        let result = handle(&["TypeScript".to_string()], &params, "flat", resolve_color);
+        if let ComponentOutput::Primitive(Primitive::Tech(config)) = result.unwrap() {
+            assert_eq!(config.label, Some("TypeSc…".to_string()));
+        } else {
+            panic!("Expected Tech primitive");
+        }
+
+        params.insert("truncate".to_string(), "middle".to_string());
+        let result = handle(&["TypeScript".to_string()], &params, "flat", resolve_color);
+        if let ComponentOutput::Primitive(Primitive::Tech(config)) = result.unwrap() {
+            assert_eq!(config.label, Some("Typ…ipt".to_string()));
+            assert_eq!(config.name, "TypeScript");
+        } else {
+            panic!("Expected Tech primitive");
+        }
+    }
 }
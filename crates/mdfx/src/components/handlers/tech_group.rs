@@ -11,14 +11,22 @@
 //! unless the badge specifies its own value.
 //!
 //! This creates a seamless "pill" group when badges are placed side-by-side.
+//!
+//! Layout params (`columns`, `wrap`, `gap`, `max_width`, `align`) arrange the
+//! badges in rows instead. The backend then renders them as one group: a
+//! single composed SVG, or HTML rows for shields.io badges.
 
-use crate::components::ComponentOutput;
+use crate::components::{ComponentOutput, PostProcess};
 use crate::error::Result;
+use crate::primitive::GroupLayout;
 use std::collections::HashMap;
 
 /// Component types that can participate in badge groups
 const GROUPABLE_COMPONENTS: &[&str] = &["tech", "version", "license"];
 
+/// Group params that configure the layout instead of being inherited
+const LAYOUT_PARAMS: &[&str] = &["columns", "wrap", "gap", "max_width", "align"];
+
 /// Find all groupable component invocations in content.
 /// Returns (start, end) positions for each match.
 fn find_groupable_components(content: &str) -> Vec<(usize, usize)> {
@@ -59,6 +67,9 @@ fn find_groupable_components(content: &str) -> Vec<(usize, usize)> {
 ///
 /// Style inheritance: Any params on the group (bg, border, text_color, etc.)
 /// are inherited by child badges unless the badge specifies its own value.
+///
+/// With layout params, corners are left to the layout, which squares them
+/// per row.
 pub fn handle(params: &HashMap<String, String>, content: Option<&str>) -> Result<ComponentOutput> {
     let content = content.unwrap_or("");
 
@@ -71,16 +82,12 @@ pub fn handle(params: &HashMap<String, String>, content: Option<&str>) -> Result
         return Ok(ComponentOutput::Template(content.to_string()));
     }
 
-    // Extract gap parameter (spacing between badges in pixels, for row layout)
-    let gap = params
-        .get("gap")
-        .and_then(|v| v.parse::<u32>().ok())
-        .unwrap_or(0);
+    let layout = parse_layout(params);
 
-    // Collect all params from group (except group-only params like "gap")
+    // Collect all params from group (except layout params like "gap")
     let inherited: Vec<(&str, &str)> = params
         .iter()
-        .filter(|(k, _)| *k != "gap")
+        .filter(|(k, _)| !LAYOUT_PARAMS.contains(&k.as_str()))
         .map(|(k, v)| (k.as_str(), v.as_str()))
         .collect();
 
@@ -91,7 +98,7 @@ pub fn handle(params: &HashMap<String, String>, content: Option<&str>) -> Result
         let full_match = &content[start..end];
 
         // Determine which corner preset to apply
-        let corner_preset = if count == 1 {
+        let corner_preset = if count == 1 || layout.is_some() {
             // Single badge: keep all corners rounded (no modification needed)
             None
         } else if i == 0 {
@@ -139,13 +146,38 @@ pub fn handle(params: &HashMap<String, String>, content: Option<&str>) -> Result
         }
     }
 
-    // If gap is specified, wrap in row for proper alignment
-    if gap > 0 {
-        // Add spacing between badges using row component
-        result = format!("{{{{ui:row}}}}{}{}", result, "{{/ui}}");
+    match layout {
+        Some(layout) => Ok(ComponentOutput::TemplateDelayed {
+            template: result,
+            post_process: PostProcess::Group(layout),
+        }),
+        None => Ok(ComponentOutput::Template(result)),
     }
+}
 
-    Ok(ComponentOutput::Template(result))
+/// Layout from the group's params, or `None` to keep badges inline as written
+///
+/// Invalid numbers are ignored and an unknown `align` falls back to left.
+pub(super) fn parse_layout(params: &HashMap<String, String>) -> Option<GroupLayout> {
+    if !LAYOUT_PARAMS.iter().any(|key| params.contains_key(*key)) {
+        return None;
+    }
+    let number = |key: &str| params.get(key).and_then(|v| v.parse::<u32>().ok());
+    let align = params
+        .get("align")
+        .map(String::as_str)
+        .filter(|align| matches!(*align, "left" | "center" | "right"))
+        .unwrap_or("left");
+
+    Some(GroupLayout {
+        columns: number("columns")
+            .filter(|&columns| columns > 0)
+            .map(|columns| columns as usize),
+        wrap: number("wrap"),
+        gap: number("gap").unwrap_or(0),
+        max_width: number("max_width"),
+        align: align.to_string(),
+    })
 }
 
 #[cfg(test)]
@@ -314,6 +346,59 @@ mod tests {
         assert!(template.contains("corners=right"));
     }
 
+    // ========================================================================
+    // Layout
+    // ========================================================================
+
+    #[rstest]
+    #[case(&[], None)]
+    #[case(&[("bg", "1a1a2e")], None)]
+    #[case(&[("gap", "4")], Some(GroupLayout { gap: 4, ..Default::default() }))]
+    #[case(
+        &[("columns", "3"), ("wrap", "400"), ("max_width", "120"), ("align", "center")],
+        Some(GroupLayout {
+            columns: Some(3),
+            wrap: Some(400),
+            max_width: Some(120),
+            align: "center".to_string(),
+            ..Default::default()
+        })
+    )]
+    #[case(&[("columns", "0"), ("align", "middle")], Some(GroupLayout::default()))]
+    fn test_parse_layout(
+        #[case] params_input: &[(&str, &str)],
+        #[case] expected: Option<GroupLayout>,
+    ) {
+        let params = params_input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(parse_layout(&params), expected);
+    }
+
+    #[test]
+    fn test_layout_defers_to_group_rendering() {
+        let mut params = HashMap::new();
+        params.insert("columns".to_string(), "2".to_string());
+        params.insert("bg".to_string(), "1a1a2e".to_string());
+        let content = "{{ui:tech:rust/}}{{ui:tech:go/}}{{ui:tech:python/}}";
+
+        let result = handle(&params, Some(content)).unwrap();
+
+        let ComponentOutput::TemplateDelayed {
+            template,
+            post_process: PostProcess::Group(layout),
+        } = result
+        else {
+            unreachable!("Expected group layout");
+        };
+        assert_eq!(layout.columns, Some(2));
+        // Styles are still inherited, but corners are left to the layout
+        assert_eq!(template.matches("bg=1a1a2e").count(), 3);
+        assert!(!template.contains("corners="));
+        assert!(!template.contains("columns="));
+    }
+
     #[test]
     fn test_ignores_non_groupable_components() {
         let params = HashMap::new();
@@ -0,0 +1,175 @@
+//! Test results component handler
+//!
+//! Reads a local JUnit XML or libtest JSON report and shows the pass, fail
+//! and skip counts as a badge or a stacked bar, so a README's test status
+//! comes from the last CI run instead of a hand-edited number.
+
+use super::{get_string, metric_badge, parse_param_clamped, resolve_color_with_default};
+use crate::components::{labels, ComponentOutput};
+use crate::error::{Error, Result};
+use crate::primitive::{BarSegment, Primitive};
+use crate::test_results::summarize;
+use std::collections::HashMap;
+
+/// Handle tests component expansion
+///
+/// - `file`: report path, relative to the project root (required)
+/// - `display`: `badge` (default) or `bar`
+/// - `label`: text before the counts (badge)
+/// - `lang`: language of the default label (badge)
+/// - `bg`: fixed color instead of success/error (badge)
+/// - `width`, `height`, `rx`, `track`: bar size and track color (bar)
+pub fn handle(
+    params: &HashMap<String, String>,
+    style: &str,
+    read_file: impl Fn(&str) -> Option<String>,
+    resolve_color: impl Fn(&str) -> String,
+) -> Result<ComponentOutput> {
+    let file = params.get("file").ok_or_else(|| {
+        Error::ParseError(
+            "tests component requires file=PATH (JUnit XML or libtest JSON)".to_string(),
+        )
+    })?;
+    let report = read_file(file)
+        .ok_or_else(|| Error::ParseError(format!("test report '{}' not found", file)))?;
+    let summary = summarize(&report).ok_or_else(|| {
+        Error::ParseError(format!(
+            "no test results in '{}' (expected JUnit XML or libtest JSON)",
+            file
+        ))
+    })?;
+
+    let counts = [
+        ("passed", summary.passed, "success"),
+        ("failed", summary.failed, "error"),
+        ("skipped", summary.skipped, "warning"),
+    ];
+
+    match get_string(params, "display", "badge").as_str() {
+        "badge" => {
+            let value = counts
+                .iter()
+                .filter(|(_, count, _)| *count > 0)
+                .map(|(name, count, _)| format!("{} {}", count, name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let status = if summary.failed > 0 {
+                "error"
+            } else {
+                "success"
+            };
+            let bg_color = resolve_color(params.get("bg").map_or(status, String::as_str));
+            let label = labels::badge_label(params, "tests")?;
+            Ok(metric_badge(
+                format!("{}: {}", label, value),
+                bg_color,
+                params,
+                style,
+                resolve_color,
+            ))
+        }
+        "bar" => Ok(ComponentOutput::Primitive(Primitive::StackedBar {
+            segments: counts
+                .iter()
+                .map(|(name, count, color)| {
+                    BarSegment::new(
+                        *name,
+                        u32::try_from(*count).unwrap_or(u32::MAX),
+                        resolve_color(color),
+                    )
+                })
+                .collect(),
+            width: parse_param_clamped(params, "width", 200, 1, 2000),
+            height: parse_param_clamped(params, "height", 10, 1, 500),
+            rx: parse_param_clamped(params, "rx", 3, 0, 100),
+            track_color: resolve_color_with_default(params, "track", "slate", &resolve_color),
+        })),
+        other => Err(Error::ParseError(format!(
+            "Unknown tests display '{}' - use badge or bar",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn identity_color(c: &str) -> String {
+        c.to_string()
+    }
+
+    fn read_junit(path: &str) -> Option<String> {
+        let report = match path {
+            "junit.xml" => r#"<testsuite tests="125" failures="2" skipped="3"/>"#,
+            "passing.xml" => r#"<testsuite tests="40" failures="0"/>"#,
+            _ => return None,
+        };
+        Some(report.to_string())
+    }
+
+    fn params(input: &[(&str, &str)]) -> HashMap<String, String> {
+        input
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[rstest]
+    #[case(&[("file", "junit.xml")], "tests: 120 passed, 2 failed, 3 skipped", "error")]
+    #[case(&[("file", "passing.xml")], "tests: 40 passed", "success")]
+    #[case(&[("file", "passing.xml"), ("label", "unit"), ("bg", "accent")], "unit: 40 passed", "accent")]
+    #[case(&[("file", "passing.xml"), ("lang", "es")], "pruebas: 40 passed", "success")]
+    fn test_tests_badge(
+        #[case] params_input: &[(&str, &str)],
+        #[case] expected_label: &str,
+        #[case] expected_color: &str,
+    ) {
+        let output = handle(&params(params_input), "flat", read_junit, identity_color).unwrap();
+        let ComponentOutput::Primitive(Primitive::Swatch { label, color, .. }) = output else {
+            panic!("Expected Swatch primitive");
+        };
+        assert_eq!(label.as_deref(), Some(expected_label));
+        assert_eq!(color, expected_color);
+    }
+
+    #[test]
+    fn test_tests_bar() {
+        let params = params(&[("file", "junit.xml"), ("display", "bar"), ("width", "300")]);
+        let output = handle(&params, "flat", read_junit, identity_color).unwrap();
+        let ComponentOutput::Primitive(Primitive::StackedBar {
+            segments,
+            width,
+            track_color,
+            ..
+        }) = output
+        else {
+            panic!("Expected StackedBar primitive");
+        };
+        assert_eq!(
+            segments,
+            vec![
+                BarSegment::new("passed", 120, "success"),
+                BarSegment::new("failed", 2, "error"),
+                BarSegment::new("skipped", 3, "warning"),
+            ]
+        );
+        assert_eq!(width, 300);
+        assert_eq!(track_color, "slate");
+    }
+
+    #[rstest]
+    #[case(&[], "requires file=PATH")]
+    #[case(&[("file", "missing.xml")], "'missing.xml' not found")]
+    #[case(&[("file", "README.md")], "no test results in 'README.md'")]
+    #[case(&[("file", "junit.xml"), ("display", "pie")], "Unknown tests display 'pie'")]
+    fn test_tests_errors(#[case] params_input: &[(&str, &str)], #[case] message: &str) {
+        let read = |path: &str| match path {
+            "README.md" => Some("# Demo\n".to_string()),
+            _ => read_junit(path),
+        };
+        let err = handle(&params(params_input), "flat", read, identity_color).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+}
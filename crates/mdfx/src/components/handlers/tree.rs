@@ -0,0 +1,235 @@
+//! File tree component handler
+//!
+//! Turns an indented list of paths into a box-drawing tree (`├──`, `└──`)
+//! inside a code fence. Entries ending in `/`, or with children, are
+//! directories.
+
+use super::{parse_bool, parse_param};
+use crate::components::ComponentOutput;
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// One entry of the tree and the entries nested under it
+#[derive(Debug, Default)]
+struct Node {
+    name: String,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn is_dir(&self) -> bool {
+        self.name.ends_with('/') || !self.children.is_empty()
+    }
+}
+
+/// Handle tree component expansion
+///
+/// - `icons`: prefix each entry with a glyph for its file type
+/// - `depth`: hide entries nested deeper than this (0 shows everything)
+pub fn handle(params: &HashMap<String, String>, content: Option<&str>) -> Result<ComponentOutput> {
+    let roots = parse(content.unwrap_or(""));
+    if roots.is_empty() {
+        return Err(Error::ParseError(
+            "tree component requires at least one entry".to_string(),
+        ));
+    }
+
+    let options = Options {
+        icons: parse_bool(params, "icons", false),
+        depth: parse_param(params, "depth", 0),
+    };
+
+    let mut lines = Vec::new();
+    for root in &roots {
+        lines.push(options.label(root));
+        render_children(root, "", 1, &options, &mut lines);
+    }
+
+    Ok(ComponentOutput::Template(format!(
+        "```\n{}\n```",
+        lines.join("\n")
+    )))
+}
+
+struct Options {
+    icons: bool,
+    depth: usize,
+}
+
+impl Options {
+    fn label(&self, node: &Node) -> String {
+        if self.icons {
+            format!("{} {}", icon(node), node.name)
+        } else {
+            node.name.clone()
+        }
+    }
+}
+
+/// Draw the children of `node`, `prefix` carrying the ancestors' guide lines
+fn render_children(
+    node: &Node,
+    prefix: &str,
+    depth: usize,
+    options: &Options,
+    lines: &mut Vec<String>,
+) {
+    if options.depth > 0 && depth > options.depth {
+        return;
+    }
+    for (i, child) in node.children.iter().enumerate() {
+        let last = i + 1 == node.children.len();
+        let (branch, guide) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        lines.push(format!("{}{}{}", prefix, branch, options.label(child)));
+        render_children(
+            child,
+            &format!("{}{}", prefix, guide),
+            depth + 1,
+            options,
+            lines,
+        );
+    }
+}
+
+/// Build the tree from indentation; list markers (`-`, `*`) are optional
+fn parse(content: &str) -> Vec<Node> {
+    // Stack of (indent, node) for the current path from the root
+    let mut stack: Vec<(usize, Node)> = Vec::new();
+    let mut roots = Vec::new();
+
+    for line in content.lines() {
+        let name = line.trim();
+        let name = match name.strip_prefix(['-', '*']) {
+            Some(rest) if rest.is_empty() || rest.starts_with(char::is_whitespace) => rest.trim(),
+            _ => name,
+        };
+        if name.is_empty() {
+            continue;
+        }
+        let indent = line
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum();
+
+        while stack.last().is_some_and(|(top, _)| *top >= indent) {
+            close(&mut stack, &mut roots);
+        }
+        stack.push((
+            indent,
+            Node {
+                name: name.to_string(),
+                children: Vec::new(),
+            },
+        ));
+    }
+    while !stack.is_empty() {
+        close(&mut stack, &mut roots);
+    }
+    roots
+}
+
+/// Pop the innermost open node and attach it to its parent
+fn close(stack: &mut Vec<(usize, Node)>, roots: &mut Vec<Node>) {
+    if let Some((_, node)) = stack.pop() {
+        match stack.last_mut() {
+            Some((_, parent)) => parent.children.push(node),
+            None => roots.push(node),
+        }
+    }
+}
+
+/// Glyph for a tree entry, by file extension
+fn icon(node: &Node) -> &'static str {
+    if node.is_dir() {
+        return "📁";
+    }
+    let ext = node
+        .name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "rs" => "🦀",
+        "py" => "🐍",
+        "js" | "ts" | "jsx" | "tsx" | "go" | "c" | "h" | "cpp" | "java" | "rb" | "sh" => "📜",
+        "md" | "txt" | "rst" => "📝",
+        "toml" | "json" | "yaml" | "yml" | "lock" | "ini" => "⚙️",
+        "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" => "🖼️",
+        _ => "📄",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn tree(content: &str, params: &[(&str, &str)]) -> String {
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let ComponentOutput::Template(template) = handle(&params, Some(content)).unwrap() else {
+            panic!("Expected template");
+        };
+        template
+    }
+
+    const PROJECT: &str = "crate/\n  src/\n    lib.rs\n    parser.rs\n  Cargo.toml";
+
+    #[test]
+    fn test_tree_box_drawing() {
+        assert_eq!(
+            tree(PROJECT, &[]),
+            "```\n\
+             crate/\n\
+             ├── src/\n\
+             │   ├── lib.rs\n\
+             │   └── parser.rs\n\
+             └── Cargo.toml\n\
+             ```"
+        );
+    }
+
+    #[test]
+    fn test_tree_list_markers_and_tabs() {
+        assert_eq!(
+            tree("- src/\n\t- lib.rs\n\n* README.md", &[]),
+            "```\nsrc/\n└── lib.rs\nREADME.md\n```"
+        );
+    }
+
+    #[rstest]
+    #[case("1", "crate/\n├── src/\n└── Cargo.toml")]
+    #[case(
+        "0",
+        "crate/\n├── src/\n│   ├── lib.rs\n│   └── parser.rs\n└── Cargo.toml"
+    )]
+    fn test_tree_depth(#[case] depth: &str, #[case] expected: &str) {
+        assert_eq!(
+            tree(PROJECT, &[("depth", depth)]),
+            format!("```\n{}\n```", expected)
+        );
+    }
+
+    #[test]
+    fn test_tree_icons() {
+        assert_eq!(
+            tree("docs\n  guide.md\n  logo.PNG\n  run", &[("icons", "true")]),
+            "```\n📁 docs\n├── 📝 guide.md\n├── 🖼️ logo.PNG\n└── 📄 run\n```"
+        );
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("  \n- \n")]
+    fn test_tree_requires_entries(#[case] content: &str) {
+        let err = handle(&HashMap::new(), Some(content)).unwrap_err();
+        assert!(err.to_string().contains("at least one entry"));
+    }
+}
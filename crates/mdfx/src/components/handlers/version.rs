@@ -3,6 +3,7 @@
 //! Renders semantic version badges with status-aware coloring.
 //! Status detection and rendering is delegated to badgefx.
 
+use super::truncate_label;
 use crate::components::ComponentOutput;
 use crate::error::{Error, Result};
 use crate::primitive::{Primitive, VersionConfig};
@@ -17,6 +18,7 @@ use std::collections::HashMap;
 /// - {{ui:version:2.0.0-beta.1/}} -> yellow (auto-detected beta)
 /// - {{ui:version:0.1.0/}} -> yellow (0.x is beta)
 /// - {{ui:version:1.5.0:status=deprecated/}} -> red (override)
+/// - {{ui:version:2.0.0-beta.1:max_width=6/}} -> "v2.0.…", still yellow
 pub fn handle(
     args: &[String],
     params: &HashMap<String, String>,
@@ -29,20 +31,37 @@ pub fn handle(
         ));
     }
 
-    let version = &args[0];
+    let mut version = args[0].clone();
+    let mut prefix = params.get("prefix").cloned();
+    let mut status = params.get("status").cloned();
+
+    // A truncated label is shown as-is, with the status detected from the
+    // full version so the color doesn't change
+    let prefix_str = prefix.as_deref().unwrap_or("v");
+    let shown = if version.starts_with(['v', 'V']) || prefix_str.is_empty() {
+        version.clone()
+    } else {
+        format!("{}{}", prefix_str, version)
+    };
+    let truncated = truncate_label(params, &shown)?;
+    if truncated != shown {
+        status = status.or_else(|| Some(badgefx::version::detect_status(&version).as_str().into()));
+        version = truncated;
+        prefix = Some(String::new());
+    }
 
     // Build VersionConfig - badgefx handles status detection
     let config = VersionConfig {
-        version: version.clone(),
+        version,
         style: style.to_string(),
-        status: params.get("status").cloned(),
+        status,
         bg_color: params.get("bg").map(|c| resolve_color(c)),
         text_color: params
             .get("text")
             .or_else(|| params.get("text_color"))
             .or_else(|| params.get("color"))
             .map(|c| resolve_color(c)),
-        prefix: params.get("prefix").cloned(),
+        prefix,
         border_color: params.get("border").map(|c| resolve_color(c)),
         border_width: params.get("border_width").and_then(|v| v.parse().ok()),
         rx: params.get("rx").and_then(|v| v.parse().ok()),
@@ -106,6 +125,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_handle_max_width() {
+        let mut params = HashMap::new();
+        params.insert("max_width".to_string(), "6".to_string());
+
+        let result = handle(
+            &["2.0.0-beta.1".to_string()],
+            &params,
+            "flat",
+            identity_color,
+        );
+        if let Ok(ComponentOutput::Primitive(Primitive::Version(config))) = result {
+            assert_eq!(config.version, "v2.0.…");
+            assert_eq!(config.prefix, Some(String::new()));
+            assert_eq!(config.status, Some("beta".to_string()));
+        } else {
+            panic!("Expected Version primitive");
+        }
+
+        params.insert("max_width".to_string(), "20".to_string());
+        let result = handle(&["1.0.0".to_string()], &params, "flat", identity_color);
+        if let Ok(ComponentOutput::Primitive(Primitive::Version(config))) = result {
+            assert_eq!(config.version, "1.0.0");
+            assert_eq!(config.status, None);
+        } else {
+            panic!("Expected Version primitive");
+        }
+
+        params.insert("truncate".to_string(), "start".to_string());
+        assert!(handle(&["1.0.0".to_string()], &params, "flat", identity_color).is_err());
+    }
+
     #[test]
     fn test_handle_missing_version() {
         let result = handle(&[], &HashMap::new(), "flat", identity_color);
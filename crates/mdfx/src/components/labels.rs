@@ -0,0 +1,402 @@
+//! Localized badge labels
+//!
+//! Live badges and the `coverage` and `tests` badges label their values in
+//! English. `lang=` on a badge, or `"lang"` in `.mdfx.json`, translates the
+//! common labels; labels without a translation stay English. `label=` and
+//! the `"labels"` map in `.mdfx.json` replace a label outright.
+
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// Languages with built-in label translations, besides English
+pub const LANGUAGES: [&str; 7] = ["de", "es", "fr", "it", "ja", "pt", "zh"];
+
+/// Translations of common labels, keyed by the lowercase English label, in
+/// the order of [`LANGUAGES`]
+const TRANSLATIONS: &[(&str, [&str; 7])] = &[
+    (
+        "backers",
+        [
+            "Unterstützer",
+            "Mecenas",
+            "Soutiens",
+            "Sostenitori",
+            "支援者",
+            "Apoiadores",
+            "支持者",
+        ],
+    ),
+    (
+        "build",
+        [
+            "Build",
+            "Compilación",
+            "Build",
+            "Build",
+            "ビルド",
+            "Build",
+            "构建",
+        ],
+    ),
+    (
+        "contributors",
+        [
+            "Mitwirkende",
+            "Colaboradores",
+            "Contributeurs",
+            "Collaboratori",
+            "コントリビューター",
+            "Contribuidores",
+            "贡献者",
+        ],
+    ),
+    (
+        "coverage",
+        [
+            "Abdeckung",
+            "Cobertura",
+            "Couverture",
+            "Copertura",
+            "カバレッジ",
+            "Cobertura",
+            "覆盖率",
+        ],
+    ),
+    (
+        "dependents",
+        [
+            "Abhängige",
+            "Dependientes",
+            "Dépendants",
+            "Dipendenti",
+            "依存元",
+            "Dependentes",
+            "被依赖",
+        ],
+    ),
+    (
+        "downloads",
+        [
+            "Downloads",
+            "Descargas",
+            "Téléchargements",
+            "Download",
+            "ダウンロード",
+            "Downloads",
+            "下载量",
+        ],
+    ),
+    (
+        "forks",
+        [
+            "Forks",
+            "Forks",
+            "Forks",
+            "Fork",
+            "フォーク",
+            "Forks",
+            "复刻",
+        ],
+    ),
+    (
+        "issues",
+        [
+            "Issues",
+            "Incidencias",
+            "Tickets",
+            "Issue",
+            "イシュー",
+            "Issues",
+            "议题",
+        ],
+    ),
+    (
+        "language",
+        [
+            "Sprache",
+            "Lenguaje",
+            "Langage",
+            "Linguaggio",
+            "言語",
+            "Linguagem",
+            "语言",
+        ],
+    ),
+    (
+        "last commit",
+        [
+            "Letzter Commit",
+            "Último commit",
+            "Dernier commit",
+            "Ultimo commit",
+            "最終コミット",
+            "Último commit",
+            "最近提交",
+        ],
+    ),
+    (
+        "license",
+        [
+            "Lizenz",
+            "Licencia",
+            "Licence",
+            "Licenza",
+            "ライセンス",
+            "Licença",
+            "许可证",
+        ],
+    ),
+    (
+        "online",
+        [
+            "Online",
+            "En línea",
+            "En ligne",
+            "Online",
+            "オンライン",
+            "Online",
+            "在线",
+        ],
+    ),
+    (
+        "patrons",
+        [
+            "Patrons",
+            "Patronos",
+            "Mécènes",
+            "Mecenati",
+            "パトロン",
+            "Patronos",
+            "赞助人",
+        ],
+    ),
+    (
+        "size",
+        [
+            "Größe",
+            "Tamaño",
+            "Taille",
+            "Dimensione",
+            "サイズ",
+            "Tamanho",
+            "大小",
+        ],
+    ),
+    (
+        "sponsors",
+        [
+            "Sponsoren",
+            "Patrocinadores",
+            "Sponsors",
+            "Sponsor",
+            "スポンサー",
+            "Patrocinadores",
+            "赞助者",
+        ],
+    ),
+    (
+        "stars",
+        [
+            "Sterne",
+            "Estrellas",
+            "Étoiles",
+            "Stelle",
+            "スター",
+            "Estrelas",
+            "星标",
+        ],
+    ),
+    (
+        "status",
+        [
+            "Status",
+            "Estado",
+            "Statut",
+            "Stato",
+            "ステータス",
+            "Status",
+            "状态",
+        ],
+    ),
+    (
+        "tests",
+        [
+            "Tests",
+            "Pruebas",
+            "Tests",
+            "Test",
+            "テスト",
+            "Testes",
+            "测试",
+        ],
+    ),
+    (
+        "updated",
+        [
+            "Aktualisiert",
+            "Actualizado",
+            "Mis à jour",
+            "Aggiornato",
+            "更新日",
+            "Atualizado",
+            "更新于",
+        ],
+    ),
+    (
+        "version",
+        [
+            "Version",
+            "Versión",
+            "Version",
+            "Versione",
+            "バージョン",
+            "Versão",
+            "版本",
+        ],
+    ),
+    (
+        "vulnerabilities",
+        [
+            "Schwachstellen",
+            "Vulnerabilidades",
+            "Vulnérabilités",
+            "Vulnerabilità",
+            "脆弱性",
+            "Vulnerabilidades",
+            "漏洞",
+        ],
+    ),
+    (
+        "watchers",
+        [
+            "Beobachter",
+            "Observadores",
+            "Observateurs",
+            "Osservatori",
+            "ウォッチャー",
+            "Observadores",
+            "关注者",
+        ],
+    ),
+];
+
+/// Language of a tag, or `None` if there are no labels for it
+///
+/// Only the language subtag counts, so `de`, `de-DE` and `de_AT` are all
+/// German. `en` is accepted and keeps the English labels.
+pub fn parse_lang(tag: &str) -> Option<&'static str> {
+    let language = tag
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    available().find(|lang| *lang == language)
+}
+
+/// Accepted `lang=` values
+pub fn available() -> impl Iterator<Item = &'static str> {
+    std::iter::once("en").chain(LANGUAGES)
+}
+
+/// Translation of an English label, matched without regard to case or
+/// `_`, so both `Last commit` and the metric `last_commit` are found
+///
+/// A label that starts in lowercase gets a lowercase translation where the
+/// script has case (`stars` is `sterne`, `Stars` is `Sterne`).
+pub fn translate(label: &str, lang: &str) -> Option<String> {
+    let index = LANGUAGES.iter().position(|l| *l == lang)?;
+    let key = label.to_lowercase().replace('_', " ");
+    let (_, translations) = TRANSLATIONS.iter().find(|(english, _)| *english == key)?;
+    let translation = translations[index];
+    if label.starts_with(|c: char| c.is_lowercase()) {
+        let mut chars = translation.chars();
+        let first = chars.next()?;
+        return Some(first.to_lowercase().chain(chars).collect());
+    }
+    Some(translation.to_string())
+}
+
+/// Label of a badge: `label=` if set, otherwise `english` translated to
+/// `lang=`
+///
+/// Fails on a `lang` without translations, listing the available ones.
+pub fn badge_label(params: &HashMap<String, String>, english: &str) -> Result<String> {
+    if let Some(label) = params.get("label") {
+        return Ok(label.clone());
+    }
+    let Some(tag) = params.get("lang") else {
+        return Ok(english.to_string());
+    };
+    let lang = parse_lang(tag).ok_or_else(|| {
+        Error::ParseError(format!(
+            "Unknown lang '{}'. Available: {}",
+            tag,
+            available().collect::<Vec<_>>().join(", ")
+        ))
+    })?;
+    Ok(translate(english, lang).unwrap_or_else(|| english.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("de", Some("de"))]
+    #[case("de-DE", Some("de"))]
+    #[case("pt_BR", Some("pt"))]
+    #[case("EN-us", Some("en"))]
+    #[case("ko", None)]
+    #[case("", None)]
+    fn test_parse_lang(#[case] tag: &str, #[case] expected: Option<&str>) {
+        assert_eq!(parse_lang(tag), expected);
+    }
+
+    #[rstest]
+    #[case("Stars", "de", Some("Sterne"))]
+    #[case("stars", "de", Some("sterne"))]
+    #[case("Last commit", "fr", Some("Dernier commit"))]
+    #[case("last_commit", "es", Some("último commit"))]
+    #[case("coverage", "ja", Some("カバレッジ"))]
+    #[case("Stars", "en", None)]
+    #[case("SourceRank", "de", None)]
+    fn test_translate(#[case] label: &str, #[case] lang: &str, #[case] expected: Option<&str>) {
+        assert_eq!(translate(label, lang).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(&[], "Stars", "Stars")]
+    #[case(&[("lang", "de")], "Stars", "Sterne")]
+    #[case(&[("lang", "en")], "Stars", "Stars")]
+    #[case(&[("lang", "zh-CN")], "Downloads", "下载量")]
+    #[case(&[("lang", "it")], "SourceRank", "SourceRank")]
+    #[case(&[("lang", "de"), ("label", "★")], "Stars", "★")]
+    fn test_badge_label(
+        #[case] params: &[(&str, &str)],
+        #[case] english: &str,
+        #[case] expected: &str,
+    ) {
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(badge_label(&params, english).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_unknown_lang() {
+        let params = HashMap::from([("lang".to_string(), "xx".to_string())]);
+        let err = badge_label(&params, "Stars").unwrap_err();
+        assert!(err.to_string().contains("Unknown lang 'xx'"), "{}", err);
+        assert!(err.to_string().contains("en, de, es"), "{}", err);
+    }
+
+    #[test]
+    fn test_every_label_translated() {
+        for (english, translations) in TRANSLATIONS {
+            assert_eq!(english, &english.to_lowercase());
+            assert!(translations.iter().all(|t| !t.is_empty()), "{}", english);
+        }
+    }
+}
@@ -7,20 +7,59 @@
 //! verbose primitive calls like `{{shields:block:color=...}}`.
 
 mod handlers;
+pub mod labels;
+pub mod param_schema;
 pub mod params;
+mod plugin;
+#[cfg(feature = "wasm")]
+mod wasm;
 
+pub use handlers::matrix::{MatrixCell, MatrixTable};
 #[cfg(feature = "fetch")]
 pub use handlers::FetchContext;
+pub use param_schema::{ParamIssue, ParamSchema, ParamSpec, ParamType};
+pub use plugin::{ComponentPlugin, PluginCall};
+#[cfg(feature = "wasm")]
+pub use wasm::WasmPlugin;
 
 use crate::error::{Error, Result};
-use crate::primitive::Primitive;
+use crate::options::Clock;
+use crate::primitive::{GroupLayout, Primitive};
+use crate::registry::{Component, Registry};
+use param_schema::normalize_key;
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Components renderer for high-level UI elements
 pub struct ComponentsRenderer {
     palette: HashMap<String, String>,
     components: HashMap<String, ComponentDef>,
+    plugins: HashMap<String, Arc<dyn ComponentPlugin>>,
+    changelog: Option<String>,
+    project_files: HashMap<String, String>,
+    project_root: Option<PathBuf>,
+    /// Render live badges as placeholders when their data can't be fetched
+    placeholders: bool,
+    /// Locale for live badge values that don't set `locale=`
+    locale: Option<String>,
+    /// Language of badge labels that don't set `lang=`
+    lang: Option<String>,
+    /// Labels by metric, used when a badge sets no `label=`
+    labels: HashMap<String, String>,
+    /// Project icons (name -> SVG document)
+    icons: HashMap<String, String>,
+    /// Reject icons with content sanitizing would remove
+    strict_icons: bool,
+    /// Allow `icon=url:https://...` icons
+    remote_icons: bool,
+    /// Allow built-in components to fetch data, like `maintenance:auto:repo=`
+    network: bool,
+    /// Allow running local programs, like `git` for `maintenance:auto`
+    commands: bool,
+    /// Source of today's date for relative dates
+    clock: Clock,
     #[cfg(feature = "fetch")]
     fetch_ctx: Option<handlers::FetchContext>,
 }
@@ -38,6 +77,21 @@ pub enum PostProcess {
     /// Converts markdown images to HTML img tags and wraps in `<p align="...">`
     #[serde(skip)]
     Row { align: String },
+    /// Center the rendered block on targets that support HTML
+    #[serde(skip)]
+    Center,
+    /// Group layout (tech-group, statcard-row): child components are rendered
+    /// together as one group instead of being parsed as a template
+    #[serde(skip)]
+    Group(GroupLayout),
+    /// Comparison matrix: rendered as a markdown table by the parser, with
+    /// mark cells as badges or, on text-only backends, glyphs
+    #[serde(skip)]
+    Matrix(MatrixTable),
+    /// Markdown appended after the rendered block without template
+    /// processing (latest-release notes quoted from the changelog)
+    #[serde(skip)]
+    AppendRaw(String),
 }
 
 /// A component definition from registry.json
@@ -53,18 +107,40 @@ pub struct ComponentDef {
     pub template: String,
     #[serde(default)]
     pub post_process: PostProcess,
+    /// Parameter aliases from the registry, mapped to canonical names
+    #[serde(skip)]
+    pub param_aliases: HashMap<String, String>,
 }
 
-/// Intermediate structure to parse registry.json for components
-#[derive(Debug, Deserialize)]
-struct RegistryComponentsExtract {
-    palette: HashMap<String, String>,
-    renderables: RenderablesExtract,
-}
+impl From<&Component> for ComponentDef {
+    fn from(component: &Component) -> Self {
+        let post_process = match component.post_process.as_deref() {
+            Some("blockquote") => PostProcess::Blockquote,
+            _ => PostProcess::None,
+        };
 
-#[derive(Debug, Deserialize)]
-struct RenderablesExtract {
-    components: HashMap<String, ComponentDef>,
+        let param_aliases = component
+            .optional_params
+            .iter()
+            .flatten()
+            .flat_map(|(name, param)| {
+                param
+                    .aliases
+                    .iter()
+                    .map(move |alias| (alias.clone(), name.clone()))
+            })
+            .collect();
+
+        ComponentDef {
+            component_type: component.component_type.clone(),
+            self_closing: component.self_closing,
+            description: component.description.clone().unwrap_or_default(),
+            args: component.args.clone(),
+            template: component.template.clone().unwrap_or_default(),
+            post_process,
+            param_aliases,
+        }
+    }
 }
 
 /// Output from expanding a component
@@ -86,28 +162,302 @@ pub enum ComponentOutput {
 impl ComponentsRenderer {
     /// Create a new components renderer by loading from registry.json
     pub fn new() -> Result<Self> {
-        let data = include_str!("../../data/registry.json");
-        let registry: RegistryComponentsExtract = serde_json::from_str(data).map_err(|e| {
-            Error::ParseError(format!(
-                "Failed to parse registry.json for components: {}",
-                e
-            ))
-        })?;
+        Ok(Self::with_registry(&*Registry::shared()?))
+    }
 
-        Ok(ComponentsRenderer {
-            palette: registry.palette,
-            components: registry.renderables.components,
+    /// Create a components renderer from an existing registry
+    ///
+    /// The palette and component definitions are copied, so palette
+    /// extensions don't affect the registry.
+    pub fn with_registry(registry: &Registry) -> Self {
+        ComponentsRenderer {
+            palette: registry.palette().clone(),
+            components: registry
+                .components()
+                .iter()
+                .map(|(name, component)| (name.clone(), ComponentDef::from(component)))
+                .collect(),
+            plugins: HashMap::new(),
+            changelog: None,
+            project_files: HashMap::new(),
+            project_root: None,
+            placeholders: false,
+            locale: None,
+            lang: None,
+            labels: HashMap::new(),
+            icons: HashMap::new(),
+            strict_icons: false,
+            remote_icons: true,
+            network: true,
+            commands: true,
+            clock: Clock::System,
             #[cfg(feature = "fetch")]
             fetch_ctx: None,
-        })
+        }
+    }
+
+    /// Register a custom component
+    ///
+    /// Fails if the name is empty or already used by a built-in or
+    /// previously registered component.
+    pub fn register_plugin(&mut self, plugin: Arc<dyn ComponentPlugin>) -> Result<()> {
+        let name = plugin.name();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(Error::ParseError(format!(
+                "Invalid component name '{}': use letters, digits, '-' or '_'",
+                name
+            )));
+        }
+        if self.has(name) {
+            return Err(Error::ParseError(format!(
+                "Component '{}' is already defined",
+                name
+            )));
+        }
+        self.plugins.insert(name.to_string(), plugin);
+        Ok(())
+    }
+
+    /// Set the changelog read by `{{ui:latest-release/}}`
+    pub fn set_changelog(&mut self, markdown: impl Into<String>) {
+        self.changelog = Some(markdown.into());
+    }
+
+    /// Set the locale used to format live badge values (thousands
+    /// separators, decimal marks, dates) when a template sets no `locale=`
+    pub fn set_locale(&mut self, locale: impl Into<String>) {
+        self.locale = Some(locale.into());
+    }
+
+    /// Set the language of live, `coverage` and `tests` badge labels when a
+    /// template sets no `lang=`
+    pub fn set_lang(&mut self, lang: impl Into<String>) {
+        self.lang = Some(lang.into());
+    }
+
+    /// Set the label of a metric (`stars`, `coverage`, `tests`, ...), used
+    /// instead of the built-in or translated one when a template sets no
+    /// `label=`
+    pub fn set_label(&mut self, metric: &str, label: impl Into<String>) {
+        self.labels
+            .insert(params::normalize_metric(metric), label.into());
+    }
+
+    /// Set the contents of a project file (a manifest read by the toolchain
+    /// badges, or a coverage or test report), keyed by its path
+    pub fn set_project_file(&mut self, name: impl Into<String>, contents: impl Into<String>) {
+        self.project_files.insert(name.into(), contents.into());
+    }
+
+    /// Set the directory that `{{ui:coverage:file=.../}}`, `{{ui:tests:file=.../}}`,
+    /// `{{ui:bench:.../}}` and the toolchain badges read files from
+    ///
+    /// Files set with [`set_project_file`](Self::set_project_file) take
+    /// precedence. Without a root, only those are available.
+    pub fn set_project_root(&mut self, root: impl Into<PathBuf>) {
+        self.project_root = Some(root.into());
+    }
+
+    /// Contents of a project file, from memory or the project root
+    ///
+    /// Files on disk must resolve to a path inside the root: absolute paths,
+    /// and `..` or symlinks leading out of it, read nothing.
+    pub(crate) fn project_file(&self, path: &str) -> Option<String> {
+        if let Some(contents) = self.project_files.get(path) {
+            return Some(contents.clone());
+        }
+        let root = self.project_root.as_ref()?.canonicalize().ok()?;
+        let file = root.join(path).canonicalize().ok()?;
+        if std::path::Path::new(path).is_absolute() || !file.starts_with(&root) {
+            tracing::warn!("Refused to read {}: outside the project root", path);
+            return None;
+        }
+        std::fs::read_to_string(file).ok()
+    }
+
+    /// ISO date of the last commit, for `{{ui:maintenance:auto/}}`
+    ///
+    /// With `repo` and a fetch context, the last push to that GitHub
+    /// repository; otherwise the newest commit of the git repository at the
+    /// project root. `None` when neither is available.
+    fn last_commit(&self, repo: Option<&str>) -> Result<Option<String>> {
+        if let Some(repo) = repo.filter(|_| !self.network) {
+            return Err(Error::NotAllowed(format!(
+                "maintenance:auto for {} needs network access, which is disabled",
+                repo
+            )));
+        }
+        #[cfg(feature = "fetch")]
+        if let (Some(repo), Some(ctx)) = (repo, &self.fetch_ctx) {
+            let value = ctx
+                .fetcher()
+                .fetch("github", repo, "last_commit")
+                .map_err(|e| Error::FetchFailed(format!("github data: {}", e)))?;
+            return Ok((!value.is_unavailable()).then(|| value.to_string()));
+        }
+        let _ = repo;
+        let Some(root) = &self.project_root else {
+            return Ok(None);
+        };
+        if !self.commands {
+            return Err(Error::NotAllowed(
+                "maintenance:auto runs git to date the last commit, which is disabled; use repo=owner/repo or an explicit status".to_string(),
+            ));
+        }
+        let output = std::process::Command::new("git")
+            .args(["log", "-1", "--format=%cs"])
+            .current_dir(root)
+            .output();
+        Ok(output
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|date| !date.is_empty()))
+    }
+
+    /// Replace the icon of a tech or swatch badge with sanitized markup when
+    /// it is a project icon or a remote icon (`icon=url:https://...`)
+    ///
+    /// Project icons are found by `icon=` or, for tech badges without one,
+    /// by the tech name. Remote icons are fetched through the fetch context
+    /// and its cache; without one, or when fetching fails, the icon is
+    /// dropped in placeholder mode and is an error otherwise. Markup can
+    /// only come from these, so a literal `icon=<...>` is rejected.
+    fn resolve_icon(
+        &self,
+        component: &str,
+        positional: &[String],
+        params: &mut HashMap<String, String>,
+    ) -> Result<()> {
+        let icon = match params.get("icon") {
+            Some(icon) => icon.clone(),
+            None if component == "tech" => match positional.first() {
+                Some(name) => name.clone(),
+                None => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+        if icon.starts_with('<') {
+            return Err(Error::ParseError(
+                "icon= takes an icon name, SVG path data or url:https://...".to_string(),
+            ));
+        }
+        if let Some(svg) = self.icons.get(&icon) {
+            let markup = crate::icon::to_markup(svg, self.strict_icons)
+                .map_err(|e| Error::ParseError(format!("Icon '{}': {}", icon, e)))?;
+            params.insert("icon".to_string(), markup);
+            return Ok(());
+        }
+        let Some(url) = crate::icon::remote_url(&icon) else {
+            return Ok(());
+        };
+        if !self.remote_icons {
+            return Err(Error::NotAllowed(format!(
+                "remote icon {} needs network access, which is disabled",
+                url
+            )));
+        }
+
+        #[cfg(feature = "fetch")]
+        if let Some(ctx) = &self.fetch_ctx {
+            let fetched = ctx
+                .fetcher()
+                .fetch("icon", url, "svg")
+                .map_err(|e| Error::FetchFailed(format!("icon {}: {}", url, e)))
+                .and_then(|svg| crate::icon::to_markup(&svg.to_string(), self.strict_icons));
+            match fetched {
+                Ok(markup) => {
+                    params.insert("icon".to_string(), markup);
+                }
+                Err(e) if self.placeholders => {
+                    tracing::warn!("Dropped remote icon {} ({})", url, e);
+                    params.remove("icon");
+                }
+                Err(e) => return Err(e),
+            }
+            return Ok(());
+        }
+
+        if self.placeholders {
+            params.remove("icon");
+            return Ok(());
+        }
+        Err(Error::ParseError(format!(
+            "Remote icon {} requires fetch context. Use --offline=false or configure fetch.",
+            url
+        )))
+    }
+
+    /// Define a project icon from an SVG document, usable as `icon=name`
+    /// and as the icon of `{{ui:tech:name/}}`
+    ///
+    /// The document is sanitized when a badge uses it; see [`crate::icon`].
+    pub fn set_icon(&mut self, name: &str, svg: &str) {
+        self.icons.insert(name.to_string(), svg.to_string());
+    }
+
+    /// Reject project and remote icons that need anything removed by
+    /// sanitizing, instead of embedding what remains
+    pub fn set_strict_icons(&mut self, strict: bool) {
+        self.strict_icons = strict;
+    }
+
+    /// Allow `icon=url:https://...` icons, which are fetched over the network
+    pub fn set_remote_icons(&mut self, allowed: bool) {
+        self.remote_icons = allowed;
     }
 
     /// Set the fetch context for dynamic badges
     #[cfg(feature = "fetch")]
-    pub fn set_fetch_context(&mut self, ctx: handlers::FetchContext) {
+    pub fn set_fetch_context(&mut self, mut ctx: handlers::FetchContext) {
+        ctx.set_placeholders(self.placeholders);
+        ctx.set_clock(self.clock);
         self.fetch_ctx = Some(ctx);
     }
 
+    /// Allow built-in components other than live badges to fetch data
+    /// (`{{ui:maintenance:auto:repo=.../}}`)
+    pub fn set_network(&mut self, allowed: bool) {
+        self.network = allowed;
+    }
+
+    /// Allow components to run local programs (`git` for
+    /// `{{ui:maintenance:auto/}}` without `repo=`)
+    pub fn set_commands(&mut self, allowed: bool) {
+        self.commands = allowed;
+    }
+
+    /// Set where today's date comes from, for live badges with
+    /// `format=relative` or `age_color` and for `{{ui:maintenance:auto/}}`
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+        #[cfg(feature = "fetch")]
+        if let Some(ctx) = &mut self.fetch_ctx {
+            ctx.set_clock(clock);
+        }
+    }
+
+    /// Render live badges as gray `metric: —` placeholders when fetching
+    /// is unavailable (no fetch context, or the `fetch` feature is off) or
+    /// fails, instead of failing the document
+    pub fn set_placeholders(&mut self, enabled: bool) {
+        self.placeholders = enabled;
+        #[cfg(feature = "fetch")]
+        if let Some(ctx) = &mut self.fetch_ctx {
+            ctx.set_placeholders(enabled);
+        }
+    }
+
+    /// Get the fetch context, if set
+    #[cfg(feature = "fetch")]
+    pub fn fetch_context(&self) -> Option<&handlers::FetchContext> {
+        self.fetch_ctx.as_ref()
+    }
+
     /// Check if fetch context is available
     #[cfg(feature = "fetch")]
     pub fn has_fetch_context(&self) -> bool {
@@ -155,6 +505,10 @@ impl ComponentsRenderer {
         args: &[String],
         content: Option<&str>,
     ) -> Result<ComponentOutput> {
+        if let Some(plugin) = self.plugins.get(component) {
+            return self.expand_plugin(plugin.as_ref(), args, content);
+        }
+
         // Get component definition (single source of truth)
         let comp = self.components.get(component).ok_or_else(|| {
             Error::ParseError(format!(
@@ -175,6 +529,8 @@ impl ComponentsRenderer {
                 self.expand_dynamic(component, args, content)
             }
             #[cfg(not(feature = "fetch"))]
+            "dynamic" if self.placeholders => self.expand_placeholder(component, args),
+            #[cfg(not(feature = "fetch"))]
             "dynamic" => Err(Error::ParseError(format!(
                 "Dynamic component '{}' requires the 'fetch' feature. Rebuild with: --features fetch",
                 component
@@ -191,13 +547,53 @@ impl ComponentsRenderer {
         }
     }
 
+    /// Validate parameters against the plugin's schema and expand it
+    fn expand_plugin(
+        &self,
+        plugin: &dyn ComponentPlugin,
+        args: &[String],
+        content: Option<&str>,
+    ) -> Result<ComponentOutput> {
+        let (positional, params) = Self::split_params(args);
+
+        let schema = plugin.params();
+        if !schema.is_empty() {
+            let mut unknown: Vec<&str> = params
+                .keys()
+                .map(String::as_str)
+                .filter(|key| !schema.iter().any(|p| p.name == *key))
+                .collect();
+            if !unknown.is_empty() {
+                unknown.sort_unstable();
+                let known: Vec<&str> = schema.iter().map(|p| p.name).collect();
+                return Err(Error::ParseError(format!(
+                    "Unknown parameter(s) {} for component '{}'. Available: {}",
+                    unknown.join(", "),
+                    plugin.name(),
+                    known.join(", ")
+                )));
+            }
+        }
+
+        plugin.expand(&PluginCall {
+            args: positional,
+            params,
+            content,
+            renderer: self,
+        })
+    }
+
     /// Extract style= parameter from args, returning (remaining_args, style)
     fn split_style_arg(args: &[String]) -> (Vec<String>, String) {
         let mut style: Option<String> = None;
         let mut kept = Vec::new();
 
         for arg in args {
-            if let Some(rest) = arg.strip_prefix("style=") {
+            if let Some(rest) = arg
+                .split_once('=')
+                .filter(|(key, _)| normalize_key(key) == "style")
+                .map(|(_, value)| value)
+            {
                 // Last one wins if repeated
                 style = Some(rest.to_string());
             } else {
@@ -211,8 +607,8 @@ impl ComponentsRenderer {
         )
     }
 
-    /// Extract key=value parameters from args, returning (positional_args, params_map)
-    fn extract_params(args: &[String]) -> (Vec<String>, HashMap<String, String>) {
+    /// Split key=value parameters from args as written, returning (positional_args, params_map)
+    fn split_params(args: &[String]) -> (Vec<String>, HashMap<String, String>) {
         let mut params = HashMap::new();
         let mut positional = Vec::new();
 
@@ -227,6 +623,39 @@ impl ComponentsRenderer {
         (positional, params)
     }
 
+    /// Extract key=value parameters from args, returning (positional_args, params_map)
+    ///
+    /// Keys are normalized (`labelColor` and `label-color` become
+    /// `label_color`) and the component's registry aliases resolved to
+    /// canonical names. A canonical key wins over an alias regardless of order.
+    fn extract_params(
+        &self,
+        component: &str,
+        args: &[String],
+    ) -> (Vec<String>, HashMap<String, String>) {
+        let aliases = self.components.get(component).map(|c| &c.param_aliases);
+        let mut params = HashMap::new();
+        let mut aliased = HashMap::new();
+        let mut positional = Vec::new();
+
+        for arg in args {
+            if let Some((key, value)) = arg.split_once('=') {
+                let key = normalize_key(key);
+                match aliases.and_then(|a| a.get(&key)) {
+                    Some(canonical) => aliased.insert(canonical.clone(), value.to_string()),
+                    None => params.insert(key, value.to_string()),
+                };
+            } else {
+                positional.push(arg.clone());
+            }
+        }
+        for (key, value) in aliased {
+            params.entry(key).or_insert(value);
+        }
+
+        (positional, params)
+    }
+
     /// Expand a native component to a Primitive (or TemplateDelayed for row)
     fn expand_native(
         &self,
@@ -234,8 +663,20 @@ impl ComponentsRenderer {
         args: &[String],
         content: Option<&str>,
     ) -> Result<ComponentOutput> {
+        // Dividers use style= for the line pattern, not a badge style
+        if component == "divider" {
+            let (_, params) = self.extract_params(component, args);
+            return handlers::divider::handle(&params);
+        }
+
         let (args, style) = Self::split_style_arg(args);
-        let (positional, params) = Self::extract_params(&args);
+        let (positional, mut params) = self.extract_params(component, &args);
+        if matches!(component, "coverage" | "tests") {
+            self.apply_label_defaults(component, &mut params);
+        }
+        if matches!(component, "swatch" | "tech") {
+            self.resolve_icon(component, &positional, &mut params)?;
+        }
 
         // Create a closure for color resolution
         let resolve = |color: &str| self.resolve_color(color);
@@ -244,15 +685,55 @@ impl ComponentsRenderer {
             "swatch" => handlers::swatch::handle(&positional, &params, &style, resolve),
             "tech" => handlers::tech::handle(&positional, &params, &style, resolve),
             "tech-group" => handlers::tech_group::handle(&params, content),
+            "hero" => handlers::hero::handle(&params, &style),
+            "latest-release" => {
+                handlers::latest_release::handle(&params, &style, self.changelog.as_deref())
+            }
+            "bench" => handlers::bench::handle(
+                &positional,
+                &params,
+                &style,
+                |path| self.project_file(path),
+                resolve,
+            ),
+            "coverage" => {
+                handlers::coverage::handle(&params, &style, |path| self.project_file(path), resolve)
+            }
+            "tests" => handlers::test_results::handle(
+                &params,
+                &style,
+                |path| self.project_file(path),
+                resolve,
+            ),
+            "matrix" => handlers::matrix::handle(&params, content, &style, resolve),
+            "tree" => handlers::tree::handle(&params, content),
             "progress" => handlers::progress::handle(&positional, &params, resolve),
             "donut" => handlers::donut::handle(&positional, &params, resolve),
             "gauge" => handlers::gauge::handle(&positional, &params, resolve),
             "sparkline" => handlers::sparkline::handle(&positional, &params, resolve),
             "rating" => handlers::rating::handle(&positional, &params, resolve),
             "waveform" => handlers::waveform::handle(&positional, &params, resolve),
+            "statcard" => handlers::statcard::handle(&params, resolve),
+            "statcard-row" => handlers::statcard::handle_row(&params, content),
+            "steps" => handlers::steps::handle(&params, content, resolve),
             "row" => handlers::row::handle(&params, content),
             "version" => handlers::version::handle(&positional, &params, &style, resolve),
             "license" => handlers::license::handle(&positional, &params, &style, resolve),
+            "maintenance" => handlers::maintenance::handle(
+                &positional,
+                &params,
+                &style,
+                self.clock.today(),
+                |repo| self.last_commit(repo),
+                resolve,
+            ),
+            "msrv" | "node-engines" | "python-requires" => handlers::project::handle(
+                component,
+                &params,
+                &style,
+                |path| self.project_file(path),
+                resolve,
+            ),
             _ => Err(Error::ParseError(format!(
                 "Native component '{}' has no implementation",
                 component
@@ -268,15 +749,28 @@ impl ComponentsRenderer {
         args: &[String],
         _content: Option<&str>,
     ) -> Result<ComponentOutput> {
-        let fetch_ctx = self.fetch_ctx.as_ref().ok_or_else(|| {
-            Error::ParseError(
+        let Some(fetch_ctx) = self.fetch_ctx.as_ref() else {
+            if self.placeholders {
+                return self.expand_placeholder(component, args);
+            }
+            return Err(Error::ParseError(
                 "Dynamic badges require fetch context. Use --offline=false or configure fetch."
                     .to_string(),
-            )
-        })?;
+            ));
+        };
 
         let (args, style) = Self::split_style_arg(args);
-        let (positional, params) = Self::extract_params(&args);
+        let (positional, mut params) = self.extract_params(component, &args);
+        if let Some(locale) = &self.locale {
+            params
+                .entry("locale".to_string())
+                .or_insert_with(|| locale.clone());
+        }
+        if component == "live" {
+            if let Some((source, rest)) = positional.split_first() {
+                self.apply_label_defaults(&Self::live_metric(source, rest, &params), &mut params);
+            }
+        }
 
         // Create a closure for color resolution
         let resolve = |color: &str| self.resolve_color(color);
@@ -355,12 +849,100 @@ impl ComponentsRenderer {
                         resolve,
                         fetch_ctx,
                     ),
+                    "scorecard" => handlers::handle_scorecard(
+                        &remaining_args,
+                        &params,
+                        &style,
+                        resolve,
+                        fetch_ctx,
+                    ),
+                    "osv" => {
+                        handlers::handle_osv(&remaining_args, &params, &style, resolve, fetch_ctx)
+                    }
+                    "librariesio" => handlers::handle_librariesio(
+                        &remaining_args,
+                        &params,
+                        &style,
+                        resolve,
+                        fetch_ctx,
+                    ),
+                    "discord" => handlers::handle_discord(
+                        &remaining_args,
+                        &params,
+                        &style,
+                        resolve,
+                        fetch_ctx,
+                    ),
+                    "matrix" => handlers::handle_matrix(
+                        &remaining_args,
+                        &params,
+                        &style,
+                        resolve,
+                        fetch_ctx,
+                    ),
+                    "reddit" => handlers::handle_reddit(
+                        &remaining_args,
+                        &params,
+                        &style,
+                        resolve,
+                        fetch_ctx,
+                    ),
+                    "docsrs" => handlers::handle_docsrs(
+                        &remaining_args,
+                        &params,
+                        &style,
+                        resolve,
+                        fetch_ctx,
+                    ),
+                    "readthedocs" => handlers::handle_readthedocs(
+                        &remaining_args,
+                        &params,
+                        &style,
+                        resolve,
+                        fetch_ctx,
+                    ),
+                    "sponsors" => handlers::handle_sponsors(
+                        &remaining_args,
+                        &params,
+                        &style,
+                        resolve,
+                        fetch_ctx,
+                    ),
+                    "opencollective" => handlers::handle_opencollective(
+                        &remaining_args,
+                        &params,
+                        &style,
+                        resolve,
+                        fetch_ctx,
+                    ),
+                    "patreon" => handlers::handle_patreon(
+                        &remaining_args,
+                        &params,
+                        &style,
+                        resolve,
+                        fetch_ctx,
+                    ),
                     _ => Err(Error::ParseError(format!(
-                        "Unknown live source '{}'. Available: github, npm, crates, pypi, codecov, actions, docker, packagist, rubygems, nuget",
+                        "Unknown live source '{}'. Available: github, npm, crates, pypi, codecov, actions, docker, packagist, rubygems, nuget, scorecard, osv, librariesio, discord, matrix, reddit, docsrs, readthedocs, sponsors, opencollective, patreon",
                         source
                     ))),
                 }
             }
+            "ci-matrix" => handlers::ci_matrix::handle(
+                &positional,
+                &params,
+                &style,
+                |query| {
+                    handlers::github::fetch_value(
+                        fetch_ctx,
+                        "actions",
+                        query,
+                        "conclusion",
+                        "2088FF",
+                    )
+                },
+                resolve,
+            ),
             _ => Err(Error::ParseError(format!(
                 "Dynamic component '{}' has no implementation",
                 component
@@ -368,6 +950,61 @@ impl ComponentsRenderer {
         }
     }
 
+    /// Expand a dynamic component as a placeholder, without fetching
+    fn expand_placeholder(&self, component: &str, args: &[String]) -> Result<ComponentOutput> {
+        let (args, style) = Self::split_style_arg(args);
+        let (positional, mut params) = self.extract_params(component, &args);
+        if let ("live", Some((source, rest))) = (component, positional.split_first()) {
+            self.apply_label_defaults(&Self::live_metric(source, rest, &params), &mut params);
+        }
+        match (component, positional.split_first()) {
+            ("live", Some((source, rest))) => {
+                handlers::handle_placeholder(source, rest, &params, &style, |color: &str| {
+                    self.resolve_color(color)
+                })
+            }
+            ("live", None) => Err(Error::ParseError(
+                "live component requires source (github, npm, crates, pypi)".to_string(),
+            )),
+            ("ci-matrix", _) => handlers::ci_matrix::handle(
+                &positional,
+                &params,
+                &style,
+                |_| Ok(None),
+                |color| self.resolve_color(color),
+            ),
+            _ => Err(Error::ParseError(format!(
+                "Dynamic component '{}' has no implementation",
+                component
+            ))),
+        }
+    }
+
+    /// Add the configured `lang` and the configured label of `metric` to
+    /// badge params that don't set them
+    fn apply_label_defaults(&self, metric: &str, params: &mut HashMap<String, String>) {
+        if let Some(lang) = &self.lang {
+            params
+                .entry("lang".to_string())
+                .or_insert_with(|| lang.clone());
+        }
+        if let Some(label) = self.labels.get(metric) {
+            params
+                .entry("label".to_string())
+                .or_insert_with(|| label.clone());
+        }
+    }
+
+    /// Metric of a live badge: its third argument, `metric=`, or the
+    /// source's default
+    fn live_metric(source: &str, rest: &[String], params: &HashMap<String, String>) -> String {
+        rest.get(1)
+            .or_else(|| params.get("metric"))
+            .map(|metric| params::normalize_metric(metric))
+            .or_else(|| params::default_metric(source).map(String::from))
+            .unwrap_or_default()
+    }
+
     /// Expand a component using template substitution (internal method)
     fn expand_template(
         &self,
@@ -406,7 +1043,12 @@ impl ComponentsRenderer {
         let processed = match &comp.post_process {
             PostProcess::None => expanded,
             PostProcess::Blockquote => self.apply_blockquote(&expanded),
-            PostProcess::Row { .. } => expanded, // Delayed; handled in parser
+            // Delayed; handled in parser
+            PostProcess::Row { .. }
+            | PostProcess::Center
+            | PostProcess::Group(_)
+            | PostProcess::Matrix(_)
+            | PostProcess::AppendRaw(_) => expanded,
         };
 
         Ok(processed)
@@ -439,6 +1081,13 @@ impl ComponentsRenderer {
         handlers::row::apply_row(content, align)
     }
 
+    /// Build a comparison matrix table from rendered cells
+    ///
+    /// Delegates to the matrix handler module.
+    pub fn matrix_to_markdown(header: &[String], rows: &[Vec<String>]) -> String {
+        handlers::matrix::to_markdown(header, rows)
+    }
+
     /// Resolve a color from palette or pass through
     ///
     /// # Arguments
@@ -448,7 +1097,7 @@ impl ComponentsRenderer {
     /// # Returns
     ///
     /// Hex code if found in palette, otherwise the original string
-    fn resolve_color(&self, color: &str) -> String {
+    pub(crate) fn resolve_color(&self, color: &str) -> String {
         self.palette
             .get(color)
             .cloned()
@@ -519,9 +1168,29 @@ impl ComponentsRenderer {
         result
     }
 
-    /// Check if a component exists
+    /// Check if a component exists, including registered plugins
     pub fn has(&self, name: &str) -> bool {
-        self.components.contains_key(name)
+        self.components.contains_key(name) || self.plugins.contains_key(name)
+    }
+
+    /// Check if a component is a registered plugin
+    pub(crate) fn is_plugin(&self, name: &str) -> bool {
+        self.plugins.contains_key(name)
+    }
+
+    /// Check if a component reads project files
+    pub(crate) fn reads_project_files(name: &str) -> bool {
+        matches!(
+            name,
+            "bench" | "coverage" | "tests" | "msrv" | "node-engines" | "python-requires"
+        )
+    }
+
+    /// List registered plugin components, sorted by name
+    pub fn plugins(&self) -> Vec<&dyn ComponentPlugin> {
+        let mut plugins: Vec<_> = self.plugins.values().map(|p| p.as_ref()).collect();
+        plugins.sort_by(|a, b| a.name().cmp(b.name()));
+        plugins
     }
 
     /// List all available components
@@ -555,6 +1224,82 @@ mod tests {
         assert!(renderer.is_ok());
     }
 
+    struct Dot;
+
+    impl ComponentPlugin for Dot {
+        fn name(&self) -> &str {
+            "dot"
+        }
+
+        fn params(&self) -> &[params::ParamInfo] {
+            static PARAMS: &[params::ParamInfo] = &[params::ParamInfo {
+                name: "color",
+                description: "Dot color",
+                example: "color=accent",
+                values: None,
+            }];
+            PARAMS
+        }
+
+        fn expand(&self, call: &PluginCall<'_>) -> Result<ComponentOutput> {
+            let color = call.resolve_color(call.param("color").unwrap_or("accent"));
+            Ok(ComponentOutput::Template(format!("[{}]", color)))
+        }
+    }
+
+    #[test]
+    fn test_plugin_expands_with_palette() {
+        let mut renderer = ComponentsRenderer::new().unwrap();
+        renderer.register_plugin(Arc::new(Dot)).unwrap();
+
+        assert!(renderer.has("dot"));
+        let accent = renderer.resolve_color("accent");
+        let result = renderer.expand("dot", &[], None).unwrap();
+        assert_eq!(result, ComponentOutput::Template(format!("[{}]", accent)));
+    }
+
+    #[test]
+    fn test_plugin_rejects_unknown_params() {
+        let mut renderer = ComponentsRenderer::new().unwrap();
+        renderer.register_plugin(Arc::new(Dot)).unwrap();
+
+        let err = renderer
+            .expand("dot", &["size=3".to_string()], None)
+            .unwrap_err();
+        assert!(err.to_string().contains("size"));
+        assert!(err.to_string().contains("Available: color"));
+    }
+
+    #[test]
+    fn test_plugin_name_conflicts() {
+        struct Swatch;
+        impl ComponentPlugin for Swatch {
+            fn name(&self) -> &str {
+                "swatch"
+            }
+            fn expand(&self, _: &PluginCall<'_>) -> Result<ComponentOutput> {
+                unreachable!()
+            }
+        }
+
+        let mut renderer = ComponentsRenderer::new().unwrap();
+        assert!(renderer.register_plugin(Arc::new(Swatch)).is_err());
+        renderer.register_plugin(Arc::new(Dot)).unwrap();
+        assert!(renderer.register_plugin(Arc::new(Dot)).is_err());
+    }
+
+    #[test]
+    fn test_with_registry_loads_all_components() {
+        let registry = Registry::new().unwrap();
+        let renderer = ComponentsRenderer::with_registry(&registry);
+
+        assert_eq!(renderer.components.len(), registry.components().len());
+        assert_eq!(
+            renderer.resolve_color("accent"),
+            registry.resolve_color("accent").unwrap()
+        );
+    }
+
     // ========================================================================
     // Swatch Color Resolution (Parameterized)
     // ========================================================================
@@ -686,6 +1431,30 @@ mod tests {
         assert_eq!(opacity, Some(1.0)); // clamped to 1.0
     }
 
+    #[rstest]
+    #[case("label_color=red")]
+    #[case("labelColor=red")]
+    #[case("label-color=red")]
+    #[case("LABEL_COLOR=red")]
+    fn test_extract_params_normalizes_keys(#[case] arg: &str) {
+        let renderer = ComponentsRenderer::new().unwrap();
+        let (_, params) = renderer.extract_params("swatch", &[arg.to_string()]);
+        assert_eq!(params.get("label_color").map(String::as_str), Some("red"));
+    }
+
+    #[rstest]
+    #[case(&["color=red"], "red")]
+    #[case(&["color=red", "track=blue"], "blue")]
+    #[case(&["track=blue", "color=red"], "blue")]
+    #[case(&["Track=blue", "color=red"], "blue")]
+    fn test_extract_params_resolves_aliases(#[case] args: &[&str], #[case] expected: &str) {
+        let renderer = ComponentsRenderer::new().unwrap();
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let (_, params) = renderer.extract_params("progress", &args);
+        assert_eq!(params.get("track").map(String::as_str), Some(expected));
+        assert!(!params.contains_key("color"));
+    }
+
     #[test]
     fn test_extend_palette() {
         let mut renderer = ComponentsRenderer::new().unwrap();
@@ -740,6 +1509,45 @@ mod tests {
         assert_eq!(cfg.bg_color, "DEA584"); // Rust brand color
     }
 
+    #[rstest]
+    #[case("tech", "acme")]
+    #[case("swatch", "FF0000")]
+    fn test_remote_icon_without_fetch(#[case] component: &str, #[case] arg: &str) {
+        let args = [
+            arg.to_string(),
+            "icon=url:https://example.com/logo.svg".to_string(),
+        ];
+        let mut renderer = ComponentsRenderer::new().unwrap();
+        let err = renderer.expand(component, &args, None).unwrap_err();
+        assert!(
+            err.to_string().contains("requires fetch context"),
+            "{}",
+            err
+        );
+
+        renderer.set_placeholders(true);
+        let icon = match renderer.expand(component, &args, None).unwrap() {
+            ComponentOutput::Primitive(Primitive::Tech(cfg)) => cfg.icon,
+            ComponentOutput::Primitive(Primitive::Swatch { icon, .. }) => icon,
+            other => unreachable!("Unexpected output {:?}", other),
+        };
+        assert_eq!(icon, None);
+    }
+
+    #[test]
+    fn test_icon_markup_rejected() {
+        let renderer = ComponentsRenderer::new().unwrap();
+        let result = renderer.expand(
+            "tech",
+            &[
+                "acme".to_string(),
+                "icon=<script>alert(1)</script>".to_string(),
+            ],
+            None,
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_expand_unknown_component() {
         let renderer = ComponentsRenderer::new().unwrap();
@@ -761,6 +1569,22 @@ mod tests {
     #[case("tech", true)]
     #[case("row", true)]
     #[case("tech-group", true)]
+    #[case("hero", true)]
+    #[case("statcard", true)]
+    #[case("statcard-row", true)]
+    #[case("matrix", true)]
+    #[case("tree", true)]
+    #[case("divider", true)]
+    #[case("steps", true)]
+    #[case("latest-release", true)]
+    #[case("msrv", true)]
+    #[case("coverage", true)]
+    #[case("bench", true)]
+    #[case("tests", true)]
+    #[case("node-engines", true)]
+    #[case("python-requires", true)]
+    #[case("maintenance", true)]
+    #[case("ci-matrix", true)]
     #[case("nonexistent", false)]
     #[case("unknown-component", false)]
     fn test_has_component(#[case] name: &str, #[case] expected: bool) {
@@ -841,6 +1665,7 @@ mod style_tests {
     #[case(&["F41C80"], "flat-square")] // default
     #[case(&["abc123", "style=plastic"], "plastic")]
     #[case(&["color", "width=10", "style=social"], "social")]
+    #[case(&["F41C80", "Style=plastic"], "plastic")]
     fn test_split_style_arg(#[case] args: &[&str], #[case] expected_style: &str) {
         let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
         let (_, style) = ComponentsRenderer::split_style_arg(&args);
@@ -887,6 +1712,30 @@ mod style_tests {
         "Line1 Line2 Line3",
         "<p align=\"center\">"
     )]
+    #[case(
+        "![a \"quoted\" <b>&</b>](x.png)",
+        "center",
+        "<img alt=\"a &quot;quoted&quot; &lt;b&gt;&amp;&lt;/b&gt;\" src=\"x.png\">",
+        "<p align=\"center\">"
+    )]
+    #[case(
+        "![x](javascript:alert(1\"))",
+        "center",
+        "<img alt=\"x\" src=\"#\">",
+        "<p align=\"center\">"
+    )]
+    #[case(
+        "![x](a.png?q=\"onerror=1)",
+        "center",
+        "src=\"a.png?q=&quot;onerror=1\"",
+        "<p align=\"center\">"
+    )]
+    #[case(
+        "![](a.svg =80x20)",
+        "center",
+        "<img alt=\"\" src=\"a.svg\" width=\"80\" height=\"20\">",
+        "<p align=\"center\">"
+    )]
     fn test_apply_row(
         #[case] input: &str,
         #[case] align: &str,
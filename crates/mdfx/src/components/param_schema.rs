@@ -0,0 +1,488 @@
+//! Typed component parameters
+//!
+//! Handlers read `key=value` parameters leniently: a value that doesn't parse
+//! falls back to the default and an unknown key is ignored. [`ParamSchema`]
+//! describes what a component actually accepts - the registry's
+//! `optional_params` merged with the handler metadata in [`params`] - so
+//! check mode and the LSP can warn about `width=abc` or a misspelled key
+//! instead of silently rendering the default.
+//!
+//! Keys are matched after [`normalize_key`], so `labelColor` and
+//! `label-color` both mean `label_color`, and a parameter may list aliases
+//! in the registry (`progress` accepts `color=` for `track=`).
+
+use super::params::{self, ParamInfo};
+use crate::registry::Registry;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Canonical spelling of a parameter key: camelCase and kebab-case become
+/// snake_case
+pub fn normalize_key(key: &str) -> String {
+    let mut normalized = String::with_capacity(key.len() + 4);
+    let mut prev_lower = false;
+    for c in key.chars() {
+        if c == '-' {
+            normalized.push('_');
+            prev_lower = false;
+        } else if c.is_ascii_uppercase() {
+            if prev_lower {
+                normalized.push('_');
+            }
+            normalized.push(c.to_ascii_lowercase());
+            prev_lower = false;
+        } else {
+            normalized.push(c);
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        }
+    }
+    normalized
+}
+
+/// Value type of a component parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamType {
+    Number,
+    Boolean,
+    /// Palette name or hex value
+    Color,
+    /// Shield style name or alias
+    ShieldStyle,
+    /// One of a fixed list of values
+    Enum,
+    String,
+}
+
+impl ParamType {
+    /// Parse a registry type name; unknown names are free-form strings
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "number" => ParamType::Number,
+            "boolean" => ParamType::Boolean,
+            "color" => ParamType::Color,
+            "shield_style" => ParamType::ShieldStyle,
+            "enum" => ParamType::Enum,
+            _ => ParamType::String,
+        }
+    }
+
+    /// Registry type name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParamType::Number => "number",
+            ParamType::Boolean => "boolean",
+            ParamType::Color => "color",
+            ParamType::ShieldStyle => "shield_style",
+            ParamType::Enum => "enum",
+            ParamType::String => "string",
+        }
+    }
+}
+
+/// One parameter a component accepts
+#[derive(Debug, Clone)]
+pub struct ParamSpec {
+    pub name: String,
+    pub param_type: ParamType,
+    /// Default as written in the registry (may be prose such as "auto")
+    pub default: Option<String>,
+    pub description: Option<String>,
+    /// Accepted values of an enum or shield style parameter
+    pub values: Vec<String>,
+    /// Values the handler documents for completion; not enforced
+    pub suggestions: Vec<String>,
+    /// Other names accepted for this parameter
+    pub aliases: Vec<String>,
+    pub example: Option<&'static str>,
+}
+
+impl ParamSpec {
+    /// A parameter only the handler metadata knows about
+    fn from_handler(info: &ParamInfo) -> Self {
+        let suggestions: Vec<String> = info
+            .values
+            .unwrap_or_default()
+            .iter()
+            .map(|(value, _)| value.to_string())
+            .collect();
+        let is_flag =
+            !suggestions.is_empty() && suggestions.iter().all(|v| v == "true" || v == "false");
+
+        ParamSpec {
+            name: info.name.to_string(),
+            param_type: if is_flag {
+                ParamType::Boolean
+            } else {
+                ParamType::String
+            },
+            default: None,
+            description: Some(info.description.to_string()),
+            values: Vec::new(),
+            suggestions,
+            aliases: Vec::new(),
+            example: Some(info.example),
+        }
+    }
+
+    /// Check a value, returning what was expected if it doesn't fit
+    pub fn check(&self, value: &str) -> std::result::Result<(), String> {
+        match self.param_type {
+            // A non-numeric default such as "auto" can also be written out
+            ParamType::Number
+                if value.parse::<f64>().is_err() && self.default.as_deref() != Some(value) =>
+            {
+                Err("a number".to_string())
+            }
+            ParamType::Boolean if !matches!(value, "true" | "false" | "1" | "0") => {
+                Err("true or false".to_string())
+            }
+            ParamType::Enum | ParamType::ShieldStyle
+                if !self.values.is_empty() && !self.values.iter().any(|v| v == value) =>
+            {
+                Err(format!("one of: {}", self.values.join(", ")))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A problem with the parameters of one component call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamIssue {
+    /// The component has no parameter of this name
+    Unknown {
+        component: String,
+        param: String,
+        known: Vec<String>,
+    },
+    /// The value doesn't fit the parameter's type
+    InvalidValue {
+        component: String,
+        param: String,
+        value: String,
+        expected: String,
+    },
+}
+
+impl fmt::Display for ParamIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamIssue::Unknown {
+                component,
+                param,
+                known,
+            } => write!(
+                f,
+                "Unknown parameter '{}' for component '{}', ignored. Available: {}",
+                param,
+                component,
+                known.join(", ")
+            ),
+            ParamIssue::InvalidValue {
+                component,
+                param,
+                value,
+                expected,
+            } => write!(
+                f,
+                "Invalid value '{}' for parameter '{}' of component '{}', default used. Expected {}",
+                value, param, component, expected
+            ),
+        }
+    }
+}
+
+/// Handler metadata merged into a component's registry parameters
+fn handler_params(component: &str) -> &'static [ParamInfo] {
+    match component {
+        // Project badges render as tech badges; groups pass params to them
+        "tech" | "tech-group" | "msrv" | "node-engines" | "python-requires" => params::TECH_PARAMS,
+        "live" | "coverage" | "bench" | "tests" => params::BADGE_PARAMS,
+        _ => params::params_for_visualization(component).unwrap_or_default(),
+    }
+}
+
+/// Parameters accepted by one component
+#[derive(Debug, Clone)]
+pub struct ParamSchema {
+    component: String,
+    params: BTreeMap<String, ParamSpec>,
+}
+
+impl ParamSchema {
+    /// Schema of a registry component, or `None` if there is no such component
+    pub fn new(registry: &Registry, component: &str) -> Option<Self> {
+        let def = registry.component(component)?;
+
+        let mut params: BTreeMap<String, ParamSpec> = def
+            .optional_params
+            .iter()
+            .flatten()
+            .map(|(name, param)| {
+                let spec = ParamSpec {
+                    name: name.clone(),
+                    param_type: ParamType::parse(&param.param_type),
+                    default: Some(param.default.clone()).filter(|d| !d.is_empty()),
+                    description: param.description.clone(),
+                    values: param.values.clone().unwrap_or_default(),
+                    suggestions: Vec::new(),
+                    aliases: param.aliases.clone(),
+                    example: None,
+                };
+                (name.clone(), spec)
+            })
+            .collect();
+
+        let aliases: Vec<String> = params
+            .values()
+            .flat_map(|spec| spec.aliases.iter().cloned())
+            .collect();
+        // Handler metadata lists aliases as parameters of their own
+        for info in handler_params(component)
+            .iter()
+            .filter(|info| !aliases.iter().any(|a| a == info.name))
+        {
+            let handler = ParamSpec::from_handler(info);
+            let spec = params
+                .entry(info.name.to_string())
+                .or_insert_with(|| handler.clone());
+            spec.description = spec.description.take().or(handler.description);
+            spec.example = handler.example;
+            spec.suggestions = handler.suggestions;
+        }
+
+        // `style=` and the display size are split off before any handler runs
+        if matches!(def.component_type.as_str(), "native" | "dynamic") {
+            for info in params::DISPLAY_PARAMS {
+                params
+                    .entry(info.name.to_string())
+                    .or_insert_with(|| ParamSpec {
+                        param_type: ParamType::Number,
+                        ..ParamSpec::from_handler(info)
+                    });
+            }
+            params
+                .entry("style".to_string())
+                .or_insert_with(|| ParamSpec {
+                    name: "style".to_string(),
+                    param_type: ParamType::ShieldStyle,
+                    default: Some(registry.default_shield_style().to_string()),
+                    description: Some("Badge style".to_string()),
+                    values: Vec::new(),
+                    suggestions: Vec::new(),
+                    aliases: Vec::new(),
+                    example: None,
+                });
+        }
+
+        // The handlers, not the registry, know which sources exist
+        if component == "live" {
+            if let Some(source) = params.get_mut("source") {
+                source.values = params::valid_live_sources().map(String::from).collect();
+            }
+        }
+
+        let mut shield_styles: Vec<&String> = registry
+            .shield_styles()
+            .values()
+            .flat_map(|s| std::iter::once(&s.id).chain(s.aliases.iter()))
+            .collect();
+        shield_styles.sort_unstable();
+        for spec in params.values_mut() {
+            if spec.param_type == ParamType::ShieldStyle && spec.values.is_empty() {
+                spec.values = shield_styles.iter().map(|s| s.to_string()).collect();
+                // Styles a handler renders beyond the shield styles (tech outline)
+                for extra in std::mem::take(&mut spec.suggestions) {
+                    if !spec.values.contains(&extra) {
+                        spec.values.push(extra);
+                    }
+                }
+            }
+        }
+
+        Some(ParamSchema {
+            component: component.to_string(),
+            params,
+        })
+    }
+
+    /// Component the schema describes
+    pub fn component(&self) -> &str {
+        &self.component
+    }
+
+    /// Look up a parameter by name, alias or any key spelling
+    pub fn get(&self, name: &str) -> Option<&ParamSpec> {
+        self.params.get(&self.canonical(name))
+    }
+
+    /// Canonical name of a key: normalized, with aliases resolved
+    pub fn canonical(&self, key: &str) -> String {
+        let key = normalize_key(key);
+        if self.params.contains_key(&key) {
+            return key;
+        }
+        self.params
+            .values()
+            .find(|spec| spec.aliases.contains(&key))
+            .map(|spec| spec.name.clone())
+            .unwrap_or(key)
+    }
+
+    /// Parameters in name order
+    pub fn iter(&self) -> impl Iterator<Item = &ParamSpec> {
+        self.params.values()
+    }
+
+    /// Check the `key=value` arguments of a component call
+    ///
+    /// Positional arguments (those without `=`) are not checked.
+    pub fn validate(&self, args: &[String]) -> Vec<ParamIssue> {
+        args.iter()
+            .filter_map(|arg| arg.split_once('='))
+            .filter_map(|(key, value)| {
+                let Some(spec) = self.get(key) else {
+                    return Some(ParamIssue::Unknown {
+                        component: self.component.clone(),
+                        param: key.to_string(),
+                        known: self.params.keys().cloned().collect(),
+                    });
+                };
+                spec.check(value)
+                    .err()
+                    .map(|expected| ParamIssue::InvalidValue {
+                        component: self.component.clone(),
+                        param: key.to_string(),
+                        value: value.to_string(),
+                        expected,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn schema(component: &str) -> ParamSchema {
+        ParamSchema::new(&Registry::new().unwrap(), component).unwrap()
+    }
+
+    fn issues(component: &str, args: &[&str]) -> Vec<ParamIssue> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        schema(component).validate(&args)
+    }
+
+    #[test]
+    fn test_unknown_component() {
+        assert!(ParamSchema::new(&Registry::new().unwrap(), "nope").is_none());
+    }
+
+    #[rstest]
+    #[case("progress", &["75", "width=200", "fill=accent", "label=true"])]
+    #[case("progress", &["75", "thumb=12", "thumb_border=000000"])]
+    #[case("license", &["MIT", "width=auto", "style=flat"])]
+    #[case("tech", &["rust", "logo_size=14", "style=outline", "corners=left"])]
+    #[case("row", &["align=center"])]
+    #[case("live", &["github", "rust-lang/rust", "stars", "icon=github", "text=000000"])]
+    #[case("swatch", &["cobalt", "opacity=0.5", "style=square"])]
+    #[case("gauge", &["40", "display_width=120", "displayHeight=30"])]
+    fn test_valid_params(#[case] component: &str, #[case] args: &[&str]) {
+        assert_eq!(issues(component, args), vec![]);
+    }
+
+    #[rstest]
+    #[case("progress", &["75", "width=abc"], "width", "a number")]
+    #[case("tech", &["rust", "display_height=big"], "display_height", "a number")]
+    #[case("donut", &["50", "label=yes"], "label", "true or false")]
+    #[case("row", &["align=middle"], "align", "one of: left, center, right")]
+    #[case("version", &["1.0", "status=rc"], "status", "one of: stable, beta, alpha, deprecated, dev")]
+    fn test_invalid_value(
+        #[case] component: &str,
+        #[case] args: &[&str],
+        #[case] param: &str,
+        #[case] expected: &str,
+    ) {
+        let found = issues(component, args);
+        assert_eq!(found.len(), 1);
+        let ParamIssue::InvalidValue {
+            param: found_param,
+            expected: found_expected,
+            ..
+        } = &found[0]
+        else {
+            panic!("expected an invalid value: {:?}", found);
+        };
+        assert_eq!(found_param, param);
+        assert_eq!(found_expected, expected);
+    }
+
+    #[test]
+    fn test_invalid_shield_style() {
+        let found = issues("swatch", &["cobalt", "style=rounded-square"]);
+        assert_eq!(found.len(), 1);
+        let message = found[0].to_string();
+        assert!(message.contains("'swatch'"), "{}", message);
+        assert!(message.contains("flat-square"), "{}", message);
+    }
+
+    #[test]
+    fn test_unknown_param() {
+        let found = issues("progress", &["75", "widht=200"]);
+        assert_eq!(found.len(), 1);
+        let message = found[0].to_string();
+        assert!(
+            message.starts_with("Unknown parameter 'widht' for component 'progress'"),
+            "{}",
+            message
+        );
+        assert!(message.contains("width"), "{}", message);
+    }
+
+    #[rstest]
+    #[case("label_color", "label_color")]
+    #[case("labelColor", "label_color")]
+    #[case("label-color", "label_color")]
+    #[case("LABEL_COLOR", "label_color")]
+    #[case("thumbBorderWidth", "thumb_border_width")]
+    #[case("rx", "rx")]
+    fn test_normalize_key(#[case] key: &str, #[case] expected: &str) {
+        assert_eq!(normalize_key(key), expected);
+    }
+
+    #[rstest]
+    #[case("progress", &["75", "thumbBorder=000000", "label-color=red"])]
+    #[case("progress", &["75", "color=red"])]
+    #[case("version", &["1.0", "textColor=000000"])]
+    #[case("waveform", &["1,-1", "bar=4"])]
+    fn test_spellings_and_aliases_accepted(#[case] component: &str, #[case] args: &[&str]) {
+        assert_eq!(issues(component, args), vec![]);
+    }
+
+    #[test]
+    fn test_alias_checked_against_canonical() {
+        let found = issues("waveform", &["1,-1", "bar=wide"]);
+        assert_eq!(found.len(), 1);
+        assert!(found[0].to_string().contains("'bar'"), "{}", found[0]);
+        assert_eq!(schema("progress").canonical("color"), "track");
+    }
+
+    #[test]
+    fn test_live_sources_enforced() {
+        let spec = schema("live");
+        let source = spec.get("source").unwrap();
+        assert!(source.values.contains(&"readthedocs".to_string()));
+        assert!(source.check("gitlab").is_err());
+    }
+
+    #[test]
+    fn test_handler_suggestions_not_enforced() {
+        // logo_size lists presets but also takes pixel sizes
+        let spec = schema("tech");
+        let logo_size = spec.get("logo_size").unwrap();
+        assert!(logo_size.suggestions.contains(&"md".to_string()));
+        assert!(logo_size.check("14").is_ok());
+    }
+}
@@ -212,7 +212,7 @@ pub static TECH_PARAMS: &[ParamInfo] = &[
     // Advanced
     ParamInfo {
         name: "icon",
-        description: "Custom SVG path data",
+        description: "Custom SVG path data, or url:https://... for a remote SVG icon",
         example: "icon=M12 2L2 7...",
         values: None,
     },
@@ -230,6 +230,71 @@ pub static TECH_PARAMS: &[ParamInfo] = &[
     },
 ];
 
+/// Parameters shared by `label: value` badges (live, coverage, bench, tests)
+pub static BADGE_PARAMS: &[ParamInfo] = &[
+    ParamInfo {
+        name: "text",
+        description: "Text color",
+        example: "text=000000",
+        values: None,
+    },
+    ParamInfo {
+        name: "text_color",
+        description: "Alias for text",
+        example: "text_color=000000",
+        values: None,
+    },
+    ParamInfo {
+        name: "width",
+        description: "Badge width in pixels",
+        example: "width=120",
+        values: None,
+    },
+    ParamInfo {
+        name: "border",
+        description: "Border color",
+        example: "border=FFFFFF",
+        values: None,
+    },
+    ParamInfo {
+        name: "border_width",
+        description: "Border width in pixels",
+        example: "border_width=2",
+        values: None,
+    },
+    ParamInfo {
+        name: "rx",
+        description: "Corner radius",
+        example: "rx=6",
+        values: None,
+    },
+    ParamInfo {
+        name: "icon",
+        description: "Simple Icons logo name, or url:https://... for a remote SVG icon",
+        example: "icon=rust",
+        values: None,
+    },
+];
+
+/// Display size parameters, accepted by every image component
+///
+/// The parser applies these to the rendered reference, so the SVG itself
+/// doesn't change.
+pub static DISPLAY_PARAMS: &[ParamInfo] = &[
+    ParamInfo {
+        name: "display_width",
+        description: "Displayed width in pixels; height follows the aspect ratio",
+        example: "display_width=120",
+        values: None,
+    },
+    ParamInfo {
+        name: "display_height",
+        description: "Displayed height in pixels; width follows the aspect ratio",
+        example: "display_height=28",
+        values: None,
+    },
+];
+
 /// Type alias for live source definitions: (source_name, description, metrics)
 pub type LiveSourceDef = (
     &'static str,
@@ -237,7 +302,7 @@ pub type LiveSourceDef = (
     &'static [(&'static str, &'static str)],
 );
 
-/// Live badge sources and their metrics
+/// Live badge sources and their metrics; the first metric is the default
 pub static LIVE_SOURCES: &[LiveSourceDef] = &[
     (
         "github",
@@ -254,6 +319,11 @@ pub static LIVE_SOURCES: &[LiveSourceDef] = &[
             ("branch", "Default branch name"),
             ("topics", "Repository topics"),
             ("description", "Repository description"),
+            ("last_commit", "Date of the last push"),
+            ("downloads", "Asset downloads of all releases"),
+            ("release_downloads", "Asset downloads of the latest release"),
+            ("assets", "Asset count of the latest release"),
+            ("release_age", "Days since the latest release"),
         ],
     ),
     (
@@ -303,14 +373,14 @@ pub static LIVE_SOURCES: &[LiveSourceDef] = &[
         "actions",
         "GitHub Actions workflow status",
         &[
-            (
-                "status",
-                "Workflow run status (completed, in_progress, queued)",
-            ),
             (
                 "conclusion",
                 "Workflow conclusion (success, failure, cancelled)",
             ),
+            (
+                "status",
+                "Workflow run status (completed, in_progress, queued)",
+            ),
             ("run_number", "Workflow run number"),
             ("workflow", "Workflow name"),
             ("event", "Trigger event"),
@@ -327,6 +397,7 @@ pub static LIVE_SOURCES: &[LiveSourceDef] = &[
             ("tag", "Latest tag"),
             ("description", "Image description"),
             ("official", "Official or Community"),
+            ("updated", "Date the image was last pushed"),
         ],
     ),
     (
@@ -370,6 +441,113 @@ pub static LIVE_SOURCES: &[LiveSourceDef] = &[
             ("license", "Package license"),
         ],
     ),
+    (
+        "scorecard",
+        "OpenSSF Scorecard security posture",
+        &[
+            ("score", "Aggregate score (0-10)"),
+            ("date", "Date of the latest scan"),
+            ("binary_artifacts", "Binary-Artifacts check score"),
+            ("branch_protection", "Branch-Protection check score"),
+            ("ci_tests", "CI-Tests check score"),
+            ("cii_best_practices", "CII-Best-Practices check score"),
+            ("code_review", "Code-Review check score"),
+            ("contributors", "Contributors check score"),
+            ("dangerous_workflow", "Dangerous-Workflow check score"),
+            (
+                "dependency_update_tool",
+                "Dependency-Update-Tool check score",
+            ),
+            ("fuzzing", "Fuzzing check score"),
+            ("license", "License check score"),
+            ("maintained", "Maintained check score"),
+            ("packaging", "Packaging check score"),
+            ("pinned_dependencies", "Pinned-Dependencies check score"),
+            ("sast", "SAST check score"),
+            ("security_policy", "Security-Policy check score"),
+            ("signed_releases", "Signed-Releases check score"),
+            ("token_permissions", "Token-Permissions check score"),
+            ("vulnerabilities", "Vulnerabilities check score"),
+        ],
+    ),
+    (
+        "osv",
+        "OSV known vulnerabilities for a package version",
+        &[
+            ("vulnerabilities", "Number of known advisories"),
+            ("vulnerable", "Whether any advisory applies"),
+            ("ids", "Advisory IDs"),
+        ],
+    ),
+    (
+        "librariesio",
+        "Libraries.io SourceRank and dependents (needs LIBRARIES_IO_API_KEY)",
+        &[
+            ("sourcerank", "SourceRank score"),
+            ("dependents", "Packages depending on it"),
+            ("dependent_repos", "Repositories depending on it"),
+            ("stars", "Repository stars"),
+            ("forks", "Repository forks"),
+            ("version", "Latest release"),
+        ],
+    ),
+    (
+        "discord",
+        "Discord server members by invite code or server id",
+        &[
+            ("members", "Approximate member count"),
+            ("online", "Members online now"),
+            ("name", "Server name"),
+        ],
+    ),
+    (
+        "matrix",
+        "Matrix room members (server/room for #room:server)",
+        &[("members", "Joined members"), ("name", "Room name")],
+    ),
+    (
+        "reddit",
+        "Reddit subreddit subscribers",
+        &[
+            ("subscribers", "Subscriber count"),
+            ("active", "Users online now"),
+            ("title", "Subreddit title"),
+        ],
+    ),
+    (
+        "docsrs",
+        "docs.rs documentation build status",
+        &[
+            ("status", "Build status: passing or failing"),
+            ("version", "Release the status is for"),
+        ],
+    ),
+    (
+        "readthedocs",
+        "Read the Docs build status",
+        &[("status", "Build status: passing, failing or unknown")],
+    ),
+    (
+        "sponsors",
+        "GitHub Sponsors of a user or organization (needs GITHUB_TOKEN)",
+        &[
+            ("sponsors", "Number of sponsors"),
+            ("sponsoring", "Accounts it sponsors"),
+        ],
+    ),
+    (
+        "opencollective",
+        "Open Collective backers and balance",
+        &[
+            ("backers", "Number of backers"),
+            ("balance", "Current balance with currency"),
+        ],
+    ),
+    (
+        "patreon",
+        "Patreon patrons by numeric campaign id",
+        &[("patrons", "Number of patrons")],
+    ),
 ];
 
 /// Get valid live sources
@@ -385,13 +563,26 @@ pub fn metrics_for_source(source: &str) -> Option<&'static [(&'static str, &'sta
         .map(|(_, _, metrics)| *metrics)
 }
 
+/// Metric used when a live badge doesn't name one
+pub fn default_metric(source: &str) -> Option<&'static str> {
+    metrics_for_source(source).and_then(|metrics| metrics.first().map(|(name, _)| *name))
+}
+
 /// Check if a metric is valid for a source
+///
+/// `-` and `_` are interchangeable, as in `release-downloads`.
 pub fn is_valid_metric(source: &str, metric: &str) -> bool {
+    let metric = normalize_metric(metric);
     metrics_for_source(source)
         .map(|metrics| metrics.iter().any(|(name, _)| *name == metric))
         .unwrap_or(false)
 }
 
+/// Canonical spelling of a metric name, with `_` for `-`
+pub fn normalize_metric(metric: &str) -> String {
+    metric.replace('-', "_")
+}
+
 /// Get all valid tech badge parameter names
 pub fn valid_tech_param_names() -> impl Iterator<Item = &'static str> {
     TECH_PARAMS.iter().map(|p| p.name)
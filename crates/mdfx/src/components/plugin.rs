@@ -0,0 +1,86 @@
+//! Custom components supplied by library users
+//!
+//! A [`ComponentPlugin`] adds a `{{ui:NAME:...}}` component without touching
+//! `registry.json`. Register it with
+//! [`TemplateParser::register_component`](crate::TemplateParser::register_component);
+//! the plugin receives the parsed arguments and returns a [`ComponentOutput`]
+//! that is rendered like any built-in component.
+//!
+//! ```
+//! use mdfx::{ComponentOutput, ComponentPlugin, PluginCall, Result, TemplateParser};
+//! use std::sync::Arc;
+//!
+//! struct Callout;
+//!
+//! impl ComponentPlugin for Callout {
+//!     fn name(&self) -> &str {
+//!         "callout"
+//!     }
+//!
+//!     fn expand(&self, call: &PluginCall<'_>) -> Result<ComponentOutput> {
+//!         let kind = call.args.first().map(String::as_str).unwrap_or("note");
+//!         Ok(ComponentOutput::Template(format!(
+//!             "{{{{mathbold}}}}{}{{{{/mathbold}}}}: {}",
+//!             kind.to_uppercase(),
+//!             call.content.unwrap_or_default()
+//!         )))
+//!     }
+//! }
+//!
+//! let mut parser = TemplateParser::new().unwrap();
+//! parser.register_component(Arc::new(Callout)).unwrap();
+//!
+//! let out = parser.process("{{ui:callout:tip}}Use aliases{{/ui}}").unwrap();
+//! assert_eq!(out, "𝐓𝐈𝐏: Use aliases");
+//! ```
+
+use super::params::ParamInfo;
+use super::{ComponentOutput, ComponentsRenderer};
+use crate::error::Result;
+use std::collections::HashMap;
+
+/// A user-defined `{{ui:...}}` component
+pub trait ComponentPlugin: Send + Sync {
+    /// Component name as written after `ui:`
+    fn name(&self) -> &str;
+
+    /// One-line description, shown alongside built-in components
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// Accepted `key=value` parameters
+    ///
+    /// When non-empty, parameters not listed here are rejected before
+    /// [`expand`](Self::expand) is called.
+    fn params(&self) -> &[ParamInfo] {
+        &[]
+    }
+
+    /// Expand one use of the component
+    fn expand(&self, call: &PluginCall<'_>) -> Result<ComponentOutput>;
+}
+
+/// Arguments for one plugin component use
+pub struct PluginCall<'a> {
+    /// Positional arguments, in order
+    pub args: Vec<String>,
+    /// `key=value` arguments
+    pub params: HashMap<String, String>,
+    /// Block content for `{{ui:name}}...{{/ui}}`, `None` when self-closing
+    pub content: Option<&'a str>,
+    pub(super) renderer: &'a ComponentsRenderer,
+}
+
+impl PluginCall<'_> {
+    /// Get a `key=value` parameter
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+
+    /// Resolve a palette name (including custom palette entries) to hex,
+    /// returning other values unchanged
+    pub fn resolve_color(&self, color: &str) -> String {
+        self.renderer.resolve_color(color)
+    }
+}
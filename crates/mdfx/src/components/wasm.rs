@@ -0,0 +1,320 @@
+//! Component plugins compiled to WebAssembly
+//!
+//! A [`WasmPlugin`] is a [`ComponentPlugin`] whose `expand` runs in a
+//! WebAssembly module, so CLI users can add components without building
+//! mdfx. The CLI loads the modules listed under `"plugins"` in `.mdfx.json`.
+//!
+//! Modules run sandboxed: they get no imports, so they can't read files,
+//! open connections or see the environment, and every call starts from a
+//! fresh instance with capped fuel and memory.
+//!
+//! # Module interface
+//!
+//! A plugin module exports:
+//!
+//! - `memory`: its linear memory
+//! - `alloc(len: i32) -> i32`: a buffer of `len` bytes for the call
+//! - `expand(ptr: i32, len: i32) -> i64`: expand one component use
+//!
+//! `expand` receives a JSON object, written to a buffer from `alloc`:
+//!
+//! ```json
+//! {"args": ["tip"], "params": {"icon": "bulb"}, "content": "Use aliases"}
+//! ```
+//!
+//! `content` is `null` for self-closing uses. It returns the location of a
+//! UTF-8 JSON reply in its memory, packed as `(ptr << 32) | len`. The reply
+//! is either `{"template": "..."}`, mdfx markup expanded like a built-in
+//! component's template, or `{"error": "..."}`.
+
+use super::plugin::{ComponentPlugin, PluginCall};
+use super::ComponentOutput;
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use wasmi::{Config, Engine, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Instructions a single expansion may run
+const FUEL_PER_CALL: u64 = 50_000_000;
+
+/// Largest linear memory a plugin may grow to (16 MiB)
+const MAX_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Largest reply a plugin may return (1 MiB)
+const MAX_REPLY_BYTES: usize = 1024 * 1024;
+
+/// A `{{ui:...}}` component implemented by a WebAssembly module
+pub struct WasmPlugin {
+    name: String,
+    description: String,
+    engine: Engine,
+    module: Module,
+}
+
+#[derive(Serialize)]
+struct Request<'a> {
+    args: &'a [String],
+    params: &'a HashMap<String, String>,
+    content: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Reply {
+    Template(String),
+    Error(String),
+}
+
+impl WasmPlugin {
+    /// Compile a plugin from WebAssembly bytes (binary format)
+    pub fn from_bytes(name: impl Into<String>, wasm: &[u8]) -> Result<Self> {
+        let name = name.into();
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm)
+            .map_err(|e| Error::ParseError(format!("Plugin '{}': invalid module: {}", name, e)))?;
+        if let Some(import) = module.imports().next() {
+            return Err(Error::ParseError(format!(
+                "Plugin '{}': imports {}::{}, but plugins can't import anything",
+                name,
+                import.module(),
+                import.name()
+            )));
+        }
+        Ok(Self {
+            name,
+            description: String::new(),
+            engine,
+            module,
+        })
+    }
+
+    /// Compile a plugin from a `.wasm` file
+    pub fn load(name: impl Into<String>, path: &Path) -> Result<Self> {
+        let name = name.into();
+        let wasm = std::fs::read(path).map_err(|e| {
+            Error::ParseError(format!(
+                "Plugin '{}': can't read {}: {}",
+                name,
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_bytes(name, &wasm)
+    }
+
+    /// Set the description shown alongside built-in components
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Run `expand` in a fresh instance, returning the raw reply
+    fn call(&self, request: &[u8]) -> std::result::Result<Vec<u8>, String> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .instances(1)
+            .build();
+        let mut store: Store<StoreLimits> = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(FUEL_PER_CALL).map_err(|e| e.to_string())?;
+
+        let instance = Linker::<StoreLimits>::new(&self.engine)
+            .instantiate(&mut store, &self.module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| e.to_string())?;
+        let memory: Memory = instance
+            .get_memory(&store, "memory")
+            .ok_or("module doesn't export its memory")?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&store, "alloc")
+            .map_err(|e| format!("alloc: {}", e))?;
+        let expand = instance
+            .get_typed_func::<(i32, i32), i64>(&store, "expand")
+            .map_err(|e| format!("expand: {}", e))?;
+
+        let len = i32::try_from(request.len()).map_err(|_| "arguments are too large")?;
+        let ptr = alloc.call(&mut store, len).map_err(|e| e.to_string())?;
+        memory
+            .write(&mut store, ptr as u32 as usize, request)
+            .map_err(|e| e.to_string())?;
+        let packed = expand
+            .call(&mut store, (ptr, len))
+            .map_err(|e| e.to_string())? as u64;
+
+        let (ptr, len) = ((packed >> 32) as usize, (packed & 0xFFFF_FFFF) as usize);
+        if len > MAX_REPLY_BYTES {
+            return Err(format!(
+                "reply is {} bytes, the limit is {}",
+                len, MAX_REPLY_BYTES
+            ));
+        }
+        if ptr
+            .checked_add(len)
+            .is_none_or(|end| end > memory.data(&store).len())
+        {
+            return Err("reply is outside the module's memory".to_string());
+        }
+        let mut reply = vec![0; len];
+        memory
+            .read(&store, ptr, &mut reply)
+            .map_err(|_| "reply is outside the module's memory")?;
+        Ok(reply)
+    }
+}
+
+impl ComponentPlugin for WasmPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn expand(&self, call: &PluginCall<'_>) -> Result<ComponentOutput> {
+        let request = serde_json::to_vec(&Request {
+            args: &call.args,
+            params: &call.params,
+            content: call.content,
+        })?;
+        let failed =
+            |message: String| Error::ParseError(format!("Plugin '{}': {}", self.name, message));
+        let reply = self.call(&request).map_err(failed)?;
+        match serde_json::from_slice(&reply) {
+            Ok(Reply::Template(template)) => Ok(ComponentOutput::Template(template)),
+            Ok(Reply::Error(message)) => Err(failed(message)),
+            Err(e) => Err(failed(format!("invalid reply: {}", e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TemplateParser;
+    use rstest::rstest;
+    use std::sync::Arc;
+
+    /// Replies with `reply`, ignoring its arguments
+    fn fixed_reply(reply: &str) -> String {
+        format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 1024) "{}")
+                (func (export "alloc") (param i32) (result i32) i32.const 0)
+                (func (export "expand") (param i32 i32) (result i64)
+                    i64.const {}))"#,
+            reply.replace('"', "\\\""),
+            (1024u64 << 32) | reply.len() as u64
+        )
+    }
+
+    /// Replies with the request JSON as the template, `"` swapped for `'`
+    const ECHO: &str = r#"(module
+        (memory (export "memory") 1)
+        (data (i32.const 0) "{\"template\":\"")
+        (func (export "alloc") (param i32) (result i32) i32.const 13)
+        (func (export "expand") (param $ptr i32) (param $len i32) (result i64)
+            (local $i i32)
+            (block $done
+                (loop $next
+                    (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                    (if (i32.eq (i32.load8_u (i32.add (local.get $ptr) (local.get $i))) (i32.const 34))
+                        (then (i32.store8 (i32.add (local.get $ptr) (local.get $i)) (i32.const 39))))
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br $next)))
+            ;; close the string and the object: `"}`
+            (i32.store8 (i32.add (local.get $ptr) (local.get $len)) (i32.const 34))
+            (i32.store8 (i32.add (i32.add (local.get $ptr) (local.get $len)) (i32.const 1)) (i32.const 125))
+            (i64.extend_i32_u (i32.add (local.get $len) (i32.const 15)))))"#;
+
+    fn parser_with(name: &str, wat: &str) -> TemplateParser {
+        let wasm = wat::parse_str(wat).unwrap();
+        let mut parser = TemplateParser::new().unwrap();
+        parser
+            .register_component(Arc::new(WasmPlugin::from_bytes(name, &wasm).unwrap()))
+            .unwrap();
+        parser
+    }
+
+    #[test]
+    fn test_template_reply_is_expanded() {
+        let parser = parser_with(
+            "shout",
+            &fixed_reply(r#"{"template":"{{mathbold}}HI{{/mathbold}}"}"#),
+        );
+        assert_eq!(parser.process("{{ui:shout/}}").unwrap(), "𝐇𝐈");
+    }
+
+    #[test]
+    fn test_module_receives_arguments() {
+        let parser = parser_with("echo", ECHO);
+        assert_eq!(
+            parser.process("{{ui:echo:tip:size=2}}body{{/ui}}").unwrap(),
+            "{'args':['tip'],'params':{'size':'2'},'content':'body'}"
+        );
+        assert_eq!(
+            parser.process("{{ui:echo/}}").unwrap(),
+            "{'args':[],'params':{},'content':null}"
+        );
+    }
+
+    #[test]
+    fn test_error_reply() {
+        let parser = parser_with("fail", &fixed_reply(r#"{"error":"no such icon"}"#));
+        let err = parser.process("{{ui:fail/}}").unwrap_err();
+        assert!(err.to_string().contains("Plugin 'fail': no such icon"));
+    }
+
+    #[test]
+    fn test_runaway_module_runs_out_of_fuel() {
+        let parser = parser_with(
+            "spin",
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) i32.const 0)
+                (func (export "expand") (param i32 i32) (result i64)
+                    (loop $forever (br $forever))
+                    i64.const 0))"#,
+        );
+        let err = parser.process("{{ui:spin/}}").unwrap_err();
+        assert!(err.to_string().contains("Plugin 'spin'"), "{}", err);
+    }
+
+    #[rstest]
+    #[case((1024u64 << 32) | 0xFFFF_FFFF, "reply is 4294967295 bytes")]
+    #[case((60_000u64 << 32) | 10_000, "outside the module's memory")]
+    #[case((0xFFFF_FFFFu64 << 32) | 16, "outside the module's memory")]
+    fn test_reply_out_of_range(#[case] packed: u64, #[case] message: &str) {
+        let parser = parser_with(
+            "liar",
+            &format!(
+                r#"(module
+                    (memory (export "memory") 1)
+                    (func (export "alloc") (param i32) (result i32) i32.const 0)
+                    (func (export "expand") (param i32 i32) (result i64)
+                        i64.const {}))"#,
+                packed as i64
+            ),
+        );
+        let err = parser.process("{{ui:liar/}}").unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+
+    #[test]
+    fn test_imports_are_rejected() {
+        let wasm = wat::parse_str(
+            r#"(module (import "env" "read_file" (func (param i32))) (memory (export "memory") 1))"#,
+        )
+        .unwrap();
+        let err = WasmPlugin::from_bytes("reader", &wasm).err().unwrap();
+        assert!(
+            err.to_string().contains("imports env::read_file"),
+            "{}",
+            err
+        );
+    }
+}
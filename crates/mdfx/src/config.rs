@@ -1,11 +1,18 @@
 //! Configuration file support for mdfx
 //!
 //! Loads project-specific configuration from `.mdfx.json` files,
-//! including user-defined template partials.
+//! including user-defined template partials, palette colors, glyphs, and
+//! style defaults.
 
 use crate::error::{Error, Result};
+use crate::formatter::FormatOptions;
+use crate::manifest::FilenameScheme;
+use crate::options::LimitsConfig;
+use crate::publish::PublishConfig;
+use crate::targets::{get_target, split_front_matter, BackendType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
 
 /// A user-defined partial template
@@ -19,6 +26,309 @@ pub struct PartialDef {
     pub description: Option<String>,
 }
 
+/// Default spacing or separator for one style, from `"styles"` in `.mdfx.json`
+///
+/// Applied to `{{style}}` templates that set neither `:spacing=` nor
+/// `:separator=`. A document can override them in its front matter, see
+/// [`front_matter_styles`].
+///
+/// ```json
+/// "styles": {
+///   "mathbold": { "spacing": 1 },
+///   "script": { "separator": "dot" }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StyleDefaults {
+    /// Spaces between characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<usize>,
+
+    /// Separator name (`dot`, `arrow`) or a single character; takes
+    /// precedence over `spacing`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separator: Option<String>,
+}
+
+impl StyleDefaults {
+    /// Parse the front matter form, `spacing=1` and/or `separator=dot`
+    /// separated by spaces
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut defaults = Self::default();
+        for part in spec.split_whitespace() {
+            match part.split_once('=') {
+                Some(("spacing", value)) => {
+                    defaults.spacing = Some(value.parse().map_err(|_| {
+                        Error::ParseError(format!(
+                            "Invalid spacing '{}' in style defaults, expected a number",
+                            value
+                        ))
+                    })?);
+                }
+                Some(("separator", value)) if !value.is_empty() => {
+                    defaults.separator = Some(value.to_string());
+                }
+                _ => {
+                    return Err(Error::ParseError(format!(
+                        "Invalid style default '{}'. Use spacing=N or separator=NAME",
+                        part
+                    )))
+                }
+            }
+        }
+        Ok(defaults)
+    }
+}
+
+/// Style defaults set by a document's front matter
+///
+/// Read from an `mdfx-styles` block, one style per line; keys other than
+/// `mdfx-styles` are ignored:
+///
+/// ```yaml
+/// ---
+/// title: Release notes
+/// mdfx-styles:
+///   mathbold: spacing=1
+///   script: separator=dot
+/// ---
+/// ```
+pub fn front_matter_styles(markdown: &str) -> Result<HashMap<String, StyleDefaults>> {
+    let (front_matter, _) = split_front_matter(markdown);
+    let mut styles = HashMap::new();
+    let mut in_block = false;
+
+    for line in front_matter.lines().skip(1) {
+        if !line.starts_with([' ', '\t']) {
+            in_block = line.trim_end() == "mdfx-styles:";
+            continue;
+        }
+        if !in_block || line.trim().is_empty() {
+            continue;
+        }
+        let (style, spec) = line.trim().split_once(':').ok_or_else(|| {
+            Error::ParseError(format!(
+                "Invalid mdfx-styles entry '{}'. Use STYLE: spacing=N or STYLE: separator=NAME",
+                line.trim()
+            ))
+        })?;
+        styles.insert(style.trim().to_string(), StyleDefaults::parse(spec)?);
+    }
+
+    Ok(styles)
+}
+
+/// Source repository of the project, from `"repository"` in `.mdfx.json`
+///
+/// Targets that show a README away from the repository (crates.io) use it
+/// to turn relative links into absolute URLs.
+///
+/// ```json
+/// "repository": { "url": "https://github.com/owner/repo", "branch": "main" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepositoryConfig {
+    /// Web URL of the repository
+    pub url: String,
+
+    /// Branch that links point at
+    #[serde(default = "default_branch")]
+    pub branch: String,
+}
+
+fn default_branch() -> String {
+    "main".to_string()
+}
+
+impl RepositoryConfig {
+    /// Repository at `url`, linking to the `main` branch
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            branch: default_branch(),
+        }
+    }
+
+    /// Set the branch that links point at
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = branch.into();
+        self
+    }
+
+    /// Host name of the repository URL
+    pub fn host(&self) -> Option<&str> {
+        let rest = self
+            .url
+            .split_once("://")
+            .map_or(self.url.as_str(), |(_, r)| r);
+        rest.split(['/', ':']).next().filter(|h| !h.is_empty())
+    }
+
+    /// URL of the page showing `path` (a file or directory)
+    pub fn file_url(&self, path: &str) -> String {
+        self.url_for(path, false)
+    }
+
+    /// URL of the raw contents of `path`, for images
+    pub fn raw_url(&self, path: &str) -> String {
+        self.url_for(path, true)
+    }
+
+    fn url_for(&self, path: &str, raw: bool) -> String {
+        let base = self.url.trim_end_matches('/').trim_end_matches(".git");
+        let path = path.trim_start_matches("./").trim_start_matches('/');
+        let view = if raw { "raw" } else { "blob" };
+        match self.host() {
+            Some("github.com") if raw => {
+                let repo = base.split_once("github.com/").map_or(base, |(_, r)| r);
+                format!(
+                    "https://raw.githubusercontent.com/{}/{}/{}",
+                    repo, self.branch, path
+                )
+            }
+            Some(host) if host.contains("gitlab") => {
+                format!("{}/-/{}/{}/{}", base, view, self.branch, path)
+            }
+            _ => format!("{}/{}/{}/{}", base, view, self.branch, path),
+        }
+    }
+}
+
+/// Settings for one target, from `"targets"` in `.mdfx.json`
+///
+/// Applied by `process`, `build`, and `watch` whenever that target is
+/// rendered. Command-line flags still take precedence.
+///
+/// ```json
+/// "targets": {
+///   "github": { "palette": { "accent": "58A6FF" } },
+///   "pypi": { "backend": "plaintext", "post_process": false, "wrap": 79 }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TargetOverrides {
+    /// Palette colors for this target, layered over `palette`
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub palette: HashMap<String, String>,
+
+    /// Backend to render with instead of the target's preferred one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend: Option<BackendType>,
+
+    /// Directory generated assets are written to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assets_dir: Option<String>,
+
+    /// Run the target's post-processing (alert conversion, link rewriting,
+    /// escaping); defaults to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_process: Option<bool>,
+
+    /// Column width to reflow paragraphs to, after post-processing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrap: Option<usize>,
+}
+
+impl TargetOverrides {
+    /// Whether the target's post-processing should run
+    pub fn runs_post_process(&self) -> bool {
+        self.post_process.unwrap_or(true)
+    }
+
+    /// `markdown` reflowed to the `wrap` width, or unchanged without one
+    ///
+    /// See [`reflow`](crate::reflow::reflow) for what is rewrapped.
+    pub fn apply_wrap(&self, markdown: String) -> String {
+        match self.wrap {
+            Some(width) => crate::reflow::reflow(&markdown, width),
+            None => markdown,
+        }
+    }
+}
+
+/// A named chain of stages for `mdfx run`, from `"pipelines"` in `.mdfx.json`
+///
+/// Each stage takes the text the previous one produced, so a document can be
+/// rendered for one target and then checked or reflowed without
+/// intermediate files.
+///
+/// ```json
+/// "pipelines": {
+///   "readme": {
+///     "input": "README.template.md",
+///     "output": "README.md",
+///     "stages": [{ "process": "github" }, { "wrap": 100 }, "link_check"]
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineConfig {
+    /// File the first stage reads
+    pub input: String,
+
+    /// File the last stage's text is written to (default: stdout)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+
+    /// Stages, run in order
+    #[serde(default)]
+    pub stages: Vec<PipelineStage>,
+}
+
+/// A WebAssembly component plugin, from `"plugins"` in `.mdfx.json`
+///
+/// The CLI registers each one as `{{ui:NAME:...}}`; see
+/// [`WasmPlugin`](crate::WasmPlugin) (feature `wasm`) for the module interface.
+///
+/// ```json
+/// "plugins": {
+///   "callout": { "wasm": "plugins/callout.wasm", "description": "Boxed note" }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginConfig {
+    /// Path to the `.wasm` module, relative to the working directory
+    pub wasm: String,
+
+    /// One-line description, shown alongside built-in components
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// One step of a [`PipelineConfig`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineStage {
+    /// Expand templates for a target, applying its `targets` settings and
+    /// post-processing like `mdfx process`
+    Process(String),
+    /// Run only a target's post-processing, e.g. to convert callouts
+    PostProcess(String),
+    /// Reflow paragraphs to a column width
+    Wrap(usize),
+    /// Fail if anchors, relative links or asset references are broken
+    LinkCheck,
+    /// Fail on constructs a target can't render
+    Compat(String),
+    /// Write the text so far to a file and keep going
+    Write(String),
+}
+
+impl fmt::Display for PipelineStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipelineStage::Process(target) => write!(f, "process {}", target),
+            PipelineStage::PostProcess(target) => write!(f, "post_process {}", target),
+            PipelineStage::Wrap(width) => write!(f, "wrap {}", width),
+            PipelineStage::LinkCheck => write!(f, "link_check"),
+            PipelineStage::Compat(target) => write!(f, "compat {}", target),
+            PipelineStage::Write(path) => write!(f, "write {}", path),
+        }
+    }
+}
+
 /// mdfx configuration loaded from `.mdfx.json`
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MdfxConfig {
@@ -29,6 +339,82 @@ pub struct MdfxConfig {
     /// Custom color palette overrides
     #[serde(default)]
     pub palette: HashMap<String, String>,
+
+    /// Custom glyphs (name -> character), usable as `{{glyph:name/}}`
+    #[serde(default)]
+    pub glyphs: HashMap<String, String>,
+
+    /// Options for `mdfx fmt` and LSP formatting
+    #[serde(default)]
+    pub format: FormatOptions,
+
+    /// Nesting and size limits for template expansion
+    #[serde(default)]
+    pub limits: LimitsConfig,
+
+    /// Object storage for `mdfx process --publish`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish: Option<PublishConfig>,
+
+    /// Source repository, for targets that rewrite relative links
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<RepositoryConfig>,
+
+    /// Per-target settings, keyed by target name or alias
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub targets: HashMap<String, TargetOverrides>,
+
+    /// Changelog for `{{ui:latest-release/}}` (default: `CHANGELOG.md`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<String>,
+
+    /// Default spacing or separator per style, keyed by style name or alias
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub styles: HashMap<String, StyleDefaults>,
+
+    /// Leave `$...$` and `$$...$$` math regions unprocessed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub math: Option<bool>,
+
+    /// Parse markdown as CommonMark so templates leave link destinations,
+    /// reference definitions, and HTML tags alone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commonmark: Option<bool>,
+
+    /// Hash length and readable slug of generated asset filenames
+    #[serde(default)]
+    pub filenames: FilenameScheme,
+
+    /// Default `locale=` for live badge values (e.g. `de-DE`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// Default `lang=` for badge labels (e.g. `de`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+
+    /// Badge labels by metric (e.g. `"stars": "★"`), used when a badge sets
+    /// no `label=`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub labels: HashMap<String, String>,
+
+    /// Project icons (name -> SVG document), usable as `icon=name` on tech
+    /// and swatch badges and as the icon of `{{ui:tech:name/}}`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub icons: HashMap<String, String>,
+
+    /// Reject icons that need scripts, event handlers or external
+    /// references removed, instead of embedding the rest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict_icons: Option<bool>,
+
+    /// Named stage chains for `mdfx run`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub pipelines: HashMap<String, PipelineConfig>,
+
+    /// WebAssembly component plugins, keyed by component name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub plugins: HashMap<String, PluginConfig>,
 }
 
 impl MdfxConfig {
@@ -100,10 +486,62 @@ impl MdfxConfig {
         self.partials.keys()
     }
 
+    /// Settings for a target, matched by its name or any of its aliases
+    ///
+    /// Returns the defaults when the config has no entry for the target.
+    pub fn target_overrides(&self, target: &str) -> TargetOverrides {
+        let name = get_target(target).map_or_else(|| target.to_string(), |t| t.name().to_string());
+        let mut keys: Vec<&String> = self
+            .targets
+            .keys()
+            .filter(|key| key.as_str() == name || get_target(key).is_some_and(|t| t.name() == name))
+            .collect();
+        // An exact name wins over aliases
+        keys.sort_by_key(|key| (key.as_str() != name, key.as_str()));
+        keys.first()
+            .and_then(|key| self.targets.get(*key))
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Merge another config into this one (other takes precedence)
     pub fn merge(&mut self, other: MdfxConfig) {
         self.partials.extend(other.partials);
         self.palette.extend(other.palette);
+        self.glyphs.extend(other.glyphs);
+        self.format = other.format;
+        self.limits = other.limits;
+        if other.publish.is_some() {
+            self.publish = other.publish;
+        }
+        if other.repository.is_some() {
+            self.repository = other.repository;
+        }
+        self.targets.extend(other.targets);
+        if other.changelog.is_some() {
+            self.changelog = other.changelog;
+        }
+        self.styles.extend(other.styles);
+        if other.math.is_some() {
+            self.math = other.math;
+        }
+        if other.commonmark.is_some() {
+            self.commonmark = other.commonmark;
+        }
+        self.filenames = other.filenames;
+        if other.locale.is_some() {
+            self.locale = other.locale;
+        }
+        if other.lang.is_some() {
+            self.lang = other.lang;
+        }
+        self.labels.extend(other.labels);
+        self.icons.extend(other.icons);
+        if other.strict_icons.is_some() {
+            self.strict_icons = other.strict_icons;
+        }
+        self.pipelines.extend(other.pipelines);
+        self.plugins.extend(other.plugins);
     }
 }
 
@@ -136,11 +574,15 @@ mod tests {
             },
             "palette": {
                 "brand": "FF5500"
+            },
+            "glyphs": {
+                "logo": "⌬"
             }
         }"#;
 
         let config: MdfxConfig = serde_json::from_str(json).unwrap();
         assert_eq!(config.partials.len(), 2);
+        assert_eq!(config.glyphs.get("logo"), Some(&"⌬".to_string()));
         assert!(config.has_partial("hero"));
         assert!(config.has_partial("techstack"));
         assert_eq!(config.palette.get("brand"), Some(&"FF5500".to_string()));
@@ -173,6 +615,8 @@ mod tests {
         let config: MdfxConfig = serde_json::from_str(json).unwrap();
         assert!(config.partials.is_empty());
         assert!(config.palette.is_empty());
+        assert!(config.glyphs.is_empty());
+        assert!(config.changelog.is_none());
     }
 
     #[test]
@@ -195,9 +639,242 @@ mod tests {
             },
         );
 
+        config2.changelog = Some("docs/CHANGES.md".to_string());
+
         config1.merge(config2);
         assert!(config1.has_partial("a"));
         assert!(config1.has_partial("b"));
+        assert_eq!(config1.changelog.as_deref(), Some("docs/CHANGES.md"));
+    }
+
+    #[rstest]
+    #[case(
+        "https://github.com/owner/repo",
+        "https://github.com/owner/repo/blob/main/docs/guide.md",
+        "https://raw.githubusercontent.com/owner/repo/main/assets/a.svg"
+    )]
+    #[case(
+        "https://gitlab.com/group/repo.git",
+        "https://gitlab.com/group/repo/-/blob/main/docs/guide.md",
+        "https://gitlab.com/group/repo/-/raw/main/assets/a.svg"
+    )]
+    #[case(
+        "https://codeberg.org/owner/repo/",
+        "https://codeberg.org/owner/repo/blob/main/docs/guide.md",
+        "https://codeberg.org/owner/repo/raw/main/assets/a.svg"
+    )]
+    fn test_repository_urls(#[case] url: &str, #[case] file: &str, #[case] raw: &str) {
+        let repo = RepositoryConfig::new(url);
+        assert_eq!(repo.file_url("./docs/guide.md"), file);
+        assert_eq!(repo.raw_url("/assets/a.svg"), raw);
+    }
+
+    #[test]
+    fn test_repository_from_json() {
+        let config: MdfxConfig =
+            serde_json::from_str(r#"{"repository": {"url": "https://github.com/o/r"}}"#).unwrap();
+        let repo = config.repository.unwrap();
+        assert_eq!(repo.branch, "main");
+        assert_eq!(repo.host(), Some("github.com"));
+    }
+
+    #[rstest]
+    #[case("github", Some(BackendType::Shields), None)]
+    #[case("pypi", Some(BackendType::PlainText), Some(false))]
+    #[case("crates-io", None, None)] // configured by alias
+    #[case("npm", None, None)]
+    fn test_target_overrides(
+        #[case] target: &str,
+        #[case] backend: Option<BackendType>,
+        #[case] post_process: Option<bool>,
+    ) {
+        let config: MdfxConfig = serde_json::from_str(
+            r#"{"targets": {
+                "github": {"backend": "shields", "palette": {"accent": "58A6FF"}},
+                "pypi": {"backend": "plaintext", "post_process": false, "wrap": 20},
+                "crates.io": {"assets_dir": "crate-assets"}
+            }}"#,
+        )
+        .unwrap();
+
+        let overrides = config.target_overrides(target);
+        assert_eq!(overrides.backend, backend);
+        assert_eq!(overrides.post_process, post_process);
+        assert_eq!(overrides.runs_post_process(), post_process.unwrap_or(true));
+        assert_eq!(
+            overrides.assets_dir.is_some(),
+            target == "crates-io",
+            "{target}"
+        );
+        assert_eq!(overrides.wrap.is_some(), target == "pypi", "{target}");
+    }
+
+    #[test]
+    fn test_target_overrides_wrap() {
+        let wrapped = TargetOverrides {
+            wrap: Some(12),
+            ..Default::default()
+        };
+        assert_eq!(
+            wrapped.apply_wrap("A paragraph of text\n".to_string()),
+            "A paragraph\nof text\n"
+        );
+        let text = "A paragraph of text\n".to_string();
+        assert_eq!(TargetOverrides::default().apply_wrap(text.clone()), text);
+    }
+
+    #[rstest]
+    #[case("spacing=1", Some(1), None)]
+    #[case("separator=dot", None, Some("dot"))]
+    #[case("  spacing=2 separator=→ ", Some(2), Some("→"))]
+    #[case("", None, None)]
+    fn test_style_defaults_parse(
+        #[case] spec: &str,
+        #[case] spacing: Option<usize>,
+        #[case] separator: Option<&str>,
+    ) {
+        let defaults = StyleDefaults::parse(spec).unwrap();
+        assert_eq!(defaults.spacing, spacing);
+        assert_eq!(defaults.separator.as_deref(), separator);
+    }
+
+    #[rstest]
+    #[case("spacing=x")]
+    #[case("gap=1")]
+    #[case("separator=")]
+    fn test_style_defaults_parse_invalid(#[case] spec: &str) {
+        assert!(StyleDefaults::parse(spec).is_err());
+    }
+
+    #[test]
+    fn test_front_matter_styles() {
+        let markdown = "---\ntitle: Notes\nmdfx-styles:\n  mathbold: spacing=1\n  script: separator=dot\ntags:\n  - a: b\n---\n# Body\n";
+        let styles = front_matter_styles(markdown).unwrap();
+        assert_eq!(styles.len(), 2);
+        assert_eq!(styles["mathbold"].spacing, Some(1));
+        assert_eq!(styles["script"].separator.as_deref(), Some("dot"));
+
+        // No front matter, or none at the start of the document
+        assert!(
+            front_matter_styles("# Title\n---\nmdfx-styles:\n  x: spacing=1\n---\n")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_styles_from_json() {
+        let config: MdfxConfig =
+            serde_json::from_str(r#"{"styles": {"mathbold": {"spacing": 1}}}"#).unwrap();
+        assert_eq!(config.styles["mathbold"].spacing, Some(1));
+        assert!(config.styles["mathbold"].separator.is_none());
+    }
+
+    #[test]
+    fn test_math_from_json() {
+        let config: MdfxConfig = serde_json::from_str(r#"{"math": true}"#).unwrap();
+        assert_eq!(config.math, Some(true));
+
+        let mut merged = config.clone();
+        merged.merge(MdfxConfig::new());
+        assert_eq!(merged.math, Some(true));
+    }
+
+    #[test]
+    fn test_commonmark_from_json() {
+        let mut config: MdfxConfig = serde_json::from_str(r#"{"commonmark": true}"#).unwrap();
+        assert_eq!(config.commonmark, Some(true));
+
+        config.merge(serde_json::from_str(r#"{"commonmark": false}"#).unwrap());
+        assert_eq!(config.commonmark, Some(false));
+    }
+
+    #[test]
+    fn test_icons_from_json() {
+        let mut config: MdfxConfig = serde_json::from_str(
+            r#"{"icons": {"acme": "<svg viewBox=\"0 0 16 16\"/>"}, "strict_icons": true}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.icons.get("acme").map(String::as_str),
+            Some("<svg viewBox=\"0 0 16 16\"/>")
+        );
+        assert_eq!(config.strict_icons, Some(true));
+
+        config.merge(serde_json::from_str(r#"{"icons": {"other": "<svg/>"}}"#).unwrap());
+        assert_eq!(config.icons.len(), 2);
+        assert_eq!(config.strict_icons, Some(true));
+    }
+
+    #[test]
+    fn test_pipelines_from_json() {
+        let config: MdfxConfig = serde_json::from_str(
+            r#"{"pipelines": {"readme": {
+                "input": "README.template.md",
+                "output": "README.md",
+                "stages": [{"process": "github"}, {"wrap": 100}, "link_check", {"write": "a.md"}]
+            }}}"#,
+        )
+        .unwrap();
+        let pipeline = &config.pipelines["readme"];
+        assert_eq!(pipeline.output.as_deref(), Some("README.md"));
+        assert_eq!(
+            pipeline.stages,
+            vec![
+                PipelineStage::Process("github".to_string()),
+                PipelineStage::Wrap(100),
+                PipelineStage::LinkCheck,
+                PipelineStage::Write("a.md".to_string()),
+            ]
+        );
+        assert_eq!(pipeline.stages[1].to_string(), "wrap 100");
+
+        let unknown = r#"{"pipelines": {"x": {"input": "a.md", "stages": ["upload"]}}}"#;
+        assert!(serde_json::from_str::<MdfxConfig>(unknown).is_err());
+    }
+
+    #[test]
+    fn test_plugins_from_json() {
+        let mut config: MdfxConfig = serde_json::from_str(
+            r#"{"plugins": {"callout": {"wasm": "plugins/callout.wasm", "description": "Boxed note"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.plugins["callout"].wasm, "plugins/callout.wasm");
+        assert_eq!(
+            config.plugins["callout"].description.as_deref(),
+            Some("Boxed note")
+        );
+
+        config
+            .merge(serde_json::from_str(r#"{"plugins": {"tag": {"wasm": "tag.wasm"}}}"#).unwrap());
+        assert_eq!(config.plugins.len(), 2);
+        assert_eq!(config.plugins["tag"].description, None);
+    }
+
+    #[test]
+    fn test_locale_from_json() {
+        let config: MdfxConfig = serde_json::from_str(r#"{"locale": "de-DE"}"#).unwrap();
+        assert_eq!(config.locale.as_deref(), Some("de-DE"));
+
+        let mut merged = config.clone();
+        merged.merge(MdfxConfig::new());
+        assert_eq!(merged.locale.as_deref(), Some("de-DE"));
+    }
+
+    #[test]
+    fn test_labels_from_json() {
+        let config: MdfxConfig =
+            serde_json::from_str(r#"{"lang": "de", "labels": {"stars": "★"}}"#).unwrap();
+        assert_eq!(config.lang.as_deref(), Some("de"));
+        assert_eq!(config.labels["stars"], "★");
+
+        let mut merged = config.clone();
+        merged.merge(MdfxConfig {
+            labels: HashMap::from([("forks".to_string(), "⑂".to_string())]),
+            ..MdfxConfig::new()
+        });
+        assert_eq!(merged.lang.as_deref(), Some("de"));
+        assert_eq!(merged.labels.len(), 2);
     }
 
     #[test]
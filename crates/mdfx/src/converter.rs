@@ -1,42 +1,219 @@
 use crate::error::{Error, Result};
-use crate::styles::{Style, StylesData};
+use crate::styles::{RandomMarks, Style, StylesData};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
 
 lazy_static! {
     static ref STYLES: StylesData = StylesData::load().expect("Failed to load styles.json");
+
+    /// Map from alias -> style ID, built once alongside STYLES
+    static ref ALIASES: HashMap<String, String> = STYLES
+        .styles
+        .iter()
+        .flat_map(|(id, style)| {
+            style
+                .aliases
+                .iter()
+                .map(move |alias| (alias.clone(), id.clone()))
+        })
+        .collect();
+
+    /// Map from style ID -> precomputed ASCII conversions
+    static ref ASCII_TABLES: HashMap<String, AsciiTable> = STYLES
+        .styles
+        .iter()
+        .map(|(id, style)| (id.clone(), AsciiTable::new(style)))
+        .collect();
+}
+
+/// A style's conversions for the 128 ASCII characters
+///
+/// Converting ASCII text indexes this table by byte instead of hashing
+/// each character into the style's mapping.
+struct AsciiTable {
+    chars: [char; 128],
+    suffix: Option<&'static str>,
+    /// Every ASCII letter and digit has a styled equivalent
+    complete: bool,
+}
+
+impl AsciiTable {
+    fn new(style: &'static Style) -> Self {
+        let mut chars = ['\0'; 128];
+        for (byte, slot) in (0u8..128).zip(chars.iter_mut()) {
+            *slot = style.convert_char(char::from(byte));
+        }
+        Self {
+            chars,
+            suffix: style.suffix.as_deref(),
+            complete: !(0u8..128).any(|byte| style.is_unmapped(char::from(byte))),
+        }
+    }
+
+    /// Convert ASCII-only `text`
+    fn convert(&self, text: &str, out: &mut String) {
+        for byte in text.bytes() {
+            out.push(self.chars[usize::from(byte)]);
+            if let Some(suffix) = self.suffix {
+                if !char::from(byte).is_whitespace() {
+                    out.push_str(suffix);
+                }
+            }
+        }
+    }
+}
+
+/// SplitMix64 generator for styles with random marks
+///
+/// Written out rather than taken from a crate so the marks for a given seed
+/// never change between mdfx versions.
+struct MarkRng(u64);
+
+impl MarkRng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Append between 1 and `marks.max` marks from the pool
+    fn push_marks(&mut self, marks: &RandomMarks, out: &mut String) {
+        let pool: Vec<char> = marks.pool.chars().collect();
+        if pool.is_empty() || marks.max == 0 {
+            return;
+        }
+        let count = 1 + self.next() as usize % marks.max;
+        for _ in 0..count {
+            out.push(pool[self.next() as usize % pool.len()]);
+        }
+    }
+}
+
+/// What to do with a letter or digit that a style has no equivalent for
+///
+/// Whitespace, punctuation, and symbols are never styled and always pass
+/// through; see [`Style::is_unmapped`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Fallback {
+    /// Keep the character unstyled
+    #[default]
+    Passthrough,
+    /// Drop the character
+    Skip,
+    /// Fail with [`Error::UnmappedChar`]
+    Error,
+    /// Use the styled form of the character without accents, or in the
+    /// other case, and keep it unstyled if there is neither
+    Closest,
+}
+
+impl Fallback {
+    /// Parse a policy name (passthrough, skip, error, closest)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "passthrough" => Some(Fallback::Passthrough),
+            "skip" => Some(Fallback::Skip),
+            "error" => Some(Fallback::Error),
+            "closest" => Some(Fallback::Closest),
+            _ => None,
+        }
+    }
+
+    /// Policy name, as accepted by [`parse`](Self::parse)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Fallback::Passthrough => "passthrough",
+            Fallback::Skip => "skip",
+            Fallback::Error => "error",
+            Fallback::Closest => "closest",
+        }
+    }
 }
 
 /// Main converter for Unicode text styling
+///
+/// Style data is parsed once per process and shared by all converters, so
+/// creating one is cheap.
+#[derive(Clone)]
 pub struct Converter {
-    styles: HashMap<String, Style>,
+    styles: &'static HashMap<String, Style>,
     /// Map from alias -> style ID for O(1) lookup
-    alias_map: HashMap<String, String>,
+    alias_map: &'static HashMap<String, String>,
+    /// Policy for characters a style has no equivalent for
+    fallback: Fallback,
+    /// Seed for styles with random marks
+    seed: u64,
 }
 
 impl Converter {
     /// Create a new converter with all available styles loaded
     pub fn new() -> Result<Self> {
-        let styles_data = &*STYLES;
-
-        // Build alias map for O(1) alias lookup
-        let alias_map: HashMap<String, String> = styles_data
-            .styles
-            .iter()
-            .flat_map(|(id, style)| {
-                style
-                    .aliases
-                    .iter()
-                    .map(move |alias| (alias.clone(), id.clone()))
-            })
-            .collect();
-
         Ok(Self {
-            styles: styles_data.styles.clone(),
-            alias_map,
+            styles: &STYLES.styles,
+            alias_map: &ALIASES,
+            fallback: Fallback::default(),
+            seed: 0,
         })
     }
 
+    /// Set the seed for styles with random marks, such as `zalgo`
+    ///
+    /// Conversions are deterministic: the same text, style, and seed always
+    /// give the same marks. The default seed is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdfx::Converter;
+    ///
+    /// let converter = Converter::new().unwrap().with_seed(7);
+    /// let once = converter.convert("glitch", "zalgo").unwrap();
+    /// assert_eq!(once, converter.convert("glitch", "zalgo").unwrap());
+    /// assert!(once.starts_with('g'));
+    /// ```
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the policy for letters and digits a style has no equivalent for
+    ///
+    /// Applies to every conversion method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdfx::{Converter, Fallback};
+    ///
+    /// let converter = Converter::new().unwrap().with_fallback(Fallback::Closest);
+    /// assert_eq!(converter.convert("Café", "mathbold").unwrap(), "𝐂𝐚𝐟𝐞\u{301}");
+    /// ```
+    pub fn with_fallback(mut self, fallback: Fallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Letters and digits in `text` that `style` has no equivalent for,
+    /// in order of first appearance
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UnknownStyle` if the style doesn't exist.
+    pub fn unmapped_chars(&self, text: &str, style: &str) -> Result<Vec<char>> {
+        let style_obj = self.get_style(style)?;
+        let mut unmapped = Vec::new();
+        for c in text.chars().filter(|&c| style_obj.is_unmapped(c)) {
+            if !unmapped.contains(&c) {
+                unmapped.push(c);
+            }
+        }
+        Ok(unmapped)
+    }
+
     /// Internal unified method for converting text with optional character separation
     ///
     /// This method handles all conversion cases: no spacing, space-based spacing,
@@ -59,28 +236,89 @@ impl Converter {
 
         // Fast path: no separation needed
         if count == 0 || separator.is_empty() {
-            let result: String = text
-                .chars()
-                .map(|c| style_obj.convert_char_to_string(c))
-                .collect();
-            return Ok(result);
+            return self.convert_styled(style_obj, text);
         }
 
-        // With separation: convert each char and add separator between
-        let mut result = String::new();
-        let mut chars = text.chars().peekable();
+        // With separation: convert each grapheme and add separator between,
+        // so emoji sequences and combining marks are never split apart.
+        // Graphemes dropped by `Fallback::Skip` don't get a separator.
+        let mut rng = MarkRng(self.seed);
+        let mut converted = Vec::new();
+        for grapheme in text.graphemes(true) {
+            let mut styled = String::new();
+            for c in grapheme.chars() {
+                self.push_with_fallback(style_obj, c, &mut styled, &mut rng)?;
+            }
+            if !styled.is_empty() {
+                converted.push(styled);
+            }
+        }
+        if style_obj.reverse {
+            converted.reverse();
+        }
 
-        while let Some(c) = chars.next() {
-            result.push_str(&style_obj.convert_char_to_string(c));
+        Ok(converted.join(&separator.repeat(count)))
+    }
 
-            // Add separator after each character except the last
-            if chars.peek().is_some() {
-                for _ in 0..count {
-                    result.push_str(separator);
-                }
+    /// Append `c` converted to `style`, applying the fallback policy if the
+    /// style has no equivalent for it
+    fn push_with_fallback(
+        &self,
+        style: &Style,
+        c: char,
+        out: &mut String,
+        rng: &mut MarkRng,
+    ) -> Result<()> {
+        if !style.is_unmapped(c) {
+            style.push_converted(c, out);
+            if let Some(marks) = style.random_marks.as_ref().filter(|_| !c.is_whitespace()) {
+                rng.push_marks(marks, out);
+            }
+            return Ok(());
+        }
+        match self.fallback {
+            Fallback::Passthrough => out.push(c),
+            Fallback::Skip => {}
+            Fallback::Error => {
+                return Err(Error::UnmappedChar {
+                    ch: c,
+                    style: style.id.clone(),
+                })
             }
+            Fallback::Closest => match closest(style, c) {
+                Some(closest) => out.push_str(&closest),
+                None => out.push(c),
+            },
         }
+        Ok(())
+    }
 
+    /// Convert text with an already resolved style
+    ///
+    /// ASCII-only text, the common case, goes through the style's
+    /// precomputed [`AsciiTable`] unless the fallback policy or random marks
+    /// have work to do; anything else uses the mapping. Reversing styles
+    /// reverse the result by grapheme, so combining marks stay attached.
+    fn convert_styled(&self, style: &Style, text: &str) -> Result<String> {
+        let mut result = String::with_capacity(text.len() * 4);
+        match ASCII_TABLES.get(&style.id) {
+            Some(table)
+                if text.is_ascii()
+                    && style.random_marks.is_none()
+                    && (table.complete || self.fallback == Fallback::Passthrough) =>
+            {
+                table.convert(text, &mut result)
+            }
+            _ => {
+                let mut rng = MarkRng(self.seed);
+                for c in text.chars() {
+                    self.push_with_fallback(style, c, &mut result, &mut rng)?;
+                }
+            }
+        }
+        if style.reverse {
+            result = result.graphemes(true).rev().collect();
+        }
         Ok(result)
     }
 
@@ -108,6 +346,32 @@ impl Converter {
         self.convert_with_char_between(text, style, "", 0)
     }
 
+    /// Convert many strings to the same Unicode style
+    ///
+    /// Equivalent to calling [`convert`](Self::convert) on each string, but
+    /// the style is resolved once for the whole batch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdfx::Converter;
+    ///
+    /// let converter = Converter::new().unwrap();
+    /// let result = converter.convert_many(&["GO", "RUST"], "mathbold").unwrap();
+    /// assert_eq!(result, vec!["𝐆𝐎", "𝐑𝐔𝐒𝐓"]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UnknownStyle` if the style doesn't exist.
+    pub fn convert_many(&self, texts: &[&str], style: &str) -> Result<Vec<String>> {
+        let style_obj = self.get_style(style)?;
+        texts
+            .iter()
+            .map(|text| self.convert_styled(style_obj, text))
+            .collect()
+    }
+
     /// Convert text to a specified Unicode style with character spacing
     ///
     /// # Arguments
@@ -202,6 +466,34 @@ impl Converter {
     }
 }
 
+/// Styled form of `c` without accents, or in the other case
+///
+/// Accents are kept as combining marks after the styled base letter.
+fn closest(style: &Style, c: char) -> Option<String> {
+    let mut decomposed = std::iter::once(c).nfd();
+    let base = decomposed.next()?;
+    let alternatives = [
+        Some(base),
+        single(base.to_lowercase()),
+        single(base.to_uppercase()),
+    ];
+    let styled = alternatives
+        .into_iter()
+        .flatten()
+        .find(|&alt| style.supports_char(alt))?;
+
+    let mut out = String::new();
+    style.push_converted(styled, &mut out);
+    out.extend(decomposed);
+    Some(out)
+}
+
+/// The only character of a case mapping, if it maps to exactly one
+fn single(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c)
+}
+
 impl Default for Converter {
     fn default() -> Self {
         Self::new().expect("Failed to create default converter")
@@ -227,7 +519,7 @@ mod tests {
     fn test_list_styles() {
         let converter = Converter::new().unwrap();
         let styles = converter.list_styles();
-        assert_eq!(styles.len(), 24);
+        assert_eq!(styles.len(), 29);
     }
 
     #[test]
@@ -256,6 +548,9 @@ mod tests {
     #[case("TEST", "mb", "𝐓𝐄𝐒𝐓")] // alias test
     #[case("A B  C", "mathbold", "𝐀 𝐁  𝐂")] // preserves whitespace
     #[case("Hello, World!", "mathbold", "𝐇𝐞𝐥𝐥𝐨, 𝐖𝐨𝐫𝐥𝐝!")] // preserves punctuation
+    #[case("Hello, World!", "upside-down", "¡plɹoM 'ollǝH")] // reversed
+    #[case("bad (dog)", "mirror", "(ǫob) bɒd")]
+    #[case("Ae\u{301}", "ud", "ǝ\u{301}∀")] // combining mark stays attached
     fn test_convert(#[case] input: &str, #[case] style: &str, #[case] expected: &str) {
         let converter = Converter::new().unwrap();
         let result = converter.convert(input, style).unwrap();
@@ -273,12 +568,154 @@ mod tests {
         "strikethrough",
         "H\u{0336}i\u{0336} t\u{0336}h\u{0336}e\u{0336}r\u{0336}e\u{0336}"
     )]
+    #[case("Go on", "underline", "G\u{0332}o\u{0332} o\u{0332}n\u{0332}")]
+    #[case("v2", "overline", "v\u{0305}2\u{0305}")]
     fn test_strikethrough(#[case] input: &str, #[case] style: &str, #[case] expected: &str) {
         let converter = Converter::new().unwrap();
         let result = converter.convert(input, style).unwrap();
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_zalgo_is_capped_and_seeded() {
+        let converter = Converter::new().unwrap();
+        let zalgo = converter.get_style("zalgo").unwrap();
+        let marks = zalgo.random_marks.as_ref().unwrap();
+
+        let result = converter.convert("Hello world", "zalgo").unwrap();
+        let mut base = String::new();
+        let mut runs = Vec::new();
+        for c in result.chars() {
+            if marks.pool.contains(c) {
+                *runs.last_mut().unwrap() += 1;
+            } else {
+                base.push(c);
+                runs.push(0);
+            }
+        }
+        assert_eq!(base, "Hello world");
+        for (c, run) in base.chars().zip(runs) {
+            let expected = if c == ' ' { 0..=0 } else { 1..=marks.max };
+            assert!(expected.contains(&run), "{} marks on '{}'", run, c);
+        }
+
+        assert_eq!(result, converter.convert("Hello world", "zalgo").unwrap());
+        let reseeded = converter.clone().with_seed(42);
+        assert_ne!(result, reseeded.convert("Hello world", "zalgo").unwrap());
+        assert_eq!(
+            reseeded.convert_many(&["ab", "ab"], "zalgo").unwrap()[1],
+            reseeded.convert("ab", "zalgo").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_convert_many() {
+        let converter = Converter::new().unwrap();
+        let texts = ["Hello", "", "café 42", "a\tb"];
+        for style in converter.list_ids() {
+            let batch = converter.convert_many(&texts, &style).unwrap();
+            let single: Vec<_> = texts
+                .iter()
+                .map(|text| converter.convert(text, &style).unwrap())
+                .collect();
+            assert_eq!(batch, single, "style {}", style);
+        }
+        assert!(matches!(
+            converter.convert_many(&texts, "fakestyle"),
+            Err(Error::UnknownStyle(_))
+        ));
+    }
+
+    #[test]
+    fn test_ascii_table_matches_mappings() {
+        let converter = Converter::new().unwrap();
+        let ascii: String = (0u8..128).map(char::from).collect();
+        for style in converter.list_styles() {
+            if style.random_marks.is_some() {
+                continue;
+            }
+            let mut expected: String = ascii
+                .chars()
+                .map(|c| style.convert_char_to_string(c))
+                .collect();
+            if style.reverse {
+                expected = expected.graphemes(true).rev().collect();
+            }
+            assert_eq!(
+                converter.convert_styled(style, &ascii).unwrap(),
+                expected,
+                "style {}",
+                style.id
+            );
+        }
+    }
+
+    // ========================================================================
+    // Fallback Tests (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("Café 1!", "mathbold", Fallback::Passthrough, "𝐂𝐚𝐟é 𝟏!")]
+    #[case("Café 1!", "mathbold", Fallback::Skip, "𝐂𝐚𝐟 𝟏!")]
+    #[case("Café 1!", "mathbold", Fallback::Closest, "𝐂𝐚𝐟𝐞\u{301} 𝟏!")]
+    #[case("Go 2", "negative-squared", Fallback::Skip, "🅶 ")]
+    #[case("Go 2", "negative-squared", Fallback::Closest, "🅶🅾 2")]
+    #[case("Go", "small-caps", Fallback::Closest, "ɢᴏ")]
+    #[case("Go", "strikethrough", Fallback::Error, "G\u{0336}o\u{0336}")]
+    fn test_fallback(
+        #[case] input: &str,
+        #[case] style: &str,
+        #[case] fallback: Fallback,
+        #[case] expected: &str,
+    ) {
+        let converter = Converter::new().unwrap().with_fallback(fallback);
+        assert_eq!(converter.convert(input, style).unwrap(), expected);
+        assert_eq!(converter.convert_many(&[input], style).unwrap(), [expected]);
+    }
+
+    #[test]
+    fn test_fallback_error() {
+        let converter = Converter::new().unwrap().with_fallback(Fallback::Error);
+        assert_eq!(converter.convert("Hi 1", "mathbold").unwrap(), "𝐇𝐢 𝟏");
+        let Err(Error::UnmappedChar { ch, style }) = converter.convert("Hi 1", "italic") else {
+            unreachable!("Expected UnmappedChar error");
+        };
+        assert_eq!((ch, style.as_str()), ('1', "italic"));
+    }
+
+    #[test]
+    fn test_fallback_skip_drops_separator() {
+        let converter = Converter::new().unwrap().with_fallback(Fallback::Skip);
+        let result = converter
+            .convert_with_separator("AéB", "mathbold", "·", 1)
+            .unwrap();
+        assert_eq!(result, "𝐀·𝐁");
+    }
+
+    #[test]
+    fn test_unmapped_chars() {
+        let converter = Converter::new().unwrap();
+        let unmapped = converter
+            .unmapped_chars("Ärger 2024, ok?", "italic")
+            .unwrap();
+        assert_eq!(unmapped, ['Ä', '2', '0', '4']);
+        assert!(converter
+            .unmapped_chars("Strike 1", "strikethrough")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[rstest]
+    #[case("passthrough", Some(Fallback::Passthrough))]
+    #[case("closest", Some(Fallback::Closest))]
+    #[case("nearest", None)]
+    fn test_fallback_parse(#[case] name: &str, #[case] expected: Option<Fallback>) {
+        assert_eq!(Fallback::parse(name), expected);
+        if let Some(fallback) = expected {
+            assert_eq!(fallback.as_str(), name);
+        }
+    }
+
     // ========================================================================
     // Style Existence Tests
     // ========================================================================
@@ -340,6 +777,9 @@ mod tests {
     #[case("ABC", "mathbold", "→", 1, "𝐀→𝐁→𝐂")]
     #[case("AB", "mathbold", "·", 3, "𝐀···𝐁")] // multiple count
     #[case("X", "mathbold", "·", 1, "𝐗")] // single char - no separator
+    #[case("A👩‍💻B", "mathbold", "·", 1, "𝐀·👩‍💻·𝐁")] // ZWJ sequence kept whole
+    #[case("e\u{301}x", "mathbold", "·", 1, "𝐞\u{301}·𝐱")] // combining mark stays attached
+    #[case("ab", "upside-down", "·", 1, "q·ɐ")] // reversed after separating
     fn test_separator(
         #[case] input: &str,
         #[case] style: &str,
@@ -0,0 +1,158 @@
+//! Coverage report parsing
+//!
+//! Reads the line coverage percentage from a local report for
+//! `{{ui:coverage:file=.../}}`. The format is detected from the content:
+//!
+//! - **lcov** (`lcov.info`, from grcov, cargo-llvm-cov, c8, coverage.py): `LH`/`LF` totals
+//! - **Cobertura XML** (`cobertura.xml`): the `line-rate` of the root element
+//! - **tarpaulin JSON** (`tarpaulin-report.json`): `covered`/`coverable` counts
+//!
+//! ```
+//! use mdfx::coverage::{coverage_color, line_coverage};
+//!
+//! let lcov = "SF:src/lib.rs\nLF:40\nLH:34\nend_of_record\n";
+//! assert_eq!(line_coverage(lcov), Some(85.0));
+//! assert_eq!(coverage_color(85.0), "84CC16");
+//! ```
+
+/// Line coverage in percent (0-100), or `None` if the report isn't recognized
+pub fn line_coverage(report: &str) -> Option<f64> {
+    let trimmed = report.trim_start();
+    if trimmed.starts_with('{') {
+        tarpaulin(trimmed)
+    } else if trimmed.starts_with('<') {
+        cobertura(trimmed)
+    } else {
+        lcov(trimmed)
+    }
+}
+
+/// Badge color for a coverage percentage
+///
+/// Same scale as the Codecov live badge: green from 90%, lime from 80%,
+/// yellow from 70%, orange from 50%, red below.
+pub fn coverage_color(percent: f64) -> &'static str {
+    match percent {
+        p if p >= 90.0 => "22C55E",
+        p if p >= 80.0 => "84CC16",
+        p if p >= 70.0 => "EAB308",
+        p if p >= 50.0 => "F97316",
+        _ => "EF4444",
+    }
+}
+
+/// Sum of `LH` (lines hit) over `LF` (lines found) across all records
+fn lcov(report: &str) -> Option<f64> {
+    let (mut found, mut hit) = (0u64, 0u64);
+    for line in report.lines().map(str::trim) {
+        if let Some(count) = line.strip_prefix("LF:") {
+            found += count.trim().parse::<u64>().ok()?;
+        } else if let Some(count) = line.strip_prefix("LH:") {
+            hit += count.trim().parse::<u64>().ok()?;
+        }
+    }
+    percent(hit, found)
+}
+
+/// `line-rate` (0-1) on the root `<coverage>` element
+fn cobertura(report: &str) -> Option<f64> {
+    let start = report.find("<coverage")?;
+    let tag = &report[start..];
+    let tag = &tag[..tag.find('>')?];
+    let (_, rest) = tag.split_once(" line-rate=")?;
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let (rate, _) = rest[1..].split_once(quote)?;
+    let rate: f64 = rate.trim().parse().ok()?;
+    (0.0..=1.0).contains(&rate).then_some(rate * 100.0)
+}
+
+/// Top-level `covered`/`coverable`, else the per-file counts, else `coverage`
+fn tarpaulin(report: &str) -> Option<f64> {
+    let json: serde_json::Value = serde_json::from_str(report).ok()?;
+    let count = |value: &serde_json::Value, key: &str| value.get(key).and_then(|v| v.as_u64());
+
+    if let (Some(covered), Some(coverable)) = (count(&json, "covered"), count(&json, "coverable")) {
+        return percent(covered, coverable);
+    }
+    if let Some(files) = json.get("files").and_then(|f| f.as_array()) {
+        let (covered, coverable) = files.iter().fold((0, 0), |(covered, coverable), file| {
+            (
+                covered + count(file, "covered").unwrap_or(0),
+                coverable + count(file, "coverable").unwrap_or(0),
+            )
+        });
+        return percent(covered, coverable);
+    }
+    json.get("coverage")
+        .and_then(|c| c.as_f64())
+        .filter(|c| (0.0..=100.0).contains(c))
+}
+
+fn percent(covered: u64, total: u64) -> Option<f64> {
+    (total > 0 && covered <= total).then(|| covered as f64 * 100.0 / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const LCOV: &str = "TN:
+SF:src/lib.rs
+DA:1,1
+DA:2,0
+LF:30
+LH:24
+end_of_record
+SF:src/parser.rs
+LF:10
+LH:10
+end_of_record
+";
+
+    const COBERTURA: &str = r#"<?xml version="1.0" ?>
+<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">
+<coverage branch-rate="0.5" line-rate="0.8125" lines-covered="13" lines-valid="16" version="7.4">
+    <packages/>
+</coverage>
+"#;
+
+    #[rstest]
+    #[case(LCOV, Some(85.0))]
+    #[case(COBERTURA, Some(81.25))]
+    #[case(
+        r#"{"files": [], "coverage": 71.5, "covered": 143, "coverable": 200}"#,
+        Some(71.5)
+    )]
+    #[case(
+        r#"{"files": [{"path": ["src", "lib.rs"], "covered": 3, "coverable": 4}, {"covered": 1, "coverable": 4}]}"#,
+        Some(50.0)
+    )]
+    #[case(r#"{"coverage": 64.2}"#, Some(64.2))]
+    fn test_line_coverage(#[case] report: &str, #[case] expected: Option<f64>) {
+        assert_eq!(line_coverage(report), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("SF:src/lib.rs\nLF:0\nLH:0\nend_of_record\n")]
+    #[case("LF:ten\nLH:5\n")]
+    #[case("<coverage lines-valid=\"10\"></coverage>")]
+    #[case("<coverage line-rate=\"1.5\"></coverage>")]
+    #[case(r#"{"name": "not a report"}"#)]
+    #[case("# README\n\nNothing to see")]
+    fn test_line_coverage_unrecognized(#[case] report: &str) {
+        assert_eq!(line_coverage(report), None);
+    }
+
+    #[rstest]
+    #[case(100.0, "22C55E")]
+    #[case(90.0, "22C55E")]
+    #[case(85.0, "84CC16")]
+    #[case(70.0, "EAB308")]
+    #[case(50.0, "F97316")]
+    #[case(49.9, "EF4444")]
+    fn test_coverage_color(#[case] percent: f64, #[case] expected: &str) {
+        assert_eq!(coverage_color(percent), expected);
+    }
+}
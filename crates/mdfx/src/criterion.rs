@@ -0,0 +1,152 @@
+//! Criterion benchmark results
+//!
+//! Reads the `estimates.json` files that [criterion](https://docs.rs/criterion)
+//! writes under `target/criterion/<benchmark id>/`, for `{{ui:bench:.../}}`.
+//! `new/` holds the latest run and `base/` (or a `--save-baseline` name) the
+//! one it is compared against. Times are in nanoseconds.
+//!
+//! ```
+//! use mdfx::criterion::{point_estimate, TimeUnit};
+//!
+//! let estimates = r#"{"mean": {"point_estimate": 1234567.0}, "median": {"point_estimate": 1200000.0}}"#;
+//! let mean = point_estimate(estimates, "mean").unwrap();
+//!
+//! assert_eq!(TimeUnit::auto(mean).format(mean, 2), "1.23 ms");
+//! ```
+
+/// Default criterion output directory, relative to the project root
+pub const DEFAULT_DIR: &str = "target/criterion";
+
+/// Path of a run's `estimates.json`: `<dir>/<id>/<run>/estimates.json`
+pub fn estimates_path(dir: &str, id: &str, run: &str) -> String {
+    format!(
+        "{}/{}/{}/estimates.json",
+        dir.trim_end_matches('/'),
+        id.trim_matches('/'),
+        run
+    )
+}
+
+/// A statistic (`mean`, `median`, `slope`, ...) from `estimates.json`
+///
+/// `None` if the file isn't valid JSON or doesn't have that statistic;
+/// criterion writes `"slope": null` when it didn't use linear sampling.
+pub fn point_estimate(estimates_json: &str, stat: &str) -> Option<f64> {
+    let estimates: serde_json::Value = serde_json::from_str(estimates_json).ok()?;
+    estimates
+        .get(stat)?
+        .get("point_estimate")?
+        .as_f64()
+        .filter(|ns| ns.is_finite() && *ns >= 0.0)
+}
+
+/// Unit for displaying a duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    Nanos,
+    Micros,
+    Millis,
+    Seconds,
+}
+
+impl TimeUnit {
+    /// Parse `ns`, `us` (or `µs`), `ms` or `s`
+    pub fn parse(unit: &str) -> Option<Self> {
+        match unit {
+            "ns" => Some(TimeUnit::Nanos),
+            "us" | "µs" => Some(TimeUnit::Micros),
+            "ms" => Some(TimeUnit::Millis),
+            "s" => Some(TimeUnit::Seconds),
+            _ => None,
+        }
+    }
+
+    /// The largest unit in which `ns` is at least 1
+    pub fn auto(ns: f64) -> Self {
+        match ns {
+            ns if ns >= 1e9 => TimeUnit::Seconds,
+            ns if ns >= 1e6 => TimeUnit::Millis,
+            ns if ns >= 1e3 => TimeUnit::Micros,
+            _ => TimeUnit::Nanos,
+        }
+    }
+
+    /// Nanoseconds per unit
+    pub fn nanos(self) -> f64 {
+        match self {
+            TimeUnit::Nanos => 1.0,
+            TimeUnit::Micros => 1e3,
+            TimeUnit::Millis => 1e6,
+            TimeUnit::Seconds => 1e9,
+        }
+    }
+
+    /// Unit suffix
+    pub fn suffix(self) -> &'static str {
+        match self {
+            TimeUnit::Nanos => "ns",
+            TimeUnit::Micros => "µs",
+            TimeUnit::Millis => "ms",
+            TimeUnit::Seconds => "s",
+        }
+    }
+
+    /// `ns` in this unit with `precision` decimals, e.g. `1.23 ms`
+    pub fn format(self, ns: f64, precision: usize) -> String {
+        format!("{:.*} {}", precision, ns / self.nanos(), self.suffix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const ESTIMATES: &str = r#"{
+        "mean": {"confidence_interval": {"confidence_level": 0.95, "lower_bound": 1200.0, "upper_bound": 1300.0}, "point_estimate": 1250.5, "standard_error": 20.0},
+        "median": {"confidence_interval": {"confidence_level": 0.95, "lower_bound": 1190.0, "upper_bound": 1260.0}, "point_estimate": 1220.0, "standard_error": 15.0},
+        "slope": null
+    }"#;
+
+    #[rstest]
+    #[case("mean", Some(1250.5))]
+    #[case("median", Some(1220.0))]
+    #[case("slope", None)]
+    #[case("mode", None)]
+    fn test_point_estimate(#[case] stat: &str, #[case] expected: Option<f64>) {
+        assert_eq!(point_estimate(ESTIMATES, stat), expected);
+    }
+
+    #[test]
+    fn test_point_estimate_invalid_json() {
+        assert_eq!(point_estimate("not json", "mean"), None);
+    }
+
+    #[rstest]
+    #[case(845.0, "845.00 ns")]
+    #[case(1250.5, "1.25 µs")]
+    #[case(12_345_678.0, "12.35 ms")]
+    #[case(2.5e9, "2.50 s")]
+    fn test_auto_unit(#[case] ns: f64, #[case] expected: &str) {
+        assert_eq!(TimeUnit::auto(ns).format(ns, 2), expected);
+    }
+
+    #[rstest]
+    #[case("ns", Some(TimeUnit::Nanos))]
+    #[case("us", Some(TimeUnit::Micros))]
+    #[case("µs", Some(TimeUnit::Micros))]
+    #[case("ms", Some(TimeUnit::Millis))]
+    #[case("s", Some(TimeUnit::Seconds))]
+    #[case("min", None)]
+    fn test_parse_unit(#[case] unit: &str, #[case] expected: Option<TimeUnit>) {
+        assert_eq!(TimeUnit::parse(unit), expected);
+    }
+
+    #[test]
+    fn test_estimates_path() {
+        assert_eq!(
+            estimates_path("target/criterion/", "parser/process_large", "new"),
+            "target/criterion/parser/process_large/new/estimates.json"
+        );
+    }
+}
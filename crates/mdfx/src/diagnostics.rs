@@ -0,0 +1,220 @@
+//! Diagnostics collected while processing in recovery mode
+//!
+//! [`TemplateParser::process_with_diagnostics`](crate::TemplateParser::process_with_diagnostics)
+//! keeps going after a template fails: the failing template is left in the
+//! output as written and a [`Diagnostic`] records what went wrong, so one run
+//! reports every problem in a document.
+
+use crate::renderer::RenderedAsset;
+use serde::Serialize;
+use std::fmt;
+use std::ops::Range;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The template could not be rendered
+    Error,
+    /// The template was left as text but may be a mistake
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in the input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// Byte range of the offending template (or its opening tag)
+    pub range: Range<usize>,
+    /// 1-based line of `range.start`
+    pub line: usize,
+    /// 1-based column (in characters) of `range.start`
+    pub column: usize,
+}
+
+impl Diagnostic {
+    pub(crate) fn error(message: impl Into<String>, range: Range<usize>) -> Self {
+        Self::new(Severity::Error, message, range)
+    }
+
+    pub(crate) fn warning(message: impl Into<String>, range: Range<usize>) -> Self {
+        Self::new(Severity::Warning, message, range)
+    }
+
+    fn new(severity: Severity, message: impl Into<String>, range: Range<usize>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            range,
+            line: 0,
+            column: 0,
+        }
+    }
+
+    /// Fill in line and column from the source text
+    pub(crate) fn locate(&mut self, source: &str) {
+        (self.line, self.column) = line_column(source, self.range.start);
+    }
+}
+
+/// 1-based line and column (in characters) of byte `offset` in `source`
+pub(crate) fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let mut start = offset.min(source.len());
+    while !source.is_char_boundary(start) {
+        start -= 1;
+    }
+    let before = &source[..start];
+    let line_start = before.rfind('\n').map(|p| p + 1).unwrap_or(0);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.line, self.column, self.severity, self.message
+        )
+    }
+}
+
+/// Partially processed output with every diagnostic found
+#[derive(Debug, Clone)]
+pub struct RecoveredMarkdown {
+    /// Processed markdown; failed templates are kept as written
+    pub markdown: String,
+    /// File-based assets from templates that rendered
+    pub assets: Vec<RenderedAsset>,
+    /// Problems in source order
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl RecoveredMarkdown {
+    /// Whether any diagnostic is an error
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TemplateParser;
+
+    fn check(input: &str) -> RecoveredMarkdown {
+        TemplateParser::new()
+            .unwrap()
+            .process_with_diagnostics(input)
+    }
+
+    #[test]
+    fn test_collects_multiple_errors() {
+        let result = check("{{nosuch}}A{{/nosuch}}\n{{mathbold}}B{{/mathbold}}\n{{glyph:nope/}}");
+
+        assert_eq!(result.diagnostics.len(), 2);
+        assert_eq!(
+            (result.diagnostics[0].line, result.diagnostics[0].column),
+            (1, 1)
+        );
+        assert_eq!(
+            (result.diagnostics[1].line, result.diagnostics[1].column),
+            (3, 1)
+        );
+        assert!(result.diagnostics[1].message.contains("nope"));
+        assert_eq!(
+            result.markdown,
+            "{{nosuch}}A{{/nosuch}}\n𝐁\n{{glyph:nope/}}"
+        );
+    }
+
+    #[test]
+    fn test_nested_error_attributed_to_child() {
+        let input = "{{frame:star}}x {{nosuch}}A{{/nosuch}}{{/frame}}";
+        let result = check(input);
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(
+            &input[result.diagnostics[0].range.clone()],
+            "{{nosuch}}A{{/nosuch}}"
+        );
+        // The frame still renders around the failed child
+        let clean = TemplateParser::new()
+            .unwrap()
+            .process("{{frame:star}}x MARK{{/frame}}")
+            .unwrap();
+        assert_eq!(
+            result.markdown,
+            clean.replace("MARK", "{{nosuch}}A{{/nosuch}}")
+        );
+    }
+
+    #[test]
+    fn test_unclosed_tag_reports_opening_tag() {
+        let input = "ok {{mathbold}}A\n{{italic}}b{{/italic}}";
+        let result = check(input);
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(&input[result.diagnostics[0].range.clone()], "{{mathbold}}");
+        assert!(result.markdown.ends_with("𝑏"));
+    }
+
+    #[test]
+    fn test_unknown_partial_is_warning() {
+        let result = check("{{partial:missing}}x{{/partial}}");
+
+        assert!(!result.has_errors());
+        assert_eq!(result.diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_bad_params_are_warnings() {
+        let input = "x {{ui:progress:50:width=abc:colour=red/}}";
+        let result = check(input);
+
+        assert!(!result.has_errors());
+        assert_eq!(result.diagnostics.len(), 2);
+        assert!(result
+            .diagnostics
+            .iter()
+            .all(|d| d.severity == Severity::Warning
+                && &input[d.range.clone()] == "{{ui:progress:50:width=abc:colour=red/}}"));
+        assert!(result.diagnostics[0].message.contains("'width'"));
+        assert!(result.diagnostics[1].message.contains("'colour'"));
+        // The component still renders with defaults
+        assert!(!result.markdown.contains("{{ui:"));
+    }
+
+    #[test]
+    fn test_clean_input_matches_process() {
+        let input = "# {{mathbold}}T{{/mathbold}}\n\n```\n{{x}}\n```\n";
+        let result = check(input);
+
+        assert!(result.diagnostics.is_empty());
+        assert_eq!(
+            result.markdown,
+            TemplateParser::new().unwrap().process(input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_locate_counts_characters() {
+        let mut diag = Diagnostic::error("x", 9..10);
+        diag.locate("a\n→→ {{x");
+        assert_eq!((diag.line, diag.column), (2, 4));
+    }
+}
@@ -20,6 +20,11 @@ pub enum Error {
     #[error("Unknown shield style '{0}'. Run `mdfx shields list` to see available styles.")]
     UnknownShieldStyle(String),
 
+    /// A style has no equivalent for a letter or digit and the fallback
+    /// policy is `error`
+    #[error("Style '{style}' has no equivalent for '{ch}'. Use fallback=passthrough, skip, or closest to allow it.")]
+    UnmappedChar { ch: char, style: String },
+
     /// Invalid color specification
     #[error("Invalid color '{0}'. Use 6-digit hex codes (e.g., 2B6CB0) or palette names (e.g., cobalt).")]
     InvalidColor(String),
@@ -67,6 +72,75 @@ pub enum Error {
     /// UTF-8 encoding error
     #[error("Invalid UTF-8: {0}")]
     InvalidUtf8(#[from] std::string::FromUtf8Error),
+
+    /// A parser limit (nesting depth, input or output size) was exceeded
+    #[error("Limit exceeded: {0}")]
+    LimitExceeded(String),
+
+    /// A template used a feature disabled by the parser options
+    #[error("Not allowed: {0}")]
+    NotAllowed(String),
+
+    /// Uploading assets to object storage failed
+    #[error("Publish failed: {0}")]
+    PublishError(String),
+
+    /// Live data could not be fetched from its source
+    #[error("Fetch failed: {0}")]
+    FetchFailed(String),
+
+    /// Two different assets were given the same filename
+    #[error("Asset filename collision: {0} already holds different content; raise \"hash_length\" under \"filenames\" in .mdfx.json")]
+    AssetCollision(PathBuf),
+
+    /// A manifest signature is missing, malformed, or doesn't match
+    #[error("Invalid signature: {0}")]
+    SignatureInvalid(String),
+
+    /// A check found problems: asset verification, `check`, `fmt --check`, or lint
+    #[error("{0}")]
+    CheckFailed(String),
+}
+
+/// Broad category of an [`Error`], for callers that branch on the kind of
+/// failure rather than its message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// A template, config file, or other input could not be parsed or rendered
+    Parse,
+    /// Reading or writing a file failed
+    Io,
+    /// Fetching live data or uploading assets failed
+    Fetch,
+    /// A check ran and found problems
+    Check,
+}
+
+impl ErrorKind {
+    /// Lowercase name (`parse`, `io`, `fetch`, `check`)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::Parse => "parse",
+            ErrorKind::Io => "io",
+            ErrorKind::Fetch => "fetch",
+            ErrorKind::Check => "check",
+        }
+    }
+}
+
+impl Error {
+    /// Category of this error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::FileNotFound(_)
+            | Error::PermissionDenied(_)
+            | Error::IoError(_)
+            | Error::AssetCollision(_) => ErrorKind::Io,
+            Error::FetchFailed(_) | Error::PublishError(_) => ErrorKind::Fetch,
+            Error::CheckFailed(_) | Error::SignatureInvalid(_) => ErrorKind::Check,
+            _ => ErrorKind::Parse,
+        }
+    }
 }
 
 /// Result type for mdfx operations
@@ -0,0 +1,579 @@
+//! Template syntax formatter
+//!
+//! Normalizes mdfx template syntax without changing rendered output:
+//! canonical style/frame names instead of aliases, consistent `key=value`
+//! parameter ordering, and a configurable policy for frame/partial closers.
+//!
+//! Content inside fenced code blocks and inline code is left untouched.
+
+use crate::error::Result;
+use crate::registry::Registry;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// How closing tags for frames and partials are written
+///
+/// Styles always require named closers (`{{/mathbold}}`), so the policy
+/// only affects templates that accept both forms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloserPolicy {
+    /// Keep closers as written
+    #[default]
+    Preserve,
+    /// Rewrite `{{/}}` to `{{/frame}}` or `{{/partial}}`
+    Named,
+    /// Rewrite `{{/frame}}` and `{{/partial}}` to `{{/}}`
+    Universal,
+}
+
+impl CloserPolicy {
+    /// Parse a policy name (preserve, named, universal)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "preserve" => Some(CloserPolicy::Preserve),
+            "named" => Some(CloserPolicy::Named),
+            "universal" => Some(CloserPolicy::Universal),
+            _ => None,
+        }
+    }
+}
+
+/// Formatting options, configurable under `"format"` in `.mdfx.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatOptions {
+    /// Sort trailing `key=value` parameters alphabetically
+    pub sort_params: bool,
+    /// Replace style and frame aliases with canonical names
+    pub canonical_names: bool,
+    /// Closing tag policy for frames and partials
+    pub closers: CloserPolicy,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            sort_params: true,
+            canonical_names: true,
+            closers: CloserPolicy::Preserve,
+        }
+    }
+}
+
+/// Kind of an open block template, used to rewrite its closer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TagKind {
+    Style,
+    Frame,
+    Partial,
+    /// `{{raw}}`: everything up to `{{/raw}}` is copied as written
+    Raw,
+    /// `{{!--`: everything up to `--}}` is copied as written
+    Comment,
+    Other,
+}
+
+/// An open block template awaiting its closer
+#[derive(Debug, Clone)]
+struct OpenTag {
+    kind: TagKind,
+    /// Name as written in the source (what the closer must match)
+    written: String,
+    /// Name emitted by the formatter
+    canonical: String,
+}
+
+/// Formatter for mdfx template syntax
+pub struct Formatter {
+    registry: Arc<Registry>,
+    options: FormatOptions,
+    /// Style aliases shared by several styles (never rewritten)
+    ambiguous_aliases: HashSet<String>,
+}
+
+impl Formatter {
+    /// Create a formatter with default options
+    pub fn new() -> Result<Self> {
+        Self::with_options(FormatOptions::default())
+    }
+
+    /// Create a formatter with custom options
+    pub fn with_options(options: FormatOptions) -> Result<Self> {
+        let registry = Registry::shared()?;
+
+        let mut seen = HashSet::new();
+        let mut ambiguous_aliases = HashSet::new();
+        for style in registry.styles().values() {
+            for alias in &style.aliases {
+                if !seen.insert(alias.clone()) {
+                    ambiguous_aliases.insert(alias.clone());
+                }
+            }
+        }
+
+        Ok(Self {
+            registry,
+            options,
+            ambiguous_aliases,
+        })
+    }
+
+    /// Format a markdown document
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mdfx::Formatter;
+    ///
+    /// let formatter = Formatter::new().unwrap();
+    /// let result = formatter.format("{{mb}}Title{{/mb}} {{ui:tech:rust:style=flat:bg=000/}}");
+    /// assert_eq!(result, "{{mathbold}}Title{{/mathbold}} {{ui:tech:rust:bg=000:style=flat/}}");
+    /// ```
+    pub fn format(&self, markdown: &str) -> String {
+        let mut result = String::with_capacity(markdown.len());
+        let mut stack: Vec<OpenTag> = Vec::new();
+        let mut in_code_block = false;
+
+        for (idx, line) in markdown.split('\n').enumerate() {
+            if idx > 0 {
+                result.push('\n');
+            }
+
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+                result.push_str(line);
+                continue;
+            }
+            if in_code_block {
+                result.push_str(line);
+                continue;
+            }
+
+            // Odd segments between backticks are inline code
+            for (seg_idx, segment) in line.split('`').enumerate() {
+                if seg_idx > 0 {
+                    result.push('`');
+                }
+                if seg_idx % 2 == 1 {
+                    result.push_str(segment);
+                } else {
+                    result.push_str(&self.format_segment(segment, &mut stack));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Check whether a document is already formatted
+    pub fn is_formatted(&self, markdown: &str) -> bool {
+        self.format(markdown) == markdown
+    }
+
+    /// Format all templates in a segment of text (no code spans)
+    fn format_segment(&self, text: &str, stack: &mut Vec<OpenTag>) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+
+        loop {
+            let literal_end = match stack.last().map(|tag| tag.kind) {
+                Some(TagKind::Raw) => Some("{{/raw}}"),
+                Some(TagKind::Comment) => Some("--}}"),
+                _ => None,
+            };
+            if let Some(closer) = literal_end {
+                let Some(end) = rest.find(closer) else {
+                    result.push_str(rest);
+                    return result;
+                };
+                stack.pop();
+                result.push_str(&rest[..end + closer.len()]);
+                rest = &rest[end + closer.len()..];
+            }
+
+            let Some(open) = rest.find("{{") else {
+                break;
+            };
+            result.push_str(&rest[..open]);
+            let after = &rest[open + 2..];
+
+            // An escaped `\{{` is literal text
+            if rest[..open].ends_with('\\') {
+                result.push_str("{{");
+                rest = after;
+                continue;
+            }
+
+            // Comments are copied as written, like raw blocks
+            if let Some(comment) = after.strip_prefix("!--") {
+                stack.push(OpenTag {
+                    kind: TagKind::Comment,
+                    written: "!--".to_string(),
+                    canonical: "!--".to_string(),
+                });
+                result.push_str("{{!--");
+                rest = comment;
+                continue;
+            }
+
+            let Some(close) = after.find("}}") else {
+                result.push_str(&rest[open..]);
+                return result;
+            };
+
+            let content = &after[..close];
+            if content.contains("{{") {
+                // Not a template we understand; emit the braces and move on
+                result.push_str("{{");
+                rest = after;
+                continue;
+            }
+
+            result.push_str("{{");
+            result.push_str(&self.format_template(content, stack));
+            result.push_str("}}");
+            rest = &after[close + 2..];
+        }
+
+        result.push_str(rest);
+        result
+    }
+
+    /// Format the content of a single template (between `{{` and `}}`)
+    fn format_template(&self, content: &str, stack: &mut Vec<OpenTag>) -> String {
+        // Close-all
+        if content == "//" {
+            stack.clear();
+            return content.to_string();
+        }
+
+        if content == "raw" {
+            stack.push(OpenTag {
+                kind: TagKind::Raw,
+                written: "raw".to_string(),
+                canonical: "raw".to_string(),
+            });
+            return content.to_string();
+        }
+
+        // Closing tags
+        if let Some(name) = content.strip_prefix('/') {
+            return self.format_closer(name, stack);
+        }
+
+        // Self-closing templates
+        if let Some(body) = content.strip_suffix('/') {
+            return format!("{}/", self.format_body(body));
+        }
+
+        // Block templates
+        if content.starts_with("frame:") || content.starts_with("fr:") {
+            stack.push(OpenTag {
+                kind: TagKind::Frame,
+                written: "frame".to_string(),
+                canonical: "frame".to_string(),
+            });
+            return self.format_body(content);
+        }
+        if content.starts_with("partial:") {
+            stack.push(OpenTag {
+                kind: TagKind::Partial,
+                written: "partial".to_string(),
+                canonical: "partial".to_string(),
+            });
+            return content.to_string();
+        }
+        if content.starts_with("ui:") {
+            stack.push(OpenTag {
+                kind: TagKind::Other,
+                written: "ui".to_string(),
+                canonical: "ui".to_string(),
+            });
+            return self.format_body(content);
+        }
+        if ["glyph:", "kbd:", "num:", "shields:", "snippet:", "t:"]
+            .iter()
+            .any(|prefix| content.starts_with(prefix))
+        {
+            return content.to_string();
+        }
+
+        let (name, args) = match content.split_once(':') {
+            Some((name, args)) => (name, Some(args)),
+            None => (content, None),
+        };
+
+        if self.registry.style(name).is_some() {
+            let canonical = self.canonical_style(name).to_string();
+            stack.push(OpenTag {
+                kind: TagKind::Style,
+                written: name.to_string(),
+                canonical: canonical.clone(),
+            });
+            return match args {
+                Some(args) => format!("{}:{}", canonical, self.sort_params(args)),
+                None => canonical,
+            };
+        }
+
+        if self.registry.component(name).is_some() {
+            stack.push(OpenTag {
+                kind: TagKind::Other,
+                written: name.to_string(),
+                canonical: name.to_string(),
+            });
+            return self.format_body(content);
+        }
+
+        content.to_string()
+    }
+
+    /// Format a closing tag (content after the leading `/`)
+    fn format_closer(&self, name: &str, stack: &mut Vec<OpenTag>) -> String {
+        let policy = self.options.closers;
+
+        if name.is_empty() {
+            let Some(tag) = stack.pop() else {
+                return "/".to_string();
+            };
+            return match (policy, tag.kind) {
+                (CloserPolicy::Named, TagKind::Frame | TagKind::Partial) => {
+                    format!("/{}", tag.canonical)
+                }
+                _ => "/".to_string(),
+            };
+        }
+
+        match stack.last() {
+            Some(tag) if tag.written == name => {
+                let tag = stack.pop().expect("stack is non-empty");
+                match (policy, tag.kind) {
+                    (CloserPolicy::Universal, TagKind::Frame | TagKind::Partial) => "/".to_string(),
+                    _ => format!("/{}", tag.canonical),
+                }
+            }
+            _ => format!("/{}", name),
+        }
+    }
+
+    /// Format a template body: canonical frame names and sorted parameters
+    fn format_body(&self, body: &str) -> String {
+        if let Some(spec) = body.strip_prefix("frame:") {
+            return format!("frame:{}", self.format_frame_spec(spec));
+        }
+        if let Some(spec) = body.strip_prefix("fr:") {
+            return format!("fr:{}", self.format_frame_spec(spec));
+        }
+        self.sort_params(body)
+    }
+
+    /// Canonicalize frame names in a frame spec (e.g. `grad+star*2/reverse`)
+    fn format_frame_spec(&self, spec: &str) -> String {
+        if !self.options.canonical_names || spec.starts_with("glyph:") {
+            return spec.to_string();
+        }
+
+        let name_end = spec.find([':', '/']).unwrap_or(spec.len());
+        let (names, rest) = spec.split_at(name_end);
+
+        let canonical: Vec<String> = names
+            .split('+')
+            .map(|part| {
+                let (name, count) = match part.find('*') {
+                    Some(pos) => part.split_at(pos),
+                    None => (part, ""),
+                };
+                let name = self.registry.frame_id(name).unwrap_or(name);
+                format!("{}{}", name, count)
+            })
+            .collect();
+
+        format!("{}{}", canonical.join("+"), rest)
+    }
+
+    /// Resolve a style name to its canonical id (if enabled and unambiguous)
+    fn canonical_style<'a>(&'a self, name: &'a str) -> &'a str {
+        if !self.options.canonical_names || self.ambiguous_aliases.contains(name) {
+            return name;
+        }
+        self.registry.style_id(name).unwrap_or(name)
+    }
+
+    /// Sort trailing `key=value` segments of a colon-separated body
+    ///
+    /// Positional arguments keep their order. Bodies where a positional
+    /// segment follows a named one (e.g. URLs containing `:`) are left as-is.
+    fn sort_params(&self, body: &str) -> String {
+        if !self.options.sort_params {
+            return body.to_string();
+        }
+
+        let parts: Vec<&str> = body.split(':').collect();
+        let Some(first_named) = parts.iter().position(|p| p.contains('=')) else {
+            return body.to_string();
+        };
+
+        let (positional, named) = parts.split_at(first_named);
+        if named.iter().any(|p| !p.contains('=')) {
+            return body.to_string();
+        }
+
+        let mut named = named.to_vec();
+        named.sort_by_key(|p| p.split_once('=').map(|(k, _)| k).unwrap_or(p));
+
+        positional
+            .iter()
+            .chain(named.iter())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(":")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn format_with(input: &str, closers: CloserPolicy) -> String {
+        let options = FormatOptions {
+            closers,
+            ..Default::default()
+        };
+        Formatter::with_options(options).unwrap().format(input)
+    }
+
+    // ========================================================================
+    // Canonical Names (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case("{{mb}}Hi{{/mb}}", "{{mathbold}}Hi{{/mathbold}}")]
+    #[case("{{mathbold}}Hi{{/mathbold}}", "{{mathbold}}Hi{{/mathbold}}")]
+    #[case(
+        "{{sc:spacing=1}}Hi{{/sc}}",
+        "{{small-caps:spacing=1}}Hi{{/small-caps}}"
+    )]
+    #[case("{{frame:grad}}Hi{{/frame}}", "{{frame:gradient}}Hi{{/frame}}")]
+    #[case("{{fr:grad+stars*2}}Hi{{/}}", "{{fr:gradient+star*2}}Hi{{/}}")]
+    #[case("{{frame:glyph:star*3}}Hi{{/}}", "{{frame:glyph:star*3}}Hi{{/}}")]
+    #[case("{{circled}}Hi{{/circled}}", "{{circled}}Hi{{/circled}}")] // ambiguous alias
+    fn test_canonical_names(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Formatter::new().unwrap().format(input), expected);
+    }
+
+    // ========================================================================
+    // Parameter Ordering (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case(
+        "{{ui:tech:rust:style=flat:bg=000/}}",
+        "{{ui:tech:rust:bg=000:style=flat/}}"
+    )]
+    #[case("{{ui:swatch:accent/}}", "{{ui:swatch:accent/}}")]
+    #[case(
+        "{{ui:progress:50:width=100:fill=accent/}}",
+        "{{ui:progress:50:fill=accent:width=100/}}"
+    )]
+    #[case(
+        "{{ui:tech:rust:url=https://x.dev:bg=000/}}",
+        "{{ui:tech:rust:url=https://x.dev:bg=000/}}"
+    )]
+    fn test_sort_params(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Formatter::new().unwrap().format(input), expected);
+    }
+
+    // ========================================================================
+    // Closer Policy (Parameterized)
+    // ========================================================================
+
+    #[rstest]
+    #[case(
+        CloserPolicy::Preserve,
+        "{{frame:star}}A{{/}} {{frame:star}}B{{/frame}}",
+        "{{frame:star}}A{{/}} {{frame:star}}B{{/frame}}"
+    )]
+    #[case(
+        CloserPolicy::Named,
+        "{{frame:star}}A{{/}}",
+        "{{frame:star}}A{{/frame}}"
+    )]
+    #[case(
+        CloserPolicy::Named,
+        "{{partial:hero}}A{{/}}",
+        "{{partial:hero}}A{{/partial}}"
+    )]
+    #[case(
+        CloserPolicy::Universal,
+        "{{frame:star}}A{{/frame}}",
+        "{{frame:star}}A{{/}}"
+    )]
+    #[case(
+        CloserPolicy::Universal,
+        "{{mb}}A{{/mb}}",
+        "{{mathbold}}A{{/mathbold}}"
+    )]
+    #[case(
+        CloserPolicy::Named,
+        "{{frame:star}}{{mb}}A{{//}}",
+        "{{frame:star}}{{mathbold}}A{{//}}"
+    )]
+    fn test_closer_policy(
+        #[case] policy: CloserPolicy,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_with(input, policy), expected);
+    }
+
+    #[test]
+    fn test_multiline_frame_closer() {
+        let input = "{{frame:star}}\nline one\n{{/}}";
+        assert_eq!(
+            format_with(input, CloserPolicy::Named),
+            "{{frame:star}}\nline one\n{{/frame}}"
+        );
+    }
+
+    #[test]
+    fn test_code_preserved() {
+        let input = "```\n{{mb}}x{{/mb}}\n```\nUse `{{mb}}` for {{mb}}bold{{/mb}}\n";
+        let expected = "```\n{{mb}}x{{/mb}}\n```\nUse `{{mb}}` for {{mathbold}}bold{{/mathbold}}\n";
+        assert_eq!(Formatter::new().unwrap().format(input), expected);
+    }
+
+    #[test]
+    fn test_escapes_preserved() {
+        let input = "\\{{mb}}x\\{{/mb}} {{raw}}{{mb}}y{{/mb}}\n{{sc}}{{/raw}} {{mb}}z{{/mb}}";
+        let expected =
+            "\\{{mb}}x\\{{/mb}} {{raw}}{{mb}}y{{/mb}}\n{{sc}}{{/raw}} {{mathbold}}z{{/mathbold}}";
+        assert_eq!(Formatter::new().unwrap().format(input), expected);
+    }
+
+    #[test]
+    fn test_comments_preserved() {
+        let input = "{{!-- use {{mb}} here }} --}} {{mb}}a{{/mb}} {{!-- two\n{{sc}} lines --}}{{sc}}b{{/sc}}";
+        let expected = "{{!-- use {{mb}} here }} --}} {{mathbold}}a{{/mathbold}} {{!-- two\n{{sc}} lines --}}{{small-caps}}b{{/small-caps}}";
+        assert_eq!(Formatter::new().unwrap().format(input), expected);
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        let formatter = Formatter::new().unwrap();
+        let input = "{{sc}}A{{/sc}} {{fr:grad}}B{{/}} {{ui:tech:go:text=fff:bg=000/}}";
+        let once = formatter.format(input);
+        assert_eq!(formatter.format(&once), once);
+        assert!(formatter.is_formatted(&once));
+    }
+
+    #[test]
+    fn test_options_from_json() {
+        let options: FormatOptions = serde_json::from_str(r#"{"closers": "named"}"#).unwrap();
+        assert_eq!(options.closers, CloserPolicy::Named);
+        assert!(options.sort_params);
+        assert!(options.canonical_names);
+    }
+}
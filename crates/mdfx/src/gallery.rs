@@ -0,0 +1,470 @@
+//! Gallery of everything the registry can render
+//!
+//! Renders every style, frame, glyph group, and component with sample
+//! parameters into one showcase page, so the catalog can be browsed instead
+//! of discovered by trial and error. Components that read project files are
+//! given sample files and live badges render as placeholders, so the gallery
+//! builds offline and outside a project.
+//!
+//! Two formats are supported:
+//!
+//! - [`GalleryFormat::Markdown`] - a markdown page with tables for styles,
+//!   frames, and glyphs and a section per component
+//! - [`GalleryFormat::Html`] - a standalone HTML page with the same layout
+
+use crate::error::Result;
+use crate::parser::{ProcessedMarkdown, TemplateParser};
+use crate::registry::Registry;
+use crate::renderer::Renderer;
+use badgefx::escape::{escape_attr, escape_text};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Text the style and frame samples are rendered with
+pub const SAMPLE_TEXT: &str = "Sample Text";
+
+/// Output format for [`generate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GalleryFormat {
+    /// Markdown page
+    Markdown,
+    /// Standalone HTML page
+    Html,
+}
+
+impl GalleryFormat {
+    /// Parse a format name (markdown, html)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "markdown" | "md" => Some(GalleryFormat::Markdown),
+            "html" => Some(GalleryFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+/// Sample call of every registry component
+const COMPONENT_SAMPLES: &[(&str, &str)] = &[
+    ("swatch", "{{ui:swatch:accent:label=accent/}}"),
+    ("tech", "{{ui:tech:rust/}}"),
+    (
+        "tech-group",
+        "{{ui:tech-group}}{{ui:tech:rust/}}{{ui:tech:typescript/}}{{ui:tech:docker/}}{{/ui}}",
+    ),
+    ("version", "{{ui:version:1.2.0/}}"),
+    ("license", "{{ui:license:MIT/}}"),
+    ("msrv", "{{ui:msrv/}}"),
+    ("node-engines", "{{ui:node-engines/}}"),
+    ("python-requires", "{{ui:python-requires/}}"),
+    ("maintenance", "{{ui:maintenance:active/}}"),
+    ("coverage", "{{ui:coverage:file=coverage/lcov.info/}}"),
+    ("tests", "{{ui:tests:file=target/nextest/ci/junit.xml/}}"),
+    ("bench", "{{ui:bench:parser/small/}}"),
+    ("live", "{{ui:live:github:rust-lang/rust:stars/}}"),
+    (
+        "ci-matrix",
+        "{{ui:ci-matrix:rust-lang/rust:workflows=CI,Release/}}",
+    ),
+    ("progress", "{{ui:progress:75/}}"),
+    ("donut", "{{ui:donut:75/}}"),
+    ("gauge", "{{ui:gauge:75/}}"),
+    ("sparkline", "{{ui:sparkline:1,3,2,6,4,8,5,7/}}"),
+    ("waveform", "{{ui:waveform:5,8,-3,6,-2,9,-5,4,7,-1/}}"),
+    ("rating", "{{ui:rating:3.5/}}"),
+    (
+        "statcard",
+        "{{ui:statcard:label=Downloads:value=1.2M:icon=download:trend=+8%/}}",
+    ),
+    (
+        "statcard-row",
+        "{{ui:statcard-row}}{{ui:statcard:label=Stars:value=4k/}}{{ui:statcard:label=Forks:value=210/}}{{/ui}}",
+    ),
+    ("hero", "{{ui:hero:title=mdfx:subtitle=Markdown effects/}}"),
+    ("row", "{{ui:row}}{{ui:tech:rust/}} {{ui:tech:go/}}{{/ui}}"),
+    (
+        "steps",
+        "{{ui:steps:current=2}}\n1. Install\n2. Configure\n3. Ship\n{{/ui}}",
+    ),
+    (
+        "matrix",
+        "{{ui:matrix}}\nFeature | mdfx | other\nUnicode styles | yes | no\nSVG output | yes | partial\n{{/ui}}",
+    ),
+    ("tree", "{{ui:tree}}\nsrc/\n  lib.rs\n  parser.rs\n{{/ui}}"),
+    (
+        "divider",
+        "{{ui:divider:style=wave:width=40:label=Section/}}",
+    ),
+    ("latest-release", "{{ui:latest-release/}}"),
+];
+
+/// Project files read by the file-based component samples
+const SAMPLE_FILES: &[(&str, &str)] = &[
+    ("Cargo.toml", "[package]\nrust-version = \"1.70\"\n"),
+    ("package.json", r#"{"engines": {"node": ">=18"}}"#),
+    ("pyproject.toml", "[project]\nrequires-python = \">=3.9\"\n"),
+    (
+        "coverage/lcov.info",
+        "SF:src/lib.rs\nLF:300\nLH:256\nend_of_record\n",
+    ),
+    (
+        "target/nextest/ci/junit.xml",
+        r#"<testsuites><testsuite name="mdfx" tests="48" failures="0" skipped="2"/></testsuites>"#,
+    ),
+    (
+        "target/criterion/parser/small/new/estimates.json",
+        r#"{"mean": {"point_estimate": 42000.0}}"#,
+    ),
+    (
+        "target/criterion/parser/small/base/estimates.json",
+        r#"{"mean": {"point_estimate": 48000.0}}"#,
+    ),
+];
+
+/// Changelog read by the `latest-release` sample
+const SAMPLE_CHANGELOG: &str = "## [1.2.0] - 2024-05-01\n\n### Added\n- Gallery of every renderable\n- Math regions left as written\n";
+
+lazy_static! {
+    static ref MARKDOWN_IMAGE: Regex = Regex::new(r"!\[([^\]]*)\]\(([^)\s]+)\)").unwrap();
+}
+
+/// Render the gallery with `backend`
+///
+/// The returned assets are the files the backend produced, e.g. the SVGs
+/// of the SVG backend, and are written like those of any processed document.
+pub fn generate(
+    registry: Arc<Registry>,
+    backend: Box<dyn Renderer>,
+    format: GalleryFormat,
+) -> Result<ProcessedMarkdown> {
+    let mut parser = TemplateParser::with_registry(registry.clone(), backend)?;
+    for (name, contents) in SAMPLE_FILES {
+        parser.set_project_file(*name, *contents);
+    }
+    parser.set_changelog(SAMPLE_CHANGELOG);
+    parser.set_placeholders(true);
+
+    let mut page = Page::new(format);
+    let mut assets = Vec::new();
+    let mut render = |template: &str| -> Result<String> {
+        let processed = parser.process_with_assets(template)?;
+        assets.extend(processed.assets);
+        Ok(processed.markdown)
+    };
+
+    page.heading(1, "mdfx Gallery");
+    page.paragraph(&format!(
+        "Every style, frame, glyph, and component in registry {}, rendered by `mdfx gen gallery`.",
+        registry.version()
+    ));
+
+    page.heading(2, "Styles");
+    let mut rows = Vec::new();
+    for id in sorted_keys(registry.styles()) {
+        let style = &registry.styles()[id];
+        let template = format!("{{{{{id}}}}}{SAMPLE_TEXT}{{{{/{id}}}}}");
+        let output = render(&template)?;
+        rows.push([named(&style.name, id, &style.aliases), template, output]);
+    }
+    page.table(["Style", "Template", "Output"], &rows, Some(1));
+
+    page.heading(2, "Frames");
+    let mut rows = Vec::new();
+    for id in sorted_keys(registry.frames()) {
+        let frame = &registry.frames()[id];
+        let template = format!("{{{{frame:{id}}}}}{SAMPLE_TEXT}{{{{/frame}}}}");
+        let output = render(&template)?;
+        let description = frame.description.as_deref().unwrap_or(id);
+        rows.push([named(description, id, &frame.aliases), template, output]);
+    }
+    page.table(["Frame", "Template", "Output"], &rows, Some(1));
+
+    page.heading(2, "Glyphs");
+    page.paragraph("Insert a glyph with `{{glyph:NAME/}}`, e.g. `{{glyph:star.filled/}}`.");
+    let mut groups: BTreeMap<&str, Vec<(&str, &str)>> = BTreeMap::new();
+    for (name, glyph) in registry.glyphs() {
+        let group = name.split('.').next().unwrap_or(name);
+        groups.entry(group).or_default().push((name, glyph));
+    }
+    let rows: Vec<[String; 2]> = groups
+        .into_iter()
+        .map(|(group, mut glyphs)| {
+            glyphs.sort_unstable();
+            let glyphs: Vec<&str> = glyphs.into_iter().map(|(_, glyph)| glyph).collect();
+            [group.to_string(), glyphs.join(" ")]
+        })
+        .collect();
+    page.table(["Group", "Glyphs"], &rows, None);
+
+    page.heading(2, "Components");
+    for (name, template) in COMPONENT_SAMPLES {
+        let Some(component) = registry.component(name) else {
+            continue;
+        };
+        let output = render(template)?;
+        page.heading(3, name);
+        if let Some(description) = &component.description {
+            page.paragraph(description);
+        }
+        page.code(template);
+        page.output(&output);
+    }
+
+    Ok(ProcessedMarkdown {
+        markdown: page.finish(),
+        assets,
+    })
+}
+
+/// Sorted names of a registry map
+fn sorted_keys<V>(map: &std::collections::HashMap<String, V>) -> Vec<&str> {
+    let mut keys: Vec<&str> = map.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    keys
+}
+
+/// Display name with the id and aliases to write in templates
+fn named(name: &str, id: &str, aliases: &[String]) -> String {
+    let mut names = vec![id];
+    names.extend(aliases.iter().map(String::as_str));
+    format!("{} ({})", name, names.join(", "))
+}
+
+/// Page being written in one of the gallery formats
+struct Page {
+    format: GalleryFormat,
+    out: String,
+}
+
+impl Page {
+    fn new(format: GalleryFormat) -> Self {
+        let out = match format {
+            GalleryFormat::Markdown => String::new(),
+            GalleryFormat::Html => concat!(
+                "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n",
+                "<title>mdfx Gallery</title>\n<style>\n",
+                "body { font-family: system-ui, sans-serif; max-width: 960px; margin: 2em auto; padding: 0 1em; }\n",
+                "table { border-collapse: collapse; }\n",
+                "th, td { border: 1px solid #d0d7de; padding: 4px 8px; text-align: left; }\n",
+                "pre { background: #f6f8fa; padding: 8px; }\n",
+                "</style>\n</head>\n<body>\n",
+            )
+            .to_string(),
+        };
+        Self { format, out }
+    }
+
+    fn heading(&mut self, level: usize, text: &str) {
+        match self.format {
+            GalleryFormat::Markdown => {
+                self.out
+                    .push_str(&format!("{} {}\n\n", "#".repeat(level), text));
+            }
+            GalleryFormat::Html => {
+                self.out
+                    .push_str(&format!("<h{level}>{}</h{level}>\n", escape_text(text)));
+            }
+        }
+    }
+
+    /// Paragraph of text where backticks mark code
+    fn paragraph(&mut self, text: &str) {
+        match self.format {
+            GalleryFormat::Markdown => self.out.push_str(&format!("{}\n\n", text)),
+            GalleryFormat::Html => {
+                let html: String = text
+                    .split('`')
+                    .enumerate()
+                    .map(|(i, part)| match i % 2 {
+                        0 => escape_text(part).into_owned(),
+                        _ => format!("<code>{}</code>", escape_text(part)),
+                    })
+                    .collect();
+                self.out.push_str(&format!("<p>{}</p>\n", html));
+            }
+        }
+    }
+
+    fn code(&mut self, template: &str) {
+        match self.format {
+            GalleryFormat::Markdown => {
+                self.out
+                    .push_str(&format!("```markdown\n{}\n```\n\n", template));
+            }
+            GalleryFormat::Html => {
+                self.out.push_str(&format!(
+                    "<pre><code>{}</code></pre>\n",
+                    escape_text(template)
+                ));
+            }
+        }
+    }
+
+    /// Rendered output of a component sample
+    fn output(&mut self, markdown: &str) {
+        match self.format {
+            GalleryFormat::Markdown => self.out.push_str(&format!("{}\n\n", markdown)),
+            GalleryFormat::Html => self.out.push_str(&format!("{}\n", html_block(markdown))),
+        }
+    }
+
+    /// Table with the template in `code_column` shown as code
+    fn table<const N: usize>(
+        &mut self,
+        headers: [&str; N],
+        rows: &[[String; N]],
+        code_column: Option<usize>,
+    ) {
+        let is_code = |column: usize| code_column == Some(column);
+        match self.format {
+            GalleryFormat::Markdown => {
+                self.out.push_str(&format!("| {} |\n", headers.join(" | ")));
+                self.out.push_str(&format!("|{}\n", "---|".repeat(N)));
+                for row in rows {
+                    let cells: Vec<String> = row
+                        .iter()
+                        .enumerate()
+                        .map(|(column, cell)| {
+                            let cell = cell.replace('|', "\\|");
+                            if is_code(column) {
+                                format!("`{}`", cell)
+                            } else {
+                                cell
+                            }
+                        })
+                        .collect();
+                    self.out.push_str(&format!("| {} |\n", cells.join(" | ")));
+                }
+                self.out.push('\n');
+            }
+            GalleryFormat::Html => {
+                self.out.push_str("<table>\n<tr>");
+                for header in headers {
+                    self.out
+                        .push_str(&format!("<th>{}</th>", escape_text(header)));
+                }
+                self.out.push_str("</tr>\n");
+                for row in rows {
+                    self.out.push_str("<tr>");
+                    for (column, cell) in row.iter().enumerate() {
+                        let cell = if is_code(column) {
+                            format!("<code>{}</code>", escape_text(cell))
+                        } else {
+                            html_inline(cell)
+                        };
+                        self.out.push_str(&format!("<td>{}</td>", cell));
+                    }
+                    self.out.push_str("</tr>\n");
+                }
+                self.out.push_str("</table>\n");
+            }
+        }
+    }
+
+    fn finish(mut self) -> String {
+        if self.format == GalleryFormat::Html {
+            self.out.push_str("</body>\n</html>\n");
+        }
+        self.out.truncate(self.out.trim_end().len());
+        self.out.push('\n');
+        self.out
+    }
+}
+
+/// Rendered markdown as HTML: images become `<img>`, HTML is kept, and
+/// other multi-line output such as trees and tables is shown preformatted
+fn html_block(markdown: &str) -> String {
+    let trimmed = markdown.trim();
+    if trimmed.starts_with('<') || !trimmed.contains('\n') {
+        return format!("<div>{}</div>", html_inline(trimmed));
+    }
+    format!("<pre>{}</pre>", html_inline(trimmed))
+}
+
+/// Text with markdown images turned into `<img>` and HTML tags kept
+fn html_inline(markdown: &str) -> String {
+    let keep_tags = markdown.trim_start().starts_with('<');
+    let mut html = String::new();
+    let mut last = 0;
+    for image in MARKDOWN_IMAGE.captures_iter(markdown) {
+        let whole = image.get(0).expect("match");
+        html.push_str(&text_or_html(&markdown[last..whole.start()], keep_tags));
+        html.push_str(&format!(
+            "<img src=\"{}\" alt=\"{}\">",
+            escape_attr(&image[2]),
+            escape_attr(&image[1])
+        ));
+        last = whole.end();
+    }
+    html.push_str(&text_or_html(&markdown[last..], keep_tags));
+    html
+}
+
+fn text_or_html(text: &str, keep_tags: bool) -> String {
+    if keep_tags {
+        text.to_string()
+    } else {
+        escape_text(text).into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::svg::SvgBackend;
+
+    fn gallery(format: GalleryFormat) -> ProcessedMarkdown {
+        let registry = Registry::shared().unwrap();
+        generate(registry, Box::new(SvgBackend::new("assets")), format).unwrap()
+    }
+
+    #[test]
+    fn test_every_component_has_a_sample() {
+        let registry = Registry::shared().unwrap();
+        for name in registry.components().keys() {
+            assert!(
+                COMPONENT_SAMPLES.iter().any(|(sample, _)| sample == name),
+                "no gallery sample for component '{}'",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_markdown_gallery() {
+        let registry = Registry::shared().unwrap();
+        let gallery = gallery(GalleryFormat::Markdown);
+        let markdown = &gallery.markdown;
+
+        assert!(markdown.starts_with("# mdfx Gallery\n"));
+        assert!(markdown.contains("| `{{mathbold}}Sample Text{{/mathbold}}` | 𝐒𝐚𝐦𝐩𝐥𝐞 𝐓𝐞𝐱𝐭 |"));
+        assert!(markdown.contains("`{{frame:gradient}}Sample Text{{/frame}}`"));
+        assert!(markdown.contains("| pipe | \\| |"), "{}", markdown);
+        for name in registry.components().keys() {
+            assert!(markdown.contains(&format!("\n### {}\n", name)), "{}", name);
+        }
+        assert!(markdown.contains("```markdown\n{{ui:donut:75/}}\n```\n\n![](assets/donut_"));
+        assert!(!gallery.assets.is_empty());
+    }
+
+    #[test]
+    fn test_html_gallery() {
+        let gallery = gallery(GalleryFormat::Html);
+        let html = &gallery.markdown;
+
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.ends_with("</body>\n</html>\n"));
+        assert!(html.contains("<td><code>{{mathbold}}Sample Text{{/mathbold}}</code></td>"));
+        assert!(html
+            .contains("<pre><code>{{ui:donut:75/}}</code></pre>\n<div><img src=\"assets/donut_"));
+        assert!(!html.contains("![]("));
+    }
+
+    #[test]
+    fn test_gallery_format_parse() {
+        assert_eq!(GalleryFormat::parse("md"), Some(GalleryFormat::Markdown));
+        assert_eq!(GalleryFormat::parse("html"), Some(GalleryFormat::Html));
+        assert_eq!(GalleryFormat::parse("pdf"), None);
+    }
+}
@@ -0,0 +1,462 @@
+//! Syntax highlighting grammar generation
+//!
+//! Emits editor grammars for mdfx template syntax embedded in markdown.
+//! Style, component, and frame names are taken from the [`Registry`], so
+//! highlighting only recognizes names the parser actually accepts and stays
+//! in sync as the registry grows.
+//!
+//! Two formats are supported:
+//!
+//! - [`GrammarFormat::TextMate`] - a JSON injection grammar for `text.html.markdown`
+//!   (VS Code, Sublime Text, and other TextMate-compatible editors)
+//! - [`GrammarFormat::TreeSitter`] - a `grammar.js` for `tree-sitter generate`,
+//!   intended to be used as a markdown inline injection
+
+use crate::registry::Registry;
+use serde_json::json;
+
+/// Scope name of the generated TextMate injection grammar
+pub const TEXTMATE_SCOPE: &str = "text.mdfx.injection";
+
+/// Output format for [`generate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrammarFormat {
+    /// TextMate JSON injection grammar
+    TextMate,
+    /// tree-sitter `grammar.js`
+    TreeSitter,
+}
+
+impl GrammarFormat {
+    /// Parse a format name (textmate, tree-sitter)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "textmate" | "tmlanguage" => Some(GrammarFormat::TextMate),
+            "tree-sitter" | "treesitter" => Some(GrammarFormat::TreeSitter),
+            _ => None,
+        }
+    }
+}
+
+/// Keywords that open non-style templates
+const DIRECTIVES: [&str; 6] = ["partial", "kbd", "num", "shields", "t", "snippet"];
+
+/// Closers that are not style names
+const CLOSERS: [&str; 6] = [
+    "{{//}}",
+    "{{/}}",
+    "{{/frame}}",
+    "{{/fr}}",
+    "{{/ui}}",
+    "{{/partial}}",
+];
+
+/// Generate a grammar in the given format
+pub fn generate(registry: &Registry, format: GrammarFormat) -> String {
+    match format {
+        GrammarFormat::TextMate => textmate(registry),
+        GrammarFormat::TreeSitter => tree_sitter(registry),
+    }
+}
+
+/// Registry names used by the grammars, sorted longest-first
+///
+/// Longest-first ordering keeps regex alternations from matching a prefix
+/// (e.g. `bold` before `bold-italic`).
+struct Names {
+    styles: Vec<String>,
+    components: Vec<String>,
+    frames: Vec<String>,
+}
+
+impl Names {
+    fn from_registry(registry: &Registry) -> Self {
+        let styles = registry
+            .styles()
+            .values()
+            .flat_map(|s| std::iter::once(&s.id).chain(s.aliases.iter()));
+        let frames = registry
+            .frames()
+            .iter()
+            .flat_map(|(id, f)| std::iter::once(id).chain(f.aliases.iter()));
+
+        Self {
+            styles: sorted(styles),
+            components: sorted(registry.components().keys()),
+            frames: sorted(frames),
+        }
+    }
+}
+
+fn sorted<'a>(names: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut names: Vec<String> = names.cloned().collect();
+    names.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    names.dedup();
+    names
+}
+
+/// Build a regex alternation of literal names
+fn alternation(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|n| regex::escape(n))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Generate a TextMate injection grammar (JSON)
+pub fn textmate(registry: &Registry) -> String {
+    let names = Names::from_registry(registry);
+    let styles = alternation(&names.styles);
+    let components = alternation(&names.components);
+    let frames = alternation(&names.frames);
+    let begin = json!({ "name": "punctuation.definition.template.begin.mdfx" });
+    let end = json!({ "name": "punctuation.definition.template.end.mdfx" });
+    let separator = json!({ "name": "punctuation.separator.mdfx" });
+    let keyword = json!({ "name": "keyword.control.mdfx" });
+
+    let grammar = json!({
+        "scopeName": TEXTMATE_SCOPE,
+        "injectionSelector": "L:text.html.markdown -markup.fenced_code -markup.inline.raw",
+        "patterns": [{ "include": "#mdfx-template" }],
+        "repository": {
+            "mdfx-template": {
+                "patterns": [
+                    { "include": "#mdfx-closer" },
+                    { "include": "#mdfx-component" },
+                    { "include": "#mdfx-glyph" },
+                    { "include": "#mdfx-frame" },
+                    { "include": "#mdfx-directive" },
+                    { "include": "#mdfx-style-open" },
+                    { "include": "#mdfx-style-close" }
+                ]
+            },
+            "mdfx-closer": {
+                "name": "meta.template.mdfx.block.close",
+                "match": "(\\{\\{)(/)(/|frame|ui|partial)?(\\}\\})",
+                "captures": {
+                    "1": begin,
+                    "2": separator,
+                    "3": keyword,
+                    "4": end
+                }
+            },
+            "mdfx-component": {
+                "name": "meta.template.mdfx.component",
+                "match": format!(
+                    "(\\{{\\{{)(ui)(:)({})((?::[^}}]*?)?)(/?\\}}\\}})",
+                    components
+                ),
+                "captures": {
+                    "1": begin,
+                    "2": keyword,
+                    "3": separator,
+                    "4": { "name": "entity.name.tag.mdfx" },
+                    "5": { "patterns": [{ "include": "#mdfx-params" }] },
+                    "6": end
+                }
+            },
+            "mdfx-glyph": {
+                "name": "meta.template.mdfx.glyph",
+                "match": "(\\{\\{)(glyph)(:)([^}/]+)(/\\}\\})",
+                "captures": {
+                    "1": begin,
+                    "2": keyword,
+                    "3": separator,
+                    "4": { "name": "constant.character.glyph.mdfx" },
+                    "5": end
+                }
+            },
+            "mdfx-frame": {
+                "name": "meta.template.mdfx.frame",
+                "match": "(\\{\\{)(frame|fr)(:)([^}]+?)(/?\\}\\})",
+                "captures": {
+                    "1": begin,
+                    "2": keyword,
+                    "3": separator,
+                    "4": { "patterns": [{ "include": "#mdfx-frame-spec" }] },
+                    "5": end
+                }
+            },
+            "mdfx-frame-spec": {
+                "patterns": [
+                    {
+                        "match": "(glyph)(:)([^*+/:}]+)",
+                        "captures": {
+                            "1": keyword,
+                            "2": separator,
+                            "3": { "name": "constant.character.glyph.mdfx" }
+                        }
+                    },
+                    {
+                        "name": "entity.name.type.frame.mdfx",
+                        "match": format!("(?<![\\w-])(?:{})(?![\\w-])", frames)
+                    },
+                    {
+                        "name": "keyword.operator.mdfx",
+                        "match": "[+*/]"
+                    },
+                    {
+                        "name": "constant.numeric.mdfx",
+                        "match": "\\d+"
+                    }
+                ]
+            },
+            "mdfx-directive": {
+                "name": "meta.template.mdfx.directive",
+                "match": format!(
+                    "(\\{{\\{{)({})(:)([^}}]*?)(/?\\}}\\}})",
+                    DIRECTIVES.join("|")
+                ),
+                "captures": {
+                    "1": begin,
+                    "2": keyword,
+                    "3": separator,
+                    "4": { "patterns": [{ "include": "#mdfx-params" }] },
+                    "5": end
+                }
+            },
+            "mdfx-style-open": {
+                "name": "meta.template.mdfx.block.open",
+                "match": format!("(\\{{\\{{)({})((?::[^}}]*)?)(\\}}\\}})", styles),
+                "captures": {
+                    "1": begin,
+                    "2": { "name": "entity.name.function.mdfx" },
+                    "3": { "patterns": [{ "include": "#mdfx-params" }] },
+                    "4": end
+                }
+            },
+            "mdfx-style-close": {
+                "name": "meta.template.mdfx.block.close",
+                "match": format!("(\\{{\\{{)(/)({})(\\}}\\}})", styles),
+                "captures": {
+                    "1": begin,
+                    "2": separator,
+                    "3": { "name": "entity.name.function.mdfx" },
+                    "4": end
+                }
+            },
+            "mdfx-params": {
+                "patterns": [
+                    {
+                        "match": "([a-zA-Z_][a-zA-Z0-9_]*)(=)([^:}]*)",
+                        "captures": {
+                            "1": { "name": "variable.parameter.mdfx" },
+                            "2": { "name": "keyword.operator.assignment.mdfx" },
+                            "3": { "name": "string.unquoted.mdfx" }
+                        }
+                    },
+                    {
+                        "name": "punctuation.separator.mdfx",
+                        "match": ":"
+                    },
+                    {
+                        "name": "variable.other.mdfx",
+                        "match": "[^:=}]+"
+                    }
+                ]
+            }
+        }
+    });
+
+    let mut output = serde_json::to_string_pretty(&grammar).unwrap_or_default();
+    output.push('\n');
+    output
+}
+
+/// Quote a string as a JavaScript string literal
+fn js_string(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Format names as a tree-sitter `choice(...)` of string literals
+fn js_choice(names: &[String]) -> String {
+    let items: Vec<String> = names
+        .iter()
+        .map(|n| format!("      {},", js_string(n)))
+        .collect();
+    format!("choice(\n{}\n    )", items.join("\n"))
+}
+
+/// Generate a tree-sitter grammar (`grammar.js`)
+pub fn tree_sitter(registry: &Registry) -> String {
+    let names = Names::from_registry(registry);
+    let closers: Vec<String> = CLOSERS.iter().map(|c| c.to_string()).collect();
+    let directives: Vec<String> = DIRECTIVES.iter().map(|d| d.to_string()).collect();
+
+    format!(
+        r#"/**
+ * tree-sitter grammar for mdfx template syntax
+ *
+ * Generated by mdfx {version} from the template registry. Regenerate with:
+ *   mdfx gen grammar --format tree-sitter > grammar.js
+ */
+
+module.exports = grammar({{
+  name: 'mdfx',
+
+  extras: $ => [],
+
+  rules: {{
+    document: $ => repeat(choice($._template, $.text)),
+
+    text: $ => token(prec(-1, choice(/[^{{]+/, '{{'))),
+
+    _template: $ => choice(
+      $.closer,
+      $.style_open,
+      $.style_close,
+      $.component,
+      $.glyph,
+      $.frame,
+      $.directive,
+    ),
+
+    closer: $ => {closers},
+
+    style_open: $ => seq('{{{{', field('name', $.style_name), optional(seq(':', $.params)), '}}}}'),
+
+    style_close: $ => seq('{{{{/', field('name', $.style_name), '}}}}'),
+
+    component: $ => seq(
+      '{{{{ui:',
+      field('name', $.component_name),
+      optional(seq(':', $.params)),
+      choice('/}}}}', '}}}}'),
+    ),
+
+    glyph: $ => seq('{{{{glyph:', field('name', $.glyph_name), '/}}}}'),
+
+    frame: $ => seq(
+      choice('{{{{frame:', '{{{{fr:'),
+      $.frame_spec,
+      optional(seq(':', field('content', $.value))),
+      choice('/}}}}', '}}}}'),
+    ),
+
+    frame_spec: $ => seq(
+      $._frame_part,
+      repeat(seq('+', $._frame_part)),
+      optional(seq('/', field('modifier', $.modifier))),
+    ),
+
+    _frame_part: $ => seq(
+      choice(field('name', $.frame_name), seq('glyph:', field('glyph', $.glyph_name))),
+      optional(seq('*', field('count', $.count))),
+    ),
+
+    directive: $ => seq(
+      '{{{{',
+      field('kind', {directives}),
+      ':',
+      $.params,
+      choice('/}}}}', '}}}}'),
+    ),
+
+    params: $ => seq($.param, repeat(seq(':', $.param))),
+
+    param: $ => seq(field('key', $.value), optional(seq('=', field('value', $.value)))),
+
+    value: $ => /([^:=}}\/]|\/[^}}])+/,
+
+    glyph_name: $ => /[a-zA-Z0-9._-]+/,
+
+    modifier: $ => /[a-z]+/,
+
+    count: $ => /\d+/,
+
+    style_name: $ => {styles},
+
+    component_name: $ => {components},
+
+    frame_name: $ => {frames},
+  }},
+}});
+"#,
+        version = env!("CARGO_PKG_VERSION"),
+        closers = js_choice(&closers),
+        directives = js_choice(&directives),
+        styles = js_choice(&names.styles),
+        components = js_choice(&names.components),
+        frames = js_choice(&names.frames),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use rstest::rstest;
+
+    fn registry() -> Registry {
+        Registry::new().unwrap()
+    }
+
+    fn textmate_json() -> serde_json::Value {
+        serde_json::from_str(&textmate(&registry())).unwrap()
+    }
+
+    fn pattern(grammar: &serde_json::Value, rule: &str) -> Regex {
+        let source = grammar["repository"][rule]["match"].as_str().unwrap();
+        // Oniguruma lookbehind isn't supported by the regex crate; the rules
+        // tested here don't use it.
+        Regex::new(source).unwrap()
+    }
+
+    #[rstest]
+    #[case("textmate", Some(GrammarFormat::TextMate))]
+    #[case("tree-sitter", Some(GrammarFormat::TreeSitter))]
+    #[case("vim", None)]
+    fn test_format_parse(#[case] input: &str, #[case] expected: Option<GrammarFormat>) {
+        assert_eq!(GrammarFormat::parse(input), expected);
+    }
+
+    #[test]
+    fn test_textmate_includes_registry_names() {
+        let grammar = textmate_json();
+        assert_eq!(grammar["scopeName"], TEXTMATE_SCOPE);
+
+        let components = grammar["repository"]["mdfx-component"]["match"]
+            .as_str()
+            .unwrap();
+        for name in registry().components().keys() {
+            assert!(components.contains(&regex::escape(name)), "{}", name);
+        }
+    }
+
+    #[rstest]
+    #[case("mdfx-style-open", "{{mathbold}}", true)]
+    #[case("mdfx-style-open", "{{bold-italic:spacing=1}}", true)]
+    #[case("mdfx-style-open", "{{notastyle}}", false)]
+    #[case("mdfx-style-close", "{{/mb}}", true)]
+    #[case("mdfx-component", "{{ui:tech:rust/}}", true)]
+    #[case("mdfx-component", "{{ui:progress:50:width=200/}}", true)]
+    #[case("mdfx-component", "{{ui:nonexistent/}}", false)]
+    #[case("mdfx-closer", "{{/}}", true)]
+    #[case("mdfx-closer", "{{//}}", true)]
+    #[case("mdfx-glyph", "{{glyph:star/}}", true)]
+    fn test_textmate_matches(#[case] rule: &str, #[case] input: &str, #[case] expected: bool) {
+        let re = pattern(&textmate_json(), rule);
+        let full = re.find(input).map(|m| m.as_str() == input).unwrap_or(false);
+        assert_eq!(full, expected, "{} on {}", rule, input);
+    }
+
+    #[test]
+    fn test_longest_name_first() {
+        let re = pattern(&textmate_json(), "mdfx-style-open");
+        let caps = re.captures("{{bold-italic}}").unwrap();
+        assert_eq!(&caps[2], "bold-italic");
+    }
+
+    #[test]
+    fn test_tree_sitter_grammar() {
+        let grammar = tree_sitter(&registry());
+
+        assert!(grammar.contains("name: 'mdfx'"));
+        assert!(grammar.contains("style_open: $ => seq('{{'"));
+        assert!(grammar.contains("'{{/}}',"));
+        assert!(grammar.contains("'mathbold',"));
+        assert!(grammar.contains("'tech-group',"));
+        assert!(grammar.contains("'gradient',"));
+    }
+}
@@ -0,0 +1,122 @@
+//! Rendering middleware
+//!
+//! Hooks registered on [`TemplateParser`](crate::TemplateParser) can rewrite
+//! output as it is produced: [`AssetHook`]s see every [`RenderedAsset`] from
+//! the backend before its markdown is embedded, and [`MarkdownHook`]s see the
+//! final document. Hooks run in registration order.
+//!
+//! Closures with the matching signature implement both traits:
+//!
+//! ```
+//! use mdfx::{RenderedAsset, TemplateParser};
+//! use std::sync::Arc;
+//!
+//! let mut parser = TemplateParser::new().unwrap();
+//!
+//! // Route shields.io badges through a proxy
+//! parser.add_asset_hook(Arc::new(|asset: &mut RenderedAsset| {
+//!     *asset.markdown_mut() = asset
+//!         .to_markdown()
+//!         .replace("https://img.shields.io/", "https://badges.example.com/");
+//!     Ok(())
+//! }));
+//!
+//! // Stamp the generated document
+//! parser.add_markdown_hook(Arc::new(|markdown: String| {
+//!     Ok(format!("{}\n<!-- generated by mdfx -->", markdown))
+//! }));
+//!
+//! let out = parser.process("{{ui:swatch:accent/}}").unwrap();
+//! assert!(out.starts_with("![](https://badges.example.com/"));
+//! assert!(out.ends_with("<!-- generated by mdfx -->"));
+//! ```
+
+use crate::error::Result;
+use crate::renderer::RenderedAsset;
+
+/// Intercepts assets rendered by the backend
+///
+/// Changes to the asset's markdown, path, or bytes are reflected both in
+/// the document and in the assets returned from
+/// [`process_with_assets`](crate::TemplateParser::process_with_assets).
+pub trait AssetHook: Send + Sync {
+    /// Inspect or rewrite one rendered asset
+    fn process_asset(&self, asset: &mut RenderedAsset) -> Result<()>;
+}
+
+impl<F> AssetHook for F
+where
+    F: Fn(&mut RenderedAsset) -> Result<()> + Send + Sync,
+{
+    fn process_asset(&self, asset: &mut RenderedAsset) -> Result<()> {
+        self(asset)
+    }
+}
+
+/// Rewrites the fully processed markdown
+pub trait MarkdownHook: Send + Sync {
+    /// Return the document to use in place of `markdown`
+    fn process_markdown(&self, markdown: String) -> Result<String>;
+}
+
+impl<F> MarkdownHook for F
+where
+    F: Fn(String) -> Result<String> + Send + Sync,
+{
+    fn process_markdown(&self, markdown: String) -> Result<String> {
+        self(markdown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::renderer::svg::SvgBackend;
+    use crate::{Error, RenderedAsset, TemplateParser};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_asset_hook_rewrites_file_assets() {
+        let mut parser = TemplateParser::with_backend(Box::new(SvgBackend::new("assets"))).unwrap();
+        parser.add_asset_hook(Arc::new(|asset: &mut RenderedAsset| {
+            if let RenderedAsset::File {
+                relative_path,
+                markdown_ref,
+                ..
+            } = asset
+            {
+                *markdown_ref = markdown_ref.replace("assets/", "https://cdn.example.com/");
+                *relative_path = relative_path.replace("assets/", "public/");
+            }
+            Ok(())
+        }));
+
+        let processed = parser.process_with_assets("{{ui:progress:50/}}").unwrap();
+
+        assert!(processed.markdown.contains("https://cdn.example.com/"));
+        assert!(processed.assets[0]
+            .file_path()
+            .unwrap()
+            .starts_with("public/"));
+    }
+
+    #[test]
+    fn test_hooks_run_in_order() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.add_markdown_hook(Arc::new(|md: String| Ok(format!("{}1", md))));
+        parser.add_markdown_hook(Arc::new(|md: String| Ok(format!("{}2", md))));
+
+        assert_eq!(parser.process("x").unwrap(), "x12");
+    }
+
+    #[test]
+    fn test_hook_errors_propagate() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.add_asset_hook(Arc::new(|_: &mut RenderedAsset| {
+            Err(Error::ParseError("blocked".to_string()))
+        }));
+
+        assert!(parser.process("plain text").is_ok());
+        let err = parser.process("{{ui:swatch:accent/}}").unwrap_err();
+        assert!(err.to_string().contains("blocked"));
+    }
+}
@@ -0,0 +1,118 @@
+//! Translated strings for `{{t:key/}}` templates
+//!
+//! A string file is a JSON object mapping keys to text, one file per
+//! language (`i18n/fr.json`, `i18n/de.json`). Nested objects group keys:
+//! `{"intro": {"title": "Bonjour"}}` defines `intro.title`. Values may
+//! contain templates, which are expanded where the string is used.
+
+use crate::error::{Error, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Whether `key` can be written in a `{{t:key/}}` template
+///
+/// Keys are `.`-separated parts of ASCII letters, digits, `_` and `-`,
+/// each starting with a letter or digit.
+pub fn is_valid_key(key: &str) -> bool {
+    key.split('.').all(|part| {
+        part.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+    })
+}
+
+/// Parse a string file into a map from dotted key to text
+///
+/// Fails on invalid JSON, on values that are neither text nor objects, and
+/// on keys that can't be used in a template.
+pub fn parse_strings(json: &str) -> Result<HashMap<String, String>> {
+    let value: Value = serde_json::from_str(json)?;
+    let mut strings = HashMap::new();
+    match value {
+        Value::Object(map) => flatten("", map, &mut strings)?,
+        _ => {
+            return Err(Error::ParseError(
+                "String file must be a JSON object of keys to text".to_string(),
+            ))
+        }
+    }
+    Ok(strings)
+}
+
+/// Add the strings of `map` to `strings`, prefixing keys with `prefix`
+fn flatten(
+    prefix: &str,
+    map: serde_json::Map<String, Value>,
+    strings: &mut HashMap<String, String>,
+) -> Result<()> {
+    for (name, value) in map {
+        let key = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        if !is_valid_key(&key) {
+            return Err(Error::ParseError(format!(
+                "Invalid string key '{}': use letters, digits, '_', '-' and '.'",
+                key
+            )));
+        }
+        match value {
+            Value::String(text) => {
+                strings.insert(key, text);
+            }
+            Value::Object(nested) => flatten(&key, nested, strings)?,
+            other => {
+                return Err(Error::ParseError(format!(
+                    "String '{}' must be text, found {}",
+                    key, other
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_parse_strings_flattens_objects() {
+        let strings = parse_strings(
+            r#"{
+                "title": "Bonjour",
+                "intro": { "lead": "Un outil", "install": { "cargo": "Avec cargo" } }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(strings.len(), 3);
+        assert_eq!(strings["title"], "Bonjour");
+        assert_eq!(strings["intro.lead"], "Un outil");
+        assert_eq!(strings["intro.install.cargo"], "Avec cargo");
+    }
+
+    #[rstest]
+    #[case(r#"["a"]"#, "must be a JSON object")]
+    #[case(r#"{"count": 3}"#, "String 'count' must be text, found 3")]
+    #[case(r#"{"a b": "x"}"#, "Invalid string key 'a b'")]
+    #[case(r#"{"intro": {"": "x"}}"#, "Invalid string key 'intro.'")]
+    fn test_parse_strings_errors(#[case] json: &str, #[case] message: &str) {
+        let err = parse_strings(json).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+
+    #[rstest]
+    #[case("title", true)]
+    #[case("intro.lead_2", true)]
+    #[case("see-also", true)]
+    #[case(".hidden", false)]
+    #[case("a/b", false)]
+    #[case("intro..lead", false)]
+    #[case("", false)]
+    fn test_is_valid_key(#[case] key: &str, #[case] expected: bool) {
+        assert_eq!(is_valid_key(key), expected);
+    }
+}
@@ -0,0 +1,495 @@
+//! Icons from SVG documents: remote icons (`icon=url:https://...`) and
+//! icons defined under `"icons"` in `.mdfx.json`
+//!
+//! An SVG document becomes icon markup for a 24×24 box, the size of the
+//! built-in Simple Icons. [`sanitize`] first removes everything that could
+//! run code or load other resources: `script`, `foreignObject`, `iframe`,
+//! `embed` and `object` elements, event handler attributes, links and
+//! animations that leave the document, `javascript:` values, and styles
+//! that import or reference external files. Elements and attributes are
+//! matched by local name, so a `prefix:` doesn't hide them, and prefixes
+//! bound to the SVG or XHTML namespaces are dropped. The drawing is then fitted
+//! into the box by its `viewBox`, whatever size it declares, and a
+//! monochrome icon has its one color replaced with `currentColor` so it
+//! takes the badge's logo color.
+//!
+//! In strict mode ([`ParserOptions::strict_icons`](crate::ParserOptions::strict_icons))
+//! an icon with any of that content is rejected instead of cleaned.
+//!
+//! ```
+//! use mdfx::icon::to_markup;
+//!
+//! let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 48 48">
+//!   <script>alert(1)</script>
+//!   <path fill="#000" d="M0 0h48v48H0z" onclick="alert(1)"/>
+//! </svg>"##;
+//! let markup = to_markup(svg, false).unwrap();
+//! assert!(markup.starts_with("<svg viewBox=\"0 0 48 48\" width=\"24\" height=\"24\">"));
+//! assert!(markup.contains("<path fill=\"currentColor\" d=\"M0 0h48v48H0z\"/>"));
+//! assert!(!markup.contains("alert"));
+//!
+//! assert!(to_markup(svg, true).is_err());
+//! ```
+
+use crate::error::{Error, Result};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::collections::BTreeSet;
+
+/// Prefix of `icon=` values naming a remote icon
+pub const URL_PREFIX: &str = "url:";
+
+/// Largest icon document accepted, in bytes
+pub const MAX_ICON_BYTES: usize = 256 * 1024;
+
+/// Most elements an icon may have
+pub const MAX_ICON_ELEMENTS: usize = 4096;
+
+/// Elements removed with their content
+const BLOCKED_ELEMENTS: [&str; 5] = ["script", "foreignobject", "iframe", "embed", "object"];
+
+/// Namespaces a prefix can't be bound to, since prefixed elements in them
+/// are live SVG or HTML
+const REBOUND_NAMESPACES: [&str; 2] =
+    ["http://www.w3.org/2000/svg", "http://www.w3.org/1999/xhtml"];
+
+/// Embedded images links may point to; SVG images are left out as they
+/// could carry their own scripts
+const RASTER_DATA: [&str; 4] = [
+    "data:image/png",
+    "data:image/jpeg",
+    "data:image/gif",
+    "data:image/webp",
+];
+
+lazy_static! {
+    static ref COMMENT: Regex = Regex::new(r"(?s)<!--.*?-->").unwrap();
+    static ref PROCESSING: Regex = Regex::new(r"(?s)<\?.*?\?>").unwrap();
+    static ref DOCTYPE: Regex = Regex::new(r"(?is)<!DOCTYPE[^\[>]*(?:\[.*?\])?\s*>").unwrap();
+    static ref ROOT: Regex = Regex::new(r"(?s)<svg\b([^>]*)>(.*)</svg\s*>").unwrap();
+    static ref BLOCKED: Vec<Regex> = BLOCKED_ELEMENTS
+        .iter()
+        .map(|name| {
+            Regex::new(&format!(
+                r"(?is)<(?:[\w.-]+:)?{0}\b[^>]*?(?:/>|>.*?</(?:[\w.-]+:)?{0}\s*>)",
+                name
+            ))
+            .unwrap()
+        })
+        .collect();
+    static ref STYLE: Regex =
+        Regex::new(r"(?is)<(?:[\w.-]+:)?style\b[^>]*>(.*?)</(?:[\w.-]+:)?style\s*>").unwrap();
+    static ref TAG: Regex = Regex::new(
+        r#"<([A-Za-z][\w:.-]*)((?:\s+[^\s=/>]+(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s"'>]+))?)*)\s*(/?)>"#
+    )
+    .unwrap();
+    static ref ATTR: Regex =
+        Regex::new(r#"([^\s=/>]+)(?:\s*=\s*("[^"]*"|'[^']*'|[^\s"'>]+))?"#).unwrap();
+    static ref ACTIVE: Regex =
+        Regex::new(r"(?i)<\s*(?:[\w.-]+:)?(?:script|foreignobject|iframe|embed|object)\b|<[^>]*\s(?:[\w.-]+:)?on[a-z]+\s*=")
+            .unwrap();
+    static ref EXTERNAL_CSS: Regex =
+        Regex::new(r#"(?i)@import|expression\s*\(|url\(\s*['"]?\s*[^#'"\s)]"#).unwrap();
+    static ref VIEW_BOX: Regex = Regex::new(r#"\bviewBox\s*=\s*["']([^"']+)["']"#).unwrap();
+    static ref WIDTH: Regex = Regex::new(r#"\swidth\s*=\s*["']([\d.]+)"#).unwrap();
+    static ref HEIGHT: Regex = Regex::new(r#"\sheight\s*=\s*["']([\d.]+)"#).unwrap();
+    static ref PAINT: Regex =
+        Regex::new(r#"\b(fill|stroke)(\s*=\s*["']|\s*:\s*)(#[0-9A-Fa-f]{3,8}|[a-zA-Z]+)"#).unwrap();
+}
+
+/// The URL of a remote icon, for `icon=` values starting with `url:`
+pub fn remote_url(icon: &str) -> Option<&str> {
+    icon.strip_prefix(URL_PREFIX)
+}
+
+/// Turn an SVG document into sanitized 24×24 icon markup
+///
+/// Fails when the document has no `<svg>` root element, is larger than
+/// [`MAX_ICON_BYTES`] or has more than [`MAX_ICON_ELEMENTS`] elements, and
+/// in `strict` mode when [`sanitize`] would have to remove anything.
+pub fn to_markup(svg: &str, strict: bool) -> Result<String> {
+    let svg = sanitize(svg, strict)?;
+    let root = ROOT
+        .captures(&svg)
+        .ok_or_else(|| Error::ParseError("Icon is not an SVG document".to_string()))?;
+    let attrs = &root[1];
+    let view_box = match VIEW_BOX.captures(attrs) {
+        Some(caps) => caps[1].split_whitespace().collect::<Vec<_>>().join(" "),
+        None => {
+            let size = |re: &Regex| re.captures(attrs).map(|caps| caps[1].to_string());
+            let width = size(&WIDTH).unwrap_or_else(|| "24".to_string());
+            let height = size(&HEIGHT).unwrap_or_else(|| width.clone());
+            format!("0 0 {} {}", width, height)
+        }
+    };
+
+    let body = &root[2];
+    let body = if is_monochrome(body) {
+        PAINT
+            .replace_all(body, |caps: &Captures| match &caps[3] {
+                "none" | "currentColor" | "transparent" | "inherit" => caps[0].to_string(),
+                _ => format!("{}{}currentColor", &caps[1], &caps[2]),
+            })
+            .into_owned()
+    } else {
+        body.to_string()
+    };
+
+    Ok(format!(
+        "<svg viewBox=\"{}\" width=\"24\" height=\"24\">{}</svg>",
+        crate::escape::escape_attr(&view_box),
+        body
+    ))
+}
+
+/// Remove content that could run code or load other resources from SVG
+/// markup
+///
+/// Comments, processing instructions and `DOCTYPE` declarations (which can
+/// define entities) are dropped too. In `strict` mode, markup that needs
+/// anything but comments and processing instructions removed is rejected
+/// with [`Error::NotAllowed`] naming what was found. Markup over the size
+/// limits is rejected with [`Error::LimitExceeded`] in either mode.
+///
+/// ```
+/// use mdfx::icon::sanitize;
+///
+/// let svg = "<svg><a href=\"https://example.com\"><path d=\"M0 0\" onload=\"x()\"/></a></svg>";
+/// assert_eq!(sanitize(svg, false).unwrap(), "<svg><a><path d=\"M0 0\"/></a></svg>");
+/// assert!(sanitize(svg, true).is_err());
+/// ```
+pub fn sanitize(svg: &str, strict: bool) -> Result<String> {
+    if svg.len() > MAX_ICON_BYTES {
+        return Err(Error::LimitExceeded(format!(
+            "icon is larger than {} KiB",
+            MAX_ICON_BYTES / 1024
+        )));
+    }
+
+    let mut found = BTreeSet::new();
+    let svg = COMMENT.replace_all(svg, "");
+    let svg = PROCESSING.replace_all(&svg, "");
+    let mut svg = DOCTYPE
+        .replace_all(&svg, |_: &Captures| {
+            found.insert("a DOCTYPE declaration".to_string());
+            ""
+        })
+        .into_owned();
+    for (name, re) in BLOCKED_ELEMENTS.iter().zip(BLOCKED.iter()) {
+        svg = re
+            .replace_all(&svg, |_: &Captures| {
+                found.insert(format!("<{}>", name));
+                ""
+            })
+            .into_owned();
+    }
+    let svg = STYLE.replace_all(&svg, |caps: &Captures| {
+        if EXTERNAL_CSS.is_match(&caps[1]) {
+            found.insert("external references in <style>".to_string());
+            String::new()
+        } else {
+            caps[0].to_string()
+        }
+    });
+
+    let mut elements = 0;
+    let svg = TAG.replace_all(&svg, |caps: &Captures| {
+        elements += 1;
+        let name = local_name(&caps[1]).to_ascii_lowercase();
+        if BLOCKED_ELEMENTS.contains(&name.as_str()) {
+            found.insert(format!("<{}>", name));
+            return String::new();
+        }
+        let attrs: Vec<&str> = ATTR
+            .captures_iter(&caps[2])
+            .filter(|attr| !rebinds_namespace(attr))
+            .filter(|attr| match unsafe_attribute(attr) {
+                Some(reason) => {
+                    found.insert(reason);
+                    false
+                }
+                None => true,
+            })
+            .map(|attr| attr.get(0).map_or("", |m| m.as_str()))
+            .collect();
+        let mut tag = format!("<{}", &caps[1]);
+        for attr in attrs {
+            tag.push(' ');
+            tag.push_str(attr);
+        }
+        tag.push_str(&caps[3]);
+        tag.push('>');
+        tag
+    });
+
+    if elements > MAX_ICON_ELEMENTS {
+        return Err(Error::LimitExceeded(format!(
+            "icon has more than {} elements",
+            MAX_ICON_ELEMENTS
+        )));
+    }
+    // Anything still active is in markup the tag pattern couldn't read
+    if ACTIVE.is_match(&svg) {
+        return Err(Error::NotAllowed(
+            "icon has malformed markup around a script or event handler".to_string(),
+        ));
+    }
+    if strict && !found.is_empty() {
+        return Err(Error::NotAllowed(format!(
+            "icon contains {} (strict icons)",
+            found.into_iter().collect::<Vec<_>>().join(", ")
+        )));
+    }
+    Ok(svg.into_owned())
+}
+
+/// Name without its `prefix:`
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Whether an attribute is an `xmlns:prefix` declaration binding a prefix to
+/// the SVG or XHTML namespace
+///
+/// These are dropped without counting as unsafe content: the unprefixed
+/// elements icons draw with don't need them.
+fn rebinds_namespace(attr: &Captures) -> bool {
+    let value = attr.get(2).map_or("", |m| m.as_str());
+    let value = value.trim_matches(|c| c == '"' || c == '\'').trim();
+    attr[1].to_ascii_lowercase().starts_with("xmlns:")
+        && REBOUND_NAMESPACES
+            .iter()
+            .any(|namespace| value.eq_ignore_ascii_case(namespace))
+}
+
+/// Why an attribute can't be kept, if it can't
+fn unsafe_attribute(attr: &Captures) -> Option<String> {
+    let qualified = attr[1].to_ascii_lowercase();
+    let name = local_name(&qualified);
+    let value = attr.get(2).map_or("", |m| m.as_str());
+    let value = value.trim_matches(|c| c == '"' || c == '\'').trim();
+    let compact: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    if name.starts_with("on") {
+        Some(format!("event handler {}=", qualified))
+    } else if compact.contains("javascript:") {
+        Some("javascript: values".to_string())
+    } else if name == "href"
+        && !value.starts_with('#')
+        && !RASTER_DATA.iter().any(|prefix| compact.starts_with(prefix))
+    {
+        Some("external links".to_string())
+    } else if name == "attributename" && (compact == "href" || compact.ends_with(":href")) {
+        Some("animated links".to_string())
+    } else if name == "style" && EXTERNAL_CSS.is_match(value) {
+        Some("external references in style=".to_string())
+    } else {
+        None
+    }
+}
+
+/// Whether the markup paints with at most one color
+///
+/// Gradients and patterns (`url(#...)`) count as more than one color, so
+/// such icons keep their own colors.
+fn is_monochrome(body: &str) -> bool {
+    if body.contains("url(") {
+        return false;
+    }
+    let colors: BTreeSet<String> = PAINT
+        .captures_iter(body)
+        .map(|caps| caps[3].to_ascii_lowercase())
+        .filter(|color| {
+            !matches!(
+                color.as_str(),
+                "none" | "currentcolor" | "transparent" | "inherit"
+            )
+        })
+        .map(|color| match color.as_str() {
+            "#000" | "black" => "#000000".to_string(),
+            "#fff" | "white" => "#ffffff".to_string(),
+            _ => color,
+        })
+        .collect();
+    colors.len() <= 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_remote_url() {
+        assert_eq!(
+            remote_url("url:https://example.com/logo.svg"),
+            Some("https://example.com/logo.svg")
+        );
+        assert_eq!(remote_url("rust"), None);
+    }
+
+    #[rstest]
+    #[case("<script>alert(1)</script>")]
+    #[case("<SCRIPT type=\"text/javascript\">alert(1)</SCRIPT>")]
+    #[case("<script href=\"x.js\"/>")]
+    #[case("<foreignObject><div>x</div></foreignObject>")]
+    fn test_strips_active_elements(#[case] element: &str) {
+        let markup =
+            to_markup(&format!("<svg>{}<path d=\"M0 0\"/></svg>", element), false).unwrap();
+        assert_eq!(
+            markup,
+            "<svg viewBox=\"0 0 24 24\" width=\"24\" height=\"24\"><path d=\"M0 0\"/></svg>"
+        );
+    }
+
+    #[rstest]
+    #[case(r#"<x:script xmlns:x="http://www.w3.org/2000/svg">alert(1)</x:script>"#)]
+    #[case(r#"<h:script xmlns:h="http://www.w3.org/1999/xhtml">alert(1)</h:script>"#)]
+    #[case(r#"<x:foreignObject xmlns:x="http://www.w3.org/2000/svg"><p>alert(1)</p></x:foreignObject>"#)]
+    #[case(
+        r#"<h:iframe xmlns:h="http://www.w3.org/1999/xhtml" src="https://evil.test/alert(1)"/>"#
+    )]
+    #[case(r#"<h:embed src="alert(1).swf"/><h:object data="alert(1)"></h:object>"#)]
+    fn test_strips_prefixed_active_elements(#[case] element: &str) {
+        let svg = format!("<svg>{}<path d=\"M0 0\"/></svg>", element);
+        let markup = to_markup(&svg, false).unwrap();
+        assert_eq!(
+            markup,
+            "<svg viewBox=\"0 0 24 24\" width=\"24\" height=\"24\"><path d=\"M0 0\"/></svg>"
+        );
+        assert!(to_markup(&svg, true).is_err());
+    }
+
+    #[test]
+    fn test_drops_rebound_namespace_prefixes() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:svg="http://www.w3.org/2000/svg" xmlns:h="http://www.w3.org/1999/XHTML" xmlns:dc="http://purl.org/dc/elements/1.1/"><path x:onclick="alert(1)" d="M0 0"/></svg>"#;
+        let clean = sanitize(svg, false).unwrap();
+        assert_eq!(
+            clean,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:dc="http://purl.org/dc/elements/1.1/"><path d="M0 0"/></svg>"#
+        );
+        let err = sanitize(svg, true).unwrap_err();
+        assert!(err.to_string().contains("x:onclick"), "{}", err);
+    }
+
+    #[rstest]
+    #[case("<path onload=\"alert(1)\" d=\"M0 0\"/>")]
+    #[case("<path ONCLICK='alert(1)' d=\"M0 0\"/>")]
+    #[case("<a href=\"javascript:alert(1)\"><path d=\"M0 0\"/></a>")]
+    #[case("<a xlink:href=' javascript:alert(1)'><path d=\"M0 0\"/></a>")]
+    fn test_strips_scripting_attributes(#[case] element: &str) {
+        let markup = to_markup(&format!("<svg>{}</svg>", element), false).unwrap();
+        assert!(!markup.to_lowercase().contains("alert"), "{}", markup);
+    }
+
+    #[rstest]
+    #[case("<svg viewBox=\"0 0 100 50\">", "0 0 100 50")]
+    #[case("<svg viewBox='0,0,32,32'>", "0,0,32,32")]
+    #[case("<svg width=\"16\" height=\"16\">", "0 0 16 16")]
+    #[case("<svg width=\"64px\">", "0 0 64 64")]
+    #[case("<svg xmlns=\"http://www.w3.org/2000/svg\">", "0 0 24 24")]
+    fn test_view_box(#[case] open: &str, #[case] expected: &str) {
+        let markup = to_markup(&format!("{}<path d=\"M0 0\"/></svg>", open), false).unwrap();
+        assert!(
+            markup.starts_with(&format!("<svg viewBox=\"{}\"", expected)),
+            "{}",
+            markup
+        );
+    }
+
+    #[test]
+    fn test_prolog_and_comments() {
+        let svg = "<?xml version=\"1.0\"?>\n<!-- <svg viewBox=\"0 0 1 1\"> -->\n\
+                   <svg viewBox=\"0 0 10 10\"><rect width=\"10\" height=\"10\"/></svg>\n";
+        assert_eq!(
+            to_markup(svg, false).unwrap(),
+            "<svg viewBox=\"0 0 10 10\" width=\"24\" height=\"24\"><rect width=\"10\" height=\"10\"/></svg>"
+        );
+    }
+
+    #[test]
+    fn test_monochrome_is_recolored() {
+        let svg = "<svg><path fill=\"#000\" d=\"M0 0\"/><path style=\"fill: black\" d=\"M1 1\"/>\
+                   <path fill=\"none\" stroke=\"#000000\" d=\"M2 2\"/></svg>";
+        let markup = to_markup(svg, false).unwrap();
+        assert_eq!(markup.matches("currentColor").count(), 3, "{}", markup);
+        assert!(markup.contains("fill=\"none\""));
+    }
+
+    #[rstest]
+    #[case("<path fill=\"#F74C00\" d=\"M0 0\"/><path fill=\"#000\" d=\"M1 1\"/>")]
+    #[case("<defs><linearGradient id=\"g\"/></defs><path fill=\"url(#g)\" d=\"M0 0\"/><path fill=\"red\" d=\"M1 1\"/>")]
+    fn test_multicolor_keeps_colors(#[case] body: &str) {
+        let markup = to_markup(&format!("<svg>{}</svg>", body), false).unwrap();
+        assert!(!markup.contains("currentColor"), "{}", markup);
+    }
+
+    #[rstest]
+    #[case("<image href=\"https://tracker.example/p.png\"/>", "<image/>")]
+    #[case("<use xlink:href=\"other.svg#icon\"/>", "<use/>")]
+    #[case("<use href=\"#shape\"/>", "<use href=\"#shape\"/>")]
+    #[case(
+        "<image href=\"data:image/png;base64,AAAA\"/>",
+        "<image href=\"data:image/png;base64,AAAA\"/>"
+    )]
+    #[case("<image href=\"data:image/svg+xml;base64,AAAA\"/>", "<image/>")]
+    #[case("<set attributeName=\"href\" to=\"#x\"/>", "<set to=\"#x\"/>")]
+    #[case("<path style=\"fill:url(https://x/y)\"/>", "<path/>")]
+    #[case("<style>@import url(x.css);</style><g/>", "<g/>")]
+    #[case("<style>.a{fill:red}</style>", "<style>.a{fill:red}</style>")]
+    #[case("<iframe src=\"https://example.com\"></iframe>", "")]
+    fn test_sanitize(#[case] body: &str, #[case] expected: &str) {
+        assert_eq!(
+            sanitize(&format!("<svg>{}</svg>", body), false).unwrap(),
+            format!("<svg>{}</svg>", expected)
+        );
+    }
+
+    #[rstest]
+    #[case("<script>alert(1)</script>", "<script>")]
+    #[case("<path onload=\"x()\"/>", "event handler onload=")]
+    #[case("<a href=\"https://example.com\"/>", "external links")]
+    #[case("<a href=\"java\nscript:x()\"/>", "javascript: values")]
+    #[case("<!DOCTYPE svg [<!ENTITY x \"y\">]><svg/>", "a DOCTYPE declaration")]
+    fn test_strict_rejects(#[case] markup: &str, #[case] reason: &str) {
+        let err = sanitize(&format!("<svg>{}</svg>", markup), true).unwrap_err();
+        assert!(matches!(err, Error::NotAllowed(_)), "{}", err);
+        assert!(err.to_string().contains(reason), "{}", err);
+    }
+
+    #[test]
+    fn test_strict_accepts_clean_icons() {
+        let svg = "<?xml version=\"1.0\"?><!-- logo --><svg viewBox=\"0 0 24 24\">\
+                   <defs><linearGradient id=\"g\"/></defs><use href=\"#g\"/>\
+                   <path fill=\"url(#g)\" d=\"M0 0\"/></svg>";
+        assert!(to_markup(svg, true).is_ok());
+    }
+
+    #[test]
+    fn test_malformed_active_markup_rejected() {
+        assert!(sanitize("<svg><script src=\"x.js\"</svg>", false).is_err());
+    }
+
+    #[test]
+    fn test_size_limits() {
+        let big = format!("<svg>{}</svg>", " ".repeat(MAX_ICON_BYTES));
+        assert!(matches!(
+            sanitize(&big, false),
+            Err(Error::LimitExceeded(_))
+        ));
+        let many = format!("<svg>{}</svg>", "<g/>".repeat(MAX_ICON_ELEMENTS));
+        assert!(matches!(
+            sanitize(&many, false),
+            Err(Error::LimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_not_svg() {
+        assert!(to_markup("<html><body>Not found</body></html>", false).is_err());
+    }
+}
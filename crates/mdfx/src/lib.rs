@@ -38,18 +38,53 @@
 //! - Zero-copy operations for maximum performance
 //! - Comprehensive error handling
 
+pub mod analysis;
+pub mod asset_diff;
+pub mod ast;
+pub mod changelog;
+pub mod commonmark;
+pub mod compat;
 pub mod components;
 pub mod config;
 pub mod converter;
+pub mod coverage;
+pub mod criterion;
+pub mod diagnostics;
 pub mod error;
+pub mod formatter;
+pub mod gallery;
+pub mod grammar;
+pub mod hooks;
+pub mod i18n;
+pub mod icon;
+pub mod links;
 pub mod manifest;
+pub mod numbers;
+pub mod options;
+pub mod palette;
 pub mod parser;
 pub mod primitive;
+pub mod project;
+pub mod publish;
+pub mod reflow;
 pub mod registry;
+pub mod render;
 pub mod renderer;
+pub mod schema;
 pub mod shields;
+#[cfg(feature = "sign")]
+pub mod signing;
+pub mod snippet;
+pub mod stats;
 pub mod styles;
 pub mod targets;
+pub mod test_results;
+pub mod vfs;
+pub mod width;
+pub mod wiki;
+
+/// Escaping for SVG and HTML output, shared with badgefx
+pub use badgefx::escape;
 
 // Test utilities - macros available crate-wide in test mode
 #[cfg(test)]
@@ -57,25 +92,49 @@ pub mod targets;
 pub mod test_utils;
 
 // Re-export main types for convenience
+pub use analysis::TemplateUsage;
+pub use asset_diff::{AssetChange, AssetDiff};
 #[cfg(feature = "fetch")]
 pub use components::FetchContext;
-pub use components::{ComponentDef, ComponentOutput, ComponentsRenderer, PostProcess};
-pub use config::{expand_partial, MdfxConfig, PartialDef};
-pub use converter::Converter;
-pub use error::{Error, Result};
-pub use manifest::{AssetEntry, AssetManifest, PrimitiveInfo, VerificationResult};
+#[cfg(feature = "wasm")]
+pub use components::WasmPlugin;
+pub use components::{
+    ComponentDef, ComponentOutput, ComponentPlugin, ComponentsRenderer, ParamIssue, ParamSchema,
+    PluginCall, PostProcess,
+};
+pub use config::{
+    expand_partial, front_matter_styles, MdfxConfig, PartialDef, PipelineConfig, PipelineStage,
+    PluginConfig, RepositoryConfig, StyleDefaults, TargetOverrides,
+};
+pub use converter::{Converter, Fallback};
+pub use diagnostics::{Diagnostic, RecoveredMarkdown, Severity};
+pub use error::{Error, ErrorKind, Result};
+pub use formatter::{CloserPolicy, FormatOptions, Formatter};
+pub use gallery::GalleryFormat;
+pub use grammar::GrammarFormat;
+pub use hooks::{AssetHook, MarkdownHook};
+pub use manifest::{AssetEntry, AssetManifest, FilenameScheme, PrimitiveInfo, VerificationResult};
+pub use numbers::NumberFormat;
+pub use options::{Clock, LimitsConfig, ParserOptions};
 pub use parser::{ProcessedMarkdown, TemplateParser};
-pub use primitive::Primitive;
+pub use primitive::{BarSegment, GroupLayout, Primitive, StepState};
 pub use registry::{EvalContext, Frame, Registry, ResolvedRenderable};
+pub use render::{render_component, RenderOptions};
 pub use renderer::plaintext::PlainTextBackend;
-pub use renderer::{RenderedAsset, Renderer};
+pub use renderer::{DisplaySize, ImageDimensions, InlineMode, RenderedAsset, Renderer};
+pub use schema::SchemaFormat;
 pub use shields::ShieldStyle as ShieldsShieldStyle;
 pub use shields::ShieldsRenderer;
+pub use stats::{
+    ParseObserver, Profiler, RenderEvent, StatsCollector, TemplateEvent, TemplateStats,
+};
 pub use styles::{Style, StyleCategory, StyleSupport, StylesData};
 pub use targets::{
-    available_targets, default_target, detect_target_from_path, get_target, BackendType,
-    GitHubTarget, LocalDocsTarget, NpmTarget, Target,
+    available_targets, default_target, detect_target_from_path, get_target, register_target,
+    BackendType, ConfluenceTarget, CratesIoTarget, DocsFlavor, DocsSiteTarget, GitHubTarget,
+    JiraTarget, LocalDocsTarget, NpmTarget, Target,
 };
+pub use vfs::{DiskFs, MemoryFs, Vfs};
 
 #[cfg(test)]
 mod tests {
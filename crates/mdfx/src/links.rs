@@ -0,0 +1,641 @@
+//! Link and reference validation
+//!
+//! [`extract_links`] finds the links, images, reference definitions, and HTML
+//! `href`/`src` attributes in rendered markdown, skipping code. A
+//! [`LinkChecker`] resolves the local ones: `#anchors` against the
+//! document's headings, relative paths (including generated assets) against
+//! the filesystem, and `guide.md#anchor` against the headings of the linked
+//! file. External URLs are extracted but not requested; `mdfx lint links
+//! --external` checks them over HTTP.
+//!
+//! ```
+//! use mdfx::links::{extract_links, LinkChecker, LinkKind};
+//! use std::path::Path;
+//!
+//! let markdown = "# Install\n\nSee [install](#install) and [usage](#usage).\n";
+//!
+//! let links = extract_links(markdown);
+//! assert_eq!(links[0].kind, LinkKind::Anchor);
+//!
+//! let problems = LinkChecker::new(".").check(Path::new("README.md"), markdown);
+//! assert_eq!(problems.len(), 1);
+//! assert_eq!(problems[0].link.target, "#usage");
+//! ```
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+lazy_static! {
+    /// `](target)`, `](<target>)`, with an optional title or ` =WxH` size
+    static ref INLINE_TARGET: Regex = Regex::new(
+        r#"\]\(\s*(<[^>]*>|[^)\s]+)(?:\s+(?:"[^"]*"|'[^']*'|=\d*x\d*))?\s*\)"#
+    )
+    .unwrap();
+    static ref REFERENCE_DEF: Regex = Regex::new(r"^ {0,3}\[[^\]]+\]:\s*(<[^>]*>|\S+)").unwrap();
+    static ref AUTOLINK: Regex = Regex::new(r"<(https?://[^>\s]+)>").unwrap();
+    static ref HTML_URL_ATTR: Regex =
+        Regex::new(r#"(?i)\b(href|src)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    static ref HTML_ID_ATTR: Regex =
+        Regex::new(r#"(?i)\b(?:id|name)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    static ref ATX_HEADING: Regex = Regex::new(r"^ {0,3}#{1,6}(?:[ \t]+(.*?))?(?:[ \t]+#+)?[ \t]*$").unwrap();
+    static ref SETEXT_UNDERLINE: Regex = Regex::new(r"^ {0,3}(?:=+|-+)[ \t]*$").unwrap();
+    static ref LINK_TEXT: Regex = Regex::new(r"!?\[([^\]]*)\]\([^)]*\)").unwrap();
+    static ref HTML_TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+}
+
+/// Image extensions; relative links to these are assets even outside `![]()`
+const ASSET_EXTENSIONS: &[&str] = &["svg", "png", "jpg", "jpeg", "gif", "webp", "avif", "ico"];
+
+/// What a link points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    /// `#section` in the same document
+    Anchor,
+    /// Relative path to a file or directory, optionally with `#section`
+    File,
+    /// Relative path to an image, such as a generated SVG
+    Asset,
+    /// `http://` or `https://` URL
+    External,
+}
+
+/// A link found in markdown
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Link {
+    /// Link target as written
+    pub target: String,
+    /// What the target points at
+    pub kind: LinkKind,
+    /// Whether the link displays an image (`![](...)` or `src=`)
+    pub image: bool,
+    /// 1-based line
+    pub line: usize,
+    /// 1-based column (in characters) of the target
+    pub column: usize,
+    /// Byte range of the target in the document
+    pub range: Range<usize>,
+}
+
+/// A local link that does not resolve
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LinkProblem {
+    /// The broken link
+    pub link: Link,
+    /// What is wrong with it
+    pub message: String,
+}
+
+/// Classify a link target, or `None` for targets that can't be checked
+/// (`mailto:`, `data:`, and other schemes)
+fn classify(target: &str, image: bool) -> Option<LinkKind> {
+    if target.is_empty() {
+        return None;
+    }
+    if target.starts_with('#') {
+        return Some(LinkKind::Anchor);
+    }
+    let lower = target.to_ascii_lowercase();
+    if lower.starts_with("http://") || lower.starts_with("https://") || target.starts_with("//") {
+        return Some(LinkKind::External);
+    }
+    if let Some(colon) = target.find(':') {
+        let scheme = &target[..colon];
+        if !scheme.is_empty()
+            && !scheme.contains('/')
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        {
+            return None;
+        }
+    }
+    let path = split_fragment(target).0;
+    let is_asset = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| ASSET_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()));
+    Some(if image || is_asset {
+        LinkKind::Asset
+    } else {
+        LinkKind::File
+    })
+}
+
+/// Lowercase host of an `http(s)://` or `//` URL
+pub(crate) fn url_host(url: &str) -> Option<String> {
+    let lower = url.to_ascii_lowercase();
+    let rest = ["https://", "http://", "//"]
+        .iter()
+        .find_map(|scheme| lower.strip_prefix(scheme))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+/// Split `path?query#fragment` into the path and the fragment
+fn split_fragment(target: &str) -> (&str, Option<&str>) {
+    let (rest, fragment) = match target.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (target, None),
+    };
+    (rest.split('?').next().unwrap_or(rest), fragment)
+}
+
+/// Decode `%XX` escapes, leaving invalid ones as written
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        if let Some(byte) = escaped {
+            out.push(byte);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// A line outside fenced code blocks
+pub(crate) struct ProseLine<'a> {
+    /// 1-based line number
+    pub number: usize,
+    /// Byte offset of the line in the document
+    pub offset: usize,
+    /// The line as written
+    pub text: &'a str,
+    /// The line with inline code replaced by spaces
+    ///
+    /// Code spans are blanked byte for byte, so offsets into `prose` are
+    /// offsets into `text`.
+    pub prose: String,
+}
+
+impl ProseLine<'_> {
+    /// 1-based column (in characters) of a byte offset in the line
+    pub fn column(&self, offset: usize) -> usize {
+        self.text[..offset].chars().count() + 1
+    }
+}
+
+/// Lines outside fenced code blocks
+pub(crate) fn prose_lines(markdown: &str) -> Vec<ProseLine<'_>> {
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut offset = 0;
+
+    for (idx, raw) in markdown.split_inclusive('\n').enumerate() {
+        let line_offset = offset;
+        offset += raw.len();
+        let text = raw.trim_end_matches('\n').trim_end_matches('\r');
+        let trimmed = text.trim_start();
+        let marker = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m));
+        match (fence, marker) {
+            (None, Some(m)) => fence = Some(m),
+            (Some(open), Some(m)) if open == m => fence = None,
+            (None, None) => lines.push(ProseLine {
+                number: idx + 1,
+                offset: line_offset,
+                text,
+                prose: blank_code_spans(text),
+            }),
+            _ => {}
+        }
+    }
+    lines
+}
+
+/// Replace backticks and the code between them with spaces
+fn blank_code_spans(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_code = false;
+    for c in line.chars() {
+        if c == '`' {
+            in_code = !in_code;
+            out.push(' ');
+        } else if in_code {
+            out.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Whether the `]` at byte `close` ends the text of an image (`![...]`)
+fn closes_image_text(line: &str, close: usize) -> bool {
+    let bytes = line.as_bytes();
+    let mut depth = 0;
+    for i in (0..close).rev() {
+        match bytes[i] {
+            b']' => depth += 1,
+            b'[' if depth == 0 => return i > 0 && bytes[i - 1] == b'!',
+            b'[' => depth -= 1,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Find every link in `markdown`, in document order
+///
+/// Links inside fenced code blocks and inline code are ignored, as are
+/// schemes other than `http` and `https`.
+pub fn extract_links(markdown: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+
+    for line in prose_lines(markdown) {
+        let prose = line.prose.as_str();
+        // (byte range in the line, is an image)
+        let mut found: Vec<(Range<usize>, bool)> = Vec::new();
+        let unbracket = |m: regex::Match| {
+            if m.as_str().starts_with('<') && m.as_str().ends_with('>') && m.len() > 1 {
+                m.start() + 1..m.end() - 1
+            } else {
+                m.range()
+            }
+        };
+
+        for caps in INLINE_TARGET.captures_iter(prose) {
+            let image = closes_image_text(prose, caps.get(0).unwrap().start());
+            found.push((unbracket(caps.get(1).unwrap()), image));
+        }
+        if let Some(caps) = REFERENCE_DEF.captures(prose) {
+            found.push((unbracket(caps.get(1).unwrap()), false));
+        }
+        for caps in AUTOLINK.captures_iter(prose) {
+            let m = caps.get(1).unwrap();
+            // `](<https://...>)` was already found as an inline target
+            if !prose[..m.start() - 1].ends_with('(') {
+                found.push((m.range(), false));
+            }
+        }
+        for caps in HTML_URL_ATTR.captures_iter(prose) {
+            let m = caps.get(2).or_else(|| caps.get(3)).unwrap();
+            found.push((m.range(), caps[1].eq_ignore_ascii_case("src")));
+        }
+
+        found.sort_by_key(|(range, _)| range.start);
+        for (range, image) in found {
+            let raw = &prose[range.clone()];
+            let start = range.start + (raw.len() - raw.trim_start().len());
+            let target = raw.trim();
+            if let Some(kind) = classify(target, image) {
+                links.push(Link {
+                    target: target.to_string(),
+                    kind,
+                    image,
+                    line: line.number,
+                    column: line.column(start),
+                    range: line.offset + start..line.offset + start + target.len(),
+                });
+            }
+        }
+    }
+
+    links
+}
+
+/// Replace link targets, keeping everything else as written
+///
+/// `rewrite` receives each link from [`extract_links`] and returns its new
+/// target, or `None` to leave it alone.
+///
+/// ```
+/// use mdfx::links::{rewrite_links, LinkKind};
+///
+/// let out = rewrite_links("[guide](docs/guide.md) [top](#top)", |link| {
+///     (link.kind == LinkKind::File).then(|| format!("https://example.com/{}", link.target))
+/// });
+/// assert_eq!(out, "[guide](https://example.com/docs/guide.md) [top](#top)");
+/// ```
+pub fn rewrite_links(markdown: &str, mut rewrite: impl FnMut(&Link) -> Option<String>) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let mut last = 0;
+    for link in extract_links(markdown) {
+        if let Some(target) = rewrite(&link) {
+            out.push_str(&markdown[last..link.range.start]);
+            out.push_str(&target);
+            last = link.range.end;
+        }
+    }
+    out.push_str(&markdown[last..]);
+    out
+}
+
+/// GitHub-style anchor for a heading
+///
+/// Link syntax and HTML tags are reduced to their text, the result is
+/// lowercased, punctuation is dropped, and spaces become hyphens.
+///
+/// ```
+/// use mdfx::links::slugify;
+///
+/// assert_eq!(slugify("Getting Started!"), "getting-started");
+/// assert_eq!(slugify("The `Vfs` trait"), "the-vfs-trait");
+/// ```
+pub fn slugify(heading: &str) -> String {
+    let text = LINK_TEXT.replace_all(heading, "$1");
+    let text = HTML_TAG.replace_all(&text, "");
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Text of an ATX heading line (`## Title`)
+pub(crate) fn atx_heading(line: &str) -> Option<&str> {
+    ATX_HEADING
+        .captures(line)
+        .map(|caps| caps.get(1).map_or("", |m| m.as_str()))
+}
+
+/// Anchors defined by `markdown`
+///
+/// Includes a slug for every ATX (`## Title`) and setext heading, numbered
+/// `-1`, `-2`, ... when a slug repeats, plus HTML `id` and `name` attributes.
+pub fn heading_anchors(markdown: &str) -> HashSet<String> {
+    let mut anchors = HashSet::new();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut add_heading = |text: &str, anchors: &mut HashSet<String>| {
+        let slug = slugify(text);
+        let count = counts.entry(slug.clone()).or_insert(0);
+        let anchor = match *count {
+            0 => slug,
+            n => format!("{}-{}", slug, n),
+        };
+        *count += 1;
+        anchors.insert(anchor);
+    };
+
+    let mut previous: Option<(usize, &str)> = None;
+    for line in prose_lines(markdown) {
+        if let Some(text) = atx_heading(line.text) {
+            add_heading(text, &mut anchors);
+            previous = None;
+        } else if SETEXT_UNDERLINE.is_match(line.text)
+            && previous.is_some_and(|(prev_no, text)| {
+                prev_no + 1 == line.number && !text.trim().is_empty()
+            })
+        {
+            add_heading(previous.unwrap().1, &mut anchors);
+            previous = None;
+        } else {
+            previous = Some((line.number, line.text));
+        }
+
+        for caps in HTML_ID_ATTR.captures_iter(&line.prose) {
+            let m = caps.get(1).or_else(|| caps.get(2)).unwrap();
+            anchors.insert(m.as_str().to_string());
+        }
+    }
+
+    anchors
+}
+
+/// Resolves local links against headings and the filesystem
+///
+/// Anchors of linked markdown files are read once and cached, so one checker
+/// can be reused across a set of documents.
+#[derive(Debug)]
+pub struct LinkChecker {
+    root: PathBuf,
+    anchors: HashMap<PathBuf, Option<HashSet<String>>>,
+}
+
+impl LinkChecker {
+    /// Create a checker; links starting with `/` resolve against `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            anchors: HashMap::new(),
+        }
+    }
+
+    /// Check the local links in `markdown`, which was read from `path`
+    ///
+    /// Relative links resolve against the directory containing `path`.
+    /// External links are not checked.
+    pub fn check(&mut self, path: &Path, markdown: &str) -> Vec<LinkProblem> {
+        let own_anchors = heading_anchors(markdown);
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let mut problems = Vec::new();
+
+        for link in extract_links(markdown) {
+            let message = match link.kind {
+                LinkKind::External => None,
+                LinkKind::Anchor => {
+                    let anchor = percent_decode(&link.target[1..]);
+                    (!anchor.is_empty() && !own_anchors.contains(&anchor))
+                        .then(|| format!("no heading for anchor '{}'", link.target))
+                }
+                LinkKind::File | LinkKind::Asset => self.check_path(dir, &link),
+            };
+            if let Some(message) = message {
+                problems.push(LinkProblem { link, message });
+            }
+        }
+
+        problems
+    }
+
+    fn check_path(&mut self, dir: &Path, link: &Link) -> Option<String> {
+        let (path, fragment) = split_fragment(&link.target);
+        let decoded = percent_decode(path);
+        let resolved = match decoded.strip_prefix('/') {
+            Some(rooted) => self.root.join(rooted),
+            None if decoded.is_empty() => return None,
+            None => dir.join(&decoded),
+        };
+
+        if !resolved.exists() {
+            let what = match link.kind {
+                LinkKind::Asset => "asset",
+                _ => "file",
+            };
+            return Some(format!("{} not found: {}", what, path));
+        }
+
+        let anchor = percent_decode(fragment.filter(|f| !f.is_empty())?);
+        let is_markdown = resolved
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| matches!(e.to_ascii_lowercase().as_str(), "md" | "markdown"));
+        if !is_markdown {
+            return None;
+        }
+        let anchors = self
+            .anchors
+            .entry(resolved)
+            .or_insert_with_key(|file| fs::read_to_string(file).ok().map(|s| heading_anchors(&s)));
+        match anchors {
+            Some(anchors) if !anchors.contains(&anchor) => {
+                Some(format!("no heading for anchor '#{}' in {}", anchor, path))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn targets(markdown: &str) -> Vec<(String, LinkKind)> {
+        extract_links(markdown)
+            .into_iter()
+            .map(|l| (l.target, l.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_links() {
+        let markdown = "\
+[![ci](assets/mdfx/badge_1.svg)](https://ci.example.com) [docs](docs/guide.md#setup \"Guide\")
+<img src=\"assets/logo.png\" width=\"20\"> <a href='#usage'>Usage</a>
+![](assets/mdfx/swatch_2.svg =20x20) <https://example.com> [mail](mailto:me@example.com)
+
+[ref]: ./CHANGELOG.md
+";
+        assert_eq!(
+            targets(markdown),
+            vec![
+                ("assets/mdfx/badge_1.svg".to_string(), LinkKind::Asset),
+                ("https://ci.example.com".to_string(), LinkKind::External),
+                ("docs/guide.md#setup".to_string(), LinkKind::File),
+                ("assets/logo.png".to_string(), LinkKind::Asset),
+                ("#usage".to_string(), LinkKind::Anchor),
+                ("assets/mdfx/swatch_2.svg".to_string(), LinkKind::Asset),
+                ("https://example.com".to_string(), LinkKind::External),
+                ("./CHANGELOG.md".to_string(), LinkKind::File),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_images_and_ranges() {
+        let markdown = "`𝐜𝐨𝐝𝐞` [![a](x.png)](docs/) <img src=\"b\"> [c](<my file.md>)";
+        let links = extract_links(markdown);
+
+        let images: Vec<_> = links.iter().map(|l| (l.target.as_str(), l.image)).collect();
+        assert_eq!(
+            images,
+            vec![
+                ("x.png", true),
+                ("docs/", false),
+                ("b", true),
+                ("my file.md", false)
+            ]
+        );
+        assert_eq!(links[2].kind, LinkKind::Asset);
+        for link in &links {
+            assert_eq!(&markdown[link.range.clone()], link.target);
+        }
+
+        let out = rewrite_links(markdown, |l| l.image.then(|| "IMG".to_string()));
+        assert_eq!(
+            out,
+            "`𝐜𝐨𝐝𝐞` [![a](IMG)](docs/) <img src=\"IMG\"> [c](<my file.md>)"
+        );
+    }
+
+    #[test]
+    fn test_code_is_skipped() {
+        let markdown = "```md\n[a](#nowhere)\n```\n`[b](missing.md)` [c](#real)\n";
+        let links = extract_links(markdown);
+        assert_eq!(links.len(), 1);
+        assert_eq!((links[0].line, links[0].column), (4, 23));
+    }
+
+    #[rstest]
+    #[case("https://img.shields.io/badge/x", Some("img.shields.io"))]
+    #[case("HTTP://User@Example.COM:8080/a", Some("example.com"))]
+    #[case("//cdn.example.com/a.svg", Some("cdn.example.com"))]
+    #[case("assets/a.svg", None)]
+    fn test_url_host(#[case] url: &str, #[case] expected: Option<&str>) {
+        assert_eq!(url_host(url).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("Getting Started", "getting-started")]
+    #[case("API: `Vfs` & friends", "api-vfs--friends")]
+    #[case("[Link](https://x.com) here", "link-here")]
+    #[case("<img src=\"a.svg\"> Badges", "badges")]
+    #[case("snake_case-name", "snake_case-name")]
+    #[case("Café Ünïcode", "café-ünïcode")]
+    fn test_slugify(#[case] heading: &str, #[case] expected: &str) {
+        assert_eq!(slugify(heading), expected);
+    }
+
+    #[test]
+    fn test_heading_anchors() {
+        let markdown = "\
+# Usage
+## Usage ##
+### The `Vfs` trait
+Setext
+------
+<a id=\"custom\"></a>
+```
+# Not a heading
+```
+";
+        let anchors = heading_anchors(markdown);
+        let mut sorted: Vec<_> = anchors.iter().map(String::as_str).collect();
+        sorted.sort();
+        assert_eq!(
+            sorted,
+            vec!["custom", "setext", "the-vfs-trait", "usage", "usage-1"]
+        );
+    }
+
+    #[test]
+    fn test_check_files_and_anchors() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        fs::create_dir_all(dir.path().join("assets")).unwrap();
+        fs::write(dir.path().join("docs/guide.md"), "# Setup\n").unwrap();
+        fs::write(dir.path().join("assets/my badge.svg"), "<svg/>").unwrap();
+        let markdown = "\
+# Intro
+[ok](#intro) [bad](#outro)
+[guide](docs/guide.md#setup) [guide](docs/guide.md#missing) [gone](docs/old.md)
+![](assets/my%20badge.svg) ![](assets/missing.svg) [root](/docs/guide.md)
+";
+
+        let mut checker = LinkChecker::new(dir.path());
+        let problems = checker.check(&dir.path().join("README.md"), markdown);
+        let messages: Vec<_> = problems.iter().map(|p| p.message.as_str()).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "no heading for anchor '#outro'",
+                "no heading for anchor '#missing' in docs/guide.md",
+                "file not found: docs/old.md",
+                "asset not found: assets/missing.svg",
+            ]
+        );
+        assert_eq!((problems[0].link.line, problems[0].link.column), (2, 20));
+    }
+}
@@ -5,10 +5,14 @@
 //! - Atomic manifest writes (prevents corruption)
 //! - Incremental manifest updates (merge capability)
 //! - Provenance tracking (source files, version, timestamp)
+//! - Reproducible output (no timestamps, sorted entries)
 //! - Content-addressed filenames (stable across Rust versions)
 
 use crate::error::Result;
 use crate::primitive::Primitive;
+use crate::renderer::svg::svg_dimensions;
+use crate::renderer::RenderedAsset;
+use crate::vfs::Vfs;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fs;
@@ -17,6 +21,9 @@ use std::path::Path;
 /// Current manifest schema version
 pub const MANIFEST_VERSION: &str = "1.1.0";
 
+/// Timestamp recorded by [`AssetManifest::make_reproducible`]: the Unix epoch
+pub const REPRODUCIBLE_TIMESTAMP: &str = "1970-01-01T00:00:00+00:00";
+
 /// Manifest entry for a generated asset
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetEntry {
@@ -31,6 +38,12 @@ pub struct AssetEntry {
     pub primitive: PrimitiveInfo,
     /// File size in bytes
     pub size_bytes: usize,
+    /// Image width in pixels, when the asset is an SVG with a fixed size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// Image height in pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
 
     // Provenance tracking fields (v1.1.0)
     /// Source template files that reference this asset
@@ -42,6 +55,10 @@ pub struct AssetEntry {
     /// mdfx version that generated this asset
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub generator_version: Option<String>,
+    /// Namespace of the run that generated this asset, also the start of
+    /// its filename
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
 }
 
 impl AssetEntry {
@@ -60,6 +77,7 @@ impl AssetEntry {
         let hash = format!("{:x}", hasher.finalize());
 
         let source_files = source_file.into_iter().collect();
+        let size = std::str::from_utf8(bytes).ok().and_then(svg_dimensions);
 
         Self {
             path,
@@ -67,9 +85,12 @@ impl AssetEntry {
             asset_type,
             primitive: PrimitiveInfo::from(primitive),
             size_bytes: bytes.len(),
+            width: size.map(|(w, _)| w),
+            height: size.map(|(_, h)| h),
             source_files,
             generated_at: Some(chrono::Utc::now().to_rfc3339()),
             generator_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            namespace: None,
         }
     }
 }
@@ -127,6 +148,21 @@ pub enum PrimitiveInfo {
         width: u32,
         height: u32,
     },
+    StatCard {
+        label: String,
+        value: String,
+    },
+    Steps {
+        items: Vec<String>,
+        current: Option<usize>,
+    },
+    StackedBar {
+        segments: Vec<(String, u32)>,
+    },
+    Group {
+        items: Vec<PrimitiveInfo>,
+        columns: Option<usize>,
+    },
 }
 
 impl From<&Primitive> for PrimitiveInfo {
@@ -209,6 +245,24 @@ impl From<&Primitive> for PrimitiveInfo {
                 width: *width,
                 height: *height,
             },
+            Primitive::StatCard { label, value, .. } => PrimitiveInfo::StatCard {
+                label: label.clone(),
+                value: value.clone(),
+            },
+            Primitive::Steps { items, current, .. } => PrimitiveInfo::Steps {
+                items: items.clone(),
+                current: *current,
+            },
+            Primitive::StackedBar { segments, .. } => PrimitiveInfo::StackedBar {
+                segments: segments
+                    .iter()
+                    .map(|s| (s.label.clone(), s.value))
+                    .collect(),
+            },
+            Primitive::Group { items, layout } => PrimitiveInfo::Group {
+                items: items.iter().map(PrimitiveInfo::from).collect(),
+                columns: layout.columns,
+            },
         }
     }
 }
@@ -277,6 +331,26 @@ impl AssetManifest {
         self.total_assets = self.assets.len();
     }
 
+    /// Add a file-based asset returned by a renderer
+    ///
+    /// The asset type is derived from the primitive. Inline assets are ignored.
+    pub fn add_rendered(&mut self, asset: &RenderedAsset) {
+        if let RenderedAsset::File {
+            relative_path,
+            bytes,
+            primitive,
+            ..
+        } = asset
+        {
+            self.add_asset(
+                relative_path.clone(),
+                bytes,
+                primitive,
+                primitive.kind().to_string(),
+            );
+        }
+    }
+
     /// Add a pre-built asset entry
     pub fn add_entry(&mut self, entry: AssetEntry) {
         self.total_size_bytes += entry.size_bytes;
@@ -294,7 +368,7 @@ impl AssetManifest {
     /// * `new_paths` - Set of paths that should exist after merge (for cleanup)
     pub fn merge(&mut self, new_assets: Vec<AssetEntry>, new_paths: Option<HashSet<String>>) {
         // Build a map of new assets by path
-        let new_by_path: std::collections::HashMap<_, _> = new_assets
+        let new_by_path: std::collections::BTreeMap<_, _> = new_assets
             .into_iter()
             .map(|a| (a.path.clone(), a))
             .collect();
@@ -330,6 +404,56 @@ impl AssetManifest {
         self.created_at = chrono::Utc::now().to_rfc3339();
     }
 
+    /// Record every asset as belonging to `namespace`
+    pub fn set_namespace(&mut self, namespace: &str) {
+        for asset in &mut self.assets {
+            asset.namespace = Some(namespace.to_string());
+        }
+    }
+
+    /// Add the assets of `previous` that belong to other namespaces than
+    /// `namespace`
+    ///
+    /// Runs sharing an assets directory each replace only their own entries,
+    /// so the manifest lists the assets of every namespace. Assets without a
+    /// namespace form their own.
+    pub fn keep_other_namespaces(&mut self, previous: AssetManifest, namespace: Option<&str>) {
+        for entry in previous.assets {
+            if entry.namespace.as_deref() != namespace && self.get_asset(&entry.path).is_none() {
+                self.add_entry(entry);
+            }
+        }
+    }
+
+    /// Drop the assets outside `namespace`
+    pub fn retain_namespace(&mut self, namespace: &str) {
+        self.assets
+            .retain(|a| a.namespace.as_deref() == Some(namespace));
+        self.total_assets = self.assets.len();
+        self.total_size_bytes = self.assets.iter().map(|a| a.size_bytes).sum();
+    }
+
+    /// Remove everything that differs between two runs over the same input
+    ///
+    /// Timestamps become [`REPRODUCIBLE_TIMESTAMP`], generator versions lose
+    /// any `+build` metadata, and assets are sorted by path, so identical
+    /// inputs give a byte-identical manifest on every machine.
+    pub fn make_reproducible(&mut self) {
+        let version = env!("CARGO_PKG_VERSION")
+            .split('+')
+            .next()
+            .unwrap_or_default();
+
+        self.created_at = REPRODUCIBLE_TIMESTAMP.to_string();
+        self.generator_version = Some(version.to_string());
+        for asset in &mut self.assets {
+            asset.generated_at = Some(REPRODUCIBLE_TIMESTAMP.to_string());
+            asset.generator_version = Some(version.to_string());
+            asset.source_files.sort();
+        }
+        self.assets.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
     /// Write manifest to file (standard write)
     pub fn write(&self, manifest_path: &Path) -> Result<()> {
         let json = serde_json::to_string_pretty(self)?;
@@ -337,6 +461,12 @@ impl AssetManifest {
         Ok(())
     }
 
+    /// Write manifest through a [`Vfs`], e.g. into a [`MemoryFs`](crate::vfs::MemoryFs)
+    pub fn write_to(&self, fs: &mut dyn Vfs, manifest_path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs.write(manifest_path, json.as_bytes())
+    }
+
     /// Write manifest atomically (prevents corruption on crash/interrupt)
     ///
     /// This writes to a temporary file first, then atomically renames it.
@@ -514,15 +644,72 @@ pub enum VerificationResult {
 ///
 /// Uses first 16 characters of SHA-256 hash for stable, unique filenames.
 /// This is deterministic across Rust versions (unlike DefaultHasher).
+/// See [`FilenameScheme`] for longer hashes or readable names.
 pub fn content_addressed_filename(bytes: &[u8], type_prefix: &str) -> String {
-    use sha2::{Digest, Sha256};
+    FilenameScheme::default().filename(bytes, type_prefix, None)
+}
 
-    let mut hasher = Sha256::new();
-    hasher.update(bytes);
-    let hash = format!("{:x}", hasher.finalize());
+/// How content-addressed asset filenames are built
+///
+/// Configurable under `"filenames"` in `.mdfx.json`:
+///
+/// ```json
+/// { "filenames": { "hash_length": 24, "slug": true } }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilenameScheme {
+    /// Hex characters of the SHA-256 hash to keep, from 8 to 64
+    pub hash_length: usize,
+    /// Insert a readable name between type and hash (`tech_rust_<hash>.svg`)
+    pub slug: bool,
+}
 
-    // Use first 16 chars of SHA-256 (64 bits of entropy, sufficient for dedup)
-    format!("{}_{}.svg", type_prefix, &hash[..16])
+impl Default for FilenameScheme {
+    fn default() -> Self {
+        Self {
+            hash_length: 16,
+            slug: false,
+        }
+    }
+}
+
+impl FilenameScheme {
+    /// Shortest hash allowed (32 bits)
+    pub const MIN_HASH_LENGTH: usize = 8;
+    /// Longest slug kept in a filename
+    const MAX_SLUG_LENGTH: usize = 32;
+
+    /// Filename for `bytes`, e.g. `tech_1a2b3c4d5e6f7a8b.svg`
+    ///
+    /// `slug` is used only when the scheme asks for one; it is lowercased
+    /// and reduced to letters, digits, and `-`. Hash lengths outside 8-64
+    /// are clamped.
+    pub fn filename(&self, bytes: &[u8], type_prefix: &str, slug: Option<&str>) -> String {
+        use sha2::{Digest, Sha256};
+
+        let hash = format!("{:x}", Sha256::digest(bytes));
+        let hash = &hash[..self.hash_length.clamp(Self::MIN_HASH_LENGTH, hash.len())];
+
+        match slug.filter(|_| self.slug).map(slugify) {
+            Some(slug) if !slug.is_empty() => format!("{}_{}_{}.svg", type_prefix, slug, hash),
+            _ => format!("{}_{}.svg", type_prefix, hash),
+        }
+    }
+}
+
+/// Lowercase `text`, replacing runs of other characters with `-`
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(FilenameScheme::MAX_SLUG_LENGTH);
+    slug.trim_end_matches('-').to_string()
 }
 
 #[cfg(test)]
@@ -569,6 +756,44 @@ mod tests {
         assert_eq!(manifest.assets[0].asset_type, "swatch");
         assert_eq!(manifest.assets[0].size_bytes, svg_bytes.len());
         assert!(!manifest.assets[0].sha256.is_empty());
+        assert_eq!(manifest.assets[0].width, None);
+    }
+
+    #[test]
+    fn test_asset_dimensions_recorded() {
+        let mut manifest = AssetManifest::new("svg", "assets");
+        manifest.add_asset(
+            "assets/a.svg".to_string(),
+            br#"<svg xmlns="http://www.w3.org/2000/svg" width="80" height="20"></svg>"#,
+            &Primitive::simple_swatch("FF0000", "flat"),
+            "swatch".to_string(),
+        );
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["assets"][0]["width"], 80);
+        assert_eq!(json["assets"][0]["height"], 20);
+    }
+
+    #[test]
+    fn test_add_rendered_and_write_to_vfs() {
+        let mut manifest = AssetManifest::new("svg", "assets");
+        manifest.add_rendered(&RenderedAsset::File {
+            relative_path: "assets/gauge.svg".to_string(),
+            bytes: b"<svg/>".to_vec(),
+            markdown_ref: "![](assets/gauge.svg)".to_string(),
+            primitive: Box::new(Primitive::simple_gauge(50, "slate", "accent")),
+        });
+        manifest.add_rendered(&RenderedAsset::InlineMarkdown("![](x)".to_string()));
+
+        let mut fs = crate::vfs::MemoryFs::new();
+        manifest
+            .write_to(&mut fs, Path::new("assets/manifest.json"))
+            .unwrap();
+
+        let written: AssetManifest =
+            serde_json::from_slice(fs.get("assets/manifest.json").unwrap()).unwrap();
+        assert_eq!(written.total_assets, 1);
+        assert_eq!(written.assets[0].asset_type, "gauge");
     }
 
     #[test]
@@ -714,6 +939,73 @@ mod tests {
         assert_eq!(manifest.assets[0].path, "new.svg");
     }
 
+    #[test]
+    fn test_namespaces() {
+        let primitive = Primitive::simple_swatch("FF0000", "flat");
+        let run = |paths: &[&str], namespace: Option<&str>| {
+            let mut manifest = AssetManifest::new("svg", "assets");
+            for path in paths {
+                manifest.add_asset(path.to_string(), b"<svg/>", &primitive, "swatch".into());
+            }
+            if let Some(namespace) = namespace {
+                manifest.set_namespace(namespace);
+            }
+            manifest
+        };
+
+        let mut previous = run(&["api-a.svg", "api-b.svg"], Some("api-"));
+        previous.keep_other_namespaces(run(&["guide-a.svg"], Some("guide-")), Some("api-"));
+        previous.keep_other_namespaces(run(&["plain.svg"], None), Some("api-"));
+
+        // A new api- run replaces api-b.svg but keeps the other namespaces
+        let mut manifest = run(&["api-a.svg", "api-c.svg"], Some("api-"));
+        manifest.keep_other_namespaces(previous, Some("api-"));
+        let mut paths = manifest.asset_paths();
+        paths.sort();
+        assert_eq!(
+            paths,
+            ["api-a.svg", "api-c.svg", "guide-a.svg", "plain.svg"]
+        );
+        assert_eq!(manifest.total_assets, 4);
+        assert_eq!(
+            manifest
+                .get_asset("guide-a.svg")
+                .unwrap()
+                .namespace
+                .as_deref(),
+            Some("guide-")
+        );
+
+        manifest.retain_namespace("guide-");
+        assert_eq!(manifest.asset_paths(), ["guide-a.svg"]);
+        assert_eq!(manifest.total_assets, 1);
+        assert!(serde_json::to_string(&manifest)
+            .unwrap()
+            .contains(r#""namespace":"guide-""#));
+    }
+
+    #[test]
+    fn test_make_reproducible() {
+        let build = |order: &[&str]| {
+            let mut manifest = AssetManifest::new("svg", "assets");
+            for color in order {
+                manifest.add_asset(
+                    format!("assets/swatch_{}.svg", color),
+                    color.as_bytes(),
+                    &Primitive::simple_swatch(*color, "flat"),
+                    "swatch".to_string(),
+                );
+            }
+            manifest.make_reproducible();
+            serde_json::to_string_pretty(&manifest).unwrap()
+        };
+
+        let json = build(&["FF0000", "00FF00"]);
+        assert_eq!(json, build(&["00FF00", "FF0000"]));
+        assert!(!json.contains(&chrono::Utc::now().format("%Y-%m-%d").to_string()));
+        assert!(json.contains(REPRODUCIBLE_TIMESTAMP));
+        assert!(json.find("swatch_00FF00").unwrap() < json.find("swatch_FF0000").unwrap());
+    }
     #[test]
     fn test_verify_valid_asset() {
         let temp_dir = TempDir::new().unwrap();
@@ -823,6 +1115,36 @@ mod tests {
         assert_ne!(name1, name2);
     }
 
+    #[rstest]
+    #[case(FilenameScheme { hash_length: 24, slug: false }, Some("rust"), "tech_", 24)]
+    #[case(FilenameScheme { hash_length: 8, slug: true }, Some("rust"), "tech_rust_", 8)]
+    #[case(FilenameScheme { hash_length: 2, slug: true }, Some("Apache 2.0!"), "tech_apache-2-0_", 8)]
+    #[case(FilenameScheme { hash_length: 100, slug: true }, Some("--"), "tech_", 64)]
+    #[case(FilenameScheme { hash_length: 16, slug: true }, None, "tech_", 16)]
+    fn test_filename_scheme(
+        #[case] scheme: FilenameScheme,
+        #[case] slug: Option<&str>,
+        #[case] prefix: &str,
+        #[case] hash_length: usize,
+    ) {
+        let name = scheme.filename(b"<svg/>", "tech", slug);
+        let hash = name
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(".svg"))
+            .unwrap_or_else(|| panic!("unexpected filename {}", name));
+        assert_eq!(hash.len(), hash_length);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_default_scheme_matches_content_addressed_filename() {
+        assert_eq!(
+            FilenameScheme::default().filename(b"<svg/>", "swatch", Some("red")),
+            content_addressed_filename(b"<svg/>", "swatch")
+        );
+        assert_eq!(slugify(&"x".repeat(40)).len(), 32);
+    }
+
     #[test]
     fn test_stats() {
         let mut manifest = AssetManifest::new("svg", "assets");
@@ -0,0 +1,190 @@
+//! Number formats for `{{num:format:value/}}` templates
+//!
+//! Unicode has dedicated forms for small numbers: circled numbers up to 50,
+//! superscript and subscript digits, and Roman numerals. [`NumberFormat`]
+//! converts an integer to one of them, and reports when the number is
+//! outside what the format covers so callers can fall back to plain digits.
+
+use std::ops::RangeInclusive;
+
+/// Unicode form to write an integer in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberFormat {
+    /// ⓪ and ① through ㊿
+    Circled,
+    /// Ⅰ through ⅯⅯⅯⅭⅯⅩⅭⅠⅩ
+    Roman,
+    /// ⁻¹²³
+    Superscript,
+    /// ₋₁₂₃
+    Subscript,
+}
+
+/// Superscript digits 0–9
+const SUPERSCRIPT_DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+/// Subscript digits 0–9
+const SUBSCRIPT_DIGITS: [char; 10] = ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'];
+
+/// Roman numeral letters and subtractive pairs, largest first
+const ROMAN: [(i64, &str); 13] = [
+    (1000, "Ⅿ"),
+    (900, "ⅭⅯ"),
+    (500, "Ⅾ"),
+    (400, "ⅭⅮ"),
+    (100, "Ⅽ"),
+    (90, "ⅩⅭ"),
+    (50, "Ⅼ"),
+    (40, "ⅩⅬ"),
+    (10, "Ⅹ"),
+    (9, "ⅠⅩ"),
+    (5, "Ⅴ"),
+    (4, "ⅠⅤ"),
+    (1, "Ⅰ"),
+];
+
+impl NumberFormat {
+    /// Parse a format name (circled, roman, super, sub)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "circled" | "circle" => Some(NumberFormat::Circled),
+            "roman" => Some(NumberFormat::Roman),
+            "super" | "sup" | "superscript" => Some(NumberFormat::Superscript),
+            "sub" | "subscript" => Some(NumberFormat::Subscript),
+            _ => None,
+        }
+    }
+
+    /// Name used in templates
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NumberFormat::Circled => "circled",
+            NumberFormat::Roman => "roman",
+            NumberFormat::Superscript => "super",
+            NumberFormat::Subscript => "sub",
+        }
+    }
+
+    /// Numbers this format can write, `None` if it can write any integer
+    pub fn range(self) -> Option<RangeInclusive<i64>> {
+        match self {
+            NumberFormat::Circled => Some(0..=50),
+            NumberFormat::Roman => Some(1..=3999),
+            NumberFormat::Superscript | NumberFormat::Subscript => None,
+        }
+    }
+
+    /// Write `n` in this format, or `None` if it is outside [`range`](Self::range)
+    ///
+    /// Roman numerals 1–12 use the single-character forms (Ⅻ); larger ones
+    /// are spelled with the numeral letters Ⅰ, Ⅴ, Ⅹ, Ⅼ, Ⅽ, Ⅾ, and Ⅿ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mdfx::NumberFormat;
+    ///
+    /// assert_eq!(NumberFormat::Circled.format(12).as_deref(), Some("⑫"));
+    /// assert_eq!(NumberFormat::Roman.format(2024).as_deref(), Some("ⅯⅯⅩⅩⅠⅤ"));
+    /// assert_eq!(NumberFormat::Superscript.format(-2).as_deref(), Some("⁻²"));
+    /// assert_eq!(NumberFormat::Circled.format(51), None);
+    /// ```
+    pub fn format(self, n: i64) -> Option<String> {
+        if self.range().is_some_and(|range| !range.contains(&n)) {
+            return None;
+        }
+        Some(match self {
+            NumberFormat::Circled => circled(n).to_string(),
+            NumberFormat::Roman => roman(n),
+            NumberFormat::Superscript => digits(n, '⁻', &SUPERSCRIPT_DIGITS),
+            NumberFormat::Subscript => digits(n, '₋', &SUBSCRIPT_DIGITS),
+        })
+    }
+}
+
+/// Circled form of 0–50
+fn circled(n: i64) -> char {
+    let code = match n {
+        0 => 0x24EA,
+        1..=20 => 0x2460 + n - 1,
+        21..=35 => 0x3251 + n - 21,
+        _ => 0x32B1 + n - 36,
+    };
+    char::from_u32(code as u32).unwrap_or('?')
+}
+
+/// Roman numeral for 1–3999
+fn roman(mut n: i64) -> String {
+    if n <= 12 {
+        return char::from_u32(0x2160 + n as u32 - 1)
+            .unwrap_or('?')
+            .to_string();
+    }
+    let mut out = String::new();
+    for (value, numeral) in ROMAN {
+        while n >= value {
+            out.push_str(numeral);
+            n -= value;
+        }
+    }
+    out
+}
+
+/// `n` written with the given digits and minus sign
+fn digits(n: i64, minus: char, digits: &[char; 10]) -> String {
+    let mut out = String::new();
+    if n < 0 {
+        out.push(minus);
+    }
+    out.extend(
+        n.unsigned_abs()
+            .to_string()
+            .bytes()
+            .map(|b| digits[(b - b'0') as usize]),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(NumberFormat::Circled, 0, Some("⓪"))]
+    #[case(NumberFormat::Circled, 1, Some("①"))]
+    #[case(NumberFormat::Circled, 20, Some("⑳"))]
+    #[case(NumberFormat::Circled, 21, Some("㉑"))]
+    #[case(NumberFormat::Circled, 35, Some("㉟"))]
+    #[case(NumberFormat::Circled, 36, Some("㊱"))]
+    #[case(NumberFormat::Circled, 50, Some("㊿"))]
+    #[case(NumberFormat::Circled, 51, None)]
+    #[case(NumberFormat::Circled, -1, None)]
+    #[case(NumberFormat::Roman, 4, Some("Ⅳ"))]
+    #[case(NumberFormat::Roman, 12, Some("Ⅻ"))]
+    #[case(NumberFormat::Roman, 14, Some("ⅩⅠⅤ"))]
+    #[case(NumberFormat::Roman, 1994, Some("ⅯⅭⅯⅩⅭⅠⅤ"))]
+    #[case(NumberFormat::Roman, 3999, Some("ⅯⅯⅯⅭⅯⅩⅭⅠⅩ"))]
+    #[case(NumberFormat::Roman, 0, None)]
+    #[case(NumberFormat::Roman, 4000, None)]
+    #[case(NumberFormat::Superscript, 2, Some("²"))]
+    #[case(NumberFormat::Superscript, -10, Some("⁻¹⁰"))]
+    #[case(NumberFormat::Subscript, 2024, Some("₂₀₂₄"))]
+    #[case(NumberFormat::Subscript, i64::MIN, Some("₋₉₂₂₃₃₇₂₀₃₆₈₅₄₇₇₅₈₀₈"))]
+    fn test_format(#[case] format: NumberFormat, #[case] n: i64, #[case] expected: Option<&str>) {
+        assert_eq!(format.format(n).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case("circled", Some(NumberFormat::Circled))]
+    #[case("sup", Some(NumberFormat::Superscript))]
+    #[case("subscript", Some(NumberFormat::Subscript))]
+    #[case("roman", Some(NumberFormat::Roman))]
+    #[case("Roman", None)]
+    fn test_parse(#[case] name: &str, #[case] expected: Option<NumberFormat>) {
+        assert_eq!(NumberFormat::parse(name), expected);
+        if let Some(format) = expected {
+            assert_eq!(NumberFormat::parse(format.as_str()), Some(format));
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Parser limits and sandboxing
+//!
+//! [`ParserOptions`] controls what a [`TemplateParser`](crate::TemplateParser)
+//! may do while expanding templates. The default is the trusted mode used by
+//! the CLI, which still enforces generous nesting and output limits so a
+//! mistake in a partial fails with an error instead of hanging. Projects can
+//! change those limits under `"limits"` in `.mdfx.json` ([`LimitsConfig`]).
+//!
+//! Services that render markdown submitted by users should switch to
+//! [`ParserOptions::untrusted`], which tightens those limits and turns off
+//! every component that reaches outside the parser.
+//!
+//! ```
+//! use mdfx::{Error, ParserOptions, TemplateParser};
+//!
+//! let mut parser = TemplateParser::new().unwrap();
+//! parser.set_options(ParserOptions::untrusted());
+//! parser.add_partial("loop", "{{partial:loop/}}");
+//!
+//! assert!(matches!(
+//!     parser.process("{{partial:loop/}}"),
+//!     Err(Error::LimitExceeded(_))
+//! ));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Default nesting limit for template expansion
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// Default limit on processed output size (64 MiB)
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 64 * 1024 * 1024;
+
+/// What the parser is allowed to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParserOptions {
+    /// Maximum nesting of template expansions (frames inside frames,
+    /// partials expanding to partials, components expanding to templates)
+    pub max_depth: Option<usize>,
+    /// Maximum size in bytes of the processed output
+    ///
+    /// Checked while templates expand, so partials that multiply their
+    /// content, and `spacing=`/`pad=` counts, fail before the output is
+    /// built. With no limit, padding is still capped at
+    /// [`DEFAULT_MAX_OUTPUT_BYTES`].
+    pub max_output_bytes: Option<usize>,
+    /// Maximum size in bytes of the markdown passed in
+    pub max_input_bytes: Option<usize>,
+    /// Allow components that fetch live data (`{{ui:live:...}}`)
+    pub allow_network: bool,
+    /// Allow components registered with
+    /// [`TemplateParser::register_component`](crate::TemplateParser::register_component)
+    pub allow_plugins: bool,
+    /// Allow components that run local programs: `{{ui:maintenance:auto/}}`
+    /// without `repo=` runs `git log` in the project root
+    pub allow_commands: bool,
+    /// Allow templates that read project files: `{{snippet:...}}` and the
+    /// `bench`, `coverage`, `tests`, `msrv`, `node-engines` and
+    /// `python-requires` components
+    ///
+    /// Files come from
+    /// [`set_project_file`](crate::TemplateParser::set_project_file) or the
+    /// [project root](crate::TemplateParser::set_project_root).
+    pub allow_files: bool,
+    /// Process templates inside inline code spans
+    ///
+    /// Off by default, so inline code shows template syntax as written.
+    /// Templates opted in with a force marker (`` `{{!mathbold}}X{{/mathbold}}` ``)
+    /// are processed either way.
+    pub process_inline_code: bool,
+    /// Leave HTML comments and `<pre>`/`<script>` blocks as written, like
+    /// code fences
+    ///
+    /// Only blocks with a closing `-->`, `</pre>`, or `</script>` are kept.
+    pub preserve_html_blocks: bool,
+    /// Leave `$...$` and `$$...$$` math regions as written
+    ///
+    /// Off by default since a lone `$` is common in prose. Set with
+    /// `"math": true` in `.mdfx.json`.
+    pub preserve_math: bool,
+    /// Parse the markdown as CommonMark first and leave link destinations,
+    /// reference definitions, and HTML tags as written
+    ///
+    /// Off by default. Applies to [`process`](crate::TemplateParser::process)
+    /// and [`process_with_assets`](crate::TemplateParser::process_with_assets);
+    /// see [`commonmark`](crate::commonmark). Set with `"commonmark": true`
+    /// in `.mdfx.json`.
+    pub commonmark: bool,
+    /// Reject project and remote icons containing anything sanitizing would
+    /// remove (scripts, event handlers, external references), instead of
+    /// embedding what remains
+    ///
+    /// Off by default; see [`icon::sanitize`](crate::icon::sanitize). Set
+    /// with `"strict_icons": true` in `.mdfx.json`.
+    pub strict_icons: bool,
+    /// Where today's date comes from, for live badges with
+    /// `format=relative` or `age_color`
+    ///
+    /// The system clock by default. The CLI pins it to `SOURCE_DATE_EPOCH`
+    /// when that is set, and leaves it [`Clock::Unavailable`] for
+    /// `--reproducible` builds without it.
+    pub clock: Clock,
+}
+
+/// Where the current date comes from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Clock {
+    /// Read the system clock
+    #[default]
+    System,
+    /// A fixed time, in seconds since the Unix epoch
+    Fixed(i64),
+    /// No current date: templates that need one fail, so output doesn't
+    /// depend on the day it's built
+    Unavailable,
+}
+
+impl Clock {
+    /// Days since the Unix epoch, or `None` when the clock is unavailable
+    pub fn today(self) -> Option<i64> {
+        match self {
+            Clock::System => Some(
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs() as i64 / 86_400),
+            ),
+            Clock::Fixed(secs) => Some(secs.div_euclid(86_400)),
+            Clock::Unavailable => None,
+        }
+    }
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self::trusted()
+    }
+}
+
+impl ParserOptions {
+    /// Every component is available; nesting and output size are capped at
+    /// [`DEFAULT_MAX_DEPTH`] and [`DEFAULT_MAX_OUTPUT_BYTES`]
+    pub fn trusted() -> Self {
+        Self {
+            max_depth: Some(DEFAULT_MAX_DEPTH),
+            max_output_bytes: Some(DEFAULT_MAX_OUTPUT_BYTES),
+            max_input_bytes: None,
+            allow_network: true,
+            allow_plugins: true,
+            allow_commands: true,
+            allow_files: true,
+            process_inline_code: false,
+            preserve_html_blocks: true,
+            preserve_math: false,
+            commonmark: false,
+            strict_icons: false,
+            clock: Clock::System,
+        }
+    }
+
+    /// Limits for markdown from untrusted sources
+    ///
+    /// Nesting is capped at 16 levels, input at 256 KiB and output at 1 MiB.
+    /// Icons that need sanitizing are rejected, and so is every template
+    /// that reaches outside the parser: live data components and remote
+    /// icons (network), snippets and the report and toolchain badges
+    /// (project files), `{{ui:maintenance:auto/}}` (runs `git`) and plugin
+    /// components. No built-in template reads environment variables.
+    pub fn untrusted() -> Self {
+        Self {
+            max_depth: Some(16),
+            max_output_bytes: Some(1024 * 1024),
+            max_input_bytes: Some(256 * 1024),
+            allow_network: false,
+            allow_plugins: false,
+            allow_commands: false,
+            allow_files: false,
+            process_inline_code: false,
+            preserve_html_blocks: true,
+            preserve_math: false,
+            commonmark: false,
+            strict_icons: true,
+            clock: Clock::System,
+        }
+    }
+
+    /// Override limits with those set in a config file
+    pub fn apply_limits(&mut self, limits: &LimitsConfig) {
+        if let Some(depth) = limits.max_depth {
+            self.max_depth = Some(depth);
+        }
+        if let Some(bytes) = limits.max_output_bytes {
+            self.max_output_bytes = Some(bytes);
+        }
+        if let Some(bytes) = limits.max_input_bytes {
+            self.max_input_bytes = Some(bytes);
+        }
+    }
+}
+
+/// Limits configurable under `"limits"` in `.mdfx.json`
+///
+/// Unset fields keep the parser's current value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LimitsConfig {
+    /// Maximum nesting of template expansions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<usize>,
+    /// Maximum size in bytes of the processed output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_bytes: Option<usize>,
+    /// Maximum size in bytes of the markdown passed in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_input_bytes: Option<usize>,
+}
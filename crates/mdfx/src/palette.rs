@@ -0,0 +1,734 @@
+//! Palette import and generation
+//!
+//! Builds mdfx palettes, the flat `{"name": "RRGGBB"}` map read by
+//! `--palette` and the `palette` section of `.mdfx.json`, either from a
+//! base color ([`generate`]) or from design-token files ([`import`]), so a
+//! brand palette doesn't have to be typed in by hand.
+//!
+//! [`import`] reads three formats:
+//!
+//! - [`TokenFormat::Figma`] - Tokens Studio (Figma Tokens) and W3C design
+//!   token JSON, where a token is `{"value": ...}` or `{"$value": ...}`
+//! - [`TokenFormat::Tailwind`] - the `colors` objects of a
+//!   `tailwind.config.js`, read as object literals without running the file
+//! - [`TokenFormat::Css`] - CSS custom properties (`--brand: #f50;`)
+//!
+//! Token paths are flattened into palette names: segments are lowercased and
+//! joined with `-`, runs of other characters become `-`, and `color`/`colors`
+//! groups and Tailwind's `DEFAULT` are dropped, so `colors.brand.DEFAULT` is
+//! `brand` and `colors.brand.500` is `brand-500`. Values may be hex (alpha is
+//! dropped), `rgb()`/`rgba()`, `hsl()`/`hsla()`, or a reference to another
+//! color (`{colors.brand}` or `var(--brand)`). Anything else is skipped.
+//!
+//! ```
+//! use mdfx::palette::{import, TokenFormat};
+//!
+//! let css = ":root { --color-brand: #ff5500; --space-2: 8px; --accent: var(--color-brand); }";
+//! let palette = import(css, TokenFormat::Css).unwrap();
+//!
+//! assert_eq!(palette.colors["brand"], "FF5500");
+//! assert_eq!(palette.colors["accent"], "FF5500");
+//! assert_eq!(palette.skipped, vec!["space-2".to_string()]);
+//! ```
+//!
+//! [`generate`] derives colors from a base with the hue rotation and tints
+//! of `mdfx-colors`:
+//!
+//! ```
+//! use mdfx::palette::{generate, Scheme};
+//!
+//! let palette = generate("2B6CB0", Scheme::Triadic, "brand").unwrap();
+//! assert_eq!(palette["brand"], "2B6CB0");
+//! assert_eq!(palette.len(), 3);
+//! ```
+
+use crate::error::{Error, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Design-token format read by [`import`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenFormat {
+    /// Tokens Studio (Figma Tokens) or W3C design token JSON
+    Figma,
+    /// `tailwind.config.js` theme colors
+    Tailwind,
+    /// CSS custom properties
+    Css,
+}
+
+impl TokenFormat {
+    /// Parse a format name (figma, tailwind, css)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "figma" | "figma-tokens" | "tokens" | "json" => Some(TokenFormat::Figma),
+            "tailwind" => Some(TokenFormat::Tailwind),
+            "css" | "css-vars" => Some(TokenFormat::Css),
+            _ => None,
+        }
+    }
+
+    /// Format of a file, from its extension
+    pub fn detect(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(TokenFormat::Figma),
+            "js" | "cjs" | "mjs" | "ts" => Some(TokenFormat::Tailwind),
+            "css" | "scss" => Some(TokenFormat::Css),
+            _ => None,
+        }
+    }
+}
+
+/// Palette read from a token file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportedPalette {
+    /// Colors by palette name, as `RRGGBB`
+    pub colors: BTreeMap<String, String>,
+    /// Names of tokens that aren't colors or couldn't be resolved
+    pub skipped: Vec<String>,
+}
+
+/// Color scheme derived by [`generate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// The base and its neighbors 30° either side on the color wheel
+    Analogous,
+    /// The base and the colors 120° and 240° around the wheel
+    Triadic,
+    /// Tints and shades of the base from `50` (lightest) to `900`
+    Shades,
+}
+
+impl Scheme {
+    /// Parse a scheme name (analogous, triadic, shades)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "analogous" => Some(Scheme::Analogous),
+            "triadic" => Some(Scheme::Triadic),
+            "shades" | "tints" => Some(Scheme::Shades),
+            _ => None,
+        }
+    }
+}
+
+/// Scale steps of [`Scheme::Shades`]: lighten below 500, darken above
+const SHADE_STEPS: [(u16, f32); 10] = [
+    (50, 0.9),
+    (100, 0.8),
+    (200, 0.6),
+    (300, 0.4),
+    (400, 0.2),
+    (500, 0.0),
+    (600, 0.15),
+    (700, 0.3),
+    (800, 0.45),
+    (900, 0.6),
+];
+
+/// Generate a palette from a base color
+///
+/// The base keeps `name`; derived colors are `NAME-analogous-1`/`-2`,
+/// `NAME-triadic-1`/`-2`, or the `NAME-50` ... `NAME-900` scale with
+/// `NAME-500` equal to the base.
+pub fn generate(base: &str, scheme: Scheme, name: &str) -> Result<BTreeMap<String, String>> {
+    if mdfx_colors::parse_hex(base).is_none() || base.trim_start_matches('#').len() != 6 {
+        return Err(Error::InvalidColor(base.to_string()));
+    }
+    let hex = |color: String| color.trim_start_matches('#').to_string();
+
+    let mut palette = BTreeMap::new();
+    palette.insert(name.to_string(), hex(format!("#{}", base.to_uppercase())));
+    match scheme {
+        Scheme::Analogous | Scheme::Triadic => {
+            let (label, step) = match scheme {
+                Scheme::Analogous => ("analogous", 30.0),
+                _ => ("triadic", 120.0),
+            };
+            for (i, degrees) in [step, -step].into_iter().enumerate() {
+                palette.insert(
+                    format!("{}-{}-{}", name, label, i + 1),
+                    hex(mdfx_colors::rotate_hue(base, degrees)),
+                );
+            }
+        }
+        Scheme::Shades => {
+            for (level, amount) in SHADE_STEPS {
+                let color = if level < 500 {
+                    mdfx_colors::lighten(base, amount)
+                } else {
+                    mdfx_colors::darken(base, amount)
+                };
+                palette.insert(format!("{}-{}", name, level), hex(color));
+            }
+        }
+    }
+    Ok(palette)
+}
+
+lazy_static! {
+    static ref CSS_VAR: Regex = Regex::new(r"--([A-Za-z0-9_-]+)\s*:\s*([^;{}]+)").unwrap();
+    static ref CSS_COMMENT: Regex = Regex::new(r"(?s)/\*.*?\*/").unwrap();
+    static ref FUNCTION: Regex = Regex::new(r"^(rgba?|hsla?)\(\s*([^)]*?)\s*\)$").unwrap();
+}
+
+/// Read the colors of a token file
+pub fn import(source: &str, format: TokenFormat) -> Result<ImportedPalette> {
+    let mut tokens = Vec::new();
+    match format {
+        TokenFormat::Figma => {
+            let json: Value = serde_json::from_str(source)?;
+            collect_json_tokens(&json, &mut Vec::new(), &mut tokens);
+        }
+        TokenFormat::Tailwind => {
+            let objects = tailwind_colors(source)?;
+            if objects.is_empty() {
+                return Err(Error::ParseError(
+                    "no `colors: { ... }` object found in the Tailwind config".to_string(),
+                ));
+            }
+            for object in objects {
+                collect_json_tokens(&object, &mut Vec::new(), &mut tokens);
+            }
+        }
+        TokenFormat::Css => {
+            let source = CSS_COMMENT.replace_all(source, "");
+            for var in CSS_VAR.captures_iter(&source) {
+                tokens.push((flatten([&var[1]]), var[2].trim().to_string()));
+            }
+        }
+    }
+
+    // Later definitions win, like `extend` over `theme` and cascading CSS
+    let mut raw: BTreeMap<String, String> = BTreeMap::new();
+    let mut order = Vec::new();
+    for (name, value) in tokens {
+        if name.is_empty() {
+            continue;
+        }
+        if raw.insert(name.clone(), value).is_none() {
+            order.push(name);
+        }
+    }
+
+    let mut palette = ImportedPalette::default();
+    for name in order {
+        match resolve(&raw, &raw[&name], 0) {
+            Some(hex) => {
+                palette.colors.insert(name, hex);
+            }
+            None => palette.skipped.push(name),
+        }
+    }
+    Ok(palette)
+}
+
+/// Palette name of a token path
+fn flatten<'a>(path: impl IntoIterator<Item = &'a str>) -> String {
+    let segments: Vec<&str> = path.into_iter().collect();
+    let last = segments.len().saturating_sub(1);
+    let mut parts = Vec::new();
+    for (i, segment) in segments.into_iter().enumerate() {
+        let segment = segment.trim_start_matches('-');
+        let lower = segment.to_lowercase();
+        let is_group = i < last && (lower == "color" || lower == "colors");
+        if is_group || segment == "DEFAULT" {
+            continue;
+        }
+        for word in lower.split(|c: char| !c.is_ascii_alphanumeric()) {
+            match word {
+                "" => {}
+                // `--color-brand` is the CSS spelling of `colors.brand`
+                "color" | "colors" if parts.is_empty() && i == 0 && lower.contains('-') => {}
+                _ => parts.push(word.to_string()),
+            }
+        }
+    }
+    parts.join("-")
+}
+
+/// Collect `(name, value)` pairs from nested token JSON
+fn collect_json_tokens<'a>(
+    value: &'a Value,
+    path: &mut Vec<&'a str>,
+    tokens: &mut Vec<(String, String)>,
+) {
+    match value {
+        Value::Object(map) => {
+            if let Some(token) = map.get("value").or_else(|| map.get("$value")) {
+                let kind = map.get("type").or_else(|| map.get("$type"));
+                if kind.is_none_or(|kind| kind == "color") {
+                    if let Some(token) = token.as_str() {
+                        tokens.push((flatten(path.iter().copied()), token.to_string()));
+                        return;
+                    }
+                }
+                tokens.push((flatten(path.iter().copied()), String::new()));
+                return;
+            }
+            for (key, child) in map {
+                // `$themes`, `$metadata` and `$description` aren't tokens
+                if key.starts_with('$') {
+                    continue;
+                }
+                path.push(key);
+                collect_json_tokens(child, path, tokens);
+                path.pop();
+            }
+        }
+        Value::String(s) => tokens.push((flatten(path.iter().copied()), s.clone())),
+        _ => tokens.push((flatten(path.iter().copied()), String::new())),
+    }
+}
+
+/// Resolve a token value to `RRGGBB`, following references
+fn resolve(raw: &BTreeMap<String, String>, value: &str, depth: usize) -> Option<String> {
+    if depth > 16 {
+        return None;
+    }
+    let value = value.trim();
+    let reference = value
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+        .map(|path| flatten(path.split('.')))
+        .or_else(|| {
+            value
+                .strip_prefix("var(")
+                .and_then(|v| v.strip_suffix(')'))
+                .map(|var| flatten([var.split(',').next().unwrap_or(var).trim()]))
+        });
+    match reference {
+        Some(name) => resolve(raw, raw.get(&name)?, depth + 1),
+        None => parse_color(value),
+    }
+}
+
+/// Parse a CSS color value into `RRGGBB`
+fn parse_color(value: &str) -> Option<String> {
+    let lower = value.to_ascii_lowercase();
+    let (r, g, b) = if let Some(hex) = lower.strip_prefix('#') {
+        match hex.len() {
+            3 | 6 => mdfx_colors::parse_hex(hex)?,
+            4 | 8 => mdfx_colors::parse_hex(&hex[..hex.len() / 4 * 3])?,
+            _ => return None,
+        }
+    } else {
+        let function = FUNCTION.captures(&lower)?;
+        let channels: Vec<&str> = function[2]
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if channels.len() < 3 {
+            return None;
+        }
+        if function[1].starts_with("rgb") {
+            (
+                rgb_channel(channels[0])?,
+                rgb_channel(channels[1])?,
+                rgb_channel(channels[2])?,
+            )
+        } else {
+            let hue: f32 = channels[0].trim_end_matches("deg").parse().ok()?;
+            let saturation = percent(channels[1])?;
+            let lightness = percent(channels[2])?;
+            let hex = mdfx_colors::from_hsl(hue, saturation, lightness);
+            mdfx_colors::parse_hex(&hex)?
+        }
+    };
+    Some(format!("{:02X}{:02X}{:02X}", r, g, b))
+}
+
+/// An `rgb()` channel, `0`-`255` or a percentage
+fn rgb_channel(s: &str) -> Option<u8> {
+    let value = match s.strip_suffix('%') {
+        Some(p) => p.parse::<f32>().ok()? * 2.55,
+        None => s.parse::<f32>().ok()?,
+    };
+    Some(value.round().clamp(0.0, 255.0) as u8)
+}
+
+/// A percentage as a fraction
+fn percent(s: &str) -> Option<f32> {
+    let value: f32 = s.trim_end_matches('%').parse().ok()?;
+    Some((value / 100.0).clamp(0.0, 1.0))
+}
+
+/// Object literals assigned to `colors` in a Tailwind config, as JSON
+fn tailwind_colors(source: &str) -> Result<Vec<Value>> {
+    let source = strip_js_comments(source);
+    let mut objects = Vec::new();
+    let mut from = 0;
+    while let Some(p) = source[from..].find("colors").map(|p| from + p) {
+        from = p + "colors".len();
+        let before = source[..p].chars().next_back();
+        if before.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+            continue;
+        }
+        let rest = source[from..].trim_start_matches(['"', '\'']).trim_start();
+        let Some(rest) = rest.strip_prefix(':') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        if !rest.starts_with('{') {
+            continue;
+        }
+        let mut reader = JsReader {
+            src: rest.as_bytes(),
+            pos: 0,
+        };
+        objects.push(reader.object()?);
+    }
+    Ok(objects)
+}
+
+/// Source with `//` and `/* */` comments removed, leaving strings intact
+fn strip_js_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut quote = None;
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) => {
+                out.push(c);
+                if c == '\\' {
+                    if let Some(next) = chars.next() {
+                        out.push(next);
+                    }
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c == '/' && chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            None if c == '/' && chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            }
+            None => {
+                if matches!(c, '"' | '\'' | '`') {
+                    quote = Some(c);
+                }
+                out.push(c);
+            }
+        }
+    }
+    out
+}
+
+/// Lenient reader for JavaScript object literals
+///
+/// Keys may be bare or quoted and trailing commas are allowed. Values that
+/// aren't strings or objects, such as `colors.slate` or function calls,
+/// become `null` and are skipped by the import.
+struct JsReader<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl JsReader<'_> {
+    fn skip_space(&mut self) {
+        while self.src.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self, expected: &str) -> Error {
+        Error::ParseError(format!(
+            "expected {} in Tailwind colors at byte {}",
+            expected, self.pos
+        ))
+    }
+
+    fn object(&mut self) -> Result<Value> {
+        self.pos += 1; // `{`
+        let mut map = serde_json::Map::new();
+        loop {
+            self.skip_space();
+            match self.src.get(self.pos) {
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(map));
+                }
+                Some(b',') => {
+                    self.pos += 1;
+                    continue;
+                }
+                None => return Err(self.error("`}`")),
+                _ => {}
+            }
+            // `...defaultTheme.colors` spreads can't be read statically
+            if self.src[self.pos..].starts_with(b"...") {
+                self.value();
+                continue;
+            }
+            let key = self.key()?;
+            self.skip_space();
+            if self.src.get(self.pos) != Some(&b':') {
+                return Err(self.error("`:`"));
+            }
+            self.pos += 1;
+            self.skip_space();
+            let value = if self.src.get(self.pos) == Some(&b'{') {
+                self.object()?
+            } else {
+                self.value()
+            };
+            map.insert(key, value);
+        }
+    }
+
+    fn key(&mut self) -> Result<String> {
+        match self.src.get(self.pos) {
+            Some(&q) if matches!(q, b'"' | b'\'') => self.string(q),
+            _ => {
+                let start = self.pos;
+                while self
+                    .src
+                    .get(self.pos)
+                    .is_some_and(|&c| c.is_ascii_alphanumeric() || matches!(c, b'_' | b'$'))
+                {
+                    self.pos += 1;
+                }
+                if start == self.pos {
+                    return Err(self.error("a key"));
+                }
+                Ok(String::from_utf8_lossy(&self.src[start..self.pos]).into_owned())
+            }
+        }
+    }
+
+    fn string(&mut self, quote: u8) -> Result<String> {
+        self.pos += 1;
+        let start = self.pos;
+        while let Some(&c) = self.src.get(self.pos) {
+            if c == quote {
+                let s = String::from_utf8_lossy(&self.src[start..self.pos]).into_owned();
+                self.pos += 1;
+                return Ok(s);
+            }
+            self.pos += if c == b'\\' { 2 } else { 1 };
+        }
+        Err(self.error("a closing quote"))
+    }
+
+    /// A string value, or `null` after skipping any other expression
+    fn value(&mut self) -> Value {
+        if let Some(&q) = self.src.get(self.pos) {
+            if matches!(q, b'"' | b'\'' | b'`') {
+                let save = self.pos;
+                if let Ok(s) = self.string(q) {
+                    self.skip_space();
+                    if matches!(self.src.get(self.pos), Some(b',' | b'}') | None) {
+                        return Value::String(s);
+                    }
+                }
+                self.pos = save;
+            }
+        }
+        // Skip to the `,` or `}` that ends the expression
+        let mut depth = 0usize;
+        while let Some(&c) = self.src.get(self.pos) {
+            match c {
+                b'(' | b'[' | b'{' => depth += 1,
+                b')' | b']' => depth = depth.saturating_sub(1),
+                b'}' if depth == 0 => break,
+                b'}' => depth -= 1,
+                b',' if depth == 0 => break,
+                b'"' | b'\'' | b'`' => {
+                    let _ = self.string(c);
+                    continue;
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        Value::Null
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("#FF5500", Some("FF5500"))]
+    #[case("#f50", Some("FF5500"))]
+    #[case("#ff550080", Some("FF5500"))]
+    #[case("rgb(255, 85, 0)", Some("FF5500"))]
+    #[case("rgba(255 85 0 / 0.5)", Some("FF5500"))]
+    #[case("rgb(100%, 0%, 0%)", Some("FF0000"))]
+    #[case("hsl(20, 100%, 50%)", Some("FF5500"))]
+    #[case("hsl(210deg 50% 40%)", Some("336699"))]
+    #[case("8px", None)]
+    #[case("transparent", None)]
+    fn test_parse_color(#[case] value: &str, #[case] expected: Option<&str>) {
+        assert_eq!(parse_color(value).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(&["colors", "brand", "DEFAULT"], "brand")]
+    #[case(&["colors", "brand", "500"], "brand-500")]
+    #[case(&["global", "Color", "Brand Primary"], "global-brand-primary")]
+    #[case(&["--color-brand-500"], "brand-500")]
+    #[case(&["--brand_accent"], "brand-accent")]
+    #[case(&["--text-color"], "text-color")]
+    #[case(&["surface", "color"], "surface-color")]
+    fn test_flatten(#[case] path: &[&str], #[case] expected: &str) {
+        assert_eq!(flatten(path.iter().copied()), expected);
+    }
+
+    #[test]
+    fn test_import_figma_tokens() {
+        let json = r##"{
+            "global": {
+                "colors": {
+                    "brand": {"value": "#FF5500", "type": "color"},
+                    "accent": {"value": "{global.colors.brand}", "type": "color"}
+                },
+                "spacing": {"sm": {"value": "4", "type": "spacing"}}
+            },
+            "$themes": []
+        }"##;
+        let palette = import(json, TokenFormat::Figma).unwrap();
+
+        assert_eq!(palette.colors["global-brand"], "FF5500");
+        assert_eq!(palette.colors["global-accent"], "FF5500");
+        assert_eq!(palette.skipped, vec!["global-spacing-sm".to_string()]);
+    }
+
+    #[test]
+    fn test_import_w3c_tokens() {
+        let json = r##"{"color": {"$type": "color", "ink": {"$value": "#222"}}}"##;
+        let palette = import(json, TokenFormat::Figma).unwrap();
+
+        assert_eq!(palette.colors["ink"], "222222");
+    }
+
+    #[test]
+    fn test_import_tailwind_config() {
+        let config = r#"
+            const colors = require('tailwindcss/colors')
+
+            module.exports = {
+              theme: {
+                colors: {
+                  // brand palette
+                  'brand': {
+                    DEFAULT: '#FF5500',
+                    light: "rgb(255, 136, 77)", /* hover */
+                  },
+                  gray: colors.gray,
+                },
+                extend: {
+                  colors: {
+                    ink: '#222',
+                    brand: { DEFAULT: '#EE4400' },
+                  },
+                },
+              },
+              plugins: [require('@tailwindcss/forms')],
+            }
+        "#;
+        let palette = import(config, TokenFormat::Tailwind).unwrap();
+
+        assert_eq!(palette.colors["brand"], "EE4400");
+        assert_eq!(palette.colors["brand-light"], "FF884D");
+        assert_eq!(palette.colors["ink"], "222222");
+        assert_eq!(palette.skipped, vec!["gray".to_string()]);
+    }
+
+    #[test]
+    fn test_import_tailwind_without_colors() {
+        let err = import("module.exports = {}", TokenFormat::Tailwind).unwrap_err();
+        assert!(err.to_string().contains("no `colors: { ... }` object"));
+    }
+
+    #[test]
+    fn test_import_css_vars() {
+        let css = r#"
+            :root {
+              /* --ignored: #000; */
+              --color-primary: #2B6CB0;
+              --secondary: hsl(145, 45%, 51%);
+              --link: var(--color-primary);
+              --missing: var(--nope);
+            }
+            .dark { --color-primary: #90CDF4; }
+        "#;
+        let palette = import(css, TokenFormat::Css).unwrap();
+
+        assert_eq!(palette.colors["primary"], "90CDF4");
+        assert_eq!(palette.colors["secondary"], "4ABA79");
+        assert_eq!(palette.colors["link"], "90CDF4");
+        assert!(!palette.colors.contains_key("ignored"));
+        assert_eq!(palette.skipped, vec!["missing".to_string()]);
+    }
+
+    #[test]
+    fn test_generate_analogous() {
+        let palette = generate("#2b6cb0", Scheme::Analogous, "primary").unwrap();
+
+        assert_eq!(palette["primary"], "2B6CB0");
+        assert_eq!(palette["primary-analogous-1"], "2C2BB0");
+        assert_eq!(palette["primary-analogous-2"], "2BAEB0");
+        assert_eq!(palette.len(), 3);
+    }
+
+    #[test]
+    fn test_generate_triadic() {
+        let palette = generate("FF0000", Scheme::Triadic, "brand").unwrap();
+
+        assert_eq!(palette["brand-triadic-1"], "00FF00");
+        assert_eq!(palette["brand-triadic-2"], "0000FF");
+    }
+
+    #[test]
+    fn test_generate_shades() {
+        let palette = generate("2B6CB0", Scheme::Shades, "primary").unwrap();
+
+        assert_eq!(palette.len(), 11);
+        assert_eq!(palette["primary-500"], "2B6CB0");
+        assert_eq!(palette["primary-50"], "EAF0F7");
+        assert_eq!(palette["primary-900"], "112B46");
+        // Each step is darker than the last
+        let luminance =
+            |level: u16| mdfx_colors::luminance(&palette[&format!("primary-{}", level)]);
+        for pair in SHADE_STEPS.windows(2) {
+            assert!(luminance(pair[0].0) > luminance(pair[1].0));
+        }
+    }
+
+    #[rstest]
+    #[case("blue")]
+    #[case("#F50")]
+    #[case("2B6CB0FF")]
+    fn test_generate_invalid_base(#[case] base: &str) {
+        let err = generate(base, Scheme::Shades, "primary").unwrap_err();
+        assert!(matches!(err, Error::InvalidColor(_)));
+    }
+
+    #[rstest]
+    #[case("tokens.json", Some(TokenFormat::Figma))]
+    #[case("tailwind.config.js", Some(TokenFormat::Tailwind))]
+    #[case("tailwind.config.ts", Some(TokenFormat::Tailwind))]
+    #[case("vars.css", Some(TokenFormat::Css))]
+    #[case("palette.yaml", None)]
+    fn test_detect_format(#[case] path: &str, #[case] expected: Option<TokenFormat>) {
+        assert_eq!(TokenFormat::detect(Path::new(path)), expected);
+    }
+}
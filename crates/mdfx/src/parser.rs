@@ -1,12 +1,71 @@
-use crate::components::{ComponentOutput, ComponentsRenderer, PostProcess};
-use crate::config::{expand_partial, MdfxConfig};
-use crate::converter::Converter;
+use crate::analysis::TemplateUsage;
+use crate::ast::{self, Node, NodeKind};
+use crate::commonmark::Masked;
+use crate::components::param_schema::normalize_key;
+use crate::components::{
+    ComponentOutput, ComponentPlugin, ComponentsRenderer, MatrixCell, MatrixTable, ParamSchema,
+    PostProcess,
+};
+use crate::config::{expand_partial, front_matter_styles, MdfxConfig, StyleDefaults};
+use crate::converter::{Converter, Fallback};
+use crate::diagnostics::{Diagnostic, RecoveredMarkdown};
 use crate::error::{Error, Result};
+use crate::hooks::{AssetHook, MarkdownHook};
+use crate::i18n;
+use crate::numbers::NumberFormat;
+use crate::options::ParserOptions;
+use crate::primitive::{GroupLayout, Primitive};
 use crate::registry::Registry;
 use crate::renderer::shields::ShieldsBackend;
-use crate::renderer::{RenderedAsset, Renderer};
+use crate::renderer::{DisplaySize, RenderedAsset, Renderer};
 use crate::shields::ShieldsRenderer;
+use crate::snippet;
+use crate::stats::{ParseObserver, RenderEvent, TemplateEvent};
+use crate::targets::Target;
+use crate::vfs::{self, AssetWriteSummary, Vfs};
+use crate::width::{self, display_width};
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::debug;
+
+/// Split `display_width=`/`display_height=` off a component's arguments
+///
+/// They size the rendered image reference, so no handler sees them.
+fn split_display_size(args: Vec<String>) -> Result<(DisplaySize, Vec<String>)> {
+    let mut display = DisplaySize::default();
+    let mut rest = Vec::with_capacity(args.len());
+    for arg in args {
+        let side = match arg.split_once('=') {
+            Some((key, value)) => match normalize_key(key).as_str() {
+                "display_width" => Some((&mut display.width, "display_width", value)),
+                "display_height" => Some((&mut display.height, "display_height", value)),
+                _ => None,
+            },
+            None => None,
+        };
+        match side {
+            Some((slot, name, value)) => {
+                let pixels = value
+                    .trim()
+                    .trim_end_matches("px")
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|&px| px > 0)
+                    .ok_or_else(|| {
+                        Error::ParseError(format!(
+                            "{} must be a positive number of pixels, got '{}'",
+                            name, value
+                        ))
+                    })?;
+                *slot = Some(pixels);
+            }
+            None => rest.push(arg),
+        }
+    }
+    Ok((display, rest))
+}
 
 /// Variation Selector 15 - forces text presentation for Unicode characters
 /// that have both text and emoji variants (e.g., ☢ renders as glyph, not emoji)
@@ -25,6 +84,228 @@ fn text_style(glyph: &str) -> String {
     result
 }
 
+/// Format and value of a number template
+fn parse_num(data: &NumData) -> Result<(NumberFormat, i64)> {
+    let format = NumberFormat::parse(&data.format).ok_or_else(|| {
+        Error::ParseError(format!(
+            "Unknown number format '{}'. Available: circled, roman, super, sub",
+            data.format
+        ))
+    })?;
+    let n = data.value.parse().map_err(|_| {
+        Error::ParseError(format!(
+            "Invalid number '{}' in {{{{num:{}}}}}: expected an integer",
+            data.value, data.format
+        ))
+    })?;
+    Ok((format, n))
+}
+
+/// A template handler: returns the expansion, collected assets, and end position
+type TemplateHandler =
+    fn(&TemplateParser, &[char], usize) -> Result<Option<(String, Vec<RenderedAsset>, usize)>>;
+
+/// Extract the style, frame, component, or glyph name from template source
+///
+/// `{{mathbold}}A{{/mathbold}}` yields `mathbold`, `{{ui:tech:rust/}}` yields
+/// `tech`, and `{{frame:star}}...` yields `star`.
+fn template_name<'a>(kind: &str, source: &'a str) -> &'a str {
+    let inner = source.trim_start_matches('{');
+    let end = inner.find(['}', '/']).unwrap_or(inner.len());
+    let head = &inner[..end];
+
+    match kind {
+        "style" => head.split(':').next().unwrap_or(head),
+        _ => head.split(':').nth(1).unwrap_or(head),
+    }
+}
+
+/// Append a text or code node, skipping empty spans
+fn push_node(nodes: &mut Vec<Node>, kind: NodeKind, range: std::ops::Range<usize>) {
+    if !range.is_empty() {
+        nodes.push(Node { kind, range });
+    }
+}
+
+/// HTML whose content is left as written: comments, `<pre>`, and `<script>`
+const HTML_BLOCKS: [(&str, &str); 3] = [
+    ("<!--", "-->"),
+    ("<pre", "</pre>"),
+    ("<script", "</script>"),
+];
+
+/// Split text into ranges, flagging closed HTML blocks
+fn split_html_blocks(text: &str) -> Vec<(bool, std::ops::Range<usize>)> {
+    let lower = text.to_ascii_lowercase();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while let Some(block) = next_html_block(&lower, start) {
+        if start < block.start {
+            parts.push((false, start..block.start));
+        }
+        start = block.end;
+        parts.push((true, block));
+    }
+    if start < text.len() {
+        parts.push((false, start..text.len()));
+    }
+    parts
+}
+
+/// Range of the first closed HTML block at or after `from` in lowercased text
+fn next_html_block(lower: &str, from: usize) -> Option<std::ops::Range<usize>> {
+    HTML_BLOCKS
+        .iter()
+        .filter_map(|(open, close)| {
+            let mut search = from;
+            loop {
+                let pos = search + lower[search..].find(open)?;
+                let after = lower[pos + open.len()..].chars().next();
+                // `<pre>` and `<pre class="...">`, not `<prefix>`
+                let is_tag = *open == "<!--"
+                    || matches!(after, Some('>' | '/'))
+                    || after.is_some_and(char::is_whitespace);
+                if is_tag {
+                    let end = lower[pos..].find(close)? + pos + close.len();
+                    return Some(pos..end);
+                }
+                search = pos + open.len();
+            }
+        })
+        .min_by_key(|block| block.start)
+}
+
+/// Range of the first `$$...$$` or `$...$` math region at or after `from`
+fn next_math_region(text: &str, from: usize) -> Option<std::ops::Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            // `\$` is a literal dollar
+            b'\\' => i += 1,
+            b'$' if bytes.get(i + 1) == Some(&b'$') => {
+                if let Some(p) = text[i + 2..].find("$$") {
+                    return Some(i..i + 2 + p + 2);
+                }
+                i += 1;
+            }
+            b'$' => {
+                if let Some(end) = inline_math_end(text, i) {
+                    return Some(i..end);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// End of inline math opened by the `$` at `start`
+///
+/// As in Pandoc, the opening `$` is followed by a non-space, the closing one
+/// on the same line follows a non-space and is not followed by a digit, so
+/// prices like `$5 and $10` are not math.
+fn inline_math_end(text: &str, start: usize) -> Option<usize> {
+    let rest = &text[start + 1..];
+    if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+    let mut search = 0;
+    while let Some(p) = line[search..].find('$').map(|p| search + p) {
+        let before = line[..p].chars().next_back();
+        let after = line[p + 1..].chars().next();
+        if before.is_some_and(|c| !c.is_whitespace() && c != '\\')
+            && !after.is_some_and(|c| c.is_ascii_digit())
+        {
+            return Some(start + 1 + p + 1);
+        }
+        search = p + 1;
+    }
+    None
+}
+
+/// Split text into ranges, flagging math regions
+fn split_math_regions(text: &str) -> Vec<(bool, std::ops::Range<usize>)> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while let Some(region) = next_math_region(text, start) {
+        if start < region.start {
+            parts.push((false, start..region.start));
+        }
+        start = region.end;
+        parts.push((true, region));
+    }
+    if start < text.len() {
+        parts.push((false, start..text.len()));
+    }
+    parts
+}
+
+/// Byte offset of the first `{{!` force marker, skipping `{{!--` comments
+fn find_force_marker(text: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(pos) = text[from..].find("{{!").map(|p| from + p) {
+        if !text[pos + 3..].starts_with("--") {
+            return Some(pos);
+        }
+        from = pos + 3;
+    }
+    None
+}
+
+/// Original source of a node, or its canonical form if the range doesn't
+/// point at it (after `{{//}}` expansion)
+fn verbatim(node: &Node, source: &str) -> String {
+    match source.get(node.range.clone()) {
+        Some(text) if text.starts_with("{{") => text.to_string(),
+        _ => ast::to_source(std::slice::from_ref(node)),
+    }
+}
+
+/// Stand-in for a failed template's source while its parent is re-rendered,
+/// using private-use characters so it can't be parsed as a template
+fn repair_placeholder(index: usize) -> String {
+    format!("\u{E000}{}\u{E001}", index)
+}
+
+/// Locate template content inside its source: right after the opening tag
+/// for block templates, otherwise the last occurrence (self-closing frames)
+fn content_offset(source: &str, content: &str) -> usize {
+    let open_end = source.find("}}").map(|p| p + 2).unwrap_or(0);
+    if source[open_end..].starts_with(content) {
+        open_end
+    } else {
+        source.rfind(content).unwrap_or(open_end)
+    }
+}
+
+/// Widest a frame can be made with `/width=N`, in display columns
+const MAX_FRAME_WIDTH: usize = 1000;
+
+/// Split a `/width=N` modifier off a frame spec
+///
+/// Works for every frame kind, so it is handled before the spec is parsed.
+/// Widths over [`MAX_FRAME_WIDTH`] are rejected.
+fn take_width_modifier(spec: &str) -> Result<(String, Option<usize>)> {
+    let mut width = None;
+    let mut parts = Vec::new();
+    for (i, part) in spec.split('/').enumerate() {
+        match part.strip_prefix("width=").map(str::parse::<usize>) {
+            Some(Ok(n)) if i > 0 && n > MAX_FRAME_WIDTH => {
+                return Err(Error::ParseError(format!(
+                    "frame width must be at most {} columns, got {}",
+                    MAX_FRAME_WIDTH, n
+                )));
+            }
+            Some(Ok(n)) if i > 0 => width = Some(n),
+            _ => parts.push(part),
+        }
+    }
+    Ok((parts.join("/"), width))
+}
+
 /// Template data extracted from parsing
 #[derive(Debug, Clone)]
 struct TemplateData {
@@ -32,6 +313,8 @@ struct TemplateData {
     style: String,
     spacing: usize,
     separator: Option<String>,
+    fallback: Option<Fallback>,
+    seed: Option<u64>,
     content: String,
 }
 
@@ -57,7 +340,7 @@ struct UIData {
 struct ShieldData {
     end_pos: usize,
     shield_type: String, // "block", "twotone", "bar", "icon"
-    params: std::collections::HashMap<String, String>,
+    params: std::collections::BTreeMap<String, String>,
 }
 
 /// Partial template data
@@ -82,6 +365,44 @@ struct KbdData {
     keys: String,
 }
 
+/// Number template data: `{{num:format:value/}}`
+#[derive(Debug, Clone)]
+struct NumData {
+    end_pos: usize,
+    format: String,
+    value: String,
+}
+
+/// Translated string template data: `{{t:key/}}`
+#[derive(Debug, Clone)]
+struct TranslationData {
+    end_pos: usize,
+    key: String,
+}
+
+/// Source snippet template data: `{{snippet:path:region=name/}}`
+#[derive(Debug, Clone)]
+struct SnippetData {
+    end_pos: usize,
+    path: String,
+    region: Option<String>,
+    lang: Option<String>,
+}
+
+/// Comment template data: `{{!-- text --}}`
+#[derive(Debug, Clone)]
+struct CommentData {
+    end_pos: usize,
+    text: String,
+}
+
+/// Escaped text data: `\{{` or `{{raw}}...{{/raw}}`
+#[derive(Debug, Clone)]
+struct RawData {
+    end_pos: usize,
+    content: String,
+}
+
 /// Frame modifiers parsed from style string
 #[derive(Debug, Clone)]
 struct FrameModifiers {
@@ -92,6 +413,16 @@ struct FrameModifiers {
     count: Option<usize>,
 }
 
+/// Glyph frame parsed from a `glyph:NAME*COUNT/pad=.../...` spec
+#[derive(Debug, Clone)]
+struct GlyphFrameSpec {
+    glyph: String,
+    count: usize,
+    pad: String,
+    separator: Option<String>,
+    spacing: Option<usize>,
+}
+
 /// Result of processing markdown with file-based assets
 #[derive(Debug, Clone)]
 pub struct ProcessedMarkdown {
@@ -101,14 +432,50 @@ pub struct ProcessedMarkdown {
     pub assets: Vec<RenderedAsset>,
 }
 
+impl ProcessedMarkdown {
+    /// Write file-based assets through `fs`, skipping files that exist
+    pub fn write_assets(&self, fs: &mut dyn Vfs) -> Result<AssetWriteSummary> {
+        vfs::write_assets(fs, &self.assets)
+    }
+}
+
 /// Parser for processing markdown with style templates
 pub struct TemplateParser {
     converter: Converter,
     components_renderer: ComponentsRenderer,
     shields_renderer: ShieldsRenderer, // Keep for {{shields:*}} escape hatch
     backend: Box<dyn Renderer>,        // Pluggable rendering backend
-    registry: Registry,                // Unified registry for resolution
+    registry: Arc<Registry>,           // Unified registry for resolution
     partials: HashMap<String, String>, // User-defined partial templates
+    strings: HashMap<String, String>,  // Translated strings for {{t:key/}}
+    glyphs: HashMap<String, String>,   // User-defined glyphs (checked before registry)
+    style_defaults: HashMap<String, StyleDefaults>, // Per-style spacing/separator, by style id
+    document_styles: RefCell<HashMap<String, StyleDefaults>>, // Front matter overrides
+    observers: Vec<Arc<dyn ParseObserver>>, // Instrumentation hooks
+    asset_hooks: Vec<Arc<dyn AssetHook>>, // Rewrite rendered assets
+    markdown_hooks: Vec<Arc<dyn MarkdownHook>>, // Rewrite final output
+    options: ParserOptions,            // Limits and sandboxing
+    html: bool,                        // Target renders HTML blocks like <div align>
+    depth: Cell<usize>,                // Current template expansion nesting
+    partial_stack: RefCell<Vec<String>>, // Partials being expanded, outermost first
+}
+
+/// Decrements the parser's expansion depth when dropped
+struct DepthGuard<'a>(&'a Cell<usize>);
+
+impl Drop for DepthGuard<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() - 1);
+    }
+}
+
+/// Clears the current document's style overrides when dropped
+struct DocumentGuard<'a>(&'a RefCell<HashMap<String, StyleDefaults>>);
+
+impl Drop for DocumentGuard<'_> {
+    fn drop(&mut self) {
+        self.0.borrow_mut().clear();
+    }
 }
 
 impl TemplateParser {
@@ -119,21 +486,73 @@ impl TemplateParser {
 
     /// Create a template parser with a custom backend
     pub fn with_backend(backend: Box<dyn Renderer>) -> Result<Self> {
-        let converter = Converter::new()?;
-        let components_renderer = ComponentsRenderer::new()?;
-        let shields_renderer = ShieldsRenderer::new()?;
-        let registry = Registry::new()?;
+        Self::with_registry(Registry::shared()?, backend)
+    }
+
+    /// Create a template parser that shares an existing registry
+    ///
+    /// Use with [`Registry::shared`] when building many parsers, e.g. one per
+    /// file or per watch rebuild.
+    pub fn with_registry(registry: Arc<Registry>, backend: Box<dyn Renderer>) -> Result<Self> {
         Ok(Self {
-            converter,
-            components_renderer,
-            shields_renderer,
+            converter: Converter::new()?,
+            components_renderer: ComponentsRenderer::with_registry(&registry),
+            shields_renderer: ShieldsRenderer::with_registry(&registry),
             backend,
             registry,
             partials: HashMap::new(),
+            strings: HashMap::new(),
+            glyphs: HashMap::new(),
+            style_defaults: HashMap::new(),
+            document_styles: RefCell::new(HashMap::new()),
+            observers: Vec::new(),
+            asset_hooks: Vec::new(),
+            markdown_hooks: Vec::new(),
+            options: ParserOptions::default(),
+            html: true,
+            depth: Cell::new(0),
+            partial_stack: RefCell::new(Vec::new()),
         })
     }
 
-    /// Load partials from an MdfxConfig
+    /// Set limits and sandboxing, see [`ParserOptions`]
+    ///
+    /// Use [`ParserOptions::untrusted`] when processing markdown from users.
+    pub fn set_options(&mut self, options: ParserOptions) {
+        self.options = options;
+        self.apply_component_options();
+    }
+
+    /// Pass the icon settings and clock of the options on to the components
+    /// renderer
+    fn apply_component_options(&mut self) {
+        self.components_renderer
+            .set_strict_icons(self.options.strict_icons);
+        self.components_renderer
+            .set_remote_icons(self.options.allow_network);
+        self.components_renderer
+            .set_network(self.options.allow_network);
+        self.components_renderer
+            .set_commands(self.options.allow_commands);
+        self.components_renderer.set_clock(self.options.clock);
+    }
+
+    /// Current limits and sandboxing
+    pub fn options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    /// Adapt component output to `target`
+    ///
+    /// Components that center their output in HTML, like `hero`, emit plain
+    /// markdown instead when the target doesn't render `<div>`. Without a
+    /// target, HTML is assumed.
+    pub fn set_target(&mut self, target: &dyn Target) {
+        self.html = target.supports_html() || target.allowed_html_tags().contains(&"div");
+    }
+
+    /// Load partials, palette, glyphs, icons, and style defaults from an
+    /// MdfxConfig
     ///
     /// # Example
     ///
@@ -155,6 +574,96 @@ impl TemplateParser {
             self.components_renderer
                 .extend_palette(config.palette.clone());
         }
+
+        // Load custom glyphs
+        for (name, glyph) in &config.glyphs {
+            self.glyphs.insert(name.clone(), glyph.clone());
+        }
+
+        for (style, defaults) in &config.styles {
+            self.set_style_defaults(style, defaults.clone());
+        }
+
+        self.options.apply_limits(&config.limits);
+        if let Some(math) = config.math {
+            self.options.preserve_math = math;
+        }
+        if let Some(commonmark) = config.commonmark {
+            self.options.commonmark = commonmark;
+        }
+        if let Some(locale) = &config.locale {
+            self.components_renderer.set_locale(locale);
+        }
+        if let Some(lang) = &config.lang {
+            self.components_renderer.set_lang(lang);
+        }
+        for (metric, label) in &config.labels {
+            self.components_renderer.set_label(metric, label);
+        }
+        for (name, svg) in &config.icons {
+            self.components_renderer.set_icon(name, svg);
+        }
+        if let Some(strict) = config.strict_icons {
+            self.options.strict_icons = strict;
+        }
+        self.apply_component_options();
+    }
+
+    /// Set the spacing or separator used by `{{style}}` templates that
+    /// specify neither
+    ///
+    /// `style` may be a style name or alias. Front matter in a document can
+    /// override this for that document, see [`front_matter_styles`].
+    pub fn set_style_defaults(&mut self, style: &str, defaults: StyleDefaults) {
+        let id = self.style_id(style);
+        self.style_defaults.insert(id, defaults);
+    }
+
+    /// Canonical id of a style name or alias, or the name itself if unknown
+    fn style_id(&self, style: &str) -> String {
+        self.registry
+            .style(style)
+            .map_or_else(|| style.to_string(), |s| s.id.clone())
+    }
+
+    /// Apply the front matter style overrides of `markdown` until the guard
+    /// is dropped
+    fn enter_document(&self, markdown: &str) -> Result<DocumentGuard<'_>> {
+        let styles = front_matter_styles(markdown)?;
+        let mut current = self.document_styles.borrow_mut();
+        current.clear();
+        for (style, defaults) in styles {
+            current.insert(self.style_id(&style), defaults);
+        }
+        Ok(DocumentGuard(&self.document_styles))
+    }
+
+    /// Set the Keep-a-Changelog markdown read by `{{ui:latest-release/}}`
+    ///
+    /// The CLI loads `CHANGELOG.md`, or the `changelog` path from
+    /// `.mdfx.json`.
+    pub fn set_changelog(&mut self, markdown: impl Into<String>) {
+        self.components_renderer.set_changelog(markdown);
+    }
+
+    /// Set the contents of a project file read by `{{ui:msrv/}}`,
+    /// `{{ui:node-engines/}}`, `{{ui:python-requires/}}` or
+    /// `{{ui:coverage:file=.../}}`, without touching the disk
+    ///
+    /// `name` is the path as the component sees it, e.g. `Cargo.toml` or
+    /// `coverage/lcov.info`.
+    pub fn set_project_file(&mut self, name: impl Into<String>, contents: impl Into<String>) {
+        self.components_renderer.set_project_file(name, contents);
+    }
+
+    /// Read project files for those components from `root`
+    ///
+    /// The CLI uses the current directory. Without a root, only files set
+    /// with [`set_project_file`](Self::set_project_file) are available.
+    /// Paths that resolve outside the root, whether absolute or through `..`
+    /// or symlinks, aren't read.
+    pub fn set_project_root(&mut self, root: impl Into<std::path::PathBuf>) {
+        self.components_renderer.set_project_root(root);
     }
 
     /// Set the fetch context for dynamic badges (GitHub, npm, etc.)
@@ -166,6 +675,50 @@ impl TemplateParser {
         self.components_renderer.set_fetch_context(ctx);
     }
 
+    /// Render live badges as gray `metric: —` placeholders when their data
+    /// can't be fetched, instead of failing
+    ///
+    /// Applies when no fetch context is set (or the `fetch` feature is
+    /// disabled), and to fetches that fail or come back unavailable. Invalid
+    /// sources and metrics are still errors.
+    pub fn set_placeholders(&mut self, enabled: bool) {
+        self.components_renderer.set_placeholders(enabled);
+    }
+
+    /// Get the fetch context for dynamic badges, if one is set
+    #[cfg(feature = "fetch")]
+    pub fn fetch_context(&self) -> Option<&crate::components::FetchContext> {
+        self.components_renderer.fetch_context()
+    }
+
+    /// Register an observer notified after every template expansion
+    ///
+    /// See [`StatsCollector`](crate::StatsCollector) for a ready-made observer
+    /// that counts templates by type.
+    pub fn add_observer(&mut self, observer: Arc<dyn ParseObserver>) {
+        self.observers.push(observer);
+    }
+
+    /// Register a hook that sees every asset rendered by the backend
+    ///
+    /// See [`hooks`](crate::hooks).
+    pub fn add_asset_hook(&mut self, hook: Arc<dyn AssetHook>) {
+        self.asset_hooks.push(hook);
+    }
+
+    /// Register a hook that rewrites the final processed markdown
+    pub fn add_markdown_hook(&mut self, hook: Arc<dyn MarkdownHook>) {
+        self.markdown_hooks.push(hook);
+    }
+
+    /// Register a custom `{{ui:...}}` component
+    ///
+    /// See [`ComponentPlugin`]. Fails if a component with the same name
+    /// already exists.
+    pub fn register_component(&mut self, plugin: Arc<dyn ComponentPlugin>) -> Result<()> {
+        self.components_renderer.register_plugin(plugin)
+    }
+
     /// Add a single partial template
     ///
     /// # Arguments
@@ -181,6 +734,22 @@ impl TemplateParser {
         self.partials.contains_key(name)
     }
 
+    /// Set the translated strings used by `{{t:key/}}` templates
+    ///
+    /// Replaces any strings set before. See [`i18n::parse_strings`](crate::i18n::parse_strings)
+    /// for reading them from a JSON string file.
+    pub fn set_strings(&mut self, strings: HashMap<String, String>) {
+        self.strings = strings;
+    }
+
+    /// Look up a glyph, preferring user-defined glyphs over the registry
+    fn lookup_glyph(&self, name: &str) -> Option<&str> {
+        self.glyphs
+            .get(name)
+            .map(|s| s.as_str())
+            .or_else(|| self.registry.glyph(name))
+    }
+
     /// Extend the color palette with custom definitions
     ///
     /// Custom colors override built-in palette colors with the same name.
@@ -237,6 +806,24 @@ impl TemplateParser {
     /// println!("{}", processed.markdown);
     /// ```
     pub fn process_with_assets(&self, markdown: &str) -> Result<ProcessedMarkdown> {
+        self.check_input(markdown)?;
+        let _document = self.enter_document(markdown)?;
+
+        // In CommonMark mode, link destinations and HTML tags are swapped for
+        // placeholders until the output is built
+        let masked = self
+            .options
+            .commonmark
+            .then(|| Masked::new(markdown))
+            .flatten();
+        // Document byte offset of each byte of `markdown`, plus its end, so
+        // observers can be told where templates are
+        let offsets: Option<Vec<usize>> = (!self.observers.is_empty()).then(|| match &masked {
+            Some(masked) => masked.source_offsets(),
+            None => (0..=markdown.len()).collect(),
+        });
+        let markdown = masked.as_ref().map_or(markdown, |m| m.text.as_str());
+
         // Split markdown into code blocks and content sections
         // Code blocks are preserved as-is, content sections are processed
         let mut result = String::new();
@@ -246,6 +833,14 @@ impl TemplateParser {
         let had_trailing_newline = markdown.ends_with('\n');
 
         let lines: Vec<&str> = markdown.lines().collect();
+        let line_starts: Vec<usize> = markdown
+            .split_inclusive('\n')
+            .scan(0, |start, line| {
+                let line_start = *start;
+                *start += line.len();
+                Some(line_start)
+            })
+            .collect();
         let mut i = 0;
 
         while i < lines.len() {
@@ -276,9 +871,17 @@ impl TemplateParser {
 
             // Not a code block, collect lines until next code block or EOF
             let mut content_section = String::new();
+            let mut section_offsets = offsets.as_ref().map(|_| Vec::new());
             let section_start = i;
 
             while i < lines.len() && !lines[i].trim().starts_with("```") {
+                let line_offsets = offsets
+                    .as_ref()
+                    .map(|o| &o[line_starts[i]..=line_starts[i] + lines[i].len()]);
+                if let (Some(section), Some(line)) = (&mut section_offsets, line_offsets) {
+                    // The joining newline stands for the line's `\n` or `\r\n`
+                    section.extend_from_slice(line);
+                }
                 if i > section_start {
                     content_section.push('\n');
                 }
@@ -287,7 +890,8 @@ impl TemplateParser {
             }
 
             // Process the entire content section (preserves multi-line constructs like frames)
-            let (processed, assets) = self.process_line_with_assets(&content_section)?;
+            let (processed, assets) =
+                self.process_section(&content_section, section_offsets.as_deref())?;
             result.push_str(&processed);
 
             // Add newline after section if not at EOF
@@ -307,138 +911,1262 @@ impl TemplateParser {
         if !had_trailing_newline && result.ends_with('\n') {
             result.pop();
         }
+        if let Some(ref masked) = masked {
+            result = masked.restore(&result);
+        }
+        self.check_output(result.len())?;
 
         Ok(ProcessedMarkdown {
-            markdown: result,
+            markdown: self.apply_markdown_hooks(result)?,
             assets: all_assets,
         })
     }
 
+    /// Run markdown hooks in registration order
+    fn apply_markdown_hooks(&self, mut markdown: String) -> Result<String> {
+        for hook in &self.markdown_hooks {
+            markdown = hook.process_markdown(markdown)?;
+        }
+        Ok(markdown)
+    }
+
+    /// Process a non-fenced section, leaving HTML blocks as written
+    ///
+    /// `offsets`, when spans are wanted, holds the document byte offset of
+    /// each byte of the text plus its end; the functions below take the
+    /// slice for their part of the text.
+    fn process_section(
+        &self,
+        section: &str,
+        offsets: Option<&[usize]>,
+    ) -> Result<(String, Vec<RenderedAsset>)> {
+        if !self.options.preserve_html_blocks {
+            return self.process_line_with_assets(section, offsets);
+        }
+
+        let mut result = String::new();
+        let mut assets = Vec::new();
+        for (is_html, range) in split_html_blocks(section) {
+            if is_html {
+                result.push_str(&section[range]);
+            } else {
+                let part_offsets = offsets.map(|o| &o[range.start..=range.end]);
+                let (processed, new_assets) =
+                    self.process_line_with_assets(&section[range], part_offsets)?;
+                result.push_str(&processed);
+                assets.extend(new_assets);
+            }
+        }
+        Ok((result, assets))
+    }
+
     /// Process a single line, handling inline code markers (with asset collection)
-    fn process_line_with_assets(&self, line: &str) -> Result<(String, Vec<RenderedAsset>)> {
+    fn process_line_with_assets(
+        &self,
+        line: &str,
+        offsets: Option<&[usize]>,
+    ) -> Result<(String, Vec<RenderedAsset>)> {
         // Split by backticks to separate inline code from regular text
         let parts: Vec<&str> = line.split('`').collect();
 
         let mut result = String::new();
         let mut all_assets = Vec::new();
+        let mut start = 0;
 
         for (i, part) in parts.iter().enumerate() {
             if i > 0 {
                 // Add back the backtick separator
                 result.push('`');
+                start += 1;
             }
+            let part_offsets = offsets.map(|o| &o[start..=start + part.len()]);
+            start += part.len();
 
             // Odd indices are inside inline code, even indices are outside
             if i % 2 == 0 {
                 // Outside inline code - process templates
-                let (processed, assets) = self.process_templates_with_assets(part)?;
+                let (processed, assets) = self.process_text(part, part_offsets)?;
                 result.push_str(&processed);
                 all_assets.extend(assets);
             } else {
-                // Inside inline code - preserve as-is
-                result.push_str(part);
+                // Inside inline code - preserve as-is unless opted in
+                let (processed, assets) = self.process_inline_code(part, part_offsets)?;
+                result.push_str(&processed);
+                all_assets.extend(assets);
             }
         }
 
         Ok((result, all_assets))
     }
 
-    /// Process templates in a text segment using state machine (no asset collection)
-    ///
-    /// This uses a character-by-character state machine parser instead of regex
-    /// for better performance and error messages.
-    fn process_templates(&self, text: &str) -> Result<String> {
-        Ok(self.process_templates_with_assets(text)?.0)
-    }
-
-    // ========================================================================
-    // Template Handlers - each returns Option<(output, assets, end_pos)>
-    // ========================================================================
-
-    /// Handle partial template expansion
-    fn handle_partial(
+    /// Process text outside code, leaving math regions as written when
+    /// `preserve_math` is set
+    fn process_text(
         &self,
-        chars: &[char],
-        start: usize,
-    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
-        if let Some(data) = self.parse_partial_at(chars, start)? {
-            if let Some(template) = self.partials.get(&data.partial_name) {
-                let expanded = expand_partial(template, &data.content);
-                let (processed, assets) = self.process_templates_with_assets(&expanded)?;
-                return Ok(Some((processed, assets, data.end_pos)));
+        text: &str,
+        offsets: Option<&[usize]>,
+    ) -> Result<(String, Vec<RenderedAsset>)> {
+        if !self.options.preserve_math {
+            return self.process_templates_at(text, offsets);
+        }
+
+        let mut result = String::new();
+        let mut assets = Vec::new();
+        for (is_math, range) in split_math_regions(text) {
+            if is_math {
+                result.push_str(&text[range]);
+            } else {
+                let part_offsets = offsets.map(|o| &o[range.start..=range.end]);
+                let (processed, new_assets) =
+                    self.process_templates_at(&text[range], part_offsets)?;
+                result.push_str(&processed);
+                assets.extend(new_assets);
             }
         }
-        Ok(None)
+        Ok((result, assets))
     }
 
-    /// Handle UI component expansion
-    fn handle_ui(
+    /// Process the inside of an inline code span: templates opted in with
+    /// `{{!`, plus every other template with `process_inline_code`
+    fn process_inline_code(
         &self,
-        chars: &[char],
-        start: usize,
-    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
-        let Some(data) = self.parse_ui_at(chars, start)? else {
-            return Ok(None);
-        };
+        code: &str,
+        offsets: Option<&[usize]>,
+    ) -> Result<(String, Vec<RenderedAsset>)> {
+        let mut result = String::new();
+        let mut assets = Vec::new();
+        let mut start = 0;
+        let mut search = 0;
 
-        let output = self.components_renderer.expand(
-            &data.component_name,
-            &data.args,
-            data.content.as_deref(),
-        )?;
+        while let Some(pos) = find_force_marker(&code[search..]).map(|p| search + p) {
+            let Some((_, end)) = self.parse_forced_at(code, pos, 0, None)? else {
+                search = pos + 3;
+                continue;
+            };
+            let text_offsets = offsets.map(|o| &o[start..=pos]);
+            let (text, text_assets) = self.process_code_text(&code[start..pos], text_offsets)?;
+            let forced = format!("{{{{{}", &code[pos + 3..end]);
+            let (out, forced_assets) = self.process_templates_with_assets(&forced)?;
+            result.push_str(&text);
+            result.push_str(&out);
+            assets.extend(text_assets);
+            assets.extend(forced_assets);
+            start = end;
+            search = end;
+        }
 
-        let (result, assets) = match output {
-            ComponentOutput::Primitive(primitive) => {
-                let rendered = self.backend.render(&primitive)?;
-                let markdown = rendered.to_markdown().to_string();
-                let assets = if rendered.is_file_based() {
-                    vec![rendered]
-                } else {
-                    vec![]
-                };
-                (markdown, assets)
-            }
-            ComponentOutput::Template(template) => self.process_templates_with_assets(&template)?,
-            ComponentOutput::TemplateDelayed {
-                template,
-                post_process,
-            } => {
-                let (processed, assets) = self.process_templates_with_assets(&template)?;
-                let final_output = match post_process {
-                    PostProcess::Row { align } => ComponentsRenderer::apply_row(&processed, &align),
-                    _ => processed,
-                };
-                (final_output, assets)
-            }
-        };
+        let text_offsets = offsets.map(|o| &o[start..]);
+        let (text, text_assets) = self.process_code_text(&code[start..], text_offsets)?;
+        result.push_str(&text);
+        assets.extend(text_assets);
+        Ok((result, assets))
+    }
 
-        Ok(Some((result, assets, data.end_pos)))
+    /// Inline code text outside forced templates, processed only with
+    /// `process_inline_code`
+    fn process_code_text(
+        &self,
+        text: &str,
+        offsets: Option<&[usize]>,
+    ) -> Result<(String, Vec<RenderedAsset>)> {
+        if self.options.process_inline_code {
+            self.process_templates_at(text, offsets)
+        } else {
+            Ok((text.to_string(), Vec::new()))
+        }
     }
 
-    /// Handle frame template expansion
-    fn handle_frame(
+    /// Parse the template force-marked with `{{!` at byte `pos` of `code`,
+    /// returning its node and end offset in `code`
+    fn parse_forced_at(
         &self,
-        chars: &[char],
-        start: usize,
-    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
-        let Some(data) = self.parse_frame_at(chars, start)? else {
+        code: &str,
+        pos: usize,
+        base: usize,
+        diagnostics: Option<&mut Vec<Diagnostic>>,
+    ) -> Result<Option<(NodeKind, usize)>> {
+        let unmarked = format!("{{{{{}", &code[pos + 3..]);
+        let chars: Vec<char> = unmarked.chars().collect();
+        let bytes: Vec<usize> = unmarked
+            .char_indices()
+            .map(|(b, _)| b)
+            .chain(std::iter::once(unmarked.len()))
+            .collect();
+
+        // Past the `{{`, offsets in `unmarked` are one byte short of `code`
+        let parsed =
+            self.parse_node_at(&unmarked, &chars, &bytes, 0, base + pos + 1, diagnostics)?;
+        Ok(parsed.map(|(kind, end)| (kind, pos + 1 + bytes[end])))
+    }
+
+    // ========================================================================
+    // Syntax tree
+    // ========================================================================
+
+    /// Parse markdown into a template syntax tree
+    ///
+    /// Recognises the same templates as [`process`](Self::process), including
+    /// partials registered with this parser, and keeps code blocks and inline
+    /// code as opaque [`NodeKind::Code`] nodes. See [`crate::ast`].
+    pub fn parse_ast(&self, markdown: &str) -> Result<Vec<Node>> {
+        self.parse_ast_inner(markdown, None)
+    }
+
+    /// Process markdown, collecting every error instead of stopping at the first
+    ///
+    /// Templates that fail to parse or render are kept in the output as
+    /// written and reported as [`Diagnostic`]s; everything else is processed
+    /// normally. When a nested template fails, the diagnostic points at it and
+    /// the enclosing template still renders around the original source.
+    pub fn process_with_diagnostics(&self, markdown: &str) -> RecoveredMarkdown {
+        let mut diagnostics = Vec::new();
+        if let Err(e) = self.check_input(markdown) {
+            let mut diagnostic = Diagnostic::error(e.to_string(), 0..0);
+            diagnostic.locate(markdown);
+            return RecoveredMarkdown {
+                markdown: markdown.to_string(),
+                assets: Vec::new(),
+                diagnostics: vec![diagnostic],
+            };
+        }
+        let _document = match self.enter_document(markdown) {
+            Ok(guard) => Some(guard),
+            Err(e) => {
+                diagnostics.push(Diagnostic::error(e.to_string(), 0..0));
+                None
+            }
+        };
+
+        let nodes = self
+            .parse_ast_inner(markdown, Some(&mut diagnostics))
+            .unwrap_or_default();
+
+        let mut repairs = Vec::new();
+        let mut output = String::new();
+        let mut assets = Vec::new();
+        for node in &nodes {
+            match &node.kind {
+                NodeKind::Text(text) | NodeKind::Code(text) => output.push_str(text),
+                _ => {
+                    let rendered = self.render_node(node).or_else(|e| {
+                        let repaired =
+                            self.repair_node(node, e, markdown, &mut diagnostics, &mut repairs);
+                        self.render_node(&repaired)
+                    });
+                    match rendered {
+                        Ok((out, node_assets)) => {
+                            output.push_str(&out);
+                            assets.extend(node_assets);
+                        }
+                        Err(_) => output.push_str(&verbatim(node, markdown)),
+                    }
+                }
+            }
+        }
+
+        // Put the source of failed templates back in place of their placeholders
+        for (index, source) in repairs.iter().enumerate() {
+            output = output.replace(&repair_placeholder(index), source);
+        }
+
+        let output = match self.apply_markdown_hooks(output.clone()) {
+            Ok(hooked) => hooked,
+            Err(e) => {
+                diagnostics.push(Diagnostic::error(e.to_string(), 0..0));
+                output
+            }
+        };
+
+        for diagnostic in &mut diagnostics {
+            diagnostic.locate(markdown);
+        }
+        diagnostics.sort_by_key(|d| (d.range.start, d.severity));
+
+        RecoveredMarkdown {
+            markdown: output,
+            assets,
+            diagnostics,
+        }
+    }
+
+    /// Every template in the markdown, with the problems rendering it would
+    /// report, without rendering
+    ///
+    /// Templates are listed in source order, including templates nested in
+    /// frames, components, and partials. Each gets the warnings
+    /// [`process_with_diagnostics`](Self::process_with_diagnostics) reports
+    /// for it, and an error when its style, frame, component, glyph, string,
+    /// or snippet doesn't exist or its arguments are invalid. Templates that
+    /// fail to parse are listed from their opening tag. Components are
+    /// checked for existence only, so their output (including live data) is
+    /// never built. See [`crate::analysis`].
+    pub fn analyze(&self, markdown: &str) -> Vec<TemplateUsage> {
+        if self.check_input(markdown).is_err() {
+            return Vec::new();
+        }
+        let mut diagnostics = Vec::new();
+        let nodes = self
+            .parse_ast_inner(markdown, Some(&mut diagnostics))
+            .unwrap_or_default();
+
+        let mut usages = Vec::new();
+        self.collect_usages(&nodes, &mut usages);
+        for diagnostic in diagnostics {
+            match usages
+                .iter_mut()
+                .rfind(|u| u.range.start == diagnostic.range.start)
+            {
+                Some(usage) => usage.diagnostics.push(diagnostic),
+                None => usages.push(TemplateUsage::from_tag(markdown, diagnostic)),
+            }
+        }
+
+        usages.sort_by_key(|u| u.range.start);
+        for usage in &mut usages {
+            usage.diagnostics.sort_by_key(|d| d.severity);
+            usage.locate(markdown);
+        }
+        usages
+    }
+
+    /// Add the usages of `nodes` and their children, in source order
+    fn collect_usages(&self, nodes: &[Node], usages: &mut Vec<TemplateUsage>) {
+        for node in nodes {
+            if let Some(mut usage) = TemplateUsage::from_node(node) {
+                if let Err(e) = self.validate_node(node) {
+                    usage
+                        .diagnostics
+                        .push(Diagnostic::error(e.to_string(), node.range.clone()));
+                }
+                usages.push(usage);
+            }
+            self.collect_usages(node.children(), usages);
+        }
+    }
+
+    /// Check that a template would render, without rendering components
+    /// or nested templates
+    fn validate_node(&self, node: &Node) -> Result<()> {
+        match &node.kind {
+            NodeKind::Style { style, .. } => {
+                if self.registry.style(style).is_none() {
+                    return Err(Error::UnknownStyle(style.clone()));
+                }
+                Ok(())
+            }
+            NodeKind::Frame { spec, .. } => {
+                self.apply_frame(&take_width_modifier(spec)?.0, "")?;
+                Ok(())
+            }
+            NodeKind::Component { name, .. } => {
+                self.check_component_allowed(name)?;
+                if !self.components_renderer.has(name) {
+                    return Err(Error::ParseError(format!(
+                        "Unknown component '{}'. Run `mdfx components list` to see available components.",
+                        name
+                    )));
+                }
+                Ok(())
+            }
+            NodeKind::Shield { .. }
+            | NodeKind::Glyph { .. }
+            | NodeKind::Kbd { .. }
+            | NodeKind::Number { .. } => self.render_node(node).map(|_| ()),
+            NodeKind::Translation { key } => self.translation(key).map(|_| ()),
+            NodeKind::Snippet { path, region, .. } => {
+                self.snippet_code(path, region.as_deref()).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Render one node; templates are rendered from their source form
+    fn render_node(&self, node: &Node) -> Result<(String, Vec<RenderedAsset>)> {
+        match &node.kind {
+            NodeKind::Text(text) | NodeKind::Code(text) => Ok((text.clone(), Vec::new())),
+            NodeKind::InlineCode(children) => {
+                let mut out = String::new();
+                let mut assets = Vec::new();
+                for child in children {
+                    let (text, child_assets) = self.render_node(child)?;
+                    out.push_str(&text);
+                    assets.extend(child_assets);
+                }
+                Ok((out, assets))
+            }
+            _ => self.process_templates_with_assets(&ast::to_source(std::slice::from_ref(node))),
+        }
+    }
+
+    /// Replace failing templates under `node` with placeholders for their
+    /// source text, which is recorded in `repairs`
+    ///
+    /// `err` is the error from rendering `node`. Children are repaired first;
+    /// if that makes `node` render, the failure was theirs. Otherwise `node`
+    /// itself is reported and replaced.
+    fn repair_node(
+        &self,
+        node: &Node,
+        mut err: Error,
+        source: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+        repairs: &mut Vec<String>,
+    ) -> Node {
+        let mut fixed = node.clone();
+        let before = diagnostics.len();
+
+        // Children of a template that is too deep or too large fail the same
+        // way, so only the template itself is reported
+        let limited = matches!(err, Error::LimitExceeded(_));
+        if let Some(children) = fixed.children_mut().filter(|_| !limited) {
+            for child in children.iter_mut().filter(|c| c.is_template()) {
+                if let Err(e) = self.render_node(child) {
+                    *child = self.repair_node(child, e, source, diagnostics, repairs);
+                }
+            }
+        }
+
+        if diagnostics.len() > before {
+            match self.render_node(&fixed) {
+                Ok(_) => return fixed,
+                Err(e) => err = e,
+            }
+        }
+
+        diagnostics.push(Diagnostic::error(err.to_string(), node.range.clone()));
+        repairs.push(verbatim(node, source));
+        Node {
+            kind: NodeKind::Text(repair_placeholder(repairs.len() - 1)),
+            range: node.range.clone(),
+        }
+    }
+
+    /// Shared implementation of [`parse_ast`](Self::parse_ast); with
+    /// `diagnostics`, syntax errors are recorded and the text kept literally
+    fn parse_ast_inner(
+        &self,
+        markdown: &str,
+        mut diagnostics: Option<&mut Vec<Diagnostic>>,
+    ) -> Result<Vec<Node>> {
+        let mut nodes = Vec::new();
+        let mut offset = 0;
+        let mut section_start = 0;
+        let mut fence_start = None;
+
+        for line in markdown.split_inclusive('\n') {
+            let is_fence = line.trim().starts_with("```");
+            match fence_start {
+                None if is_fence => {
+                    self.parse_section(
+                        &markdown[section_start..offset],
+                        section_start,
+                        &mut nodes,
+                        diagnostics.as_deref_mut(),
+                    )?;
+                    fence_start = Some(offset);
+                }
+                Some(start) if is_fence => {
+                    let end = offset + line.len();
+                    push_node(
+                        &mut nodes,
+                        NodeKind::Code(markdown[start..end].to_string()),
+                        start..end,
+                    );
+                    fence_start = None;
+                    section_start = end;
+                }
+                _ => {}
+            }
+            offset += line.len();
+        }
+
+        match fence_start {
+            // Unterminated fence runs to the end, as in `process`
+            Some(start) => push_node(
+                &mut nodes,
+                NodeKind::Code(markdown[start..].to_string()),
+                start..markdown.len(),
+            ),
+            None => self.parse_section(
+                &markdown[section_start..],
+                section_start,
+                &mut nodes,
+                diagnostics,
+            )?,
+        }
+
+        Ok(nodes)
+    }
+
+    /// Render a syntax tree produced by [`parse_ast`](Self::parse_ast)
+    ///
+    /// Text and code are copied as-is; each template node is rendered from
+    /// its [`ast::to_source`] form, so edits to the tree are reflected in
+    /// the output.
+    pub fn render_ast(&self, nodes: &[Node]) -> Result<ProcessedMarkdown> {
+        let mut markdown = String::new();
+        let mut assets = Vec::new();
+        let _document = match nodes.first().map(|node| &node.kind) {
+            Some(NodeKind::Text(text)) => Some(self.enter_document(text)?),
+            _ => None,
+        };
+
+        for node in nodes {
+            match &node.kind {
+                NodeKind::Text(text) | NodeKind::Code(text) => markdown.push_str(text),
+                _ => {
+                    let (out, node_assets) = self.render_node(node)?;
+                    markdown.push_str(&out);
+                    assets.extend(node_assets);
+                }
+            }
+            self.check_output(markdown.len())?;
+        }
+
+        Ok(ProcessedMarkdown {
+            markdown: self.apply_markdown_hooks(markdown)?,
+            assets,
+        })
+    }
+
+    /// Parse a non-fenced section, keeping HTML blocks as code
+    fn parse_section(
+        &self,
+        section: &str,
+        base: usize,
+        nodes: &mut Vec<Node>,
+        mut diagnostics: Option<&mut Vec<Diagnostic>>,
+    ) -> Result<()> {
+        if !self.options.preserve_html_blocks {
+            return self.parse_code_spans(section, base, nodes, diagnostics);
+        }
+
+        for (is_html, range) in split_html_blocks(section) {
+            let text = &section[range.clone()];
+            let start = base + range.start;
+            if is_html {
+                push_node(
+                    nodes,
+                    NodeKind::Code(text.to_string()),
+                    start..base + range.end,
+                );
+            } else {
+                self.parse_code_spans(text, start, nodes, diagnostics.as_deref_mut())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Split text on backticks, parsing text outside inline code
+    fn parse_code_spans(
+        &self,
+        section: &str,
+        base: usize,
+        nodes: &mut Vec<Node>,
+        mut diagnostics: Option<&mut Vec<Diagnostic>>,
+    ) -> Result<()> {
+        let parts: Vec<&str> = section.split('`').collect();
+        let mut start = 0;
+
+        for (i, part) in parts.iter().enumerate() {
+            if i % 2 == 0 {
+                self.parse_text(part, base + start, nodes, diagnostics.as_deref_mut())?;
+            } else {
+                // Inline code owns the backtick before it and the one after, if any
+                let end = start + part.len() + usize::from(i + 1 < parts.len());
+                let code = &section[start - 1..end];
+                let range = base + start - 1..base + end;
+                match self.parse_inline_code(code, range.start, diagnostics.as_deref_mut())? {
+                    Some(children) => nodes.push(Node {
+                        kind: NodeKind::InlineCode(children),
+                        range,
+                    }),
+                    None => push_node(nodes, NodeKind::Code(code.to_string()), range),
+                }
+            }
+            start += part.len() + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Parse text outside code, keeping math regions as code when
+    /// `preserve_math` is set
+    fn parse_text(
+        &self,
+        text: &str,
+        base: usize,
+        nodes: &mut Vec<Node>,
+        mut diagnostics: Option<&mut Vec<Diagnostic>>,
+    ) -> Result<()> {
+        if !self.options.preserve_math {
+            return self.parse_segment(text, base, nodes, diagnostics);
+        }
+
+        for (is_math, range) in split_math_regions(text) {
+            let start = base + range.start;
+            if is_math {
+                let math = text[range.clone()].to_string();
+                push_node(nodes, NodeKind::Code(math), start..base + range.end);
+            } else {
+                self.parse_segment(&text[range], start, nodes, diagnostics.as_deref_mut())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse an inline code span, backticks included, into nodes; `None` if
+    /// no template in it is processed
+    fn parse_inline_code(
+        &self,
+        code: &str,
+        base: usize,
+        mut diagnostics: Option<&mut Vec<Diagnostic>>,
+    ) -> Result<Option<Vec<Node>>> {
+        let mut nodes = Vec::new();
+        let mut start = 0;
+        let mut search = 0;
+
+        while let Some(pos) = find_force_marker(&code[search..]).map(|p| search + p) {
+            let parsed = self.parse_forced_at(code, pos, base, diagnostics.as_deref_mut());
+            let parsed = match (parsed, diagnostics.as_deref_mut()) {
+                (Ok(parsed), _) => parsed,
+                (Err(e), Some(diagnostics)) => {
+                    let tag_end = code[pos..].find("}}").map_or(code.len(), |p| pos + p + 2);
+                    diagnostics.push(Diagnostic::error(e.to_string(), base + pos..base + tag_end));
+                    None
+                }
+                (Err(e), None) => return Err(e),
+            };
+            let Some((kind, end)) = parsed else {
+                search = pos + 3;
+                continue;
+            };
+            self.parse_code_text(
+                &code[start..pos],
+                base + start,
+                &mut nodes,
+                diagnostics.as_deref_mut(),
+            )?;
+            nodes.push(Node {
+                kind,
+                range: base + pos..base + end,
+            });
+            start = end;
+            search = end;
+        }
+
+        self.parse_code_text(&code[start..], base + start, &mut nodes, diagnostics)?;
+        Ok(nodes.iter().any(Node::is_template).then_some(nodes))
+    }
+
+    /// Inline code text outside forced templates, parsed only with
+    /// `process_inline_code`
+    fn parse_code_text(
+        &self,
+        text: &str,
+        base: usize,
+        nodes: &mut Vec<Node>,
+        diagnostics: Option<&mut Vec<Diagnostic>>,
+    ) -> Result<()> {
+        if self.options.process_inline_code {
+            self.parse_segment(text, base, nodes, diagnostics)
+        } else {
+            push_node(
+                nodes,
+                NodeKind::Code(text.to_string()),
+                base..base + text.len(),
+            );
+            Ok(())
+        }
+    }
+
+    /// Parse templates in a text segment starting at byte offset `base`
+    fn parse_segment(
+        &self,
+        text: &str,
+        base: usize,
+        nodes: &mut Vec<Node>,
+        mut diagnostics: Option<&mut Vec<Diagnostic>>,
+    ) -> Result<()> {
+        let text = self.expand_close_all(text);
+        let chars: Vec<char> = text.chars().collect();
+        let bytes: Vec<usize> = text
+            .char_indices()
+            .map(|(b, _)| b)
+            .chain(std::iter::once(text.len()))
+            .collect();
+
+        let mut text_start = 0;
+        let mut i = 0;
+        while i < chars.len() {
+            if chars[i] == '\\' || (i + 1 < chars.len() && chars[i] == '{' && chars[i + 1] == '{') {
+                let parsed =
+                    self.parse_node_at(&text, &chars, &bytes, i, base, diagnostics.as_deref_mut());
+                let parsed = match (parsed, diagnostics.as_deref_mut()) {
+                    (Ok(parsed), _) => parsed,
+                    (Err(e), Some(diagnostics)) => {
+                        // Report the opening tag and keep scanning inside it
+                        let tag_end = text[bytes[i]..]
+                            .find("}}")
+                            .map_or(text.len(), |p| bytes[i] + p + 2);
+                        let limited = matches!(e, Error::LimitExceeded(_));
+                        diagnostics.push(Diagnostic::error(
+                            e.to_string(),
+                            base + bytes[i]..base + tag_end,
+                        ));
+                        if limited {
+                            // Anything further in would hit the same limit
+                            break;
+                        }
+                        None
+                    }
+                    (Err(e), None) => return Err(e),
+                };
+                if let Some((kind, end)) = parsed {
+                    if text_start < i {
+                        let literal = text[bytes[text_start]..bytes[i]].to_string();
+                        push_node(
+                            nodes,
+                            NodeKind::Text(literal),
+                            base + bytes[text_start]..base + bytes[i],
+                        );
+                    }
+                    nodes.push(Node {
+                        kind,
+                        range: base + bytes[i]..base + bytes[end],
+                    });
+                    i = end;
+                    text_start = end;
+                    continue;
+                }
+            }
+            i += 1;
+        }
+
+        if text_start < chars.len() {
+            let literal = text[bytes[text_start]..].to_string();
+            push_node(
+                nodes,
+                NodeKind::Text(literal),
+                base + bytes[text_start]..base + text.len(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Parse nested template content, offsetting ranges to its position in `text`
+    fn parse_children(
+        &self,
+        text: &str,
+        bytes: &[usize],
+        start: usize,
+        base: usize,
+        content: &str,
+        diagnostics: Option<&mut Vec<Diagnostic>>,
+    ) -> Result<Vec<Node>> {
+        let _depth = self.enter_expansion()?;
+        let mut nodes = Vec::new();
+        let offset = content_offset(&text[bytes[start]..], content);
+        self.parse_segment(
+            content,
+            base + bytes[start] + offset,
+            &mut nodes,
+            diagnostics,
+        )?;
+        Ok(nodes)
+    }
+
+    /// Warn about parameters a component would ignore or replace with its default
+    fn check_params(
+        &self,
+        component: &str,
+        args: &[String],
+        range: std::ops::Range<usize>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        // Plugins reject unknown parameters themselves
+        if self.components_renderer.is_plugin(component) {
+            return;
+        }
+        let Some(schema) = ParamSchema::new(&self.registry, component) else {
+            return;
+        };
+        for issue in schema.validate(args) {
+            diagnostics.push(Diagnostic::warning(issue.to_string(), range.clone()));
+        }
+    }
+
+    /// Warn about characters a style template passes through unstyled
+    ///
+    /// Only templates without an explicit `fallback=` are reported; the
+    /// other policies already say what happens to such characters.
+    fn check_unmapped(
+        &self,
+        data: &TemplateData,
+        range: std::ops::Range<usize>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        if data.fallback.is_some() {
+            return;
+        }
+        // Unknown styles are reported when the template is rendered
+        let Ok(unmapped) = self.converter.unmapped_chars(&data.content, &data.style) else {
+            return;
+        };
+        if unmapped.is_empty() {
+            return;
+        }
+        let chars: Vec<String> = unmapped.iter().map(|c| format!("'{}'", c)).collect();
+        diagnostics.push(Diagnostic::warning(
+            format!(
+                "Style '{}' has no equivalent for {}; left unstyled (set fallback=skip, error, or closest)",
+                data.style,
+                chars.join(", ")
+            ),
+            range,
+        ));
+    }
+
+    /// Try each template parser in handler priority order
+    fn parse_node_at(
+        &self,
+        text: &str,
+        chars: &[char],
+        bytes: &[usize],
+        start: usize,
+        base: usize,
+        mut diagnostics: Option<&mut Vec<Diagnostic>>,
+    ) -> Result<Option<(NodeKind, usize)>> {
+        if let Some(data) = self.parse_comment_at(chars, start)? {
+            return Ok(Some((NodeKind::Comment(data.text), data.end_pos)));
+        }
+        if let Some(data) = self.parse_raw_at(chars, start)? {
+            return Ok(Some((NodeKind::Raw(data.content), data.end_pos)));
+        }
+        if let Some(data) = self.parse_partial_at(chars, start)? {
+            if self.partials.contains_key(&data.partial_name) {
+                let kind = NodeKind::Partial {
+                    children: self.parse_children(
+                        text,
+                        bytes,
+                        start,
+                        base,
+                        &data.content,
+                        diagnostics,
+                    )?,
+                    name: data.partial_name,
+                };
+                return Ok(Some((kind, data.end_pos)));
+            }
+            if let Some(diagnostics) = diagnostics.as_deref_mut() {
+                let tag_end = text[bytes[start]..]
+                    .find("}}")
+                    .map_or(text.len(), |p| bytes[start] + p + 2);
+                diagnostics.push(Diagnostic::warning(
+                    format!("Unknown partial '{}', left as text", data.partial_name),
+                    base + bytes[start]..base + tag_end,
+                ));
+            }
+        }
+        if let Some(data) = self.parse_ui_at(chars, start)? {
+            if let Some(diagnostics) = diagnostics.as_deref_mut() {
+                let tag_end = text[bytes[start]..]
+                    .find("}}")
+                    .map_or(text.len(), |p| bytes[start] + p + 2);
+                self.check_params(
+                    &data.component_name,
+                    &data.args,
+                    base + bytes[start]..base + tag_end,
+                    diagnostics,
+                );
+            }
+            let kind = NodeKind::Component {
+                children: data
+                    .content
+                    .as_deref()
+                    .map(|content| {
+                        self.parse_children(text, bytes, start, base, content, diagnostics)
+                    })
+                    .transpose()?,
+                name: data.component_name,
+                args: data.args,
+            };
+            return Ok(Some((kind, data.end_pos)));
+        }
+        if let Some(data) = self.parse_frame_at(chars, start)? {
+            let kind = NodeKind::Frame {
+                children: self.parse_children(
+                    text,
+                    bytes,
+                    start,
+                    base,
+                    &data.content,
+                    diagnostics,
+                )?,
+                spec: data.frame_style,
+            };
+            return Ok(Some((kind, data.end_pos)));
+        }
+        if let Some(data) = self.parse_shields_at(chars, start)? {
+            let kind = NodeKind::Shield {
+                shield_type: data.shield_type,
+                params: data.params.into_iter().collect(),
+            };
+            return Ok(Some((kind, data.end_pos)));
+        }
+        if let Some(data) = self.parse_glyph_at(chars, start)? {
+            let kind = NodeKind::Glyph {
+                name: data.glyph_name,
+            };
+            return Ok(Some((kind, data.end_pos)));
+        }
+        if let Some(data) = self.parse_kbd_at(chars, start)? {
+            return Ok(Some((NodeKind::Kbd { keys: data.keys }, data.end_pos)));
+        }
+        if let Some(data) = self.parse_num_at(chars, start)? {
+            if let Some(diagnostics) = diagnostics {
+                // Invalid formats and values are reported when rendered
+                if let Ok((format, n)) = parse_num(&data) {
+                    if format.format(n).is_none() {
+                        diagnostics.push(Diagnostic::warning(
+                            format!(
+                                "{} has no {} form; written as plain digits",
+                                n,
+                                format.as_str()
+                            ),
+                            base + bytes[start]..base + bytes[data.end_pos],
+                        ));
+                    }
+                }
+            }
+            let kind = NodeKind::Number {
+                format: data.format,
+                value: data.value,
+            };
+            return Ok(Some((kind, data.end_pos)));
+        }
+        if let Some(data) = self.parse_t_at(chars, start)? {
+            return Ok(Some((
+                NodeKind::Translation { key: data.key },
+                data.end_pos,
+            )));
+        }
+        if let Some(data) = self.parse_snippet_at(chars, start)? {
+            let kind = NodeKind::Snippet {
+                path: data.path,
+                region: data.region,
+                lang: data.lang,
+            };
+            return Ok(Some((kind, data.end_pos)));
+        }
+        if let Some(data) = self.parse_template_at(chars, start)? {
+            if let Some(diagnostics) = diagnostics {
+                let tag_end = text[bytes[start]..]
+                    .find("}}")
+                    .map_or(text.len(), |p| bytes[start] + p + 2);
+                self.check_unmapped(&data, base + bytes[start]..base + tag_end, diagnostics);
+            }
+            let kind = NodeKind::Style {
+                style: data.style,
+                spacing: data.spacing,
+                separator: data.separator,
+                fallback: data.fallback,
+                seed: data.seed,
+                content: data.content,
+            };
+            return Ok(Some((kind, data.end_pos)));
+        }
+        Ok(None)
+    }
+
+    /// Process templates in a text segment using state machine (no asset collection)
+    ///
+    /// This uses a character-by-character state machine parser instead of regex
+    /// for better performance and error messages.
+    fn process_templates(&self, text: &str) -> Result<String> {
+        Ok(self.process_templates_with_assets(text)?.0)
+    }
+
+    // ========================================================================
+    // Template Handlers - each returns Option<(output, assets, end_pos)>
+    // ========================================================================
+
+    /// Handle partial template expansion
+    fn handle_partial(
+        &self,
+        chars: &[char],
+        start: usize,
+    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
+        if let Some(data) = self.parse_partial_at(chars, start)? {
+            if let Some(template) = self.partials.get(&data.partial_name) {
+                self.enter_partial(&data.partial_name)?;
+                let expanded = expand_partial(template, &data.content);
+                let result = self.process_templates_with_assets(&expanded);
+                self.partial_stack.borrow_mut().pop();
+                let (processed, assets) = result?;
+                return Ok(Some((processed, assets, data.end_pos)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Handle UI component expansion
+    fn handle_ui(
+        &self,
+        chars: &[char],
+        start: usize,
+    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
+        let Some(data) = self.parse_ui_at(chars, start)? else {
             return Ok(None);
         };
+        self.check_component_allowed(&data.component_name)?;
 
-        // Process content recursively
-        let (content, assets) = self.process_templates_with_assets(&data.content)?;
+        let args = self.expand_param_styles(&data.args)?;
+        let (display, args) = split_display_size(args)?;
+        let output = self.components_renderer.expand(
+            &data.component_name,
+            &args,
+            data.content.as_deref(),
+        )?;
+        let is_image = matches!(
+            output,
+            ComponentOutput::Primitive(_)
+                | ComponentOutput::TemplateDelayed {
+                    post_process: PostProcess::Group(_),
+                    ..
+                }
+        );
+        if !display.is_empty() && !is_image {
+            return Err(Error::ParseError(format!(
+                "display_width and display_height only apply to image components, not '{}'",
+                data.component_name
+            )));
+        }
 
-        let framed = if data.frame_style.starts_with("glyph:") {
-            self.apply_glyph_frame(&data.frame_style[6..], &content)?
-        } else if data.frame_style.contains('+') {
-            self.apply_combo_frame(&data.frame_style, &content)?
+        let (mut result, mut assets) = match output {
+            ComponentOutput::Primitive(primitive) => {
+                self.render_primitive(&primitive, self.is_dynamic_component(&data.component_name))?
+            }
+            ComponentOutput::Template(template) => self.process_templates_with_assets(&template)?,
+            ComponentOutput::TemplateDelayed {
+                post_process: PostProcess::Group(layout),
+                template,
+            } => self.render_group(&template, layout)?,
+            ComponentOutput::TemplateDelayed {
+                post_process: PostProcess::Matrix(table),
+                ..
+            } => self.render_matrix(&table)?,
+            ComponentOutput::TemplateDelayed {
+                template,
+                post_process,
+            } => {
+                let (processed, assets) = self.process_templates_with_assets(&template)?;
+                let final_output = match post_process {
+                    PostProcess::Row { align } => ComponentsRenderer::apply_row(&processed, &align),
+                    PostProcess::Center if self.html => {
+                        format!("<div align=\"center\">\n\n{}\n\n</div>", processed)
+                    }
+                    PostProcess::AppendRaw(raw) => format!("{}\n\n{}", processed, raw),
+                    _ => processed,
+                };
+                (final_output, assets)
+            }
+        };
+
+        // Scale the reference, not the asset, so every size shares one file
+        if !display.is_empty() {
+            display.apply(
+                &mut result,
+                assets.first().and_then(RenderedAsset::file_bytes),
+                self.html,
+            );
+            if let Some(asset) = assets.first_mut() {
+                asset.markdown_mut().clone_from(&result);
+            }
+        }
+
+        Ok(Some((result, assets, data.end_pos)))
+    }
+
+    /// Expand style templates in `key=value` arguments, so labels can use
+    /// Unicode styling
+    ///
+    /// Only style templates are expanded; anything else in a value is passed
+    /// to the component as written.
+    fn expand_param_styles(&self, args: &[String]) -> Result<Vec<String>> {
+        args.iter()
+            .map(|arg| match arg.split_once('=') {
+                Some((key, value)) if value.contains("{{") => {
+                    let chars: Vec<char> = value.chars().collect();
+                    let mut expanded = String::new();
+                    let mut i = 0;
+                    while i < chars.len() {
+                        if self.nested_style_end(&chars, i)?.is_some() {
+                            if let Some((styled, _, end)) = self.handle_style(&chars, i)? {
+                                expanded.push_str(&styled);
+                                i = end;
+                                continue;
+                            }
+                        }
+                        expanded.push(chars[i]);
+                        i += 1;
+                    }
+                    Ok(format!("{}={}", key, expanded))
+                }
+                _ => Ok(arg.clone()),
+            })
+            .collect()
+    }
+
+    /// Render a primitive with the backend and run asset hooks on it
+    fn render_primitive(
+        &self,
+        primitive: &Primitive,
+        live: bool,
+    ) -> Result<(String, Vec<RenderedAsset>)> {
+        let started = Instant::now();
+        let mut rendered = if live {
+            self.backend.render_live(primitive)?
+        } else {
+            self.backend.render(primitive)?
+        };
+        if !self.observers.is_empty() {
+            let event = RenderEvent {
+                primitive: primitive.kind(),
+                started,
+                elapsed: started.elapsed(),
+            };
+            for observer in &self.observers {
+                observer.primitive_rendered(&event);
+            }
+        }
+        for hook in &self.asset_hooks {
+            hook.process_asset(&mut rendered)?;
+        }
+        let markdown = rendered.to_markdown().to_string();
+        let assets = if rendered.is_file_based() {
+            vec![rendered]
         } else {
-            self.apply_standard_frame(&data.frame_style, &content)?
+            vec![]
+        };
+        Ok((markdown, assets))
+    }
+
+    /// Expand the badges in a tech-group layout and render them as one group
+    fn render_group(
+        &self,
+        template: &str,
+        layout: GroupLayout,
+    ) -> Result<(String, Vec<RenderedAsset>)> {
+        let chars: Vec<char> = template.chars().collect();
+        let not_a_badge = |pos: usize| {
+            let found: String = chars[pos..].iter().take(30).collect();
+            Error::ParseError(format!(
+                "tech-group and statcard-row layouts can only contain components, found '{}'",
+                found.trim_end()
+            ))
         };
+        let mut items = Vec::new();
+        let mut pos = 0;
+
+        while pos < chars.len() {
+            if chars[pos].is_whitespace() {
+                pos += 1;
+                continue;
+            }
+            let Some(data) = self.parse_ui_at(&chars, pos)? else {
+                return Err(not_a_badge(pos));
+            };
+            self.check_component_allowed(&data.component_name)?;
+            let output = self.components_renderer.expand(
+                &data.component_name,
+                &data.args,
+                data.content.as_deref(),
+            )?;
+            let ComponentOutput::Primitive(primitive) = output else {
+                return Err(not_a_badge(pos));
+            };
+            items.push(primitive);
+            pos = data.end_pos;
+        }
+
+        self.render_primitive(&Primitive::Group { items, layout }, false)
+    }
+
+    /// Render a comparison matrix as a markdown table
+    fn render_matrix(&self, table: &MatrixTable) -> Result<(String, Vec<RenderedAsset>)> {
+        let mut assets = Vec::new();
+        let mut header = Vec::new();
+        for cell in &table.header {
+            let (rendered, cell_assets) = self.process_templates_with_assets(cell)?;
+            header.push(rendered);
+            assets.extend(cell_assets);
+        }
+
+        let mut rows = Vec::new();
+        for row in &table.rows {
+            let mut cells = Vec::new();
+            for cell in row {
+                let (rendered, cell_assets) = match cell {
+                    MatrixCell::Text(template) => self.process_templates_with_assets(template)?,
+                    MatrixCell::Mark { text, .. } if !self.backend.renders_images() => {
+                        (text.to_string(), Vec::new())
+                    }
+                    MatrixCell::Mark { badge, .. } => self.render_primitive(badge, false)?,
+                };
+                cells.push(rendered);
+                assets.extend(cell_assets);
+            }
+            rows.push(cells);
+        }
+
+        Ok((
+            ComponentsRenderer::matrix_to_markdown(&header, &rows),
+            assets,
+        ))
+    }
+
+    /// Handle frame template expansion
+    fn handle_frame(
+        &self,
+        chars: &[char],
+        start: usize,
+    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
+        let Some(data) = self.parse_frame_at(chars, start)? else {
+            return Ok(None);
+        };
+
+        // Process content recursively
+        let (content, assets) = self.process_templates_with_assets(&data.content)?;
+
+        let (spec, width) = take_width_modifier(&data.frame_style)?;
+        let mut framed = self.apply_frame(&spec, &content)?;
+
+        // Center the content so the framed text fills `width` columns
+        if let Some(width) = width {
+            let frame_width = display_width(&framed).saturating_sub(display_width(&content));
+            if width > display_width(&framed) {
+                self.check_padding(1, content.len().saturating_add(width))?;
+                let padded = width::center(&content, width.saturating_sub(frame_width));
+                framed = self.apply_frame(&spec, &padded)?;
+            }
+        }
 
         Ok(Some((framed, assets, data.end_pos)))
     }
 
+    /// Apply a frame spec (without `/width=`) to rendered content
+    fn apply_frame(&self, spec: &str, content: &str) -> Result<String> {
+        if let Some(glyph_spec) = spec.strip_prefix("glyph:") {
+            self.apply_glyph_frame(glyph_spec, content)
+        } else if spec.contains('+') {
+            self.apply_combo_frame(spec, content)
+        } else {
+            self.apply_standard_frame(spec, content)
+        }
+    }
+
     /// Handle shields template (escape hatch)
     fn handle_shields(
         &self,
@@ -524,25 +2252,125 @@ impl TemplateParser {
         };
 
         let glyph_char = self
-            .registry
-            .glyph(&data.glyph_name)
+            .lookup_glyph(&data.glyph_name)
             .ok_or_else(|| Error::UnknownGlyph(data.glyph_name.clone()))?;
 
         Ok(Some((text_style(glyph_char), vec![], data.end_pos)))
     }
 
-    /// Handle kbd template
-    fn handle_kbd(
+    /// Handle kbd template
+    fn handle_kbd(
+        &self,
+        chars: &[char],
+        start: usize,
+    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
+        let Some(data) = self.parse_kbd_at(chars, start)? else {
+            return Ok(None);
+        };
+
+        let expanded = self.expand_kbd(&data.keys);
+        Ok(Some((expanded, vec![], data.end_pos)))
+    }
+
+    /// Handle number template: the value in the requested Unicode form
+    fn handle_num(
+        &self,
+        chars: &[char],
+        start: usize,
+    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
+        let Some(data) = self.parse_num_at(chars, start)? else {
+            return Ok(None);
+        };
+
+        let (format, n) = parse_num(&data)?;
+        let formatted = format.format(n).unwrap_or_else(|| n.to_string());
+        Ok(Some((formatted, vec![], data.end_pos)))
+    }
+
+    /// Handle translated string template: the string for the key, with its
+    /// templates expanded
+    fn handle_translation(
+        &self,
+        chars: &[char],
+        start: usize,
+    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
+        let Some(data) = self.parse_t_at(chars, start)? else {
+            return Ok(None);
+        };
+
+        let text = self.translation(&data.key)?;
+        let (expanded, assets) = self.process_templates_with_assets(text)?;
+        Ok(Some((expanded, assets, data.end_pos)))
+    }
+
+    /// The string for a `{{t:key/}}` template
+    fn translation(&self, key: &str) -> Result<&String> {
+        self.strings.get(key).ok_or_else(|| {
+            Error::ParseError(format!(
+                "Unknown string '{}' in {{{{t:{}/}}}}: add it to the string file",
+                key, key
+            ))
+        })
+    }
+
+    /// Handle source snippet template: the region of a project file in a
+    /// code fence
+    fn handle_snippet(
+        &self,
+        chars: &[char],
+        start: usize,
+    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
+        let Some(data) = self.parse_snippet_at(chars, start)? else {
+            return Ok(None);
+        };
+
+        let code = self.snippet_code(&data.path, data.region.as_deref())?;
+        let lang = data
+            .lang
+            .as_deref()
+            .unwrap_or_else(|| snippet::language(&data.path));
+        Ok(Some((snippet::fenced(&code, lang), vec![], data.end_pos)))
+    }
+
+    /// The code of a snippet template: the region of the project file, or
+    /// the whole file without region markers
+    fn snippet_code(&self, path: &str, region: Option<&str>) -> Result<String> {
+        if !self.options.allow_files {
+            return Err(Error::NotAllowed(format!(
+                "snippet '{}' reads a project file, which is disabled",
+                path
+            )));
+        }
+        let source = self
+            .components_renderer
+            .project_file(path)
+            .ok_or_else(|| Error::ParseError(format!("snippet file '{}' not found", path)))?;
+        match region {
+            Some(region) => snippet::extract_region(&source, region),
+            None => Ok(snippet::strip_markers(&source)),
+        }
+    }
+
+    /// Handle comment template: removed from the output
+    fn handle_comment(
+        &self,
+        chars: &[char],
+        start: usize,
+    ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
+        Ok(self
+            .parse_comment_at(chars, start)?
+            .map(|data| (String::new(), vec![], data.end_pos)))
+    }
+
+    /// Handle raw block: content is emitted as written
+    fn handle_raw(
         &self,
         chars: &[char],
         start: usize,
     ) -> Result<Option<(String, Vec<RenderedAsset>, usize)>> {
-        let Some(data) = self.parse_kbd_at(chars, start)? else {
-            return Ok(None);
-        };
-
-        let expanded = self.expand_kbd(&data.keys);
-        Ok(Some((expanded, vec![], data.end_pos)))
+        Ok(self
+            .parse_raw_at(chars, start)?
+            .map(|data| (data.content, vec![], data.end_pos)))
     }
 
     /// Handle style template
@@ -559,30 +2387,61 @@ impl TemplateParser {
             return Err(Error::UnknownStyle(data.style));
         }
 
-        let converted = if let Some(ref sep) = data.separator {
-            self.converter
-                .convert_with_separator(&data.content, &data.style, sep, 1)?
-        } else if data.spacing > 0 {
-            self.converter
-                .convert_with_spacing(&data.content, &data.style, data.spacing)?
+        let (spacing, separator) = if data.separator.is_none() && data.spacing == 0 {
+            self.default_spacing(&data.style)?
+        } else {
+            (data.spacing, data.separator)
+        };
+
+        let converter = self
+            .converter
+            .clone()
+            .with_fallback(data.fallback.unwrap_or_default())
+            .with_seed(data.seed.unwrap_or_default());
+        let converted = if let Some(ref sep) = separator {
+            converter.convert_with_separator(&data.content, &data.style, sep, 1)?
+        } else if spacing > 0 {
+            self.check_padding(spacing, data.content.chars().count())?;
+            converter.convert_with_spacing(&data.content, &data.style, spacing)?
         } else {
-            self.converter.convert(&data.content, &data.style)?
+            converter.convert(&data.content, &data.style)?
         };
 
         Ok(Some((converted, vec![], data.end_pos)))
     }
 
+    /// Spacing and resolved separator for a style template that sets
+    /// neither, from front matter or else the config
+    fn default_spacing(&self, style: &str) -> Result<(usize, Option<String>)> {
+        let id = self.style_id(style);
+        let document = self.document_styles.borrow();
+        let Some(defaults) = document.get(&id).or_else(|| self.style_defaults.get(&id)) else {
+            return Ok((0, None));
+        };
+        let separator = defaults
+            .separator
+            .as_deref()
+            .map(|sep| self.resolve_separator(sep))
+            .transpose()?;
+        Ok((defaults.spacing.unwrap_or(0), separator))
+    }
+
     // ========================================================================
     // Frame application helpers
     // ========================================================================
 
     /// Apply glyph-based frame (e.g., glyph:star*3/pad=0)
     fn apply_glyph_frame(&self, spec: &str, content: &str) -> Result<String> {
-        let (glyph_name, count, pad, separator, spacing) = Self::parse_glyph_frame_spec(spec);
+        let GlyphFrameSpec {
+            glyph: glyph_name,
+            count,
+            pad,
+            separator,
+            spacing,
+        } = self.parse_glyph_frame_spec(spec)?;
 
         let glyph_raw = self
-            .registry
-            .glyph(&glyph_name)
+            .lookup_glyph(&glyph_name)
             .ok_or_else(|| Error::UnknownGlyph(glyph_name.clone()))?;
 
         // Apply text-style (VS15) to force glyph rendering, not emoji
@@ -595,6 +2454,7 @@ impl TemplateParser {
                 .collect::<Vec<_>>()
                 .join(sep_char)
         } else if let Some(n) = spacing {
+            self.check_padding(n, count)?;
             let spaces = " ".repeat(n);
             (0..count)
                 .map(|_| glyph_char.as_str())
@@ -680,6 +2540,7 @@ impl TemplateParser {
                     .map(|s| s.to_string())
                     .unwrap_or_else(|| sep.to_string())
             } else if let Some(n) = mods.spacing {
+                self.check_padding(n, prefix.len() + suffix.len())?;
                 " ".repeat(n)
             } else {
                 String::new()
@@ -724,56 +2585,97 @@ impl TemplateParser {
     // Main parsing loop
     // ========================================================================
 
+    /// Template handlers in priority order, labelled for instrumentation
+    const HANDLERS: [(&'static str, TemplateHandler); 12] = [
+        ("comment", Self::handle_comment),
+        ("raw", Self::handle_raw),
+        ("partial", Self::handle_partial),
+        ("ui", Self::handle_ui),
+        ("frame", Self::handle_frame),
+        ("shields", Self::handle_shields),
+        ("glyph", Self::handle_glyph),
+        ("kbd", Self::handle_kbd),
+        ("num", Self::handle_num),
+        ("t", Self::handle_translation),
+        ("snippet", Self::handle_snippet),
+        ("style", Self::handle_style),
+    ];
+
     /// Process templates in a text segment with asset collection
     fn process_templates_with_assets(&self, text: &str) -> Result<(String, Vec<RenderedAsset>)> {
-        let text = self.expand_close_all(text);
+        self.process_templates_at(text, None)
+    }
+
+    /// Process templates in a text segment, reporting spans from `offsets`,
+    /// the document byte offset of each byte of `text` plus its end
+    fn process_templates_at(
+        &self,
+        text: &str,
+        offsets: Option<&[usize]>,
+    ) -> Result<(String, Vec<RenderedAsset>)> {
+        let _depth = self.enter_expansion()?;
+        let (expanded, sources) = self.expand_close_all_mapped(text);
+        // Byte offset in the document of each char, when spans are known
+        let byte_offsets: Option<Vec<usize>> = offsets.map(|offsets| {
+            expanded
+                .char_indices()
+                .map(|(b, _)| b)
+                .chain(std::iter::once(expanded.len()))
+                .map(|b| offsets[sources[b]])
+                .collect()
+        });
+        let text = expanded;
         let chars: Vec<char> = text.chars().collect();
         let mut result = String::new();
         let mut assets = Vec::new();
         let mut i = 0;
 
         while i < chars.len() {
+            // An escaped `\{{` is literal
+            if chars[i] == '\\' {
+                if let Some(data) = self.parse_raw_at(&chars, i)? {
+                    result.push_str(&data.content);
+                    i = data.end_pos;
+                    continue;
+                }
+            }
+
             // Check for template start
             if i + 1 < chars.len() && chars[i] == '{' && chars[i + 1] == '{' {
                 // Try each handler in priority order
-                if let Some((out, new_assets, end)) = self.handle_partial(&chars, i)? {
-                    result.push_str(&out);
-                    assets.extend(new_assets);
-                    i = end;
-                    continue;
-                }
-                if let Some((out, new_assets, end)) = self.handle_ui(&chars, i)? {
-                    result.push_str(&out);
-                    assets.extend(new_assets);
-                    i = end;
-                    continue;
-                }
-                if let Some((out, new_assets, end)) = self.handle_frame(&chars, i)? {
-                    result.push_str(&out);
-                    assets.extend(new_assets);
-                    i = end;
-                    continue;
-                }
-                if let Some((out, new_assets, end)) = self.handle_shields(&chars, i)? {
-                    result.push_str(&out);
-                    assets.extend(new_assets);
-                    i = end;
-                    continue;
-                }
-                if let Some((out, new_assets, end)) = self.handle_glyph(&chars, i)? {
-                    result.push_str(&out);
-                    assets.extend(new_assets);
-                    i = end;
-                    continue;
-                }
-                if let Some((out, new_assets, end)) = self.handle_kbd(&chars, i)? {
-                    result.push_str(&out);
-                    assets.extend(new_assets);
-                    i = end;
-                    continue;
+                let started = Instant::now();
+                let mut handled = None;
+                for (kind, handler) in Self::HANDLERS {
+                    if let Some(expansion) = handler(self, &chars, i)? {
+                        handled = Some((kind, expansion));
+                        break;
+                    }
                 }
-                if let Some((out, new_assets, end)) = self.handle_style(&chars, i)? {
+
+                if let Some((kind, (out, new_assets, end))) = handled {
+                    let elapsed = started.elapsed();
+                    debug!(
+                        kind,
+                        template = %chars[i..end].iter().collect::<String>(),
+                        elapsed_us = elapsed.as_micros() as u64,
+                        "expanded template"
+                    );
+                    if !self.observers.is_empty() {
+                        let source: String = chars[i..end].iter().collect();
+                        let event = TemplateEvent {
+                            kind,
+                            name: template_name(kind, &source),
+                            source: &source,
+                            span: byte_offsets.as_ref().map(|o| o[i]..o[end]),
+                            started,
+                            elapsed,
+                        };
+                        for observer in &self.observers {
+                            observer.template_expanded(&event);
+                        }
+                    }
                     result.push_str(&out);
+                    self.check_output(result.len())?;
                     assets.extend(new_assets);
                     i = end;
                     continue;
@@ -788,14 +2690,140 @@ impl TemplateParser {
         Ok((result, assets))
     }
 
+    // ========================================================================
+    // Limits
+    // ========================================================================
+
+    /// Count one level of template expansion, failing past `max_depth`
+    fn enter_expansion(&self) -> Result<DepthGuard<'_>> {
+        let depth = self.depth.get() + 1;
+        if let Some(max) = self.options.max_depth {
+            if depth > max {
+                return Err(Error::LimitExceeded(format!(
+                    "templates nested more than {} levels deep",
+                    max
+                )));
+            }
+        }
+        self.depth.set(depth);
+        Ok(DepthGuard(&self.depth))
+    }
+
+    /// Push a partial onto the expansion stack, failing if it is already
+    /// being expanded
+    fn enter_partial(&self, name: &str) -> Result<()> {
+        let mut stack = self.partial_stack.borrow_mut();
+        if stack.iter().any(|p| p == name) {
+            let chain: Vec<&str> = stack
+                .iter()
+                .map(String::as_str)
+                .skip_while(|p| *p != name)
+                .chain(std::iter::once(name))
+                .collect();
+            return Err(Error::LimitExceeded(format!(
+                "partial '{}' includes itself ({})",
+                name,
+                chain.join(" → ")
+            )));
+        }
+        stack.push(name.to_string());
+        Ok(())
+    }
+
+    fn check_input(&self, markdown: &str) -> Result<()> {
+        match self.options.max_input_bytes {
+            Some(max) if markdown.len() > max => Err(Error::LimitExceeded(format!(
+                "input is {} bytes, the limit is {}",
+                markdown.len(),
+                max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_output(&self, len: usize) -> Result<()> {
+        match self.options.max_output_bytes {
+            Some(max) if len > max => Err(Error::LimitExceeded(format!(
+                "output grew past {} bytes",
+                max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check `count` runs of `unit` bytes of padding against the output
+    /// limit before building them
+    ///
+    /// Without a limit, the default one still applies, so a huge count
+    /// fails instead of exhausting memory.
+    fn check_padding(&self, unit: usize, count: usize) -> Result<()> {
+        let max = self
+            .options
+            .max_output_bytes
+            .unwrap_or(crate::options::DEFAULT_MAX_OUTPUT_BYTES);
+        match unit.checked_mul(count) {
+            Some(len) if len <= max => Ok(()),
+            _ => Err(Error::LimitExceeded(format!(
+                "padding would grow the output past {} bytes",
+                max
+            ))),
+        }
+    }
+
+    /// Whether a component renders live data fetched over the network
+    fn is_dynamic_component(&self, name: &str) -> bool {
+        self.components_renderer
+            .get(name)
+            .is_some_and(|c| c.component_type == "dynamic")
+    }
+
+    /// Reject components disabled by the parser options
+    fn check_component_allowed(&self, name: &str) -> Result<()> {
+        if !self.options.allow_plugins && self.components_renderer.is_plugin(name) {
+            return Err(Error::NotAllowed(format!(
+                "plugin component '{}' is disabled",
+                name
+            )));
+        }
+        if !self.options.allow_network && self.is_dynamic_component(name) {
+            return Err(Error::NotAllowed(format!(
+                "component '{}' needs network access, which is disabled",
+                name
+            )));
+        }
+        if !self.options.allow_files && ComponentsRenderer::reads_project_files(name) {
+            return Err(Error::NotAllowed(format!(
+                "component '{}' reads project files, which is disabled",
+                name
+            )));
+        }
+        Ok(())
+    }
+
     /// Pre-process text to expand {{//}} into appropriate closing tags
     ///
     /// This scans for all open tags (frames, styles, UI components) and when
     /// {{//}} is encountered, replaces it with the appropriate closing tags
     /// in reverse order (LIFO).
     fn expand_close_all(&self, text: &str) -> String {
+        self.expand_close_all_mapped(text).0
+    }
+
+    /// [`expand_close_all`](Self::expand_close_all), also returning the byte
+    /// of `text` that each byte of the result (plus its end) came from
+    ///
+    /// Closers written for a `{{//}}` map to its end, except the first byte,
+    /// which maps to its start, so a template ending just before it keeps
+    /// its own span.
+    fn expand_close_all_mapped(&self, text: &str) -> (String, Vec<usize>) {
         let mut result = String::new();
+        let mut sources = Vec::with_capacity(text.len() + 1);
         let chars: Vec<char> = text.chars().collect();
+        let bytes: Vec<usize> = text
+            .char_indices()
+            .map(|(b, _)| b)
+            .chain(std::iter::once(text.len()))
+            .collect();
         let mut i = 0;
 
         // Track open tags: (tag_type, closer)
@@ -803,6 +2831,22 @@ impl TemplateParser {
         let mut open_tags: Vec<(&str, String)> = Vec::new();
 
         while i < chars.len() {
+            // Escaped text and comments are copied through untouched
+            let literal_end = match self.parse_comment_at(&chars, i) {
+                Ok(Some(data)) => Some(data.end_pos),
+                _ => self
+                    .parse_raw_at(&chars, i)
+                    .ok()
+                    .flatten()
+                    .map(|d| d.end_pos),
+            };
+            if let Some(end) = literal_end {
+                result.extend(&chars[i..end]);
+                sources.extend(bytes[i]..bytes[end]);
+                i = end;
+                continue;
+            }
+
             // Check for {{//}}
             if i + 5 < chars.len()
                 && chars[i] == '{'
@@ -813,9 +2857,17 @@ impl TemplateParser {
                 && chars[i + 5] == '}'
             {
                 // Expand to all closing tags in reverse order
+                let inserted = result.len();
                 for (_, closer) in open_tags.iter().rev() {
                     result.push_str(closer);
                 }
+                sources.extend((inserted..result.len()).map(|b| {
+                    if b == inserted {
+                        bytes[i]
+                    } else {
+                        bytes[i + 6]
+                    }
+                }));
                 open_tags.clear();
                 i += 6;
                 continue;
@@ -853,8 +2905,10 @@ impl TemplateParser {
                     // Check if it's a block style (not self-closing, has closing tag)
                     // Skip known prefixes
                     if !name.is_empty()
-                        && !["frame", "fr", "ui", "shields", "glyph", "kbd"]
-                            .contains(&name.as_str())
+                        && ![
+                            "frame", "fr", "ui", "shields", "glyph", "kbd", "num", "t", "snippet",
+                        ]
+                        .contains(&name.as_str())
                         && j < chars.len()
                     {
                         // Check for closing }} after optional params
@@ -910,10 +2964,12 @@ impl TemplateParser {
             }
 
             result.push(chars[i]);
+            sources.extend(bytes[i]..bytes[i + 1]);
             i += 1;
         }
 
-        result
+        sources.push(text.len());
+        (result, sources)
     }
 
     /// Check if characters match a string at position
@@ -992,9 +3048,12 @@ impl TemplateParser {
             return Ok(None);
         }
 
-        // Parse optional parameters: :spacing=N and/or :separator=name
+        // Parse optional parameters: :spacing=N, :separator=name,
+        // :fallback=policy, :seed=N
         let mut spacing = 0;
         let mut separator: Option<String> = None;
+        let mut fallback: Option<Fallback> = None;
+        let mut seed: Option<u64> = None;
 
         // Helper function to check if chars match a string at position i
         let matches_str = |chars: &[char], i: usize, s: &str| -> bool {
@@ -1045,34 +3104,39 @@ impl TemplateParser {
                     i += 1;
                 }
 
-                // Resolve separator using unified Registry
-                // First, try to resolve as a known separator
-                if let Some(sep_value) = self.registry.separator(&sep_input) {
-                    separator = Some(sep_value.to_string());
-                } else {
-                    // Not a known separator - check if it's a single grapheme literal
-                    use unicode_segmentation::UnicodeSegmentation;
-                    let graphemes: Vec<&str> = sep_input.graphemes(true).collect();
+                separator = Some(self.resolve_separator(&sep_input)?);
+            }
+            // Check for "fallback="
+            else if matches_str(chars, i, "fallback=") {
+                i += 9; // length of "fallback="
 
-                    if graphemes.len() == 1 {
-                        // Single grapheme - accept as literal separator
-                        separator = Some(sep_input.clone());
-                    } else {
-                        // Multi-grapheme unknown name - error with suggestions
-                        let available: Vec<&str> = self
-                            .registry
-                            .glyphs()
-                            .keys()
-                            .map(|name| name.as_str())
-                            .take(8)
-                            .collect();
-
-                        return Err(Error::ParseError(format!(
-                            "Unknown glyph '{}'. Available glyphs: {}. Or use a single character like '→' or '·'.",
-                            sep_input,
-                            available.join(", ")
-                        )));
-                    }
+                let mut policy = String::new();
+                while i < chars.len() && chars[i].is_ascii_alphabetic() {
+                    policy.push(chars[i]);
+                    i += 1;
+                }
+
+                fallback = Some(Fallback::parse(&policy).ok_or_else(|| {
+                    Error::ParseError(format!(
+                        "Unknown fallback '{}'. Use passthrough, skip, error, or closest.",
+                        policy
+                    ))
+                })?);
+            }
+            // Check for "seed="
+            else if matches_str(chars, i, "seed=") {
+                i += 5; // length of "seed="
+
+                let mut num_str = String::new();
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    num_str.push(chars[i]);
+                    i += 1;
+                }
+
+                match num_str.parse::<u64>() {
+                    Ok(value) => seed = Some(value),
+                    // Invalid number
+                    Err(_) => return Ok(None),
                 }
             } else {
                 // Unknown parameter
@@ -1112,6 +3176,8 @@ impl TemplateParser {
                         style,
                         spacing,
                         separator,
+                        fallback,
+                        seed,
                         content,
                     }));
                 }
@@ -1124,11 +3190,37 @@ impl TemplateParser {
         Err(Error::UnclosedTag(style))
     }
 
+    /// Resolve a separator name from the registry, or accept a single
+    /// grapheme as a literal separator
+    fn resolve_separator(&self, input: &str) -> Result<String> {
+        if let Some(sep_value) = self.registry.separator(input) {
+            return Ok(sep_value.to_string());
+        }
+
+        use unicode_segmentation::UnicodeSegmentation;
+        if input.graphemes(true).count() == 1 {
+            return Ok(input.to_string());
+        }
+
+        // Multi-grapheme unknown name - error with suggestions
+        let available: Vec<&str> = self
+            .registry
+            .glyphs()
+            .keys()
+            .map(|name| name.as_str())
+            .take(8)
+            .collect();
+        Err(Error::ParseError(format!(
+            "Unknown glyph '{}'. Available glyphs: {}. Or use a single character like '→' or '·'.",
+            input,
+            available.join(", ")
+        )))
+    }
+
     /// Parse glyph frame spec: NAME[*COUNT][/pad=VALUE][/separator=VALUE][/spacing=N]
-    /// Returns (glyph_name, count, padding_string, separator_option, spacing_option)
-    fn parse_glyph_frame_spec(
-        spec: &str,
-    ) -> (String, usize, String, Option<String>, Option<usize>) {
+    ///
+    /// Fails when `pad=N` asks for more padding than the output can hold.
+    fn parse_glyph_frame_spec(&self, spec: &str) -> Result<GlyphFrameSpec> {
         let mut remaining = spec.to_string();
         let mut count: usize = 1;
         let mut pad = " ".to_string(); // default: single space
@@ -1153,6 +3245,7 @@ impl TemplateParser {
             if let Some(pad_value) = modifier.strip_prefix("pad=") {
                 // Check if it's a number (meaning N spaces)
                 if let Ok(num_spaces) = pad_value.parse::<usize>() {
+                    self.check_padding(2, num_spaces)?;
                     pad = " ".repeat(num_spaces);
                 } else {
                     // Use literal string
@@ -1183,7 +3276,13 @@ impl TemplateParser {
             }
         }
 
-        (remaining, count, pad, separator, spacing)
+        Ok(GlyphFrameSpec {
+            glyph: remaining,
+            count,
+            pad,
+            separator,
+            spacing,
+        })
     }
 
     /// Parse frame style and extract modifiers (separator, spacing, reverse, count)
@@ -1665,6 +3764,15 @@ impl TemplateParser {
             while i < chars.len() {
                 let ch = chars[i];
 
+                // A style template in a value is taken whole, with its : and }}
+                if has_equals && ch == '{' {
+                    if let Some(end) = self.nested_style_end(chars, i)? {
+                        arg.extend(&chars[i..end]);
+                        i = end;
+                        continue;
+                    }
+                }
+
                 // Track if we've seen '=' to know if we're in a key=value argument
                 if ch == '=' {
                     has_equals = true;
@@ -1673,15 +3781,16 @@ impl TemplateParser {
                     continue;
                 }
 
-                // For key=value args, only stop at : or }
-                // For positional args, also stop at /
-                if ch == ':' || ch == '}' {
-                    break;
-                }
+                // A URL scheme separator belongs to the value (url=https://...),
+                // as does the colon of a remote icon (icon=url:https://...)
+                let is_scheme = has_equals
+                    && ch == ':'
+                    && ((chars.get(i + 1) == Some(&'/') && chars.get(i + 2) == Some(&'/'))
+                        || arg.ends_with("=url"));
 
-                // For positional args (no =), stop at /
-                // For key=value args, allow / in values
-                if ch == '/' && !has_equals {
+                // Stop at : or }; / belongs to the arg (a path or benchmark id)
+                // unless it starts the self-closing marker
+                if (ch == ':' && !is_scheme) || ch == '}' {
                     break;
                 }
 
@@ -1745,6 +3854,10 @@ impl TemplateParser {
                     let mut k = i + open_prefix_chars.len();
                     let mut is_self_closing = false;
                     while k < chars.len() {
+                        if let Some(end) = self.nested_style_end(chars, k)? {
+                            k = end;
+                            continue;
+                        }
                         if k + 2 < chars.len()
                             && chars[k] == '/'
                             && chars[k + 1] == '}'
@@ -1806,6 +3919,71 @@ impl TemplateParser {
         Err(Error::UnclosedTag("ui".to_string()))
     }
 
+    /// End of a style template starting at `start` inside a parameter value
+    ///
+    /// `label={{mathbold}}A:B{{/mathbold}}` keeps the `:` and `}}` of the
+    /// template in the value. Other templates are not recognised there.
+    fn nested_style_end(&self, chars: &[char], start: usize) -> Result<Option<usize>> {
+        if chars.get(start..start + 2) != Some(&['{', '{']) {
+            return Ok(None);
+        }
+        Ok(self
+            .parse_template_at(chars, start)?
+            .filter(|data| self.registry.style(&data.style).is_some())
+            .map(|data| data.end_pos))
+    }
+
+    /// Try to parse a comment template at position i: `{{!-- text --}}`,
+    /// ending at the first `--}}`
+    fn parse_comment_at(&self, chars: &[char], start: usize) -> Result<Option<CommentData>> {
+        if !self.matches_at(chars, start, "{{!--") {
+            return Ok(None);
+        }
+
+        let text_start = start + 5;
+        let mut i = text_start;
+        while i < chars.len() {
+            if self.matches_at(chars, i, "--}}") {
+                return Ok(Some(CommentData {
+                    end_pos: i + 4,
+                    text: chars[text_start..i].iter().collect(),
+                }));
+            }
+            i += 1;
+        }
+
+        Err(Error::UnclosedTag("!--".to_string()))
+    }
+
+    /// Try to parse escaped text at position i: `\{{` (a literal `{{`) or
+    /// `{{raw}}...{{/raw}}` (content kept as written, up to the first
+    /// `{{/raw}}`)
+    fn parse_raw_at(&self, chars: &[char], start: usize) -> Result<Option<RawData>> {
+        if self.matches_at(chars, start, "\\{{") {
+            return Ok(Some(RawData {
+                end_pos: start + 3,
+                content: "{{".to_string(),
+            }));
+        }
+        if !self.matches_at(chars, start, "{{raw}}") {
+            return Ok(None);
+        }
+
+        let content_start = start + 7;
+        let mut i = content_start;
+        while i < chars.len() {
+            if self.matches_at(chars, i, "{{/raw}}") {
+                return Ok(Some(RawData {
+                    end_pos: i + 8,
+                    content: chars[content_start..i].iter().collect(),
+                }));
+            }
+            i += 1;
+        }
+
+        Err(Error::UnclosedTag("raw".to_string()))
+    }
+
     /// Try to parse a shields template starting at position i
     /// Returns: Some(ShieldData) or None if not a valid shields template
     ///
@@ -1849,7 +4027,7 @@ impl TemplateParser {
         }
 
         // Parse parameters (key=value pairs separated by :)
-        let mut params = std::collections::HashMap::new();
+        let mut params = std::collections::BTreeMap::new();
 
         while i < chars.len() && chars[i] == ':' {
             i += 1; // skip ':'
@@ -1940,65 +4118,195 @@ impl TemplateParser {
         }
 
         // Must be self-closing (ends with /}})
-        if i + 2 < chars.len() && chars[i] == '/' && chars[i + 1] == '}' && chars[i + 2] == '}' {
-            let end_pos = i + 3;
-            return Ok(Some(GlyphData {
-                end_pos,
-                glyph_name,
+        if i + 2 < chars.len() && chars[i] == '/' && chars[i + 1] == '}' && chars[i + 2] == '}' {
+            let end_pos = i + 3;
+            return Ok(Some(GlyphData {
+                end_pos,
+                glyph_name,
+            }));
+        }
+
+        // Not a valid glyph template
+        Ok(None)
+    }
+
+    /// Try to parse a kbd template starting at position i
+    /// Returns: Some(KbdData) or None if not a valid kbd template
+    ///
+    /// Supports self-closing only: {{kbd:Ctrl+C/}}
+    /// Expands to: <kbd>Ctrl</kbd>+<kbd>C</kbd>
+    fn parse_kbd_at(&self, chars: &[char], start: usize) -> Result<Option<KbdData>> {
+        let mut i = start;
+
+        // Must start with {{kbd:
+        if i + 7 >= chars.len() {
+            return Ok(None);
+        }
+
+        // Check for "{{kbd:"
+        let kbd_start = "{{kbd:";
+        let kbd_chars: Vec<char> = kbd_start.chars().collect();
+        for (idx, &expected) in kbd_chars.iter().enumerate() {
+            if chars[i + idx] != expected {
+                return Ok(None);
+            }
+        }
+        i += kbd_chars.len();
+
+        // Parse key sequence (everything until /}})
+        let mut keys = String::new();
+        while i < chars.len() {
+            let ch = chars[i];
+            if ch == '/' {
+                break;
+            }
+            keys.push(ch);
+            i += 1;
+        }
+
+        // Keys must be non-empty
+        if keys.is_empty() {
+            return Ok(None);
+        }
+
+        // Must be self-closing (ends with /}})
+        if i + 2 < chars.len() && chars[i] == '/' && chars[i + 1] == '}' && chars[i + 2] == '}' {
+            let end_pos = i + 3;
+            return Ok(Some(KbdData { end_pos, keys }));
+        }
+
+        // Not a valid kbd template
+        Ok(None)
+    }
+
+    /// Try to parse a number template starting at position i
+    /// Returns: Some(NumData) or None if not a valid number template
+    ///
+    /// Supports self-closing only: {{num:roman:2024/}}
+    fn parse_num_at(&self, chars: &[char], start: usize) -> Result<Option<NumData>> {
+        let prefix: Vec<char> = "{{num:".chars().collect();
+        if !chars[start..].starts_with(&prefix) {
+            return Ok(None);
+        }
+        let mut i = start + prefix.len();
+
+        // Parse format name (letters only)
+        let mut format = String::new();
+        while i < chars.len() && chars[i].is_ascii_alphabetic() {
+            format.push(chars[i]);
+            i += 1;
+        }
+        if format.is_empty() || chars.get(i) != Some(&':') {
+            return Ok(None);
+        }
+        i += 1;
+
+        // Parse value (everything until /}})
+        let mut value = String::new();
+        while i < chars.len() && chars[i] != '/' && chars[i] != '}' {
+            value.push(chars[i]);
+            i += 1;
+        }
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        // Must be self-closing (ends with /}})
+        if chars[i..].starts_with(&['/', '}', '}']) {
+            return Ok(Some(NumData {
+                end_pos: i + 3,
+                format,
+                value,
+            }));
+        }
+
+        // Not a valid number template
+        Ok(None)
+    }
+
+    /// Try to parse a translated string template starting at position i
+    /// Returns: Some(TranslationData) or None if not a valid string template
+    ///
+    /// Supports self-closing only: {{t:intro.title/}}
+    fn parse_t_at(&self, chars: &[char], start: usize) -> Result<Option<TranslationData>> {
+        let prefix: Vec<char> = "{{t:".chars().collect();
+        if !chars[start..].starts_with(&prefix) {
+            return Ok(None);
+        }
+        let mut i = start + prefix.len();
+
+        // Parse key (everything until /}})
+        let mut key = String::new();
+        while i < chars.len() && chars[i] != '/' && chars[i] != '}' {
+            key.push(chars[i]);
+            i += 1;
+        }
+        if !i18n::is_valid_key(&key) {
+            return Ok(None);
+        }
+
+        // Must be self-closing (ends with /}})
+        if chars[i..].starts_with(&['/', '}', '}']) {
+            return Ok(Some(TranslationData {
+                end_pos: i + 3,
+                key,
             }));
         }
 
-        // Not a valid glyph template
+        // Not a valid string template
         Ok(None)
     }
 
-    /// Try to parse a kbd template starting at position i
-    /// Returns: Some(KbdData) or None if not a valid kbd template
+    /// Try to parse a source snippet template starting at position i
+    /// Returns: Some(SnippetData) or None if not a valid snippet template
     ///
-    /// Supports self-closing only: {{kbd:Ctrl+C/}}
-    /// Expands to: <kbd>Ctrl</kbd>+<kbd>C</kbd>
-    fn parse_kbd_at(&self, chars: &[char], start: usize) -> Result<Option<KbdData>> {
-        let mut i = start;
-
-        // Must start with {{kbd:
-        if i + 7 >= chars.len() {
+    /// Supports self-closing only: {{snippet:src/lib.rs:region=example:lang=rust/}}
+    fn parse_snippet_at(&self, chars: &[char], start: usize) -> Result<Option<SnippetData>> {
+        let prefix: Vec<char> = "{{snippet:".chars().collect();
+        if !chars[start..].starts_with(&prefix) {
             return Ok(None);
         }
+        let mut i = start + prefix.len();
 
-        // Check for "{{kbd:"
-        let kbd_start = "{{kbd:";
-        let kbd_chars: Vec<char> = kbd_start.chars().collect();
-        for (idx, &expected) in kbd_chars.iter().enumerate() {
-            if chars[i + idx] != expected {
+        // Parse arguments (everything until /}}, paths may contain '/')
+        let mut args = String::new();
+        while i < chars.len() && !chars[i..].starts_with(&['/', '}', '}']) {
+            if chars[i] == '}' || chars[i] == '{' || chars[i] == '\n' {
                 return Ok(None);
             }
-        }
-        i += kbd_chars.len();
-
-        // Parse key sequence (everything until /}})
-        let mut keys = String::new();
-        while i < chars.len() {
-            let ch = chars[i];
-            if ch == '/' {
-                break;
-            }
-            keys.push(ch);
+            args.push(chars[i]);
             i += 1;
         }
-
-        // Keys must be non-empty
-        if keys.is_empty() {
+        if i == chars.len() {
             return Ok(None);
         }
 
-        // Must be self-closing (ends with /}})
-        if i + 2 < chars.len() && chars[i] == '/' && chars[i + 1] == '}' && chars[i + 2] == '}' {
-            let end_pos = i + 3;
-            return Ok(Some(KbdData { end_pos, keys }));
+        let mut parts = args.split(':');
+        let path = parts.next().unwrap_or_default().to_string();
+        if path.is_empty() || path.contains(char::is_whitespace) {
+            return Ok(None);
         }
-
-        // Not a valid kbd template
-        Ok(None)
+        let mut data = SnippetData {
+            end_pos: i + 3,
+            path,
+            region: None,
+            lang: None,
+        };
+        for part in parts {
+            match part.split_once('=') {
+                Some(("region", name)) if snippet::is_valid_region(name) => {
+                    data.region = Some(name.to_string())
+                }
+                Some(("lang", lang)) if !lang.is_empty() => data.lang = Some(lang.to_string()),
+                _ => {
+                    return Err(Error::ParseError(format!(
+                        "Invalid snippet parameter '{}': expected region=NAME or lang=LANG",
+                        part
+                    )))
+                }
+            }
+        }
+        Ok(Some(data))
     }
 
     /// Expand kbd keys to HTML
@@ -2030,6 +4338,7 @@ impl Default for TemplateParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diagnostics::Severity;
     use crate::{
         test_process, test_process_bookends, test_process_contains, test_process_err,
         test_process_unchanged,
@@ -2238,6 +4547,385 @@ And `{{mathbold}}inline code{{/mathbold}}` is also preserved."#
         );
     }
 
+    #[rstest]
+    #[case("{{mathbold}}Café 1{{/mathbold}}", "𝐂𝐚𝐟é 𝟏")]
+    #[case("{{mathbold:fallback=skip}}Café 1{{/mathbold}}", "𝐂𝐚𝐟 𝟏")]
+    #[case("{{mathbold:fallback=closest}}Café{{/mathbold}}", "𝐂𝐚𝐟𝐞\u{301}")]
+    #[case(
+        "{{negative-squared:separator=dot:fallback=closest}}ok{{/negative-squared}}",
+        "🅾·🅺"
+    )]
+    fn test_template_fallback(#[case] input: &str, #[case] expected: &str) {
+        test_process!(input => expected);
+    }
+
+    #[test]
+    fn test_template_zalgo_seed() {
+        let parser = TemplateParser::new().unwrap();
+        let converter = Converter::new().unwrap();
+        assert_eq!(
+            parser.process("{{zalgo}}hi{{/zalgo}}").unwrap(),
+            converter.convert("hi", "zalgo").unwrap()
+        );
+        assert_eq!(
+            parser.process("{{zalgo:seed=9}}hi{{/zalgo}}").unwrap(),
+            converter.with_seed(9).convert("hi", "zalgo").unwrap()
+        );
+        test_process_unchanged!("{{zalgo:seed=x}}hi{{/zalgo}}");
+    }
+
+    #[rstest]
+    #[case("{{italic:fallback=error}}Top 10{{/italic}}")]
+    #[case("{{italic:fallback=nearest}}Top{{/italic}}")]
+    fn test_template_fallback_errors(#[case] input: &str) {
+        test_process_err!(input);
+    }
+
+    #[test]
+    fn test_unmapped_chars_warning() {
+        let parser = TemplateParser::new().unwrap();
+        let input = "x {{italic}}Top 10 ✓{{/italic}} {{italic:fallback=skip}}1{{/italic}}";
+        let result = parser.process_with_diagnostics(input);
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(&input[diagnostic.range.clone()], "{{italic}}");
+        assert!(
+            diagnostic.message.contains("no equivalent for '1', '0'"),
+            "{}",
+            diagnostic.message
+        );
+        assert_eq!(result.markdown, "x 𝑇𝑜𝑝 10 ✓ ");
+    }
+
+    #[rstest]
+    #[case("Step {{num:circled:12/}}", "Step ⑫")]
+    #[case("{{num:roman:2024/}}", "ⅯⅯⅩⅩⅠⅤ")]
+    #[case("x{{num:super:2/}} + y{{num:sub:-1/}}", "x² + y₋₁")]
+    #[case("{{num:circled:51/}}", "51")] // beyond Unicode coverage
+    #[case("{{num:roman:0/}}", "0")]
+    #[case("{{num:roman/}}", "{{num:roman/}}")] // not a number template
+    #[case("`{{num:roman:4/}}`", "`{{num:roman:4/}}`")]
+    fn test_num_template(#[case] input: &str, #[case] expected: &str) {
+        test_process!(input => expected);
+    }
+
+    #[rstest]
+    #[case("{{num:hex:10/}}")]
+    #[case("{{num:roman:IV/}}")]
+    #[case("{{num:super:1.5/}}")]
+    fn test_num_template_errors(#[case] input: &str) {
+        test_process_err!(input);
+    }
+
+    #[test]
+    fn test_num_out_of_range_warning() {
+        let parser = TemplateParser::new().unwrap();
+        let input = "{{num:circled:7/}} {{num:circled:64/}}";
+        let result = parser.process_with_diagnostics(input);
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(&input[diagnostic.range.clone()], "{{num:circled:64/}}");
+        assert_eq!(
+            diagnostic.message,
+            "64 has no circled form; written as plain digits"
+        );
+        assert_eq!(result.markdown, "⑦ 64");
+    }
+
+    fn parser_with_strings() -> TemplateParser {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_strings(
+            crate::i18n::parse_strings(
+                r#"{
+                    "title": "Bonjour",
+                    "intro": { "lead": "Un outil {{mathbold}}rapide{{/mathbold}}" },
+                    "nested": "{{t:title/}} !"
+                }"#,
+            )
+            .unwrap(),
+        );
+        parser
+    }
+
+    #[rstest]
+    #[case("# {{t:title/}}", "# Bonjour")]
+    #[case("{{t:intro.lead/}}", "Un outil 𝐫𝐚𝐩𝐢𝐝𝐞")]
+    #[case("{{t:nested/}}", "Bonjour !")]
+    #[case("[{{t:title/}}](#top) {{t:title/}}", "[Bonjour](#top) Bonjour")]
+    #[case("`{{t:title/}}`", "`{{t:title/}}`")]
+    #[case("{{t:not a key/}}", "{{t:not a key/}}")] // not a string template
+    fn test_translation_template(#[case] input: &str, #[case] expected: &str) {
+        let parser = parser_with_strings();
+        assert_eq!(parser.process(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_translation_unknown_key() {
+        let parser = parser_with_strings();
+        let err = parser.process("{{t:outro/}}").unwrap_err();
+        assert!(
+            err.to_string().contains("Unknown string 'outro'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_translation_ast() {
+        let parser = parser_with_strings();
+        let nodes = parser.parse_ast("# {{t:title/}}").unwrap();
+        assert!(matches!(&nodes[1].kind, NodeKind::Translation { key } if key == "title"));
+        assert_eq!(parser.render_ast(&nodes).unwrap().markdown, "# Bonjour");
+    }
+
+    fn parser_with_snippets() -> TemplateParser {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_project_file(
+            "src/lib.rs",
+            "pub fn demo() {\n    // mdfx:begin example\n    let x = demo::run(\"{{mathbold}}\");\n    // mdfx:end example\n}\n",
+        );
+        parser.set_project_file("run.sh", "cargo run\n");
+        parser
+    }
+
+    #[rstest]
+    #[case(
+        "{{snippet:src/lib.rs:region=example/}}",
+        "```rust\nlet x = demo::run(\"{{mathbold}}\");\n```"
+    )]
+    #[case(
+        "{{snippet:src/lib.rs/}}",
+        "```rust\npub fn demo() {\n    let x = demo::run(\"{{mathbold}}\");\n}\n```"
+    )]
+    #[case("{{snippet:run.sh:lang=console/}}", "```console\ncargo run\n```")]
+    #[case("`{{snippet:run.sh/}}`", "`{{snippet:run.sh/}}`")]
+    #[case("{{snippet:/}}", "{{snippet:/}}")] // not a snippet template
+    fn test_snippet_template(#[case] input: &str, #[case] expected: &str) {
+        let parser = parser_with_snippets();
+        assert_eq!(parser.process(input).unwrap(), expected);
+        let nodes = parser.parse_ast(input).unwrap();
+        assert_eq!(parser.render_ast(&nodes).unwrap().markdown, expected);
+    }
+
+    #[rstest]
+    #[case("{{snippet:missing.rs/}}", "snippet file 'missing.rs' not found")]
+    #[case("{{snippet:src/lib.rs:region=other/}}", "no 'mdfx:begin other' marker")]
+    #[case(
+        "{{snippet:src/lib.rs:regoin=example/}}",
+        "Invalid snippet parameter 'regoin=example'"
+    )]
+    fn test_snippet_errors(#[case] input: &str, #[case] message: &str) {
+        let err = parser_with_snippets().process(input).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+
+    #[test]
+    fn test_snippet_reads_project_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("examples")).unwrap();
+        std::fs::write(
+            dir.path().join("examples/basic.py"),
+            "import mdfx\n# mdfx:begin usage\nprint(mdfx.render())\n# mdfx:end usage\n",
+        )
+        .unwrap();
+
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_project_root(dir.path());
+        let result = parser
+            .process("Usage:\n\n{{snippet:examples/basic.py:region=usage/}}\n")
+            .unwrap();
+
+        assert_eq!(result, "Usage:\n\n```python\nprint(mdfx.render())\n```\n");
+    }
+
+    #[test]
+    fn test_snippet_stays_inside_project_root() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_project_root(concat!(env!("CARGO_MANIFEST_DIR"), "/src"));
+        assert!(parser.process("{{snippet:lib.rs/}}").is_ok());
+
+        let absolute = concat!(env!("CARGO_MANIFEST_DIR"), "/src/lib.rs");
+        for path in [
+            "/etc/hostname",
+            absolute,
+            "../Cargo.toml",
+            "renderer/../../Cargo.toml",
+        ] {
+            let err = parser
+                .process(&format!("{{{{snippet:{}/}}}}", path))
+                .unwrap_err();
+            assert!(err.to_string().contains("not found"), "{}: {}", path, err);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_project_files_dont_follow_symlinks_out_of_root() {
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "secret").unwrap();
+        let root = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("link")).unwrap();
+
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_project_root(root.path());
+        assert!(parser.process("{{snippet:link/secret.txt/}}").is_err());
+        assert!(parser
+            .process("{{ui:coverage:file=link/secret.txt/}}")
+            .is_err());
+    }
+
+    #[test]
+    fn test_analyze_lists_templates() {
+        let parser = TemplateParser::new().unwrap();
+        let input = "# {{mathbold:spacing=1}}Title{{/mathbold}}\n\n{{fr:gradient}}{{ui:tech:rust:style=flat/}} {{glyph:star/}}{{/}}\n`{{ui:tech:go/}}` {{num:roman:4/}}";
+        let usages = parser.analyze(input);
+
+        let found: Vec<(&str, &str, usize, usize)> = usages
+            .iter()
+            .map(|u| (u.kind, u.name.as_str(), u.line, u.column))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("style", "mathbold", 1, 3),
+                ("frame", "gradient", 3, 1),
+                ("ui", "tech", 3, 16),
+                ("glyph", "star", 3, 45),
+                ("num", "roman", 4, 19),
+            ]
+        );
+        assert!(usages
+            .iter()
+            .all(|u| u.is_valid() && u.diagnostics.is_empty()));
+        assert_eq!(usages[0].params["spacing"], "1");
+        assert_eq!(
+            &input[usages[2].range.clone()],
+            "{{ui:tech:rust:style=flat/}}"
+        );
+        assert_eq!(usages[2].args, ["rust"]);
+        assert_eq!(usages[2].params["style"], "flat");
+        assert_eq!(usages[4].args, ["4"]);
+    }
+
+    #[rstest]
+    #[case("{{nosuchstyle}}x{{/nosuchstyle}}", "Unknown style")]
+    #[case("{{fr:nosuchframe}}x{{/}}", "nosuchframe")]
+    #[case("{{ui:nosuchcomponent/}}", "Unknown component 'nosuchcomponent'")]
+    #[case("{{glyph:nosuchglyph/}}", "nosuchglyph")]
+    #[case("{{num:roman:IV/}}", "IV")]
+    #[case("{{t:missing/}}", "Unknown string 'missing'")]
+    #[case("{{snippet:missing.rs/}}", "snippet file 'missing.rs' not found")]
+    fn test_analyze_errors(#[case] input: &str, #[case] message: &str) {
+        let usages = TemplateParser::new().unwrap().analyze(input);
+        assert_eq!(usages.len(), 1);
+        assert!(!usages[0].is_valid());
+        assert_eq!(usages[0].diagnostics.len(), 1);
+        let diagnostic = &usages[0].diagnostics[0];
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert!(
+            diagnostic.message.contains(message),
+            "{}",
+            diagnostic.message
+        );
+        assert_eq!((diagnostic.line, diagnostic.column), (1, 1));
+    }
+
+    #[test]
+    fn test_analyze_warnings_and_syntax_errors() {
+        let parser = TemplateParser::new().unwrap();
+        let input = "{{ui:tech:rust:colour=red/}}\n{{mathbold}}never closed";
+        let usages = parser.analyze(input);
+
+        assert_eq!(usages.len(), 2);
+        assert_eq!((usages[0].kind, usages[0].name.as_str()), ("ui", "tech"));
+        assert!(usages[0].is_valid());
+        assert_eq!(usages[0].diagnostics.len(), 1);
+        assert_eq!(usages[0].diagnostics[0].severity, Severity::Warning);
+
+        assert_eq!(
+            (usages[1].kind, usages[1].name.as_str()),
+            ("style", "mathbold")
+        );
+        assert_eq!(&input[usages[1].range.clone()], "{{mathbold}}");
+        assert_eq!((usages[1].line, usages[1].column), (2, 1));
+        assert!(!usages[1].is_valid());
+    }
+
+    #[test]
+    fn test_analyze_does_not_render() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_placeholders(false);
+        let usages = parser.analyze("{{ui:live:github:stars:rust-lang/rust/}}");
+        assert_eq!(usages.len(), 1);
+        assert!(usages[0].is_valid(), "{:?}", usages[0].diagnostics);
+
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_options(ParserOptions::untrusted());
+        let usages = parser.analyze("{{ui:live:github:stars:rust-lang/rust/}}");
+        assert!(!usages[0].is_valid());
+    }
+
+    #[test]
+    fn test_analyze_snippet_region() {
+        let parser = parser_with_snippets();
+        let usages = parser
+            .analyze("{{snippet:src/lib.rs:region=example/}} {{snippet:src/lib.rs:region=nope/}}");
+        assert_eq!(usages.len(), 2);
+        assert!(usages[0].is_valid());
+        assert_eq!(usages[0].params["region"], "example");
+        assert!(!usages[1].is_valid());
+    }
+
+    fn parser_with_style_defaults() -> TemplateParser {
+        let mut parser = TemplateParser::new().unwrap();
+        let config: MdfxConfig = serde_json::from_str(
+            r#"{"styles": {"bold": {"spacing": 1}, "script": {"separator": "dot"}}}"#,
+        )
+        .unwrap();
+        parser.load_config(&config);
+        parser
+    }
+
+    #[rstest]
+    #[case("{{mathbold}}HI{{/mathbold}}", "𝐇 𝐈")]
+    #[case("{{mb}}HI{{/mb}}", "𝐇 𝐈")]
+    #[case("{{mathbold:spacing=2}}HI{{/mathbold}}", "𝐇  𝐈")]
+    #[case("{{mathbold:separator=dash}}HI{{/mathbold}}", "𝐇─𝐈")]
+    #[case("{{script}}ab{{/script}}", "𝒶·𝒷")]
+    #[case("{{italic}}HI{{/italic}}", "𝐻𝐼")]
+    fn test_style_defaults_from_config(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(
+            parser_with_style_defaults().process(input).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_style_defaults_front_matter_override() {
+        let parser = parser_with_style_defaults();
+        let input = "---\nmdfx-styles:\n  mb: spacing=0\n  italic: separator=→\n---\n\
+                     {{mathbold}}HI{{/mathbold}} {{italic}}HI{{/italic}}";
+        let output = parser.process(input).unwrap();
+        assert!(output.ends_with("---\n𝐇𝐈 𝐻→𝐼"), "{}", output);
+
+        // Overrides last for one document only
+        assert_eq!(
+            parser.process("{{mathbold}}HI{{/mathbold}}").unwrap(),
+            "𝐇 𝐈"
+        );
+        assert_eq!(parser.process_with_diagnostics(input).markdown, output);
+    }
+
+    #[test]
+    fn test_style_defaults_invalid_front_matter() {
+        let parser = TemplateParser::new().unwrap();
+        let input = "---\nmdfx-styles:\n  mathbold: spacing=wide\n---\nx";
+        assert!(parser.process(input).is_err());
+        assert!(parser.process_with_diagnostics(input).has_errors());
+    }
+
     // Frame template tests
     #[test]
     fn test_frame_template_plain_text() {
@@ -2351,6 +5039,104 @@ And `{{mathbold}}inline code{{/mathbold}}` is also preserved."#
         assert_eq!(name, "unknown");
     }
 
+    #[test]
+    fn test_custom_glyph_from_config() {
+        let mut parser = TemplateParser::new().unwrap();
+        let mut config = MdfxConfig::new();
+        config.glyphs.insert("logo".to_string(), "⌬".to_string());
+        parser.load_config(&config);
+
+        let result = parser
+            .process("{{glyph:logo/}} {{frame:glyph:logo}}X{{/frame}}")
+            .unwrap();
+        assert_eq!(result, "⌬\u{fe0e} ⌬\u{fe0e} X ⌬\u{fe0e}");
+    }
+
+    #[test]
+    fn test_project_icon_from_config() {
+        use crate::renderer::svg::SvgBackend;
+
+        let mut parser =
+            TemplateParser::with_backend(Box::new(SvgBackend::new("assets/test"))).unwrap();
+        let mut config = MdfxConfig::new();
+        config.icons.insert(
+            "acme".to_string(),
+            "<svg viewBox=\"0 0 16 16\"><path d=\"M0 0h16v16H0z\" onload=\"x()\"/></svg>"
+                .to_string(),
+        );
+        parser.load_config(&config);
+
+        let processed = parser
+            .process_with_assets("{{ui:tech:acme/}} {{ui:swatch:000000:icon=acme/}}")
+            .unwrap();
+        let svgs: Vec<&str> = processed
+            .assets
+            .iter()
+            .filter_map(|asset| asset.file_bytes())
+            .map(|bytes| std::str::from_utf8(bytes).unwrap())
+            .collect();
+        assert_eq!(svgs.len(), 2);
+        for svg in svgs {
+            assert!(svg.contains("<svg viewBox=\"0 0 16 16\" width=\"24\" height=\"24\">"));
+            assert!(!svg.contains("onload"), "{}", svg);
+        }
+
+        config.strict_icons = Some(true);
+        parser.load_config(&config);
+        assert!(parser.process("{{ui:tech:acme/}}").is_err());
+    }
+
+    #[test]
+    fn test_display_size_keeps_asset() {
+        use crate::renderer::svg::SvgBackend;
+
+        let parser =
+            TemplateParser::with_backend(Box::new(SvgBackend::new("assets/test"))).unwrap();
+        let plain = parser.process_with_assets("{{ui:swatch:F41C80/}}").unwrap();
+        let scaled = parser
+            .process_with_assets("{{ui:swatch:F41C80:display_width=40/}}")
+            .unwrap();
+
+        assert_eq!(plain.assets[0].file_bytes(), scaled.assets[0].file_bytes());
+        let path = plain.assets[0].file_path().unwrap();
+        let expected = format!(r#"<img src="{}" width="40" height="40" alt="">"#, path);
+        assert_eq!(scaled.markdown, expected);
+        assert_eq!(scaled.assets[0].to_markdown(), expected);
+    }
+
+    #[test]
+    fn test_display_size_without_html() {
+        use crate::renderer::svg::SvgBackend;
+
+        let mut parser =
+            TemplateParser::with_backend(Box::new(SvgBackend::new("assets/test"))).unwrap();
+        parser.set_target(crate::targets::get_target("pypi").unwrap().as_ref());
+        let plain = parser.process("{{ui:swatch:F41C80/}}").unwrap();
+        let scaled = parser
+            .process("{{ui:swatch:F41C80:display_width=40/}}")
+            .unwrap();
+        assert!(plain.starts_with("!["), "{}", plain);
+        assert_eq!(scaled, plain);
+    }
+
+    #[rstest]
+    #[case(
+        "{{ui:swatch:F41C80:display_height=0/}}",
+        "display_height must be a positive"
+    )]
+    #[case(
+        "{{ui:swatch:F41C80:display_width=wide/}}",
+        "display_width must be a positive"
+    )]
+    #[case(
+        "{{ui:row:display_width=40}}x{{/ui}}",
+        "only apply to image components"
+    )]
+    fn test_display_size_errors(#[case] input: &str, #[case] message: &str) {
+        let err = TemplateParser::new().unwrap().process(input).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+
     #[test]
     fn test_frame_glyph_multiplier() {
         test_process!(
@@ -2640,20 +5426,67 @@ And `{{mathbold}}inline code{{/mathbold}}` is also preserved."#
         );
     }
 
-    #[test]
-    fn test_frame_count_and_reverse() {
-        // Repeat star 2 times then reverse
-        // Count first: ★★ Title ☆☆, then reverse: ☆☆ Title ★★ (with spacing)
-        test_process!("{{fr:star*2/reverse}}Title{{/}}" => " ☆\u{fe0e}☆\u{fe0e}Title★\u{fe0e}★\u{fe0e} ");
+    #[test]
+    fn test_frame_count_and_reverse() {
+        // Repeat star 2 times then reverse
+        // Count first: ★★ Title ☆☆, then reverse: ☆☆ Title ★★ (with spacing)
+        test_process!("{{fr:star*2/reverse}}Title{{/}}" => " ☆\u{fe0e}☆\u{fe0e}Title★\u{fe0e}★\u{fe0e} ");
+    }
+
+    #[test]
+    fn test_frame_count_with_separator() {
+        // ★★★ with separator between graphemes: ★·★·★
+        test_process!(
+            "{{fr:star*3/separator=·}}Title{{/}}"
+            => "★\u{fe0e}·★\u{fe0e}·★\u{fe0e} Title ☆\u{fe0e}·☆\u{fe0e}·☆\u{fe0e}"
+        );
+    }
+
+    #[test]
+    fn test_frame_width_centers_content() {
+        test_process!(
+            "{{fr:star/width=12}}AB{{/}}"
+            => "★\u{fe0e}    AB    ☆\u{fe0e}"
+        );
+    }
+
+    #[rstest]
+    #[case("{{fr:star/width=12}}中文{{/}}")]
+    #[case("{{fr:star/width=12}}🚀 A{{/}}")]
+    #[case("{{fr:star/width=12}}{{mathbold}}AB{{/mathbold}}{{/}}")]
+    #[case("{{frame:glyph:star*2/width=12}}AB{{/frame}}")]
+    #[case("{{fr:gradient+star/width=12}}{{/}}")]
+    #[case("{{fr:gradient/width=12}}X{{/}}")]
+    #[case("{{fr:star*2/separator=dot/width=12}}AB{{/}}")]
+    fn test_frame_width_fills_columns(#[case] input: &str) {
+        let parser = TemplateParser::new().unwrap();
+        let output = parser.process(input).unwrap();
+        assert_eq!(width::display_width(&output), 12, "{:?}", output);
+    }
+
+    #[test]
+    fn test_frame_width_narrower_than_content() {
+        test_process!("{{fr:star/width=2}}AB{{/}}" => "★\u{fe0e} AB ☆\u{fe0e}");
+        test_process!("{{fr:gradient/width=1}}{{/}}" => "▓\u{fe0e}▒\u{fe0e}░\u{fe0e}  ░\u{fe0e}▒\u{fe0e}▓\u{fe0e}");
+    }
+
+    #[rstest]
+    #[case("{{frame:gradient/width=99999999999}}x{{/frame}}")]
+    #[case("{{fr:star/width=1001}}x{{/}}")]
+    fn test_frame_width_cap(#[case] input: &str) {
+        let err = TemplateParser::new().unwrap().process(input).unwrap_err();
+        assert!(err.to_string().contains("at most 1000 columns"), "{}", err);
+
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_options(ParserOptions::untrusted());
+        assert!(parser.process(input).is_err());
     }
 
     #[test]
-    fn test_frame_count_with_separator() {
-        // ★★★ with separator between graphemes: ★·★·★
-        test_process!(
-            "{{fr:star*3/separator=·}}Title{{/}}"
-            => "★\u{fe0e}·★\u{fe0e}·★\u{fe0e} Title ☆\u{fe0e}·☆\u{fe0e}·☆\u{fe0e}"
-        );
+    fn test_frame_width_at_cap() {
+        let parser = TemplateParser::new().unwrap();
+        let output = parser.process("{{fr:star/width=1000}}x{{/}}").unwrap();
+        assert_eq!(width::display_width(&output), 1000);
     }
 
     #[test]
@@ -2808,122 +5641,607 @@ And `{{mathbold}}inline code{{/mathbold}}` is also preserved."#
         );
     }
 
-    #[test]
-    fn test_composition_multiple_styles_in_frame() {
-        test_process!(
-            "{{frame:solid-both}}{{mathbold}}A{{/mathbold}} and {{italic}}B{{/italic}}{{/frame}}"
-            => "█\u{fe0e}▌\u{fe0e}𝐀 and 𝐵▐\u{fe0e}█\u{fe0e}"
-        );
+    #[test]
+    fn test_composition_multiple_styles_in_frame() {
+        test_process!(
+            "{{frame:solid-both}}{{mathbold}}A{{/mathbold}} and {{italic}}B{{/italic}}{{/frame}}"
+            => "█\u{fe0e}▌\u{fe0e}𝐀 and 𝐵▐\u{fe0e}█\u{fe0e}"
+        );
+    }
+
+    #[test]
+    fn test_complex_composition() {
+        test_process_contains!(
+            r#"# {{frame:gradient}}{{mathbold:separator=dot}}TITLE{{/mathbold}}{{/frame}}
+
+{{frame:solid-left}}{{italic}}Important note{{/italic}}{{/frame}}
+
+Regular text with {{mathbold:spacing=1}}spacing{{/mathbold}}"#
+            => [
+                "▓\u{fe0e}▒\u{fe0e}░\u{fe0e} 𝐓·𝐈·𝐓·𝐋·𝐄 ░\u{fe0e}▒\u{fe0e}▓\u{fe0e}",
+                "█\u{fe0e}▌\u{fe0e}𝐼𝑚𝑝𝑜𝑟𝑡𝑎𝑛𝑡 𝑛𝑜𝑡𝑒",
+                "𝐬 𝐩 𝐚 𝐜 𝐢 𝐧 𝐠"
+            ]
+        );
+    }
+
+    // UI Component Tests
+
+    #[test]
+    fn test_ui_swatch() {
+        // Should expand to shields:block with pink color resolved
+        test_process_contains!("{{ui:swatch:pink/}}" => ["![](", "F41C80"]);
+    }
+
+    #[test]
+    fn test_ui_tech() {
+        // Should expand to shields:icon with logo
+        test_process_contains!("{{ui:tech:rust/}}" => ["![](", "logo=rust"]);
+    }
+
+    #[test]
+    fn test_ui_multiple_inline() {
+        // Should have two shields with different logos
+        test_process_contains!("{{ui:tech:rust/}} {{ui:tech:python/}}" => ["logo=rust", "logo=python"]);
+    }
+
+    #[test]
+    fn test_ui_in_markdown() {
+        test_process_contains!("# Header\n\n{{ui:swatch:pink/}}\n\n## Section" => ["# Header", "![](", "## Section"]);
+    }
+
+    #[test]
+    fn test_ui_unknown_component() {
+        // Keep verbose - tests specific error message content
+        let parser = TemplateParser::new().unwrap();
+        let result = parser.process("{{ui:nonexistent/}}");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown component"));
+    }
+
+    #[test]
+    fn test_ui_unclosed() {
+        test_process_err!("{{ui:row}}TITLE");
+    }
+
+    #[rstest]
+    #[case("\\{{mathbold}}Title\\{{/mathbold}}", "{{mathbold}}Title{{/mathbold}}")]
+    #[case(
+        "Use {{raw}}{{ui:tech:rust/}}{{/raw}} for badges",
+        "Use {{ui:tech:rust/}} for badges"
+    )]
+    #[case(
+        "{{raw}}{{mathbold}}A{{//}}{{/raw}} {{mb}}B{{//}}",
+        "{{mathbold}}A{{//}} 𝐁"
+    )]
+    #[case("{{frame:star}}\\{{x}}{{/frame}}", "★\u{fe0e} {{x}} ☆\u{fe0e}")]
+    #[case(
+        "{{ui:row}}\\{{glyph:star/}}{{/ui}}",
+        "<p align=\"center\">\n{{glyph:star/}}\n</p>"
+    )]
+    #[case("a \\ b \\{ c", "a \\ b \\{ c")]
+    fn test_escaped_templates(#[case] input: &str, #[case] expected: &str) {
+        let parser = TemplateParser::new().unwrap();
+        assert_eq!(parser.process(input).unwrap(), expected);
+        assert_eq!(parser.process_with_diagnostics(input).markdown, expected);
+        let nodes = parser.parse_ast(input).unwrap();
+        assert_eq!(parser.render_ast(&nodes).unwrap().markdown, expected);
+    }
+
+    #[test]
+    fn test_raw_unclosed() {
+        test_process_err!("{{raw}}{{mathbold}}A");
+    }
+
+    #[rstest]
+    #[case("a{{!-- note --}}b", "ab")]
+    #[case("{{!-- {{mathbold}} and }} --}}{{mb}}A{{/mb}}", "𝐀")]
+    #[case("# T\n{{!-- spans\ntwo lines --}}\nbody", "# T\n\nbody")]
+    #[case("{{frame:star}}x{{!-- y --}}{{/frame}}", "★\u{fe0e} x ☆\u{fe0e}")]
+    #[case("`{{!-- kept --}}` \\{{!-- x --}}", "`{{!-- kept --}}` {{!-- x --}}")]
+    #[case("{{raw}}{{!-- kept --}}{{/raw}}", "{{!-- kept --}}")]
+    fn test_comments_removed(#[case] input: &str, #[case] expected: &str) {
+        let parser = TemplateParser::new().unwrap();
+        assert_eq!(parser.process(input).unwrap(), expected);
+        assert_eq!(parser.process_with_diagnostics(input).markdown, expected);
+        let nodes = parser.parse_ast(input).unwrap();
+        assert_eq!(parser.render_ast(&nodes).unwrap().markdown, expected);
+    }
+
+    #[test]
+    fn test_comment_unclosed() {
+        test_process_err!("a {{!-- note }}");
+    }
+
+    #[rstest]
+    #[case("`{{!mathbold}}X{{/mathbold}}`", "`𝐗`")]
+    #[case(
+        "`a {{mb}}b{{/mb}} {{!mb}}c{{/mb}}` {{mb}}d{{/mb}}",
+        "`a {{mb}}b{{/mb}} 𝐜` 𝐝"
+    )]
+    #[case("`{{!frame:star}}x{{/frame}}`", "`★\u{fe0e} x ☆\u{fe0e}`")]
+    #[case("`{{!-- kept --}}` `{{!}}`", "`{{!-- kept --}}` `{{!}}`")]
+    fn test_inline_code_force_marker(#[case] input: &str, #[case] expected: &str) {
+        let parser = TemplateParser::new().unwrap();
+        assert_eq!(parser.process(input).unwrap(), expected);
+        assert_eq!(parser.process_with_diagnostics(input).markdown, expected);
+        let nodes = parser.parse_ast(input).unwrap();
+        assert_eq!(parser.render_ast(&nodes).unwrap().markdown, expected);
+        let source = ast::to_source(&nodes);
+        assert_eq!(parser.process(&source).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_process_inline_code_option() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_options(ParserOptions {
+            process_inline_code: true,
+            ..ParserOptions::trusted()
+        });
+        let input = "`{{mb}}a{{/mb}} {{!mb}}b{{/mb}}{{!-- c --}}` x";
+
+        assert_eq!(parser.process(input).unwrap(), "`𝐚 𝐛` x");
+        let nodes = parser.parse_ast(input).unwrap();
+        assert_eq!(parser.render_ast(&nodes).unwrap().markdown, "`𝐚 𝐛` x");
+        // Written back with markers, the span renders without the option too
+        let source = ast::to_source(&nodes);
+        assert_eq!(
+            TemplateParser::new().unwrap().process(&source).unwrap(),
+            "`𝐚 𝐛` x"
+        );
+    }
+
+    #[rstest]
+    #[case("<!-- {{mb}}a{{/mb}} -->{{mb}}b{{/mb}}", "<!-- {{mb}}a{{/mb}} -->𝐛")]
+    #[case(
+        "<PRE class=\"x\">\n{{mb}}a{{/mb}}\n</PRE>\n{{mb}}b{{/mb}}",
+        "<PRE class=\"x\">\n{{mb}}a{{/mb}}\n</PRE>\n𝐛"
+    )]
+    #[case(
+        "<script>let t = `{{x}}`;</script> `{{mb}}` {{mb}}c{{/mb}}",
+        "<script>let t = `{{x}}`;</script> `{{mb}}` 𝐜"
+    )]
+    #[case("<prefix>{{mb}}a{{/mb}}</prefix>", "<prefix>𝐚</prefix>")]
+    #[case("<!-- unclosed {{mb}}a{{/mb}}", "<!-- unclosed 𝐚")]
+    fn test_html_blocks_preserved(#[case] input: &str, #[case] expected: &str) {
+        let parser = TemplateParser::new().unwrap();
+        assert_eq!(parser.process(input).unwrap(), expected);
+        assert_eq!(parser.process_with_diagnostics(input).markdown, expected);
+        let nodes = parser.parse_ast(input).unwrap();
+        assert_eq!(parser.render_ast(&nodes).unwrap().markdown, expected);
+    }
+
+    #[rstest]
+    #[case("$x^{{2}}$ {{mb}}a{{/mb}}", "$x^{{2}}$ 𝐚")]
+    #[case(
+        "$$\n\\frac{{{mb}}a{{/mb}}}{2}\n$$\n{{mb}}b{{/mb}}",
+        "$$\n\\frac{{{mb}}a{{/mb}}}{2}\n$$\n𝐛"
+    )]
+    #[case("$5 and {{mb}}c{{/mb}} $10", "$5 and 𝐜 $10")]
+    #[case("\\$x {{mb}}d{{/mb}}$", "\\$x 𝐝$")]
+    #[case("`$a` {{mb}}e{{/mb}} `$b`", "`$a` 𝐞 `$b`")]
+    fn test_math_preserved(#[case] input: &str, #[case] expected: &str) {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.load_config(&serde_json::from_str(r#"{"math": true}"#).unwrap());
+        assert_eq!(parser.process(input).unwrap(), expected);
+        assert_eq!(parser.process_with_diagnostics(input).markdown, expected);
+        let nodes = parser.parse_ast(input).unwrap();
+        assert_eq!(parser.render_ast(&nodes).unwrap().markdown, expected);
+    }
+
+    #[test]
+    fn test_math_processed_by_default() {
+        let output = TemplateParser::new()
+            .unwrap()
+            .process("$x {{mb}}a{{/mb}}$")
+            .unwrap();
+        assert_eq!(output, "$x 𝐚$");
+    }
+
+    #[rstest]
+    #[case("{{mb}}see [docs](https://x.dev){{/mb}}", "𝐬𝐞𝐞 [𝐝𝐨𝐜𝐬](https://x.dev)")]
+    #[case(
+        "{{mb}}[docs][ref]{{/mb}}\n\n[ref]: https://x.dev",
+        "[𝐝𝐨𝐜𝐬][ref]\n\n[ref]: https://x.dev"
+    )]
+    #[case(
+        "<a href=\"{{mb}}x{{/mb}}\">{{mb}}x{{/mb}}</a>",
+        "<a href=\"{{mb}}x{{/mb}}\">𝐱</a>"
+    )]
+    #[case("{{mb}}ok{{/mb}} \u{F0000} [a](b)", "𝐨𝐤 \u{F0000} [a](b)")]
+    fn test_commonmark_mode(#[case] input: &str, #[case] expected: &str) {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.load_config(&serde_json::from_str(r#"{"commonmark": true}"#).unwrap());
+        assert_eq!(parser.process(input).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_commonmark_mode_off_by_default() {
+        let output = TemplateParser::new()
+            .unwrap()
+            .process("{{mb}}[a](b){{/mb}}")
+            .unwrap();
+        assert_eq!(output, "[𝐚](𝐛)");
+    }
+
+    #[test]
+    fn test_html_blocks_processed_when_disabled() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_options(ParserOptions {
+            preserve_html_blocks: false,
+            ..ParserOptions::trusted()
+        });
+        let input = "<!-- {{mb}}a{{/mb}} --> <pre>{{mb}}b{{/mb}}</pre>";
+
+        assert_eq!(parser.process(input).unwrap(), "<!-- 𝐚 --> <pre>𝐛</pre>");
+        let nodes = parser.parse_ast(input).unwrap();
+        assert_eq!(
+            parser.render_ast(&nodes).unwrap().markdown,
+            "<!-- 𝐚 --> <pre>𝐛</pre>"
+        );
+    }
+
+    #[test]
+    fn test_inline_code_force_marker_error() {
+        let input = "`{{!mathbold}}X` ok";
+        test_process_err!(input);
+
+        let result = TemplateParser::new()
+            .unwrap()
+            .process_with_diagnostics(input);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(&input[result.diagnostics[0].range.clone()], "{{!mathbold}}");
+        assert_eq!(result.markdown, input);
+    }
+
+    #[rstest]
+    #[case("{{ui:tech:rust:label={{mathbold}}Rust{{/mathbold}}/}}", "-𝐑𝐮𝐬𝐭-")]
+    #[case(
+        "{{ui:tech:rust:label={{mb:separator=dot}}Rust: 1{{/mb}}:style=flat/}}",
+        "-𝐑·𝐮·𝐬·𝐭·:·%20·𝟏-"
+    )]
+    #[case("{{ui:swatch:pink:label=a {{script}}b{{/script}} c/}}", "-a%20𝒷%20c-")]
+    #[case(
+        "{{ui:row}}{{ui:tech:rust:label={{mathbold}}R{{/mathbold}}/}}{{/ui}}",
+        "-𝐑-"
+    )]
+    fn test_ui_param_with_style(#[case] input: &str, #[case] expected: &str) {
+        let output = TemplateParser::new().unwrap().process(input).unwrap();
+        assert!(output.contains(expected), "{}", output);
+        assert!(!output.contains("{{"), "{}", output);
+    }
+
+    #[test]
+    fn test_ui_param_style_uses_style_defaults() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_style_defaults("mathbold", StyleDefaults::parse("spacing=1").unwrap());
+        let output = parser
+            .process("{{ui:tech:rust:label={{mathbold}}Rs{{/mathbold}}/}}")
+            .unwrap();
+        assert!(output.contains("-𝐑%20𝐬-"), "{}", output);
+    }
+
+    #[test]
+    fn test_ui_param_unknown_style_not_taken() {
+        // The value ends at the unknown template's `}}`
+        test_process_err!("{{ui:tech:rust:label={{nosuch}}R{{/nosuch}}/}}");
+    }
+
+    #[test]
+    fn test_frame_multiline() {
+        test_process_bookends!(
+            "{{frame:gradient}}\nLine 1\nLine 2\n{{/frame}}"
+            => starts "▓\u{fe0e}▒\u{fe0e}░\u{fe0e}", ends "░\u{fe0e}▒\u{fe0e}▓\u{fe0e}",
+            contains ["Line 1", "Line 2"]
+        );
+    }
+
+    #[test]
+    fn test_frame_multiline_with_styles() {
+        test_process_contains!(
+            "{{frame:solid-left}}\n### {{mathbold}}Title{{/mathbold}}\nContent\n{{/frame}}"
+            => ["█\u{fe0e}▌\u{fe0e}", "𝐓𝐢𝐭𝐥𝐞", "Content"]
+        );
+    }
+
+    #[test]
+    fn test_frame_multiline_with_ui_components() {
+        use crate::renderer::svg::SvgBackend;
+
+        let parser =
+            TemplateParser::with_backend(Box::new(SvgBackend::new("assets/test"))).unwrap();
+        let input = "{{frame:gradient}}\n{{ui:swatch:pink/}}\n{{ui:swatch:success/}}\n{{/frame}}";
+        let result = parser.process_with_assets(input).unwrap();
+
+        // Should process frame correctly
+        assert!(result.markdown.starts_with("▓\u{fe0e}▒\u{fe0e}░\u{fe0e}"));
+        assert!(result.markdown.ends_with("░\u{fe0e}▒\u{fe0e}▓\u{fe0e}"));
+
+        // Should generate assets for UI components
+        assert_eq!(result.assets.len(), 2);
+        // Should generate assets for UI components (both swatches)
+        assert!(result.markdown.contains("![](assets/test/swatch_"));
+    }
+
+    #[test]
+    fn test_process_with_assets_preserves_code_blocks() {
+        use crate::renderer::svg::SvgBackend;
+
+        let parser =
+            TemplateParser::with_backend(Box::new(SvgBackend::new("assets/test"))).unwrap();
+        let input = "```\n{{ui:swatch:pink/}}\n```\n{{ui:swatch:pink/}}";
+        let result = parser.process_with_assets(input).unwrap();
+
+        // Code block should be preserved
+        assert!(result.markdown.contains("```\n{{ui:swatch:pink/}}\n```"));
+
+        // Only one asset generated (outside code block)
+        assert_eq!(result.assets.len(), 1);
+    }
+
+    #[test]
+    fn test_tech_group_layout_composes_one_svg() {
+        use crate::renderer::svg::SvgBackend;
+
+        let parser =
+            TemplateParser::with_backend(Box::new(SvgBackend::new("assets/test"))).unwrap();
+        let input = "{{ui:tech-group:columns=2:gap=4}}\n{{ui:tech:rust/}}{{ui:tech:go/}}\n{{ui:version:1.0.0/}}\n{{/ui}}";
+        let result = parser.process_with_assets(input).unwrap();
+
+        assert_eq!(result.assets.len(), 1);
+        assert!(result.markdown.starts_with("![](assets/test/group_"));
+        let svg = String::from_utf8_lossy(result.assets[0].file_bytes().unwrap()).into_owned();
+        // Version badge starts the second row, below the gap
+        assert!(svg.contains("<g transform=\"translate(0 24)\"><svg"));
+    }
+
+    #[test]
+    fn test_tech_group_layout_html_rows() {
+        let parser = TemplateParser::new().unwrap();
+        let result = parser
+            .process(
+                "{{ui:tech-group:columns=1:align=right}}{{ui:tech:rust/}}{{ui:tech:go/}}{{/ui}}",
+            )
+            .unwrap();
+
+        assert!(result.starts_with("<p align=\"right\">"));
+        assert_eq!(result.matches("<img ").count(), 2);
+        assert!(result.contains("> <br> <img "));
+    }
+
+    #[test]
+    fn test_tech_group_layout_rejects_text() {
+        let parser = TemplateParser::new().unwrap();
+        let err = parser
+            .process("{{ui:tech-group:columns=2}}{{ui:tech:rust/}} and more{{/ui}}")
+            .unwrap_err();
+        assert!(err.to_string().contains("found 'and more'"), "{}", err);
+    }
+
+    #[test]
+    fn test_statcard_row_composes_one_svg() {
+        use crate::renderer::svg::SvgBackend;
+
+        let parser =
+            TemplateParser::with_backend(Box::new(SvgBackend::new("assets/test"))).unwrap();
+        let input = "{{ui:statcard-row}}\n\
+            {{ui:statcard:label=Downloads:value=1.2M:icon=download:trend=+8%/}}\n\
+            {{ui:statcard:label=Issues:value=12:trend=-3/}}\n\
+            {{/ui}}";
+        let result = parser.process_with_assets(input).unwrap();
+
+        assert_eq!(result.assets.len(), 1);
+        assert!(result.markdown.starts_with("![](assets/test/group_"));
+        let svg = String::from_utf8_lossy(result.assets[0].file_bytes().unwrap()).into_owned();
+        assert!(svg.contains("width=\"328\" height=\"80\""));
+        assert!(svg.contains("<g transform=\"translate(168 0)\"><svg"));
+        assert!(svg.contains(">▲ 8%</text>"));
+        assert!(svg.contains("fill=\"#EF4444\" font-family=\"Arial, sans-serif\" font-size=\"12\" font-weight=\"bold\">▼ 3</text>"));
+    }
+
+    #[test]
+    fn test_matrix_renders_badge_table() {
+        let parser = TemplateParser::new().unwrap();
+        let result = parser
+            .process("{{ui:matrix}}\nFeature | {{mathbold}}A{{/mathbold}} | B\nSVG | yes | partial\n{{/ui}}")
+            .unwrap();
+
+        let lines: Vec<&str> = result.lines().collect();
+        assert_eq!(lines[0], "| Feature | 𝐀 | B |");
+        assert_eq!(lines[1], "| --- | :---: | :---: |");
+        assert!(lines[2].starts_with("| SVG | ![](https://img.shields.io/badge/-✓-22C55E?"));
+        assert!(lines[2].contains("/badge/-~-EAB308?"));
     }
 
     #[test]
-    fn test_complex_composition() {
-        test_process_contains!(
-            r#"# {{frame:gradient}}{{mathbold:separator=dot}}TITLE{{/mathbold}}{{/frame}}
+    fn test_matrix_text_backend_uses_glyphs() {
+        use crate::renderer::plaintext::PlainTextBackend;
 
-{{frame:solid-left}}{{italic}}Important note{{/italic}}{{/frame}}
+        let parser = TemplateParser::with_backend(Box::new(PlainTextBackend::new())).unwrap();
+        let result = parser
+            .process(
+                "{{ui:matrix}}\nFeature | A | B\nSVG | yes | no\nDocs | partial | soon\n{{/ui}}",
+            )
+            .unwrap();
 
-Regular text with {{mathbold:spacing=1}}spacing{{/mathbold}}"#
-            => [
-                "▓\u{fe0e}▒\u{fe0e}░\u{fe0e} 𝐓·𝐈·𝐓·𝐋·𝐄 ░\u{fe0e}▒\u{fe0e}▓\u{fe0e}",
-                "█\u{fe0e}▌\u{fe0e}𝐼𝑚𝑝𝑜𝑟𝑡𝑎𝑛𝑡 𝑛𝑜𝑡𝑒",
-                "𝐬 𝐩 𝐚 𝐜 𝐢 𝐧 𝐠"
-            ]
+        assert_eq!(
+            result,
+            "| Feature | A | B |\n| --- | :---: | :---: |\n| SVG | ✓ | ✗ |\n| Docs | ~ | soon |"
         );
     }
 
-    // UI Component Tests
-
     #[test]
-    fn test_ui_swatch() {
-        // Should expand to shields:block with pink color resolved
-        test_process_contains!("{{ui:swatch:pink/}}" => ["![](", "F41C80"]);
-    }
+    fn test_steps_text_backend_uses_circled_digits() {
+        use crate::renderer::plaintext::PlainTextBackend;
 
-    #[test]
-    fn test_ui_tech() {
-        // Should expand to shields:icon with logo
-        test_process_contains!("{{ui:tech:rust/}}" => ["![](", "logo=rust"]);
+        let parser = TemplateParser::with_backend(Box::new(PlainTextBackend::new())).unwrap();
+        let result = parser
+            .process("{{ui:steps:current=2}}1. Install\n2. Configure\n3. Ship{{/ui}}")
+            .unwrap();
+
+        assert_eq!(result, "❶ Install ── ❷ **Configure** ── ③ Ship");
     }
 
     #[test]
-    fn test_ui_multiple_inline() {
-        // Should have two shields with different logos
-        test_process_contains!("{{ui:tech:rust/}} {{ui:tech:python/}}" => ["logo=rust", "logo=python"]);
+    fn test_latest_release_keeps_notes_literal() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_changelog(
+            "## [1.2.0] - 2024-05-01\n\n- Added `{{ui:tree}}`\n- {{mathbold}}Bold{{/mathbold}} headings\n",
+        );
+        let result = parser.process("{{ui:latest-release/}}").unwrap();
+
+        assert!(result.starts_with("![](https://img.shields.io/badge/"));
+        assert!(result.contains(" Released 2024-05-01\n\n### What's new\n\n"));
+        assert!(result.ends_with("- Added `{{ui:tree}}`\n- {{mathbold}}Bold{{/mathbold}} headings"));
     }
 
     #[test]
-    fn test_ui_in_markdown() {
-        test_process_contains!("# Header\n\n{{ui:swatch:pink/}}\n\n## Section" => ["# Header", "![](", "## Section"]);
+    fn test_msrv_reads_cargo_toml() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_project_file("Cargo.toml", "[package]\nrust-version = \"1.70\"\n");
+        let result = parser.process("{{ui:msrv/}}").unwrap();
+
+        assert!(result.starts_with("![](https://img.shields.io/badge/"));
+        assert!(result.contains("MSRV%201.70"), "{}", result);
     }
 
     #[test]
-    fn test_ui_unknown_component() {
-        // Keep verbose - tests specific error message content
-        let parser = TemplateParser::new().unwrap();
-        let result = parser.process("{{ui:nonexistent/}}");
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("Unknown component"));
+    fn test_coverage_reads_project_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("coverage")).unwrap();
+        std::fs::write(
+            dir.path().join("coverage/lcov.info"),
+            "SF:src/lib.rs\nLF:50\nLH:46\nend_of_record\n",
+        )
+        .unwrap();
+
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_project_root(dir.path());
+        let result = parser
+            .process("{{ui:coverage:file=coverage/lcov.info/}}")
+            .unwrap();
+
+        assert!(result.contains("-coverage:%2092%25-22C55E?"), "{}", result);
+        assert!(result.contains("22C55E"));
     }
 
     #[test]
-    fn test_ui_unclosed() {
-        test_process_err!("{{ui:row}}TITLE");
+    fn test_bench_compares_with_baseline() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_project_file(
+            "target/criterion/parser/small/new/estimates.json",
+            r#"{"mean": {"point_estimate": 42000.0}}"#,
+        );
+        parser.set_project_file(
+            "target/criterion/parser/small/base/estimates.json",
+            r#"{"mean": {"point_estimate": 48000.0}}"#,
+        );
+        let result = parser.process("{{ui:bench:parser/small/}}").unwrap();
+
+        assert!(
+            result.contains("-parser/small:%2042.00%20µs%20(--12.5%25)-22C55E?"),
+            "{}",
+            result
+        );
     }
 
     #[test]
-    fn test_frame_multiline() {
-        test_process_bookends!(
-            "{{frame:gradient}}\nLine 1\nLine 2\n{{/frame}}"
-            => starts "▓\u{fe0e}▒\u{fe0e}░\u{fe0e}", ends "░\u{fe0e}▒\u{fe0e}▓\u{fe0e}",
-            contains ["Line 1", "Line 2"]
+    fn test_tests_reads_junit_report() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_project_file(
+            "target/nextest/ci/junit.xml",
+            r#"<testsuites><testsuite name="mdfx" tests="48" failures="0" skipped="2"/></testsuites>"#,
+        );
+
+        let badge = parser
+            .process("{{ui:tests:file=target/nextest/ci/junit.xml/}}")
+            .unwrap();
+        assert!(
+            badge.contains("-tests:%2046%20passed,%202%20skipped-22C55E?"),
+            "{}",
+            badge
         );
+
+        let bar = parser
+            .process("{{ui:tests:file=target/nextest/ci/junit.xml:display=bar/}}")
+            .unwrap();
+        assert!(bar.contains("/badge/passed-46-22C55E?"), "{}", bar);
+        assert!(bar.contains("/badge/skipped-2-EAB308?"), "{}", bar);
+        assert!(!bar.contains("failed"), "{}", bar);
     }
 
     #[test]
-    fn test_frame_multiline_with_styles() {
-        test_process_contains!(
-            "{{frame:solid-left}}\n### {{mathbold}}Title{{/mathbold}}\nContent\n{{/frame}}"
-            => ["█\u{fe0e}▌\u{fe0e}", "𝐓𝐢𝐭𝐥𝐞", "Content"]
+    fn test_tree_renders_code_fence() {
+        let parser = TemplateParser::new().unwrap();
+        let result = parser
+            .process("Layout:\n\n{{ui:tree}}src/\n  lib.rs\n  parser.rs{{/ui}}\n")
+            .unwrap();
+
+        assert_eq!(
+            result,
+            "Layout:\n\n```\nsrc/\n├── lib.rs\n└── parser.rs\n```\n"
         );
     }
 
-    #[test]
-    fn test_frame_multiline_with_ui_components() {
-        use crate::renderer::svg::SvgBackend;
+    #[rstest]
+    #[case("{{ui:divider:width=6/}}", "──────")]
+    #[case("{{ui:divider:style=wave:width=9:label=Usage/}}", "∿ Usage ∿")]
+    #[case("{{ui:divider:pattern==-:width=5/}}", "=-=-=")]
+    fn test_divider_component(#[case] input: &str, #[case] expected: &str) {
+        test_process!(input => expected);
+    }
 
-        let parser =
-            TemplateParser::with_backend(Box::new(SvgBackend::new("assets/test"))).unwrap();
-        let input = "{{frame:gradient}}\n{{ui:swatch:pink/}}\n{{ui:swatch:success/}}\n{{/frame}}";
-        let result = parser.process_with_assets(input).unwrap();
+    #[test]
+    fn test_maintenance_describe() {
+        let parser = TemplateParser::new().unwrap();
+        let result = parser
+            .process("{{ui:maintenance:deprecated:describe=true/}}")
+            .unwrap();
+        let (badge, description) = result.split_once("\n\n").unwrap();
+        assert!(badge.contains("Unsupported-E05D44"), "{}", badge);
+        assert_eq!(
+            description,
+            "Project Status: Unsupported – The project has reached a stable, usable state \
+             but the author(s) have ceased all work on it. A new maintainer may be desired."
+        );
+    }
 
-        // Should process frame correctly
-        assert!(result.markdown.starts_with("▓\u{fe0e}▒\u{fe0e}░\u{fe0e}"));
-        assert!(result.markdown.ends_with("░\u{fe0e}▒\u{fe0e}▓\u{fe0e}"));
+    #[rstest]
+    #[case("github", true)]
+    #[case("gitlab", true)]
+    #[case("pypi", false)]
+    #[case("jira", false)]
+    fn test_hero_centered_per_target(#[case] target: &str, #[case] centered: bool) {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_target(crate::targets::get_target(target).unwrap().as_ref());
+        let result = parser
+            .process("{{ui:hero:title=mdfx:badges=rust:links=Docs|https://docs.rs/mdfx/}}")
+            .unwrap();
 
-        // Should generate assets for UI components
-        assert_eq!(result.assets.len(), 2);
-        // Should generate assets for UI components (both swatches)
-        assert!(result.markdown.contains("![](assets/test/swatch_"));
+        assert_eq!(
+            result.starts_with("<div align=\"center\">\n\n# mdfx"),
+            centered
+        );
+        assert!(result.contains("[Docs](https://docs.rs/mdfx)"));
+        assert_eq!(result.matches("![](").count(), 1);
     }
 
-    #[test]
-    fn test_process_with_assets_preserves_code_blocks() {
+    #[rstest]
+    #[case("{{ui:tech:rust:url=https://rust-lang.org/}}", "https://rust-lang.org")]
+    #[case(
+        "{{ui:tech:rust:url=https://rust-lang.org:label=Rust/}}",
+        "https://rust-lang.org"
+    )]
+    fn test_url_param_keeps_scheme(#[case] input: &str, #[case] url: &str) {
         use crate::renderer::svg::SvgBackend;
 
         let parser =
             TemplateParser::with_backend(Box::new(SvgBackend::new("assets/test"))).unwrap();
-        let input = "```\n{{ui:swatch:pink/}}\n```\n{{ui:swatch:pink/}}";
-        let result = parser.process_with_assets(input).unwrap();
-
-        // Code block should be preserved
-        assert!(result.markdown.contains("```\n{{ui:swatch:pink/}}\n```"));
-
-        // Only one asset generated (outside code block)
-        assert_eq!(result.assets.len(), 1);
+        let result = parser.process(input).unwrap();
+        assert!(result.ends_with(&format!("]({})", url)), "{}", result);
     }
 
     #[test]
@@ -3264,3 +6582,331 @@ mod partial_tests {
         assert_eq!(result, "Hello, World!");
     }
 }
+
+#[cfg(test)]
+mod options_tests {
+    use super::*;
+    use crate::components::PluginCall;
+    use rstest::rstest;
+
+    fn untrusted() -> TemplateParser {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_options(ParserOptions::untrusted());
+        parser
+    }
+
+    struct Echo;
+
+    impl ComponentPlugin for Echo {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn expand(&self, call: &PluginCall<'_>) -> Result<ComponentOutput> {
+            Ok(ComponentOutput::Template(call.args.join(" ")))
+        }
+    }
+
+    #[test]
+    fn test_default_options_are_trusted() {
+        let parser = TemplateParser::new().unwrap();
+        assert_eq!(parser.options(), &ParserOptions::trusted());
+    }
+
+    #[test]
+    fn test_self_referencing_partial_hits_depth_limit() {
+        let mut parser = untrusted();
+        parser.add_partial("loop", "x{{partial:loop/}}");
+
+        let err = parser.process("{{partial:loop/}}").unwrap_err();
+        assert!(matches!(err, Error::LimitExceeded(_)), "{}", err);
+        assert!(err.to_string().contains("loop → loop"));
+    }
+
+    #[test]
+    fn test_nesting_within_limit() {
+        let parser = untrusted();
+        let input = format!("{}x{}", "{{fr:star}}".repeat(10), "{{/}}".repeat(10));
+        assert!(parser.process(&input).is_ok());
+    }
+
+    #[test]
+    fn test_exponential_partials_hit_output_limit() {
+        let mut parser = untrusted();
+        parser.add_partial("a0", "lol".repeat(10));
+        for i in 1..10 {
+            let body = format!("{{{{partial:a{}/}}}}", i - 1).repeat(10);
+            parser.add_partial(format!("a{}", i), body);
+        }
+
+        let err = parser.process("{{partial:a9/}}").unwrap_err();
+        assert!(matches!(err, Error::LimitExceeded(_)), "{}", err);
+    }
+
+    #[rstest]
+    #[case("{{mathbold:spacing=99999999999}}ab{{/mathbold}}")]
+    #[case("{{frame:glyph:star/pad=99999999999}}x{{/frame}}")]
+    #[case("{{frame:glyph:star*3/spacing=99999999999}}x{{/frame}}")]
+    #[case("{{frame:gradient/spacing=99999999999}}x{{/frame}}")]
+    fn test_huge_padding_hits_output_limit(#[case] input: &str) {
+        let err = untrusted().process(input).unwrap_err();
+        assert!(matches!(err, Error::LimitExceeded(_)), "{}", err);
+
+        // Trusted parsers still refuse rather than exhaust memory
+        let err = TemplateParser::new().unwrap().process(input).unwrap_err();
+        assert!(matches!(err, Error::LimitExceeded(_)), "{}", err);
+    }
+
+    #[test]
+    fn test_input_size_limit() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_options(ParserOptions {
+            max_input_bytes: Some(8),
+            ..ParserOptions::trusted()
+        });
+
+        assert!(parser.process("short").is_ok());
+        assert!(matches!(
+            parser.process("far too long"),
+            Err(Error::LimitExceeded(_))
+        ));
+
+        let recovered = parser.process_with_diagnostics("far too long");
+        assert!(recovered.has_errors());
+        assert_eq!(recovered.markdown, "far too long");
+    }
+
+    #[test]
+    fn test_untrusted_rejects_live_badges() {
+        let err = untrusted()
+            .process("{{ui:live:crates:serde:version/}}")
+            .unwrap_err();
+        assert!(matches!(err, Error::NotAllowed(_)), "{}", err);
+    }
+
+    #[test]
+    fn test_untrusted_rejects_remote_icons() {
+        let err = untrusted()
+            .process("{{ui:tech:acme:icon=url:https://example.com/logo.svg/}}")
+            .unwrap_err();
+        assert!(err.to_string().contains("needs network access"), "{}", err);
+    }
+
+    #[test]
+    fn test_untrusted_rejects_plugins() {
+        let mut parser = untrusted();
+        parser.register_component(Arc::new(Echo)).unwrap();
+        assert!(matches!(
+            parser.process("{{ui:echo:hi/}}"),
+            Err(Error::NotAllowed(_))
+        ));
+
+        parser.set_options(ParserOptions {
+            allow_plugins: true,
+            ..ParserOptions::untrusted()
+        });
+        assert_eq!(parser.process("{{ui:echo:hi/}}").unwrap(), "hi");
+    }
+
+    #[rstest]
+    #[case("{{snippet:src/lib.rs/}}")]
+    #[case("{{ui:coverage:file=lcov.info/}}")]
+    #[case("{{ui:tests:file=junit.xml/}}")]
+    #[case("{{ui:msrv/}}")]
+    #[case("{{ui:bench:parse/}}")]
+    fn test_untrusted_rejects_project_files(#[case] template: &str) {
+        let mut parser = untrusted();
+        parser.set_project_file("src/lib.rs", "pub fn f() {}\n");
+        parser.set_project_file("Cargo.toml", "[package]\nrust-version = \"1.70\"\n");
+        let err = parser.process(template).unwrap_err();
+        assert!(matches!(err, Error::NotAllowed(_)), "{}", err);
+        let err = parser.validate(template).unwrap_err();
+        assert!(matches!(err, Error::NotAllowed(_)), "{}", err);
+    }
+
+    #[rstest]
+    #[case("{{snippet:/etc/hostname/}}")]
+    #[case("{{snippet:../Cargo.toml/}}")]
+    fn test_untrusted_snippets_with_project_root(#[case] template: &str) {
+        let mut parser = untrusted();
+        parser.set_project_root(concat!(env!("CARGO_MANIFEST_DIR"), "/src"));
+        let err = parser.process(template).unwrap_err();
+        assert!(matches!(err, Error::NotAllowed(_)), "{}", err);
+    }
+
+    #[test]
+    fn test_allow_files() {
+        let mut parser = untrusted();
+        parser.set_project_file("src/lib.rs", "pub fn f() {}\n");
+        parser.set_options(ParserOptions {
+            allow_files: true,
+            ..ParserOptions::untrusted()
+        });
+        assert_eq!(
+            parser.process("{{snippet:src/lib.rs/}}").unwrap(),
+            "```rust\npub fn f() {}\n```"
+        );
+    }
+
+    #[test]
+    fn test_untrusted_rejects_maintenance_auto() {
+        let mut parser = untrusted();
+        parser.set_project_root(env!("CARGO_MANIFEST_DIR"));
+        for template in [
+            "{{ui:maintenance:auto/}}",
+            "{{ui:maintenance:auto:repo=rust-lang/rust/}}",
+        ] {
+            let err = parser.process(template).unwrap_err();
+            assert!(matches!(err, Error::NotAllowed(_)), "{}", err);
+        }
+        assert!(parser.process("{{ui:maintenance:active/}}").is_ok());
+    }
+
+    fn nested_frames(depth: usize) -> String {
+        format!("{}x{}", "{{fr:star}}".repeat(depth), "{{/}}".repeat(depth))
+    }
+
+    #[test]
+    fn test_default_depth_limit() {
+        let parser = TemplateParser::new().unwrap();
+        // One level for the top-level text plus one per frame
+        assert!(parser.process(&nested_frames(63)).is_ok());
+
+        let err = parser.process(&nested_frames(64)).unwrap_err();
+        assert!(matches!(err, Error::LimitExceeded(_)), "{}", err);
+        assert!(err.to_string().contains("64 levels"));
+    }
+
+    #[test]
+    fn test_deep_nesting_in_ast_is_limited() {
+        let parser = TemplateParser::new().unwrap();
+        assert!(matches!(
+            parser.parse_ast(&nested_frames(500)),
+            Err(Error::LimitExceeded(_))
+        ));
+
+        let recovered = parser.process_with_diagnostics(&nested_frames(500));
+        assert!(recovered.has_errors());
+    }
+
+    #[test]
+    fn test_mutually_recursive_partials() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.add_partial("a", "[{{partial:b/}}]");
+        parser.add_partial("b", "({{partial:c/}})");
+        parser.add_partial("c", "{{partial:b/}}");
+
+        let err = parser.process("{{partial:a/}}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Limit exceeded: partial 'b' includes itself (b → c → b)"
+        );
+    }
+
+    #[test]
+    fn test_partial_reused_in_siblings_is_not_a_cycle() {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.add_partial("dot", "•");
+        parser.add_partial("dots", "{{partial:dot/}}{{partial:dot/}}");
+
+        assert_eq!(parser.process("{{partial:dots/}}").unwrap(), "••");
+    }
+
+    #[test]
+    fn test_config_limits() {
+        let config: MdfxConfig =
+            serde_json::from_str(r#"{"limits": {"max_depth": 3, "max_output_bytes": 64}}"#)
+                .unwrap();
+        let mut parser = TemplateParser::new().unwrap();
+        parser.load_config(&config);
+
+        assert_eq!(parser.options().max_depth, Some(3));
+        assert!(parser.process(&nested_frames(2)).is_ok());
+        assert!(parser.process(&nested_frames(3)).is_err());
+        assert!(matches!(
+            parser.process(&"x".repeat(65)),
+            Err(Error::LimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_untrusted_renders_builtins() {
+        let parser = untrusted();
+        assert_eq!(
+            parser.process("{{mathbold}}AB{{/mathbold}}").unwrap(),
+            TemplateParser::new()
+                .unwrap()
+                .process("{{mathbold}}AB{{/mathbold}}")
+                .unwrap()
+        );
+        assert!(parser.process("{{ui:tech:rust/}}").is_ok());
+    }
+}
+
+#[cfg(test)]
+mod placeholder_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("{{ui:live:crates:serde/}}", "version:%20—-6B7280")]
+    #[case("{{ui:live:github:rust-lang/rust:forks/}}", "forks:%20—-6B7280")]
+    #[case("{{ui:live:actions:rust-lang/rust/}}", "conclusion:%20—-6B7280")]
+    #[case(
+        "{{ui:live:github:rust-lang/rust:release-downloads/}}",
+        "release__downloads:%20—-6B7280"
+    )]
+    #[case(
+        "{{ui:live:npm:react:metric=license:bg=112233/}}",
+        "license:%20—-112233"
+    )]
+    #[case("{{ui:live:github:rust-lang/rust:lang=de/}}", "sterne:%20—-6B7280")]
+    #[case("{{ui:live:crates:serde:label=crate/}}", "crate:%20—-6B7280")]
+    #[case(
+        "{{ui:ci-matrix:owner/repo:workflows=test,lint/}}",
+        "test:%20—-6B7280?style=flat-square\"> <img alt=\"\" src=\"https://img.shields.io/badge/-lint:%20—-6B7280"
+    )]
+    #[case(
+        "{{ui:ci-matrix:owner/repo:workflows=test,lint:display=badge/}}",
+        "ci:%20test%20—%20lint%20—-6B7280"
+    )]
+    fn test_live_badge_placeholders(#[case] template: &str, #[case] expected: &str) {
+        let mut parser = TemplateParser::new().unwrap();
+        assert!(parser.process(template).is_err());
+
+        parser.set_placeholders(true);
+        let output = parser.process(template).unwrap();
+        assert!(output.contains(expected), "{}", output);
+    }
+
+    #[rstest]
+    #[case("{{ui:live:nope:x/}}")]
+    #[case("{{ui:live:crates/}}")]
+    #[case("{{ui:ci-matrix:owner/repo/}}")]
+    #[case("{{ui:live:crates:serde:lang=xx/}}")]
+    fn test_live_badge_placeholders_reject_bad_templates(#[case] template: &str) {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_placeholders(true);
+        assert!(parser.process(template).is_err());
+    }
+
+    #[rstest]
+    #[case("{{ui:live:github:rust-lang/rust/}}", "étoiles:%20—-6B7280")]
+    #[case("{{ui:live:github:rust-lang/rust:forks/}}", "⑂:%20—-6B7280")]
+    #[case("{{ui:live:github:rust-lang/rust:lang=it/}}", "stelle:%20—-6B7280")]
+    #[case(
+        "{{ui:live:github:rust-lang/rust:last-commit/}}",
+        "dernier%20commit:%20—-6B7280"
+    )]
+    fn test_live_badge_labels_from_config(#[case] template: &str, #[case] expected: &str) {
+        let mut parser = TemplateParser::new().unwrap();
+        parser.load_config(&MdfxConfig {
+            lang: Some("fr".to_string()),
+            labels: HashMap::from([("forks".to_string(), "⑂".to_string())]),
+            ..MdfxConfig::new()
+        });
+        parser.set_placeholders(true);
+        let output = parser.process(template).unwrap();
+        assert!(output.contains(expected), "{}", output);
+    }
+}
@@ -14,6 +14,10 @@
 //! - Sparkline: Mini inline chart for data visualization
 //! - Rating: Star/heart rating display with partial fills
 //! - Waveform: Audio-style visualization with bars above/below center
+//! - StatCard: Card with icon, big number, label and trend arrow
+//! - Steps: Numbered step markers joined by connecting lines
+//! - StackedBar: Bar split into colored segments by count (e.g., test results)
+//! - Group: Badges or cards arranged in rows by a group layout
 //!
 //! Text-based transformations (frames, styles, badges) remain as direct
 //! Unicode rendering and don't use this abstraction.
@@ -60,6 +64,115 @@ impl ThumbConfig {
     }
 }
 
+/// Where a step of a `Steps` primitive stands relative to the current one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepState {
+    Done,
+    Current,
+    Pending,
+}
+
+impl StepState {
+    /// State of the 0-based step `index`; without a current step all are done
+    pub fn of(index: usize, current: Option<usize>) -> Self {
+        match current {
+            Some(current) if index + 1 == current => StepState::Current,
+            Some(current) if index + 1 > current => StepState::Pending,
+            _ => StepState::Done,
+        }
+    }
+}
+
+/// One part of a `StackedBar` primitive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarSegment {
+    /// What the segment counts (e.g., "passed")
+    pub label: String,
+    /// Segment size; segments are drawn in proportion to their sum
+    pub value: u32,
+    /// Segment color
+    pub color: String,
+}
+
+impl BarSegment {
+    /// Create a segment
+    pub fn new(label: impl Into<String>, value: u32, color: impl Into<String>) -> Self {
+        BarSegment {
+            label: label.into(),
+            value,
+            color: color.into(),
+        }
+    }
+}
+
+/// How a tech-group or statcard-row arranges its items into rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupLayout {
+    /// Maximum badges per row
+    pub columns: Option<usize>,
+    /// Maximum row width in pixels; badges that don't fit start a new row. SVG-only.
+    pub wrap: Option<u32>,
+    /// Space between badges and between rows in pixels
+    pub gap: u32,
+    /// Maximum badge width in pixels; wider badges are scaled down. SVG-only.
+    pub max_width: Option<u32>,
+    /// Row alignment: "left", "center", or "right"
+    pub align: String,
+}
+
+impl Default for GroupLayout {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            wrap: None,
+            gap: 0,
+            max_width: None,
+            align: "left".to_string(),
+        }
+    }
+}
+
+impl GroupLayout {
+    /// Split badges with the given widths into rows
+    ///
+    /// A row ends when it holds `columns` badges, or when the next badge
+    /// would make it wider than `wrap`. Every row holds at least one badge.
+    pub fn rows(&self, widths: &[u32]) -> Vec<std::ops::Range<usize>> {
+        let mut rows = Vec::new();
+        let mut start = 0;
+        let mut row_width = 0;
+
+        for (i, &width) in widths.iter().enumerate() {
+            let full = self
+                .columns
+                .is_some_and(|columns| i - start >= columns.max(1));
+            let overflows = self
+                .wrap
+                .is_some_and(|wrap| i > start && row_width + self.gap + width > wrap);
+            if full || overflows {
+                rows.push(start..i);
+                start = i;
+                row_width = 0;
+            }
+            row_width += if i > start { self.gap } else { 0 } + width;
+        }
+        if start < widths.len() {
+            rows.push(start..widths.len());
+        }
+        rows
+    }
+
+    /// Split `count` badges into rows of `columns`, for output where badge
+    /// widths aren't known
+    pub fn rows_by_columns(&self, count: usize) -> Vec<std::ops::Range<usize>> {
+        let columns = self.columns.unwrap_or(count).max(1);
+        (0..count)
+            .step_by(columns)
+            .map(|start| start..(start + columns).min(count))
+            .collect()
+    }
+}
+
 /// Configuration for a technology badge.
 ///
 /// Using a struct with Default allows tests to use `..Default::default()`
@@ -439,9 +552,100 @@ pub enum Primitive {
         /// Center line color
         center_line_color: Option<String>,
     },
+
+    /// Stat card - a big number with label, icon and trend, for README dashboards
+    StatCard {
+        /// Caption shown above the value
+        label: String,
+        /// Headline value (e.g., "1.2M")
+        value: String,
+        /// Icon name: a built-in glyph (download, star, ...) or a Simple Icons name
+        icon: Option<String>,
+        /// Change indicator (e.g., "+8%"); a leading + or - picks the arrow
+        trend: Option<String>,
+        /// Card width in pixels
+        width: u32,
+        /// Card height in pixels
+        height: u32,
+        /// Corner radius
+        rx: u32,
+        /// Card background color
+        bg_color: String,
+        /// Value text color
+        value_color: String,
+        /// Label and neutral trend color
+        label_color: String,
+        /// Icon color
+        icon_color: String,
+        /// Trend arrow and text color
+        trend_color: String,
+    },
+
+    /// Steps - numbered markers joined by lines, for onboarding sections
+    Steps {
+        /// Step labels in order
+        items: Vec<String>,
+        /// 1-based current step; earlier steps are done, later ones pending.
+        /// `None` draws every step as done.
+        current: Option<usize>,
+        /// Marker diameter in pixels
+        size: u32,
+        /// Distance between marker centers in pixels
+        spacing: u32,
+        /// Marker and line color for done and current steps
+        color: String,
+        /// Marker and line color for pending steps
+        pending_color: String,
+        /// Step number color
+        text_color: String,
+        /// Label color
+        label_color: String,
+    },
+
+    /// Stacked bar - one bar split into colored segments, like a multi-part progress bar
+    StackedBar {
+        /// Segments from left to right; empty ones are skipped
+        segments: Vec<BarSegment>,
+        /// Total width in pixels
+        width: u32,
+        /// Bar height in pixels
+        height: u32,
+        /// Corner radius
+        rx: u32,
+        /// Color behind the segments, seen when all of them are empty
+        track_color: String,
+    },
+
+    /// Badges or stat cards laid out in rows, rendered as one image where the backend can
+    Group {
+        /// Badges in reading order
+        items: Vec<Primitive>,
+        /// How to arrange the badges
+        layout: GroupLayout,
+    },
 }
 
 impl Primitive {
+    /// Lowercase name of the variant, as recorded in manifest entries
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Primitive::Swatch { .. } => "swatch",
+            Primitive::Tech(_) => "tech",
+            Primitive::Version(_) => "version",
+            Primitive::License(_) => "license",
+            Primitive::Progress { .. } => "progress",
+            Primitive::Donut { .. } => "donut",
+            Primitive::Gauge { .. } => "gauge",
+            Primitive::Sparkline { .. } => "sparkline",
+            Primitive::Rating { .. } => "rating",
+            Primitive::Waveform { .. } => "waveform",
+            Primitive::StatCard { .. } => "statcard",
+            Primitive::Steps { .. } => "steps",
+            Primitive::StackedBar { .. } => "stackedbar",
+            Primitive::Group { .. } => "group",
+        }
+    }
+
     /// Get the default shield style
     pub fn default_style() -> &'static str {
         "flat-square"
@@ -561,6 +765,49 @@ impl Primitive {
         }
     }
 
+    /// Create a simple stat card with defaults (no icon or trend)
+    pub fn simple_statcard(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Primitive::StatCard {
+            label: label.into(),
+            value: value.into(),
+            icon: None,
+            trend: None,
+            width: 160,
+            height: 80,
+            rx: 8,
+            bg_color: "282F3C".to_string(),    // dark3
+            value_color: "FFFFFF".to_string(), // white
+            label_color: "6B7280".to_string(), // gray
+            icon_color: "F41C80".to_string(),  // accent
+            trend_color: "6B7280".to_string(), // gray
+        }
+    }
+
+    /// Create simple steps with defaults (every step done)
+    pub fn simple_steps<S: Into<String>>(items: impl IntoIterator<Item = S>) -> Self {
+        Primitive::Steps {
+            items: items.into_iter().map(Into::into).collect(),
+            current: None,
+            size: 28,
+            spacing: 120,
+            color: "F41C80".to_string(),         // accent
+            pending_color: "475569".to_string(), // slate
+            text_color: "FFFFFF".to_string(),    // white
+            label_color: "6B7280".to_string(),   // gray
+        }
+    }
+
+    /// Create a simple stacked bar with defaults
+    pub fn simple_stacked_bar(segments: Vec<BarSegment>) -> Self {
+        Primitive::StackedBar {
+            segments,
+            width: 200,
+            height: 10,
+            rx: 3,
+            track_color: "475569".to_string(), // slate
+        }
+    }
+
     /// Create a simple waveform with defaults
     pub fn simple_waveform(
         values: Vec<f32>,
@@ -607,6 +854,39 @@ mod tests {
         assert_eq!(s, style);
     }
 
+    #[test]
+    fn test_group_layout_rows_by_columns() {
+        let layout = GroupLayout {
+            columns: Some(2),
+            wrap: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(layout.rows_by_columns(5), vec![0..2, 2..4, 4..5]);
+        assert_eq!(GroupLayout::default().rows_by_columns(3).len(), 1);
+        assert!(layout.rows_by_columns(0).is_empty());
+    }
+
+    #[rstest]
+    #[case(GroupLayout::default(), &[40, 50, 60], &[(0, 3)])]
+    #[case(GroupLayout { columns: Some(2), ..Default::default() }, &[40, 50, 60], &[(0, 2), (2, 3)])]
+    #[case(GroupLayout { wrap: Some(100), ..Default::default() }, &[40, 50, 60], &[(0, 2), (2, 3)])]
+    #[case(GroupLayout { wrap: Some(100), gap: 20, ..Default::default() }, &[40, 50, 60], &[(0, 1), (1, 2), (2, 3)])]
+    #[case(GroupLayout { wrap: Some(10), ..Default::default() }, &[40, 50], &[(0, 1), (1, 2)])]
+    #[case(GroupLayout { columns: Some(3), wrap: Some(95), ..Default::default() }, &[40, 50, 30, 20], &[(0, 2), (2, 4)])]
+    #[case(GroupLayout { columns: Some(2), ..Default::default() }, &[], &[])]
+    fn test_group_layout_rows(
+        #[case] layout: GroupLayout,
+        #[case] widths: &[u32],
+        #[case] expected: &[(usize, usize)],
+    ) {
+        let rows: Vec<_> = layout
+            .rows(widths)
+            .iter()
+            .map(|r| (r.start, r.end))
+            .collect();
+        assert_eq!(rows, expected);
+    }
+
     #[test]
     fn test_primitive_swatch_with_options() {
         let swatch = Primitive::Swatch {
@@ -726,4 +1006,17 @@ mod tests {
         assert_eq!(height, 10);
         assert_eq!(fill_height, 10);
     }
+
+    #[rstest]
+    #[case(0, None, StepState::Done)]
+    #[case(0, Some(2), StepState::Done)]
+    #[case(1, Some(2), StepState::Current)]
+    #[case(2, Some(2), StepState::Pending)]
+    fn test_step_state(
+        #[case] index: usize,
+        #[case] current: Option<usize>,
+        #[case] expected: StepState,
+    ) {
+        assert_eq!(StepState::of(index, current), expected);
+    }
 }
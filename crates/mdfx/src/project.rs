@@ -0,0 +1,147 @@
+//! Project manifest parsing
+//!
+//! Reads toolchain requirements from the manifests at a project root, for
+//! `{{ui:msrv/}}`, `{{ui:node-engines/}}` and `{{ui:python-requires/}}`.
+//! Only the keys those components need are looked up; the files aren't
+//! otherwise validated.
+//!
+//! ```
+//! use mdfx::project::{node_engines, rust_version};
+//!
+//! let cargo_toml = "[package]\nname = \"demo\"\nrust-version = \"1.70\"\n";
+//! assert_eq!(rust_version(cargo_toml).as_deref(), Some("1.70"));
+//!
+//! let package_json = r#"{"name": "demo", "engines": {"node": ">=18"}}"#;
+//! assert_eq!(node_engines(package_json).as_deref(), Some(">=18"));
+//! ```
+
+/// Rust package manifest
+pub const CARGO_TOML: &str = "Cargo.toml";
+
+/// npm package manifest
+pub const PACKAGE_JSON: &str = "package.json";
+
+/// Python project manifest
+pub const PYPROJECT_TOML: &str = "pyproject.toml";
+
+/// `rust-version` from `[package]`, or `[workspace.package]` in a workspace root
+pub fn rust_version(cargo_toml: &str) -> Option<String> {
+    toml_string(cargo_toml, "package", "rust-version")
+        .or_else(|| toml_string(cargo_toml, "workspace.package", "rust-version"))
+}
+
+/// `name` from `[package]`
+pub fn package_name(cargo_toml: &str) -> Option<String> {
+    toml_string(cargo_toml, "package", "name")
+}
+
+/// `edition` from `[package]`, or `[workspace.package]` in a workspace root
+pub fn edition(cargo_toml: &str) -> Option<String> {
+    toml_string(cargo_toml, "package", "edition")
+        .or_else(|| toml_string(cargo_toml, "workspace.package", "edition"))
+}
+
+/// `engines.node` from `package.json`
+pub fn node_engines(package_json: &str) -> Option<String> {
+    let manifest: serde_json::Value = serde_json::from_str(package_json).ok()?;
+    let range = manifest.get("engines")?.get("node")?.as_str()?.trim();
+    (!range.is_empty()).then(|| range.to_string())
+}
+
+/// `requires-python` from `[project]`, or the Poetry `python` dependency
+pub fn python_requires(pyproject: &str) -> Option<String> {
+    toml_string(pyproject, "project", "requires-python")
+        .or_else(|| toml_string(pyproject, "tool.poetry.dependencies", "python"))
+}
+
+/// The string value of `key` in `[table]`
+///
+/// Only single-line `key = "value"` entries are read, which is how these
+/// keys are written in practice.
+fn toml_string(toml: &str, table: &str, key: &str) -> Option<String> {
+    let mut current = String::new();
+    for line in toml.lines().map(str::trim) {
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header.trim_start_matches('[').split(']').next()?;
+            current = header
+                .split('.')
+                .map(|part| part.trim().trim_matches('"'))
+                .collect::<Vec<_>>()
+                .join(".");
+            continue;
+        }
+        if current != table {
+            continue;
+        }
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        if name.trim().trim_matches('"') != key {
+            continue;
+        }
+        let value = value.trim();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let (text, _) = value[1..].split_once(quote)?;
+        return (!text.trim().is_empty()).then(|| text.trim().to_string());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("[package]\nname = \"demo\"\nrust-version = \"1.70\"\n", Some("1.70"))]
+    #[case("[package]\nrust-version = '1.74.1' # MSRV\n", Some("1.74.1"))]
+    #[case(
+        "[workspace]\nmembers = []\n\n[workspace.package]\nrust-version = \"1.75\"\n",
+        Some("1.75")
+    )]
+    #[case(
+        "[package]\nrust-version.workspace = true\n\n[workspace.package]\nrust-version = \"1.75\"\n",
+        Some("1.75")
+    )]
+    #[case("[dependencies]\nrust-version = \"1.0\"\n", None)]
+    #[case("[package]\nname = \"demo\"\n", None)]
+    fn test_rust_version(#[case] cargo_toml: &str, #[case] expected: Option<&str>) {
+        assert_eq!(rust_version(cargo_toml).as_deref(), expected);
+    }
+
+    #[test]
+    fn test_package_name_and_edition() {
+        let cargo_toml =
+            "[package]\nname = \"demo\"\nedition = \"2021\"\n\n[dependencies]\nname = \"x\"\n";
+        assert_eq!(package_name(cargo_toml).as_deref(), Some("demo"));
+        assert_eq!(edition(cargo_toml).as_deref(), Some("2021"));
+
+        let workspace =
+            "[workspace]\nmembers = [\"a\"]\n\n[workspace.package]\nedition = \"2024\"\n";
+        assert_eq!(package_name(workspace), None);
+        assert_eq!(edition(workspace).as_deref(), Some("2024"));
+    }
+
+    #[rstest]
+    #[case(r#"{"engines": {"node": ">=18 <22"}}"#, Some(">=18 <22"))]
+    #[case(r#"{"engines": {"npm": ">=9"}}"#, None)]
+    #[case(r#"{"engines": {"node": ""}}"#, None)]
+    #[case("not json", None)]
+    fn test_node_engines(#[case] package_json: &str, #[case] expected: Option<&str>) {
+        assert_eq!(node_engines(package_json).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "[project]\nname = \"demo\"\nrequires-python = \">=3.9\"\n",
+        Some(">=3.9")
+    )]
+    #[case(
+        "[tool.poetry.dependencies]\npython = \"^3.10\"\nrequests = \"^2.31\"\n",
+        Some("^3.10")
+    )]
+    #[case("[tool.ruff]\ntarget-version = \"py39\"\n", None)]
+    fn test_python_requires(#[case] pyproject: &str, #[case] expected: Option<&str>) {
+        assert_eq!(python_requires(pyproject).as_deref(), expected);
+    }
+}
@@ -0,0 +1,256 @@
+//! Uploading assets over HTTP
+
+use super::sigv4::{encode_key, sha256_hex, Signer};
+use super::{rewrite_asset_refs, PublishConfig, PublishProvider};
+use crate::error::{Error, Result};
+use crate::parser::ProcessedMarkdown;
+use crate::vfs::{self, AssetWriteSummary, Vfs};
+use std::path::Path;
+use std::time::Duration;
+
+/// Content-addressed assets never change, so caches may keep them forever
+const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Credentials for a storage provider
+#[derive(Clone)]
+pub enum Credentials {
+    /// Access key pair: AWS keys for S3, HMAC keys for GCS
+    Hmac {
+        access_key_id: String,
+        secret_access_key: String,
+        /// Session token for temporary AWS credentials
+        session_token: Option<String>,
+    },
+    /// Azure shared access signature with write permission on the container
+    Sas(String),
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Credentials::Hmac { access_key_id, .. } => f
+                .debug_struct("Hmac")
+                .field("access_key_id", access_key_id)
+                .finish_non_exhaustive(),
+            Credentials::Sas(_) => f.write_str("Sas(..)"),
+        }
+    }
+}
+
+impl Credentials {
+    /// Read credentials for `provider` from the environment
+    ///
+    /// | Provider | Variables |
+    /// |----------|-----------|
+    /// | S3 | `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`, optional `AWS_SESSION_TOKEN` |
+    /// | GCS | `GCS_HMAC_ACCESS_ID`, `GCS_HMAC_SECRET` |
+    /// | Azure | `AZURE_STORAGE_SAS_TOKEN` |
+    pub fn from_env(provider: PublishProvider) -> Result<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .map_err(|_| Error::PublishError(format!("environment variable {} not set", name)))
+        };
+        Ok(match provider {
+            PublishProvider::S3 => Credentials::Hmac {
+                access_key_id: var("AWS_ACCESS_KEY_ID")?,
+                secret_access_key: var("AWS_SECRET_ACCESS_KEY")?,
+                session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+            },
+            PublishProvider::Gcs => Credentials::Hmac {
+                access_key_id: var("GCS_HMAC_ACCESS_ID")?,
+                secret_access_key: var("GCS_HMAC_SECRET")?,
+                session_token: None,
+            },
+            PublishProvider::Azure => Credentials::Sas(var("AZURE_STORAGE_SAS_TOKEN")?),
+        })
+    }
+}
+
+/// Uploads assets to object storage
+///
+/// `Publisher` is a [`Vfs`]: `exists` checks for the object with a `HEAD`
+/// request and `write` uploads it, using the asset's file name as the key.
+pub struct Publisher {
+    config: PublishConfig,
+    credentials: Credentials,
+    agent: ureq::Agent,
+}
+
+impl Publisher {
+    /// Create a publisher, checking that the config is complete
+    pub fn new(config: PublishConfig, credentials: Credentials) -> Result<Self> {
+        if config.bucket.is_empty() {
+            return Err(Error::PublishError("no bucket configured".to_string()));
+        }
+        if config.provider == PublishProvider::Azure && config.account.is_none() {
+            return Err(Error::PublishError(
+                "Azure publishing needs an \"account\"".to_string(),
+            ));
+        }
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .build();
+        Ok(Self {
+            config,
+            credentials,
+            agent,
+        })
+    }
+
+    /// Create a publisher with credentials from the environment
+    pub fn from_env(config: PublishConfig) -> Result<Self> {
+        let credentials = Credentials::from_env(config.provider)?;
+        Self::new(config, credentials)
+    }
+
+    /// Storage configuration
+    pub fn config(&self) -> &PublishConfig {
+        &self.config
+    }
+
+    /// Upload the assets and point the markdown at their public URLs
+    pub fn publish(&mut self, processed: &mut ProcessedMarkdown) -> Result<AssetWriteSummary> {
+        let summary = vfs::write_assets(self, &processed.assets)?;
+        processed.markdown = rewrite_asset_refs(&processed.markdown, &processed.assets, |name| {
+            self.config.public_url(name)
+        });
+        Ok(summary)
+    }
+
+    fn object_key(&self, path: &Path) -> Result<String> {
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| Error::PublishError(format!("invalid asset path {}", path.display())))?;
+        Ok(self.config.object_key(file_name))
+    }
+
+    fn request(&self, method: &str, key: &str, body: &[u8]) -> ureq::Request {
+        let url = format!("{}/{}", self.config.storage_url(), encode_key(key));
+
+        match &self.credentials {
+            Credentials::Sas(token) => {
+                let url = format!("{}?{}", url, token.trim_start_matches('?'));
+                self.agent
+                    .request(method, &url)
+                    .set("x-ms-blob-type", "BlockBlob")
+            }
+            Credentials::Hmac {
+                access_key_id,
+                secret_access_key,
+                session_token,
+            } => {
+                let after_scheme = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+                let (host, path) = after_scheme.split_at(after_scheme.find('/').unwrap_or(0));
+                let payload_hash = sha256_hex(body);
+                let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+                let mut headers = vec![
+                    ("host", host),
+                    ("x-amz-content-sha256", payload_hash.as_str()),
+                    ("x-amz-date", amz_date.as_str()),
+                ];
+                if let Some(token) = session_token {
+                    headers.push(("x-amz-security-token", token.as_str()));
+                }
+                let signer = Signer {
+                    access_key_id,
+                    secret_access_key,
+                    region: self.config.region(),
+                    service: "s3",
+                };
+                let authorization =
+                    signer.authorization(method, path, &headers, &payload_hash, &amz_date);
+
+                let mut request = self
+                    .agent
+                    .request(method, &url)
+                    .set("authorization", &authorization);
+                for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+                    request = request.set(name, value);
+                }
+                request
+            }
+        }
+    }
+}
+
+impl Vfs for Publisher {
+    fn exists(&self, path: &Path) -> bool {
+        let Ok(key) = self.object_key(path) else {
+            return false;
+        };
+        self.request("HEAD", &key, b"").call().is_ok()
+    }
+
+    fn write(&mut self, path: &Path, bytes: &[u8]) -> Result<()> {
+        let key = self.object_key(path)?;
+        let response = self
+            .request("PUT", &key, bytes)
+            .set("content-type", "image/svg+xml")
+            .set("cache-control", CACHE_CONTROL)
+            .send_bytes(bytes);
+
+        match response {
+            Ok(_) => Ok(()),
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                Err(Error::PublishError(format!(
+                    "uploading {} returned HTTP {}: {}",
+                    key,
+                    code,
+                    body.trim()
+                )))
+            }
+            Err(e) => Err(Error::PublishError(format!("uploading {}: {}", key, e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hmac() -> Credentials {
+        Credentials::Hmac {
+            access_key_id: "AKID".to_string(),
+            secret_access_key: "secret".to_string(),
+            session_token: None,
+        }
+    }
+
+    #[test]
+    fn test_requires_bucket_and_azure_account() {
+        assert!(Publisher::new(PublishConfig::default(), hmac()).is_err());
+
+        let azure = PublishConfig {
+            provider: PublishProvider::Azure,
+            bucket: "assets".to_string(),
+            ..Default::default()
+        };
+        assert!(Publisher::new(azure, Credentials::Sas("sv=1".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_debug_hides_secrets() {
+        let debug = format!("{:?}", hmac());
+        assert!(debug.contains("AKID"));
+        assert!(!debug.contains("secret"));
+    }
+
+    #[test]
+    fn test_object_key_uses_file_name() {
+        let config = PublishConfig {
+            bucket: "b".to_string(),
+            prefix: Some("readme".to_string()),
+            ..Default::default()
+        };
+        let publisher = Publisher::new(config, hmac()).unwrap();
+        assert_eq!(
+            publisher
+                .object_key(Path::new("assets/mdfx/swatch_1.svg"))
+                .unwrap(),
+            "readme/swatch_1.svg"
+        );
+    }
+}
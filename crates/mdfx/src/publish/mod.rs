@@ -0,0 +1,246 @@
+//! Publishing generated assets to object storage
+//!
+//! Teams that don't want SVG files in git can upload them to S3, Google
+//! Cloud Storage, or Azure Blob Storage and point the markdown at the public
+//! URLs instead. The bucket is configured under `"publish"` in `.mdfx.json`
+//! ([`PublishConfig`]); credentials come from the environment.
+//!
+//! Asset names are content hashes, so an object that already exists is never
+//! uploaded again. Uploading needs the `publish` feature, which provides
+//! [`Publisher`]; rewriting references with [`rewrite_asset_refs`] is always
+//! available.
+//!
+//! ```
+//! use mdfx::publish::{PublishConfig, PublishProvider};
+//!
+//! let config = PublishConfig {
+//!     provider: PublishProvider::S3,
+//!     bucket: "docs-assets".to_string(),
+//!     region: Some("eu-west-1".to_string()),
+//!     prefix: Some("readme".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! assert_eq!(
+//!     config.public_url("swatch_ab12.svg"),
+//!     "https://docs-assets.s3.eu-west-1.amazonaws.com/readme/swatch_ab12.svg"
+//! );
+//! ```
+
+#[cfg(feature = "publish")]
+mod client;
+#[cfg(feature = "publish")]
+mod sigv4;
+
+#[cfg(feature = "publish")]
+pub use client::{Credentials, Publisher};
+
+use crate::renderer::RenderedAsset;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Object storage service
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PublishProvider {
+    /// Amazon S3 or an S3-compatible service (set `endpoint`)
+    #[default]
+    S3,
+    /// Google Cloud Storage, through its S3-compatible XML API
+    Gcs,
+    /// Azure Blob Storage
+    Azure,
+}
+
+/// Where to publish assets, from the `"publish"` section of `.mdfx.json`
+///
+/// ```json
+/// "publish": {
+///   "provider": "s3",
+///   "bucket": "docs-assets",
+///   "region": "eu-west-1",
+///   "prefix": "readme",
+///   "public_url": "https://cdn.example.com"
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PublishConfig {
+    /// Storage service
+    pub provider: PublishProvider,
+    /// Bucket (S3, GCS) or container (Azure)
+    pub bucket: String,
+    /// S3 region, defaults to `us-east-1`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Storage account name, required for Azure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// Custom S3-compatible endpoint (MinIO, R2), addressed path-style
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Key prefix for uploaded objects
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// Base URL used in markdown, e.g. a CDN in front of the bucket
+    ///
+    /// Defaults to the bucket's own URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_url: Option<String>,
+}
+
+impl PublishConfig {
+    /// S3 region, `auto` for GCS
+    pub fn region(&self) -> &str {
+        match self.provider {
+            PublishProvider::Gcs => "auto",
+            _ => self.region.as_deref().unwrap_or("us-east-1"),
+        }
+    }
+
+    /// Object key for an asset file name
+    pub fn object_key(&self, file_name: &str) -> String {
+        match self.prefix.as_deref().map(|p| p.trim_matches('/')) {
+            Some(prefix) if !prefix.is_empty() => format!("{}/{}", prefix, file_name),
+            _ => file_name.to_string(),
+        }
+    }
+
+    /// URL that objects are uploaded to, without a trailing slash
+    pub fn storage_url(&self) -> String {
+        if let Some(endpoint) = &self.endpoint {
+            return format!("{}/{}", endpoint.trim_end_matches('/'), self.bucket);
+        }
+        match self.provider {
+            PublishProvider::S3 => {
+                format!("https://{}.s3.{}.amazonaws.com", self.bucket, self.region())
+            }
+            PublishProvider::Gcs => format!("https://storage.googleapis.com/{}", self.bucket),
+            PublishProvider::Azure => format!(
+                "https://{}.blob.core.windows.net/{}",
+                self.account.as_deref().unwrap_or_default(),
+                self.bucket
+            ),
+        }
+    }
+
+    /// Public URL of an asset file name
+    pub fn public_url(&self, file_name: &str) -> String {
+        let base = match &self.public_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => self.storage_url(),
+        };
+        format!("{}/{}", base, self.object_key(file_name))
+    }
+}
+
+/// Replace local asset paths in `markdown` with URLs
+///
+/// `url_for` receives each file-based asset's file name. References are
+/// found through each asset's markdown reference, so paths written with an
+/// assets prefix and paths inside `<img>` tags are rewritten too.
+pub fn rewrite_asset_refs(
+    markdown: &str,
+    assets: &[RenderedAsset],
+    url_for: impl Fn(&str) -> String,
+) -> String {
+    let mut out = markdown.to_string();
+    for asset in assets {
+        let RenderedAsset::File {
+            relative_path,
+            markdown_ref,
+            ..
+        } = asset
+        else {
+            continue;
+        };
+        let Some(file_name) = Path::new(relative_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+        else {
+            continue;
+        };
+        let Some(end) = markdown_ref.find(file_name).map(|i| i + file_name.len()) else {
+            continue;
+        };
+        let start = markdown_ref[..end]
+            .rfind(['(', '"', '\'', ' '])
+            .map_or(0, |i| i + 1);
+        let local = &markdown_ref[start..end];
+        out = out.replace(local, &url_for(file_name));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Primitive;
+    use rstest::rstest;
+
+    fn config(provider: PublishProvider) -> PublishConfig {
+        PublishConfig {
+            provider,
+            bucket: "docs".to_string(),
+            account: Some("acct".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case(PublishProvider::S3, "https://docs.s3.us-east-1.amazonaws.com/a.svg")]
+    #[case(PublishProvider::Gcs, "https://storage.googleapis.com/docs/a.svg")]
+    #[case(
+        PublishProvider::Azure,
+        "https://acct.blob.core.windows.net/docs/a.svg"
+    )]
+    fn test_default_public_url(#[case] provider: PublishProvider, #[case] expected: &str) {
+        assert_eq!(config(provider).public_url("a.svg"), expected);
+    }
+
+    #[test]
+    fn test_endpoint_prefix_and_cdn() {
+        let mut cfg = PublishConfig {
+            endpoint: Some("http://localhost:9000/".to_string()),
+            prefix: Some("/mdfx/".to_string()),
+            ..config(PublishProvider::S3)
+        };
+        assert_eq!(cfg.storage_url(), "http://localhost:9000/docs");
+        assert_eq!(cfg.object_key("a.svg"), "mdfx/a.svg");
+
+        cfg.public_url = Some("https://cdn.example.com/".to_string());
+        assert_eq!(
+            cfg.public_url("a.svg"),
+            "https://cdn.example.com/mdfx/a.svg"
+        );
+    }
+
+    #[test]
+    fn test_config_from_json() {
+        let cfg: crate::MdfxConfig =
+            serde_json::from_str(r#"{"publish": {"provider": "gcs", "bucket": "b"}}"#).unwrap();
+        let publish = cfg.publish.unwrap();
+        assert_eq!(publish.provider, PublishProvider::Gcs);
+        assert_eq!(publish.region(), "auto");
+    }
+
+    #[test]
+    fn test_rewrite_asset_refs() {
+        let asset = RenderedAsset::File {
+            relative_path: "docs/assets/swatch_1.svg".to_string(),
+            bytes: Vec::new(),
+            markdown_ref: "![](assets/swatch_1.svg)".to_string(),
+            primitive: Box::new(Primitive::simple_swatch("FF0000", "flat")),
+        };
+        let markdown = "![](assets/swatch_1.svg) <img src=\"assets/swatch_1.svg\">";
+
+        let out = rewrite_asset_refs(markdown, &[asset], |name| {
+            format!("https://cdn.example.com/{}", name)
+        });
+
+        assert_eq!(
+            out,
+            "![](https://cdn.example.com/swatch_1.svg) <img src=\"https://cdn.example.com/swatch_1.svg\">"
+        );
+    }
+}
@@ -0,0 +1,413 @@
+//! Paragraph reflow for wrapped markdown
+//!
+//! [`reflow`] rewraps paragraphs, list items and block quotes to a column
+//! width, measured with [`display_width`]. Headings, tables, code, HTML
+//! blocks and front matter are left as written. Lines only break between
+//! words, never inside a template, code span, HTML tag or link target, and
+//! hard line breaks are kept.
+//!
+//! ```
+//! use mdfx::reflow::reflow;
+//!
+//! let markdown = "A short paragraph\nthat was wrapped early.\n\n| a | b |\n|---|---|\n";
+//! assert_eq!(
+//!     reflow(markdown, 30),
+//!     "A short paragraph that was\nwrapped early.\n\n| a | b |\n|---|---|\n"
+//! );
+//! ```
+
+use crate::width::display_width;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref FENCE: Regex = Regex::new(r"^ {0,3}(`{3,}|~{3,})").unwrap();
+    static ref ATX_HEADING: Regex = Regex::new(r"^ {0,3}#{1,6}(?:[ \t]|$)").unwrap();
+    static ref SETEXT_UNDERLINE: Regex = Regex::new(r"^ {0,3}(?:=+|-+)[ \t]*$").unwrap();
+    static ref THEMATIC_BREAK: Regex =
+        Regex::new(r"^ {0,3}(?:(?:-[ \t]*){3,}|(?:\*[ \t]*){3,}|(?:_[ \t]*){3,})$").unwrap();
+    static ref TABLE_DELIMITER: Regex =
+        Regex::new(r"^ {0,3}\|?[ \t]*:?-+:?[ \t]*(?:\|[ \t]*:?-+:?[ \t]*)*\|?[ \t]*$").unwrap();
+    static ref REFERENCE_DEF: Regex = Regex::new(r"^ {0,3}\[[^\]]+\]:").unwrap();
+    static ref BLOCKQUOTE: Regex = Regex::new(r"^ {0,3}> ?").unwrap();
+    /// List marker with its indentation and spacing
+    static ref LIST_ITEM: Regex = Regex::new(r"^ {0,3}(?:[-+*]|\d{1,9}[.)])(?: {1,4}|$)").unwrap();
+    /// Words that would start a block if they began a line
+    static ref BLOCK_WORD: Regex =
+        Regex::new(r"^(?:#{1,6}|[-+*]|=+|-+|\d{1,9}[.)]|>.*|\|.*|`{3,}.*|~{3,}.*)$").unwrap();
+}
+
+/// Rewrap the paragraphs of `markdown` to `width` columns
+///
+/// Words wider than `width` get a line of their own. A width of 0 leaves
+/// the markdown unchanged.
+pub fn reflow(markdown: &str, width: usize) -> String {
+    if width == 0 {
+        return markdown.to_string();
+    }
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut out = reflow_lines(&lines, width, true).join("\n");
+    if markdown.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// Reflow a run of lines; `document` is false inside list items and quotes
+fn reflow_lines(lines: &[&str], width: usize, document: bool) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut i = 0;
+
+    // Front matter is left as written
+    if document && lines.first() == Some(&"---") {
+        if let Some(end) = lines[1..].iter().position(|l| *l == "---" || *l == "...") {
+            out.extend(lines[..end + 2].iter().map(|l| l.to_string()));
+            i = end + 2;
+        }
+    }
+
+    while i < lines.len() {
+        let line = lines[i];
+
+        if line.trim().is_empty() {
+            out.push(String::new());
+            i += 1;
+        } else if let Some(fence) = FENCE.captures(line) {
+            let fence = &fence[1];
+            let marker = &fence[..1];
+            let end = lines[i + 1..]
+                .iter()
+                .position(|l| {
+                    let l = l.trim_start();
+                    l.starts_with(fence) && l.trim_start_matches(marker).trim().is_empty()
+                })
+                .map_or(lines.len(), |p| i + p + 2);
+            out.extend(lines[i..end].iter().map(|l| l.to_string()));
+            i = end;
+        } else if is_verbatim_line(line) || is_indented_code(line) {
+            out.push(line.to_string());
+            i += 1;
+        } else if BLOCKQUOTE.is_match(line) {
+            let end = lines[i..]
+                .iter()
+                .position(|l| !BLOCKQUOTE.is_match(l))
+                .map_or(lines.len(), |p| i + p);
+            let inner: Vec<&str> = lines[i..end]
+                .iter()
+                .map(|l| &l[BLOCKQUOTE.find(l).map_or(0, |m| m.end())..])
+                .collect();
+            for quoted in reflow_lines(&inner, width.saturating_sub(2).max(1), false) {
+                out.push(if quoted.is_empty() {
+                    ">".to_string()
+                } else {
+                    format!("> {}", quoted)
+                });
+            }
+            i = end;
+        } else if let Some(item) = LIST_ITEM.find(line) {
+            let marker = item.as_str().to_string();
+            let indent = display_width(&marker);
+            let (inner, end) = list_item(lines, i, marker.len(), indent);
+            let pad = " ".repeat(indent);
+            let item_width = width.saturating_sub(indent).max(1);
+            for (n, text) in reflow_lines(&inner, item_width, false)
+                .into_iter()
+                .enumerate()
+            {
+                out.push(match n {
+                    0 => format!("{}{}", marker, text).trim_end().to_string(),
+                    _ if text.is_empty() => text,
+                    _ => format!("{}{}", pad, text),
+                });
+            }
+            i = end;
+        } else if is_table(lines, i) || is_html(line) {
+            // Tables and HTML blocks run to the next blank line
+            let end = lines[i..]
+                .iter()
+                .position(|l| l.trim().is_empty())
+                .map_or(lines.len(), |p| i + p);
+            out.extend(lines[i..end].iter().map(|l| l.to_string()));
+            i = end;
+        } else {
+            let end = paragraph_end(lines, i);
+            if end < lines.len() && SETEXT_UNDERLINE.is_match(lines[end]) {
+                // A setext heading: its text and underline stay as written
+                out.extend(lines[i..=end].iter().map(|l| l.to_string()));
+                i = end + 1;
+            } else {
+                out.extend(fill_paragraph(&lines[i..end], width));
+                i = end;
+            }
+        }
+    }
+
+    out
+}
+
+/// Single lines that are never rewrapped
+fn is_verbatim_line(line: &str) -> bool {
+    ATX_HEADING.is_match(line) || THEMATIC_BREAK.is_match(line) || REFERENCE_DEF.is_match(line)
+}
+
+/// Whether `line` is indented code
+fn is_indented_code(line: &str) -> bool {
+    line.starts_with("    ") || line.starts_with('\t')
+}
+
+/// Whether `line` starts an HTML block
+fn is_html(line: &str) -> bool {
+    let rest = line.trim_start().strip_prefix('<').unwrap_or_default();
+    rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!')
+}
+
+/// Whether a table starts at line `i`: a row followed by a delimiter row,
+/// or a line starting with `|`
+fn is_table(lines: &[&str], i: usize) -> bool {
+    lines[i].trim_start().starts_with('|')
+        || (lines[i].contains('|')
+            && lines
+                .get(i + 1)
+                .is_some_and(|next| next.contains('|') && TABLE_DELIMITER.is_match(next)))
+}
+
+/// Whether `line` starts a block that interrupts a paragraph
+fn interrupts_paragraph(line: &str) -> bool {
+    line.trim().is_empty()
+        || FENCE.is_match(line)
+        || BLOCKQUOTE.is_match(line)
+        || LIST_ITEM.is_match(line)
+        || is_verbatim_line(line)
+        || is_html(line)
+}
+
+/// Index of the line after the paragraph starting at line `start`
+fn paragraph_end(lines: &[&str], start: usize) -> usize {
+    let mut end = start + 1;
+    while end < lines.len()
+        && !interrupts_paragraph(lines[end])
+        && !SETEXT_UNDERLINE.is_match(lines[end])
+        && !is_table(lines, end)
+    {
+        end += 1;
+    }
+    end
+}
+
+/// Content lines of the list item starting at line `start`, without its
+/// marker or indentation, and the index of the line after it
+fn list_item<'a>(
+    lines: &[&'a str],
+    start: usize,
+    marker_len: usize,
+    indent: usize,
+) -> (Vec<&'a str>, usize) {
+    let pad = " ".repeat(indent);
+    let mut inner = vec![&lines[start][marker_len..]];
+    let mut i = start + 1;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.trim().is_empty() {
+            // A blank line continues the item only if indented content follows
+            let next = lines[i..].iter().position(|l| !l.trim().is_empty());
+            match next.map(|p| lines[i + p]) {
+                Some(next) if next.starts_with(&pad) => inner.push(""),
+                _ => break,
+            }
+        } else if let Some(content) = line.strip_prefix(&pad) {
+            inner.push(content);
+        } else if inner.last().is_some_and(|l| !l.trim().is_empty()) && !interrupts_paragraph(line)
+        {
+            // Lazy continuation of the item's paragraph
+            inner.push(line.trim_start());
+        } else {
+            break;
+        }
+        i += 1;
+    }
+    (inner, i)
+}
+
+/// Rewrap paragraph lines, keeping hard line breaks
+fn fill_paragraph(lines: &[&str], width: usize) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut words = Vec::new();
+    for (n, line) in lines.iter().enumerate() {
+        let text = line.trim_start();
+        words.extend(split_words(text.trim_end()));
+        // Two trailing spaces or a backslash end a line in the output too
+        if n + 1 < lines.len() && (text.ends_with("  ") || text.ends_with('\\')) {
+            let mut filled = fill(&words, width);
+            if let Some(last) = filled.last_mut().filter(|_| text.ends_with("  ")) {
+                last.push_str("  ");
+            }
+            out.extend(filled);
+            words.clear();
+        }
+    }
+    out.extend(fill(&words, width));
+    out
+}
+
+/// Greedily fill lines of at most `width` columns with `words`
+///
+/// A word that would start a block (`-`, `1.`, `#`, `>`) stays on the line
+/// before, even past `width`, so wrapping can't change the document.
+fn fill(words: &[&str], width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in words {
+        if current.is_empty() {
+            current.push_str(word);
+        } else if display_width(&current) + 1 + display_width(word) <= width
+            || BLOCK_WORD.is_match(word)
+        {
+            current.push(' ');
+            current.push_str(word);
+        } else {
+            lines.push(std::mem::take(&mut current));
+            current.push_str(word);
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Split text at whitespace outside templates, code spans, HTML tags, link
+/// targets and task checkboxes
+fn split_words(text: &str) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &text[i..];
+        let atom_end = if rest.starts_with("{{") {
+            rest.find("}}").map(|p| i + p + 2)
+        } else if rest.starts_with('`') {
+            let run = rest.len() - rest.trim_start_matches('`').len();
+            let fence = &rest[..run];
+            rest[run..].find(fence).map(|p| i + run + p + run)
+        } else if ["[ ]", "[x]", "[X]"].iter().any(|b| rest.starts_with(b)) {
+            Some(i + 3)
+        } else if rest.starts_with("](") {
+            rest.find(')').map(|p| i + p + 1)
+        } else if rest.starts_with('<') && is_html(rest) {
+            rest.find('>').map(|p| i + p + 1)
+        } else {
+            None
+        };
+
+        if let Some(end) = atom_end {
+            start.get_or_insert(i);
+            i = end;
+        } else if bytes[i].is_ascii_whitespace() {
+            if let Some(s) = start.take() {
+                words.push(&text[s..i]);
+            }
+            i += 1;
+        } else {
+            start.get_or_insert(i);
+            i += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    if let Some(s) = start {
+        words.push(&text[s..]);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("one two three four five", 9, "one two\nthree\nfour five")]
+    #[case("one\ntwo\nthree", 80, "one two three")]
+    #[case("  indented\n   lazy", 80, "indented lazy")]
+    #[case("a veryveryverylongword b", 5, "a\nveryveryverylongword\nb")]
+    #[case("日本語 日本語 日本語", 13, "日本語 日本語\n日本語")]
+    #[case("one two", 0, "one two")]
+    fn test_fill_paragraphs(#[case] input: &str, #[case] width: usize, #[case] expected: &str) {
+        assert_eq!(reflow(input, width), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "see {{ui:tech:rust:label=Rust lang/}} now",
+        "see\n{{ui:tech:rust:label=Rust lang/}}\nnow"
+    )]
+    #[case("run `cargo build --release` now", "run\n`cargo build --release`\nnow")]
+    #[case(
+        "a <img src=\"x.svg\" alt=\"a b\"> b",
+        "a\n<img src=\"x.svg\" alt=\"a b\">\nb"
+    )]
+    #[case(
+        "[the docs](https://example.com/a b) x",
+        "[the\ndocs](https://example.com/a b)\nx"
+    )]
+    fn test_atoms_not_broken(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(reflow(input, 8), expected);
+    }
+
+    #[rstest]
+    #[case("# A very long heading that stays\ntext")]
+    #[case("```\nlong code line that stays as it is\n```")]
+    #[case("| a long | table row |\n|---|---|\n| x | y |")]
+    #[case("a | b long\n--- | ---\nc | d")]
+    #[case("<div align=\"center\">\n  <img src=\"a.svg\"> <img src=\"b.svg\">\n</div>")]
+    #[case("---\ntitle: a long front matter value\n---")]
+    #[case("Setext heading that is long\n===========")]
+    #[case("    indented code line that is long")]
+    #[case("[ref]: https://example.com/a/long/path")]
+    fn test_blocks_left_as_written(#[case] input: &str) {
+        assert_eq!(reflow(input, 10), input);
+    }
+
+    #[rstest]
+    #[case(
+        "- first item with several words\n- second",
+        "- first item\n  with several\n  words\n- second"
+    )]
+    #[case(
+        "1. numbered item\n   continued here",
+        "1. numbered\n   item\n   continued\n   here"
+    )]
+    #[case("- [ ] task with words", "- [ ] task\n  with words")]
+    #[case(
+        "- outer item\n  - inner item words",
+        "- outer item\n  - inner item\n    words"
+    )]
+    #[case(
+        "> quoted text that\n> wraps around",
+        "> quoted text\n> that wraps\n> around"
+    )]
+    fn test_lists_and_quotes(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(reflow(input, 14), expected);
+    }
+
+    #[test]
+    fn test_hard_breaks_kept() {
+        assert_eq!(
+            reflow("one two three  \nfour five\\\nsix", 10),
+            "one two\nthree  \nfour five\\\nsix"
+        );
+    }
+
+    #[test]
+    fn test_block_words_not_moved_to_line_start() {
+        assert_eq!(
+            reflow("costs 5 - 10 per unit", 7),
+            "costs 5 -\n10 per\nunit"
+        );
+        assert_eq!(reflow("see issue # 12", 9), "see issue #\n12");
+    }
+
+    #[test]
+    fn test_trailing_newline_kept() {
+        assert_eq!(reflow("a\nb\n\nc\n", 80), "a b\n\nc\n");
+    }
+}
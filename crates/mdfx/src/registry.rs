@@ -11,9 +11,11 @@
 //! - Unified resolution pipeline for all renderables
 //! - Single source of truth for palette colors
 
+use crate::components::param_schema::normalize_key;
 use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
 /// Variation Selector 15 - forces text presentation for Unicode characters
 /// that have both text and emoji variants (e.g., ★ renders as glyph, not emoji)
@@ -86,6 +88,12 @@ pub struct OptionalParam {
     pub default: String,
     #[serde(default)]
     pub description: Option<String>,
+    /// Allowed values of an `enum` parameter
+    #[serde(default)]
+    pub values: Option<Vec<String>>,
+    /// Other names accepted for this parameter
+    #[serde(default)]
+    pub aliases: Vec<String>,
 }
 
 /// A component definition (native or expand type)
@@ -107,6 +115,19 @@ pub struct Component {
     pub post_process: Option<String>,
 }
 
+impl Component {
+    /// Name a `key=` parameter is read under: its canonical spelling, with
+    /// an alias resolved to the parameter it stands for
+    pub fn param_name(&self, key: &str) -> String {
+        let key = normalize_key(key);
+        self.optional_params
+            .iter()
+            .flatten()
+            .find(|(_, param)| param.aliases.iter().any(|a| normalize_key(a) == key))
+            .map_or(key, |(name, _)| name.clone())
+    }
+}
+
 /// Suffix generation mode for frames
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -274,6 +295,9 @@ pub struct RegistryData {
     pub metadata: RegistryMetadata,
 }
 
+/// Lazily parsed embedded registry, see [`Registry::shared`]
+static SHARED: OnceLock<Arc<Registry>> = OnceLock::new();
+
 /// The Registry provides access to all mdfx renderables and configuration
 pub struct Registry {
     data: RegistryData,
@@ -290,6 +314,30 @@ impl Registry {
         Self::from_json(json_data)
     }
 
+    /// Get the process-wide registry loaded from the embedded JSON data
+    ///
+    /// The JSON is parsed on the first call only; later calls return the same
+    /// instance. Pass it to the `with_registry` constructors of
+    /// [`ComponentsRenderer`](crate::ComponentsRenderer),
+    /// [`ShieldsRenderer`](crate::ShieldsRenderer) and
+    /// [`TemplateParser`](crate::TemplateParser) to avoid re-parsing.
+    ///
+    /// ```
+    /// use mdfx::Registry;
+    /// use std::sync::Arc;
+    ///
+    /// let a = Registry::shared().unwrap();
+    /// let b = Registry::shared().unwrap();
+    /// assert!(Arc::ptr_eq(&a, &b));
+    /// ```
+    pub fn shared() -> Result<Arc<Self>> {
+        if let Some(registry) = SHARED.get() {
+            return Ok(registry.clone());
+        }
+        let registry = Arc::new(Self::new()?);
+        Ok(SHARED.get_or_init(|| registry).clone())
+    }
+
     /// Load the registry from a JSON string
     pub fn from_json(json: &str) -> Result<Self> {
         let data: RegistryData = serde_json::from_str(json)?;
@@ -398,6 +446,14 @@ impl Registry {
         None
     }
 
+    /// Resolve a frame name or alias to its canonical id
+    pub fn frame_id<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        if self.data.renderables.frames.contains_key(name) {
+            return Some(name);
+        }
+        self.frame_aliases.get(name).map(|id| id.as_str())
+    }
+
     /// Get all frames
     pub fn frames(&self) -> &HashMap<String, Frame> {
         &self.data.renderables.frames
@@ -450,6 +506,14 @@ impl Registry {
         None
     }
 
+    /// Resolve a style name or alias to its canonical id
+    pub fn style_id<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        if self.data.renderables.styles.contains_key(name) {
+            return Some(name);
+        }
+        self.style_aliases.get(name).map(|id| id.as_str())
+    }
+
     /// Get all styles
     pub fn styles(&self) -> &HashMap<String, Style> {
         &self.data.renderables.styles
@@ -537,6 +601,14 @@ mod tests {
         assert_eq!(registry.version(), "2.0.0");
     }
 
+    #[test]
+    fn test_shared_registry_is_reused() {
+        let first = Registry::shared().unwrap();
+        let second = Registry::shared().unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.version(), "2.0.0");
+    }
+
     #[test]
     fn test_schema_version() {
         let registry = Registry::new().unwrap();
@@ -706,6 +778,22 @@ mod tests {
         assert!(!components.is_empty());
     }
 
+    #[rstest]
+    #[case("progress", "color", "track")]
+    #[case("progress", "Color", "track")]
+    #[case("live", "age-color", "age_color")]
+    #[case("tech", "ICON", "icon")]
+    #[case("tech", "bgColor", "bg_color")]
+    fn test_component_param_name(
+        #[case] component: &str,
+        #[case] key: &str,
+        #[case] expected: &str,
+    ) {
+        let registry = Registry::new().unwrap();
+        let component = registry.component(component).unwrap();
+        assert_eq!(component.param_name(key), expected);
+    }
+
     // ========================================================================
     // Shield Style Tests
     // ========================================================================
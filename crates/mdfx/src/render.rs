@@ -0,0 +1,145 @@
+//! Rendering a single component without template syntax
+//!
+//! [`render_component`] expands one component and renders its primitive,
+//! for applications that need a badge but not a markdown document:
+//!
+//! ```
+//! use mdfx::{render_component, RenderOptions};
+//!
+//! let asset = render_component("tech", &["rust"], None).unwrap();
+//! assert!(asset.file_bytes().unwrap().starts_with(b"<svg"));
+//!
+//! let options = RenderOptions::new().param("style", "flat-square");
+//! let asset = render_component("tech", &["rust"], Some(options)).unwrap();
+//! assert!(asset.file_path().unwrap().starts_with("assets/mdfx/"));
+//! ```
+
+use crate::components::{ComponentOutput, ComponentsRenderer};
+use crate::error::{Error, Result};
+use crate::registry::Registry;
+use crate::renderer::svg::SvgBackend;
+use crate::renderer::{RenderedAsset, Renderer};
+use std::collections::HashMap;
+
+/// Default directory for SVG paths when no backend is given
+const DEFAULT_ASSETS_DIR: &str = "assets/mdfx";
+
+/// Options for [`render_component`]
+#[derive(Default)]
+pub struct RenderOptions {
+    params: Vec<(String, String)>,
+    palette: HashMap<String, String>,
+    backend: Option<Box<dyn Renderer>>,
+}
+
+impl RenderOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a `key=value` parameter, as written after a component's arguments
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.push((key.into(), value.into()));
+        self
+    }
+
+    /// Add colors to the palette, overriding built-in names
+    pub fn palette(mut self, colors: HashMap<String, String>) -> Self {
+        self.palette.extend(colors);
+        self
+    }
+
+    /// Render with `backend` instead of SVG files under `assets/mdfx`
+    pub fn backend(mut self, backend: Box<dyn Renderer>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+}
+
+/// Render one component, like `{{ui:name:args:key=value/}}` but without
+/// parsing markdown
+///
+/// Only components that render an image can be rendered this way;
+/// components that expand to other template markup (frames, rows) return
+/// an error, as they need [`TemplateParser`](crate::TemplateParser).
+/// Nothing is written to disk: file-based assets carry their bytes.
+pub fn render_component(
+    name: &str,
+    args: &[&str],
+    options: Option<RenderOptions>,
+) -> Result<RenderedAsset> {
+    let options = options.unwrap_or_default();
+
+    let mut components = ComponentsRenderer::with_registry(&*Registry::shared()?);
+    if !options.palette.is_empty() {
+        components.extend_palette(options.palette);
+    }
+
+    let args: Vec<String> = args
+        .iter()
+        .map(|arg| arg.to_string())
+        .chain(
+            options
+                .params
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value)),
+        )
+        .collect();
+
+    let primitive = match components.expand(name, &args, None)? {
+        ComponentOutput::Primitive(primitive) => primitive,
+        ComponentOutput::Template(_) | ComponentOutput::TemplateDelayed { .. } => {
+            return Err(Error::ParseError(format!(
+                "Component '{}' expands to template markup, not an image; render it with TemplateParser",
+                name
+            )))
+        }
+    };
+
+    match options.backend {
+        Some(backend) => backend.render(&primitive),
+        None => SvgBackend::new(DEFAULT_ASSETS_DIR).render(&primitive),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::shields::ShieldsBackend;
+    use crate::TemplateParser;
+
+    #[test]
+    fn test_render_matches_template() {
+        let asset = render_component("tech", &["rust"], None).unwrap();
+
+        let parser =
+            TemplateParser::with_backend(Box::new(SvgBackend::new("assets/mdfx"))).unwrap();
+        let processed = parser.process_with_assets("{{ui:tech:rust/}}").unwrap();
+        assert_eq!(Some(&asset), processed.assets.first());
+    }
+
+    #[test]
+    fn test_render_params_and_palette() {
+        let options = RenderOptions::new()
+            .palette(HashMap::from([("brand".to_string(), "FF5500".to_string())]))
+            .param("width", "40");
+        let asset = render_component("swatch", &["brand"], Some(options)).unwrap();
+        let svg = String::from_utf8(asset.file_bytes().unwrap().to_vec()).unwrap();
+        assert!(svg.contains("FF5500"));
+        assert!(svg.contains("width=\"40\""));
+    }
+
+    #[test]
+    fn test_render_with_backend() {
+        let options = RenderOptions::new().backend(Box::new(ShieldsBackend::new().unwrap()));
+        let asset = render_component("tech", &["rust"], Some(options)).unwrap();
+        assert!(asset.to_markdown().contains("img.shields.io"));
+    }
+
+    #[test]
+    fn test_render_errors() {
+        assert!(render_component("nosuch", &[], None).is_err());
+        let err = render_component("row", &[], None).unwrap_err();
+        assert!(err.to_string().contains("template markup"));
+    }
+}
@@ -0,0 +1,245 @@
+//! Display size overrides for rendered images
+//!
+//! `display_width=`/`display_height=` scale how large an image is shown
+//! without touching the SVG itself, so one asset file serves every size.
+//! The rendered reference is rewritten after the backend has produced it:
+//! markdown images become `<img width height>` (or keep their `=WxH`
+//! suffix), and raw inline SVGs get a new root size with their viewBox kept.
+//! Targets that don't render HTML keep plain markdown images unsized.
+
+use super::svg::svg_dimensions;
+use badgefx::escape::escape_attr;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+lazy_static! {
+    /// `![alt](src)`, optionally with a `=WxH` size suffix
+    static ref MARKDOWN_IMAGE: Regex =
+        Regex::new(r"!\[([^\]]*)\]\((\S+?)( =\d*x\d*)?\)").expect("valid regex");
+    /// `<img ...>` with its `src`
+    static ref HTML_IMAGE: Regex =
+        Regex::new(r#"<img\s[^>]*?src="([^"]*)"[^>]*>"#).expect("valid regex");
+    static ref HTML_ALT: Regex = Regex::new(r#"\salt="([^"]*)""#).expect("valid regex");
+    /// Root `<svg ...>` tag of an inline SVG
+    static ref SVG_ROOT: Regex = Regex::new(r"^\s*<svg\b[^>]*>").expect("valid regex");
+    static ref SVG_SIZE_ATTR: Regex =
+        Regex::new(r#"\s(?:width|height)="[^"]*""#).expect("valid regex");
+}
+
+const SVG_DATA_URI: &str = "data:image/svg+xml;base64,";
+
+/// Size an image is displayed at, independent of its drawing
+///
+/// Giving one side scales the other with the image's aspect ratio; giving
+/// both fits the image inside that box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DisplaySize {
+    /// `display_width=`, in pixels
+    pub width: Option<u32>,
+    /// `display_height=`, in pixels
+    pub height: Option<u32>,
+}
+
+impl DisplaySize {
+    /// Whether neither side is set
+    pub fn is_empty(&self) -> bool {
+        self.width.is_none() && self.height.is_none()
+    }
+
+    /// Width and height to state for an image whose size is `intrinsic`
+    ///
+    /// When the size isn't known (shields.io badges), only one side is
+    /// stated so the renderer keeps the ratio: the height if both are set.
+    pub fn fit(&self, intrinsic: Option<(u32, u32)>) -> (Option<u32>, Option<u32>) {
+        let Some((iw, ih)) = intrinsic.filter(|&(w, h)| w > 0 && h > 0) else {
+            return match (self.width, self.height) {
+                (Some(_), Some(h)) => (None, Some(h)),
+                size => size,
+            };
+        };
+        let scale = |side: u32, from: u32, to: u32| {
+            ((side as f64 * to as f64 / from as f64).round() as u32).max(1)
+        };
+        match (self.width, self.height) {
+            (Some(w), Some(h)) if w as u64 * ih as u64 <= h as u64 * iw as u64 => {
+                (Some(w), Some(scale(ih, iw, w)))
+            }
+            (Some(_), Some(h)) | (None, Some(h)) => (Some(scale(iw, ih, h)), Some(h)),
+            (Some(w), None) => (Some(w), Some(scale(ih, iw, w))),
+            (None, None) => (None, None),
+        }
+    }
+
+    /// Rewrite the image reference in `markdown` to this display size
+    ///
+    /// `file` is the SVG a file reference points at; inline SVGs and SVG
+    /// data URIs are measured from the markdown itself. Text without an
+    /// image is left unchanged, and so is a plain `![alt](src)` when `html`
+    /// is false, since markdown has no other way to size it.
+    pub fn apply(&self, markdown: &mut String, file: Option<&[u8]>, html: bool) {
+        if self.is_empty() {
+            return;
+        }
+        if let Some(root) = SVG_ROOT.find(markdown) {
+            let Some((iw, ih)) = svg_dimensions(markdown) else {
+                return;
+            };
+            let (w, h) = self.fit(Some((iw, ih)));
+            let mut tag = SVG_SIZE_ATTR.replace_all(root.as_str(), "").into_owned();
+            let mut attrs = String::new();
+            if let (Some(w), Some(h)) = (w, h) {
+                attrs.push_str(&format!(r#" width="{}" height="{}""#, w, h));
+            }
+            if !tag.contains("viewBox=") {
+                attrs.push_str(&format!(r#" viewBox="0 0 {} {}""#, iw, ih));
+            }
+            tag.insert_str(tag.find("<svg").unwrap_or(0) + 4, &attrs);
+            markdown.replace_range(root.range(), &tag);
+            return;
+        }
+
+        let intrinsic = file
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+            .or_else(|| embedded_svg(markdown))
+            .and_then(|svg| svg_dimensions(&svg));
+        let (w, h) = self.fit(intrinsic);
+        // Takes attribute-escaped values
+        let img = |src: &str, alt: &str| {
+            let mut img = format!(r#"<img src="{}""#, src);
+            if let Some(w) = w {
+                img.push_str(&format!(r#" width="{}""#, w));
+            }
+            if let Some(h) = h {
+                img.push_str(&format!(r#" height="{}""#, h));
+            }
+            format!(r#"{} alt="{}">"#, img, alt)
+        };
+
+        let resized = if let Some(caps) = HTML_IMAGE.captures(markdown) {
+            let alt = HTML_ALT
+                .captures(&caps[0])
+                .map_or(String::new(), |alt| alt[1].to_string());
+            let tag = img(&caps[1], &alt);
+            HTML_IMAGE.replace(markdown, tag.as_str()).into_owned()
+        } else {
+            MARKDOWN_IMAGE
+                .replace(markdown, |caps: &Captures| match caps.get(3) {
+                    Some(_) => format!(
+                        "![{}]({} ={}x{})",
+                        &caps[1],
+                        &caps[2],
+                        w.map_or(String::new(), |w| w.to_string()),
+                        h.map_or(String::new(), |h| h.to_string())
+                    ),
+                    None if html => img(&escape_attr(&caps[2]), &escape_attr(&caps[1])),
+                    None => caps[0].to_string(),
+                })
+                .into_owned()
+        };
+        *markdown = resized;
+    }
+}
+
+/// SVG source of the first SVG data URI in `markdown`
+fn embedded_svg(markdown: &str) -> Option<String> {
+    let start = markdown.find(SVG_DATA_URI)? + SVG_DATA_URI.len();
+    let encoded = &markdown[start..];
+    let end = encoded.find([')', '"', ' ']).unwrap_or(encoded.len());
+    let bytes = STANDARD.decode(&encoded[..end]).ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const BADGE: &[u8] =
+        br#"<svg xmlns="http://www.w3.org/2000/svg" width="80" height="20"></svg>"#;
+
+    fn size(width: Option<u32>, height: Option<u32>) -> DisplaySize {
+        DisplaySize { width, height }
+    }
+
+    #[rstest]
+    #[case(size(Some(160), None), Some((80, 20)), (Some(160), Some(40)))]
+    #[case(size(None, Some(30)), Some((80, 20)), (Some(120), Some(30)))]
+    #[case(size(Some(100), Some(100)), Some((80, 20)), (Some(100), Some(25)))]
+    #[case(size(Some(400), Some(40)), Some((80, 20)), (Some(160), Some(40)))]
+    #[case(size(Some(100), Some(40)), None, (None, Some(40)))]
+    #[case(size(Some(100), None), None, (Some(100), None))]
+    fn test_fit_keeps_aspect_ratio(
+        #[case] display: DisplaySize,
+        #[case] intrinsic: Option<(u32, u32)>,
+        #[case] expected: (Option<u32>, Option<u32>),
+    ) {
+        assert_eq!(display.fit(intrinsic), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "![](assets/tech_1.svg)",
+        r#"<img src="assets/tech_1.svg" width="40" height="10" alt="">"#
+    )]
+    #[case(
+        r#"<img src="assets/tech_1.svg" width="80" height="20" alt="">"#,
+        r#"<img src="assets/tech_1.svg" width="40" height="10" alt="">"#
+    )]
+    #[case("![](assets/tech_1.svg =80x20)", "![](assets/tech_1.svg =40x10)")]
+    #[case(
+        "[![](assets/tech_1.svg)](https://rust-lang.org)",
+        r#"[<img src="assets/tech_1.svg" width="40" height="10" alt="">](https://rust-lang.org)"#
+    )]
+    fn test_apply_to_file_reference(#[case] markdown: &str, #[case] expected: &str) {
+        let mut markdown = markdown.to_string();
+        size(Some(40), None).apply(&mut markdown, Some(BADGE), true);
+        assert_eq!(markdown, expected);
+    }
+
+    #[rstest]
+    #[case("![](assets/tech_1.svg)", "![](assets/tech_1.svg)")]
+    #[case("![](assets/tech_1.svg =80x20)", "![](assets/tech_1.svg =40x10)")]
+    fn test_apply_without_html(#[case] markdown: &str, #[case] expected: &str) {
+        let mut markdown = markdown.to_string();
+        size(Some(40), None).apply(&mut markdown, Some(BADGE), false);
+        assert_eq!(markdown, expected);
+    }
+
+    #[test]
+    fn test_apply_to_unknown_size() {
+        let mut markdown =
+            "![](https://img.shields.io/badge/a-b-red?style=flat&logo=x)".to_string();
+        size(Some(200), Some(28)).apply(&mut markdown, None, true);
+        assert_eq!(
+            markdown,
+            r#"<img src="https://img.shields.io/badge/a-b-red?style=flat&amp;logo=x" height="28" alt="">"#
+        );
+    }
+
+    #[test]
+    fn test_apply_to_data_uri() {
+        let mut markdown = format!("![]({}{})", SVG_DATA_URI, STANDARD.encode(BADGE));
+        size(None, Some(40)).apply(&mut markdown, None, true);
+        assert!(markdown.starts_with(r#"<img src="data:image/svg+xml;base64,"#));
+        assert!(markdown.ends_with(r#" width="160" height="40" alt="">"#));
+    }
+
+    #[test]
+    fn test_apply_to_inline_svg_keeps_drawing() {
+        let mut markdown = String::from_utf8(BADGE.to_vec()).unwrap();
+        size(Some(160), None).apply(&mut markdown, None, true);
+        assert_eq!(
+            markdown,
+            r#"<svg width="160" height="40" viewBox="0 0 80 20" xmlns="http://www.w3.org/2000/svg"></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_apply_leaves_text_alone() {
+        let mut markdown = "[rust]".to_string();
+        size(Some(40), None).apply(&mut markdown, None, true);
+        assert_eq!(markdown, "[rust]");
+    }
+}
@@ -65,6 +65,14 @@ impl HybridBackend {
             Primitive::Rating { .. } => true,
             // Waveforms always use SVG for proper rendering
             Primitive::Waveform { .. } => true,
+            // Stat cards always use SVG for proper rendering
+            Primitive::StatCard { .. } => true,
+            // Steps always use SVG for the connecting lines
+            Primitive::Steps { .. } => true,
+            // Stacked bars always use SVG for proportional segments
+            Primitive::StackedBar { .. } => true,
+            // Groups compose into one SVG if any badge needs SVG
+            Primitive::Group { items, .. } => items.iter().any(Self::needs_svg),
         }
     }
 }
@@ -2,6 +2,7 @@
 ///
 /// This module defines the trait-based architecture for rendering primitives
 /// to different output formats (shields.io URLs, local SVG files, etc.).
+mod display;
 pub mod hybrid;
 pub mod plaintext;
 pub mod shields;
@@ -9,6 +10,9 @@ pub mod svg;
 
 use crate::error::Result;
 use crate::primitive::Primitive;
+use serde::{Deserialize, Serialize};
+
+pub use display::DisplaySize;
 
 /// Represents the output of rendering a primitive.
 #[derive(Debug, Clone, PartialEq)]
@@ -38,6 +42,14 @@ impl RenderedAsset {
         }
     }
 
+    /// Mutable access to the Markdown representation, for rewriting references
+    pub fn markdown_mut(&mut self) -> &mut String {
+        match self {
+            RenderedAsset::InlineMarkdown(md) => md,
+            RenderedAsset::File { markdown_ref, .. } => markdown_ref,
+        }
+    }
+
     /// Check if this asset requires a file to be written
     pub fn is_file_based(&self) -> bool {
         matches!(self, RenderedAsset::File { .. })
@@ -60,6 +72,37 @@ impl RenderedAsset {
     }
 }
 
+/// How references to generated image files state the image size
+///
+/// Without a size, renderers only learn an image's dimensions once it has
+/// loaded, so the page shifts as badges appear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageDimensions {
+    /// `![](badge.svg)`
+    #[default]
+    None,
+    /// `<img src="badge.svg" width="80" height="20" alt="">`
+    Html,
+    /// `![](badge.svg =80x20)`, the size extension understood by
+    /// markdown-it-imsize and Azure DevOps
+    Suffix,
+}
+
+/// How an inline backend embeds SVGs in the markdown itself
+///
+/// Which form survives depends on the target, see
+/// [`Target::supports_inline`](crate::targets::Target::supports_inline).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InlineMode {
+    /// Raw `<svg>` markup, for renderers that pass HTML through
+    #[default]
+    Svg,
+    /// `![](data:image/svg+xml;base64,...)`
+    DataUri,
+}
+
 /// Trait for rendering primitives to output formats.
 ///
 /// Implementations handle backend-specific logic:
@@ -68,6 +111,22 @@ impl RenderedAsset {
 pub trait Renderer {
     /// Render a primitive to an asset (inline or file-based)
     fn render(&self, primitive: &Primitive) -> Result<RenderedAsset>;
+
+    /// Render a primitive built from live data (`{{ui:live:...}}`)
+    ///
+    /// Backends that emit URLs can override this to control how long
+    /// proxies cache the badge. Defaults to [`render`](Renderer::render).
+    fn render_live(&self, primitive: &Primitive) -> Result<RenderedAsset> {
+        self.render(primitive)
+    }
+
+    /// Whether this backend renders primitives as images
+    ///
+    /// Text-only backends return `false`, so components that mix badges
+    /// into other markup (like `matrix`) can fall back to plain glyphs.
+    fn renders_images(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -4,7 +4,7 @@
 /// external dependencies or Unicode decorations. Useful for PyPI package
 /// descriptions and other environments with limited rendering support.
 use crate::error::Result;
-use crate::primitive::Primitive;
+use crate::primitive::{Primitive, StepState};
 use crate::renderer::{RenderedAsset, Renderer};
 
 /// Plain text rendering backend.
@@ -170,16 +170,85 @@ impl Renderer for PlainTextBackend {
                     .collect();
                 wave
             }
+
+            Primitive::StatCard {
+                label,
+                value,
+                trend,
+                ..
+            } => {
+                let mut text = if label.is_empty() {
+                    value.clone()
+                } else {
+                    format!("{}: {}", label, value)
+                };
+                if let Some(trend) = trend {
+                    text.push_str(&format!(" ({})", trend));
+                }
+                text
+            }
+
+            Primitive::StackedBar { segments, .. } => segments
+                .iter()
+                .filter(|s| s.value > 0)
+                .map(|s| format!("{} {}", s.value, s.label))
+                .collect::<Vec<_>>()
+                .join(" · "),
+
+            Primitive::Steps { items, current, .. } => items
+                .iter()
+                .enumerate()
+                .map(|(i, label)| {
+                    // Without a current step every marker is outlined
+                    let state = StepState::of(i, *current);
+                    let marker =
+                        step_marker(i + 1, current.is_some() && state != StepState::Pending);
+                    match state {
+                        StepState::Current => format!("{} **{}**", marker, label),
+                        _ => format!("{} {}", marker, label),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ── "),
+
+            Primitive::Group { items, layout } => {
+                let badges = items
+                    .iter()
+                    .map(|item| Ok(self.render(item)?.to_markdown().to_string()))
+                    .collect::<Result<Vec<_>>>()?;
+                layout
+                    .rows_by_columns(badges.len())
+                    .into_iter()
+                    .map(|row| badges[row].join(" "))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
         };
 
         Ok(RenderedAsset::InlineMarkdown(text))
     }
+
+    fn renders_images(&self) -> bool {
+        false
+    }
+}
+
+/// Circled number for a step: filled (❶) once reached, outlined (①) otherwise
+///
+/// Numbers past the circled glyph ranges fall back to `(n)`.
+fn step_marker(n: usize, filled: bool) -> String {
+    let glyph = match (filled, n) {
+        (true, 1..=10) => char::from_u32(0x2776 + n as u32 - 1),
+        (false, 1..=20) => char::from_u32(0x2460 + n as u32 - 1),
+        _ => None,
+    };
+    glyph.map_or_else(|| format!("({})", n), String::from)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::primitive::{LicenseConfig, TechConfig, VersionConfig};
+    use crate::primitive::{BarSegment, LicenseConfig, TechConfig, VersionConfig};
     use rstest::rstest;
 
     // ========================================================================
@@ -509,4 +578,65 @@ mod tests {
         // Should contain 4 bar characters
         assert_eq!(asset.to_markdown().chars().count(), 4);
     }
+
+    // ========================================================================
+    // Stat Card Rendering
+    // ========================================================================
+
+    #[rstest]
+    #[case("Downloads", Some("+8%"), "Downloads: 1.2M (+8%)")]
+    #[case("Downloads", None, "Downloads: 1.2M")]
+    #[case("", None, "1.2M")]
+    fn test_plaintext_statcard(
+        #[case] label: &str,
+        #[case] trend: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let backend = PlainTextBackend::new();
+        let mut primitive = Primitive::simple_statcard(label, "1.2M");
+        if let Primitive::StatCard { trend: t, .. } = &mut primitive {
+            *t = trend.map(str::to_string);
+        }
+        assert_eq!(backend.render(&primitive).unwrap().to_markdown(), expected);
+    }
+
+    // ========================================================================
+    // Steps Rendering
+    // ========================================================================
+
+    #[rstest]
+    #[case(None, "① Install ── ② Configure ── ③ Ship")]
+    #[case(Some(2), "❶ Install ── ❷ **Configure** ── ③ Ship")]
+    fn test_plaintext_steps(#[case] current: Option<usize>, #[case] expected: &str) {
+        let backend = PlainTextBackend::new();
+        let mut primitive = Primitive::simple_steps(["Install", "Configure", "Ship"]);
+        if let Primitive::Steps { current: c, .. } = &mut primitive {
+            *c = current;
+        }
+        assert_eq!(backend.render(&primitive).unwrap().to_markdown(), expected);
+    }
+
+    #[test]
+    fn test_plaintext_stacked_bar() {
+        let backend = PlainTextBackend::new();
+        let primitive = Primitive::simple_stacked_bar(vec![
+            BarSegment::new("passed", 120, "22C55E"),
+            BarSegment::new("failed", 2, "EF4444"),
+            BarSegment::new("skipped", 0, "EAB308"),
+        ]);
+        assert_eq!(
+            backend.render(&primitive).unwrap().to_markdown(),
+            "120 passed · 2 failed"
+        );
+    }
+
+    #[rstest]
+    #[case(1, false, "①")]
+    #[case(20, false, "⑳")]
+    #[case(21, false, "(21)")]
+    #[case(10, true, "❿")]
+    #[case(11, true, "(11)")]
+    fn test_step_marker(#[case] n: usize, #[case] filled: bool, #[case] expected: &str) {
+        assert_eq!(step_marker(n, filled), expected);
+    }
 }
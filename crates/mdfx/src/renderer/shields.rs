@@ -2,14 +2,85 @@
 ///
 /// This backend generates shields.io badge URLs wrapped in Markdown image syntax.
 /// It's the default rendering backend for mdfx.
+use crate::components::ComponentsRenderer;
 use crate::error::Result;
-use crate::primitive::Primitive;
+use crate::primitive::{Primitive, StepState};
 use crate::renderer::{RenderedAsset, Renderer};
 use crate::shields::ShieldsRenderer;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 
 /// Shields.io rendering backend (default)
 pub struct ShieldsBackend {
     shields: ShieldsRenderer,
+    live_cache: LiveBadgeCache,
+}
+
+/// Caching of live data badges by shields.io and image proxies
+///
+/// GitHub serves README images through its camo proxy, which can keep
+/// showing an old badge after the value changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LiveBadgeCache {
+    /// Append a `v=` parameter derived from the badge content, so every new
+    /// value gets a URL the proxy hasn't cached
+    pub bust: bool,
+    /// Append `cacheSeconds=N`, telling shields.io and proxies how long the
+    /// badge may be cached
+    pub cache_seconds: Option<u32>,
+}
+
+impl LiveBadgeCache {
+    /// Add the configured parameters to every shields.io URL in `markdown`
+    pub fn apply(&self, markdown: &str) -> String {
+        if !self.bust && self.cache_seconds.is_none() {
+            return markdown.to_string();
+        }
+
+        let mut out = String::with_capacity(markdown.len() + 32);
+        let mut rest = markdown;
+        while let Some(start) = rest.find("](https://img.shields.io/") {
+            let url_start = start + 2;
+            let url_len = rest[url_start..]
+                .find(')')
+                .unwrap_or(rest.len() - url_start);
+            let url = &rest[url_start..url_start + url_len];
+
+            out.push_str(&rest[..url_start]);
+            out.push_str(url);
+            let mut separator = if url.contains('?') { '&' } else { '?' };
+            if let Some(seconds) = self.cache_seconds {
+                out.push_str(&format!("{}cacheSeconds={}", separator, seconds));
+                separator = '&';
+            }
+            if self.bust {
+                let hash = xxhash_rust::xxh3::xxh3_64(url.as_bytes());
+                out.push_str(&format!("{}v={:08x}", separator, hash >> 32));
+            }
+            rest = &rest[url_start + url_len..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// The `logo=` value for an icon: a Simple Icons slug as is, or a data URI
+/// for remote icon markup (which shields.io doesn't recolor, so it is
+/// colored here)
+fn logo_param(icon: &str, color: &str) -> String {
+    if !icon.starts_with('<') {
+        return icon.to_string();
+    }
+    let svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\">{}</svg>",
+        badgefx::render::icon_element(icon, color.trim_start_matches('#'))
+    );
+    let encoded = STANDARD
+        .encode(svg)
+        .replace('+', "%2B")
+        .replace('/', "%2F")
+        .replace('=', "%3D");
+    format!("data:image/svg%2Bxml;base64,{}", encoded)
 }
 
 impl ShieldsBackend {
@@ -17,8 +88,15 @@ impl ShieldsBackend {
     pub fn new() -> Result<Self> {
         Ok(ShieldsBackend {
             shields: ShieldsRenderer::new()?,
+            live_cache: LiveBadgeCache::default(),
         })
     }
+
+    /// Control caching of live data badges, see [`LiveBadgeCache`]
+    pub fn with_live_cache(mut self, live_cache: LiveBadgeCache) -> Self {
+        self.live_cache = live_cache;
+        self
+    }
 }
 
 impl Renderer for ShieldsBackend {
@@ -39,14 +117,18 @@ impl Renderer for ShieldsBackend {
                     (Some(icon_name), Some(label_text)) => {
                         let logo_color = icon_color.as_deref().unwrap_or("FFFFFF");
                         self.shields.render_icon_with_label(
-                            icon_name, label_text, color, logo_color, style,
+                            &logo_param(icon_name, logo_color),
+                            label_text,
+                            color,
+                            logo_color,
+                            style,
                         )?
                     }
                     // Icon only - render icon chip
                     (Some(icon_name), None) => {
                         let logo_color = icon_color.as_deref().unwrap_or("FFFFFF");
                         self.shields.render_icon_with_size(
-                            icon_name,
+                            &logo_param(icon_name, logo_color),
                             color,
                             logo_color,
                             style,
@@ -62,15 +144,40 @@ impl Renderer for ShieldsBackend {
                 }
             }
 
+            // Icon chip; a custom label (anything but the tech name) is shown
+            // next to the logo
             Primitive::Tech(cfg) => {
-                self.shields
-                    .render_icon(&cfg.name, &cfg.bg_color, &cfg.logo_color, &cfg.style)?
+                let logo = match cfg.icon.as_deref() {
+                    Some(icon) if icon.starts_with('<') => logo_param(icon, &cfg.logo_color),
+                    _ => cfg.name.clone(),
+                };
+                match cfg.label.as_deref() {
+                    Some(label) if !label.is_empty() && !label.eq_ignore_ascii_case(&cfg.name) => {
+                        self.shields.render_icon_with_label(
+                            &logo,
+                            &encode_badge_text(label),
+                            &cfg.bg_color,
+                            &cfg.logo_color,
+                            &cfg.style,
+                        )?
+                    }
+                    _ => self.shields.render_icon(
+                        &logo,
+                        &cfg.bg_color,
+                        &cfg.logo_color,
+                        &cfg.style,
+                    )?,
+                }
             }
 
             // Version badges - render as simple version label
             // Uses badgefx status detection for color if not overridden
             Primitive::Version(cfg) => {
-                let status = badgefx::version::detect_status(&cfg.version);
+                let status = cfg
+                    .status
+                    .as_deref()
+                    .and_then(badgefx::version::parse_status)
+                    .unwrap_or_else(|| badgefx::version::detect_status(&cfg.version));
                 let bg_color = cfg.bg_color.as_deref().unwrap_or_else(|| status.bg_color());
                 let prefix = cfg.prefix.as_deref().unwrap_or("v");
                 let label = if cfg.version.starts_with('v')
@@ -202,16 +309,110 @@ impl Renderer for ShieldsBackend {
                     label, positive_color
                 )
             }
+
+            // Stat cards become a label/value badge as shields.io fallback
+            // Full card rendering requires SVG backend
+            Primitive::StatCard {
+                label,
+                value,
+                trend,
+                bg_color,
+                ..
+            } => {
+                let message = match trend {
+                    Some(trend) => format!("{} {}", value, trend),
+                    None => value.clone(),
+                };
+                format!(
+                    "![](https://img.shields.io/badge/{}-{}-{}?style=flat-square)",
+                    encode_badge_text(label),
+                    encode_badge_text(&message),
+                    bg_color
+                )
+            }
+
+            // Steps become one number/label badge per step, pending steps
+            // in the pending color; connecting lines require SVG backend
+            Primitive::Steps {
+                items,
+                current,
+                color,
+                pending_color,
+                ..
+            } => items
+                .iter()
+                .enumerate()
+                .map(|(i, label)| {
+                    let fill = match StepState::of(i, *current) {
+                        StepState::Pending => pending_color,
+                        _ => color,
+                    };
+                    format!(
+                        "![](https://img.shields.io/badge/{}-{}-{}?style=flat-square)",
+                        i + 1,
+                        encode_badge_text(label),
+                        fill
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" → "),
+
+            // Stacked bars become one count badge per non-empty segment;
+            // proportional widths require SVG backend
+            Primitive::StackedBar { segments, .. } => segments
+                .iter()
+                .filter(|s| s.value > 0)
+                .map(|s| {
+                    format!(
+                        "![](https://img.shields.io/badge/{}-{}-{}?style=flat-square)",
+                        encode_badge_text(&s.label),
+                        s.value,
+                        s.color
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+
+            // Groups become HTML rows of badge images; wrap and max_width
+            // need badge widths, which only the SVG backend knows
+            Primitive::Group { items, layout } => {
+                let badges = items
+                    .iter()
+                    .map(|item| Ok(self.render(item)?.to_markdown().to_string()))
+                    .collect::<Result<Vec<_>>>()?;
+                let separator = if layout.gap > 0 { " " } else { "" };
+                let rows: Vec<String> = layout
+                    .rows_by_columns(badges.len())
+                    .into_iter()
+                    .map(|row| badges[row].join(separator))
+                    .collect();
+                ComponentsRenderer::apply_row(&rows.join(" <br> "), &layout.align)
+            }
         };
 
         Ok(RenderedAsset::InlineMarkdown(markdown))
     }
+
+    fn render_live(&self, primitive: &Primitive) -> Result<RenderedAsset> {
+        let mut rendered = self.render(primitive)?;
+        let markdown = rendered.markdown_mut();
+        *markdown = self.live_cache.apply(markdown);
+        Ok(rendered)
+    }
+}
+
+/// Escape text for a shields.io static badge path segment
+fn encode_badge_text(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('-', "--")
+        .replace('_', "__")
+        .replace(' ', "%20")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::primitive::{LicenseConfig, TechConfig, VersionConfig};
+    use crate::primitive::{BarSegment, LicenseConfig, TechConfig, VersionConfig};
     use rstest::rstest;
 
     #[test]
@@ -234,6 +435,68 @@ mod tests {
         assert!(!result.is_file_based());
     }
 
+    #[rstest]
+    #[case(false, None, "![](https://img.shields.io/badge/a-b-C?style=flat)")]
+    #[case(
+        false,
+        Some(3600),
+        "![](https://img.shields.io/badge/a-b-C?style=flat&cacheSeconds=3600)"
+    )]
+    fn test_live_cache_params(
+        #[case] bust: bool,
+        #[case] cache_seconds: Option<u32>,
+        #[case] expected: &str,
+    ) {
+        let cache = LiveBadgeCache {
+            bust,
+            cache_seconds,
+        };
+        assert_eq!(
+            cache.apply("![](https://img.shields.io/badge/a-b-C?style=flat)"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_cache_busting_follows_content() {
+        let cache = LiveBadgeCache {
+            bust: true,
+            cache_seconds: None,
+        };
+        let stars_10 = cache.apply("![](https://img.shields.io/badge/Stars:%2010-C)");
+        let stars_11 = cache.apply("![](https://img.shields.io/badge/Stars:%2011-C)");
+
+        assert!(stars_10.starts_with("![](https://img.shields.io/badge/Stars:%2010-C?v="));
+        assert_eq!(stars_10.len(), stars_11.len());
+        assert_ne!(stars_10, stars_11);
+        assert_eq!(
+            stars_10,
+            cache.apply("![](https://img.shields.io/badge/Stars:%2010-C)")
+        );
+    }
+
+    #[test]
+    fn test_live_cache_only_applies_to_live_renders() {
+        let backend = ShieldsBackend::new()
+            .unwrap()
+            .with_live_cache(LiveBadgeCache {
+                bust: false,
+                cache_seconds: Some(600),
+            });
+        let primitive = Primitive::simple_swatch("2B6CB0", "flat");
+
+        assert!(!backend
+            .render(&primitive)
+            .unwrap()
+            .to_markdown()
+            .contains("cacheSeconds"));
+        assert!(backend
+            .render_live(&primitive)
+            .unwrap()
+            .to_markdown()
+            .ends_with("&cacheSeconds=600)"));
+    }
+
     #[test]
     fn test_render_tech_primitive() {
         let backend = ShieldsBackend::new().unwrap();
@@ -252,6 +515,25 @@ mod tests {
         assert!(markdown.contains("000000"));
     }
 
+    #[rstest]
+    #[case(None, "/badge/-%20-000000?")]
+    #[case(Some("Rust"), "/badge/-%20-000000?")]
+    #[case(Some("MSRV 1.70-beta"), "/badge/-MSRV%201.70--beta-000000?")]
+    fn test_render_tech_label(#[case] label: Option<&str>, #[case] expected: &str) {
+        let backend = ShieldsBackend::new().unwrap();
+        let primitive = Primitive::Tech(TechConfig {
+            name: "rust".to_string(),
+            bg_color: "000000".to_string(),
+            logo_color: "FFFFFF".to_string(),
+            label: label.map(String::from),
+            ..Default::default()
+        });
+
+        let result = backend.render(&primitive).unwrap();
+        let markdown = result.to_markdown();
+        assert!(markdown.contains(expected), "{}", markdown);
+    }
+
     #[test]
     fn test_render_with_palette_colors() {
         let backend = ShieldsBackend::new().unwrap();
@@ -293,6 +575,21 @@ mod tests {
         assert!(markdown.contains(expected_color));
     }
 
+    #[test]
+    fn test_render_version_status_override() {
+        let backend = ShieldsBackend::new().unwrap();
+        let primitive = Primitive::Version(VersionConfig {
+            version: "1.0.0".to_string(),
+            status: Some("deprecated".to_string()),
+            style: "flat".to_string(),
+            ..Default::default()
+        });
+
+        let result = backend.render(&primitive).unwrap();
+        let markdown = result.to_markdown();
+        assert!(markdown.contains("EF4444"));
+    }
+
     #[test]
     fn test_render_version_escapes_dashes() {
         let backend = ShieldsBackend::new().unwrap();
@@ -542,4 +839,71 @@ mod tests {
         assert!(markdown.contains("5pts"));
         assert!(markdown.contains("success"));
     }
+
+    // ========================================================================
+    // Stat Card Badge Rendering
+    // ========================================================================
+
+    #[rstest]
+    #[case(None, "badge/Weekly%20downloads-1.2M-282F3C?")]
+    #[case(Some("-3%"), "badge/Weekly%20downloads-1.2M%20--3%25-282F3C?")]
+    fn test_render_statcard(#[case] trend: Option<&str>, #[case] expected: &str) {
+        let backend = ShieldsBackend::new().unwrap();
+        let mut primitive = Primitive::simple_statcard("Weekly downloads", "1.2M");
+        if let Primitive::StatCard { trend: t, .. } = &mut primitive {
+            *t = trend.map(str::to_string);
+        }
+
+        let markdown = backend
+            .render(&primitive)
+            .unwrap()
+            .to_markdown()
+            .to_string();
+        assert!(markdown.contains(expected), "{}", markdown);
+    }
+
+    // ========================================================================
+    // Steps Badge Rendering
+    // ========================================================================
+
+    #[test]
+    fn test_render_steps() {
+        let backend = ShieldsBackend::new().unwrap();
+        let mut primitive = Primitive::simple_steps(["Install", "Set up"]);
+        if let Primitive::Steps { current, .. } = &mut primitive {
+            *current = Some(1);
+        }
+
+        let markdown = backend
+            .render(&primitive)
+            .unwrap()
+            .to_markdown()
+            .to_string();
+        assert_eq!(
+            markdown,
+            "![](https://img.shields.io/badge/1-Install-F41C80?style=flat-square) → \
+             ![](https://img.shields.io/badge/2-Set%20up-475569?style=flat-square)"
+        );
+    }
+
+    // ========================================================================
+    // Stacked Bar Rendering
+    // ========================================================================
+
+    #[test]
+    fn test_render_stacked_bar() {
+        let backend = ShieldsBackend::new().unwrap();
+        let primitive = Primitive::simple_stacked_bar(vec![
+            BarSegment::new("passed", 120, "22C55E"),
+            BarSegment::new("failed", 0, "EF4444"),
+            BarSegment::new("skipped", 3, "EAB308"),
+        ]);
+
+        let asset = backend.render(&primitive).unwrap();
+        assert_eq!(
+            asset.to_markdown(),
+            "![](https://img.shields.io/badge/passed-120-22C55E?style=flat-square) \
+             ![](https://img.shields.io/badge/skipped-3-EAB308?style=flat-square)"
+        );
+    }
 }
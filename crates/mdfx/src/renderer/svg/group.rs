@@ -0,0 +1,237 @@
+//! Tech-group layout: compose several badges into one SVG
+
+use super::svg_dimensions;
+use super::utils::namespace_ids;
+use crate::error::{Error, Result};
+use crate::primitive::{GroupLayout, Primitive};
+
+/// Corner radius used for the outer corners of a seamless row
+const ROW_CORNER_RADIUS: u32 = 6;
+
+/// Render `items` with `render_item` and arrange the results in rows
+///
+/// When `gap` is zero, tech badges that don't set their own corners get
+/// square inner corners, so each row reads as one pill.
+pub fn render(
+    items: &[Primitive],
+    layout: &GroupLayout,
+    render_item: impl Fn(&Primitive) -> Result<String>,
+) -> Result<String> {
+    let mut items = items.to_vec();
+    let mut svgs = items.iter().map(&render_item).collect::<Result<Vec<_>>>()?;
+    let sizes = svgs
+        .iter()
+        .map(|svg| {
+            svg_dimensions(svg).ok_or_else(|| {
+                Error::ParseError("tech-group badge has no width and height".to_string())
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let scaled: Vec<(u32, u32)> = sizes
+        .iter()
+        .map(|&(w, h)| match layout.max_width {
+            Some(max) if w > max && w > 0 => (max, (h * max).div_ceil(w)),
+            _ => (w, h),
+        })
+        .collect();
+    let widths: Vec<u32> = scaled.iter().map(|&(w, _)| w).collect();
+    let rows = layout.rows(&widths);
+
+    if layout.gap == 0 {
+        for row in rows.iter().filter(|row| row.len() > 1) {
+            for i in row.clone() {
+                let Primitive::Tech(cfg) = &mut items[i] else {
+                    continue;
+                };
+                if cfg.corners.is_some() || cfg.rx.is_some() {
+                    continue;
+                }
+                let r = ROW_CORNER_RADIUS;
+                cfg.corners = Some(if i == row.start {
+                    [r, 0, 0, r]
+                } else if i == row.end - 1 {
+                    [0, r, r, 0]
+                } else {
+                    [0, 0, 0, 0]
+                });
+                svgs[i] = render_item(&items[i])?;
+            }
+        }
+    }
+
+    let row_width = |row: &std::ops::Range<usize>| {
+        widths[row.clone()].iter().sum::<u32>() + layout.gap * (row.len() as u32 - 1)
+    };
+    let row_height = |row: &std::ops::Range<usize>| {
+        scaled[row.clone()]
+            .iter()
+            .map(|&(_, h)| h)
+            .max()
+            .unwrap_or(0)
+    };
+    let total_width = rows.iter().map(row_width).max().unwrap_or(0);
+    let total_height = rows.iter().map(row_height).sum::<u32>()
+        + layout.gap * (rows.len() as u32).saturating_sub(1);
+
+    let mut body = String::new();
+    let mut y = 0;
+    for row in &rows {
+        let height = row_height(row);
+        let mut x = match layout.align.as_str() {
+            "center" => (total_width - row_width(row)) / 2,
+            "right" => total_width - row_width(row),
+            _ => 0,
+        };
+        for i in row.clone() {
+            let (w, h) = scaled[i];
+            let transform = if (w, h) == sizes[i] {
+                format!("translate({} {})", x, y + (height - h) / 2)
+            } else {
+                format!(
+                    "translate({} {}) scale({:.4})",
+                    x,
+                    y + (height - h) / 2,
+                    w as f32 / sizes[i].0 as f32
+                )
+            };
+            body.push_str(&format!(
+                "<g transform=\"{}\">{}</g>\n",
+                transform,
+                namespace_ids(strip_prolog(&svgs[i]), &format!("b{}", i))
+            ));
+            x += w + layout.gap;
+        }
+        y += height + layout.gap;
+    }
+
+    Ok(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n{}</svg>",
+        total_width, total_height, total_width, total_height, body
+    ))
+}
+
+/// Drop an XML declaration, which may only appear at the start of a document
+fn strip_prolog(svg: &str) -> &str {
+    match svg.trim_start().strip_prefix("<?xml") {
+        Some(rest) => rest
+            .split_once("?>")
+            .map_or(svg, |(_, body)| body.trim_start()),
+        None => svg.trim(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::TechConfig;
+    use rstest::rstest;
+
+    /// Stand-in renderer: a badge as wide as its name is long, times ten
+    fn fake_render(item: &Primitive) -> Result<String> {
+        let Primitive::Tech(cfg) = item else {
+            unreachable!("tests only use tech badges");
+        };
+        Ok(format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="20"><rect id="bg" fill="url(#bg)" data-corners="{:?}"/></svg>"#,
+            cfg.name.len() * 10,
+            cfg.corners
+        ))
+    }
+
+    fn badges(names: &[&str]) -> Vec<Primitive> {
+        names
+            .iter()
+            .map(|name| Primitive::Tech(TechConfig::new(name)))
+            .collect()
+    }
+
+    #[rstest]
+    #[case(GroupLayout::default(), "width=\"120\" height=\"20\"")]
+    #[case(GroupLayout { columns: Some(2), ..Default::default() }, "width=\"60\" height=\"40\"")]
+    #[case(GroupLayout { columns: Some(2), gap: 4, ..Default::default() }, "width=\"64\" height=\"44\"")]
+    #[case(GroupLayout { wrap: Some(60), ..Default::default() }, "width=\"60\" height=\"40\"")]
+    #[case(GroupLayout { max_width: Some(20), ..Default::default() }, "width=\"60\" height=\"20\"")]
+    fn test_group_size(#[case] layout: GroupLayout, #[case] expected: &str) {
+        let svg = render(&badges(&["rust", "go", "python"]), &layout, fake_render).unwrap();
+        assert!(svg.contains(expected), "{}", svg);
+    }
+
+    #[rstest]
+    #[case("left", "translate(0 20)")]
+    #[case("center", "translate(40 20)")]
+    #[case("right", "translate(80 20)")]
+    fn test_group_align(#[case] align: &str, #[case] last_row: &str) {
+        let layout = GroupLayout {
+            columns: Some(2),
+            align: align.to_string(),
+            ..Default::default()
+        };
+        // Rows are 100 and 20 wide
+        let svg = render(&badges(&["rust", "python", "go"]), &layout, fake_render).unwrap();
+        assert!(
+            svg.contains(&format!("<g transform=\"{}\">", last_row)),
+            "{}",
+            svg
+        );
+    }
+
+    #[test]
+    fn test_group_max_width_scales_wide_badges() {
+        let layout = GroupLayout {
+            max_width: Some(30),
+            ..Default::default()
+        };
+        let svg = render(&badges(&["go", "python"]), &layout, fake_render).unwrap();
+        assert!(svg.contains("translate(0 0)\">"));
+        assert!(svg.contains("translate(20 5) scale(0.5000)"));
+        assert!(svg.contains("width=\"50\" height=\"20\""));
+    }
+
+    #[test]
+    fn test_group_squares_inner_corners_per_row() {
+        let mut items = badges(&["rust", "go", "java", "c"]);
+        let Primitive::Tech(cfg) = &mut items[3] else {
+            unreachable!()
+        };
+        cfg.corners = Some([1, 1, 1, 1]);
+        let layout = GroupLayout {
+            columns: Some(3),
+            ..Default::default()
+        };
+        let svg = render(&items, &layout, fake_render).unwrap();
+        assert!(svg.contains("Some([6, 0, 0, 6])"));
+        assert!(svg.contains("Some([0, 0, 0, 0])"));
+        assert!(svg.contains("Some([0, 6, 6, 0])"));
+        // Single-badge row keeps its own corners
+        assert!(svg.contains("Some([1, 1, 1, 1])"));
+    }
+
+    #[test]
+    fn test_group_with_gap_keeps_corners() {
+        let layout = GroupLayout {
+            gap: 2,
+            ..Default::default()
+        };
+        let svg = render(&badges(&["rust", "go"]), &layout, fake_render).unwrap();
+        assert_eq!(svg.matches("None").count(), 2);
+    }
+
+    #[test]
+    fn test_group_namespaces_ids() {
+        let svg = render(
+            &badges(&["rust", "go"]),
+            &GroupLayout::default(),
+            fake_render,
+        )
+        .unwrap();
+        assert!(svg.contains(r#"id="b0-bg" fill="url(#b0-bg)""#));
+        assert!(svg.contains(r#"id="b1-bg" fill="url(#b1-bg)""#));
+    }
+
+    #[rstest]
+    #[case("<?xml version=\"1.0\"?>\n<svg/>", "<svg/>")]
+    #[case("  <svg/>\n", "<svg/>")]
+    fn test_strip_prolog(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(strip_prolog(input), expected);
+    }
+}
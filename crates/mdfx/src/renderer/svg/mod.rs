@@ -8,18 +8,25 @@
 
 mod donut;
 mod gauge;
+mod group;
 mod progress;
 mod rating;
 mod sparkline;
+mod stacked_bar;
+mod statcard;
+mod steps;
 pub mod swatch;
 pub mod tech;
 mod utils;
 mod waveform;
 
-use crate::error::Result;
-use crate::manifest::content_addressed_filename;
+use crate::error::{Error, Result};
+use crate::manifest::FilenameScheme;
 use crate::primitive::Primitive;
-use crate::renderer::{RenderedAsset, Renderer};
+use crate::renderer::{ImageDimensions, InlineMode, RenderedAsset, Renderer};
+use badgefx::escape::{escape_attr, sanitize_url};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 
 /// SVG rendering backend (file-based or inline)
 pub struct SvgBackend {
@@ -28,8 +35,15 @@ pub struct SvgBackend {
     /// Prefix for asset paths in markdown references (e.g., "assets/" instead of "examples/assets/")
     /// When None, uses out_dir as the prefix
     assets_prefix: Option<String>,
-    /// When true, embed SVGs as data URIs instead of writing files
-    inline: bool,
+    /// When set, embed SVGs in the markdown instead of writing files
+    inline: Option<InlineMode>,
+    /// Whether file references state the image size
+    image_dimensions: ImageDimensions,
+    /// Hash length and slug of generated filenames
+    filenames: FilenameScheme,
+    /// Prepended to every filename, so runs sharing an assets directory
+    /// don't mix their files
+    namespace: Option<String>,
 }
 
 impl SvgBackend {
@@ -38,7 +52,10 @@ impl SvgBackend {
         Self {
             out_dir: out_dir.into(),
             assets_prefix: None,
-            inline: false,
+            inline: None,
+            image_dimensions: ImageDimensions::None,
+            filenames: FilenameScheme::default(),
+            namespace: None,
         }
     }
 
@@ -50,21 +67,55 @@ impl SvgBackend {
         Self {
             out_dir: out_dir.into(),
             assets_prefix: Some(assets_prefix.into()),
-            inline: false,
+            inline: None,
+            image_dimensions: ImageDimensions::None,
+            filenames: FilenameScheme::default(),
+            namespace: None,
         }
     }
 
-    /// Create a new inline SVG backend (embeds as data URIs)
+    /// Create a new inline SVG backend (embeds raw `<svg>` markup)
     pub fn new_inline() -> Self {
+        Self::inline(InlineMode::Svg)
+    }
+
+    /// Create an inline SVG backend that embeds in `mode`
+    pub fn inline(mode: InlineMode) -> Self {
         Self {
             out_dir: String::new(),
             assets_prefix: None,
-            inline: true,
+            inline: Some(mode),
+            image_dimensions: ImageDimensions::None,
+            filenames: FilenameScheme::default(),
+            namespace: None,
         }
     }
 
+    /// State image sizes in file references, see [`ImageDimensions`]
+    pub fn with_image_dimensions(mut self, dimensions: ImageDimensions) -> Self {
+        self.image_dimensions = dimensions;
+        self
+    }
+
+    /// Name files with `scheme` instead of the default 16-character hash
+    pub fn with_filename_scheme(mut self, scheme: FilenameScheme) -> Self {
+        self.filenames = scheme;
+        self
+    }
+
+    /// Start every filename with `namespace`, e.g. `docs-api-tech_<hash>.svg`
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
     /// Check if this backend uses inline mode
     pub fn is_inline(&self) -> bool {
+        self.inline.is_some()
+    }
+
+    /// How SVGs are embedded, or `None` in file mode
+    pub fn inline_mode(&self) -> Option<InlineMode> {
         self.inline
     }
 
@@ -81,8 +132,93 @@ impl SvgBackend {
             Primitive::Sparkline { .. } => "sparkline",
             Primitive::Rating { .. } => "rating",
             Primitive::Waveform { .. } => "waveform",
+            Primitive::StatCard { .. } => "statcard",
+            Primitive::Steps { .. } => "steps",
+            Primitive::StackedBar { .. } => "stackedbar",
+            Primitive::Group { .. } => "group",
+        }
+    }
+
+    /// Readable name of a primitive for slugged filenames
+    fn slug(primitive: &Primitive) -> Option<String> {
+        match primitive {
+            Primitive::Swatch { color, label, .. } => Some(label.as_ref().unwrap_or(color).clone()),
+            Primitive::Tech(cfg) => Some(cfg.name.clone()),
+            Primitive::Version(cfg) => Some(cfg.version.clone()),
+            Primitive::License(cfg) => Some(cfg.license.clone()),
+            Primitive::Progress { percent, .. }
+            | Primitive::Donut { percent, .. }
+            | Primitive::Gauge { percent, .. } => Some(percent.to_string()),
+            Primitive::StatCard { label, .. } => Some(label.clone()),
+            _ => None,
         }
     }
+
+    /// Image reference to `src`, sized per [`ImageDimensions`] and linked
+    /// for tech badges with a `url`
+    fn image_ref(&self, src: &str, svg: &str, primitive: &Primitive) -> String {
+        let size = match self.image_dimensions {
+            ImageDimensions::None => None,
+            _ => svg_dimensions(svg),
+        };
+        let image = match size {
+            Some((w, h)) if self.image_dimensions == ImageDimensions::Html => format!(
+                r#"<img src="{}" width="{}" height="{}" alt="">"#,
+                escape_attr(src),
+                w,
+                h
+            ),
+            Some((w, h)) => format!("![]({} ={}x{})", src, w, h),
+            None => format!("![]({})", src),
+        };
+        let link = match primitive {
+            Primitive::Tech(cfg) => cfg.url.as_deref(),
+            _ => None,
+        };
+        match link {
+            Some(url) if image.starts_with('<') => format!(
+                r#"<a href="{}">{}</a>"#,
+                escape_attr(&sanitize_url(url)),
+                image
+            ),
+            Some(url) => format!("[{}]({})", image, url),
+            None => image,
+        }
+    }
+
+    /// Render a group member to SVG source
+    fn item_svg(&self, item: &Primitive) -> Result<String> {
+        match self.render(item)? {
+            RenderedAsset::File { bytes, .. } => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+            RenderedAsset::InlineMarkdown(svg) if svg.starts_with("<svg") => Ok(svg),
+            RenderedAsset::InlineMarkdown(_) => Err(Error::ParseError(
+                "tech-group layout can't compose shields.io badges into one SVG; remove source=shields"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+/// Width and height of an SVG document, from its root element
+///
+/// Fractional sizes are rounded up. Returns `None` when either attribute is
+/// missing or not a plain number (e.g. `100%`).
+pub fn svg_dimensions(svg: &str) -> Option<(u32, u32)> {
+    let start = svg.find("<svg")?;
+    let tag = &svg[start..start + svg[start..].find('>')?];
+
+    let attr = |name: &str| -> Option<u32> {
+        let key = format!("{}=\"", name);
+        let (i, _) = tag
+            .match_indices(&key)
+            .find(|(i, _)| tag[..*i].ends_with(char::is_whitespace))?;
+        let value = &tag[i + key.len()..];
+        let value = &value[..value.find('"')?];
+        let number: f32 = value.strip_suffix("px").unwrap_or(value).parse().ok()?;
+        Some(number.ceil() as u32)
+    };
+
+    Some((attr("width")?, attr("height")?))
 }
 
 impl Renderer for SvgBackend {
@@ -375,50 +511,120 @@ impl Renderer for SvgBackend {
                 *show_center_line,
                 center_line_color.as_deref(),
             ),
+
+            Primitive::StatCard {
+                label,
+                value,
+                icon,
+                trend,
+                width,
+                height,
+                rx,
+                bg_color,
+                value_color,
+                label_color,
+                icon_color,
+                trend_color,
+            } => statcard::render(
+                label,
+                value,
+                icon.as_deref(),
+                trend.as_deref(),
+                *width,
+                *height,
+                *rx,
+                bg_color,
+                value_color,
+                label_color,
+                icon_color,
+                trend_color,
+            ),
+
+            Primitive::Steps {
+                items,
+                current,
+                size,
+                spacing,
+                color,
+                pending_color,
+                text_color,
+                label_color,
+            } => steps::render(
+                items,
+                *current,
+                *size,
+                *spacing,
+                color,
+                pending_color,
+                text_color,
+                label_color,
+            ),
+
+            Primitive::StackedBar {
+                segments,
+                width,
+                height,
+                rx,
+                track_color,
+            } => stacked_bar::render(segments, *width, *height, *rx, track_color),
+
+            // Members are already scoped by their own render, and the group
+            // prefixes them by position
+            Primitive::Group { items, layout } => {
+                group::render(items, layout, |item| self.item_svg(item))?
+            }
+        };
+        let svg = match primitive {
+            Primitive::Group { .. } => svg,
+            _ => utils::scope_ids(&svg),
         };
 
-        // Handle inline mode (raw SVG) vs file mode
-        if self.inline {
+        // Handle inline modes vs file mode
+        match self.inline {
             // Output raw SVG directly (works in most markdown renderers that support HTML)
-            Ok(RenderedAsset::InlineMarkdown(svg))
-        } else {
-            // Generate content-addressed filename from rendered SVG bytes
-            // This ensures:
-            // 1. Stable filenames across Rust versions (SHA-256 based)
-            // 2. True deduplication (same content = same filename)
-            // 3. Reproducible builds
-            let svg_bytes = svg.as_bytes();
-            let type_prefix = Self::type_prefix(primitive);
-            let filename = content_addressed_filename(svg_bytes, type_prefix);
-
-            // File path uses out_dir (where files are written)
-            let out_dir = self.out_dir.trim_end_matches('/');
-            let relative_path = format!("{}/{}", out_dir, filename);
-
-            // Markdown reference uses assets_prefix if set, otherwise out_dir
-            let md_prefix = self
-                .assets_prefix
-                .as_ref()
-                .map(|p| p.trim_end_matches('/'))
-                .unwrap_or(out_dir);
-            let md_path = format!("{}/{}", md_prefix, filename);
-
-            // Generate markdown image reference, optionally wrapped in a link
-            let markdown_ref = if let Primitive::Tech(cfg) = primitive {
-                if let Some(url) = &cfg.url {
-                    format!("[![]({})]({})", md_path, url)
-                } else {
-                    format!("![]({})", md_path)
-                }
-            } else {
-                format!("![]({})", md_path)
-            };
-            Ok(RenderedAsset::File {
-                relative_path,
-                bytes: svg.into_bytes(),
-                markdown_ref,
-                primitive: Box::new(primitive.clone()),
-            })
+            Some(InlineMode::Svg) => Ok(RenderedAsset::InlineMarkdown(svg)),
+            Some(InlineMode::DataUri) => {
+                let src = format!("data:image/svg+xml;base64,{}", STANDARD.encode(&svg));
+                Ok(RenderedAsset::InlineMarkdown(
+                    self.image_ref(&src, &svg, primitive),
+                ))
+            }
+            None => {
+                // Generate content-addressed filename from rendered SVG bytes
+                // This ensures:
+                // 1. Stable filenames across Rust versions (SHA-256 based)
+                // 2. True deduplication (same content = same filename)
+                // 3. Reproducible builds
+                let svg_bytes = svg.as_bytes();
+                let type_prefix = Self::type_prefix(primitive);
+                let slug = Self::slug(primitive);
+                let filename = format!(
+                    "{}{}",
+                    self.namespace.as_deref().unwrap_or_default(),
+                    self.filenames
+                        .filename(svg_bytes, type_prefix, slug.as_deref())
+                );
+
+                // File path uses out_dir (where files are written)
+                let out_dir = self.out_dir.trim_end_matches('/');
+                let relative_path = format!("{}/{}", out_dir, filename);
+
+                // Markdown reference uses assets_prefix if set, otherwise out_dir
+                let md_prefix = self
+                    .assets_prefix
+                    .as_ref()
+                    .map(|p| p.trim_end_matches('/'))
+                    .unwrap_or(out_dir);
+                let md_path = format!("{}/{}", md_prefix, filename);
+
+                let markdown_ref = self.image_ref(&md_path, &svg, primitive);
+                Ok(RenderedAsset::File {
+                    relative_path,
+                    bytes: svg.into_bytes(),
+                    markdown_ref,
+                    primitive: Box::new(primitive.clone()),
+                })
+            }
         }
     }
 }
@@ -426,7 +632,7 @@ impl Renderer for SvgBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::primitive::TechConfig;
+    use crate::primitive::{BarSegment, TechConfig};
     use insta::assert_snapshot;
     use rstest::rstest;
 
@@ -488,6 +694,33 @@ mod tests {
         assert_eq!(result1.file_path() == result2.file_path(), should_match);
     }
 
+    #[rstest]
+    #[case(Primitive::Tech(TechConfig::new("rust")), "assets/tech_rust_")]
+    #[case(Primitive::simple_swatch("F41C80", "flat"), "assets/swatch_f41c80_")]
+    #[case(Primitive::simple_steps(["Install"]), "assets/steps_")]
+    fn test_filename_scheme_slug(#[case] primitive: Primitive, #[case] prefix: &str) {
+        let backend = SvgBackend::new("assets").with_filename_scheme(FilenameScheme {
+            hash_length: 12,
+            slug: true,
+        });
+        let asset = backend.render(&primitive).unwrap();
+        let path = asset.file_path().unwrap();
+        assert!(path.starts_with(prefix), "{}", path);
+        assert_eq!(path.len(), prefix.len() + 12 + ".svg".len());
+        assert!(asset.to_markdown().contains(path));
+    }
+
+    #[test]
+    fn test_namespace() {
+        let backend = SvgBackend::with_prefix("out/assets", "assets").with_namespace("docs-api-");
+        let asset = backend
+            .render(&Primitive::simple_swatch("F41C80", "flat"))
+            .unwrap();
+        let path = asset.file_path().unwrap();
+        assert!(path.starts_with("out/assets/docs-api-swatch_"), "{}", path);
+        assert!(asset.to_markdown().contains("(assets/docs-api-swatch_"));
+    }
+
     #[test]
     fn test_inline_mode() {
         let backend = SvgBackend::new_inline();
@@ -502,6 +735,58 @@ mod tests {
         assert!(svg.starts_with("<svg"));
     }
 
+    #[test]
+    fn test_data_uri_inline_mode() {
+        let backend = SvgBackend::inline(InlineMode::DataUri);
+        assert_eq!(backend.inline_mode(), Some(InlineMode::DataUri));
+
+        let primitive = Primitive::simple_swatch("F41C80", "flat-square");
+        let RenderedAsset::InlineMarkdown(markdown) = backend.render(&primitive).unwrap() else {
+            unreachable!("Expected InlineMarkdown asset");
+        };
+        let encoded = markdown
+            .strip_prefix("![](data:image/svg+xml;base64,")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .unwrap();
+        let svg = String::from_utf8(STANDARD.decode(encoded).unwrap()).unwrap();
+        assert_eq!(svg, render_inline_svg(&primitive));
+
+        let mut tech = TechConfig::new("rust");
+        tech.url = Some("https://rust-lang.org".to_string());
+        let backend = backend.with_image_dimensions(ImageDimensions::Html);
+        let asset = backend.render(&Primitive::Tech(tech)).unwrap();
+        let markdown = asset.to_markdown();
+        assert!(
+            markdown.starts_with(
+                r#"<a href="https://rust-lang.org"><img src="data:image/svg+xml;base64,"#
+            ),
+            "{}",
+            markdown
+        );
+    }
+
+    #[test]
+    fn test_ids_are_scoped_by_content() {
+        let gradient_swatch = |to: &str| {
+            let mut primitive = Primitive::simple_swatch("FF0000", "flat");
+            if let Primitive::Swatch { gradient, .. } = &mut primitive {
+                *gradient = Some(format!("horizontal/FF0000/{}", to));
+            }
+            render_inline_svg(&primitive)
+        };
+        let blue = gradient_swatch("0000FF");
+        let green = gradient_swatch("00FF00");
+
+        let grad_id = |svg: &str| {
+            let start = svg.find("<linearGradient id=\"").unwrap() + 20;
+            svg[start..start + svg[start..].find('"').unwrap()].to_string()
+        };
+        assert!(grad_id(&blue).ends_with("-grad"), "{}", blue);
+        assert!(blue.contains(&format!("url(#{})", grad_id(&blue))));
+        assert_ne!(grad_id(&blue), grad_id(&green));
+        assert_eq!(blue, gradient_swatch("0000FF"));
+    }
+
     // ========================================================================
     // Type Prefix Detection (Parameterized)
     // ========================================================================
@@ -511,6 +796,9 @@ mod tests {
     #[case(Primitive::Tech(TechConfig::new("rust")), "tech")]
     #[case(Primitive::simple_progress(50, "E0E0E0", "4CAF50"), "progress")]
     #[case(Primitive::simple_donut(75, "E0E0E0", "4CAF50"), "donut")]
+    #[case(Primitive::simple_statcard("Stars", "4k"), "statcard")]
+    #[case(Primitive::simple_steps(["Install"]), "steps")]
+    #[case(Primitive::simple_stacked_bar(vec![]), "stackedbar")]
     fn test_type_prefix(#[case] primitive: Primitive, #[case] expected: &str) {
         assert_eq!(SvgBackend::type_prefix(&primitive), expected);
     }
@@ -577,6 +865,84 @@ mod tests {
         assert_snapshot!("swatch_with_label", render_inline_svg(&primitive));
     }
 
+    #[test]
+    fn test_swatch_label_is_escaped() {
+        let primitive = Primitive::Swatch {
+            color: "FF5722".to_string(),
+            style: "flat-square".to_string(),
+            opacity: None,
+            width: Some(80),
+            height: Some(20),
+            border_color: None,
+            border_width: None,
+            label: Some("<b>A & B</b>".to_string()),
+            label_color: Some("FFF\" onclick=\"x".to_string()),
+            icon: None,
+            icon_color: None,
+            rx: None,
+            ry: None,
+            shadow: None,
+            gradient: None,
+            stroke_dash: None,
+            logo_size: None,
+            border_top: None,
+            border_right: None,
+            border_bottom: None,
+            border_left: None,
+        };
+        let svg = render_inline_svg(&primitive);
+
+        assert!(svg.contains(">&lt;b&gt;A &amp; B&lt;/b&gt;</text>"));
+        assert!(svg.contains("fill=\"#FFF&quot; onclick=&quot;x\""));
+    }
+
+    #[rstest]
+    #[case(r#"<svg xmlns="x" width="80" height="20">"#, Some((80, 20)))]
+    #[case("<svg height=\"12.5px\"\n  width=\"40\">", Some((40, 13)))]
+    #[case(r#"<svg stroke-width="2" height="20">"#, None)]
+    #[case(r#"<svg width="100%" height="20">"#, None)]
+    #[case("not svg", None)]
+    fn test_svg_dimensions(#[case] svg: &str, #[case] expected: Option<(u32, u32)>) {
+        assert_eq!(svg_dimensions(svg), expected);
+    }
+
+    #[rstest]
+    #[case(ImageDimensions::None, "![](assets/{})")]
+    #[case(
+        ImageDimensions::Html,
+        r#"<img src="assets/{}" width="80" height="20" alt="">"#
+    )]
+    #[case(ImageDimensions::Suffix, "![](assets/{} =80x20)")]
+    fn test_image_dimensions_in_reference(
+        #[case] dimensions: ImageDimensions,
+        #[case] expected: &str,
+    ) {
+        let backend = SvgBackend::new("assets").with_image_dimensions(dimensions);
+        let mut primitive = Primitive::simple_swatch("F41C80", "flat");
+        if let Primitive::Swatch { width, height, .. } = &mut primitive {
+            (*width, *height) = (Some(80), Some(20));
+        }
+
+        let asset = backend.render(&primitive).unwrap();
+        let file_name = asset.file_path().unwrap().trim_start_matches("assets/");
+        assert_eq!(asset.to_markdown(), expected.replace("{}", file_name));
+    }
+
+    #[test]
+    fn test_linked_tech_badge_with_html_dimensions() {
+        let mut cfg = TechConfig::new("rust");
+        cfg.url = Some("https://www.rust-lang.org".to_string());
+        let backend = SvgBackend::new("assets").with_image_dimensions(ImageDimensions::Html);
+
+        let asset = backend.render(&Primitive::Tech(cfg)).unwrap();
+        let markdown = asset.to_markdown();
+
+        assert!(
+            markdown.starts_with(r#"<a href="https://www.rust-lang.org"><img src="assets/tech_"#)
+        );
+        assert!(markdown.ends_with(r#"alt=""></a>"#));
+    }
+
     #[test]
     fn snapshot_progress_bar() {
         let primitive = Primitive::simple_progress(65u8, "E0E0E0", "4CAF50");
@@ -618,6 +984,42 @@ mod tests {
         assert_snapshot!("waveform_audio", render_inline_svg(&primitive));
     }
 
+    #[test]
+    fn snapshot_statcard() {
+        let mut primitive = Primitive::simple_statcard("Downloads", "1.2M");
+        if let Primitive::StatCard {
+            icon,
+            trend,
+            trend_color,
+            ..
+        } = &mut primitive
+        {
+            *icon = Some("download".to_string());
+            *trend = Some("+8%".to_string());
+            *trend_color = "22C55E".to_string();
+        }
+        assert_snapshot!("statcard_downloads", render_inline_svg(&primitive));
+    }
+
+    #[test]
+    fn snapshot_steps() {
+        let mut primitive = Primitive::simple_steps(["Install", "Configure", "Ship"]);
+        if let Primitive::Steps { current, .. } = &mut primitive {
+            *current = Some(2);
+        }
+        assert_snapshot!("steps_onboarding", render_inline_svg(&primitive));
+    }
+
+    #[test]
+    fn snapshot_stacked_bar() {
+        let primitive = Primitive::simple_stacked_bar(vec![
+            BarSegment::new("passed", 120, "22C55E"),
+            BarSegment::new("failed", 2, "EF4444"),
+            BarSegment::new("skipped", 3, "EAB308"),
+        ]);
+        assert_snapshot!("stacked_bar_tests", render_inline_svg(&primitive));
+    }
+
     #[test]
     fn snapshot_tech_badge() {
         let primitive = Primitive::Tech(TechConfig::new("rust"));
@@ -0,0 +1,99 @@
+//! Stacked bar SVG renderer (one bar split into proportional segments)
+
+use crate::escape::escape_text;
+use crate::primitive::BarSegment;
+
+/// Render a stacked bar
+///
+/// Segment edges are placed from the running total, so rounding never
+/// leaves a gap or pushes the last segment past the end of the track.
+pub fn render(
+    segments: &[BarSegment],
+    width: u32,
+    height: u32,
+    rx: u32,
+    track_color: &str,
+) -> String {
+    let total: u64 = segments.iter().map(|s| s.value as u64).sum();
+    let edge = |sum: u64| (width as u64 * sum).div_ceil(total.max(1)) as u32;
+
+    let mut rects = Vec::new();
+    let mut sum = 0;
+    for segment in segments.iter().filter(|s| s.value > 0) {
+        let x = edge(sum);
+        sum += segment.value as u64;
+        rects.push(format!(
+            "    <rect x=\"{}\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"#{}\"><title>{}: {}</title></rect>",
+            x,
+            edge(sum) - x,
+            height,
+            segment.color,
+            escape_text(&segment.label),
+            segment.value
+        ));
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n\
+  <defs>\n    <clipPath id=\"bar\"><rect width=\"{}\" height=\"{}\" rx=\"{}\"/></clipPath>\n  </defs>\n\
+  <rect width=\"{}\" height=\"{}\" fill=\"#{}\" rx=\"{}\"/>\n\
+  <g clip-path=\"url(#bar)\">\n{}\n  </g>\n\
+</svg>",
+        width,
+        height,
+        width,
+        height,
+        width,
+        height,
+        rx,
+        width,
+        height,
+        track_color,
+        rx,
+        rects.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(values: [u32; 3]) -> Vec<BarSegment> {
+        vec![
+            BarSegment::new("passed", values[0], "22C55E"),
+            BarSegment::new("failed", values[1], "EF4444"),
+            BarSegment::new("skipped", values[2], "EAB308"),
+        ]
+    }
+
+    #[test]
+    fn test_stacked_bar_proportions() {
+        let svg = render(&segments([6, 1, 3]), 200, 10, 3, "475569");
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"120\" height=\"10\" fill=\"#22C55E\"><title>passed: 6</title>"));
+        assert!(
+            svg.contains("<rect x=\"120\" y=\"0\" width=\"20\" height=\"10\" fill=\"#EF4444\">")
+        );
+        assert!(
+            svg.contains("<rect x=\"140\" y=\"0\" width=\"60\" height=\"10\" fill=\"#EAB308\">")
+        );
+    }
+
+    #[test]
+    fn test_stacked_bar_fills_width() {
+        // Thirds of 100px can't divide evenly; the last edge still lands on 100
+        let svg = render(&segments([1, 1, 1]), 100, 8, 0, "475569");
+        assert!(svg.contains("<rect x=\"34\" y=\"0\" width=\"33\""));
+        assert!(svg.contains("<rect x=\"67\" y=\"0\" width=\"33\""));
+    }
+
+    #[test]
+    fn test_stacked_bar_skips_empty_segments() {
+        let svg = render(&segments([5, 0, 0]), 200, 10, 3, "475569");
+        assert!(!svg.contains("failed"));
+        assert!(svg.contains("width=\"200\" height=\"10\" fill=\"#22C55E\""));
+
+        let empty = render(&segments([0, 0, 0]), 200, 10, 3, "475569");
+        assert!(!empty.contains("<title>"));
+        assert!(empty.contains("fill=\"#475569\" rx=\"3\""));
+    }
+}
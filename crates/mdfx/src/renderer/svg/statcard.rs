@@ -0,0 +1,187 @@
+//! Stat card SVG renderer (icon, big number, label, trend arrow)
+
+use crate::escape::escape_text;
+
+/// Inner padding of the card in pixels
+const PADDING: u32 = 12;
+
+/// Icon size in pixels
+const ICON_SIZE: u32 = 16;
+
+/// Built-in glyphs for common stats (24x24 viewBox); Simple Icons cover brands
+fn glyph_path(name: &str) -> Option<&'static str> {
+    match name {
+        "download" | "downloads" => Some("M5 20h14v-2H5v2zM19 9h-4V3H9v6H5l7 7 7-7z"),
+        "star" | "stars" => Some("M12 17.27L18.18 21l-1.64-7.03L22 9.24l-7.19-.61L12 2 9.19 8.63 2 9.24l5.46 4.73L5.82 21z"),
+        "heart" => Some("M12 21.35l-1.45-1.32C5.4 15.36 2 12.28 2 8.5 2 5.42 4.42 3 7.5 3c1.74 0 3.41.81 4.5 2.09C13.09 3.81 14.76 3 16.5 3 19.58 3 22 5.42 22 8.5c0 3.78-3.4 6.86-8.55 11.54L12 21.35z"),
+        "users" | "user" => Some("M16 11c1.66 0 3-1.34 3-3s-1.34-3-3-3-3 1.34-3 3 1.34 3 3 3zm-8 0c1.66 0 3-1.34 3-3S9.66 5 8 5 5 6.34 5 8s1.34 3 3 3zm0 2c-2.33 0-7 1.17-7 3.5V19h14v-2.5c0-2.33-4.67-3.5-7-3.5zm8 0c-.29 0-.62.02-.97.05 1.16.84 1.97 1.97 1.97 3.45V19h6v-2.5c0-2.33-4.67-3.5-7-3.5z"),
+        "eye" | "views" => Some("M12 4.5C7 4.5 2.73 7.61 1 12c1.73 4.39 6 7.5 11 7.5s9.27-3.11 11-7.5c-1.73-4.39-6-7.5-11-7.5zM12 17c-2.76 0-5-2.24-5-5s2.24-5 5-5 5 2.24 5 5-2.24 5-5 5zm0-8c-1.66 0-3 1.34-3 3s1.34 3 3 3 3-1.34 3-3-1.34-3-3-3z"),
+        "check" => Some("M9 16.17L4.83 12l-1.42 1.41L9 19 21 7l-1.41-1.41z"),
+        "clock" => Some("M12 2C6.5 2 2 6.5 2 12s4.5 10 10 10 10-4.5 10-10S17.5 2 12 2zm0 18c-4.41 0-8-3.59-8-8s3.59-8 8-8 8 3.59 8 8-3.59 8-8 8zm.5-13H11v6l5.2 3.2.8-1.3-4.5-2.7V7z"),
+        _ => None,
+    }
+}
+
+/// Split a trend into its arrow and the text shown after it
+///
+/// `+8%` becomes `("▲", "8%")`, `-3%` becomes `("▼", "3%")`; anything else
+/// is shown as-is without an arrow.
+fn trend_parts(trend: &str) -> (Option<&'static str>, &str) {
+    if let Some(rest) = trend.strip_prefix('+') {
+        (Some("▲"), rest)
+    } else if let Some(rest) = trend.strip_prefix('-') {
+        (Some("▼"), rest)
+    } else {
+        (None, trend)
+    }
+}
+
+/// Render a stat card
+#[allow(clippy::too_many_arguments)]
+pub fn render(
+    label: &str,
+    value: &str,
+    icon: Option<&str>,
+    trend: Option<&str>,
+    width: u32,
+    height: u32,
+    rx: u32,
+    bg_color: &str,
+    value_color: &str,
+    label_color: &str,
+    icon_color: &str,
+    trend_color: &str,
+) -> String {
+    let mut elements = vec![format!(
+        "  <rect width=\"{}\" height=\"{}\" rx=\"{}\" fill=\"#{}\"/>",
+        width, height, rx, bg_color
+    )];
+
+    // Icon and label share the top line
+    let icon_path = icon.and_then(|name| glyph_path(name).or_else(|| mdfx_icons::icon_path(name)));
+    let mut label_x = PADDING;
+    if let Some(path) = icon_path {
+        elements.push(format!(
+            "  <g transform=\"translate({}, {}) scale({:.3})\"><path fill=\"#{}\" d=\"{}\"/></g>",
+            PADDING,
+            PADDING,
+            ICON_SIZE as f32 / 24.0,
+            icon_color,
+            path
+        ));
+        label_x += ICON_SIZE + 6;
+    }
+    if !label.is_empty() {
+        elements.push(format!(
+            "  <text x=\"{}\" y=\"{}\" fill=\"#{}\" font-family=\"Arial, sans-serif\" font-size=\"12\">{}</text>",
+            label_x,
+            PADDING + 12,
+            label_color,
+            escape_text(label)
+        ));
+    }
+
+    // Big number and trend share the bottom line
+    let baseline = height.saturating_sub(PADDING);
+    let value_size = (height * 7 / 20).clamp(14, 40);
+    elements.push(format!(
+        "  <text x=\"{}\" y=\"{}\" fill=\"#{}\" font-family=\"Arial, sans-serif\" font-size=\"{}\" font-weight=\"bold\">{}</text>",
+        PADDING,
+        baseline,
+        value_color,
+        value_size,
+        escape_text(value)
+    ));
+    if let Some(trend) = trend.filter(|t| !t.is_empty()) {
+        let text = match trend_parts(trend) {
+            (Some(arrow), rest) => format!("{} {}", arrow, escape_text(rest)),
+            (None, rest) => escape_text(rest).into_owned(),
+        };
+        elements.push(format!(
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"end\" fill=\"#{}\" font-family=\"Arial, sans-serif\" font-size=\"12\" font-weight=\"bold\">{}</text>",
+            width.saturating_sub(PADDING),
+            baseline,
+            trend_color,
+            text
+        ));
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n{}\n</svg>",
+        width,
+        height,
+        width,
+        height,
+        elements.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn card(icon: Option<&str>, trend: Option<&str>) -> String {
+        render(
+            "Downloads",
+            "1.2M",
+            icon,
+            trend,
+            160,
+            80,
+            8,
+            "282F3C",
+            "FFFFFF",
+            "6B7280",
+            "F41C80",
+            "22C55E",
+        )
+    }
+
+    #[rstest]
+    #[case("+8%", Some("▲"), "8%")]
+    #[case("-3%", Some("▼"), "3%")]
+    #[case("0%", None, "0%")]
+    fn test_trend_parts(#[case] trend: &str, #[case] arrow: Option<&str>, #[case] rest: &str) {
+        assert_eq!(trend_parts(trend), (arrow, rest));
+    }
+
+    #[test]
+    fn test_statcard_layout() {
+        let svg = card(Some("download"), Some("+8%"));
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"160\" height=\"80\""));
+        assert!(svg.contains("<rect width=\"160\" height=\"80\" rx=\"8\" fill=\"#282F3C\"/>"));
+        assert!(svg.contains("<text x=\"34\" y=\"24\" fill=\"#6B7280\""));
+        assert!(svg.contains("font-size=\"28\" font-weight=\"bold\">1.2M</text>"));
+        assert!(svg.contains("text-anchor=\"end\" fill=\"#22C55E\""));
+        assert!(svg.contains(">▲ 8%</text>"));
+    }
+
+    #[rstest]
+    #[case(Some("download"), true)]
+    #[case(Some("rust"), true)]
+    #[case(Some("no-such-icon"), false)]
+    #[case(None, false)]
+    fn test_statcard_icon(#[case] icon: Option<&str>, #[case] drawn: bool) {
+        let svg = card(icon, None);
+        assert_eq!(svg.contains("<path fill=\"#F41C80\""), drawn);
+        let label_x = if drawn {
+            "x=\"34\""
+        } else {
+            "x=\"12\" y=\"24\""
+        };
+        assert!(svg.contains(label_x));
+    }
+
+    #[test]
+    fn test_statcard_escapes_text() {
+        let svg = render(
+            "<b>", "1 & 2", None, None, 160, 80, 8, "282F3C", "FFFFFF", "6B7280", "F41C80",
+            "22C55E",
+        );
+        assert!(svg.contains(">&lt;b&gt;</text>"));
+        assert!(svg.contains(">1 &amp; 2</text>"));
+        assert!(!svg.contains("text-anchor=\"end\""));
+    }
+}
@@ -0,0 +1,138 @@
+//! Steps SVG renderer (numbered markers joined by connecting lines)
+
+use crate::escape::escape_text;
+use crate::primitive::StepState;
+
+/// Room around the markers for the current-step ring
+const PADDING: u32 = 4;
+
+/// Gap between a marker and its label
+const LABEL_GAP: u32 = 18;
+
+/// Render a row of steps
+#[allow(clippy::too_many_arguments)]
+pub fn render(
+    items: &[String],
+    current: Option<usize>,
+    size: u32,
+    spacing: u32,
+    color: &str,
+    pending_color: &str,
+    text_color: &str,
+    label_color: &str,
+) -> String {
+    let column = spacing.max(size + PADDING * 2);
+    let width = column * items.len().max(1) as u32;
+    let height = PADDING + size + LABEL_GAP + 6;
+    let r = size / 2;
+    let cy = PADDING + r;
+    let cx = |i: usize| column / 2 + i as u32 * column;
+    let state_color = |state| match state {
+        StepState::Pending => pending_color,
+        _ => color,
+    };
+
+    // Lines first, so the markers sit on top of them
+    let mut elements: Vec<String> = (1..items.len())
+        .map(|i| {
+            format!(
+                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#{}\" stroke-width=\"2\"/>",
+                cx(i - 1) + r,
+                cy,
+                cx(i) - r,
+                cy,
+                state_color(StepState::of(i, current))
+            )
+        })
+        .collect();
+
+    let font_size = (size / 2).max(8);
+    for (i, label) in items.iter().enumerate() {
+        let state = StepState::of(i, current);
+        if state == StepState::Current {
+            elements.push(format!(
+                "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"#{}\" stroke-width=\"2\"/>",
+                cx(i),
+                cy,
+                r + 3,
+                color
+            ));
+        }
+        elements.push(format!(
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"#{}\"/>",
+            cx(i),
+            cy,
+            r,
+            state_color(state)
+        ));
+        elements.push(format!(
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#{}\" font-family=\"Arial, sans-serif\" font-size=\"{}\" font-weight=\"bold\">{}</text>",
+            cx(i),
+            cy + font_size * 7 / 20,
+            text_color,
+            font_size,
+            i + 1
+        ));
+        elements.push(format!(
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#{}\" font-family=\"Arial, sans-serif\" font-size=\"12\">{}</text>",
+            cx(i),
+            PADDING + size + LABEL_GAP,
+            label_color,
+            escape_text(label)
+        ));
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n{}\n</svg>",
+        width,
+        height,
+        width,
+        height,
+        elements.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(current: Option<usize>) -> String {
+        let items = ["Install", "Configure", "Ship"].map(String::from);
+        render(
+            &items, current, 28, 120, "F41C80", "475569", "FFFFFF", "6B7280",
+        )
+    }
+
+    #[test]
+    fn test_steps_layout() {
+        let svg = steps(None);
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"360\" height=\"56\""));
+        // Markers centered in 120px columns, lines between their edges
+        assert!(svg.contains("<circle cx=\"60\" cy=\"18\" r=\"14\" fill=\"#F41C80\"/>"));
+        assert!(svg.contains("<line x1=\"74\" y1=\"18\" x2=\"166\" y2=\"18\""));
+        assert!(svg.contains(">3</text>"));
+        assert!(svg.contains(">Configure</text>"));
+        assert!(!svg.contains("fill=\"none\""));
+    }
+
+    #[test]
+    fn test_steps_current() {
+        let svg = steps(Some(2));
+        assert!(
+            svg.contains("<circle cx=\"180\" cy=\"18\" r=\"17\" fill=\"none\" stroke=\"#F41C80\"")
+        );
+        assert!(svg.contains("<circle cx=\"300\" cy=\"18\" r=\"14\" fill=\"#475569\"/>"));
+        // Line into the current step is done, line into the pending one is not
+        assert!(svg.contains("x2=\"166\" y2=\"18\" stroke=\"#F41C80\""));
+        assert!(svg.contains("x2=\"286\" y2=\"18\" stroke=\"#475569\""));
+    }
+
+    #[test]
+    fn test_steps_column_fits_marker() {
+        let items = ["a".to_string(), "<b>".to_string()];
+        let svg = render(&items, None, 64, 20, "F41C80", "475569", "FFFFFF", "6B7280");
+        assert!(svg.contains("width=\"144\""));
+        assert!(svg.contains(">&lt;b&gt;</text>"));
+    }
+}
@@ -1,5 +1,7 @@
 //! Swatch SVG renderer - colored rectangles with optional enhancements
 
+use crate::escape::{escape_attr, escape_text};
+
 /// SVG style metrics for different badge styles
 pub struct SvgMetrics {
     pub height: u32,
@@ -177,7 +179,11 @@ pub fn render(opts: SwatchOptions) -> String {
         let x_pos = width / 2 + border_offset + shadow_padding / 2;
         format!(
             "\n  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"{}\" font-family=\"Verdana,DejaVu Sans,sans-serif\" font-size=\"{}\" font-weight=\"600\">{}</text>",
-            x_pos, y_pos, label_color_formatted, font_size, text
+            x_pos,
+            y_pos,
+            escape_attr(&label_color_formatted),
+            font_size,
+            escape_text(text)
         )
     } else {
         String::new()
@@ -255,7 +261,13 @@ pub fn render(opts: SwatchOptions) -> String {
 
     // Build icon element if icon is specified
     let icon_elem = if let Some(icon_name) = opts.icon {
-        if let Some(path_data) = mdfx_icons::icon_path(icon_name) {
+        // Remote icons arrive as sanitized 24x24 markup rather than a name
+        let icon = if icon_name.starts_with('<') {
+            Some(icon_name)
+        } else {
+            mdfx_icons::icon_path(icon_name)
+        };
+        if let Some(icon) = icon {
             // Simple Icons use a 24x24 viewBox
             // Scale icon to fit within swatch (with padding)
             let icon_size = (height.min(width) as f32 * 0.6).max(10.0);
@@ -269,8 +281,11 @@ pub fn render(opts: SwatchOptions) -> String {
             let color = opts.icon_color.unwrap_or("FFFFFF");
 
             format!(
-                "\n  <g transform=\"translate({:.1}, {:.1}) scale({:.3})\"><path fill=\"#{}\" d=\"{}\"/></g>",
-                icon_x, icon_y, scale, color, path_data
+                "\n  <g transform=\"translate({:.1}, {:.1}) scale({:.3})\">{}</g>",
+                icon_x,
+                icon_y,
+                scale,
+                badgefx::render::icon_element(icon, color)
             )
         } else {
             String::new()
@@ -1,6 +1,14 @@
 //! Shared SVG rendering utilities
 
 use crate::primitive::ThumbConfig;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::collections::HashSet;
+
+lazy_static! {
+    static ref SVG_ID: Regex = Regex::new(r#"\sid=["']([^"']+)["']"#).unwrap();
+    static ref ID_REF: Regex = Regex::new(r#"(\sid=["']|url\(#|href=["']#)([^"')]+)"#).unwrap();
+}
 
 /// Build stroke attribute string for SVG elements.
 ///
@@ -53,11 +61,71 @@ pub fn thumb_padding(thumb: Option<&ThumbConfig>, thickness: u32) -> u32 {
         .unwrap_or(0)
 }
 
+/// Prefix element ids, and references to them, with `prefix`
+///
+/// Covers `id=`, `url(#id)` and `href="#id"` (including `xlink:href`).
+/// References to ids the document doesn't define are left alone.
+pub fn namespace_ids(svg: &str, prefix: &str) -> String {
+    let ids: HashSet<&str> = SVG_ID
+        .captures_iter(svg)
+        .filter_map(|caps| caps.get(1).map(|m| m.as_str()))
+        .collect();
+    if ids.is_empty() {
+        return svg.to_string();
+    }
+    ID_REF
+        .replace_all(svg, |caps: &Captures| {
+            if ids.contains(&caps[2]) {
+                format!("{}{}-{}", &caps[1], prefix, &caps[2])
+            } else {
+                caps[0].to_string()
+            }
+        })
+        .into_owned()
+}
+
+/// Prefix element ids with one derived from the document's content
+///
+/// Generated SVGs use fixed ids such as `grad` or `clip-0`. When several are
+/// inlined into one HTML page, ids are shared by the whole page, so one
+/// badge's gradient would fill another's shapes. The prefix (`m` and eight
+/// hex digits) is the same for the same document, keeping output
+/// deterministic, and differs between documents whose definitions differ.
+pub fn scope_ids(svg: &str) -> String {
+    let hash = xxhash_rust::xxh3::xxh3_64(svg.as_bytes());
+    namespace_ids(svg, &format!("m{:08x}", hash >> 32))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rstest::rstest;
 
+    #[test]
+    fn test_namespace_ids() {
+        let svg = r##"<svg><defs><linearGradient id="g"/><clipPath id='c'/></defs><rect fill="url(#g)" clip-path="url(#c)"/><use xlink:href="#g"/><use href="#other"/></svg>"##;
+        assert_eq!(
+            namespace_ids(svg, "p"),
+            r##"<svg><defs><linearGradient id="p-g"/><clipPath id='p-c'/></defs><rect fill="url(#p-g)" clip-path="url(#p-c)"/><use xlink:href="#p-g"/><use href="#other"/></svg>"##
+        );
+        assert_eq!(
+            namespace_ids("<svg><rect/></svg>", "p"),
+            "<svg><rect/></svg>"
+        );
+    }
+
+    #[test]
+    fn test_scope_ids_is_content_derived() {
+        let a = r##"<svg><linearGradient id="grad"/><rect fill="url(#grad)" width="1"/></svg>"##;
+        let b = r##"<svg><linearGradient id="grad"/><rect fill="url(#grad)" width="2"/></svg>"##;
+        assert_eq!(scope_ids(a), scope_ids(a));
+        assert_ne!(scope_ids(a), scope_ids(b));
+        let scoped = scope_ids(a);
+        let id = &scoped[scoped.find("id=\"").unwrap() + 4..scoped.find("-grad").unwrap()];
+        assert_eq!(id.len(), 9, "{}", scoped);
+        assert!(scoped.contains(&format!("url(#{}-grad)", id)));
+    }
+
     #[rstest]
     #[case(Some("FF0000"), 2, " stroke=\"#FF0000\" stroke-width=\"2\"")]
     #[case(None, 2, "")]
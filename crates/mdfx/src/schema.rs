@@ -0,0 +1,467 @@
+//! Component schema export
+//!
+//! Describes every component the parser accepts - its positional arguments,
+//! optional parameters, their types, defaults, and allowed values - for
+//! external tooling such as editor plugins and the LSP. Parameters come from
+//! the same [`ParamSchema`] check mode validates against, so the export
+//! stays in sync with the registry and the component handlers.
+//!
+//! Two formats are supported:
+//!
+//! - [`SchemaFormat::JsonSchema`] - a JSON Schema (draft 2020-12) for a
+//!   component call written as `{"component": ..., "args": [...], "params": {...}}`
+//! - [`SchemaFormat::Catalog`] - a plain JSON catalog keyed by component name
+
+use crate::components::params;
+use crate::components::{ParamSchema, ParamSpec, ParamType};
+use crate::registry::{Component, Registry};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// JSON Schema dialect of the generated schema
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Output format for [`generate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaFormat {
+    /// JSON Schema for component calls
+    JsonSchema,
+    /// Plain JSON catalog of components
+    Catalog,
+}
+
+impl SchemaFormat {
+    /// Parse a format name (json-schema, catalog)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "json-schema" | "jsonschema" => Some(SchemaFormat::JsonSchema),
+            "catalog" => Some(SchemaFormat::Catalog),
+            _ => None,
+        }
+    }
+}
+
+/// Generate a schema in the given format
+pub fn generate(registry: &Registry, format: SchemaFormat) -> String {
+    let schema = match format {
+        SchemaFormat::JsonSchema => json_schema(registry),
+        SchemaFormat::Catalog => catalog(registry),
+    };
+
+    let mut output = serde_json::to_string_pretty(&schema).unwrap_or_default();
+    output.push('\n');
+    output
+}
+
+/// Sorted names of a registry map
+fn sorted_keys<V>(map: &std::collections::HashMap<String, V>) -> Vec<&str> {
+    let mut keys: Vec<&str> = map.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    keys
+}
+
+/// Parameter schema of a registry component
+fn param_schema_of(registry: &Registry, name: &str) -> ParamSchema {
+    ParamSchema::new(registry, name).expect("component is in the registry")
+}
+
+/// Live sources with their metrics, keyed by source id
+fn live_sources() -> Value {
+    let sources: Map<String, Value> = params::LIVE_SOURCES
+        .iter()
+        .map(|(id, description, metrics)| {
+            let metrics: Map<String, Value> = metrics
+                .iter()
+                .map(|(metric, description)| (metric.to_string(), json!(description)))
+                .collect();
+            (
+                id.to_string(),
+                json!({
+                    "description": description,
+                    "default_metric": params::default_metric(id),
+                    "metrics": metrics,
+                }),
+            )
+        })
+        .collect();
+    Value::Object(sources)
+}
+
+// ============================================================================
+// Catalog
+// ============================================================================
+
+/// Generate the plain JSON catalog
+pub fn catalog(registry: &Registry) -> Value {
+    let mut components = Map::new();
+    for name in sorted_keys(registry.components()) {
+        let component = &registry.components()[name];
+        let params: Map<String, Value> = param_schema_of(registry, name)
+            .iter()
+            .map(|param| {
+                let mut entry = json!({ "type": param.param_type.as_str() });
+                if let Some(ref default) = param.default {
+                    entry["default"] = json!(default);
+                }
+                if let Some(ref description) = param.description {
+                    entry["description"] = json!(description);
+                }
+                if !param.values.is_empty() {
+                    entry["values"] = json!(param.values);
+                }
+                if !param.suggestions.is_empty() {
+                    entry["suggestions"] = json!(param.suggestions);
+                }
+                if !param.aliases.is_empty() {
+                    entry["aliases"] = json!(param.aliases);
+                }
+                if let Some(example) = param.example {
+                    entry["example"] = json!(example);
+                }
+                (param.name.clone(), entry)
+            })
+            .collect();
+
+        let mut entry = json!({
+            "type": component.component_type,
+            "self_closing": component.self_closing,
+            "contexts": component.contexts,
+            "args": component.args,
+            "params": params,
+        });
+        if let Some(ref description) = component.description {
+            entry["description"] = json!(description);
+        }
+        if name == "live" {
+            entry["sources"] = live_sources();
+        }
+        components.insert(name.to_string(), entry);
+    }
+
+    let palette: BTreeMap<&String, &String> = registry.palette().iter().collect();
+    json!({
+        "version": registry.version(),
+        "components": components,
+        "shield_styles": sorted_keys(registry.shield_styles()),
+        "palette": palette,
+    })
+}
+
+// ============================================================================
+// JSON Schema
+// ============================================================================
+
+/// Generate the JSON Schema
+///
+/// Each component is a `$defs` entry named after its template (`ui:swatch`);
+/// the root schema accepts any one of them.
+pub fn json_schema(registry: &Registry) -> Value {
+    let mut defs = Map::new();
+    let mut refs = Vec::new();
+
+    let palette = sorted_keys(registry.palette());
+    defs.insert(
+        "color".to_string(),
+        json!({
+            "type": "string",
+            "description": "Palette color name or hex value without '#'",
+            "examples": palette,
+        }),
+    );
+
+    for name in sorted_keys(registry.components()) {
+        let component = &registry.components()[name];
+        let key = format!("ui:{}", name);
+        refs.push(json!({ "$ref": format!("#/$defs/{}", key) }));
+        defs.insert(key, component_schema(registry, name, component));
+    }
+
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": "mdfx components",
+        "description": format!(
+            "A {{{{ui:NAME:ARGS:PARAMS/}}}} component call (registry {})",
+            registry.version()
+        ),
+        "oneOf": refs,
+        "$defs": defs,
+    })
+}
+
+/// Schema of one component call
+fn component_schema(registry: &Registry, name: &str, component: &Component) -> Value {
+    let params = param_schema_of(registry, name);
+
+    let args: Vec<Value> = component
+        .args
+        .iter()
+        .map(|arg| {
+            let mut schema = params
+                .get(arg)
+                .map(|param| param_schema(registry, param))
+                .unwrap_or_else(|| json!({ "type": "string" }));
+            schema["title"] = json!(arg);
+            schema
+        })
+        .collect();
+
+    let mut properties = Map::new();
+    for param in params.iter() {
+        let schema = param_schema(registry, param);
+        for alias in &param.aliases {
+            let mut alias_schema = schema.clone();
+            alias_schema["description"] = json!(format!("Alias for {}", param.name));
+            properties.insert(alias.clone(), alias_schema);
+        }
+        properties.insert(param.name.clone(), schema);
+    }
+
+    let mut schema = json!({
+        "type": "object",
+        "properties": {
+            "component": { "const": name },
+            "args": { "type": "array", "prefixItems": args },
+            "params": { "type": "object", "properties": properties },
+        },
+        "required": ["component"],
+    });
+    if let Some(ref description) = component.description {
+        schema["description"] = json!(description);
+    }
+    if !component.self_closing {
+        schema["properties"]["content"] = json!({ "type": "string" });
+    }
+    if name == "live" {
+        schema["allOf"] = live_metric_rules();
+    }
+    schema
+}
+
+/// Metric constraints of the live component, one rule per source
+///
+/// The source is the first positional argument and the metric the third, or
+/// the `metric` parameter.
+fn live_metric_rules() -> Value {
+    let rules: Vec<Value> = params::LIVE_SOURCES
+        .iter()
+        .map(|(id, _, metrics)| {
+            let metric = json!({
+                "enum": metrics.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+                "default": params::default_metric(id),
+            });
+            json!({
+                "if": {
+                    "properties": { "args": { "prefixItems": [{ "const": id }] } },
+                },
+                "then": {
+                    "properties": {
+                        "args": { "prefixItems": [true, true, metric] },
+                        "params": { "properties": { "metric": metric } },
+                    },
+                },
+            })
+        })
+        .collect();
+    json!(rules)
+}
+
+/// Schema of one parameter value
+fn param_schema(registry: &Registry, param: &ParamSpec) -> Value {
+    let default = param.default.as_deref();
+    let mut schema = match param.param_type {
+        ParamType::Number => match default.map(|d| (d, d.parse::<serde_json::Number>())) {
+            Some((_, Ok(n))) => json!({ "type": "number", "default": n }),
+            // A keyword default like "auto" is accepted as written
+            Some((d, Err(_))) if !d.contains(' ') => {
+                json!({ "anyOf": [{ "type": "number" }, { "const": d }] })
+            }
+            _ => json!({ "type": "number" }),
+        },
+        ParamType::Boolean => {
+            let mut schema = json!({ "type": "boolean" });
+            if let Some(b) = default.and_then(|d| d.parse::<bool>().ok()) {
+                schema["default"] = json!(b);
+            }
+            schema
+        }
+        ParamType::Color => {
+            let mut schema = json!({ "$ref": "#/$defs/color" });
+            // Defaults like "auto" or "same as fill" aren't values
+            if let Some(d) = default.filter(|d| is_color(registry, d)) {
+                schema["default"] = json!(d);
+            }
+            schema
+        }
+        _ if !param.values.is_empty() => {
+            let mut schema = json!({ "type": "string", "enum": param.values });
+            if let Some(d) = default.filter(|d| param.values.iter().any(|v| v == d)) {
+                schema["default"] = json!(d);
+            }
+            schema
+        }
+        _ => {
+            let mut schema = json!({ "type": "string" });
+            if let Some(d) = default.filter(|d| !matches!(*d, "none" | "auto")) {
+                schema["default"] = json!(d);
+            }
+            schema
+        }
+    };
+
+    if let Some(ref description) = param.description {
+        schema["description"] = json!(description);
+    }
+    // Examples are values, not the `key=value` form handler metadata uses
+    let mut examples: Vec<&str> = param
+        .example
+        .and_then(|e| e.split_once('='))
+        .map(|(_, value)| value)
+        .into_iter()
+        .collect();
+    for suggestion in &param.suggestions {
+        if !examples.contains(&suggestion.as_str()) {
+            examples.push(suggestion);
+        }
+    }
+    if !examples.is_empty() {
+        schema["examples"] = json!(examples);
+    }
+    schema
+}
+
+/// Whether a registry default is a literal color
+fn is_color(registry: &Registry, value: &str) -> bool {
+    registry.resolve_color(value).is_some()
+        || (value.len() == 6 && value.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn registry() -> Registry {
+        Registry::new().unwrap()
+    }
+
+    fn parsed(format: SchemaFormat) -> Value {
+        serde_json::from_str(&generate(&registry(), format)).unwrap()
+    }
+
+    #[rstest]
+    #[case("json-schema", Some(SchemaFormat::JsonSchema))]
+    #[case("catalog", Some(SchemaFormat::Catalog))]
+    #[case("yaml", None)]
+    fn test_format_parse(#[case] input: &str, #[case] expected: Option<SchemaFormat>) {
+        assert_eq!(SchemaFormat::parse(input), expected);
+    }
+
+    #[test]
+    fn test_every_component_exported() {
+        let catalog = parsed(SchemaFormat::Catalog);
+        let schema = parsed(SchemaFormat::JsonSchema);
+        let components = registry();
+
+        assert_eq!(
+            catalog["components"].as_object().unwrap().len(),
+            components.components().len()
+        );
+        assert_eq!(
+            schema["oneOf"].as_array().unwrap().len(),
+            components.components().len()
+        );
+        for name in components.components().keys() {
+            assert!(catalog["components"].get(name).is_some(), "{}", name);
+            let def = &schema["$defs"][format!("ui:{}", name)];
+            assert_eq!(def["properties"]["component"]["const"], json!(name));
+        }
+    }
+
+    #[rstest]
+    #[case("progress", "width", json!({ "type": "number", "default": 100 }))]
+    #[case("donut", "label", json!({ "type": "boolean", "default": false }))]
+    #[case("row", "align", json!({ "type": "string", "enum": ["left", "center", "right"], "default": "center" }))]
+    #[case("swatch", "style", json!({ "type": "string", "default": "flat-square" }))]
+    #[case("progress", "fill", json!({ "$ref": "#/$defs/color", "default": "pink" }))]
+    fn test_param_schema(#[case] component: &str, #[case] param: &str, #[case] expected: Value) {
+        let schema = parsed(SchemaFormat::JsonSchema);
+        let actual = &schema["$defs"][format!("ui:{}", component)]["properties"]["params"]
+            ["properties"][param];
+        for (key, value) in expected.as_object().unwrap() {
+            assert_eq!(&actual[key], value, "{}.{}.{}", component, param, key);
+        }
+    }
+
+    #[test]
+    fn test_placeholder_defaults_omitted() {
+        let schema = parsed(SchemaFormat::JsonSchema);
+        let params = &schema["$defs"]["ui:progress"]["properties"]["params"]["properties"];
+        assert!(params["thumb"].get("default").is_none());
+        assert!(params["thumb_color"].get("default").is_none());
+
+        // The catalog keeps them as written
+        let catalog = parsed(SchemaFormat::Catalog);
+        assert_eq!(
+            catalog["components"]["progress"]["params"]["thumb"]["default"],
+            "none"
+        );
+    }
+
+    #[test]
+    fn test_shield_styles_enumerated() {
+        let catalog = parsed(SchemaFormat::Catalog);
+        let values = catalog["components"]["swatch"]["params"]["style"]["values"]
+            .as_array()
+            .unwrap();
+        // Every shield style, plus its aliases
+        for style in catalog["shield_styles"].as_array().unwrap() {
+            assert!(values.contains(style), "{}", style);
+        }
+        assert!(values.contains(&json!("square")));
+    }
+
+    #[test]
+    fn test_handler_params_merged() {
+        let catalog = parsed(SchemaFormat::Catalog);
+        let tech = &catalog["components"]["tech"]["params"];
+        assert_eq!(tech["bg_left"]["example"], "bg_left=DEA584");
+        assert_eq!(tech["bg_left"]["type"], "string");
+        assert!(tech["bg_left"].get("default").is_none());
+    }
+
+    #[test]
+    fn test_param_aliases() {
+        let catalog = parsed(SchemaFormat::Catalog);
+        let progress = &catalog["components"]["progress"]["params"];
+        assert_eq!(progress["track"]["aliases"], json!(["color"]));
+        assert!(progress.get("color").is_none());
+
+        let schema = parsed(SchemaFormat::JsonSchema);
+        let params = &schema["$defs"]["ui:progress"]["properties"]["params"]["properties"];
+        assert_eq!(params["color"]["$ref"], params["track"]["$ref"]);
+        assert_eq!(params["color"]["description"], "Alias for track");
+    }
+
+    #[test]
+    fn test_live_sources() {
+        let catalog = parsed(SchemaFormat::Catalog);
+        let live = &catalog["components"]["live"];
+        let sources = live["params"]["source"]["values"].as_array().unwrap();
+        assert_eq!(sources.len(), params::LIVE_SOURCES.len());
+        assert!(sources.contains(&json!("readthedocs")));
+        assert_eq!(live["sources"]["actions"]["default_metric"], "conclusion");
+
+        let schema = parsed(SchemaFormat::JsonSchema);
+        let rules = schema["$defs"]["ui:live"]["allOf"].as_array().unwrap();
+        assert_eq!(rules.len(), params::LIVE_SOURCES.len());
+        let github = rules
+            .iter()
+            .find(|r| r["if"]["properties"]["args"]["prefixItems"][0]["const"] == "github")
+            .unwrap();
+        let metrics = &github["then"]["properties"]["params"]["properties"]["metric"];
+        assert!(metrics["enum"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("stars")));
+    }
+}
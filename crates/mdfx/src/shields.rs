@@ -1,4 +1,5 @@
 use crate::error::{Error, Result};
+use crate::registry::Registry;
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -24,25 +25,32 @@ pub struct ShieldStyle {
     pub aliases: Vec<String>,
 }
 
-/// Intermediate structure to parse registry.json for shields
-#[derive(Debug, Deserialize)]
-struct RegistryShieldsExtract {
-    palette: HashMap<String, String>,
-    shield_styles: HashMap<String, ShieldStyle>,
-}
-
 impl ShieldsRenderer {
     /// Create a new shields renderer by loading from registry.json
     pub fn new() -> Result<Self> {
-        let data = include_str!("../data/registry.json");
-        let registry: RegistryShieldsExtract = serde_json::from_str(data).map_err(|e| {
-            Error::ParseError(format!("Failed to parse registry.json for shields: {}", e))
-        })?;
-
-        Ok(ShieldsRenderer {
-            palette: registry.palette,
-            styles: registry.shield_styles,
-        })
+        Ok(Self::with_registry(&*Registry::shared()?))
+    }
+
+    /// Create a shields renderer from an existing registry
+    pub fn with_registry(registry: &Registry) -> Self {
+        let styles = registry
+            .shield_styles()
+            .iter()
+            .map(|(id, style)| {
+                let style = ShieldStyle {
+                    id: style.id.clone(),
+                    name: style.name.clone(),
+                    description: style.description.clone().unwrap_or_default(),
+                    aliases: style.aliases.clone(),
+                };
+                (id.clone(), style)
+            })
+            .collect();
+
+        ShieldsRenderer {
+            palette: registry.palette().clone(),
+            styles,
+        }
     }
 
     /// Render a single color block
@@ -86,6 +94,7 @@ impl ShieldsRenderer {
 
         // URL-encode the label for safe inclusion in URL
         let encoded_label = label
+            .replace('%', "%25")
             .replace(' ', "%20")
             .replace('-', "--")
             .replace('_', "__");
@@ -591,6 +600,15 @@ mod tests {
         assert!(result.contains("test--value__here"));
     }
 
+    #[test]
+    fn test_render_labeled_block_percent() {
+        let renderer = ShieldsRenderer::new().unwrap();
+        let result = renderer
+            .render_labeled_block("22C55E", "coverage: 92%", "flat-square")
+            .unwrap();
+        assert!(result.contains("/badge/-coverage:%2092%25-22C55E?"));
+    }
+
     // ========================================================================
     // Error Cases (Parameterized)
     // ========================================================================
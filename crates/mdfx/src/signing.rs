@@ -0,0 +1,361 @@
+//! Signing `manifest.json`
+//!
+//! The manifest records a SHA-256 hash for every generated asset, so a
+//! signed manifest lets downstream consumers check that neither the
+//! manifest nor the assets were changed after mdfx wrote them. Keys and
+//! signatures use the [minisign](https://jedisct1.github.io/minisign/)
+//! format: `manifest.json.minisig` can also be checked with
+//! `minisign -Vm manifest.json -p mdfx.pub`.
+//!
+//! Secret keys are stored unencrypted (like `minisign -G -W`); keep them in
+//! a CI secret rather than the repository.
+//!
+//! ```
+//! use mdfx::signing::{PublicKey, SecretKey};
+//!
+//! let key = SecretKey::generate().unwrap();
+//! let signature = key.sign(b"{\"assets\": []}", "file:manifest.json");
+//!
+//! let public = PublicKey::parse(&key.public_key().to_minisign()).unwrap();
+//! assert_eq!(
+//!     public.verify(b"{\"assets\": []}", &signature).unwrap(),
+//!     "file:manifest.json"
+//! );
+//! assert!(public.verify(b"{\"assets\": [1]}", &signature).is_err());
+//! ```
+
+use crate::error::{Error, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Blake2b512, Digest};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+/// Filename of the detached signature, next to `manifest.json`
+pub const SIGNATURE_FILE: &str = "manifest.json.minisig";
+
+/// Ed25519 key algorithm
+const KEY_ALG: &[u8; 2] = b"Ed";
+/// Signature over the BLAKE2b-512 hash of the file
+const PREHASHED_ALG: &[u8; 2] = b"ED";
+/// BLAKE2b-256 secret key checksum
+const CHECKSUM_ALG: &[u8; 2] = b"B2";
+
+const UNTRUSTED_PREFIX: &str = "untrusted comment:";
+const TRUSTED_PREFIX: &str = "trusted comment: ";
+
+/// Key for signing manifests
+pub struct SecretKey {
+    key_id: [u8; 8],
+    key: SigningKey,
+}
+
+/// Key for verifying manifest signatures
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    key_id: [u8; 8],
+    key: VerifyingKey,
+}
+
+impl SecretKey {
+    /// Generate a new random key pair
+    pub fn generate() -> Result<Self> {
+        let mut seed = [0u8; 32];
+        let mut key_id = [0u8; 8];
+        getrandom::getrandom(&mut seed)
+            .and_then(|_| getrandom::getrandom(&mut key_id))
+            .map_err(|e| {
+                Error::IoError(std::io::Error::other(format!(
+                    "No randomness for key generation: {}",
+                    e
+                )))
+            })?;
+        Ok(Self {
+            key_id,
+            key: SigningKey::from_bytes(&seed),
+        })
+    }
+
+    /// Parse an unencrypted minisign secret key file
+    pub fn parse(text: &str) -> Result<Self> {
+        let bytes = decode_key_line(text, "secret key")?;
+        // alg(2) kdf(2) checksum alg(2) salt(32) opslimit(8) memlimit(8)
+        // key id(8) secret key(64) checksum(32)
+        if bytes.len() != 158 || &bytes[0..2] != KEY_ALG || &bytes[4..6] != CHECKSUM_ALG {
+            return Err(invalid_key("secret key", "not a minisign Ed25519 key"));
+        }
+        if bytes[2..4] != [0, 0] {
+            return Err(invalid_key(
+                "secret key",
+                "the key is password-protected; create one with `minisign -G -W` or `mdfx assets keygen`",
+            ));
+        }
+
+        let key_id: [u8; 8] = bytes[54..62].try_into().expect("8 bytes");
+        let secret: [u8; 64] = bytes[62..126].try_into().expect("64 bytes");
+        if bytes[126..158] != checksum(&key_id, &secret) {
+            return Err(invalid_key("secret key", "checksum mismatch"));
+        }
+        let key = SigningKey::from_keypair_bytes(&secret)
+            .map_err(|_| invalid_key("secret key", "public half doesn't match"))?;
+
+        Ok(Self { key_id, key })
+    }
+
+    /// The key in minisign's secret key file format
+    pub fn to_minisign(&self) -> String {
+        let secret = self.key.to_keypair_bytes();
+        let mut bytes = Vec::with_capacity(158);
+        bytes.extend_from_slice(KEY_ALG);
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(CHECKSUM_ALG);
+        bytes.extend_from_slice(&[0; 48]);
+        bytes.extend_from_slice(&self.key_id);
+        bytes.extend_from_slice(&secret);
+        bytes.extend_from_slice(&checksum(&self.key_id, &secret));
+        format!(
+            "{} mdfx secret key {}\n{}\n",
+            UNTRUSTED_PREFIX,
+            key_id_hex(&self.key_id),
+            STANDARD.encode(bytes)
+        )
+    }
+
+    /// The matching public key
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            key_id: self.key_id,
+            key: self.key.verifying_key(),
+        }
+    }
+
+    /// Sign `data`, returning the contents of a `.minisig` file
+    ///
+    /// `trusted_comment` is signed too; use it for provenance such as the
+    /// file name and the mdfx version. It must fit on one line.
+    pub fn sign(&self, data: &[u8], trusted_comment: &str) -> String {
+        let trusted_comment = trusted_comment.replace(['\r', '\n'], " ");
+        let signature = self.key.sign(&Blake2b512::digest(data)).to_bytes();
+
+        let mut line = Vec::with_capacity(74);
+        line.extend_from_slice(PREHASHED_ALG);
+        line.extend_from_slice(&self.key_id);
+        line.extend_from_slice(&signature);
+
+        let mut global = signature.to_vec();
+        global.extend_from_slice(trusted_comment.as_bytes());
+        let global = self.key.sign(&global).to_bytes();
+
+        format!(
+            "{} signature from mdfx secret key {}\n{}\n{}{}\n{}\n",
+            UNTRUSTED_PREFIX,
+            key_id_hex(&self.key_id),
+            STANDARD.encode(line),
+            TRUSTED_PREFIX,
+            trusted_comment,
+            STANDARD.encode(global)
+        )
+    }
+}
+
+impl std::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretKey")
+            .field("key_id", &key_id_hex(&self.key_id))
+            .finish_non_exhaustive()
+    }
+}
+
+impl PublicKey {
+    /// Parse a minisign public key file, or just its base64 line
+    pub fn parse(text: &str) -> Result<Self> {
+        let bytes = decode_key_line(text, "public key")?;
+        if bytes.len() != 42 || &bytes[0..2] != KEY_ALG {
+            return Err(invalid_key("public key", "not a minisign Ed25519 key"));
+        }
+        let key_id: [u8; 8] = bytes[2..10].try_into().expect("8 bytes");
+        let key = VerifyingKey::from_bytes(&bytes[10..42].try_into().expect("32 bytes"))
+            .map_err(|_| invalid_key("public key", "not a valid Ed25519 point"))?;
+        Ok(Self { key_id, key })
+    }
+
+    /// The key in minisign's public key file format
+    pub fn to_minisign(&self) -> String {
+        let mut bytes = Vec::with_capacity(42);
+        bytes.extend_from_slice(KEY_ALG);
+        bytes.extend_from_slice(&self.key_id);
+        bytes.extend_from_slice(self.key.as_bytes());
+        format!(
+            "{} minisign public key {}\n{}\n",
+            UNTRUSTED_PREFIX,
+            key_id_hex(&self.key_id),
+            STANDARD.encode(bytes)
+        )
+    }
+
+    /// Key ID as minisign prints it
+    pub fn key_id(&self) -> String {
+        key_id_hex(&self.key_id)
+    }
+
+    /// Check a `.minisig` signature of `data`, returning its trusted comment
+    pub fn verify(&self, data: &[u8], signature: &str) -> Result<String> {
+        let mut lines = signature
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty() && !line.starts_with(UNTRUSTED_PREFIX));
+        let (Some(sig_line), Some(comment_line), Some(global_line)) =
+            (lines.next(), lines.next(), lines.next())
+        else {
+            return Err(Error::SignatureInvalid("truncated signature file".into()));
+        };
+        let trusted_comment = comment_line
+            .strip_prefix(TRUSTED_PREFIX)
+            .ok_or_else(|| Error::SignatureInvalid("missing trusted comment".into()))?;
+
+        let sig_bytes = decode_base64(sig_line)?;
+        if sig_bytes.len() != 74 {
+            return Err(Error::SignatureInvalid("malformed signature".into()));
+        }
+        if sig_bytes[2..10] != self.key_id {
+            return Err(Error::SignatureInvalid(format!(
+                "signed by key {}, expected {}",
+                key_id_hex(sig_bytes[2..10].try_into().expect("8 bytes")),
+                self.key_id()
+            )));
+        }
+        let signature = Signature::from_bytes(&sig_bytes[10..74].try_into().expect("64 bytes"));
+        let signed = match &sig_bytes[0..2] {
+            alg if alg == PREHASHED_ALG => self.key.verify(&Blake2b512::digest(data), &signature),
+            alg if alg == KEY_ALG => self.key.verify(data, &signature),
+            _ => {
+                return Err(Error::SignatureInvalid(
+                    "unknown signature algorithm".into(),
+                ))
+            }
+        };
+        signed
+            .map_err(|_| Error::SignatureInvalid("content doesn't match the signature".into()))?;
+
+        let global = decode_base64(global_line)?;
+        let global = Signature::from_slice(&global)
+            .map_err(|_| Error::SignatureInvalid("malformed trusted comment signature".into()))?;
+        let mut message = sig_bytes[10..74].to_vec();
+        message.extend_from_slice(trusted_comment.as_bytes());
+        self.key.verify(&message, &global).map_err(|_| {
+            Error::SignatureInvalid("trusted comment doesn't match the signature".into())
+        })?;
+
+        Ok(trusted_comment.to_string())
+    }
+}
+
+/// BLAKE2b-256 of the algorithm, key ID, and secret key, as minisign
+/// stores it to detect a wrong password or a damaged file
+fn checksum(key_id: &[u8; 8], secret: &[u8; 64]) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(KEY_ALG);
+    hasher.update(key_id);
+    hasher.update(secret);
+    hasher.finalize().into()
+}
+
+/// The minisign key ID: the 8 ID bytes as a little-endian number in hex
+fn key_id_hex(key_id: &[u8; 8]) -> String {
+    format!("{:016X}", u64::from_le_bytes(*key_id))
+}
+
+/// Decode the base64 line of a key file, skipping its comment
+fn decode_key_line(text: &str, what: &str) -> Result<Vec<u8>> {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with(UNTRUSTED_PREFIX))
+        .ok_or_else(|| invalid_key(what, "empty key file"))?;
+    STANDARD
+        .decode(line)
+        .map_err(|_| invalid_key(what, "invalid base64"))
+}
+
+fn decode_base64(line: &str) -> Result<Vec<u8>> {
+    STANDARD
+        .decode(line.trim())
+        .map_err(|_| Error::SignatureInvalid("invalid base64".into()))
+}
+
+fn invalid_key(what: &str, reason: &str) -> Error {
+    Error::ParseError(format!("Invalid {}: {}", what, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &[u8] = br#"{"version":"1.0.0","assets":[]}"#;
+
+    #[test]
+    fn test_key_files_round_trip() {
+        let key = SecretKey::generate().unwrap();
+        let parsed = SecretKey::parse(&key.to_minisign()).unwrap();
+        assert_eq!(parsed.public_key(), key.public_key());
+
+        let public = key.public_key().to_minisign();
+        assert!(public.starts_with("untrusted comment: minisign public key "));
+        assert_eq!(PublicKey::parse(&public).unwrap(), key.public_key());
+        // The base64 line alone is accepted too
+        let line = public.lines().nth(1).unwrap();
+        assert_eq!(PublicKey::parse(line).unwrap(), key.public_key());
+    }
+
+    #[test]
+    fn test_signature_is_minisign_compatible() {
+        let key = SecretKey::generate().unwrap();
+        let signature = key.sign(MANIFEST, "timestamp:0\tfile:manifest.json");
+
+        let public = minisign_verify::PublicKey::decode(&key.public_key().to_minisign()).unwrap();
+        let decoded = minisign_verify::Signature::decode(&signature).unwrap();
+        public.verify(MANIFEST, &decoded, false).unwrap();
+        assert_eq!(decoded.trusted_comment(), "timestamp:0\tfile:manifest.json");
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let key = SecretKey::generate().unwrap();
+        let public = key.public_key();
+        let signature = key.sign(MANIFEST, "file:manifest.json");
+        assert_eq!(
+            public.verify(MANIFEST, &signature).unwrap(),
+            "file:manifest.json"
+        );
+
+        let err = public.verify(b"{}", &signature).unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Check);
+
+        let forged = signature.replace("file:manifest.json", "file:other.json");
+        assert!(public.verify(MANIFEST, &forged).is_err());
+
+        let other = SecretKey::generate().unwrap().public_key();
+        let err = other.verify(MANIFEST, &signature).unwrap_err();
+        assert!(err.to_string().contains("signed by key"));
+
+        assert!(public.verify(MANIFEST, "").is_err());
+    }
+
+    #[test]
+    fn test_invalid_secret_keys() {
+        assert!(SecretKey::parse("").is_err());
+        assert!(SecretKey::parse("untrusted comment: x\nnot base64!\n").is_err());
+
+        let key = SecretKey::generate().unwrap();
+        let mut bytes = STANDARD
+            .decode(key.to_minisign().lines().nth(1).unwrap())
+            .unwrap();
+        bytes[100] ^= 1;
+        let err = SecretKey::parse(&STANDARD.encode(&bytes)).unwrap_err();
+        assert!(err.to_string().contains("checksum"));
+
+        bytes[2..4].copy_from_slice(b"Sc");
+        let err = SecretKey::parse(&STANDARD.encode(&bytes)).unwrap_err();
+        assert!(err.to_string().contains("password-protected"));
+    }
+}
@@ -0,0 +1,435 @@
+//! Source snippets for `{{snippet:PATH/}}` templates
+//!
+//! A region is the lines between `mdfx:begin NAME` and `mdfx:end NAME`
+//! markers, written in whatever comment syntax the file uses
+//! (`// mdfx:begin example`, `# mdfx:begin example`). Marker lines are left
+//! out of the snippet, including those of other regions nested inside, and
+//! the common indentation is removed.
+//!
+//! The module also finds the Rust code blocks of a document
+//! ([`code_blocks`], [`rust_attributes`]) and turns them into programs the
+//! way rustdoc does ([`doctest_program`]), for `mdfx test-snippets`.
+//!
+//! ```
+//! use mdfx::snippet::{extract_region, fenced, language};
+//!
+//! let source = "fn main() {\n    // mdfx:begin hello\n    println!(\"hi\");\n    // mdfx:end hello\n}\n";
+//! let code = extract_region(source, "hello").unwrap();
+//! assert_eq!(code, "println!(\"hi\");");
+//! assert_eq!(
+//!     fenced(&code, language("src/main.rs")),
+//!     "```rust\nprintln!(\"hi\");\n```"
+//! );
+//! ```
+
+use crate::error::{Error, Result};
+
+/// Marker opening a region
+const BEGIN: &str = "mdfx:begin";
+
+/// Marker closing a region
+const END: &str = "mdfx:end";
+
+/// Whether `name` can be used as a region name
+///
+/// Names are ASCII letters, digits, `_`, `-` and `.`.
+pub fn is_valid_region(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// A marker on `line`: `Some((is_begin, name))`, with `name` empty for a
+/// bare `mdfx:end`
+fn marker(line: &str) -> Option<(bool, &str)> {
+    let (is_begin, rest) = match line.find(BEGIN) {
+        Some(pos) => (true, &line[pos + BEGIN.len()..]),
+        None => (false, &line[line.find(END)? + END.len()..]),
+    };
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let name = rest
+        .split_whitespace()
+        .next()
+        .filter(|name| is_valid_region(name))
+        .unwrap_or("");
+    Some((is_begin, name))
+}
+
+/// The lines of region `name` in `source`, without marker lines and with
+/// their common indentation removed
+///
+/// A bare `mdfx:end` closes the innermost open region. Fails when the
+/// region has no begin marker or isn't closed.
+pub fn extract_region(source: &str, name: &str) -> Result<String> {
+    let mut lines = source.lines();
+    lines
+        .by_ref()
+        .find(|line| marker(line) == Some((true, name)))
+        .ok_or_else(|| {
+            Error::ParseError(format!("no '{} {}' marker in snippet file", BEGIN, name))
+        })?;
+
+    let mut region = Vec::new();
+    let mut nested: Vec<&str> = Vec::new();
+    for line in lines {
+        match marker(line) {
+            Some((true, inner)) => nested.push(inner),
+            Some((false, end)) if nested.is_empty() && (end.is_empty() || end == name) => {
+                return Ok(dedent(&region));
+            }
+            Some((false, end)) => {
+                if let Some(pos) = nested.iter().rposition(|n| end.is_empty() || *n == end) {
+                    nested.truncate(pos);
+                }
+            }
+            None => region.push(line),
+        }
+    }
+    Err(Error::ParseError(format!(
+        "region '{}' has no '{} {}' marker",
+        name, END, name
+    )))
+}
+
+/// The whole of `source` without marker lines, for snippets without a region
+pub fn strip_markers(source: &str) -> String {
+    let lines: Vec<&str> = source.lines().filter(|l| marker(l).is_none()).collect();
+    dedent(&lines)
+}
+
+/// Join `lines`, removing their common leading whitespace and any blank
+/// lines at either end
+fn dedent(lines: &[&str]) -> String {
+    let start = lines.iter().position(|l| !l.trim().is_empty());
+    let end = lines.iter().rposition(|l| !l.trim().is_empty());
+    let (Some(start), Some(end)) = (start, end) else {
+        return String::new();
+    };
+    let lines = &lines[start..=end];
+
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Code fence info string for a file, from its extension
+///
+/// Unknown extensions give `""`, a fence without a language.
+pub fn language(path: &str) -> &'static str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let extension = name.rsplit_once('.').map_or("", |(_, ext)| ext);
+    match extension.to_ascii_lowercase().as_str() {
+        "rs" => "rust",
+        "py" => "python",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "jsx",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "go" => "go",
+        "rb" => "ruby",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "swift" => "swift",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "cs" => "csharp",
+        "php" => "php",
+        "lua" => "lua",
+        "zig" => "zig",
+        "ex" | "exs" => "elixir",
+        "sh" | "bash" => "bash",
+        "ps1" => "powershell",
+        "sql" => "sql",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "json" => "json",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        "md" => "markdown",
+        _ => "",
+    }
+}
+
+/// `code` in a fence tagged with `lang`, using more backticks than any
+/// run in the code
+pub fn fenced(code: &str, lang: &str) -> String {
+    let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat((longest + 1).max(3));
+    format!("{}{}\n{}\n{}", fence, lang, code, fence)
+}
+
+/// A fenced code block in markdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// 1-based line of the first line of code, after the opening fence
+    pub line: usize,
+    /// Info string after the opening fence, e.g. `rust,no_run`
+    pub info: String,
+    /// The code, without the fences or the fence's indentation
+    pub code: String,
+}
+
+/// Fenced code blocks in `markdown`, in order
+///
+/// A block left open at the end of the document runs to the end.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut lines = markdown.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'));
+        let Some(marker) = marker.filter(|_| indent < 4) else {
+            continue;
+        };
+        let fence = trimmed.len() - trimmed.trim_start_matches(marker).len();
+        let info = trimmed[fence..].trim();
+        if fence < 3 || (marker == '`' && info.contains('`')) {
+            continue;
+        }
+
+        let mut code = Vec::new();
+        for (_, line) in lines.by_ref() {
+            let rest = line.trim_start();
+            let run = rest.len() - rest.trim_start_matches(marker).len();
+            if run >= fence && rest[run..].trim().is_empty() {
+                break;
+            }
+            let strip = (line.len() - rest.len()).min(indent);
+            code.push(&line[strip..]);
+        }
+        blocks.push(CodeBlock {
+            line: index + 2,
+            info: info.to_string(),
+            code: code.join("\n"),
+        });
+    }
+    blocks
+}
+
+/// Rustdoc attributes of a Rust code block, or `None` if the info string
+/// isn't Rust
+///
+/// Like rustdoc, a block is Rust when it starts with `rust` or has only
+/// attributes (`ignore`, `no_run`, `should_panic`, `compile_fail`,
+/// `edition2021`, ...). Blocks without an info string aren't counted, since
+/// READMEs leave shell sessions and output unlabelled.
+pub fn rust_attributes(info: &str) -> Option<Vec<&str>> {
+    let mut tokens = info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .peekable();
+    tokens.peek()?;
+    let is_attribute = |t: &str| {
+        matches!(
+            t,
+            "ignore" | "no_run" | "should_panic" | "compile_fail" | "test_harness"
+        ) || t.starts_with("edition")
+    };
+    if tokens.peek() == Some(&"rust") {
+        tokens.next();
+    }
+    let attributes: Vec<&str> = tokens.collect();
+    attributes
+        .iter()
+        .all(|t| is_attribute(t))
+        .then_some(attributes)
+}
+
+/// A code block made into a program the way rustdoc does for doctests
+///
+/// Lines hidden with `# ` are kept without the marker. Code without a
+/// `fn main` is wrapped in one; code ending in `Ok::<(), E>(())` may use `?`.
+/// Returns the program and the number of lines added before the code.
+pub fn doctest_program(code: &str) -> (String, usize) {
+    let lines: Vec<&str> = code
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            match trimmed {
+                "#" => "",
+                _ if trimmed.starts_with("# ") => &trimmed[2..],
+                _ if trimmed.starts_with("##") => &line[line.len() - trimmed.len() + 1..],
+                _ => line,
+            }
+        })
+        .collect();
+    let body = lines.join("\n");
+
+    if body.contains("fn main") {
+        return (format!("{}\n", body), 0);
+    }
+    let returns_result = lines
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .find(|l| !l.is_empty())
+        .is_some_and(|l| l.starts_with("Ok::<") && l.ends_with("(())"));
+    if returns_result {
+        (
+            format!(
+                "fn main() {{ fn _inner() -> Result<(), impl core::fmt::Debug> {{\n{}\n}} _inner().unwrap() }}\n",
+                body
+            ),
+            1,
+        )
+    } else {
+        (format!("fn main() {{\n{}\n}}\n", body), 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const SOURCE: &str = "\
+use demo::Client;
+
+fn main() {
+    // mdfx:begin connect
+    let client = Client::new();
+    // mdfx:begin call
+    client.ping();
+    // mdfx:end call
+
+    client.close();
+    // mdfx:end connect
+}
+
+# mdfx:begin shell
+cargo run
+# mdfx:end
+";
+
+    #[rstest]
+    #[case(
+        "connect",
+        "let client = Client::new();\nclient.ping();\n\nclient.close();"
+    )]
+    #[case("call", "client.ping();")]
+    #[case("shell", "cargo run")]
+    fn test_extract_region(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(extract_region(SOURCE, name).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("missing", "no 'mdfx:begin missing' marker")]
+    #[case("con", "no 'mdfx:begin con' marker")]
+    fn test_extract_region_missing(#[case] name: &str, #[case] message: &str) {
+        let err = extract_region(SOURCE, name).unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+
+    #[test]
+    fn test_extract_region_unclosed() {
+        let err = extract_region("// mdfx:begin a\nx\n", "a").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("region 'a' has no 'mdfx:end a' marker"));
+    }
+
+    #[test]
+    fn test_html_comment_markers() {
+        let source = "<!-- mdfx:begin nav -->\n<nav></nav>\n<!-- mdfx:end nav -->\n";
+        assert_eq!(extract_region(source, "nav").unwrap(), "<nav></nav>");
+    }
+
+    #[test]
+    fn test_strip_markers() {
+        assert_eq!(
+            strip_markers("// mdfx:begin a\n  x\n  // mdfx:end a\n"),
+            "x"
+        );
+    }
+
+    #[rstest]
+    #[case("src/lib.rs", "rust")]
+    #[case("examples/demo.PY", "python")]
+    #[case("web/app.tsx", "tsx")]
+    #[case("Makefile", "")]
+    #[case("dir.rs/README", "")]
+    fn test_language(#[case] path: &str, #[case] expected: &str) {
+        assert_eq!(language(path), expected);
+    }
+
+    #[test]
+    fn test_code_blocks() {
+        let markdown = "# Demo\n\n```rust,no_run\nlet x = 1;\n```\n\n- item\n\n  ~~~~\n  ```\n  nested\n  ~~~~\n\n```sh\ncargo run";
+        let blocks = code_blocks(markdown);
+        assert_eq!(
+            blocks,
+            [
+                CodeBlock {
+                    line: 4,
+                    info: "rust,no_run".to_string(),
+                    code: "let x = 1;".to_string(),
+                },
+                CodeBlock {
+                    line: 10,
+                    info: String::new(),
+                    code: "```\nnested".to_string(),
+                },
+                CodeBlock {
+                    line: 15,
+                    info: "sh".to_string(),
+                    code: "cargo run".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("rust", Some(vec![]))]
+    #[case("rust,no_run", Some(vec!["no_run"]))]
+    #[case("rust ignore", Some(vec!["ignore"]))]
+    #[case("should_panic", Some(vec!["should_panic"]))]
+    #[case("compile_fail,edition2021", Some(vec!["compile_fail", "edition2021"]))]
+    #[case("", None)]
+    #[case("sh", None)]
+    #[case("rust,text", None)]
+    fn test_rust_attributes(#[case] info: &str, #[case] expected: Option<Vec<&str>>) {
+        assert_eq!(rust_attributes(info), expected);
+    }
+
+    #[rstest]
+    #[case("let x = 1;", "fn main() {\nlet x = 1;\n}\n", 1)]
+    #[case(
+        "# use std::fmt;\n#\nlet x = 1;",
+        "fn main() {\nuse std::fmt;\n\nlet x = 1;\n}\n",
+        1
+    )]
+    #[case("fn main() {}", "fn main() {}\n", 0)]
+    #[case(
+        "##[derive(Debug)]\nstruct A;",
+        "fn main() {\n#[derive(Debug)]\nstruct A;\n}\n",
+        1
+    )]
+    #[case(
+        "let n: u8 = \"1\".parse()?;\nOk::<(), std::num::ParseIntError>(())",
+        "fn main() { fn _inner() -> Result<(), impl core::fmt::Debug> {\nlet n: u8 = \"1\".parse()?;\nOk::<(), std::num::ParseIntError>(())\n} _inner().unwrap() }\n",
+        1
+    )]
+    fn test_doctest_program(#[case] code: &str, #[case] expected: &str, #[case] offset: usize) {
+        assert_eq!(doctest_program(code), (expected.to_string(), offset));
+    }
+
+    #[rstest]
+    #[case("x", "rust", "```rust\nx\n```")]
+    #[case("let s = \"```\";", "rust", "````rust\nlet s = \"```\";\n````")]
+    #[case("", "", "```\n\n```")]
+    fn test_fenced(#[case] code: &str, #[case] lang: &str, #[case] expected: &str) {
+        assert_eq!(fenced(code, lang), expected);
+    }
+}
@@ -0,0 +1,426 @@
+//! Processing instrumentation
+//!
+//! [`TemplateParser`](crate::TemplateParser) notifies registered
+//! [`ParseObserver`]s after every template expansion and backend render.
+//! [`StatsCollector`] is the built-in observer that tallies expansions by
+//! template type; [`Profiler`] records each one's timing as a trace.
+//!
+//! ```
+//! use mdfx::{StatsCollector, TemplateParser};
+//! use std::sync::Arc;
+//!
+//! let stats = Arc::new(StatsCollector::new());
+//! let mut parser = TemplateParser::new().unwrap();
+//! parser.add_observer(stats.clone());
+//!
+//! parser.process("{{mathbold}}A{{/mathbold}} {{ui:swatch:accent/}}").unwrap();
+//!
+//! let snapshot = stats.snapshot();
+//! assert_eq!(snapshot.styles, 1);
+//! assert_eq!(snapshot.components, 1);
+//! ```
+
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A completed template expansion
+#[derive(Debug, Clone)]
+pub struct TemplateEvent<'a> {
+    /// Template type: style, frame, ui, glyph, kbd, num, t, snippet, shields, or partial
+    pub kind: &'a str,
+    /// Style, frame spec, component, glyph, or partial name
+    pub name: &'a str,
+    /// The template as written, e.g. `{{ui:tech:rust/}}`
+    pub source: &'a str,
+    /// Byte range in the processed document; `None` for templates nested
+    /// in another template's output
+    pub span: Option<Range<usize>>,
+    /// When expansion started
+    pub started: Instant,
+    /// Time spent expanding, including nested templates
+    pub elapsed: Duration,
+}
+
+/// A primitive rendered by the backend, during a `{{ui:...}}` expansion
+#[derive(Debug, Clone)]
+pub struct RenderEvent<'a> {
+    /// Primitive type, see [`Primitive::kind`](crate::Primitive::kind)
+    pub primitive: &'a str,
+    /// When rendering started
+    pub started: Instant,
+    /// Time spent in the backend
+    pub elapsed: Duration,
+}
+
+/// Receives instrumentation events from the parser
+///
+/// Observers are shared (`Arc`) and called through `&self`, so
+/// implementations use interior mutability to record data.
+pub trait ParseObserver: Send + Sync {
+    /// Called after a template has been expanded
+    fn template_expanded(&self, event: &TemplateEvent<'_>);
+
+    /// Called after the backend rendered a primitive
+    fn primitive_rendered(&self, _event: &RenderEvent<'_>) {}
+}
+
+/// Counts of expanded templates by type
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TemplateStats {
+    /// Total expansions, including nested templates
+    pub total: usize,
+    /// Text style templates (`{{mathbold}}...`)
+    pub styles: usize,
+    /// Frame templates (`{{frame:...}}`, `{{fr:...}}`)
+    pub frames: usize,
+    /// UI components (`{{ui:...}}`), including live badges
+    pub components: usize,
+    /// Live data badges (`{{ui:live:...}}`)
+    pub live_badges: usize,
+    /// Glyphs, keyboard keys, shields, and partials
+    pub other: usize,
+    /// Expansions per component name
+    pub by_component: BTreeMap<String, usize>,
+}
+
+/// Observer that tallies template expansions
+#[derive(Debug, Default)]
+pub struct StatsCollector {
+    stats: Mutex<TemplateStats>,
+}
+
+impl StatsCollector {
+    /// Create an empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy of the counts recorded so far
+    pub fn snapshot(&self) -> TemplateStats {
+        self.stats.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Clear all counts
+    pub fn reset(&self) {
+        if let Ok(mut stats) = self.stats.lock() {
+            *stats = TemplateStats::default();
+        }
+    }
+}
+
+impl ParseObserver for StatsCollector {
+    fn template_expanded(&self, event: &TemplateEvent<'_>) {
+        let Ok(mut stats) = self.stats.lock() else {
+            return;
+        };
+
+        stats.total += 1;
+        match event.kind {
+            "style" => stats.styles += 1,
+            "frame" => stats.frames += 1,
+            "ui" => {
+                stats.components += 1;
+                if event.name == "live" {
+                    stats.live_badges += 1;
+                }
+                *stats
+                    .by_component
+                    .entry(event.name.to_string())
+                    .or_default() += 1;
+            }
+            _ => stats.other += 1,
+        }
+    }
+}
+
+/// A recorded expansion or render
+#[derive(Debug, Clone)]
+struct Span {
+    name: String,
+    category: String,
+    template: Option<String>,
+    range: Option<Range<usize>>,
+    start: Duration,
+    elapsed: Duration,
+}
+
+/// Observer that records when each template expansion and backend render
+/// ran, for finding slow components
+///
+/// [`to_chrome_trace`](Profiler::to_chrome_trace) writes the Chrome trace
+/// event format, which chrome://tracing, Perfetto, and speedscope load.
+/// Nested templates show up inside the template that produced them.
+///
+/// ```
+/// use mdfx::{Profiler, TemplateParser};
+/// use std::sync::Arc;
+///
+/// let profiler = Arc::new(Profiler::new());
+/// let mut parser = TemplateParser::new().unwrap();
+/// parser.add_observer(profiler.clone());
+///
+/// let markdown = "# Title\n{{ui:tech:rust/}}";
+/// parser.process(markdown).unwrap();
+///
+/// let trace = profiler.to_chrome_trace("README.md", markdown);
+/// assert_eq!(trace["traceEvents"][0]["args"]["location"], "README.md:2:1");
+/// ```
+#[derive(Debug)]
+pub struct Profiler {
+    origin: Instant,
+    spans: Mutex<Vec<Span>>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+            spans: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl Profiler {
+    /// Create a profiler; trace timestamps count from now
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, span: Span) {
+        if let Ok(mut spans) = self.spans.lock() {
+            spans.push(span);
+        }
+    }
+
+    /// Chrome trace JSON for everything recorded so far
+    ///
+    /// `file` and `source` (the processed document) turn template spans
+    /// into `file:line:column` locations.
+    pub fn to_chrome_trace(&self, file: &str, source: &str) -> serde_json::Value {
+        let mut spans = self.spans.lock().map(|s| s.clone()).unwrap_or_default();
+        // Parents before children when they start together
+        spans.sort_by(|a, b| a.start.cmp(&b.start).then(b.elapsed.cmp(&a.elapsed)));
+
+        let events: Vec<_> = spans
+            .iter()
+            .map(|span| {
+                let mut args = serde_json::Map::new();
+                if let Some(template) = &span.template {
+                    args.insert("template".into(), json!(template));
+                }
+                if let Some(range) = &span.range {
+                    let (line, column) = line_column(source, range.start);
+                    args.insert(
+                        "location".into(),
+                        json!(format!("{}:{}:{}", file, line, column)),
+                    );
+                }
+                json!({
+                    "name": span.name,
+                    "cat": span.category,
+                    "ph": "X",
+                    "ts": micros(span.start),
+                    "dur": micros(span.elapsed),
+                    "pid": 1,
+                    "tid": 1,
+                    "args": args,
+                })
+            })
+            .collect();
+
+        json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+    }
+}
+
+impl ParseObserver for Profiler {
+    fn template_expanded(&self, event: &TemplateEvent<'_>) {
+        self.record(Span {
+            name: format!("{}:{}", event.kind, event.name),
+            category: event.kind.to_string(),
+            template: Some(event.source.to_string()),
+            range: event.span.clone(),
+            start: event.started.saturating_duration_since(self.origin),
+            elapsed: event.elapsed,
+        });
+    }
+
+    fn primitive_rendered(&self, event: &RenderEvent<'_>) {
+        self.record(Span {
+            name: format!("render:{}", event.primitive),
+            category: "render".to_string(),
+            template: None,
+            range: None,
+            start: event.started.saturating_duration_since(self.origin),
+            elapsed: event.elapsed,
+        });
+    }
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1000.0
+}
+
+/// 1-based line and column (in characters) of a byte offset
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = source.get(..offset).unwrap_or(source);
+    let line_start = before.rfind('\n').map_or(0, |p| p + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TemplateParser;
+    use std::sync::Arc;
+
+    fn collect(input: &str) -> TemplateStats {
+        let stats = Arc::new(StatsCollector::new());
+        let mut parser = TemplateParser::new().unwrap();
+        parser.add_observer(stats.clone());
+        parser.process(input).unwrap();
+        stats.snapshot()
+    }
+
+    #[test]
+    fn test_counts_by_kind() {
+        let stats = collect(
+            "{{mathbold}}A{{/mathbold}} {{frame:star}}B{{/frame}} {{ui:tech:rust/}} {{ui:tech:go/}} {{glyph:star/}}",
+        );
+
+        assert_eq!(stats.styles, 1);
+        assert_eq!(stats.frames, 1);
+        assert_eq!(stats.components, 2);
+        assert_eq!(stats.other, 1);
+        assert_eq!(stats.total, 5);
+        assert_eq!(stats.by_component.get("tech"), Some(&2));
+    }
+
+    #[test]
+    fn test_nested_templates_counted() {
+        let stats = collect("{{frame:star}}{{mathbold}}A{{/mathbold}}{{/frame}}");
+
+        assert_eq!(stats.frames, 1);
+        assert_eq!(stats.styles, 1);
+    }
+
+    #[test]
+    fn test_code_not_counted() {
+        let stats = collect("`{{mathbold}}A{{/mathbold}}`\n```\n{{ui:tech:rust/}}\n```");
+        assert_eq!(stats, TemplateStats::default());
+    }
+
+    /// Records the span of each top-level template
+    #[derive(Default)]
+    struct SpanRecorder(Mutex<Vec<Range<usize>>>);
+
+    impl ParseObserver for SpanRecorder {
+        fn template_expanded(&self, event: &TemplateEvent<'_>) {
+            if let Some(span) = &event.span {
+                self.0.lock().unwrap().push(span.clone());
+            }
+        }
+    }
+
+    /// The text at each span reported while processing `markdown`
+    fn spanned(options: crate::ParserOptions, markdown: &str) -> Vec<&str> {
+        let recorder = Arc::new(SpanRecorder::default());
+        let mut parser = TemplateParser::new().unwrap();
+        parser.set_options(options);
+        parser.add_observer(recorder.clone());
+        parser.process(markdown).unwrap();
+        let spans = recorder.0.lock().unwrap();
+        spans.iter().map(|span| &markdown[span.clone()]).collect()
+    }
+
+    #[test]
+    fn test_spans_with_crlf_line_endings() {
+        let markdown = "# Title\r\n\r\n{{mathbold}}A\r\nB{{/mathbold}} {{ui:tech:rust/}}\r\n```\r\ncode\r\n```\r\n{{glyph:star/}}\r\n";
+        assert_eq!(
+            spanned(crate::ParserOptions::default(), markdown),
+            [
+                "{{mathbold}}A\r\nB{{/mathbold}}",
+                "{{ui:tech:rust/}}",
+                "{{glyph:star/}}"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spans_in_commonmark_mode() {
+        let markdown = "See [docs](https://docs.rs) <b>{{mathbold}}bold{{/mathbold}}</b> and {{ui:tech:rust/}}";
+        let options = crate::ParserOptions {
+            commonmark: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            spanned(options, markdown),
+            ["{{mathbold}}bold{{/mathbold}}", "{{ui:tech:rust/}}"]
+        );
+    }
+
+    #[test]
+    fn test_spans_with_close_all() {
+        let markdown = "{{glyph:star/}}{{frame:star}}{{mathbold}}A{{//}} then {{ui:tech:go/}}";
+        assert_eq!(
+            spanned(crate::ParserOptions::default(), markdown),
+            [
+                "{{glyph:star/}}",
+                "{{frame:star}}{{mathbold}}A{{//}}",
+                "{{ui:tech:go/}}"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let collector = StatsCollector::new();
+        collector.template_expanded(&TemplateEvent {
+            kind: "ui",
+            name: "live",
+            source: "{{ui:live:github:rust-lang/rust:stars/}}",
+            span: None,
+            started: Instant::now(),
+            elapsed: Duration::ZERO,
+        });
+        assert_eq!(collector.snapshot().live_badges, 1);
+
+        collector.reset();
+        assert_eq!(collector.snapshot(), TemplateStats::default());
+    }
+
+    #[test]
+    fn test_profiler_trace() {
+        let profiler = Arc::new(Profiler::new());
+        let mut parser =
+            TemplateParser::with_backend(Box::new(crate::renderer::svg::SvgBackend::new("assets")))
+                .unwrap();
+        parser.add_observer(profiler.clone());
+
+        let markdown =
+            "```\n{{ui:tech:go/}}\n```\nSee `x` {{frame:star}}{{ui:tech:rust/}}{{/frame}}\n";
+        parser.process(markdown).unwrap();
+
+        let trace = profiler.to_chrome_trace("doc.md", markdown);
+        let events = trace["traceEvents"].as_array().unwrap();
+        let names: Vec<_> = events.iter().map(|e| e["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["frame:star", "ui:tech", "render:tech"]);
+
+        // The frame is located in the document; its nested badge is not
+        assert_eq!(events[0]["args"]["location"], "doc.md:4:9");
+        assert_eq!(events[1]["args"]["template"], "{{ui:tech:rust/}}");
+        assert!(events[1]["args"].get("location").is_none());
+
+        // Children lie within their parent
+        let end = |e: &serde_json::Value| e["ts"].as_f64().unwrap() + e["dur"].as_f64().unwrap();
+        assert!(events[1]["ts"].as_f64() >= events[0]["ts"].as_f64());
+        assert!(end(&events[2]) <= end(&events[1]));
+        assert!(events.iter().all(|e| e["ph"] == "X"));
+    }
+}
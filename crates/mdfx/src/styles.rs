@@ -37,6 +37,25 @@ pub struct Style {
     /// Optional combining character suffix appended after each character (e.g., U+0336 for strikethrough)
     #[serde(default)]
     pub suffix: Option<String>,
+    /// Optional pseudo-random combining marks appended after each character (e.g., zalgo)
+    #[serde(default)]
+    pub random_marks: Option<RandomMarks>,
+    /// Whether converted text is reversed (e.g., upside-down and mirrored text)
+    #[serde(default)]
+    pub reverse: bool,
+}
+
+/// Combining marks drawn pseudo-randomly for each character
+///
+/// The [`Converter`](crate::Converter) picks between 1 and `max` marks from
+/// `pool` per character, from a seed, so the same text and seed always give
+/// the same output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RandomMarks {
+    /// Combining marks to draw from
+    pub pool: String,
+    /// Most marks appended to one character
+    pub max: usize,
 }
 
 impl Style {
@@ -45,6 +64,19 @@ impl Style {
         self.mappings.contains_key(&c)
     }
 
+    /// Whether `c` is a letter or digit this style has no equivalent for
+    ///
+    /// Whitespace, punctuation, and symbols aren't styled by design, and a
+    /// combining style (e.g. strikethrough) styles everything.
+    pub fn is_unmapped(&self, c: char) -> bool {
+        !self.is_combining() && c.is_alphanumeric() && !self.mappings.contains_key(&c)
+    }
+
+    /// Whether this style appends combining marks instead of mapping characters
+    pub fn is_combining(&self) -> bool {
+        self.suffix.is_some() || self.random_marks.is_some()
+    }
+
     /// Convert a character using this style's mappings
     /// Returns the original character if no mapping exists
     pub fn convert_char(&self, c: char) -> char {
@@ -54,10 +86,18 @@ impl Style {
     /// Convert a character to a string, applying mappings and optional suffix
     /// Used for styles with combining characters (e.g., strikethrough)
     pub fn convert_char_to_string(&self, c: char) -> String {
-        let converted = self.convert_char(c);
-        match &self.suffix {
-            Some(suffix) if !c.is_whitespace() => format!("{}{}", converted, suffix),
-            _ => converted.to_string(),
+        let mut out = String::new();
+        self.push_converted(c, &mut out);
+        out
+    }
+
+    /// Append a converted character and its optional suffix to `out`
+    pub fn push_converted(&self, c: char, out: &mut String) {
+        out.push(self.convert_char(c));
+        if let Some(suffix) = &self.suffix {
+            if !c.is_whitespace() {
+                out.push_str(suffix);
+            }
         }
     }
 
@@ -140,8 +180,8 @@ mod tests {
     #[test]
     fn test_load_styles() {
         let data = StylesData::load().unwrap();
-        assert_eq!(data.total_styles, 24);
-        assert_eq!(data.styles.len(), 24);
+        assert_eq!(data.total_styles, 29);
+        assert_eq!(data.styles.len(), 29);
     }
 
     // ========================================================================
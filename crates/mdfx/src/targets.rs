@@ -12,9 +12,20 @@
 //! - `NpmTarget`: SVG assets, similar to GitHub
 //! - `GitLabTarget`: SVG assets, more HTML support
 //! - `PyPITarget`: Plain text fallbacks, ASCII-safe
-
-use crate::error::Result;
+//! - `CratesIoTarget`: SVG assets, relative links pointed at the repository
+//! - `DocsSiteTarget`: MDX-safe output for Docusaurus, or MkDocs Material
+//! - `ConfluenceTarget` / `JiraTarget`: Wiki markup, assets as attachments
+
+use crate::config::{MdfxConfig, RepositoryConfig};
+use crate::error::{Error, Result};
+use crate::links::{extract_links, prose_lines, rewrite_links, url_host, LinkKind};
+use crate::renderer::{ImageDimensions, InlineMode};
+use crate::wiki::{markdown_to_wiki, WikiFormat};
+use lazy_static::lazy_static;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
 
 /// Backend types for rendering primitives
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
@@ -26,6 +37,7 @@ pub enum BackendType {
     /// shields.io URL-based badges (legacy, limited features)
     Shields,
     /// Plain text fallback (PyPI, ASCII-only contexts)
+    #[serde(alias = "plaintext")]
     PlainText,
 }
 
@@ -54,6 +66,51 @@ pub trait Target: Send + Sync {
         true // Most targets support Unicode
     }
 
+    /// Does this target render combining-mark styles (strikethrough,
+    /// underline, overline, zalgo) over the text they decorate?
+    fn supports_combining_marks(&self) -> bool {
+        self.supports_unicode_styling()
+    }
+
+    /// HTML tags rendered even though raw HTML isn't generally supported
+    fn allowed_html_tags(&self) -> &[&'static str] {
+        &[]
+    }
+
+    /// Does this target render `data:` URIs in images and links?
+    fn supports_data_uris(&self) -> bool {
+        true
+    }
+
+    /// Does this target display SVGs embedded in `mode`?
+    ///
+    /// Raw `<svg>` needs HTML passthrough; data URIs need
+    /// [`supports_data_uris`](Target::supports_data_uris).
+    fn supports_inline(&self, mode: InlineMode) -> bool {
+        match mode {
+            InlineMode::Svg => self.supports_html() || self.allowed_html_tags().contains(&"svg"),
+            InlineMode::DataUri => self.supports_data_uris(),
+        }
+    }
+
+    /// Longest heading anchor that links reliably (None = unlimited)
+    fn max_anchor_length(&self) -> Option<usize> {
+        None
+    }
+
+    /// Does this target reliably display images from `host`?
+    fn is_trusted_image_host(&self, _host: &str) -> bool {
+        true
+    }
+
+    /// Apply project settings from `.mdfx.json`
+    fn configure(&mut self, _config: &MdfxConfig) {}
+
+    /// How references to generated images state their size
+    fn image_dimensions(&self) -> ImageDimensions {
+        ImageDimensions::None
+    }
+
     /// Target-specific post-processing
     fn post_process(&self, markdown: &str) -> Result<String> {
         Ok(markdown.to_string()) // Default: no-op
@@ -69,6 +126,59 @@ pub trait Target: Send + Sync {
 // GitHub Target
 // =============================================================================
 
+/// HTML tags that survive GitHub's sanitizer (npm uses the same list)
+const GITHUB_HTML_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "dd",
+    "del",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "ol",
+    "p",
+    "picture",
+    "pre",
+    "q",
+    "s",
+    "samp",
+    "source",
+    "span",
+    "strike",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "tt",
+    "ul",
+    "var",
+];
+
 /// GitHub rendering target
 ///
 /// Characteristics:
@@ -89,6 +199,14 @@ impl Target for GitHubTarget {
         false // GitHub strips most HTML
     }
 
+    fn allowed_html_tags(&self) -> &[&'static str] {
+        GITHUB_HTML_TAGS
+    }
+
+    fn supports_data_uris(&self) -> bool {
+        false // Images are served through the camo proxy
+    }
+
     fn supports_svg_embed(&self) -> bool {
         true // Can embed SVG files
     }
@@ -105,6 +223,10 @@ impl Target for GitHubTarget {
         BackendType::Svg // SVG for full-fidelity rendering
     }
 
+    fn image_dimensions(&self) -> ImageDimensions {
+        ImageDimensions::Html // GitHub keeps width and height on <img>
+    }
+
     fn description(&self) -> &str {
         "GitHub-Flavored Markdown with SVG assets"
     }
@@ -170,6 +292,10 @@ impl Target for LocalDocsTarget {
         BackendType::Svg // Generate local SVG files
     }
 
+    fn image_dimensions(&self) -> ImageDimensions {
+        ImageDimensions::Html
+    }
+
     fn description(&self) -> &str {
         "Local documentation with SVG assets (offline-first)"
     }
@@ -194,6 +320,14 @@ impl Target for NpmTarget {
         false // npm README doesn't support HTML
     }
 
+    fn allowed_html_tags(&self) -> &[&'static str] {
+        GITHUB_HTML_TAGS
+    }
+
+    fn supports_data_uris(&self) -> bool {
+        false
+    }
+
     fn supports_svg_embed(&self) -> bool {
         true // Can embed SVGs
     }
@@ -210,6 +344,10 @@ impl Target for NpmTarget {
         BackendType::Svg // SVG for full-fidelity rendering
     }
 
+    fn image_dimensions(&self) -> ImageDimensions {
+        ImageDimensions::Html
+    }
+
     fn description(&self) -> &str {
         "npm package README with SVG assets"
     }
@@ -239,6 +377,14 @@ impl Target for GitLabTarget {
         true // GitLab allows more HTML than GitHub
     }
 
+    fn max_anchor_length(&self) -> Option<usize> {
+        Some(100) // Longer generated heading IDs don't link reliably
+    }
+
+    fn supports_inline(&self, mode: InlineMode) -> bool {
+        mode == InlineMode::DataUri // The sanitizer drops <svg> elements
+    }
+
     fn supports_svg_embed(&self) -> bool {
         true
     }
@@ -255,6 +401,10 @@ impl Target for GitLabTarget {
         BackendType::Svg // SVG for full-fidelity rendering
     }
 
+    fn image_dimensions(&self) -> ImageDimensions {
+        ImageDimensions::Html
+    }
+
     fn description(&self) -> &str {
         "GitLab-Flavored Markdown with SVG assets"
     }
@@ -325,6 +475,10 @@ impl Target for PyPITarget {
         false // PyPI often displays in ASCII-only contexts
     }
 
+    fn supports_data_uris(&self) -> bool {
+        false // readme_renderer only allows http(s) URLs
+    }
+
     fn description(&self) -> &str {
         "PyPI package description (plain text fallbacks)"
     }
@@ -372,18 +526,836 @@ fn convert_unicode_to_ascii(markdown: &str) -> String {
         .replace("ℹ️", "[i]")
 }
 
+// =============================================================================
+// crates.io Target
+// =============================================================================
+
+/// HTML tags crates.io keeps when rendering a README
+const CRATES_IO_HTML_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "dd",
+    "del",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "span",
+    "strike",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+/// Image hosts that display reliably in crates.io READMEs
+const CRATES_IO_IMAGE_HOSTS: &[&str] = &[
+    "img.shields.io",
+    "raw.githubusercontent.com",
+    "github.com",
+    "user-images.githubusercontent.com",
+    "gitlab.com",
+    "docs.rs",
+    "crates.io",
+    "static.crates.io",
+    "badgen.net",
+    "codecov.io",
+];
+
+/// crates.io README target
+///
+/// Characteristics:
+/// - Markdown flavor: CommonMark with GFM tables and task lists
+/// - HTML support: Sanitized subset (no `<picture>`, `<source>`, or layout tags)
+/// - Image support: External URLs; no `data:` URIs
+/// - Links: Relative paths don't resolve, since the README is shown away
+///   from the repository
+/// - Unicode: Full support
+///
+/// Optimizations:
+/// - Relative links are rewritten to the repository configured under
+///   `"repository"` in `.mdfx.json`: images to raw file URLs, other links to
+///   the file view
+/// - Warnings for images from hosts outside a known-good list
+#[derive(Debug, Clone, Default)]
+pub struct CratesIoTarget {
+    repository: Option<RepositoryConfig>,
+}
+
+impl CratesIoTarget {
+    /// Create a target without a repository; relative links are left as written
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rewrite relative links to point into `repository`
+    pub fn with_repository(mut self, repository: RepositoryConfig) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+}
+
+impl Target for CratesIoTarget {
+    fn name(&self) -> &str {
+        "crates-io"
+    }
+
+    fn supports_html(&self) -> bool {
+        false // Sanitized to a small subset
+    }
+
+    fn allowed_html_tags(&self) -> &[&'static str] {
+        CRATES_IO_HTML_TAGS
+    }
+
+    fn supports_svg_embed(&self) -> bool {
+        true
+    }
+
+    fn supports_external_images(&self) -> bool {
+        true
+    }
+
+    fn supports_data_uris(&self) -> bool {
+        false
+    }
+
+    fn is_trusted_image_host(&self, host: &str) -> bool {
+        CRATES_IO_IMAGE_HOSTS.contains(&host)
+            || self
+                .repository
+                .as_ref()
+                .and_then(|r| r.host())
+                .is_some_and(|h| h.eq_ignore_ascii_case(host))
+    }
+
+    fn max_line_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn preferred_backend(&self) -> BackendType {
+        BackendType::Svg
+    }
+
+    fn image_dimensions(&self) -> ImageDimensions {
+        ImageDimensions::Html
+    }
+
+    fn description(&self) -> &str {
+        "crates.io README (relative links point at the repository)"
+    }
+
+    fn configure(&mut self, config: &MdfxConfig) {
+        if let Some(repository) = &config.repository {
+            self.repository = Some(repository.clone());
+        }
+    }
+
+    fn post_process(&self, markdown: &str) -> Result<String> {
+        let links = extract_links(markdown);
+        for link in links
+            .iter()
+            .filter(|l| l.image && l.kind == LinkKind::External)
+        {
+            if let Some(host) = url_host(&link.target).filter(|h| !self.is_trusted_image_host(h)) {
+                tracing::warn!(
+                    "line {}: image from {} may not display on crates.io",
+                    link.line,
+                    host
+                );
+            }
+        }
+
+        let Some(repository) = &self.repository else {
+            if links
+                .iter()
+                .any(|l| matches!(l.kind, LinkKind::File | LinkKind::Asset))
+            {
+                tracing::warn!(
+                    "Relative links won't resolve on crates.io; set \"repository\" in .mdfx.json"
+                );
+            }
+            return Ok(markdown.to_string());
+        };
+
+        Ok(rewrite_links(markdown, |link| match link.kind {
+            LinkKind::Asset => Some(repository.raw_url(&link.target)),
+            LinkKind::File => Some(repository.file_url(&link.target)),
+            _ => None,
+        }))
+    }
+}
+
+// =============================================================================
+// Docs Site Targets
+// =============================================================================
+
+lazy_static! {
+    /// GitHub alert opening a blockquote, e.g. `> [!NOTE]`
+    static ref GITHUB_ALERT: Regex =
+        Regex::new(r"^>\s*\[!(NOTE|TIP|IMPORTANT|WARNING|CAUTION)\]\s*$").unwrap();
+    /// HTML tag at the start of the input
+    static ref HTML_TAG_START: Regex =
+        Regex::new(r#"^</?([A-Za-z][A-Za-z0-9-]*)(?:\s(?:[^>"']|"[^"]*"|'[^']*')*)?/?>"#).unwrap();
+    /// URL autolink at the start of the input, e.g. `<https://example.com>`
+    static ref AUTOLINK_START: Regex =
+        Regex::new(r"^<((?:https?://|mailto:)[^>\s]+)>").unwrap();
+    /// Block-level HTML tag opening a line
+    static ref HTML_BLOCK_OPEN: Regex =
+        Regex::new(r"^\s*<(div|details|p|section|center|table)(\s[^>]*)?>").unwrap();
+}
+
+/// HTML elements that never have content; JSX needs them self-closed
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Documentation generator a [`DocsSiteTarget`] writes for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DocsFlavor {
+    /// Docusaurus and other MDX-based sites
+    #[default]
+    Docusaurus,
+    /// MkDocs with the Material theme
+    MkDocs,
+}
+
+/// Documentation site target (Docusaurus/MDX or MkDocs Material)
+///
+/// Characteristics:
+/// - Markdown flavor: MDX (Docusaurus) or Python-Markdown (MkDocs)
+/// - HTML support: Full, but MDX parses it as JSX
+/// - Image support: Local files and external URLs
+/// - Unicode: Full support
+///
+/// Optimizations:
+/// - GitHub alerts become `:::note` (Docusaurus) or `!!! note` (MkDocs)
+///   admonitions
+/// - MDX: `{`/`}` in text are escaped, HTML comments become `{/* */}`,
+///   void tags are self-closed, autolinks become links, and stray `<` is
+///   escaped
+/// - MkDocs: block-level HTML gets a `markdown` attribute so the markdown
+///   inside still renders (needs the `md_in_html` extension)
+/// - YAML front matter is passed through untouched, or dropped with
+///   [`DocsSiteTarget::without_front_matter`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DocsSiteTarget {
+    flavor: DocsFlavor,
+    strip_front_matter: bool,
+}
+
+impl DocsSiteTarget {
+    /// Docusaurus (MDX) target
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// MkDocs Material target
+    pub fn mkdocs() -> Self {
+        Self {
+            flavor: DocsFlavor::MkDocs,
+            ..Self::default()
+        }
+    }
+
+    /// Generator this target writes for
+    pub fn flavor(&self) -> DocsFlavor {
+        self.flavor
+    }
+
+    /// Drop YAML front matter instead of passing it through
+    pub fn without_front_matter(mut self) -> Self {
+        self.strip_front_matter = true;
+        self
+    }
+}
+
+impl Target for DocsSiteTarget {
+    fn name(&self) -> &str {
+        match self.flavor {
+            DocsFlavor::Docusaurus => "docs-site",
+            DocsFlavor::MkDocs => "mkdocs",
+        }
+    }
+
+    fn supports_html(&self) -> bool {
+        true
+    }
+
+    fn supports_svg_embed(&self) -> bool {
+        true
+    }
+
+    fn supports_external_images(&self) -> bool {
+        true
+    }
+
+    fn max_line_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn preferred_backend(&self) -> BackendType {
+        BackendType::Svg
+    }
+
+    fn supports_inline(&self, mode: InlineMode) -> bool {
+        // MDX parses raw SVG as JSX, which rejects `style="..."` strings
+        mode == InlineMode::DataUri || self.flavor == DocsFlavor::MkDocs
+    }
+
+    fn image_dimensions(&self) -> ImageDimensions {
+        ImageDimensions::Html
+    }
+
+    fn description(&self) -> &str {
+        match self.flavor {
+            DocsFlavor::Docusaurus => "Docusaurus/MDX documentation page",
+            DocsFlavor::MkDocs => "MkDocs Material documentation page",
+        }
+    }
+
+    fn post_process(&self, markdown: &str) -> Result<String> {
+        let (front_matter, body) = split_front_matter(markdown);
+        let body =
+            convert_alerts_to_admonitions(&convert_callouts_to_github_alerts(body), self.flavor);
+        let body = match self.flavor {
+            DocsFlavor::Docusaurus => escape_mdx(&body),
+            DocsFlavor::MkDocs => mark_html_blocks_markdown(&body),
+        };
+
+        if self.strip_front_matter {
+            Ok(body)
+        } else {
+            Ok(format!("{}{}", front_matter, body))
+        }
+    }
+}
+
+/// Split leading YAML front matter (`---` … `---`) from the body
+pub(crate) fn split_front_matter(markdown: &str) -> (&str, &str) {
+    if !markdown.starts_with("---\n") && !markdown.starts_with("---\r\n") {
+        return ("", markdown);
+    }
+    let mut end = 0;
+    for (idx, line) in markdown.split_inclusive('\n').enumerate() {
+        end += line.len();
+        let line = line.trim_end();
+        if idx > 0 && (line == "---" || line == "...") {
+            return markdown.split_at(end);
+        }
+    }
+    ("", markdown)
+}
+
+/// Convert GitHub alert blockquotes into docs-site admonitions
+fn convert_alerts_to_admonitions(markdown: &str, flavor: DocsFlavor) -> String {
+    let mut out = Vec::new();
+    let mut lines = markdown.lines().peekable();
+    let mut fence: Option<&str> = None;
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = match fence {
+                None => Some(marker),
+                Some(open) if open == marker => None,
+                open => open,
+            };
+        }
+        let kind = match GITHUB_ALERT.captures(line) {
+            Some(caps) if fence.is_none() => admonition_kind(&caps[1]),
+            _ => {
+                out.push(line.to_string());
+                continue;
+            }
+        };
+
+        let mut body = Vec::new();
+        while let Some(next) = lines.next_if(|l| l.starts_with('>')) {
+            let next = &next[1..];
+            body.push(next.strip_prefix(' ').unwrap_or(next));
+        }
+        match flavor {
+            DocsFlavor::Docusaurus => {
+                out.push(format!(":::{}", kind));
+                out.extend(body.iter().map(|l| l.to_string()));
+                out.push(":::".to_string());
+            }
+            DocsFlavor::MkDocs => {
+                out.push(format!("!!! {}", kind));
+                out.extend(body.iter().map(|l| match l.is_empty() {
+                    true => String::new(),
+                    false => format!("    {}", l),
+                }));
+            }
+        }
+    }
+
+    let mut output = out.join("\n");
+    if markdown.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+/// Admonition type for a GitHub alert kind
+fn admonition_kind(alert: &str) -> &'static str {
+    match alert {
+        "TIP" => "tip",
+        "IMPORTANT" => "info",
+        "WARNING" => "warning",
+        "CAUTION" => "danger",
+        _ => "note",
+    }
+}
+
+/// Make markdown safe for MDX, which parses text as JSX
+fn escape_mdx(markdown: &str) -> String {
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    let mut in_comment = false;
+
+    for line in prose_lines(markdown) {
+        let prose = line.prose.as_bytes();
+        let mut i = 0;
+        while i < prose.len() {
+            let at = line.offset + i;
+            if in_comment {
+                match line.prose[i..].find("-->") {
+                    Some(end) => {
+                        edits.push((at + end..at + end + 3, "*/}".to_string()));
+                        in_comment = false;
+                        i += end + 3;
+                    }
+                    None => break,
+                }
+                continue;
+            }
+            match prose[i] {
+                b'{' | b'}' => edits.push((at..at, "\\".to_string())),
+                b'<' if line.text[i..].starts_with("<!--") => {
+                    edits.push((at..at + 4, "{/*".to_string()));
+                    in_comment = true;
+                    i += 4;
+                    continue;
+                }
+                b'<' => {
+                    let rest = &line.text[i..];
+                    if let Some(caps) = AUTOLINK_START.captures(rest) {
+                        let url = &caps[1];
+                        edits.push((at..at + caps[0].len(), format!("[{}]({})", url, url)));
+                        i += caps[0].len();
+                        continue;
+                    }
+                    if let Some(caps) = HTML_TAG_START.captures(rest) {
+                        let tag = &caps[0];
+                        let name = caps[1].to_ascii_lowercase();
+                        if VOID_ELEMENTS.contains(&name.as_str())
+                            && !tag.starts_with("</")
+                            && !tag.ends_with("/>")
+                        {
+                            let close = at + tag.len() - 1;
+                            let space = if tag[..tag.len() - 1].ends_with(' ') {
+                                ""
+                            } else {
+                                " "
+                            };
+                            edits.push((close..close, format!("{}/", space)));
+                        }
+                        i += tag.len();
+                        continue;
+                    }
+                    edits.push((at..at + 1, "&lt;".to_string()));
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    let mut out = String::with_capacity(markdown.len() + edits.len() * 2);
+    let mut copied = 0;
+    for (range, replacement) in edits {
+        out.push_str(&markdown[copied..range.start]);
+        out.push_str(&replacement);
+        copied = range.end;
+    }
+    out.push_str(&markdown[copied..]);
+    out
+}
+
+/// Add `markdown` to block-level HTML so MkDocs renders the markdown inside
+fn mark_html_blocks_markdown(markdown: &str) -> String {
+    let mut edits = Vec::new();
+    for line in prose_lines(markdown) {
+        if let Some(caps) = HTML_BLOCK_OPEN.captures(line.text) {
+            let attrs = caps.get(2).map_or("", |m| m.as_str());
+            if !attrs.split_whitespace().any(|a| a.starts_with("markdown")) {
+                edits.push(line.offset + caps[0].len() - 1);
+            }
+        }
+    }
+
+    let mut out = String::with_capacity(markdown.len() + edits.len() * 9);
+    let mut copied = 0;
+    for at in edits {
+        out.push_str(&markdown[copied..at]);
+        out.push_str(" markdown");
+        copied = at;
+    }
+    out.push_str(&markdown[copied..]);
+    out
+}
+
+// =============================================================================
+// Wiki Targets
+// =============================================================================
+
+/// Confluence page target (storage format)
+///
+/// Characteristics:
+/// - Output: Confluence storage format (XHTML with `ac:` macros), not markdown
+/// - HTML support: None from markdown (tags are dropped)
+/// - Image support: Page attachments and external URLs
+/// - Unicode: Styled text and frames degrade to plain text
+///
+/// Optimizations:
+/// - Generated SVG assets are referenced as attachments by file name
+/// - GitHub alerts become info/tip/note/warning macros
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfluenceTarget;
+
+impl Target for ConfluenceTarget {
+    fn name(&self) -> &str {
+        "confluence"
+    }
+
+    fn supports_html(&self) -> bool {
+        false // Raw HTML isn't valid storage format
+    }
+
+    fn supports_svg_embed(&self) -> bool {
+        true // SVG attachments render inline
+    }
+
+    fn supports_external_images(&self) -> bool {
+        true
+    }
+
+    fn supports_data_uris(&self) -> bool {
+        false
+    }
+
+    fn max_line_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn preferred_backend(&self) -> BackendType {
+        BackendType::Svg // Assets are uploaded as attachments
+    }
+
+    fn supports_unicode_styling(&self) -> bool {
+        false // Degraded to plain text
+    }
+
+    fn description(&self) -> &str {
+        "Confluence storage format (assets as attachments)"
+    }
+
+    fn post_process(&self, markdown: &str) -> Result<String> {
+        let markdown = convert_callouts_to_github_alerts(markdown);
+        Ok(markdown_to_wiki(&markdown, WikiFormat::Confluence))
+    }
+}
+
+/// Jira wiki markup target
+///
+/// Characteristics:
+/// - Output: Jira wiki markup (`h1.`, `*bold*`, `{code}`), not markdown
+/// - HTML support: None
+/// - Image support: Issue attachments and external URLs
+/// - Unicode: Styled text and frames degrade to plain text
+///
+/// Optimizations:
+/// - Generated SVG assets are referenced as attachments by file name
+/// - GitHub alerts become `{info}`/`{tip}`/`{note}`/`{warning}` panels
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JiraTarget;
+
+impl Target for JiraTarget {
+    fn name(&self) -> &str {
+        "jira"
+    }
+
+    fn supports_html(&self) -> bool {
+        false
+    }
+
+    fn supports_svg_embed(&self) -> bool {
+        true
+    }
+
+    fn supports_external_images(&self) -> bool {
+        true
+    }
+
+    fn supports_data_uris(&self) -> bool {
+        false
+    }
+
+    fn max_line_length(&self) -> Option<usize> {
+        None
+    }
+
+    fn preferred_backend(&self) -> BackendType {
+        BackendType::Svg
+    }
+
+    fn supports_unicode_styling(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &str {
+        "Jira wiki markup (assets as attachments)"
+    }
+
+    fn post_process(&self, markdown: &str) -> Result<String> {
+        let markdown = convert_callouts_to_github_alerts(markdown);
+        Ok(markdown_to_wiki(&markdown, WikiFormat::Jira))
+    }
+}
+
+// =============================================================================
+// Custom Targets
+// =============================================================================
+
+/// A target added with [`register_target`]
+///
+/// Lookups share one instance. Name, description, and allowed HTML tags are
+/// read once at registration, since they can't be borrowed through the lock.
+#[derive(Clone)]
+struct RegisteredTarget {
+    name: &'static str,
+    description: String,
+    allowed_html_tags: Vec<&'static str>,
+    target: Arc<Mutex<Box<dyn Target>>>,
+}
+
+impl RegisteredTarget {
+    fn inner(&self) -> MutexGuard<'_, Box<dyn Target>> {
+        self.target.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Target for RegisteredTarget {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn supports_html(&self) -> bool {
+        self.inner().supports_html()
+    }
+
+    fn supports_svg_embed(&self) -> bool {
+        self.inner().supports_svg_embed()
+    }
+
+    fn supports_external_images(&self) -> bool {
+        self.inner().supports_external_images()
+    }
+
+    fn max_line_length(&self) -> Option<usize> {
+        self.inner().max_line_length()
+    }
+
+    fn preferred_backend(&self) -> BackendType {
+        self.inner().preferred_backend()
+    }
+
+    fn supports_unicode_styling(&self) -> bool {
+        self.inner().supports_unicode_styling()
+    }
+
+    fn supports_combining_marks(&self) -> bool {
+        self.inner().supports_combining_marks()
+    }
+
+    fn allowed_html_tags(&self) -> &[&'static str] {
+        &self.allowed_html_tags
+    }
+
+    fn supports_data_uris(&self) -> bool {
+        self.inner().supports_data_uris()
+    }
+
+    fn supports_inline(&self, mode: InlineMode) -> bool {
+        self.inner().supports_inline(mode)
+    }
+
+    fn max_anchor_length(&self) -> Option<usize> {
+        self.inner().max_anchor_length()
+    }
+
+    fn is_trusted_image_host(&self, host: &str) -> bool {
+        self.inner().is_trusted_image_host(host)
+    }
+
+    fn configure(&mut self, config: &MdfxConfig) {
+        self.inner().configure(config)
+    }
+
+    fn image_dimensions(&self) -> ImageDimensions {
+        self.inner().image_dimensions()
+    }
+
+    fn post_process(&self, markdown: &str) -> Result<String> {
+        self.inner().post_process(markdown)
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+/// Targets added at runtime, looked up after the built-in ones
+static CUSTOM_TARGETS: RwLock<Vec<RegisteredTarget>> = RwLock::new(Vec::new());
+
+/// Make a custom target available to [`get_target`] and [`available_targets`]
+///
+/// Downstream crates and CLI extensions use this to add publishing targets
+/// without forking mdfx. Names are matched case-insensitively and may use
+/// letters, digits, `-`, `_`, and `.`. Fails if the name is invalid or
+/// already taken by a built-in or registered target.
+///
+/// Every lookup shares the registered instance, so settings applied through
+/// [`Target::configure`] are seen by later lookups too.
+///
+/// ```
+/// use mdfx::{get_target, register_target, BackendType, Target};
+///
+/// struct Intranet;
+///
+/// impl Target for Intranet {
+///     fn name(&self) -> &str {
+///         "intranet"
+///     }
+///     fn supports_html(&self) -> bool {
+///         true
+///     }
+///     fn supports_svg_embed(&self) -> bool {
+///         true
+///     }
+///     fn supports_external_images(&self) -> bool {
+///         false
+///     }
+///     fn max_line_length(&self) -> Option<usize> {
+///         None
+///     }
+///     fn preferred_backend(&self) -> BackendType {
+///         BackendType::Svg
+///     }
+/// }
+///
+/// register_target(Box::new(Intranet)).unwrap();
+/// assert_eq!(get_target("intranet").unwrap().name(), "intranet");
+/// assert!(register_target(Box::new(Intranet)).is_err());
+/// ```
+pub fn register_target(target: Box<dyn Target>) -> Result<()> {
+    let name = target.name();
+    if name == "auto" {
+        return Err(Error::ParseError(
+            "Target name 'auto' is reserved for auto-detection".to_string(),
+        ));
+    }
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(Error::ParseError(format!(
+            "Invalid target name '{}': use letters, digits, '-', '_' or '.'",
+            name
+        )));
+    }
+
+    let mut custom = CUSTOM_TARGETS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    if builtin_target(name).is_some() || custom.iter().any(|t| t.name.eq_ignore_ascii_case(name)) {
+        return Err(Error::ParseError(format!(
+            "Target '{}' is already defined",
+            name
+        )));
+    }
+
+    // Registrations live for the whole process, so the name can too
+    let registered = RegisteredTarget {
+        name: Box::leak(name.to_string().into_boxed_str()),
+        description: target.description().to_string(),
+        allowed_html_tags: target.allowed_html_tags().to_vec(),
+        target: Arc::new(Mutex::new(target)),
+    };
+    custom.push(registered);
+    Ok(())
+}
+
 // =============================================================================
 // Target Registry
 // =============================================================================
 
-/// Get a target by name
+/// Get a target by name, including targets added with [`register_target`]
 pub fn get_target(name: &str) -> Option<Box<dyn Target>> {
+    builtin_target(name).or_else(|| {
+        CUSTOM_TARGETS
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+            .map(|t| Box::new(t.clone()) as Box<dyn Target>)
+    })
+}
+
+fn builtin_target(name: &str) -> Option<Box<dyn Target>> {
     match name.to_lowercase().as_str() {
         "github" => Some(Box::new(GitHubTarget)),
         "local" => Some(Box::new(LocalDocsTarget)),
         "npm" => Some(Box::new(NpmTarget)),
         "gitlab" => Some(Box::new(GitLabTarget)),
         "pypi" => Some(Box::new(PyPITarget)),
+        "crates-io" | "crates.io" => Some(Box::new(CratesIoTarget::new())),
+        "docs-site" | "docusaurus" | "mdx" => Some(Box::new(DocsSiteTarget::new())),
+        "mkdocs" => Some(Box::new(DocsSiteTarget::mkdocs())),
+        "confluence" => Some(Box::new(ConfluenceTarget)),
+        "jira" => Some(Box::new(JiraTarget)),
         _ => None,
     }
 }
@@ -393,9 +1365,28 @@ pub fn default_target() -> Box<dyn Target> {
     Box::new(GitHubTarget)
 }
 
-/// List all available target names
+/// List all available target names, built-in first
 pub fn available_targets() -> Vec<&'static str> {
-    vec!["github", "local", "npm", "gitlab", "pypi"]
+    let mut names = vec![
+        "github",
+        "local",
+        "npm",
+        "gitlab",
+        "pypi",
+        "crates-io",
+        "docs-site",
+        "mkdocs",
+        "confluence",
+        "jira",
+    ];
+    names.extend(
+        CUSTOM_TARGETS
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|t| t.name),
+    );
+    names
 }
 
 /// Detect target from output path
@@ -406,6 +1397,7 @@ pub fn detect_target_from_path(path: &std::path::Path) -> Option<&'static str> {
             "README.md" => return Some("github"),
             "PKG-INFO" | "PKG-INFO.md" => return Some("pypi"),
             "package.json" => return Some("npm"),
+            _ if filename.ends_with(".mdx") => return Some("docs-site"),
             _ => {}
         }
     }
@@ -438,6 +1430,11 @@ mod tests {
     #[case("npm", false, true, true, true, BackendType::Svg, None)]
     #[case("gitlab", true, true, true, true, BackendType::Svg, None)]
     #[case("pypi", false, false, true, false, BackendType::PlainText, Some(80))]
+    #[case("crates-io", false, true, true, true, BackendType::Svg, None)]
+    #[case("docs-site", true, true, true, true, BackendType::Svg, None)]
+    #[case("mkdocs", true, true, true, true, BackendType::Svg, None)]
+    #[case("confluence", false, true, true, false, BackendType::Svg, None)]
+    #[case("jira", false, true, true, false, BackendType::Svg, None)]
     fn test_target_properties(
         #[case] name: &str,
         #[case] html: bool,
@@ -457,6 +1454,46 @@ mod tests {
         assert_eq!(target.max_line_length(), max_line);
     }
 
+    #[rstest]
+    #[case("github", false, true, None)]
+    #[case("local", true, false, None)]
+    #[case("npm", false, true, None)]
+    #[case("gitlab", true, false, Some(100))]
+    #[case("pypi", false, false, None)]
+    #[case("crates-io", false, true, None)]
+    fn test_target_compat_capabilities(
+        #[case] name: &str,
+        #[case] data_uris: bool,
+        #[case] allows_img_tag: bool,
+        #[case] max_anchor: Option<usize>,
+    ) {
+        let target = get_target(name).unwrap();
+        assert_eq!(target.supports_data_uris(), data_uris);
+        assert_eq!(target.allowed_html_tags().contains(&"img"), allows_img_tag);
+        assert_eq!(target.max_anchor_length(), max_anchor);
+    }
+
+    #[rstest]
+    #[case("github", ImageDimensions::Html)]
+    #[case("gitlab", ImageDimensions::Html)]
+    #[case("pypi", ImageDimensions::None)]
+    fn test_target_image_dimensions(#[case] name: &str, #[case] expected: ImageDimensions) {
+        assert_eq!(get_target(name).unwrap().image_dimensions(), expected);
+    }
+
+    #[rstest]
+    #[case("github", false, false)]
+    #[case("local", true, true)]
+    #[case("gitlab", false, true)]
+    #[case("docs-site", false, true)]
+    #[case("mkdocs", true, true)]
+    #[case("confluence", false, false)]
+    fn test_target_inline_modes(#[case] name: &str, #[case] svg: bool, #[case] data_uri: bool) {
+        let target = get_target(name).unwrap();
+        assert_eq!(target.supports_inline(InlineMode::Svg), svg);
+        assert_eq!(target.supports_inline(InlineMode::DataUri), data_uri);
+    }
+
     // ========================================================================
     // Target Resolution (Parameterized)
     // ========================================================================
@@ -468,11 +1505,48 @@ mod tests {
     #[case("npm", true)]
     #[case("gitlab", true)]
     #[case("pypi", true)]
+    #[case("docusaurus", true)] // alias for docs-site
+    #[case("mkdocs", true)]
+    #[case("confluence", true)]
+    #[case("jira", true)]
     #[case("unknown", false)]
     fn test_get_target(#[case] name: &str, #[case] exists: bool) {
         assert_eq!(get_target(name).is_some(), exists);
     }
 
+    #[test]
+    fn test_crates_io_rewrites_relative_links() {
+        let markdown = "[guide](docs/guide.md#setup) ![](assets/mdfx/a.svg) [top](#top)\n\
+                        <img src=\"./logo.png\"> ![](https://img.shields.io/x) `[code](x.md)`";
+
+        let mut target = CratesIoTarget::new();
+        assert_eq!(target.post_process(markdown).unwrap(), markdown);
+
+        let config: MdfxConfig = serde_json::from_str(
+            r#"{"repository": {"url": "https://github.com/o/r", "branch": "dev"}}"#,
+        )
+        .unwrap();
+        target.configure(&config);
+
+        assert_eq!(
+            target.post_process(markdown).unwrap(),
+            "[guide](https://github.com/o/r/blob/dev/docs/guide.md#setup) \
+             ![](https://raw.githubusercontent.com/o/r/dev/assets/mdfx/a.svg) [top](#top)\n\
+             <img src=\"https://raw.githubusercontent.com/o/r/dev/logo.png\"> \
+             ![](https://img.shields.io/x) `[code](x.md)`"
+        );
+    }
+
+    #[test]
+    fn test_crates_io_trusted_image_hosts() {
+        let target = CratesIoTarget::new()
+            .with_repository(RepositoryConfig::new("https://git.example.com/o/r"));
+        assert!(target.is_trusted_image_host("img.shields.io"));
+        assert!(target.is_trusted_image_host("git.example.com"));
+        assert!(!target.is_trusted_image_host("tracker.example.net"));
+        assert_eq!(get_target("crates.io").unwrap().name(), "crates-io");
+    }
+
     #[test]
     fn test_available_targets() {
         let targets = available_targets();
@@ -483,6 +1557,77 @@ mod tests {
         assert!(targets.contains(&"pypi"));
     }
 
+    #[derive(Default)]
+    struct TestTarget {
+        name: &'static str,
+        suffix: Option<String>,
+    }
+
+    impl Target for TestTarget {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn supports_html(&self) -> bool {
+            true
+        }
+        fn supports_svg_embed(&self) -> bool {
+            true
+        }
+        fn supports_external_images(&self) -> bool {
+            false
+        }
+        fn max_line_length(&self) -> Option<usize> {
+            Some(72)
+        }
+        fn preferred_backend(&self) -> BackendType {
+            BackendType::PlainText
+        }
+        fn configure(&mut self, config: &MdfxConfig) {
+            self.suffix = config.palette.get("suffix").cloned();
+        }
+        fn post_process(&self, markdown: &str) -> Result<String> {
+            Ok(format!(
+                "{}{}",
+                markdown,
+                self.suffix.as_deref().unwrap_or("")
+            ))
+        }
+    }
+
+    #[test]
+    fn test_register_target() {
+        let target = TestTarget {
+            name: "test-wiki",
+            ..Default::default()
+        };
+        register_target(Box::new(target)).unwrap();
+
+        let mut found = get_target("Test-Wiki").expect("registered target");
+        assert_eq!(found.name(), "test-wiki");
+        assert_eq!(found.max_line_length(), Some(72));
+        assert_eq!(found.preferred_backend(), BackendType::PlainText);
+        assert!(available_targets().contains(&"test-wiki"));
+
+        let mut config = MdfxConfig::new();
+        config.palette.insert("suffix".into(), "!".into());
+        found.configure(&config);
+        assert_eq!(found.post_process("hi").unwrap(), "hi!");
+    }
+
+    #[rstest]
+    #[case("github", "already defined")]
+    #[case("auto", "reserved")]
+    #[case("", "Invalid target name")]
+    #[case("my wiki", "Invalid target name")]
+    fn test_register_target_rejects(#[case] name: &'static str, #[case] message: &str) {
+        let target = TestTarget {
+            name,
+            ..Default::default()
+        };
+        let err = register_target(Box::new(target)).unwrap_err();
+        assert!(err.to_string().contains(message), "{err}");
+    }
+
     #[test]
     fn test_default_target() {
         let target = default_target();
@@ -498,6 +1643,7 @@ mod tests {
     #[case("/project/README.md", Some("github"))]
     #[case("/project/docs/index.md", Some("local"))]
     #[case("PKG-INFO", Some("pypi"))]
+    #[case("/site/docs/intro.mdx", Some("docs-site"))]
     #[case("random.md", None)]
     fn test_detect_target_from_path(#[case] path: &str, #[case] expected: Option<&str>) {
         assert_eq!(detect_target_from_path(Path::new(path)), expected);
@@ -510,6 +1656,12 @@ mod tests {
     #[rstest]
     #[case("github", "> 🟢 **Note**\n> This is a note", "[!NOTE]")]
     #[case("gitlab", "> 🔴 **Error**\n> This is an error", "**Danger**")]
+    #[case("jira", "> 🟡 **Tip**\n> Try it", "{tip}\nTry it\n{tip}")]
+    #[case(
+        "confluence",
+        "> 🟢 **Note**\n> Read",
+        "<ac:structured-macro ac:name=\"info\">"
+    )]
     fn test_post_process_alerts(
         #[case] target_name: &str,
         #[case] input: &str,
@@ -520,6 +1672,56 @@ mod tests {
         assert!(output.contains(expected_contains));
     }
 
+    #[rstest]
+    #[case("Use {{name}} here", "Use \\{\\{name\\}\\} here")]
+    #[case("`{code}` and {x}", "`{code}` and \\{x\\}")]
+    #[case("```\n{code}\n```", "```\n{code}\n```")]
+    #[case("<!-- hidden -->", "{/* hidden */}")]
+    #[case("<!-- a\nb -->", "{/* a\nb */}")]
+    #[case(
+        "<img src=\"a.svg\" alt=\"{x}\"><br>",
+        "<img src=\"a.svg\" alt=\"{x}\" /><br />"
+    )]
+    #[case("<br/> <img src=\"a\" />", "<br/> <img src=\"a\" />")]
+    #[case("a < b", "a &lt; b")]
+    #[case("<https://example.com>", "[https://example.com](https://example.com)")]
+    #[case("> [!CAUTION]\n> Hot", ":::danger\nHot\n:::")]
+    #[case("> 🟡 **Tip**\n> Try it", ":::tip\nTry it\n:::")]
+    fn test_docs_site_mdx(#[case] input: &str, #[case] expected: &str) {
+        let output = DocsSiteTarget::new().post_process(input).unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[rstest]
+    #[case(
+        "> [!NOTE]\n> One\n>\n> Two\nAfter",
+        "!!! note\n    One\n\n    Two\nAfter"
+    )]
+    #[case(
+        "<div align=\"center\">\n\n![](a.svg)\n</div>",
+        "<div align=\"center\" markdown>\n\n![](a.svg)\n</div>"
+    )]
+    #[case("<details markdown>\n<p>", "<details markdown>\n<p markdown>")]
+    #[case("Use {{name}} <!-- ok -->", "Use {{name}} <!-- ok -->")]
+    fn test_docs_site_mkdocs(#[case] input: &str, #[case] expected: &str) {
+        let output = DocsSiteTarget::mkdocs().post_process(input).unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_docs_site_front_matter() {
+        let input = "---\ntitle: {Intro}\n---\n# {Intro}\n";
+
+        let output = DocsSiteTarget::new().post_process(input).unwrap();
+        assert_eq!(output, "---\ntitle: {Intro}\n---\n# \\{Intro\\}\n");
+
+        let output = DocsSiteTarget::new()
+            .without_front_matter()
+            .post_process(input)
+            .unwrap();
+        assert_eq!(output, "# \\{Intro\\}\n");
+    }
+
     #[test]
     fn test_pypi_post_process() {
         let target = PyPITarget;
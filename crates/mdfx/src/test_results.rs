@@ -0,0 +1,217 @@
+//! Test run summaries
+//!
+//! Counts passed, failed and skipped tests in a local test report for
+//! `{{ui:tests:file=.../}}`. The format is detected from the content:
+//!
+//! - **JUnit XML** (cargo-nextest `junit.xml`, pytest `--junitxml`, jest-junit, surefire):
+//!   `tests`/`failures`/`errors`/`skipped` attributes of each `<testsuite>`
+//! - **libtest JSON** (`cargo nextest run --message-format libtest-json`,
+//!   `cargo test -- --format json`): `suite` summary events, else `test` events
+//!
+//! ```
+//! use mdfx::test_results::{summarize, TestSummary};
+//!
+//! let junit = r#"<testsuites><testsuite name="mdfx" tests="12" failures="1" errors="0" skipped="2"/></testsuites>"#;
+//! assert_eq!(
+//!     summarize(junit),
+//!     Some(TestSummary { passed: 9, failed: 1, skipped: 2 })
+//! );
+//! ```
+
+/// Test counts from one report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TestSummary {
+    pub passed: u64,
+    /// Failures and errors
+    pub failed: u64,
+    /// Skipped or ignored tests
+    pub skipped: u64,
+}
+
+impl TestSummary {
+    /// Number of tests in the run
+    pub fn total(&self) -> u64 {
+        self.passed + self.failed + self.skipped
+    }
+}
+
+/// Test counts, or `None` if the report isn't recognized or has no tests
+pub fn summarize(report: &str) -> Option<TestSummary> {
+    let trimmed = report.trim_start();
+    let summary = if trimmed.starts_with('<') {
+        junit(trimmed)
+    } else {
+        libtest_json(trimmed)
+    }?;
+    (summary.total() > 0).then_some(summary)
+}
+
+/// Sum of the `<testsuite>` counts, or the `<testsuites>` totals if there are none
+///
+/// Reports that leave out the `skipped` attribute get it from the number of
+/// `<skipped>` elements.
+fn junit(report: &str) -> Option<TestSummary> {
+    let mut suites: Vec<&str> = tags(report, "testsuite").collect();
+    if suites.is_empty() {
+        suites = tags(report, "testsuites").collect();
+    }
+    if suites.is_empty() {
+        return None;
+    }
+
+    let (mut tests, mut failed, mut skipped) = (0u64, 0u64, None);
+    for suite in suites {
+        tests += attr(suite, "tests")?;
+        failed += attr(suite, "failures").unwrap_or(0) + attr(suite, "errors").unwrap_or(0);
+        if let Some(count) = attr(suite, "skipped") {
+            skipped = Some(skipped.unwrap_or(0) + count);
+        }
+    }
+    let skipped = skipped.unwrap_or_else(|| tags(report, "skipped").count() as u64);
+
+    Some(TestSummary {
+        passed: tests.checked_sub(failed + skipped)?,
+        failed,
+        skipped,
+    })
+}
+
+/// Attribute text of each `<name ...>` start tag
+fn tags<'a>(report: &'a str, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    report
+        .match_indices('<')
+        .map(move |(i, _)| &report[i + 1..])
+        .filter_map(move |rest| {
+            let attrs = rest.strip_prefix(name)?;
+            attrs
+                .starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .then(|| &attrs[..attrs.find('>').unwrap_or(attrs.len())])
+        })
+}
+
+/// Numeric value of ` name="..."` in a tag's attribute text
+fn attr(tag: &str, name: &str) -> Option<u64> {
+    let (_, rest) = tag.split_once(&format!(" {}=", name))?;
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let (value, _) = rest[1..].split_once(quote)?;
+    value.trim().parse().ok()
+}
+
+/// Totals of the `suite` events, else a count of the finished `test` events
+fn libtest_json(report: &str) -> Option<TestSummary> {
+    let events: Vec<serde_json::Value> = report
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let count = |event: &serde_json::Value, key: &str| event.get(key).and_then(|v| v.as_u64());
+    let is = |event: &serde_json::Value, key: &str, value: &str| {
+        event.get(key).and_then(|v| v.as_str()) == Some(value)
+    };
+
+    let mut suites = events
+        .iter()
+        .filter(|e| is(e, "type", "suite") && (is(e, "event", "ok") || is(e, "event", "failed")))
+        .peekable();
+    if suites.peek().is_some() {
+        return Some(
+            suites.fold(TestSummary::default(), |sum, suite| TestSummary {
+                passed: sum.passed + count(suite, "passed").unwrap_or(0),
+                failed: sum.failed + count(suite, "failed").unwrap_or(0),
+                skipped: sum.skipped + count(suite, "ignored").unwrap_or(0),
+            }),
+        );
+    }
+
+    let tests: Vec<_> = events.iter().filter(|e| is(e, "type", "test")).collect();
+    if tests.is_empty() {
+        return None;
+    }
+    let finished = |outcome: &str| tests.iter().filter(|e| is(e, "event", outcome)).count() as u64;
+    Some(TestSummary {
+        passed: finished("ok"),
+        failed: finished("failed") + finished("timeout"),
+        skipped: finished("ignored"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const NEXTEST_JUNIT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="nextest-run" tests="5" failures="1" errors="0" uuid="4f1c" timestamp="2024-05-01T10:00:00Z" time="1.2">
+    <testsuite name="mdfx" tests="3" disabled="0" errors="0" failures="1">
+        <testcase name="parser::tests::test_a" classname="mdfx" time="0.01"/>
+        <testcase name="parser::tests::test_b" classname="mdfx" time="0.01">
+            <failure type="test failure">assertion failed</failure>
+        </testcase>
+        <testcase name="parser::tests::test_c" classname="mdfx" time="0.01"/>
+    </testsuite>
+    <testsuite name="mdfx::integration" tests="2" disabled="0" errors="0" failures="0">
+        <testcase name="test_d" classname="mdfx::integration" time="0.3"/>
+        <testcase name="test_e" classname="mdfx::integration" time="0.4"/>
+    </testsuite>
+</testsuites>
+"#;
+
+    const PYTEST_JUNIT: &str = r#"<?xml version="1.0" encoding="utf-8"?><testsuites><testsuite name="pytest" errors="1" failures="0" skipped="2" tests="10" time="0.5"><testcase classname="t" name="a"/></testsuite></testsuites>"#;
+
+    const LIBTEST_JSON: &str = r#"{ "type": "suite", "event": "started", "test_count": 4 }
+{ "type": "test", "event": "started", "name": "a" }
+{ "type": "test", "name": "a", "event": "ok" }
+{ "type": "suite", "event": "failed", "passed": 2, "failed": 1, "ignored": 1, "measured": 0, "filtered_out": 0, "exec_time": 0.01 }
+{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "suite", "event": "ok", "passed": 3, "failed": 0, "ignored": 0, "measured": 0, "filtered_out": 0, "exec_time": 0.02 }
+"#;
+
+    const LIBTEST_EVENTS: &str = r#"{"type":"test","event":"started","name":"a"}
+{"type":"test","event":"ok","name":"a"}
+{"type":"test","event":"failed","name":"b","stdout":"panicked"}
+{"type":"test","event":"ignored","name":"c"}
+{"type":"test","event":"ok","name":"d"}
+"#;
+
+    fn summary(passed: u64, failed: u64, skipped: u64) -> Option<TestSummary> {
+        Some(TestSummary {
+            passed,
+            failed,
+            skipped,
+        })
+    }
+
+    #[rstest]
+    #[case(NEXTEST_JUNIT, summary(4, 1, 0))]
+    #[case(PYTEST_JUNIT, summary(7, 1, 2))]
+    #[case(
+        r#"<testsuites tests="3" failures="0"><testcase name="a"><skipped/></testcase></testsuites>"#,
+        summary(2, 0, 1)
+    )]
+    #[case(LIBTEST_JSON, summary(5, 1, 1))]
+    #[case(LIBTEST_EVENTS, summary(2, 1, 1))]
+    fn test_summarize(#[case] report: &str, #[case] expected: Option<TestSummary>) {
+        assert_eq!(summarize(report), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("<testsuites></testsuites>")]
+    #[case(r#"<testsuite tests="0" failures="0"/>"#)]
+    #[case(r#"<testsuite tests="1" failures="3"/>"#)]
+    #[case(r#"<coverage line-rate="0.8"></coverage>"#)]
+    #[case(r#"{"name": "not a report"}"#)]
+    #[case("LF:10\nLH:8\n")]
+    fn test_summarize_unrecognized(#[case] report: &str) {
+        assert_eq!(summarize(report), None);
+    }
+
+    #[test]
+    fn test_tags_match_whole_names() {
+        let report = r#"<testsuites tests="9"><testsuite tests="2"/></testsuites>"#;
+        assert_eq!(
+            tags(report, "testsuite").collect::<Vec<_>>(),
+            [r#" tests="2"/"#]
+        );
+        assert_eq!(tags(report, "testsuites").count(), 1);
+    }
+}
@@ -0,0 +1,243 @@
+//! Virtual filesystem for asset output
+//!
+//! File-based backends (such as [`SvgBackend`](crate::renderer::svg::SvgBackend))
+//! return assets with a path and bytes; something still has to store them.
+//! [`write_assets`] does that through the [`Vfs`] trait, so the same code
+//! writes to disk ([`DiskFs`], used by the CLI) or into memory
+//! ([`MemoryFs`], for tests, WASM, and services that serve assets directly).
+//!
+//! ```
+//! use mdfx::renderer::svg::SvgBackend;
+//! use mdfx::vfs::MemoryFs;
+//! use mdfx::TemplateParser;
+//!
+//! let parser = TemplateParser::with_backend(Box::new(SvgBackend::new("assets"))).unwrap();
+//! let processed = parser.process_with_assets("{{ui:swatch:accent/}}").unwrap();
+//!
+//! let mut fs = MemoryFs::new();
+//! let summary = processed.write_assets(&mut fs).unwrap();
+//!
+//! assert_eq!(summary.written, 1);
+//! assert!(fs.paths().all(|p| p.starts_with("assets")));
+//! ```
+
+use crate::error::{Error, Result};
+use crate::renderer::RenderedAsset;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Storage for generated files
+pub trait Vfs {
+    /// Whether a file exists at `path`
+    fn exists(&self, path: &Path) -> bool;
+
+    /// Write `bytes` to `path`, creating parent directories as needed
+    fn write(&mut self, path: &Path, bytes: &[u8]) -> Result<()>;
+
+    /// Contents of the file at `path`, if the storage can read them back
+    ///
+    /// Used to detect filename collisions; storage that can't read returns
+    /// `None` and existing files are trusted to match.
+    fn read(&self, _path: &Path) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// The real filesystem
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiskFs;
+
+impl Vfs for DiskFs {
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn write(&mut self, path: &Path, bytes: &[u8]) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        fs::read(path).ok()
+    }
+}
+
+/// Files kept in memory, keyed by path
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFs {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemoryFs {
+    /// Create an empty filesystem
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Contents of the file at `path`
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&[u8]> {
+        self.files.get(path.as_ref()).map(Vec::as_slice)
+    }
+
+    /// Paths of all files, sorted
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// Number of files
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether no files have been written
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Take all files out of the filesystem
+    pub fn into_files(self) -> BTreeMap<PathBuf, Vec<u8>> {
+        self.files
+    }
+}
+
+impl Vfs for MemoryFs {
+    fn exists(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    fn write(&mut self, path: &Path, bytes: &[u8]) -> Result<()> {
+        self.files.insert(path.to_path_buf(), bytes.to_vec());
+        Ok(())
+    }
+
+    fn read(&self, path: &Path) -> Option<Vec<u8>> {
+        self.get(path).map(<[u8]>::to_vec)
+    }
+}
+
+/// What [`write_assets`] did with each file-based asset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AssetWriteSummary {
+    /// New files written
+    pub written: usize,
+    /// Files that already existed (asset names are content hashes, so the
+    /// existing file has the same bytes)
+    pub skipped: usize,
+    /// Repeated references to an asset already handled in this call
+    pub deduped: usize,
+}
+
+/// Write file-based assets, skipping files that already exist
+///
+/// Inline assets have nothing to write and are ignored. Asset names are
+/// content hashes, so a path that already holds different bytes means two
+/// assets collided; that is an error rather than a silent overwrite or skip.
+pub fn write_assets(fs: &mut dyn Vfs, assets: &[RenderedAsset]) -> Result<AssetWriteSummary> {
+    let mut summary = AssetWriteSummary::default();
+    let mut seen: HashMap<&Path, &[u8]> = HashMap::new();
+
+    for asset in assets {
+        let (Some(path), Some(bytes)) = (asset.file_path(), asset.file_bytes()) else {
+            continue;
+        };
+        let path = Path::new(path);
+        if let Some(&previous) = seen.get(path) {
+            if previous != bytes {
+                return Err(Error::AssetCollision(path.to_path_buf()));
+            }
+            summary.deduped += 1;
+            continue;
+        }
+        seen.insert(path, bytes);
+
+        if fs.exists(path) {
+            if fs.read(path).is_some_and(|existing| existing != bytes) {
+                return Err(Error::AssetCollision(path.to_path_buf()));
+            }
+            summary.skipped += 1;
+        } else {
+            fs.write(path, bytes)?;
+            summary.written += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Primitive;
+
+    fn file(path: &str, bytes: &[u8]) -> RenderedAsset {
+        RenderedAsset::File {
+            relative_path: path.to_string(),
+            bytes: bytes.to_vec(),
+            markdown_ref: format!("![]({})", path),
+            primitive: Box::new(Primitive::simple_swatch("FF0000", "flat")),
+        }
+    }
+
+    #[test]
+    fn test_write_assets_to_memory() {
+        let assets = vec![
+            file("assets/a.svg", b"<svg>a</svg>"),
+            RenderedAsset::InlineMarkdown("![](https://example.com)".to_string()),
+            file("assets/b.svg", b"<svg>b</svg>"),
+            file("assets/a.svg", b"<svg>a</svg>"),
+        ];
+        let mut fs = MemoryFs::new();
+
+        let summary = write_assets(&mut fs, &assets).unwrap();
+
+        assert_eq!(
+            summary,
+            AssetWriteSummary {
+                written: 2,
+                skipped: 0,
+                deduped: 1
+            }
+        );
+        assert_eq!(fs.get("assets/a.svg"), Some(&b"<svg>a</svg>"[..]));
+        assert_eq!(fs.len(), 2);
+    }
+
+    #[test]
+    fn test_existing_files_are_skipped() {
+        let mut fs = MemoryFs::new();
+        fs.write(Path::new("assets/a.svg"), b"same").unwrap();
+
+        let summary = write_assets(&mut fs, &[file("assets/a.svg", b"same")]).unwrap();
+
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(fs.get("assets/a.svg"), Some(&b"same"[..]));
+    }
+
+    #[test]
+    fn test_filename_collisions_are_errors() {
+        let mut fs = MemoryFs::new();
+        fs.write(Path::new("assets/a.svg"), b"old").unwrap();
+        let err = write_assets(&mut fs, &[file("assets/a.svg", b"new")]).unwrap_err();
+        assert!(matches!(err, Error::AssetCollision(_)));
+        assert_eq!(fs.get("assets/a.svg"), Some(&b"old"[..]));
+
+        let assets = [file("assets/b.svg", b"one"), file("assets/b.svg", b"two")];
+        assert!(write_assets(&mut MemoryFs::new(), &assets).is_err());
+    }
+
+    #[test]
+    fn test_disk_fs_creates_parent_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/assets/a.svg");
+        let mut fs = DiskFs;
+
+        assert!(!fs.exists(&path));
+        fs.write(&path, b"<svg/>").unwrap();
+        assert!(fs.exists(&path));
+        assert_eq!(std::fs::read(&path).unwrap(), b"<svg/>");
+    }
+}
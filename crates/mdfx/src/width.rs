@@ -0,0 +1,188 @@
+//! Display width of styled text
+//!
+//! Styled output mixes narrow letters, wide CJK and emoji, and zero-width
+//! selectors (the VS15 appended to frame glyphs), so `chars().count()` is a
+//! poor measure of how many terminal or monospace columns a string covers.
+//! Frame centering (`{{frame:star/width=30}}`) measures with
+//! [`display_width`] instead, and badge labels are shortened with
+//! [`truncate`] so a cut never splits a grapheme.
+//!
+//! ```
+//! use mdfx::width::display_width;
+//!
+//! assert_eq!(display_width("ABC"), 3);
+//! assert_eq!(display_width("日本"), 4);
+//! assert_eq!(display_width("★\u{FE0E}"), 1);
+//! ```
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Marker left where text was cut; one column wide
+const ELLIPSIS: &str = "…";
+
+/// Where [`truncate`] cuts text that is too wide
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Truncate {
+    /// Keep the start: `Typesc…`
+    #[default]
+    Ellipsis,
+    /// Keep both ends: `Type…ipt`
+    Middle,
+    /// Never cut
+    None,
+}
+
+impl Truncate {
+    /// Parse a policy name (ellipsis, middle, none)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ellipsis" | "end" => Some(Truncate::Ellipsis),
+            "middle" => Some(Truncate::Middle),
+            "none" => Some(Truncate::None),
+            _ => None,
+        }
+    }
+
+    /// Name used in templates
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Truncate::Ellipsis => "ellipsis",
+            Truncate::Middle => "middle",
+            Truncate::None => "none",
+        }
+    }
+}
+
+/// Number of columns `text` occupies in a monospace font
+pub fn display_width(text: &str) -> usize {
+    text.width()
+}
+
+/// Pad `text` with spaces on both sides to fill `width` columns
+///
+/// Extra space goes on the right when it can't be split evenly. Text that is
+/// already `width` columns or wider is returned unchanged.
+pub fn center(text: &str, width: usize) -> String {
+    let extra = width.saturating_sub(display_width(text));
+    let left = extra / 2;
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(extra - left))
+}
+
+/// Shorten `text` to at most `max_width` columns, marking the cut with `…`
+///
+/// Text that already fits, or any text under [`Truncate::None`], is returned
+/// unchanged. Cuts fall between graphemes, so an emoji sequence or a letter
+/// with combining marks is kept whole or dropped whole; a wide character
+/// that would overshoot the limit is dropped, leaving the result a column
+/// short rather than over.
+///
+/// ```
+/// use mdfx::width::{truncate, Truncate};
+///
+/// assert_eq!(truncate("TypeScript", 7, Truncate::Ellipsis), "TypeSc…");
+/// assert_eq!(truncate("TypeScript", 7, Truncate::Middle), "Typ…ipt");
+/// assert_eq!(truncate("TypeScript", 7, Truncate::None), "TypeScript");
+/// ```
+pub fn truncate(text: &str, max_width: usize, policy: Truncate) -> String {
+    if policy == Truncate::None || display_width(text) <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    let budget = max_width - display_width(ELLIPSIS);
+    match policy {
+        Truncate::Middle => {
+            let (head, used) = take_width(text.graphemes(true), budget.div_ceil(2));
+            let (mut tail, _) = take_width(text.graphemes(true).rev(), budget - used);
+            tail.reverse();
+            format!("{}{}{}", head.concat(), ELLIPSIS, tail.concat())
+        }
+        _ => {
+            let (head, _) = take_width(text.graphemes(true), budget);
+            format!("{}{}", head.concat(), ELLIPSIS)
+        }
+    }
+}
+
+/// Leading graphemes that fit in `width` columns, and the columns they use
+fn take_width<'a>(graphemes: impl Iterator<Item = &'a str>, width: usize) -> (Vec<&'a str>, usize) {
+    let mut taken = Vec::new();
+    let mut used = 0;
+    for grapheme in graphemes {
+        let w = display_width(grapheme);
+        if used + w > width {
+            break;
+        }
+        taken.push(grapheme);
+        used += w;
+    }
+    (taken, used)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("", 0)]
+    #[case("hello", 5)]
+    #[case("𝐇𝐄𝐋𝐋𝐎", 5)]
+    #[case("中文", 4)]
+    #[case("🚀", 2)]
+    #[case("👩‍💻", 2)]
+    #[case("é", 1)]
+    #[case("▓▒░\u{FE0E} ", 4)]
+    fn test_display_width(#[case] text: &str, #[case] expected: usize) {
+        assert_eq!(display_width(text), expected);
+    }
+
+    #[rstest]
+    #[case("ab", 6, "  ab  ")]
+    #[case("ab", 5, " ab  ")]
+    #[case("中", 6, "  中  ")]
+    #[case("toolong", 3, "toolong")]
+    fn test_center(#[case] text: &str, #[case] width: usize, #[case] expected: &str) {
+        assert_eq!(center(text, width), expected);
+    }
+
+    #[rstest]
+    #[case("TypeScript", 10, Truncate::Ellipsis, "TypeScript")]
+    #[case("TypeScript", 5, Truncate::Ellipsis, "Type…")]
+    #[case("TypeScript", 6, Truncate::Middle, "Typ…pt")]
+    #[case("TypeScript", 1, Truncate::Ellipsis, "…")]
+    #[case("TypeScript", 0, Truncate::Middle, "")]
+    #[case("TypeScript", 3, Truncate::None, "TypeScript")]
+    #[case("中文字幕", 6, Truncate::Ellipsis, "中文…")]
+    #[case("中文字幕", 5, Truncate::Ellipsis, "中文…")]
+    #[case("中文字幕", 4, Truncate::Ellipsis, "中…")]
+    #[case("👩‍💻👩‍💻👩‍💻", 5, Truncate::Ellipsis, "👩‍💻👩‍💻…")]
+    #[case("e\u{301}e\u{301}e\u{301}", 2, Truncate::Ellipsis, "e\u{301}…")]
+    #[case("ab👩‍💻cd", 4, Truncate::Middle, "ab…d")]
+    fn test_truncate(
+        #[case] text: &str,
+        #[case] max_width: usize,
+        #[case] policy: Truncate,
+        #[case] expected: &str,
+    ) {
+        let out = truncate(text, max_width, policy);
+        assert_eq!(out, expected);
+        if policy != Truncate::None {
+            assert!(display_width(&out) <= max_width);
+        }
+    }
+
+    #[rstest]
+    #[case("ellipsis", Some(Truncate::Ellipsis))]
+    #[case("middle", Some(Truncate::Middle))]
+    #[case("none", Some(Truncate::None))]
+    #[case("start", None)]
+    fn test_truncate_parse(#[case] name: &str, #[case] expected: Option<Truncate>) {
+        assert_eq!(Truncate::parse(name), expected);
+        if let Some(policy) = expected {
+            assert_eq!(Truncate::parse(policy.as_str()), Some(policy));
+        }
+    }
+}
@@ -0,0 +1,713 @@
+//! Wiki markup output for Confluence and Jira
+//!
+//! Corporate wikis don't take markdown: Confluence pages are stored as XHTML
+//! ("storage format") and Jira uses its own wiki markup. [`markdown_to_wiki`]
+//! converts processed markdown into either, so the same template can publish
+//! to a README and a wiki page.
+//!
+//! - Local images (generated SVG assets) become page attachments, referenced
+//!   by file name; upload the assets directory alongside the page
+//! - External images (e.g. shields.io badges) stay external links
+//! - Styled Unicode text and frame decorations degrade to plain text
+//! - GitHub alerts become info/tip/note/warning panels
+//!
+//! ```
+//! use mdfx::wiki::{markdown_to_wiki, WikiFormat};
+//!
+//! let jira = markdown_to_wiki("## Setup\n\nRun `make` **first**.", WikiFormat::Jira);
+//! assert_eq!(jira, "h2. Setup\n\nRun {{make}} *first*.\n");
+//! ```
+
+use crate::converter::Converter;
+use crate::escape::{escape_attr, escape_text, sanitize_url};
+use crate::registry::Registry;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+lazy_static! {
+    static ref HEADING: Regex = Regex::new(r"^ {0,3}(#{1,6})[ \t]+(.*?)(?:[ \t]+#+)?[ \t]*$").unwrap();
+    static ref LIST_ITEM: Regex = Regex::new(r"^(\s*)([-*+]|\d+[.)])\s+(.*)$").unwrap();
+    static ref RULE: Regex = Regex::new(r"^ {0,3}(?:(?:-[ \t]*){3,}|(?:\*[ \t]*){3,}|(?:_[ \t]*){3,})$").unwrap();
+    static ref TABLE_SEPARATOR: Regex = Regex::new(r"^\|?\s*:?-+:?\s*(\|\s*:?-+:?\s*)*\|?\s*$").unwrap();
+    static ref ALERT: Regex =
+        Regex::new(r"^>\s*\[!(NOTE|TIP|IMPORTANT|WARNING|CAUTION)\]\s*$").unwrap();
+    static ref COMMENT: Regex = Regex::new(r"(?s)<!--.*?-->").unwrap();
+    static ref INLINE: Regex = Regex::new(concat!(
+        r"`(?P<code>[^`]+)`",
+        r"|\[!\[(?P<link_alt>[^\]]*)\]\((?P<link_src>[^)\s]+)[^)]*\)\]\((?P<link_href>[^)\s]+)[^)]*\)",
+        r"|!\[(?P<alt>[^\]]*)\]\((?P<src>[^)\s]+)[^)]*\)",
+        r"|\[(?P<text>[^\]]+)\]\((?P<href>[^)\s]+)[^)]*\)",
+        r"|(?P<img><img\s[^>]*>)",
+        r"|(?P<br><br\s*/?>)",
+        r"|<[^>]+>",
+        r"|\*\*(?P<strong>.+?)\*\*|__(?P<strong2>.+?)__",
+        r"|~~(?P<del>.+?)~~",
+        r"|\*(?P<em>[^*\s](?:[^*]*[^*\s])?)\*",
+        r"|\b_(?P<em2>[^_\s](?:[^_]*[^_\s])?)_\b",
+    ))
+    .unwrap();
+    static ref HTML_ATTR: Regex = Regex::new(r#"(\w+)\s*=\s*"([^"]*)""#).unwrap();
+
+    /// Styled character → the plain character it was made from
+    ///
+    /// Reversed styles (upside-down, mirror) are left out: mapping their
+    /// characters back would still leave the text backwards.
+    static ref PLAIN_CHARS: HashMap<char, char> = Converter::new()
+        .map(|converter| {
+            let mut styles = converter.list_styles();
+            styles.retain(|style| !style.reverse);
+            styles.sort_by(|a, b| a.id.cmp(&b.id));
+            let mut plain = HashMap::new();
+            for (from, to) in styles.iter().flat_map(|style| style.mappings.iter()) {
+                if !to.is_ascii() {
+                    plain.entry(*to).or_insert(*from);
+                }
+            }
+            plain
+        })
+        .unwrap_or_default();
+    /// Combining characters some styles append (e.g. strikethrough)
+    static ref STYLE_SUFFIXES: Vec<String> = Converter::new()
+        .map(|converter| {
+            converter
+                .list_styles()
+                .iter()
+                .filter_map(|style| style.suffix.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    /// Marks styles add at random (e.g. zalgo)
+    static ref STYLE_MARKS: HashSet<char> = Converter::new()
+        .map(|converter| {
+            converter
+                .list_styles()
+                .iter()
+                .filter_map(|style| style.random_marks.as_ref())
+                .flat_map(|marks| marks.pool.chars())
+                .collect()
+        })
+        .unwrap_or_default();
+    /// Decorative two-sided frames, longest prefix first
+    static ref FRAMES: Vec<(String, String)> = Registry::shared()
+        .map(|registry| {
+            let mut frames: Vec<(String, String)> = registry
+                .frames()
+                .values()
+                .filter(|f| decorative(&f.prefix) && decorative(&f.suffix))
+                .map(|f| (f.prefix.clone(), f.suffix.clone()))
+                .collect();
+            frames.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.cmp(b)));
+            frames
+        })
+        .unwrap_or_default();
+}
+
+/// Wiki markup language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WikiFormat {
+    /// Confluence storage format (XHTML with `ac:` macros)
+    Confluence,
+    /// Jira wiki markup
+    Jira,
+}
+
+/// Frame edges worth stripping: non-empty and made only of symbols
+fn decorative(edge: &str) -> bool {
+    let edge = edge.trim();
+    !edge.is_empty() && edge.chars().all(|c| !c.is_ascii())
+}
+
+/// Degrade styled Unicode text and frame decorations to plain text
+///
+/// ```
+/// assert_eq!(mdfx::wiki::plain_text("▓▒░ 𝐓𝐈𝐓𝐋𝐄 ░▒▓"), "TITLE");
+/// ```
+pub fn plain_text(text: &str) -> String {
+    // Frames are emitted with text presentation selectors (U+FE0E)
+    let mut out: String = text
+        .chars()
+        .filter(|&c| c != '\u{FE0E}' && !STYLE_MARKS.contains(&c))
+        .map(|c| *PLAIN_CHARS.get(&c).unwrap_or(&c))
+        .collect();
+    for suffix in STYLE_SUFFIXES.iter() {
+        out = out.replace(suffix.as_str(), "");
+    }
+    for (prefix, suffix) in FRAMES.iter() {
+        let mut search = 0;
+        while let Some(start) = out[search..].find(prefix.as_str()).map(|i| i + search) {
+            let inner = start + prefix.len();
+            let Some(end) = out[inner..].find(suffix.as_str()).map(|i| i + inner) else {
+                break;
+            };
+            let content = out[inner..end].to_string();
+            out.replace_range(start..end + suffix.len(), &content);
+            search = start + content.len();
+        }
+    }
+    out
+}
+
+/// A list item with its nesting depth (1-based)
+struct ListItem {
+    depth: usize,
+    ordered: bool,
+    text: String,
+}
+
+enum Block {
+    Heading(usize, String),
+    Paragraph(String),
+    Code {
+        lang: String,
+        body: String,
+    },
+    List(Vec<ListItem>),
+    Quote(Vec<String>),
+    Alert {
+        panel: &'static str,
+        lines: Vec<String>,
+    },
+    Table {
+        header: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    Rule,
+}
+
+/// Panel macro for a GitHub alert kind (same names in Confluence and Jira)
+fn panel(alert: &str) -> &'static str {
+    match alert {
+        "TIP" => "tip",
+        "IMPORTANT" | "WARNING" => "note",
+        "CAUTION" => "warning",
+        _ => "info",
+    }
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|').map(|c| c.trim().to_string()).collect()
+}
+
+fn starts_block(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    HEADING.is_match(line)
+        || RULE.is_match(line)
+        || LIST_ITEM.is_match(line)
+        || trimmed.starts_with('>')
+        || trimmed.starts_with("```")
+        || trimmed.starts_with("~~~")
+}
+
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim_start();
+
+        if trimmed.is_empty() {
+            i += 1;
+        } else if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            let lang = trimmed[marker.len()..].trim().to_string();
+            let mut body = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with(marker) {
+                body.push(lines[i]);
+                i += 1;
+            }
+            i += 1; // closing fence
+            blocks.push(Block::Code {
+                lang,
+                body: body.join("\n"),
+            });
+        } else if let Some(caps) = HEADING.captures(line) {
+            blocks.push(Block::Heading(caps[1].len(), plain_text(&caps[2])));
+            i += 1;
+        } else if RULE.is_match(line) {
+            blocks.push(Block::Rule);
+            i += 1;
+        } else if trimmed.starts_with('>') {
+            let alert = ALERT.captures(trimmed).map(|caps| panel(&caps[1]));
+            if alert.is_some() {
+                i += 1;
+            }
+            let mut quoted = Vec::new();
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let rest = &lines[i].trim_start()[1..];
+                quoted.push(plain_text(rest.strip_prefix(' ').unwrap_or(rest)));
+                i += 1;
+            }
+            blocks.push(match alert {
+                Some(panel) => Block::Alert {
+                    panel,
+                    lines: quoted,
+                },
+                None => Block::Quote(quoted),
+            });
+        } else if trimmed.starts_with('|')
+            && lines
+                .get(i + 1)
+                .is_some_and(|l| TABLE_SEPARATOR.is_match(l))
+        {
+            let header = table_cells(&plain_text(line));
+            let mut rows = Vec::new();
+            i += 2;
+            while i < lines.len() && lines[i].trim_start().starts_with('|') {
+                rows.push(table_cells(&plain_text(lines[i])));
+                i += 1;
+            }
+            blocks.push(Block::Table { header, rows });
+        } else if LIST_ITEM.is_match(line) {
+            let mut items: Vec<ListItem> = Vec::new();
+            let mut indents: Vec<usize> = Vec::new();
+            while i < lines.len() {
+                if let Some(caps) = LIST_ITEM.captures(lines[i]) {
+                    let indent = caps[1].len();
+                    while indents.last().is_some_and(|&last| last > indent) {
+                        indents.pop();
+                    }
+                    if indents.last().is_none_or(|&last| last < indent) {
+                        indents.push(indent);
+                    }
+                    items.push(ListItem {
+                        depth: indents.len(),
+                        ordered: caps[2].starts_with(|c: char| c.is_ascii_digit()),
+                        text: plain_text(&caps[3]),
+                    });
+                } else if !lines[i].trim().is_empty()
+                    && lines[i].starts_with([' ', '\t'])
+                    && !starts_block(lines[i])
+                {
+                    // Continuation of the previous item
+                    if let Some(item) = items.last_mut() {
+                        item.text.push(' ');
+                        item.text.push_str(&plain_text(lines[i].trim()));
+                    }
+                } else {
+                    break;
+                }
+                i += 1;
+            }
+            blocks.push(Block::List(items));
+        } else {
+            let mut text = Vec::new();
+            while i < lines.len()
+                && !lines[i].trim().is_empty()
+                && (text.is_empty() || !starts_block(lines[i]))
+            {
+                text.push(plain_text(lines[i].trim()));
+                i += 1;
+            }
+            blocks.push(Block::Paragraph(text.join(" ")));
+        }
+    }
+
+    blocks
+}
+
+/// Convert markdown to Confluence storage format or Jira wiki markup
+///
+/// HTML comments are dropped, and HTML tags other than `<img>` and `<br>`
+/// are removed, keeping their content.
+pub fn markdown_to_wiki(markdown: &str, format: WikiFormat) -> String {
+    let markdown = COMMENT.replace_all(markdown, "");
+    let rendered: Vec<String> = parse_blocks(&markdown)
+        .iter()
+        .map(|block| render_block(block, format))
+        .filter(|b| !b.is_empty())
+        .collect();
+
+    match format {
+        WikiFormat::Jira => format!("{}\n", rendered.join("\n\n")),
+        WikiFormat::Confluence => format!("{}\n", rendered.join("\n")),
+    }
+}
+
+fn render_block(block: &Block, format: WikiFormat) -> String {
+    let text = |s: &str| inline(s, format);
+    match (block, format) {
+        (Block::Heading(level, s), WikiFormat::Jira) => format!("h{}. {}", level, text(s)),
+        (Block::Heading(level, s), WikiFormat::Confluence) => {
+            format!("<h{}>{}</h{}>", level, text(s), level)
+        }
+        (Block::Paragraph(s), WikiFormat::Jira) => text(s).trim().to_string(),
+        (Block::Paragraph(s), WikiFormat::Confluence) => match text(s).trim() {
+            "" => String::new(),
+            p => format!("<p>{}</p>", p),
+        },
+        (Block::Code { lang, body }, WikiFormat::Jira) if lang.is_empty() => {
+            format!("{{noformat}}\n{}\n{{noformat}}", body)
+        }
+        (Block::Code { lang, body }, WikiFormat::Jira) => {
+            format!("{{code:{}}}\n{}\n{{code}}", lang, body)
+        }
+        (Block::Code { lang, body }, WikiFormat::Confluence) => {
+            let language = match lang.is_empty() {
+                true => String::new(),
+                false => format!(
+                    "<ac:parameter ac:name=\"language\">{}</ac:parameter>",
+                    escape_text(lang)
+                ),
+            };
+            format!(
+                "<ac:structured-macro ac:name=\"code\">{}<ac:plain-text-body><![CDATA[{}]]></ac:plain-text-body></ac:structured-macro>",
+                language,
+                body.replace("]]>", "]]]]><![CDATA[>")
+            )
+        }
+        (Block::List(items), WikiFormat::Jira) => {
+            let mut markers: Vec<char> = Vec::new();
+            items
+                .iter()
+                .map(|item| {
+                    markers.truncate(item.depth - 1);
+                    markers.push(if item.ordered { '#' } else { '*' });
+                    let marker: String = markers.iter().collect();
+                    format!("{} {}", marker, text(&item.text))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        (Block::List(items), WikiFormat::Confluence) => {
+            let mut out = String::new();
+            let mut open: Vec<&str> = Vec::new();
+            for item in items {
+                let depth = item.depth.min(open.len() + 1);
+                if depth > open.len() {
+                    let tag = if item.ordered { "ol" } else { "ul" };
+                    out.push_str(&format!("<{}>", tag));
+                    open.push(tag);
+                } else {
+                    while open.len() > depth {
+                        out.push_str(&format!("</li></{}>", open.pop().unwrap_or("ul")));
+                    }
+                    out.push_str("</li>");
+                }
+                out.push_str(&format!("<li>{}", text(&item.text)));
+            }
+            while let Some(tag) = open.pop() {
+                out.push_str(&format!("</li></{}>", tag));
+            }
+            out
+        }
+        (Block::Quote(lines), WikiFormat::Jira) => {
+            format!("{{quote}}\n{}\n{{quote}}", jira_lines(lines))
+        }
+        (Block::Quote(lines), WikiFormat::Confluence) => {
+            format!("<blockquote>{}</blockquote>", confluence_paragraphs(lines))
+        }
+        (Block::Alert { panel, lines }, WikiFormat::Jira) => {
+            format!("{{{}}}\n{}\n{{{}}}", panel, jira_lines(lines), panel)
+        }
+        (Block::Alert { panel, lines }, WikiFormat::Confluence) => format!(
+            "<ac:structured-macro ac:name=\"{}\"><ac:rich-text-body>{}</ac:rich-text-body></ac:structured-macro>",
+            panel,
+            confluence_paragraphs(lines)
+        ),
+        (Block::Table { header, rows }, WikiFormat::Jira) => {
+            let cell = |c: &String| text(c).replace('|', "\\|");
+            let mut out = vec![format!(
+                "||{}||",
+                header.iter().map(cell).collect::<Vec<_>>().join("||")
+            )];
+            out.extend(
+                rows.iter()
+                    .map(|row| format!("|{}|", row.iter().map(cell).collect::<Vec<_>>().join("|"))),
+            );
+            out.join("\n")
+        }
+        (Block::Table { header, rows }, WikiFormat::Confluence) => {
+            let row = |cells: &[String], tag: &str| {
+                let cells: String = cells
+                    .iter()
+                    .map(|c| format!("<{}>{}</{}>", tag, text(c), tag))
+                    .collect();
+                format!("<tr>{}</tr>", cells)
+            };
+            let body: String = rows.iter().map(|r| row(r, "td")).collect();
+            format!("<table><tbody>{}{}</tbody></table>", row(header, "th"), body)
+        }
+        (Block::Rule, WikiFormat::Jira) => "----".to_string(),
+        (Block::Rule, WikiFormat::Confluence) => "<hr />".to_string(),
+    }
+}
+
+fn jira_lines(lines: &[String]) -> String {
+    lines
+        .iter()
+        .map(|l| inline(l, WikiFormat::Jira))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Blank-line separated paragraphs
+fn confluence_paragraphs(lines: &[String]) -> String {
+    lines
+        .split(|l| l.trim().is_empty())
+        .filter(|p| !p.is_empty())
+        .map(|p| format!("<p>{}</p>", inline(&p.join(" "), WikiFormat::Confluence)))
+        .collect()
+}
+
+fn escape_jira(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '{' | '}' | '[' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Image reference: attachments for local files, URLs otherwise
+struct Image<'a> {
+    src: &'a str,
+    alt: &'a str,
+    width: Option<&'a str>,
+    height: Option<&'a str>,
+}
+
+impl Image<'_> {
+    fn is_external(&self) -> bool {
+        self.src.starts_with("http://") || self.src.starts_with("https://")
+    }
+
+    /// Attachment file name for a local image
+    fn file_name(&self) -> &str {
+        Path::new(self.src)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(self.src)
+    }
+
+    fn render(&self, format: WikiFormat) -> String {
+        match format {
+            WikiFormat::Jira => {
+                let src = if self.is_external() {
+                    self.src
+                } else {
+                    self.file_name()
+                };
+                let params: Vec<String> = [
+                    ("width", self.width),
+                    ("height", self.height),
+                    ("alt", Some(self.alt).filter(|a| !a.is_empty())),
+                ]
+                .into_iter()
+                .filter_map(|(k, v)| v.map(|v| format!("{}={}", k, v)))
+                .collect();
+                match params.is_empty() {
+                    true => format!("!{}!", src),
+                    false => format!("!{}|{}!", src, params.join(",")),
+                }
+            }
+            WikiFormat::Confluence => {
+                let mut attrs = String::new();
+                for (name, value) in [
+                    ("alt", Some(self.alt).filter(|a| !a.is_empty())),
+                    ("width", self.width),
+                    ("height", self.height),
+                ] {
+                    if let Some(value) = value {
+                        attrs.push_str(&format!(" ac:{}=\"{}\"", name, escape_attr(value)));
+                    }
+                }
+                let resource = match self.is_external() {
+                    true => format!("<ri:url ri:value=\"{}\" />", escape_attr(self.src)),
+                    false => format!(
+                        "<ri:attachment ri:filename=\"{}\" />",
+                        escape_attr(self.file_name())
+                    ),
+                };
+                format!("<ac:image{}>{}</ac:image>", attrs, resource)
+            }
+        }
+    }
+}
+
+fn link(text: &str, href: &str, format: WikiFormat) -> String {
+    let href = sanitize_url(href);
+    match format {
+        WikiFormat::Jira => format!("[{}|{}]", text, href),
+        WikiFormat::Confluence => format!("<a href=\"{}\">{}</a>", escape_attr(&href), text),
+    }
+}
+
+/// Convert inline markdown: code, images, links, emphasis, and HTML
+fn inline(text: &str, format: WikiFormat) -> String {
+    let escape = |s: &str| match format {
+        WikiFormat::Jira => escape_jira(s),
+        WikiFormat::Confluence => escape_text(s).into_owned(),
+    };
+    let wrap = |inner: &str, jira: &str, tag: &str| match format {
+        WikiFormat::Jira => format!("{}{}{}", jira, inline(inner, format), jira),
+        WikiFormat::Confluence => format!("<{}>{}</{}>", tag, inline(inner, format), tag),
+    };
+
+    let mut out = String::new();
+    let mut last = 0;
+    for caps in INLINE.captures_iter(text) {
+        let Some(whole) = caps.get(0) else { continue };
+        out.push_str(&escape(&text[last..whole.start()]));
+        last = whole.end();
+
+        let group = |name: &str| caps.name(name).map(|m| m.as_str());
+        let converted = if let Some(code) = group("code") {
+            match format {
+                WikiFormat::Jira => format!("{{{{{}}}}}", code),
+                WikiFormat::Confluence => format!("<code>{}</code>", escape_text(code)),
+            }
+        } else if let Some(src) = group("link_src") {
+            let image = Image {
+                src,
+                alt: group("link_alt").unwrap_or_default(),
+                width: None,
+                height: None,
+            };
+            link(
+                &image.render(format),
+                group("link_href").unwrap_or_default(),
+                format,
+            )
+        } else if let Some(src) = group("src") {
+            Image {
+                src,
+                alt: group("alt").unwrap_or_default(),
+                width: None,
+                height: None,
+            }
+            .render(format)
+        } else if let Some(href) = group("href") {
+            link(
+                &inline(group("text").unwrap_or_default(), format),
+                href,
+                format,
+            )
+        } else if let Some(tag) = group("img") {
+            img_tag(tag, format)
+        } else if group("br").is_some() {
+            match format {
+                WikiFormat::Jira => "\\\\".to_string(),
+                WikiFormat::Confluence => "<br />".to_string(),
+            }
+        } else if let Some(inner) = group("strong").or_else(|| group("strong2")) {
+            wrap(inner, "*", "strong")
+        } else if let Some(inner) = group("em").or_else(|| group("em2")) {
+            wrap(inner, "_", "em")
+        } else if let Some(inner) = group("del") {
+            wrap(inner, "-", "del")
+        } else {
+            // Other HTML tags are dropped
+            String::new()
+        };
+        out.push_str(&converted);
+    }
+    out.push_str(&escape(&text[last..]));
+    out
+}
+
+fn img_tag(tag: &str, format: WikiFormat) -> String {
+    let attrs: HashMap<String, &str> = HTML_ATTR
+        .captures_iter(tag)
+        .filter_map(|c: Captures| Some((c.get(1)?.as_str().to_lowercase(), c.get(2)?.as_str())))
+        .collect();
+    let Some(src) = attrs.get("src") else {
+        return String::new();
+    };
+    Image {
+        src,
+        alt: attrs.get("alt").copied().unwrap_or_default(),
+        width: attrs.get("width").copied(),
+        height: attrs.get("height").copied(),
+    }
+    .render(format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("𝐁𝐨𝐥𝐝 𝕕𝕠𝕦𝕓𝕝𝕖", "Bold double")]
+    #[case("▓▒░ Title ░▒▓ and ★ star ☆", "Title and star")]
+    #[case("▓\u{FE0E}▒\u{FE0E}░\u{FE0E} Fast ░\u{FE0E}▒\u{FE0E}▓\u{FE0E}", "Fast")]
+    #[case("█▌Left only", "█▌Left only")]
+    #[case("→ arrows stay", "→ arrows stay")]
+    #[case("Z\u{30B}\u{317}a\u{336}p", "Zap")]
+    fn test_plain_text(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(plain_text(input), expected);
+    }
+
+    #[rstest]
+    #[case("# Title", "h1. Title")]
+    #[case("**bold** *em* ~~gone~~ `a{b}`", "*bold* _em_ -gone- {{a{b}}}")]
+    #[case(
+        "See [docs](https://x.dev) {here}",
+        "See [docs|https://x.dev] \\{here\\}"
+    )]
+    #[case("![logo](assets/mdfx/logo_1a.svg)", "!logo_1a.svg|alt=logo!")]
+    #[case(
+        "[![CI](https://img.shields.io/ci.svg)](https://ci.dev)",
+        "[!https://img.shields.io/ci.svg|alt=CI!|https://ci.dev]"
+    )]
+    #[case(
+        "<img src=\"assets/s.svg\" width=\"20\" height=\"20\" alt=\"\">",
+        "!s.svg|width=20,height=20!"
+    )]
+    #[case("- a\n  - b\n1. c\n   wrapped", "* a\n** b\n# c wrapped")]
+    #[case("> [!WARNING]\n> Careful", "{note}\nCareful\n{note}")]
+    #[case("> quoted", "{quote}\nquoted\n{quote}")]
+    #[case("| A | B |\n|---|:-:|\n| 1 | 2 |", "||A||B||\n|1|2|")]
+    #[case("```rust\nfn main() {}\n```", "{code:rust}\nfn main() {}\n{code}")]
+    #[case("<!-- hidden -->\n<div align=\"center\">\n\nText\n</div>", "Text")]
+    #[case("---", "----")]
+    #[case("[x](javascript:alert(1))", "[x|#])")]
+    fn test_jira(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(
+            markdown_to_wiki(input, WikiFormat::Jira),
+            format!("{}\n", expected)
+        );
+    }
+
+    #[rstest]
+    #[case("## A & B", "<h2>A &amp; B</h2>")]
+    #[case("one\ntwo **b**", "<p>one two <strong>b</strong></p>")]
+    #[case(
+        "![](https://img.shields.io/x.svg) ![](assets/a.svg)",
+        "<p><ac:image><ri:url ri:value=\"https://img.shields.io/x.svg\" /></ac:image> \
+         <ac:image><ri:attachment ri:filename=\"a.svg\" /></ac:image></p>"
+    )]
+    #[case("- a\n  - b\n- c", "<ul><li>a<ul><li>b</li></ul></li><li>c</li></ul>")]
+    #[case(
+        "> [!TIP]\n> Try <x>",
+        "<ac:structured-macro ac:name=\"tip\"><ac:rich-text-body><p>Try </p></ac:rich-text-body></ac:structured-macro>"
+    )]
+    #[case(
+        "| A |\n|---|\n| `1<2` |",
+        "<table><tbody><tr><th>A</th></tr><tr><td><code>1&lt;2</code></td></tr></tbody></table>"
+    )]
+    #[case(
+        "```\na ]]> b\n```",
+        "<ac:structured-macro ac:name=\"code\"><ac:plain-text-body><![CDATA[a ]]]]><![CDATA[> b]]></ac:plain-text-body></ac:structured-macro>"
+    )]
+    #[case("[x](javascript:alert(1))", "<p><a href=\"#\">x</a>)</p>")]
+    #[case(
+        "[x](https://example.com/?a=1&b=\"2\")",
+        "<p><a href=\"https://example.com/?a=1&amp;b=&quot;2&quot;\">x</a></p>"
+    )]
+    fn test_confluence(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(
+            markdown_to_wiki(input, WikiFormat::Confluence),
+            format!("{}\n", expected)
+        );
+    }
+}
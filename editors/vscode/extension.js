@@ -24,7 +24,17 @@ function activate(context) {
             { scheme: 'untitled', language: 'markdown' }
         ],
         synchronize: {
-            fileEvents: vscode.workspace.createFileSystemWatcher('**/*.md')
+            fileEvents: vscode.workspace.createFileSystemWatcher('**/{*.md,.mdfx.json}')
+        },
+        middleware: {
+            // Show "Preview" CodeLens results in a side panel
+            executeCommand: async (command, args, next) => {
+                const result = await next(command, args);
+                if (command === 'mdfx.preview' && typeof result === 'string') {
+                    showPreview(result);
+                }
+                return result;
+            }
         }
     };
 
@@ -58,6 +68,20 @@ function activate(context) {
     console.log('mdfx extension activated');
 }
 
+function showPreview(markdown) {
+    const panel = vscode.window.createWebviewPanel(
+        'mdfxPreview',
+        'mdfx Preview',
+        vscode.ViewColumn.Beside,
+        {}
+    );
+    vscode.commands
+        .executeCommand('markdown.api.render', markdown)
+        .then((html) => {
+            panel.webview.html = html;
+        });
+}
+
 function deactivate() {
     if (client) {
         return client.stop();